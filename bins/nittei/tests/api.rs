@@ -4,6 +4,8 @@ use chrono::DateTime;
 use helpers::setup::spawn_app;
 use nittei_domain::{PEMKey, Weekday};
 use nittei_sdk::{
+    APIError,
+    APIErrorVariant,
     AddServiceUserInput,
     CreateCalendarInput,
     CreateEventInput,
@@ -108,6 +110,7 @@ async fn test_crud_user() {
         limit: 100,
         skip: 0,
         metadata,
+        cursor: None,
     };
 
     let users_by_meta = admin_client
@@ -298,7 +301,7 @@ async fn test_crud_account() {
     let webhook_url = "https://example.com";
     admin_client
         .account
-        .create_webhook(webhook_url)
+        .create_webhook(webhook_url, None)
         .await
         .expect("Expected to create webhook");
     let account = admin_client.account.get().await.unwrap();
@@ -425,6 +428,7 @@ async fn test_crud_calendars() {
             name: None,
             week_start: Some(week_start),
             metadata: None,
+            expected_version: None,
         })
         .await
         .unwrap()
@@ -501,10 +505,13 @@ async fn test_crud_events() {
             reminders: Vec::new(),
             busy: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: DateTime::from_timestamp_millis(0).unwrap(),
             metadata: None,
         })
@@ -548,12 +555,18 @@ async fn test_crud_events() {
                 duration: None,
                 reminders: None,
                 recurrence: None,
+                timezone: None,
                 exdates: Some(vec![DateTime::from_timestamp_millis(0).unwrap()]),
                 recurring_event_id: None,
                 original_start_time: None,
                 service_id: None,
+                group_id: None,
+                attendees: None,
                 start_time: None,
                 metadata: None,
+                scope: None,
+                occurrence_start_time: None,
+                expected_version: None,
             })
             .await
             .is_ok()
@@ -581,6 +594,183 @@ async fn test_crud_events() {
     assert!(admin_client.event.get(event.id.clone()).await.is_err())
 }
 
+#[tokio::test]
+async fn test_update_with_expected_version() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+    let calendar = admin_client
+        .calendar
+        .get(calendar.id.clone())
+        .await
+        .unwrap()
+        .calendar;
+
+    let update_calendar = |name: &str, expected_version| UpdateCalendarInput {
+        calendar_id: calendar.id.clone(),
+        timezone: None,
+        name: Some(name.into()),
+        week_start: None,
+        metadata: None,
+        expected_version,
+    };
+    let updated_calendar = admin_client
+        .calendar
+        .update(update_calendar("First", Some(calendar.version)))
+        .await
+        .unwrap()
+        .calendar;
+    assert_eq!(updated_calendar.version, calendar.version + 1);
+
+    // The calendar has been modified since it was fetched
+    let res = admin_client
+        .calendar
+        .update(update_calendar("Second", Some(calendar.version)))
+        .await;
+    assert!(matches!(
+        res,
+        Err(APIError {
+            variant: APIErrorVariant::PreconditionFailed,
+            ..
+        })
+    ));
+    let fetched_calendar = admin_client
+        .calendar
+        .get(calendar.id.clone())
+        .await
+        .unwrap()
+        .calendar;
+    assert_eq!(fetched_calendar.name, Some("First".into()));
+    assert_eq!(fetched_calendar.version, updated_calendar.version);
+
+    let event = admin_client
+        .event
+        .create(CreateEventInput {
+            external_parent_id: None,
+            external_id: None,
+            title: None,
+            description: None,
+            event_type: None,
+            location: None,
+            status: nittei_domain::CalendarEventStatus::Tentative,
+            all_day: None,
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            duration: 1000 * 60 * 60,
+            reminders: Vec::new(),
+            busy: None,
+            recurrence: None,
+            timezone: None,
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            service_id: None,
+            group_id: None,
+            attendees: vec![],
+            start_time: DateTime::from_timestamp_millis(0).unwrap(),
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .event;
+
+    let update_event = |title: &str, expected_version| UpdateEventInput {
+        event_id: event.id.clone(),
+        title: Some(Some(title.into())),
+        description: None,
+        event_type: None,
+        location: None,
+        status: None,
+        all_day: None,
+        external_parent_id: None,
+        external_id: None,
+        busy: None,
+        duration: None,
+        reminders: None,
+        recurrence: None,
+        timezone: None,
+        exdates: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        service_id: None,
+        group_id: None,
+        attendees: None,
+        start_time: None,
+        metadata: None,
+        scope: None,
+        occurrence_start_time: None,
+        expected_version,
+    };
+
+    // Two clients fetch the same version of the event
+    let fetched_event = admin_client
+        .event
+        .get(event.id.clone())
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(fetched_event.version, event.version);
+
+    let updated_event = admin_client
+        .event
+        .update(update_event("First", Some(fetched_event.version)))
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(updated_event.version, fetched_event.version + 1);
+
+    // The second one doesn't override the changes of the first one
+    let res = admin_client
+        .event
+        .update(update_event("Second", Some(fetched_event.version)))
+        .await;
+    assert!(matches!(
+        res,
+        Err(APIError {
+            variant: APIErrorVariant::PreconditionFailed,
+            ..
+        })
+    ));
+
+    // Without an expected version, the event is always updated
+    let updated_event = admin_client
+        .event
+        .update(update_event("Third", None))
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(updated_event.title, Some("Third".into()));
+    assert_eq!(updated_event.version, fetched_event.version + 2);
+}
+
 #[tokio::test]
 async fn test_crud_service() {
     let (app, sdk, address) = spawn_app().await;
@@ -602,6 +792,9 @@ async fn test_crud_service() {
         .user;
 
     let create_service_input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: None,
         metadata: None,
         multi_person: None,
     };
@@ -622,6 +815,10 @@ async fn test_crud_service() {
             buffer_before: None,
             closest_booking_time: None,
             furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
         })
         .await;
     assert!(add_user_res.is_ok());
@@ -644,6 +841,10 @@ async fn test_crud_service() {
             buffer_before: None,
             closest_booking_time: Some(new_closest_booking_time),
             furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
         })
         .await
         .unwrap();
@@ -784,10 +985,13 @@ async fn test_freebusy_multiple() {
             reminders: Vec::new(),
             busy: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: DateTime::from_timestamp_millis(0).unwrap(),
             metadata: None,
         })
@@ -811,10 +1015,13 @@ async fn test_freebusy_multiple() {
             reminders: Vec::new(),
             busy: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: DateTime::from_timestamp_millis(1000 * 60 * 60).unwrap(),
             metadata: None,
         })
@@ -0,0 +1,246 @@
+mod helpers;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use helpers::setup::spawn_app;
+use nittei_domain::{CalendarEventStatus, Weekday};
+use nittei_sdk::{
+    CalendarEventAttendee,
+    CalendarEventAttendeeResponseStatus,
+    CreateCalendarInput,
+    CreateEventInput,
+    CreateUserInput,
+    GetUserFreeBusyInput,
+    NitteiSDK,
+    UpdateEventInput,
+};
+
+#[tokio::test]
+async fn test_event_attendees_and_freebusy() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address.clone(), res.secret_api_key);
+
+    let organizer = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let attendee = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: organizer.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+
+    let start_time = DateTime::<Utc>::from_timestamp_millis(1000 * 60 * 60 * 24).unwrap();
+    let event = admin_client
+        .event
+        .create(CreateEventInput {
+            user_id: organizer.id.clone(),
+            calendar_id: calendar.id.clone(),
+            title: None,
+            description: None,
+            event_type: None,
+            external_parent_id: None,
+            external_id: None,
+            location: None,
+            status: CalendarEventStatus::Confirmed,
+            all_day: None,
+            start_time,
+            duration: 1000 * 60 * 60,
+            busy: Some(true),
+            recurrence: None,
+            timezone: None,
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            reminders: Vec::new(),
+            attendees: vec![
+                CalendarEventAttendee {
+                    user_id: Some(attendee.id.clone()),
+                    ..Default::default()
+                },
+                CalendarEventAttendee {
+                    email: Some("John@Example.com".into()),
+                    ..Default::default()
+                },
+            ],
+            service_id: None,
+            group_id: None,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(event.attendees.len(), 2);
+    assert_eq!(event.attendees[1].email, Some("john@example.com".into()));
+
+    let event = admin_client
+        .event
+        .get(event.id.clone())
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(event.attendees.len(), 2);
+    assert_eq!(event.attendees[0].user_id, Some(attendee.id.clone()));
+
+    // The event makes the internal attendee busy
+    let free_busy = |user_id| GetUserFreeBusyInput {
+        user_id,
+        start_time,
+        end_time: start_time + TimeDelta::hours(2),
+        calendar_ids: None,
+    };
+    let busy = admin_client
+        .user
+        .free_busy(free_busy(attendee.id.clone()))
+        .await
+        .unwrap()
+        .busy;
+    assert_eq!(busy.len(), 1);
+
+    // Until the attendee declines
+    let declined_attendees = vec![
+        CalendarEventAttendee {
+            user_id: Some(attendee.id.clone()),
+            response_status: CalendarEventAttendeeResponseStatus::Declined,
+            ..Default::default()
+        },
+        CalendarEventAttendee {
+            email: Some("john@example.com".into()),
+            ..Default::default()
+        },
+    ];
+    let update_attendees = |attendees| UpdateEventInput {
+        event_id: event.id.clone(),
+        title: None,
+        description: None,
+        event_type: None,
+        external_parent_id: None,
+        external_id: None,
+        location: None,
+        status: None,
+        all_day: None,
+        start_time: None,
+        duration: None,
+        busy: None,
+        reminders: None,
+        attendees,
+        recurrence: None,
+        timezone: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        service_id: None,
+        group_id: None,
+        exdates: None,
+        metadata: None,
+        scope: None,
+        occurrence_start_time: None,
+        expected_version: None,
+    };
+    let updated = admin_client
+        .event
+        .update(update_attendees(Some(declined_attendees)))
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(
+        updated.attendees[0].response_status,
+        CalendarEventAttendeeResponseStatus::Declined
+    );
+    let busy = admin_client
+        .user
+        .free_busy(free_busy(attendee.id.clone()))
+        .await
+        .unwrap()
+        .busy;
+    assert!(busy.is_empty());
+
+    // Updating other fields keeps the attendees
+    let updated = admin_client
+        .event
+        .update(update_attendees(None))
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(updated.attendees.len(), 2);
+
+    // Providing the attendees without a response keeps the previous responses
+    let updated = admin_client
+        .event
+        .update(update_attendees(Some(vec![CalendarEventAttendee {
+            user_id: Some(attendee.id.clone()),
+            ..Default::default()
+        }])))
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(updated.attendees.len(), 1);
+    assert_eq!(
+        updated.attendees[0].response_status,
+        CalendarEventAttendeeResponseStatus::Declined
+    );
+
+    // An attendee can't be both an internal user and an email
+    let invalid = admin_client
+        .event
+        .update(update_attendees(Some(vec![CalendarEventAttendee {
+            user_id: Some(attendee.id.clone()),
+            email: Some("john@example.com".into()),
+            ..Default::default()
+        }])))
+        .await;
+    assert!(invalid.is_err());
+
+    // Internal attendees need to belong to the same account
+    let other_account = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let other_admin_client = NitteiSDK::new(address, other_account.secret_api_key);
+    let other_user = other_admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let invalid = admin_client
+        .event
+        .update(update_attendees(Some(vec![CalendarEventAttendee {
+            user_id: Some(other_user.id.clone()),
+            ..Default::default()
+        }])))
+        .await;
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,433 @@
+mod helpers;
+
+use chrono::{TimeDelta, Utc};
+use helpers::setup::spawn_app;
+use nittei_domain::{CalendarEventStatus, Weekday};
+use nittei_sdk::{
+    CreateCalendarInput,
+    CreateEventGroupInput,
+    CreateEventInput,
+    CreateUserInput,
+    NitteiSDK,
+    ShiftEventGroupEventsInput,
+    UpdateEventGroupInput,
+    UpdateEventInput,
+};
+
+#[tokio::test]
+async fn test_event_group_crud_and_cascades() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+
+    let group = admin_client
+        .event_group
+        .create(CreateEventGroupInput {
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            parent_id: Some("project_1".to_string()),
+            external_id: Some("order_1".to_string()),
+        })
+        .await
+        .unwrap()
+        .event_group;
+    assert_eq!(group.calendar_id, calendar.id);
+    assert_eq!(group.user_id, user.id);
+
+    // Using the same external id twice in an account is not allowed
+    let duplicated = admin_client
+        .event_group
+        .create(CreateEventGroupInput {
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            parent_id: None,
+            external_id: Some("order_1".to_string()),
+        })
+        .await;
+    assert!(duplicated.is_err());
+
+    let by_external_id = admin_client
+        .event_group
+        .get_by_external_id("order_1".to_string())
+        .await
+        .unwrap()
+        .event_group;
+    assert_eq!(by_external_id.id, group.id);
+
+    let by_parent_id = admin_client
+        .event_group
+        .get_by_parent_id("project_1".to_string())
+        .await
+        .unwrap()
+        .event_groups;
+    assert_eq!(by_parent_id.len(), 1);
+    assert_eq!(by_parent_id[0].id, group.id);
+
+    // Attach two events to the group
+    let start_time = Utc::now() + TimeDelta::days(1);
+    let mut event_ids = Vec::new();
+    for offset in 0..2 {
+        let event = admin_client
+            .event
+            .create(CreateEventInput {
+                external_parent_id: None,
+                external_id: None,
+                title: None,
+                description: None,
+                event_type: None,
+                location: None,
+                status: CalendarEventStatus::Confirmed,
+                all_day: None,
+                user_id: user.id.clone(),
+                calendar_id: calendar.id.clone(),
+                duration: 1000 * 60 * 60,
+                reminders: Vec::new(),
+                busy: None,
+                recurrence: None,
+                timezone: None,
+                exdates: None,
+                recurring_event_id: None,
+                original_start_time: None,
+                service_id: None,
+                group_id: Some(group.id.clone()),
+                attendees: vec![],
+                start_time: start_time + TimeDelta::hours(offset * 2),
+                metadata: None,
+            })
+            .await
+            .unwrap()
+            .event;
+        assert_eq!(event.group_id, Some(group.id.clone()));
+        event_ids.push(event.id);
+    }
+
+    let events = admin_client
+        .event_group
+        .get_events(group.id.clone())
+        .await
+        .unwrap()
+        .events;
+    assert_eq!(events.len(), 2);
+
+    // Shift all the events by 90 minutes
+    let shifted = admin_client
+        .event_group
+        .shift_events(ShiftEventGroupEventsInput {
+            group_id: group.id.clone(),
+            minutes: 90,
+        })
+        .await
+        .unwrap()
+        .events;
+    assert_eq!(shifted.len(), 2);
+    for event in &shifted {
+        let original = events.iter().find(|e| e.id == event.id).unwrap();
+        assert_eq!(
+            event.start_time,
+            original.start_time + TimeDelta::minutes(90)
+        );
+        assert_eq!(event.end_time, original.end_time + TimeDelta::minutes(90));
+    }
+    let event = admin_client
+        .event
+        .get(event_ids[0].clone())
+        .await
+        .unwrap()
+        .event;
+    let original = events.iter().find(|e| e.id == event.id).unwrap();
+    assert_eq!(
+        event.start_time,
+        original.start_time + TimeDelta::minutes(90)
+    );
+
+    // Cancel all the events
+    let cancelled = admin_client
+        .event_group
+        .cancel_events(group.id.clone())
+        .await
+        .unwrap()
+        .events;
+    assert!(
+        cancelled
+            .iter()
+            .all(|e| e.status == CalendarEventStatus::Cancelled)
+    );
+    let event = admin_client
+        .event
+        .get(event_ids[1].clone())
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(event.status, CalendarEventStatus::Cancelled);
+
+    // Update the group
+    let updated = admin_client
+        .event_group
+        .update(UpdateEventGroupInput {
+            group_id: group.id.clone(),
+            parent_id: Some(None),
+            external_id: None,
+        })
+        .await
+        .unwrap()
+        .event_group;
+    assert_eq!(updated.parent_id, None);
+    assert_eq!(updated.external_id, Some("order_1".to_string()));
+
+    // Delete all the events, the group is kept
+    let deleted = admin_client
+        .event_group
+        .delete_events(group.id.clone())
+        .await
+        .unwrap()
+        .events;
+    assert_eq!(deleted.len(), 2);
+    assert!(admin_client.event.get(event_ids[0].clone()).await.is_err());
+    assert!(admin_client.event_group.get(group.id.clone()).await.is_ok());
+
+    // Delete the group
+    admin_client
+        .event_group
+        .delete(group.id.clone())
+        .await
+        .unwrap();
+    assert!(
+        admin_client
+            .event_group
+            .get(group.id.clone())
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_event_group_is_scoped_to_account() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let admin_client = NitteiSDK::new(address.clone(), res.secret_api_key);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+    let group = admin_client
+        .event_group
+        .create(CreateEventGroupInput {
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            parent_id: None,
+            external_id: None,
+        })
+        .await
+        .unwrap()
+        .event_group;
+
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let other_admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    assert!(
+        other_admin_client
+            .event_group
+            .get(group.id.clone())
+            .await
+            .is_err()
+    );
+    assert!(
+        other_admin_client
+            .event_group
+            .cancel_events(group.id.clone())
+            .await
+            .is_err()
+    );
+    assert!(
+        other_admin_client
+            .event_group
+            .delete(group.id)
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_event_group_is_scoped_to_calendar() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let mut calendars = Vec::new();
+    for _ in 0..2 {
+        let calendar = admin_client
+            .calendar
+            .create(CreateCalendarInput {
+                user_id: user.id.clone(),
+                timezone: chrono_tz::UTC,
+                name: None,
+                key: None,
+                week_start: Weekday::Mon,
+                metadata: None,
+            })
+            .await
+            .unwrap()
+            .calendar;
+        calendars.push(calendar);
+    }
+    let group = admin_client
+        .event_group
+        .create(CreateEventGroupInput {
+            user_id: user.id.clone(),
+            calendar_id: calendars[0].id.clone(),
+            parent_id: None,
+            external_id: None,
+        })
+        .await
+        .unwrap()
+        .event_group;
+
+    let create_event = |group_id| CreateEventInput {
+        external_parent_id: None,
+        external_id: None,
+        title: None,
+        description: None,
+        event_type: None,
+        location: None,
+        status: CalendarEventStatus::Confirmed,
+        all_day: None,
+        user_id: user.id.clone(),
+        calendar_id: calendars[1].id.clone(),
+        duration: 1000 * 60 * 60,
+        reminders: Vec::new(),
+        busy: None,
+        recurrence: None,
+        timezone: None,
+        exdates: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        service_id: None,
+        group_id,
+        attendees: vec![],
+        start_time: Utc::now() + TimeDelta::days(1),
+        metadata: None,
+    };
+
+    // An event can't be attached to a group of another calendar of its user
+    assert!(
+        admin_client
+            .event
+            .create(create_event(Some(group.id.clone())))
+            .await
+            .is_err()
+    );
+
+    let event = admin_client
+        .event
+        .create(create_event(None))
+        .await
+        .unwrap()
+        .event;
+    assert!(
+        admin_client
+            .event
+            .update(UpdateEventInput {
+                event_id: event.id.clone(),
+                title: None,
+                description: None,
+                event_type: None,
+                location: None,
+                status: None,
+                all_day: None,
+                external_parent_id: None,
+                external_id: None,
+                busy: None,
+                duration: None,
+                reminders: None,
+                recurrence: None,
+                timezone: None,
+                exdates: None,
+                recurring_event_id: None,
+                original_start_time: None,
+                service_id: None,
+                group_id: Some(Some(group.id.clone())),
+                attendees: None,
+                start_time: None,
+                metadata: None,
+                scope: None,
+                occurrence_start_time: None,
+                expected_version: None,
+            })
+            .await
+            .is_err()
+    );
+    let event = admin_client.event.get(event.id).await.unwrap().event;
+    assert_eq!(event.group_id, None);
+}
@@ -0,0 +1,280 @@
+mod helpers;
+
+use std::sync::{Arc, Mutex};
+
+use axum::{Router, body::Bytes, extract::State, http::HeaderMap, routing::post};
+use chrono::Utc;
+use helpers::{setup::spawn_app, utils::wait_for_empty_outbox};
+use nittei_domain::CalendarEventStatus;
+use nittei_sdk::{
+    APIErrorVariant,
+    CreateCalendarInput,
+    CreateEventInput,
+    CreateUserInput,
+    EventWebhookPayload,
+    NitteiSDK,
+    UpdateEventInput,
+    WEBHOOK_SIGNATURE_HEADER,
+    WebhookEventType,
+    WebhookSignatureError,
+    Weekday,
+    parse_webhook,
+    verify_webhook,
+};
+use tokio::net::TcpListener;
+
+/// Webhooks received by the test server (signature header and raw body)
+type ReceivedWebhooks = Arc<Mutex<Vec<(String, Bytes)>>>;
+
+/// Start a server receiving the webhooks on the listener
+fn spawn_webhook_receiver(listener: TcpListener, received: ReceivedWebhooks) {
+    let app = Router::new()
+        .route(
+            "/",
+            post(
+                |State(received): State<ReceivedWebhooks>,
+                 headers: HeaderMap,
+                 body: Bytes| async move {
+                    let signature = headers
+                        .get(WEBHOOK_SIGNATURE_HEADER)
+                        .and_then(|signature| signature.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string();
+                    if let Ok(mut received) = received.lock() {
+                        received.push((signature, body));
+                    }
+                },
+            ),
+        )
+        .with_state(received);
+
+    tokio::spawn(async move { axum::serve(listener, app).await });
+}
+
+#[tokio::test]
+async fn test_event_lifecycle_webhooks() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let received = ReceivedWebhooks::default();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let webhook_url = format!("http://{}/", listener.local_addr().unwrap());
+    spawn_webhook_receiver(listener, received.clone());
+
+    // Opt in to all the changes, except the updates
+    let webhook = admin_client
+        .account
+        .create_webhook(
+            &webhook_url,
+            Some(vec![
+                WebhookEventType::EventCreated,
+                WebhookEventType::EventCancelled,
+                WebhookEventType::EventDeleted,
+            ]),
+        )
+        .await
+        .unwrap()
+        .account
+        .settings
+        .webhook
+        .unwrap();
+    assert_eq!(webhook.event_types.len(), 3);
+
+    // Setting the url again keeps the types of changes
+    let webhook = admin_client
+        .account
+        .create_webhook(&webhook_url, None)
+        .await
+        .unwrap()
+        .account
+        .settings
+        .webhook
+        .unwrap();
+    assert_eq!(webhook.event_types.len(), 3);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+
+    let create_event_input = |title: Option<String>| CreateEventInput {
+        user_id: user.id.clone(),
+        calendar_id: calendar.id.clone(),
+        title,
+        description: None,
+        event_type: None,
+        external_parent_id: None,
+        external_id: None,
+        location: None,
+        status: CalendarEventStatus::Confirmed,
+        all_day: None,
+        start_time: Utc::now(),
+        duration: 1000 * 60 * 60,
+        busy: None,
+        recurrence: None,
+        timezone: None,
+        exdates: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        reminders: Vec::new(),
+        attendees: Vec::new(),
+        service_id: None,
+        group_id: None,
+        metadata: None,
+    };
+    let event = admin_client
+        .event
+        .create(create_event_input(Some("Meeting".into())))
+        .await
+        .unwrap()
+        .event;
+
+    let update_event = |title: Option<String>, status| UpdateEventInput {
+        event_id: event.id.clone(),
+        title: title.map(Some),
+        description: None,
+        event_type: None,
+        external_parent_id: None,
+        external_id: None,
+        location: None,
+        status,
+        all_day: None,
+        start_time: None,
+        duration: None,
+        busy: None,
+        reminders: None,
+        attendees: None,
+        recurrence: None,
+        timezone: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        service_id: None,
+        group_id: None,
+        exdates: None,
+        metadata: None,
+        scope: None,
+        occurrence_start_time: None,
+        expected_version: None,
+    };
+
+    // Not sent, as the account didn't opt in to the updates
+    admin_client
+        .event
+        .update(update_event(Some("Renamed meeting".into()), None))
+        .await
+        .unwrap();
+    admin_client
+        .event
+        .update(update_event(None, Some(CalendarEventStatus::Cancelled)))
+        .await
+        .unwrap();
+    admin_client.event.delete(event.id.clone()).await.unwrap();
+
+    wait_for_empty_outbox(&admin_client).await;
+
+    let webhooks = received.lock().unwrap().clone();
+    assert_eq!(webhooks.len(), 3);
+    // The webhooks are signed with the key, which isn't sent
+    let payloads = webhooks
+        .iter()
+        .map(|(signature, body)| {
+            parse_webhook::<EventWebhookPayload>(signature, body, &webhook.key).unwrap()
+        })
+        .collect::<Vec<_>>();
+    for payload in &payloads {
+        assert_eq!(payload.event.id, event.id);
+    }
+    let (signature, body) = &webhooks[0];
+    assert!(matches!(
+        verify_webhook(signature, body, "other key"),
+        Err(WebhookSignatureError::NoMatchingSignature)
+    ));
+    assert!(matches!(
+        verify_webhook(signature, b"{}", &webhook.key),
+        Err(WebhookSignatureError::NoMatchingSignature)
+    ));
+
+    // The webhooks can be delivered in any order
+    let find_payload = |event_type| {
+        payloads
+            .iter()
+            .find(|payload| payload.event_type == event_type)
+            .expect("Expected the webhook to be sent")
+    };
+    // The payload is a snapshot of the event at the time of the change
+    let created = find_payload(WebhookEventType::EventCreated);
+    assert_eq!(created.event.title, Some("Meeting".into()));
+    let cancelled = find_payload(WebhookEventType::EventCancelled);
+    assert_eq!(cancelled.event.title, Some("Renamed meeting".into()));
+    assert_eq!(cancelled.event.status, CalendarEventStatus::Cancelled);
+    let deleted = find_payload(WebhookEventType::EventDeleted);
+    assert_eq!(deleted.event.version, cancelled.event.version);
+
+    // After a rotation, the webhooks are signed with both keys during the grace period
+    let rotated_webhook = admin_client
+        .account
+        .rotate_webhook_key(None)
+        .await
+        .unwrap()
+        .account
+        .settings
+        .webhook
+        .unwrap();
+    assert_ne!(rotated_webhook.key, webhook.key);
+    assert!(rotated_webhook.previous_key_expires_at.is_some());
+
+    received.lock().unwrap().clear();
+    admin_client
+        .event
+        .create(create_event_input(None))
+        .await
+        .unwrap();
+    wait_for_empty_outbox(&admin_client).await;
+
+    let webhooks = received.lock().unwrap().clone();
+    assert_eq!(webhooks.len(), 1);
+    let (signature, body) = &webhooks[0];
+    assert!(verify_webhook(signature, body, &rotated_webhook.key).is_ok());
+    assert!(verify_webhook(signature, body, &webhook.key).is_ok());
+}
+
+#[tokio::test]
+async fn test_rotate_webhook_key_without_webhook() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let res = admin_client.account.rotate_webhook_key(Some(60)).await;
+    assert!(matches!(
+        res.map_err(|e| e.variant),
+        Err(APIErrorVariant::NotFound)
+    ));
+}
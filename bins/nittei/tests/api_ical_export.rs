@@ -1,9 +1,15 @@
 mod helpers;
 
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
 use helpers::setup::spawn_app;
 use nittei_domain::{CalendarEventStatus, RRuleFrequency, RRuleOptions, Weekday};
-use nittei_sdk::{CreateCalendarInput, CreateEventInput, CreateUserInput, NitteiSDK};
+use nittei_sdk::{
+    CreateCalendarInput,
+    CreateEventInput,
+    CreateUserInput,
+    GetEventsInstancesInput,
+    NitteiSDK,
+};
 
 #[tokio::test]
 async fn test_export_calendar_ical_user_endpoint() {
@@ -62,10 +68,13 @@ async fn test_export_calendar_ical_user_endpoint() {
             reminders: Vec::new(),
             busy: Some(true),
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: event_start,
             metadata: None,
         })
@@ -212,10 +221,13 @@ async fn test_export_calendar_ical_admin_endpoint() {
             reminders: Vec::new(),
             busy: Some(true),
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: event1_start,
             metadata: None,
         })
@@ -240,10 +252,13 @@ async fn test_export_calendar_ical_admin_endpoint() {
             reminders: Vec::new(),
             busy: Some(false),
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: event2_start,
             metadata: None,
         })
@@ -393,6 +408,9 @@ async fn test_export_calendar_ical_with_recurring_events() {
         bymonth: None,
         byyearday: None,
         byweekno: None,
+        byhour: None,
+        byminute: None,
+        rdates: None,
         weekstart: None,
     };
 
@@ -414,10 +432,13 @@ async fn test_export_calendar_ical_with_recurring_events() {
             reminders: Vec::new(),
             busy: Some(true),
             recurrence: Some(recurrence),
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: recurring_event_start,
             metadata: None,
         })
@@ -666,3 +687,170 @@ async fn test_export_calendar_ical_not_found() {
         .await;
     assert!(ical_result.is_err());
 }
+
+#[tokio::test]
+async fn test_export_calendar_ical_round_trip_across_dst() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+
+    let admin_client = NitteiSDK::new(address.clone(), res.secret_api_key.clone());
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let create_calendar_input = || CreateCalendarInput {
+        user_id: user.id.clone(),
+        timezone: chrono_tz::Europe::Oslo,
+        name: Some("Oslo Calendar".to_string()),
+        key: None,
+        week_start: Weekday::Mon,
+        metadata: None,
+    };
+    let calendar = admin_client
+        .calendar
+        .create(create_calendar_input())
+        .await
+        .unwrap()
+        .calendar;
+
+    // Weekly at 09:00 in Oslo, from winter time to summer time (March 31, 2030)
+    let local = |y, m, d, h| {
+        chrono_tz::Europe::Oslo
+            .with_ymd_and_hms(y, m, d, h, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let recurring_event = admin_client
+        .event
+        .create(CreateEventInput {
+            external_parent_id: None,
+            external_id: None,
+            title: Some("Weekly Meeting".to_string()),
+            description: Some(
+                "Weekly meeting of the team, with a description which is longer than a line of the iCalendar content"
+                    .to_string(),
+            ),
+            event_type: None,
+            location: None,
+            status: CalendarEventStatus::Confirmed,
+            all_day: Some(false),
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            duration: 1000 * 60 * 60,
+            reminders: Vec::new(),
+            busy: Some(true),
+            recurrence: Some(RRuleOptions {
+                freq: RRuleFrequency::Weekly,
+                count: Some(4),
+                ..Default::default()
+            }),
+            timezone: None,
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            service_id: None,
+            group_id: None,
+            attendees: vec![],
+            start_time: local(2030, 3, 19, 9),
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .event;
+
+    let ical_content = admin_client
+        .calendar
+        .export_ical(nittei_sdk::ExportCalendarIcalInput {
+            calendar_id: calendar.id.clone(),
+            start_time: Some(local(2030, 3, 1, 0)),
+            end_time: Some(local(2030, 5, 1, 0)),
+        })
+        .await
+        .expect("Expected to export calendar as iCal");
+
+    // The series is in local time, with the definition of the timezone
+    let actual_lines: Vec<&str> = ical_content.lines().collect();
+    assert!(actual_lines.contains(&"BEGIN:VTIMEZONE"));
+    assert!(actual_lines.contains(&"TZID:Europe/Oslo"));
+    assert!(actual_lines.contains(&"RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU"));
+    assert!(actual_lines.contains(&"DTSTART;TZID=Europe/Oslo:20300319T090000"));
+    assert!(actual_lines.contains(&"DTEND;TZID=Europe/Oslo:20300319T100000"));
+    assert!(actual_lines.contains(&"RRULE:FREQ=WEEKLY;COUNT=4"));
+
+    // Lines are folded at 75 octets
+    assert!(ical_content.split("\r\n").all(|line| line.len() <= 75));
+    assert!(actual_lines.iter().any(|line| line.starts_with(' ')));
+
+    // Imported back, the occurrences stay at 09:00 in Oslo
+    let other_calendar = admin_client
+        .calendar
+        .create(create_calendar_input())
+        .await
+        .unwrap()
+        .calendar;
+    let res = admin_client
+        .calendar
+        .import_ical(nittei_sdk::ImportCalendarIcalInput {
+            user_id: user.id.clone(),
+            calendar_id: other_calendar.id.clone(),
+            ical: ical_content,
+            dry_run: None,
+        })
+        .await
+        .unwrap();
+    assert!(res.skipped.is_empty());
+    assert_eq!(res.changes.len(), 1);
+    let imported_event = &res.changes[0].event;
+    assert_eq!(
+        imported_event.external_id,
+        Some(recurring_event.id.to_string())
+    );
+    assert_eq!(imported_event.title, recurring_event.title);
+    assert_eq!(imported_event.description, recurring_event.description);
+    assert_eq!(imported_event.start_time, recurring_event.start_time);
+    assert_eq!(imported_event.recurrence, recurring_event.recurrence);
+
+    let get_instance_start_times = |event_id: nittei_sdk::ID| {
+        let admin_client = &admin_client;
+        async move {
+            admin_client
+                .event
+                .get_instances(GetEventsInstancesInput {
+                    event_id,
+                    start_time: local(2030, 3, 1, 0),
+                    end_time: local(2030, 5, 1, 0),
+                })
+                .await
+                .unwrap()
+                .instances
+                .into_iter()
+                .map(|instance| instance.start_time)
+                .collect::<Vec<_>>()
+        }
+    };
+    let expected_start_times = vec![
+        local(2030, 3, 19, 9),
+        local(2030, 3, 26, 9),
+        local(2030, 4, 2, 9),
+        local(2030, 4, 9, 9),
+    ];
+    assert_eq!(
+        get_instance_start_times(recurring_event.id.clone()).await,
+        expected_start_times
+    );
+    assert_eq!(
+        get_instance_start_times(imported_event.id.clone()).await,
+        expected_start_times
+    );
+}
@@ -0,0 +1,257 @@
+mod helpers;
+
+use chrono::{Duration, DurationRound, Utc};
+use helpers::setup::spawn_app;
+use nittei_domain::{CalendarEventStatus, RRuleFrequency, RRuleOptions};
+use nittei_sdk::{
+    APIErrorVariant,
+    CreateCalendarInput,
+    CreateEventInput,
+    CreateUserInput,
+    NitteiSDK,
+    Weekday,
+};
+use reqwest::{StatusCode, header};
+
+#[tokio::test]
+async fn test_calendar_ical_feeds() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address.clone(), res.secret_api_key);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+    let create_event_input = |title: &str| CreateEventInput {
+        user_id: user.id.clone(),
+        calendar_id: calendar.id.clone(),
+        title: Some(title.into()),
+        description: Some("Private notes".into()),
+        event_type: None,
+        external_parent_id: None,
+        external_id: None,
+        location: None,
+        status: CalendarEventStatus::Confirmed,
+        all_day: None,
+        start_time: Utc::now().duration_trunc(Duration::hours(1)).unwrap() + Duration::days(1),
+        duration: 1000 * 60 * 60,
+        busy: None,
+        recurrence: None,
+        timezone: None,
+        exdates: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        reminders: Vec::new(),
+        attendees: Vec::new(),
+        service_id: None,
+        group_id: None,
+        metadata: None,
+    };
+
+    let event = admin_client
+        .event
+        .create(create_event_input("Dentist"))
+        .await
+        .unwrap()
+        .event;
+    // A daily event started long before the horizon of the feed, and still ongoing
+    let recurring_event = admin_client
+        .event
+        .create(CreateEventInput {
+            start_time: Utc::now().duration_trunc(Duration::hours(1)).unwrap()
+                - Duration::days(400),
+            recurrence: Some(RRuleOptions {
+                freq: RRuleFrequency::Daily,
+                ..Default::default()
+            }),
+            ..create_event_input("Standup")
+        })
+        .await
+        .unwrap()
+        .event;
+    // An event outside of the horizon of the feed
+    let old_event = admin_client
+        .event
+        .create(CreateEventInput {
+            start_time: Utc::now() - Duration::days(400),
+            ..create_event_input("Old event")
+        })
+        .await
+        .unwrap()
+        .event;
+
+    let feed = admin_client
+        .calendar
+        .create_feed(calendar.id.clone(), None)
+        .await
+        .unwrap()
+        .feed;
+    assert_eq!(feed.calendar_id, calendar.id);
+    assert!(!feed.mask_events);
+    assert_eq!(feed.path, format!("/api/v1/ical/{}.ics", feed.token));
+    let masked_feed = admin_client
+        .calendar
+        .create_feed(calendar.id.clone(), Some(true))
+        .await
+        .unwrap()
+        .feed;
+    assert_ne!(masked_feed.token, feed.token);
+
+    let feeds = admin_client
+        .calendar
+        .get_feeds(calendar.id.clone())
+        .await
+        .unwrap()
+        .feeds;
+    assert_eq!(feeds.len(), 2);
+
+    // The feeds are public, no API key is needed
+    let public_client = &sdk;
+    let ical = public_client
+        .calendar
+        .get_feed_ical(&feed.token)
+        .await
+        .unwrap();
+    assert!(ical.starts_with("BEGIN:VCALENDAR"));
+    assert!(ical.contains(&format!("UID:{}", event.id)));
+    assert!(ical.contains("SUMMARY:Dentist"));
+    assert!(ical.contains("DESCRIPTION:Private notes"));
+    assert!(ical.contains(&format!("UID:{}", recurring_event.id)));
+    assert!(!ical.contains(&format!("UID:{}", old_event.id)));
+
+    // The masked feed only shows the user is busy
+    let masked_ical = public_client
+        .calendar
+        .get_feed_ical(&masked_feed.token)
+        .await
+        .unwrap();
+    assert!(masked_ical.contains(&format!("UID:{}", event.id)));
+    assert!(!masked_ical.contains("Dentist"));
+    assert!(!masked_ical.contains("Private notes"));
+    assert!(masked_ical.contains("SUMMARY:Busy"));
+
+    // Unchanged content isn't downloaded again
+    let feed_url = format!("{}{}", address, feed.path);
+    let client = reqwest::Client::new();
+    let res = client.get(&feed_url).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(
+        res.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/calendar")
+    );
+    let etag = res.headers()[header::ETAG].clone();
+    let res = client
+        .get(&feed_url)
+        .header(header::IF_NONE_MATCH, etag.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(res.headers()[header::ETAG], etag);
+
+    // A change in the calendar changes the content
+    admin_client
+        .event
+        .create(create_event_input("Haircut"))
+        .await
+        .unwrap();
+    let res = client
+        .get(&feed_url)
+        .header(header::IF_NONE_MATCH, etag.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_ne!(res.headers()[header::ETAG], etag);
+    assert!(res.text().await.unwrap().contains("SUMMARY:Haircut"));
+
+    // Unknown tokens, and URLs without the extension, aren't found
+    let res = public_client.calendar.get_feed_ical("cf_unknown").await;
+    assert!(matches!(
+        res.map_err(|e| e.variant),
+        Err(APIErrorVariant::NotFound)
+    ));
+    let res = client
+        .get(format!("{}/api/v1/ical/{}", address, feed.token))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    // Another account can't see or revoke the feeds
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let other_admin_client = NitteiSDK::new(address.clone(), res.secret_api_key);
+    assert!(
+        other_admin_client
+            .calendar
+            .get_feeds(calendar.id.clone())
+            .await
+            .is_err()
+    );
+    assert!(
+        other_admin_client
+            .calendar
+            .delete_feed(calendar.id.clone(), feed.id.clone())
+            .await
+            .is_err()
+    );
+
+    // Revoked feeds aren't accessible anymore
+    let deleted = admin_client
+        .calendar
+        .delete_feed(calendar.id.clone(), feed.id.clone())
+        .await
+        .unwrap()
+        .feed;
+    assert_eq!(deleted.id, feed.id);
+    let res = public_client.calendar.get_feed_ical(&feed.token).await;
+    assert!(matches!(
+        res.map_err(|e| e.variant),
+        Err(APIErrorVariant::NotFound)
+    ));
+    assert!(
+        public_client
+            .calendar
+            .get_feed_ical(&masked_feed.token)
+            .await
+            .is_ok()
+    );
+    let feeds = admin_client
+        .calendar
+        .get_feeds(calendar.id.clone())
+        .await
+        .unwrap()
+        .feeds;
+    assert_eq!(feeds.len(), 1);
+    assert_eq!(feeds[0].id, masked_feed.id);
+}
@@ -0,0 +1,307 @@
+mod helpers;
+
+use chrono::{DateTime, Utc};
+use helpers::setup::spawn_app;
+use nittei_domain::CalendarEventStatus;
+use nittei_sdk::{
+    APIErrorVariant,
+    CreateCalendarInput,
+    CreateUserInput,
+    GetCalendarEventsInput,
+    GetEventsInstancesInput,
+    IcalImportAction,
+    ImportCalendarIcalInput,
+    NitteiSDK,
+    Weekday,
+};
+
+/// Weekly meeting (without its 2nd occurrence, and with its 3rd one moved),
+/// a single event, an all-day event, an event with additional dates and an event which can't be imported
+const ICAL: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//Example//Example//EN\r
+BEGIN:VEVENT\r
+UID:weekly@example.com\r
+SUMMARY:Weekly meeting\r
+DTSTART;TZID=Europe/Oslo:20300107T090000\r
+DTEND;TZID=Europe/Oslo:20300107T100000\r
+RRULE:FREQ=WEEKLY;COUNT=4\r
+EXDATE;TZID=Europe/Oslo:20300114T090000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:weekly@example.com\r
+RECURRENCE-ID;TZID=Europe/Oslo:20300121T090000\r
+SUMMARY:Weekly meeting (moved)\r
+DTSTART;TZID=Europe/Oslo:20300121T140000\r
+DTEND;TZID=Europe/Oslo:20300121T150000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:single@example.com\r
+SUMMARY:Lunch\r
+LOCATION:Cafe\\, downtown\r
+DTSTART:20300108T110000Z\r
+DURATION:PT1H\r
+STATUS:TENTATIVE\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:holiday@example.com\r
+SUMMARY:Holiday\r
+DTSTART;VALUE=DATE:20300110\r
+DTEND;VALUE=DATE:20300111\r
+TRANSP:TRANSPARENT\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:rdate@example.com\r
+DTSTART:20300108T110000Z\r
+RDATE:20300109T110000Z\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+UID:period@example.com\r
+DTSTART:20300108T110000Z\r
+RDATE;VALUE=PERIOD:20300109T110000Z/PT1H\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+#[tokio::test]
+async fn test_import_calendar_ical() {
+    let utc = |datetime: &str| DateTime::parse_from_rfc3339(datetime).unwrap().to_utc();
+
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::Europe::Oslo,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+    let import_input = |ical: &str, dry_run: bool| ImportCalendarIcalInput {
+        user_id: user.id.clone(),
+        calendar_id: calendar.id.clone(),
+        ical: ical.to_string(),
+        dry_run: Some(dry_run),
+    };
+    let get_events_input = || GetCalendarEventsInput {
+        calendar_id: calendar.id.clone(),
+        start_time: utc("2030-01-01T00:00:00Z"),
+        end_time: utc("2030-02-01T00:00:00Z"),
+    };
+
+    // Dry run: nothing is written
+    let res = admin_client
+        .calendar
+        .import_ical(import_input(ICAL, true))
+        .await
+        .unwrap();
+    assert!(res.dry_run);
+    assert_eq!(res.changes.len(), 5);
+    assert!(
+        res.changes
+            .iter()
+            .all(|change| change.action == IcalImportAction::Created)
+    );
+    assert_eq!(res.skipped.len(), 1);
+    assert_eq!(res.skipped[0].uid.as_deref(), Some("period@example.com"));
+    assert_eq!(res.skipped[0].reason, "RDATE periods are not supported");
+    let events = admin_client
+        .calendar
+        .get_events(get_events_input())
+        .await
+        .unwrap()
+        .events;
+    assert!(events.is_empty());
+
+    // Import
+    let res = admin_client
+        .calendar
+        .import_ical(import_input(ICAL, false))
+        .await
+        .unwrap();
+    assert!(!res.dry_run);
+    assert_eq!(res.changes.len(), 5);
+    let change = |uid: &str, recurrence_id: Option<DateTime<Utc>>| {
+        res.changes
+            .iter()
+            .find(|change| change.uid == uid && change.recurrence_id == recurrence_id)
+            .map(|change| change.event.clone())
+    };
+    let weekly = change("weekly@example.com", None).unwrap();
+    assert_eq!(weekly.external_id.as_deref(), Some("weekly@example.com"));
+    assert_eq!(weekly.start_time, utc("2030-01-07T08:00:00Z"));
+    assert_eq!(weekly.exdates, vec![utc("2030-01-14T08:00:00Z")]);
+    let moved = change("weekly@example.com", Some(utc("2030-01-21T08:00:00Z"))).unwrap();
+    assert_eq!(moved.recurring_event_id, Some(weekly.id.clone()));
+    assert_eq!(moved.start_time, utc("2030-01-21T13:00:00Z"));
+    let single = change("single@example.com", None).unwrap();
+    assert_eq!(single.location.as_deref(), Some("Cafe, downtown"));
+    assert_eq!(single.status, CalendarEventStatus::Tentative);
+    assert_eq!(single.duration, 1000 * 60 * 60);
+    let holiday = change("holiday@example.com", None).unwrap();
+    assert!(holiday.all_day);
+    assert!(!holiday.busy);
+    let rdate = change("rdate@example.com", None).unwrap();
+    assert_eq!(
+        rdate
+            .recurrence
+            .clone()
+            .and_then(|recurrence| recurrence.rdates),
+        Some(vec![utc("2030-01-09T11:00:00Z")])
+    );
+
+    let instances = admin_client
+        .event
+        .get_instances(GetEventsInstancesInput {
+            event_id: weekly.id.clone(),
+            start_time: utc("2030-01-01T00:00:00Z"),
+            end_time: utc("2030-02-01T00:00:00Z"),
+        })
+        .await
+        .unwrap()
+        .instances;
+    let instance_start_times = instances.iter().map(|i| i.start_time).collect::<Vec<_>>();
+    assert!(instance_start_times.contains(&utc("2030-01-07T08:00:00Z")));
+    assert!(!instance_start_times.contains(&utc("2030-01-14T08:00:00Z")));
+    assert!(instance_start_times.contains(&utc("2030-01-28T08:00:00Z")));
+
+    // The event with additional dates occurs at its start and at these dates
+    let instances = admin_client
+        .event
+        .get_instances(GetEventsInstancesInput {
+            event_id: rdate.id.clone(),
+            start_time: utc("2030-01-01T00:00:00Z"),
+            end_time: utc("2030-02-01T00:00:00Z"),
+        })
+        .await
+        .unwrap()
+        .instances;
+    assert_eq!(
+        instances.iter().map(|i| i.start_time).collect::<Vec<_>>(),
+        vec![utc("2030-01-08T11:00:00Z"), utc("2030-01-09T11:00:00Z")]
+    );
+
+    // Importing the same content again changes nothing
+    let res = admin_client
+        .calendar
+        .import_ical(import_input(ICAL, false))
+        .await
+        .unwrap();
+    assert!(
+        res.changes
+            .iter()
+            .all(|change| change.action == IcalImportAction::Unchanged)
+    );
+    let events = admin_client
+        .calendar
+        .get_events(get_events_input())
+        .await
+        .unwrap()
+        .events;
+    assert_eq!(events.len(), 5);
+
+    // The changed events are updated (and the diff is returned by a dry run)
+    let updated_ical = ICAL.replace("SUMMARY:Lunch", "SUMMARY:Team lunch");
+    let res = admin_client
+        .calendar
+        .import_ical(import_input(&updated_ical, true))
+        .await
+        .unwrap();
+    let change = res
+        .changes
+        .iter()
+        .find(|change| change.uid == "single@example.com")
+        .unwrap();
+    assert_eq!(change.action, IcalImportAction::Updated);
+    assert_eq!(change.changed_fields, vec!["title".to_string()]);
+    assert_eq!(
+        admin_client
+            .event
+            .get(single.id.clone())
+            .await
+            .unwrap()
+            .event
+            .title
+            .as_deref(),
+        Some("Lunch")
+    );
+
+    let res = admin_client
+        .calendar
+        .import_ical(import_input(&updated_ical, false))
+        .await
+        .unwrap();
+    let change = res
+        .changes
+        .iter()
+        .find(|change| change.uid == "single@example.com")
+        .unwrap();
+    assert_eq!(change.action, IcalImportAction::Updated);
+    assert_eq!(change.event.id, single.id);
+    let event = admin_client
+        .event
+        .get(single.id.clone())
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(event.title.as_deref(), Some("Team lunch"));
+    assert_eq!(event.version, single.version + 1);
+    assert_eq!(
+        res.changes
+            .iter()
+            .filter(|change| change.action == IcalImportAction::Unchanged)
+            .count(),
+        4
+    );
+
+    // Invalid content
+    let res = admin_client
+        .calendar
+        .import_ical(import_input("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n", false))
+        .await;
+    assert!(res.is_err_and(|e| e.message.contains("Invalid iCalendar content")));
+
+    // The calendar must belong to the user
+    let other_user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let res = admin_client
+        .calendar
+        .import_ical(ImportCalendarIcalInput {
+            user_id: other_user.id.clone(),
+            ..import_input(ICAL, false)
+        })
+        .await;
+    assert!(matches!(
+        res.map_err(|e| e.variant),
+        Err(APIErrorVariant::NotFound)
+    ));
+}
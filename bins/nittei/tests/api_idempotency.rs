@@ -0,0 +1,88 @@
+mod helpers;
+
+use helpers::setup::spawn_app;
+use reqwest::StatusCode;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_idempotency_keys() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let api_key = res.secret_api_key;
+
+    let client = reqwest::Client::new();
+    let create_user = |api_key: &str, key: Option<&str>, body: Value| {
+        let mut request = client
+            .post(format!("{address}/api/v1/user"))
+            .header("x-api-key", api_key)
+            .json(&body);
+        if let Some(key) = key {
+            request = request.header("idempotency-key", key);
+        }
+        request.send()
+    };
+
+    let res = create_user(&api_key, Some("create_user_1"), json!({}))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(res.headers().get("idempotent-replayed").is_none());
+    let user: Value = res.json().await.unwrap();
+
+    // The retries get the same response, without creating another user
+    let res = create_user(&api_key, Some("create_user_1"), json!({}))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.headers()["idempotent-replayed"], "true");
+    assert!(
+        res.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("application/json")
+    );
+    let replayed_user: Value = res.json().await.unwrap();
+    assert_eq!(replayed_user, user);
+
+    // The key can't be reused for another request
+    let res = create_user(
+        &api_key,
+        Some("create_user_1"),
+        json!({ "metadata": { "team": "sales" } }),
+    )
+    .await
+    .unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    // Another key creates another user
+    let res = create_user(&api_key, Some("create_user_2"), json!({}))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let other_user: Value = res.json().await.unwrap();
+    assert_ne!(other_user["user"]["id"], user["user"]["id"]);
+
+    // The keys are scoped to the credentials of the client
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let res = create_user(&res.secret_api_key, Some("create_user_1"), json!({}))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(res.headers().get("idempotent-replayed").is_none());
+    let other_account_user: Value = res.json().await.unwrap();
+    assert_ne!(other_account_user["user"]["id"], user["user"]["id"]);
+
+    // Invalid keys are rejected
+    let res = create_user(&api_key, Some(&"k".repeat(256)), json!({}))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
@@ -107,10 +107,13 @@ async fn create_300_events_1_month(
                 reminders: Vec::new(),
                 busy: Some(true),
                 recurrence: None,
+                timezone: None,
                 exdates: None,
                 recurring_event_id: None,
                 original_start_time: None,
                 service_id: None,
+                group_id: None,
+                attendees: vec![],
                 start_time: DateTime::from_timestamp_millis(start_time_millis).unwrap(),
                 metadata: None,
             })
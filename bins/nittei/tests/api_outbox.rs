@@ -0,0 +1,160 @@
+mod helpers;
+
+use chrono::{DateTime, Utc};
+use helpers::{setup::spawn_app, utils::wait_for_empty_outbox};
+use nittei_domain::{CalendarEventStatus, OutboxMessage, OutboxMessageStatus, OutboxTask, Weekday};
+use nittei_sdk::{
+    APIErrorVariant,
+    CalendarEventReminder,
+    CreateCalendarInput,
+    CreateEventInput,
+    CreateUserInput,
+    ID,
+    NitteiSDK,
+};
+
+#[tokio::test]
+async fn test_outbox_messages_are_processed_and_replayed() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let account_id = res.account.id.clone();
+    let admin_client = NitteiSDK::new(address.clone(), res.secret_api_key);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+
+    // The work of the subscribers (e.g. generating the reminders) is recorded in the outbox
+    admin_client
+        .event
+        .create(CreateEventInput {
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            title: None,
+            description: None,
+            event_type: None,
+            external_parent_id: None,
+            external_id: None,
+            location: None,
+            status: CalendarEventStatus::Confirmed,
+            all_day: None,
+            start_time: Utc::now(),
+            duration: 1000 * 60 * 60,
+            busy: None,
+            recurrence: None,
+            timezone: None,
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            reminders: vec![CalendarEventReminder {
+                delta: 10,
+                identifier: "".into(),
+            }],
+            attendees: Vec::new(),
+            service_id: None,
+            group_id: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+
+    // And processed by the worker
+    wait_for_empty_outbox(&admin_client).await;
+
+    // A dead letter is only processed again once replayed
+    let mut dead_letter = OutboxMessage::new(
+        account_id,
+        OutboxTask::SyncEventReminders {
+            event_id: ID::default(),
+        },
+        DateTime::<Utc>::from_timestamp_millis(1000 * 60 * 60 * 24).unwrap(),
+    );
+    dead_letter.status = OutboxMessageStatus::DeadLetter;
+    dead_letter.attempts = 10;
+    dead_letter.last_error = Some("Unable to sync the event reminders".into());
+    app.ctx
+        .repos
+        .outbox
+        .insert(std::slice::from_ref(&dead_letter))
+        .await
+        .unwrap();
+
+    let messages = admin_client
+        .account
+        .get_outbox_messages(Some(OutboxMessageStatus::DeadLetter), None)
+        .await
+        .unwrap()
+        .messages;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].id, dead_letter.id);
+    assert_eq!(messages[0].attempts, 10);
+    assert_eq!(messages[0].last_error, dead_letter.last_error);
+    let pending = admin_client
+        .account
+        .get_outbox_messages(Some(OutboxMessageStatus::Pending), None)
+        .await
+        .unwrap()
+        .messages;
+    assert!(pending.is_empty());
+
+    // Another account can't replay it
+    let other_account = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let other_admin_client = NitteiSDK::new(address, other_account.secret_api_key);
+    let res = other_admin_client
+        .account
+        .replay_outbox_message(dead_letter.id.clone())
+        .await;
+    assert!(matches!(
+        res.map_err(|e| e.variant),
+        Err(APIErrorVariant::NotFound)
+    ));
+
+    let replayed = admin_client
+        .account
+        .replay_outbox_message(dead_letter.id.clone())
+        .await
+        .unwrap()
+        .message;
+    assert_eq!(replayed.status, OutboxMessageStatus::Pending);
+    assert_eq!(replayed.attempts, 0);
+
+    // The event doesn't exist, so there is nothing to do and the message is removed
+    wait_for_empty_outbox(&admin_client).await;
+
+    let res = admin_client
+        .account
+        .replay_outbox_message(dead_letter.id)
+        .await;
+    assert!(matches!(
+        res.map_err(|e| e.variant),
+        Err(APIErrorVariant::NotFound)
+    ));
+}
@@ -0,0 +1,91 @@
+mod helpers;
+
+use helpers::setup::spawn_app;
+use nittei_sdk::{APIErrorVariant, NitteiSDK, RateLimit};
+use reqwest::{StatusCode, header};
+
+#[tokio::test]
+async fn test_account_rate_limits() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let api_key = res.secret_api_key;
+    let admin_client = NitteiSDK::new(address.clone(), api_key.clone());
+
+    let rate_limit = RateLimit {
+        requests_per_second: 1,
+        burst: 2,
+    };
+    let account = admin_client
+        .account
+        .set_rate_limits(Some(rate_limit), None)
+        .await
+        .unwrap()
+        .account;
+    assert_eq!(account.settings.rate_limits.account, Some(rate_limit));
+    assert_eq!(account.settings.rate_limits.user, None);
+
+    // The burst is used by the requests sent at once
+    admin_client.account.get().await.unwrap();
+    admin_client.account.get().await.unwrap();
+    let res = reqwest::Client::new()
+        .get(format!("{address}/api/v1/account"))
+        .header("x-api-key", &api_key)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(res.headers()[header::RETRY_AFTER], "1");
+    assert!(matches!(
+        admin_client.account.get().await.map_err(|e| e.variant),
+        Err(APIErrorVariant::TooManyRequests)
+    ));
+
+    // The other accounts aren't limited
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let other_admin_client = NitteiSDK::new(address.clone(), res.secret_api_key);
+    for _ in 0..5 {
+        other_admin_client.account.get().await.unwrap();
+    }
+
+    // The rate limited requests aren't recorded with their idempotency key
+    let create_user = || {
+        reqwest::Client::new()
+            .post(format!("{address}/api/v1/user"))
+            .header("x-api-key", &api_key)
+            .header("idempotency-key", "create_user")
+            .json(&serde_json::json!({}))
+            .send()
+    };
+    let res = create_user().await.unwrap();
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // Refilled once the time has passed
+    tokio::time::sleep(std::time::Duration::from_millis(2100)).await;
+    admin_client.account.get().await.unwrap();
+
+    // The retry with the same idempotency key runs the request
+    let res = create_user().await.unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert!(res.headers().get("idempotent-replayed").is_none());
+
+    // Invalid limits are rejected
+    let res = admin_client
+        .account
+        .set_rate_limits(
+            Some(RateLimit {
+                requests_per_second: 0,
+                burst: 1,
+            }),
+            None,
+        )
+        .await;
+    assert!(res.is_err());
+}
@@ -1,17 +1,21 @@
 mod helpers;
 
-use chrono::DateTime;
+use chrono::{DateTime, TimeDelta, TimeZone, Utc};
 use helpers::setup::spawn_app;
-use nittei_domain::Weekday;
+use nittei_domain::{EventInstance, Weekday};
 use nittei_sdk::{
     CreateCalendarInput,
     CreateEventInput,
     CreateUserInput,
+    DeleteEventInput,
+    ExportCalendarIcalInput,
     GetEventsInstancesInput,
     // WeekDayRecurrence,
     ID,
     NitteiSDK,
     RRuleOptions,
+    RecurringEventScope,
+    UpdateEventInput,
 };
 
 #[tokio::test]
@@ -65,10 +69,13 @@ async fn test_create_event_validation() {
             reminders: Vec::new(),
             busy: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: Some(ID::default()),
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             start_time: DateTime::from_timestamp_millis(0).unwrap(),
             metadata: None,
         })
@@ -133,10 +140,13 @@ async fn test_expand_daily_recurring_event() {
                 interval: 1,
                 ..Default::default()
             }),
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             metadata: None,
         })
         .await
@@ -228,10 +238,13 @@ async fn test_expand_weekly_recurring_event() {
                 interval: 1,
                 ..Default::default()
             }),
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             metadata: None,
         })
         .await
@@ -319,10 +332,13 @@ async fn test_expand_monthly_recurring_event() {
                 interval: 1,
                 ..Default::default()
             }),
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             metadata: None,
         })
         .await
@@ -405,10 +421,13 @@ async fn test_expand_recurring_event_and_remove_exceptions() {
                 interval: 1,
                 ..Default::default()
             }),
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             metadata: None,
         })
         .await
@@ -437,10 +456,13 @@ async fn test_expand_recurring_event_and_remove_exceptions() {
             reminders: Vec::new(),
             busy: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: Some(event.id.clone()),
             original_start_time: Some(DateTime::from_timestamp_millis(0).unwrap()),
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             metadata: None,
         })
         .await
@@ -466,6 +488,7 @@ async fn test_expand_recurring_event_and_remove_exceptions() {
             reminders: Vec::new(),
             busy: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: Some(event.id.clone()),
             original_start_time: Some(
@@ -475,6 +498,8 @@ async fn test_expand_recurring_event_and_remove_exceptions() {
                 .unwrap(),
             ),
             service_id: None,
+            group_id: None,
+            attendees: vec![],
             metadata: None,
         })
         .await
@@ -512,3 +537,469 @@ async fn test_expand_recurring_event_and_remove_exceptions() {
         .find(|instance| instance.start_time == exception_removed.original_start_time.unwrap());
     assert!(removed_instance.is_none());
 }
+
+#[tokio::test]
+async fn test_update_and_delete_recurring_event_with_scope() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+
+    let start_time = DateTime::from_timestamp_millis(0).unwrap();
+    let day = |n: i64| start_time + TimeDelta::days(n);
+
+    // Daily event with 10 occurrences
+    let event = admin_client
+        .event
+        .create(CreateEventInput {
+            external_parent_id: None,
+            external_id: None,
+            title: Some("Daily".into()),
+            description: None,
+            event_type: None,
+            location: None,
+            status: nittei_domain::CalendarEventStatus::Confirmed,
+            all_day: None,
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            start_time,
+            duration: 1000 * 60 * 60,
+            reminders: Vec::new(),
+            busy: None,
+            recurrence: Some(RRuleOptions {
+                freq: nittei_sdk::RRuleFrequency::Daily,
+                interval: 1,
+                count: Some(10),
+                ..Default::default()
+            }),
+            timezone: None,
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            service_id: None,
+            group_id: None,
+            attendees: vec![],
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .event;
+
+    let update_input =
+        |event_id: &ID, scope, occurrence_start_time, title: &str| UpdateEventInput {
+            event_id: event_id.clone(),
+            title: Some(Some(title.into())),
+            description: None,
+            event_type: None,
+            external_parent_id: None,
+            external_id: None,
+            location: None,
+            status: None,
+            all_day: None,
+            start_time: None,
+            duration: None,
+            busy: None,
+            reminders: None,
+            attendees: None,
+            recurrence: None,
+            timezone: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            service_id: None,
+            group_id: None,
+            exdates: None,
+            metadata: None,
+            scope: Some(scope),
+            occurrence_start_time: Some(occurrence_start_time),
+            expected_version: None,
+        };
+
+    // Updating only one occurrence creates an exception
+    let exception = admin_client
+        .event
+        .update(update_input(
+            &event.id,
+            RecurringEventScope::This,
+            day(2),
+            "Only this one",
+        ))
+        .await
+        .unwrap()
+        .event;
+    assert_ne!(exception.id, event.id);
+    assert_eq!(exception.title, Some("Only this one".into()));
+    assert_eq!(exception.start_time, day(2));
+    assert_eq!(exception.recurring_event_id, Some(event.id.clone()));
+    assert_eq!(exception.original_start_time, Some(day(2)));
+    assert!(exception.recurrence.is_none());
+    // The exception is a new event, with its own version
+    assert_eq!(exception.version, 0);
+
+    // Updating the same occurrence again updates the existing exception
+    let same_exception = admin_client
+        .event
+        .update(update_input(
+            &event.id,
+            RecurringEventScope::This,
+            day(2),
+            "Still this one",
+        ))
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(same_exception.id, exception.id);
+    assert_eq!(same_exception.title, Some("Still this one".into()));
+
+    // Another exception, after the split
+    let later_exception = admin_client
+        .event
+        .update(update_input(
+            &event.id,
+            RecurringEventScope::This,
+            day(7),
+            "Later",
+        ))
+        .await
+        .unwrap()
+        .event;
+
+    // Not an occurrence of the series
+    let invalid = admin_client
+        .event
+        .update(update_input(
+            &event.id,
+            RecurringEventScope::ThisAndFollowing,
+            day(4) + TimeDelta::minutes(1),
+            "Invalid",
+        ))
+        .await;
+    assert!(invalid.is_err());
+
+    // Updating this and the following occurrences splits the series
+    let new_series = admin_client
+        .event
+        .update(update_input(
+            &event.id,
+            RecurringEventScope::ThisAndFollowing,
+            day(4),
+            "Following",
+        ))
+        .await
+        .unwrap()
+        .event;
+    assert_ne!(new_series.id, event.id);
+    assert_eq!(new_series.title, Some("Following".into()));
+    assert_eq!(new_series.start_time, day(4));
+    assert_eq!(new_series.recurrence.as_ref().unwrap().count, Some(6));
+    // The version of the new series is the stored one
+    assert_eq!(new_series.version, 0);
+    assert_eq!(
+        admin_client
+            .event
+            .get(new_series.id.clone())
+            .await
+            .unwrap()
+            .event
+            .version,
+        new_series.version
+    );
+
+    let instances = |event_id: ID| GetEventsInstancesInput {
+        event_id,
+        start_time,
+        end_time: day(20),
+    };
+    let original_instances = admin_client
+        .event
+        .get_instances(instances(event.id.clone()))
+        .await
+        .unwrap();
+    assert_eq!(original_instances.event.title, Some("Daily".into()));
+    // 4 occurrences left, minus the exception
+    assert_eq!(original_instances.instances.len(), 3);
+    let new_instances = admin_client
+        .event
+        .get_instances(instances(new_series.id.clone()))
+        .await
+        .unwrap();
+    // 6 occurrences, minus the moved exception
+    assert_eq!(new_instances.instances.len(), 5);
+
+    // The exceptions follow their occurrence
+    let exception = admin_client
+        .event
+        .get(exception.id.clone())
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(exception.recurring_event_id, Some(event.id.clone()));
+    let later_exception = admin_client
+        .event
+        .get(later_exception.id.clone())
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(
+        later_exception.recurring_event_id,
+        Some(new_series.id.clone())
+    );
+
+    // Deleting only one occurrence excludes it from the series
+    let deleted = admin_client
+        .event
+        .delete_with_scope(DeleteEventInput {
+            event_id: new_series.id.clone(),
+            scope: RecurringEventScope::This,
+            occurrence_start_time: Some(day(5)),
+        })
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(deleted.exdates, vec![day(5)]);
+    let new_instances = admin_client
+        .event
+        .get_instances(instances(new_series.id.clone()))
+        .await
+        .unwrap();
+    assert_eq!(new_instances.instances.len(), 4);
+
+    // Deleting this and the following occurrences truncates the series, and removes their exceptions
+    admin_client
+        .event
+        .delete_with_scope(DeleteEventInput {
+            event_id: new_series.id.clone(),
+            scope: RecurringEventScope::ThisAndFollowing,
+            occurrence_start_time: Some(day(7)),
+        })
+        .await
+        .unwrap();
+    let new_instances = admin_client
+        .event
+        .get_instances(instances(new_series.id.clone()))
+        .await
+        .unwrap();
+    // Day 4 and day 6
+    assert_eq!(new_instances.instances.len(), 2);
+    assert!(
+        admin_client
+            .event
+            .get(later_exception.id.clone())
+            .await
+            .is_err()
+    );
+
+    // The scope needs the start time of the occurrence
+    let invalid = admin_client
+        .event
+        .delete_with_scope(DeleteEventInput {
+            event_id: event.id.clone(),
+            scope: RecurringEventScope::This,
+            occurrence_start_time: None,
+        })
+        .await;
+    assert!(invalid.is_err());
+
+    // Deleting this and the following occurrences from the first one deletes the whole series
+    admin_client
+        .event
+        .delete_with_scope(DeleteEventInput {
+            event_id: new_series.id.clone(),
+            scope: RecurringEventScope::ThisAndFollowing,
+            occurrence_start_time: Some(day(4)),
+        })
+        .await
+        .unwrap();
+    assert!(admin_client.event.get(new_series.id.clone()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_expand_recurring_event_in_its_own_timezone() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    // The calendar is in UTC, the event is for a user in Paris
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+
+    // Every Monday at 09:00 in Paris, before and after the DST change of March 31, 2030
+    let paris = chrono_tz::Europe::Paris;
+    let paris_time = |day| {
+        paris
+            .with_ymd_and_hms(2030, 3, day, 9, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let event = admin_client
+        .event
+        .create(CreateEventInput {
+            external_parent_id: None,
+            external_id: None,
+            title: None,
+            description: None,
+            event_type: None,
+            location: None,
+            status: nittei_domain::CalendarEventStatus::Confirmed,
+            all_day: None,
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            start_time: paris_time(18),
+            duration: 1000 * 60 * 60,
+            reminders: Vec::new(),
+            busy: None,
+            recurrence: Some(RRuleOptions {
+                freq: nittei_sdk::RRuleFrequency::Weekly,
+                count: Some(3),
+                ..Default::default()
+            }),
+            timezone: Some(paris),
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            service_id: None,
+            group_id: None,
+            attendees: vec![],
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(event.timezone, Some(paris));
+
+    let get_instances_input = || GetEventsInstancesInput {
+        event_id: event.id.clone(),
+        start_time: paris_time(1),
+        end_time: paris_time(1) + TimeDelta::days(60),
+    };
+    let local_times = |instances: &[EventInstance]| {
+        instances
+            .iter()
+            .map(|instance| {
+                instance
+                    .start_time
+                    .with_timezone(&paris)
+                    .format("%m-%d %H:%M")
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // The occurrences stay at 09:00 (Paris time) after the DST change
+    let instances = admin_client
+        .event
+        .get_instances(get_instances_input())
+        .await
+        .unwrap()
+        .instances;
+    assert_eq!(
+        local_times(&instances),
+        vec!["03-18 09:00", "03-25 09:00", "04-01 09:00"]
+    );
+    let ical = admin_client
+        .calendar
+        .export_ical(ExportCalendarIcalInput {
+            calendar_id: calendar.id.clone(),
+            start_time: Some(paris_time(1)),
+            end_time: Some(paris_time(1) + TimeDelta::days(60)),
+        })
+        .await
+        .unwrap();
+    assert!(ical.contains("TZID:Europe/Paris\r\n"));
+    assert!(ical.contains("DTSTART;TZID=Europe/Paris:20300318T090000\r\n"));
+
+    // Without its timezone, the event is expanded in the timezone of the calendar (UTC)
+    let event = admin_client
+        .event
+        .update(UpdateEventInput {
+            event_id: event.id.clone(),
+            title: None,
+            description: None,
+            event_type: None,
+            external_parent_id: None,
+            external_id: None,
+            location: None,
+            status: None,
+            all_day: None,
+            start_time: None,
+            duration: None,
+            busy: None,
+            reminders: None,
+            attendees: None,
+            recurrence: None,
+            timezone: Some(None),
+            recurring_event_id: None,
+            original_start_time: None,
+            service_id: None,
+            group_id: None,
+            exdates: None,
+            metadata: None,
+            scope: None,
+            occurrence_start_time: None,
+            expected_version: None,
+        })
+        .await
+        .unwrap()
+        .event;
+    assert_eq!(event.timezone, None);
+    let instances = admin_client
+        .event
+        .get_instances(get_instances_input())
+        .await
+        .unwrap()
+        .instances;
+    assert_eq!(
+        local_times(&instances),
+        vec!["03-18 09:00", "03-25 09:00", "04-01 10:00"]
+    );
+}
@@ -0,0 +1,188 @@
+mod helpers;
+
+use chrono::{Duration, DurationRound, Utc};
+use helpers::setup::spawn_app;
+use nittei_domain::{CalendarEventStatus, IDQuery};
+use nittei_sdk::{
+    AccountSearchEventsRequestBody,
+    AccountSearchEventsRequestBodyFilter,
+    CalendarEventSort,
+    CreateCalendarInput,
+    CreateEventInput,
+    CreateUserInput,
+    ID,
+    KVMetadata,
+    MetadataFindInput,
+    NitteiSDK,
+    SearchEventsRequestBody,
+    SearchEventsRequestBodyFilter,
+    Weekday,
+};
+
+#[tokio::test]
+async fn test_search_events_with_cursors() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+
+    // Two events start at each hour, they are ordered by their ids
+    let start_time = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    let mut event_ids = Vec::new();
+    for i in 0..5 {
+        let event = admin_client
+            .event
+            .create(CreateEventInput {
+                user_id: user.id.clone(),
+                calendar_id: calendar.id.clone(),
+                title: Some(format!("Event {i}")),
+                description: None,
+                event_type: None,
+                external_parent_id: None,
+                external_id: None,
+                location: None,
+                status: CalendarEventStatus::Confirmed,
+                all_day: None,
+                start_time: start_time + Duration::hours(i / 2),
+                duration: 1000 * 60 * 30,
+                busy: None,
+                recurrence: None,
+                timezone: None,
+                exdates: None,
+                recurring_event_id: None,
+                original_start_time: None,
+                reminders: Vec::new(),
+                attendees: Vec::new(),
+                service_id: None,
+                group_id: None,
+                metadata: Some(serde_json::json!({ "report": "2030" })),
+            })
+            .await
+            .unwrap()
+            .event;
+        event_ids.push((event.start_time, event.id));
+    }
+    event_ids.sort_by_key(|(start_time, id)| (*start_time, *id.as_ref()));
+    let event_ids = event_ids.into_iter().map(|(_, id)| id).collect::<Vec<ID>>();
+
+    // All the events of the account, page by page
+    let mut found = Vec::new();
+    let mut cursor = None;
+    loop {
+        let res = admin_client
+            .account
+            .search_events(AccountSearchEventsRequestBody {
+                filter: AccountSearchEventsRequestBodyFilter {
+                    user_id: Some(IDQuery::Eq(user.id.clone())),
+                    ..Default::default()
+                },
+                limit: Some(2),
+                cursor,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(res.events.len() <= 2);
+        found.extend(res.events.into_iter().map(|e| e.id));
+        cursor = res.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    assert_eq!(found, event_ids);
+
+    // Same for the events of the user, in the reverse order
+    let search = |cursor: Option<String>| SearchEventsRequestBody {
+        filter: SearchEventsRequestBodyFilter {
+            user_id: user.id.clone(),
+            ..Default::default()
+        },
+        sort: Some(CalendarEventSort::StartTimeDesc),
+        limit: Some(3),
+        cursor,
+    };
+    let first_page = admin_client.event.search(search(None)).await.unwrap();
+    let second_page = admin_client
+        .event
+        .search(search(first_page.next_cursor.clone()))
+        .await
+        .unwrap();
+    assert!(second_page.next_cursor.is_none());
+    let found = first_page
+        .events
+        .iter()
+        .chain(second_page.events.iter())
+        .map(|e| e.id.clone())
+        .rev()
+        .collect::<Vec<_>>();
+    assert_eq!(found, event_ids);
+
+    // The cursors can't be used with another sort, and must be valid
+    let res = admin_client
+        .event
+        .search(SearchEventsRequestBody {
+            sort: Some(CalendarEventSort::StartTimeAsc),
+            ..search(first_page.next_cursor.clone())
+        })
+        .await;
+    assert!(res.is_err_and(|e| e.message.contains("cursor")));
+    let res = admin_client
+        .event
+        .search(search(Some("invalid".into())))
+        .await;
+    assert!(res.is_err_and(|e| e.message.contains("The cursor is invalid")));
+
+    // The events found by metadata are ordered by their ids
+    let mut found = Vec::new();
+    let mut cursor = None;
+    loop {
+        let res = admin_client
+            .event
+            .get_by_meta(MetadataFindInput {
+                limit: 2,
+                skip: 0,
+                metadata: KVMetadata {
+                    key: "report".into(),
+                    value: "2030".into(),
+                },
+                cursor,
+            })
+            .await
+            .unwrap();
+        found.extend(res.events.into_iter().map(|e| e.id));
+        cursor = res.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    let mut sorted_event_ids = event_ids.clone();
+    sorted_event_ids.sort_by_key(|id| *id.as_ref());
+    assert_eq!(found, sorted_event_ids);
+}
@@ -0,0 +1,210 @@
+mod helpers;
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{Router, extract::State, http::StatusCode, routing::post};
+use chrono::Utc;
+use helpers::{setup::spawn_app, utils::wait_for_empty_outbox};
+use nittei_domain::CalendarEventStatus;
+use nittei_sdk::{
+    APIErrorVariant,
+    CreateCalendarInput,
+    CreateEventInput,
+    CreateUserInput,
+    NitteiSDK,
+    WebhookDeliveryStatus,
+    WebhookEventType,
+    Weekday,
+};
+use tokio::net::TcpListener;
+
+/// Start a server receiving the webhooks on the listener
+/// It is unavailable for the first request, and then accepts all the webhooks
+fn spawn_flaky_webhook_receiver(listener: TcpListener, requests: Arc<AtomicUsize>) {
+    let app = Router::new()
+        .route(
+            "/",
+            post(|State(requests): State<Arc<AtomicUsize>>| async move {
+                if requests.fetch_add(1, Ordering::SeqCst) == 0 {
+                    (StatusCode::INTERNAL_SERVER_ERROR, "unavailable")
+                } else {
+                    (StatusCode::OK, "ok")
+                }
+            }),
+        )
+        .with_state(requests);
+
+    tokio::spawn(async move { axum::serve(listener, app).await });
+}
+
+#[tokio::test]
+async fn test_webhook_delivery_log_and_redelivery() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address.clone(), res.secret_api_key);
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let webhook_url = format!("http://{}/", listener.local_addr().unwrap());
+    spawn_flaky_webhook_receiver(listener, requests.clone());
+
+    admin_client
+        .account
+        .create_webhook(&webhook_url, Some(vec![WebhookEventType::EventCreated]))
+        .await
+        .unwrap();
+
+    // Nothing has been sent yet
+    let res = admin_client
+        .account
+        .get_webhook_deliveries(None, None)
+        .await
+        .unwrap();
+    assert!(res.endpoint.is_none());
+    assert!(res.deliveries.is_empty());
+
+    let user = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .user;
+    let calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            user_id: user.id.clone(),
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            week_start: Weekday::Mon,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .calendar;
+    admin_client
+        .event
+        .create(CreateEventInput {
+            user_id: user.id.clone(),
+            calendar_id: calendar.id.clone(),
+            title: None,
+            description: None,
+            event_type: None,
+            external_parent_id: None,
+            external_id: None,
+            location: None,
+            status: CalendarEventStatus::Confirmed,
+            all_day: None,
+            start_time: Utc::now(),
+            duration: 1000 * 60 * 60,
+            busy: None,
+            recurrence: None,
+            timezone: None,
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            reminders: Vec::new(),
+            attendees: Vec::new(),
+            service_id: None,
+            group_id: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+
+    wait_for_empty_outbox(&admin_client).await;
+
+    // The first attempt failed, and is recorded
+    let res = admin_client
+        .account
+        .get_webhook_deliveries(None, None)
+        .await
+        .unwrap();
+    assert_eq!(res.deliveries.len(), 1);
+    let delivery = &res.deliveries[0];
+    assert_eq!(delivery.event_type, Some(WebhookEventType::EventCreated));
+    assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+    assert!(delivery.next_attempt_at > Utc::now());
+    assert_eq!(delivery.attempts.len(), 1);
+    assert_eq!(delivery.attempts[0].url, webhook_url);
+    assert_eq!(delivery.attempts[0].status_code, Some(500));
+    assert_eq!(
+        delivery.attempts[0].response_excerpt.as_deref(),
+        Some("unavailable")
+    );
+    let endpoint = res.endpoint.unwrap();
+    assert_eq!(endpoint.url, webhook_url);
+    assert_eq!(endpoint.consecutive_failures, 1);
+    assert!(endpoint.failing_since.is_none());
+
+    // Filtered by status
+    let res = admin_client
+        .account
+        .get_webhook_deliveries(Some(WebhookDeliveryStatus::Succeeded), None)
+        .await
+        .unwrap();
+    assert!(res.deliveries.is_empty());
+
+    // Another account can't redeliver it
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .unwrap();
+    let other_admin_client = NitteiSDK::new(address, res.secret_api_key);
+    let res = other_admin_client
+        .account
+        .redeliver_webhook(delivery.id.clone())
+        .await;
+    assert!(matches!(
+        res.map_err(|e| e.variant),
+        Err(APIErrorVariant::NotFound)
+    ));
+
+    // Redelivered right away, instead of waiting for the backoff
+    let redelivered = admin_client
+        .account
+        .redeliver_webhook(delivery.id.clone())
+        .await
+        .unwrap()
+        .delivery;
+    assert_eq!(redelivered.id, delivery.id);
+    assert_eq!(redelivered.status, WebhookDeliveryStatus::Pending);
+    assert!(redelivered.next_attempt_at <= Utc::now());
+
+    let mut succeeded = None;
+    for _ in 0..50 {
+        let res = admin_client
+            .account
+            .get_webhook_deliveries(Some(WebhookDeliveryStatus::Succeeded), None)
+            .await
+            .unwrap();
+        if !res.deliveries.is_empty() {
+            succeeded = Some(res);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    let res = succeeded.expect("Expected the webhook to be redelivered");
+    assert_eq!(res.deliveries.len(), 1);
+    let delivery = &res.deliveries[0];
+    assert_eq!(delivery.attempts.len(), 2);
+    assert_eq!(delivery.attempts[1].status_code, Some(200));
+    assert_eq!(delivery.attempts[1].response_excerpt.as_deref(), Some("ok"));
+    assert_eq!(res.endpoint.unwrap().consecutive_failures, 0);
+    assert_eq!(requests.load(Ordering::SeqCst), 2);
+}
@@ -71,6 +71,10 @@ async fn create_default_service_host(
         buffer_before: None,
         closest_booking_time: None,
         furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
         service_id: service_id.clone(),
         user_id: host.id.clone(),
     };
@@ -107,6 +111,9 @@ async fn test_collective_team_scheduling() {
     let users_count_list: Vec<usize> = vec![0, 1, 5, 10];
     for users_count in users_count_list {
         let input = CreateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             multi_person: Some(ServiceMultiPersonOptions::Collective),
         };
@@ -181,11 +188,14 @@ async fn test_collective_team_scheduling() {
                 duration,
                 metadata: None,
                 recurrence: None,
+                timezone: None,
                 exdates: None,
                 recurring_event_id: None,
                 original_start_time: None,
                 reminders: Vec::new(),
                 service_id: Some(service.id.clone()),
+                group_id: None,
+                attendees: vec![],
                 start_time: available_slot,
             };
             admin_client
@@ -242,6 +252,9 @@ async fn test_collective_team_scheduling_is_collective() {
     let admin_client = NitteiSDK::new(address, res.secret_api_key);
 
     let input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: None,
         metadata: None,
         multi_person: Some(ServiceMultiPersonOptions::Collective),
     };
@@ -295,6 +308,10 @@ async fn test_collective_team_scheduling_is_collective() {
         buffer_before: None,
         closest_booking_time: None,
         furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
         service_id: service.id.clone(),
         user_id: host1.id.clone(),
     };
@@ -311,6 +328,10 @@ async fn test_collective_team_scheduling_is_collective() {
         buffer_before: None,
         closest_booking_time: None,
         furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
         service_id: service.id.clone(),
         user_id: host2.id.clone(),
     };
@@ -72,6 +72,10 @@ async fn create_default_service_host(
         buffer_before: None,
         closest_booking_time: None,
         furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
         service_id: service_id.clone(),
         user_id: host.id.clone(),
     };
@@ -109,6 +113,9 @@ async fn test_group_team_scheduling() {
     for users_count in users_count_list {
         for max_booking_spots in max_booking_spots_list.clone() {
             let input = CreateServiceInput {
+                cancellation_cutoff: None,
+                reschedule_cutoff: None,
+                distribution_window: None,
                 metadata: None,
                 multi_person: Some(ServiceMultiPersonOptions::Group(max_booking_spots)),
             };
@@ -199,11 +206,14 @@ async fn test_group_team_scheduling() {
                     duration,
                     metadata: None,
                     recurrence: None,
+                    timezone: None,
                     exdates: None,
                     recurring_event_id: None,
                     original_start_time: None,
                     reminders: Vec::new(),
                     service_id: Some(service.id.clone()),
+                    group_id: None,
+                    attendees: vec![],
                     start_time: available_slot,
                 };
                 admin_client
@@ -262,6 +272,9 @@ async fn test_group_team_scheduling_is_collective() {
 
     let max_booking_spots = 5;
     let input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: None,
         metadata: None,
         multi_person: Some(ServiceMultiPersonOptions::Group(max_booking_spots)),
     };
@@ -315,6 +328,10 @@ async fn test_group_team_scheduling_is_collective() {
         buffer_before: None,
         closest_booking_time: None,
         furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
         service_id: service.id.clone(),
         user_id: host1.id.clone(),
     };
@@ -331,6 +348,10 @@ async fn test_group_team_scheduling_is_collective() {
         buffer_before: None,
         closest_booking_time: None,
         furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
         service_id: service.id.clone(),
         user_id: host2.id.clone(),
     };
@@ -378,6 +399,9 @@ async fn test_group_team_scheduling_increase_max_count() {
     let test_set = vec![(5, 2), (1, 1), (1, 10), (10, 20)];
     for (max_booking_spots, booking_spots_inc) in test_set {
         let input = CreateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             multi_person: Some(ServiceMultiPersonOptions::Group(max_booking_spots)),
         };
@@ -433,6 +457,10 @@ async fn test_group_team_scheduling_increase_max_count() {
             buffer_before: None,
             closest_booking_time: None,
             furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
             service_id: service.id.clone(),
             user_id: host.id.clone(),
         };
@@ -534,11 +562,14 @@ async fn test_group_team_scheduling_increase_max_count() {
             duration,
             metadata: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             reminders: Vec::new(),
             service_id: Some(service.id.clone()),
+            group_id: None,
+            attendees: vec![],
             start_time: available_slot,
         };
         let service_event = admin_client
@@ -551,6 +582,9 @@ async fn test_group_team_scheduling_increase_max_count() {
         // Now there are no more spots available on that timestamp
         // But lets increase max count
         let input = UpdateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             service_id: service.id.clone(),
             multi_person: Some(ServiceMultiPersonOptions::Group(
@@ -623,6 +657,9 @@ async fn test_group_team_scheduling_increase_max_count() {
     let test_set = vec![(0, 1), (0, 2), (0, 10)];
     for (max_booking_spots, booking_spots_inc) in test_set {
         let input = CreateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             multi_person: Some(ServiceMultiPersonOptions::Group(max_booking_spots)),
         };
@@ -678,6 +715,10 @@ async fn test_group_team_scheduling_increase_max_count() {
             buffer_before: None,
             closest_booking_time: None,
             furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
             service_id: service.id.clone(),
             user_id: host.id.clone(),
         };
@@ -722,6 +763,9 @@ async fn test_group_team_scheduling_increase_max_count() {
         // Now there are no more spots available on that timestamp
         // But lets increase max count
         let input = UpdateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             service_id: service.id.clone(),
             multi_person: Some(ServiceMultiPersonOptions::Group(
@@ -813,6 +857,9 @@ async fn test_group_team_scheduling_decrease_max_count() {
     let test_set = vec![(5, 2), (1, 1), (1, 0), (10, 4)];
     for (max_booking_spots, booking_spots_dec) in test_set {
         let input = CreateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             multi_person: Some(ServiceMultiPersonOptions::Group(max_booking_spots)),
         };
@@ -868,6 +915,10 @@ async fn test_group_team_scheduling_decrease_max_count() {
             buffer_before: None,
             closest_booking_time: None,
             furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
             service_id: service.id.clone(),
             user_id: host.id.clone(),
         };
@@ -969,11 +1020,14 @@ async fn test_group_team_scheduling_decrease_max_count() {
             duration,
             metadata: None,
             recurrence: None,
+            timezone: None,
             exdates: None,
             recurring_event_id: None,
             original_start_time: None,
             reminders: Vec::new(),
             service_id: Some(service.id.clone()),
+            group_id: None,
+            attendees: vec![],
             start_time: available_slot,
         };
         let service_event = admin_client
@@ -986,6 +1040,9 @@ async fn test_group_team_scheduling_decrease_max_count() {
         // Now there are no more spots available on that timestamp
         // Lets decrease max count and check that it still is not possible to change
         let input = UpdateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             service_id: service.id.clone(),
             multi_person: Some(ServiceMultiPersonOptions::Group(
@@ -1036,6 +1093,9 @@ async fn test_combination_of_services() {
     let admin_client = NitteiSDK::new(address, res.secret_api_key);
 
     let input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: None,
         metadata: None,
         multi_person: Some(ServiceMultiPersonOptions::Group(10)),
     };
@@ -1046,6 +1106,9 @@ async fn test_combination_of_services() {
         .expect("To create service")
         .service;
     let input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: None,
         metadata: None,
         multi_person: Some(ServiceMultiPersonOptions::RoundRobinAlgorithm(
             Default::default(),
@@ -1105,6 +1168,10 @@ async fn test_combination_of_services() {
             buffer_before: None,
             closest_booking_time: None,
             furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
             service_id: service_id.clone(),
             user_id: host.id.clone(),
         };
@@ -1186,11 +1253,14 @@ async fn test_combination_of_services() {
         duration,
         metadata: None,
         recurrence: None,
+        timezone: None,
         exdates: None,
         recurring_event_id: None,
         original_start_time: None,
         reminders: Vec::new(),
         service_id: Some(group_service.id.clone()),
+        group_id: None,
+        attendees: vec![],
         start_time: available_slot,
     };
     admin_client
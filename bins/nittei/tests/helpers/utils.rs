@@ -1,5 +1,7 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
-use nittei_sdk::User;
+use nittei_sdk::{NitteiSDK, User};
 
 #[allow(dead_code)]
 pub fn format_datetime(dt: &DateTime<Utc>) -> String {
@@ -19,3 +21,16 @@ pub fn assert_equal_user_lists(users1: &[User], users2: &[User]) {
         assert_eq!(user1.id, user2.id);
     }
 }
+
+/// Wait for the outbox worker to process the messages of the account
+#[allow(dead_code)]
+pub async fn wait_for_empty_outbox(admin_client: &NitteiSDK) {
+    for _ in 0..50 {
+        let res = admin_client.account.get_outbox_messages(None, None).await;
+        if res.is_ok_and(|res| res.messages.is_empty()) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+    panic!("Expected the outbox messages to be processed");
+}
@@ -19,6 +19,7 @@ use nittei_sdk::{
     GetServiceBookingSlotsInput,
     NitteiSDK,
     RoundRobinAlgorithm,
+    UpdateServiceUserInput,
     User,
 };
 
@@ -72,6 +73,10 @@ async fn create_default_service_host(
         buffer_before: None,
         closest_booking_time: None,
         furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
         service_id: service_id.clone(),
         user_id: host.id.clone(),
     };
@@ -112,6 +117,9 @@ async fn test_round_robin_scheduling_simple_test() {
     for users_count in users_count_list {
         for alg in round_robin_algos.clone() {
             let input = CreateServiceInput {
+                cancellation_cutoff: None,
+                reschedule_cutoff: None,
+                distribution_window: None,
                 metadata: None,
                 multi_person: Some(ServiceMultiPersonOptions::RoundRobinAlgorithm(alg)),
             };
@@ -204,11 +212,14 @@ async fn test_round_robin_scheduling_simple_test() {
                     duration,
                     metadata: None,
                     recurrence: None,
+                    timezone: None,
                     exdates: None,
                     recurring_event_id: None,
                     original_start_time: None,
                     reminders: Vec::new(),
                     service_id: Some(service.id.clone()),
+                    group_id: None,
+                    attendees: vec![],
                     start_time: available_slot,
                 };
                 admin_client
@@ -257,6 +268,9 @@ async fn test_round_robin_equal_distribution_scheduling() {
 
     for upcoming_service_events_per_host in test_cases {
         let input = CreateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             multi_person: Some(ServiceMultiPersonOptions::RoundRobinAlgorithm(
                 RoundRobinAlgorithm::EqualDistribution,
@@ -302,7 +316,8 @@ async fn test_round_robin_equal_distribution_scheduling() {
             continue;
         }
         let available_slot = bookingslots[0].slots[0].start;
-        let some_time_later = available_slot + TimeDelta::milliseconds(14 * 24 * 60 * 60 * 1000);
+        // Within the default distribution window of two weeks
+        let some_time_later = available_slot + TimeDelta::days(7);
 
         // Create upcoming service_events
         for (upcoming_service_events, (host, busy_calendar)) in upcoming_service_events_per_host
@@ -326,11 +341,14 @@ async fn test_round_robin_equal_distribution_scheduling() {
                     duration,
                     metadata: None,
                     recurrence: None,
+                    timezone: None,
                     exdates: None,
                     recurring_event_id: None,
                     original_start_time: None,
                     reminders: Vec::new(),
                     service_id: Some(service.id.clone()),
+                    group_id: None,
+                    attendees: vec![],
                     start_time: some_time_later,
                 };
                 admin_client
@@ -390,11 +408,14 @@ async fn test_round_robin_equal_distribution_scheduling() {
                 duration,
                 metadata: None,
                 recurrence: None,
+                timezone: None,
                 exdates: None,
                 recurring_event_id: None,
                 original_start_time: None,
                 reminders: Vec::new(),
                 service_id: Some(service.id.clone()),
+                group_id: None,
+                attendees: vec![],
                 start_time: available_slot,
             };
             admin_client
@@ -454,6 +475,9 @@ async fn test_round_robin_availability_scheduling() {
 
     for last_assigned_service_event_per_host in test_cases {
         let input = CreateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
             metadata: None,
             multi_person: Some(ServiceMultiPersonOptions::RoundRobinAlgorithm(
                 RoundRobinAlgorithm::Availability,
@@ -522,11 +546,14 @@ async fn test_round_robin_availability_scheduling() {
                 duration,
                 metadata: None,
                 recurrence: None,
+                timezone: None,
                 exdates: None,
                 recurring_event_id: None,
                 original_start_time: None,
                 reminders: Vec::new(),
                 service_id: Some(service.id.clone()),
+                group_id: None,
+                attendees: vec![],
                 start_time: DateTime::from_timestamp_millis(0).unwrap(),
             };
             let event_id = admin_client
@@ -605,11 +632,14 @@ async fn test_round_robin_availability_scheduling() {
                 duration,
                 metadata: None,
                 recurrence: None,
+                timezone: None,
                 exdates: None,
                 recurring_event_id: None,
                 original_start_time: None,
                 reminders: Vec::new(),
                 service_id: Some(service.id.clone()),
+                group_id: None,
+                attendees: vec![],
                 start_time: available_slot,
             };
             admin_client
@@ -620,3 +650,461 @@ async fn test_round_robin_availability_scheduling() {
         }
     }
 }
+
+#[tokio::test]
+async fn test_round_robin_availability_scheduling_ignores_weights() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: None,
+        metadata: None,
+        multi_person: Some(ServiceMultiPersonOptions::RoundRobinAlgorithm(
+            RoundRobinAlgorithm::Availability,
+        )),
+    };
+    let service = admin_client
+        .service
+        .create(input)
+        .await
+        .expect("To create service")
+        .service;
+
+    let mut hosts_with_calendars = Vec::new();
+    for _ in 0..2 {
+        hosts_with_calendars.push(create_default_service_host(&admin_client, &service.id).await);
+    }
+
+    // The first host has the highest weight, but was assigned a service event more recently
+    let input = UpdateServiceUserInput {
+        service_id: service.id.clone(),
+        user_id: hosts_with_calendars[0].0.id.clone(),
+        availability: None,
+        buffer_after: None,
+        buffer_before: None,
+        closest_booking_time: None,
+        furthest_booking_time: None,
+        weight: Some(10),
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
+    };
+    admin_client
+        .service
+        .update_user(input)
+        .await
+        .expect("To update service user");
+
+    for ((host, busy_calendar), last_assigned) in hosts_with_calendars.iter().zip([2, 1]) {
+        let service_event = CreateEventInput {
+            external_parent_id: None,
+            external_id: None,
+            title: None,
+            description: None,
+            event_type: None,
+            location: None,
+            status: nittei_domain::CalendarEventStatus::Tentative,
+            all_day: None,
+            user_id: host.id.clone(),
+            busy: Some(true),
+            calendar_id: busy_calendar.id.clone(),
+            duration: 1000 * 60 * 30,
+            metadata: None,
+            recurrence: None,
+            timezone: None,
+            exdates: None,
+            recurring_event_id: None,
+            original_start_time: None,
+            reminders: Vec::new(),
+            service_id: Some(service.id.clone()),
+            group_id: None,
+            attendees: vec![],
+            start_time: DateTime::from_timestamp_millis(0).unwrap(),
+        };
+        let event_id = admin_client
+            .event
+            .create(service_event)
+            .await
+            .expect("To create service event")
+            .event
+            .id;
+
+        // Created field can only by updated through direct db queries
+        let mut service_event = app
+            .ctx
+            .repos
+            .events
+            .find(&event_id)
+            .await
+            .unwrap()
+            .expect("To find event");
+        service_event.created = DateTime::from_timestamp_millis(last_assigned).unwrap();
+        app.ctx
+            .repos
+            .events
+            .save(&service_event)
+            .await
+            .expect("To save event");
+    }
+
+    let duration = 1000 * 60 * 30;
+    let interval = 1000 * 60 * 30;
+    let tomorrow = Utc::now() + Duration::days(1);
+    let next_week = tomorrow + Duration::days(7);
+    let input = GetServiceBookingSlotsInput {
+        duration,
+        interval,
+        service_id: service.id.clone(),
+        timezone: Some(chrono_tz::UTC),
+        end_date: format_datetime(&next_week),
+        start_date: format_datetime(&tomorrow),
+        host_user_ids: None,
+    };
+    let bookingslots = admin_client
+        .service
+        .bookingslots(input)
+        .await
+        .expect("To get bookingslots")
+        .dates;
+
+    // The least recently assigned host is selected, whatever the weights
+    let input = CreateBookingIntendInput {
+        service_id: service.id.clone(),
+        host_user_ids: None,
+        timestamp: bookingslots[0].slots[0].start,
+        duration,
+        interval,
+    };
+    let booking_intend = admin_client
+        .service
+        .create_booking_intend(input)
+        .await
+        .expect("To create booking intend");
+    assert_eq!(booking_intend.selected_hosts.len(), 1);
+    assert_eq!(
+        booking_intend.selected_hosts[0].id,
+        hosts_with_calendars[1].0.id
+    );
+}
+
+#[tokio::test]
+async fn test_round_robin_priority_tiers_scheduling() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: Some(7),
+        metadata: None,
+        multi_person: Some(ServiceMultiPersonOptions::RoundRobinAlgorithm(
+            RoundRobinAlgorithm::EqualDistribution,
+        )),
+    };
+    let service = admin_client
+        .service
+        .create(input)
+        .await
+        .expect("To create service")
+        .service;
+    assert_eq!(service.distribution_window, 7);
+
+    let mut hosts_with_calendars = Vec::new();
+    for _ in 0..3 {
+        hosts_with_calendars.push(create_default_service_host(&admin_client, &service.id).await);
+    }
+    let tier_one_host = hosts_with_calendars[0].0.id.clone();
+
+    // Only the first host stays in the first tier
+    for (host, _) in hosts_with_calendars.iter().skip(1) {
+        let input = UpdateServiceUserInput {
+            service_id: service.id.clone(),
+            user_id: host.id.clone(),
+            availability: None,
+            buffer_after: None,
+            buffer_before: None,
+            closest_booking_time: None,
+            furthest_booking_time: None,
+            weight: Some(2),
+            priority: Some(2),
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
+        };
+        let service_user = admin_client
+            .service
+            .update_user(input)
+            .await
+            .expect("To update service user");
+        assert_eq!(service_user.weight, 2);
+        assert_eq!(service_user.priority, 2);
+    }
+
+    // Weights are bounded
+    let input = UpdateServiceUserInput {
+        service_id: service.id.clone(),
+        user_id: tier_one_host.clone(),
+        availability: None,
+        buffer_after: None,
+        buffer_before: None,
+        closest_booking_time: None,
+        furthest_booking_time: None,
+        weight: Some(0),
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: None,
+    };
+    assert!(admin_client.service.update_user(input).await.is_err());
+
+    let duration = 1000 * 60 * 30;
+    let interval = 1000 * 60 * 30;
+    let tomorrow = Utc::now() + Duration::days(1);
+    let next_week = tomorrow + Duration::days(7);
+    let input = GetServiceBookingSlotsInput {
+        duration,
+        interval,
+        service_id: service.id.clone(),
+        timezone: Some(chrono_tz::UTC),
+        end_date: format_datetime(&next_week),
+        start_date: format_datetime(&tomorrow),
+        host_user_ids: None,
+    };
+    let bookingslots = admin_client
+        .service
+        .bookingslots(input)
+        .await
+        .expect("To get bookingslots")
+        .dates;
+    let available_slot = bookingslots[0].slots[0].start;
+
+    let input = CreateBookingIntendInput {
+        service_id: service.id.clone(),
+        host_user_ids: None,
+        timestamp: available_slot,
+        duration,
+        interval,
+    };
+    let booking_intend = admin_client
+        .service
+        .create_booking_intend(input)
+        .await
+        .expect("To create booking intend");
+    assert_eq!(booking_intend.selected_hosts.len(), 1);
+    assert_eq!(booking_intend.selected_hosts[0].id, tier_one_host);
+
+    // Once the first tier host is busy, the second tier hosts are selected
+    let (host, busy_calendar) = &hosts_with_calendars[0];
+    let service_event = CreateEventInput {
+        external_parent_id: None,
+        external_id: None,
+        title: None,
+        description: None,
+        event_type: None,
+        location: None,
+        status: nittei_domain::CalendarEventStatus::Tentative,
+        all_day: None,
+        user_id: host.id.clone(),
+        busy: Some(true),
+        calendar_id: busy_calendar.id.clone(),
+        duration,
+        metadata: None,
+        recurrence: None,
+        timezone: None,
+        exdates: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        reminders: Vec::new(),
+        service_id: Some(service.id.clone()),
+        group_id: None,
+        attendees: vec![],
+        start_time: available_slot,
+    };
+    admin_client
+        .event
+        .create(service_event)
+        .await
+        .expect("To create service event");
+
+    let input = CreateBookingIntendInput {
+        service_id: service.id.clone(),
+        host_user_ids: None,
+        timestamp: available_slot,
+        duration,
+        interval,
+    };
+    let booking_intend = admin_client
+        .service
+        .create_booking_intend(input)
+        .await
+        .expect("To create booking intend");
+    assert_eq!(booking_intend.selected_hosts.len(), 1);
+    assert_ne!(booking_intend.selected_hosts[0].id, tier_one_host);
+}
+
+#[tokio::test]
+async fn test_round_robin_scheduling_with_max_bookings_per_day() {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let input = CreateServiceInput {
+        cancellation_cutoff: None,
+        reschedule_cutoff: None,
+        distribution_window: None,
+        metadata: None,
+        multi_person: Some(ServiceMultiPersonOptions::RoundRobinAlgorithm(
+            RoundRobinAlgorithm::Availability,
+        )),
+    };
+    let service = admin_client
+        .service
+        .create(input)
+        .await
+        .expect("To create service")
+        .service;
+
+    let (limited_host, busy_calendar) =
+        create_default_service_host(&admin_client, &service.id).await;
+    let (other_host, _) = create_default_service_host(&admin_client, &service.id).await;
+
+    let input = UpdateServiceUserInput {
+        service_id: service.id.clone(),
+        user_id: limited_host.id.clone(),
+        availability: None,
+        buffer_after: None,
+        buffer_before: None,
+        closest_booking_time: None,
+        furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: Some(1),
+        max_bookings_per_week: None,
+    };
+    let service_user = admin_client
+        .service
+        .update_user(input)
+        .await
+        .expect("To update service user");
+    assert_eq!(service_user.max_bookings_per_day, Some(1));
+
+    // The maximum number of bookings should be positive
+    let input = UpdateServiceUserInput {
+        service_id: service.id.clone(),
+        user_id: other_host.id.clone(),
+        availability: None,
+        buffer_after: None,
+        buffer_before: None,
+        closest_booking_time: None,
+        furthest_booking_time: None,
+        weight: None,
+        priority: None,
+        max_bookings_per_day: None,
+        max_bookings_per_week: Some(0),
+    };
+    assert!(admin_client.service.update_user(input).await.is_err());
+
+    let duration = 1000 * 60 * 30;
+    let interval = 1000 * 60 * 30;
+    let tomorrow = Utc::now() + Duration::days(1);
+    let next_week = tomorrow + Duration::days(7);
+    let input = GetServiceBookingSlotsInput {
+        duration,
+        interval,
+        service_id: service.id.clone(),
+        timezone: Some(chrono_tz::UTC),
+        end_date: format_datetime(&next_week),
+        start_date: format_datetime(&tomorrow),
+        host_user_ids: None,
+    };
+    let bookingslots = admin_client
+        .service
+        .bookingslots(input.clone())
+        .await
+        .expect("To get bookingslots")
+        .dates;
+    let first_slot = &bookingslots[0].slots[0];
+    assert!(first_slot.user_ids.contains(&limited_host.id));
+    let available_slot = first_slot.start;
+
+    // Book the limited host once on that day
+    let service_event = CreateEventInput {
+        external_parent_id: None,
+        external_id: None,
+        title: None,
+        description: None,
+        event_type: None,
+        location: None,
+        status: nittei_domain::CalendarEventStatus::Tentative,
+        all_day: None,
+        user_id: limited_host.id.clone(),
+        busy: Some(true),
+        calendar_id: busy_calendar.id.clone(),
+        duration,
+        metadata: None,
+        recurrence: None,
+        timezone: None,
+        exdates: None,
+        recurring_event_id: None,
+        original_start_time: None,
+        reminders: Vec::new(),
+        service_id: Some(service.id.clone()),
+        group_id: None,
+        attendees: vec![],
+        start_time: available_slot,
+    };
+    admin_client
+        .event
+        .create(service_event)
+        .await
+        .expect("To create service event");
+
+    // The limited host is not bookable anymore on that day, but it is on the next one
+    let bookingslots = admin_client
+        .service
+        .bookingslots(input)
+        .await
+        .expect("To get bookingslots")
+        .dates;
+    assert!(!bookingslots[0].slots.is_empty());
+    assert!(
+        bookingslots[0]
+            .slots
+            .iter()
+            .all(|slot| slot.user_ids == vec![other_host.id.clone()])
+    );
+    assert!(bookingslots[1].slots[0].user_ids.contains(&limited_host.id));
+
+    // So the round robin assigns the other host for the rest of the day
+    let input = CreateBookingIntendInput {
+        service_id: service.id.clone(),
+        host_user_ids: None,
+        timestamp: bookingslots[0].slots.last().expect("A slot").start,
+        duration,
+        interval,
+    };
+    let booking_intend = admin_client
+        .service
+        .create_booking_intend(input)
+        .await
+        .expect("To create booking intend");
+    assert_eq!(booking_intend.selected_hosts.len(), 1);
+    assert_eq!(booking_intend.selected_hosts[0].id, other_host.id);
+}
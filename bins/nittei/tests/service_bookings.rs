@@ -0,0 +1,648 @@
+mod helpers;
+
+use chrono::{DateTime, Duration, Utc, Weekday};
+use helpers::{setup::spawn_app, utils::format_datetime};
+use nittei_domain::{
+    BusyCalendarProvider,
+    CalendarEventStatus,
+    ID,
+    ServiceMultiPersonOptions,
+    TimePlan,
+    scheduling::RoundRobinAlgorithm,
+};
+use nittei_sdk::{
+    APIErrorVariant,
+    AddBusyCalendar,
+    AddServiceUserInput,
+    Calendar,
+    CancelBookingInput,
+    ConfirmHoldInput,
+    CreateBookingInput,
+    CreateCalendarInput,
+    CreateHoldInput,
+    CreateScheduleInput,
+    CreateServiceInput,
+    CreateUserInput,
+    GetCalendarEventsInput,
+    GetServiceBookingSlotsInput,
+    NitteiSDK,
+    ReleaseHoldInput,
+    RescheduleBookingInput,
+    UpdateServiceInput,
+    UpdateServiceUserInput,
+    User,
+};
+
+const DURATION: i64 = 1000 * 60 * 30;
+const INTERVAL: i64 = 1000 * 60 * 30;
+
+#[cfg(test)]
+async fn create_service_host(admin_client: &NitteiSDK, service_id: &ID) -> (User, Calendar) {
+    let host = admin_client
+        .user
+        .create(CreateUserInput {
+            metadata: None,
+            external_id: None,
+            user_id: None,
+        })
+        .await
+        .expect("To create user")
+        .user;
+    let schedule = admin_client
+        .schedule
+        .create(CreateScheduleInput {
+            metadata: None,
+            rules: None,
+            timezone: chrono_tz::UTC,
+            user_id: host.id.clone(),
+        })
+        .await
+        .expect("To create schedule")
+        .schedule;
+    let busy_calendar = admin_client
+        .calendar
+        .create(CreateCalendarInput {
+            metadata: None,
+            timezone: chrono_tz::UTC,
+            name: None,
+            key: None,
+            user_id: host.id.clone(),
+            week_start: Weekday::Mon,
+        })
+        .await
+        .expect("To create calendar")
+        .calendar;
+
+    admin_client
+        .service
+        .add_user(AddServiceUserInput {
+            availability: Some(TimePlan::Schedule(schedule.id.clone())),
+            buffer_after: None,
+            buffer_before: None,
+            closest_booking_time: None,
+            furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
+            service_id: service_id.clone(),
+            user_id: host.id.clone(),
+        })
+        .await
+        .expect("To add host to service");
+    admin_client
+        .service
+        .add_busy_calendar(AddBusyCalendar {
+            user_id: host.id.clone(),
+            service_id: service_id.clone(),
+            calendar: BusyCalendarProvider::Nittei(busy_calendar.id.clone()),
+        })
+        .await
+        .expect("To add busy calendar to service user");
+
+    (host, busy_calendar)
+}
+
+#[cfg(test)]
+async fn setup_service(
+    multi_person: ServiceMultiPersonOptions,
+) -> (NitteiSDK, ID, User, Calendar, Vec<DateTime<Utc>>) {
+    let (app, sdk, address) = spawn_app().await;
+    let res = sdk
+        .account
+        .create(&app.config.create_account_secret_code)
+        .await
+        .expect("Expected to create account");
+    let admin_client = NitteiSDK::new(address, res.secret_api_key);
+
+    let service = admin_client
+        .service
+        .create(CreateServiceInput {
+            cancellation_cutoff: None,
+            reschedule_cutoff: None,
+            distribution_window: None,
+            metadata: None,
+            multi_person: Some(multi_person),
+        })
+        .await
+        .expect("To create service")
+        .service;
+    let (host, calendar) = create_service_host(&admin_client, &service.id).await;
+
+    let slots = get_first_date_slots(&admin_client, &service.id).await;
+
+    (admin_client, service.id, host, calendar, slots)
+}
+
+#[cfg(test)]
+fn booking_input(service_id: &ID, timestamp: DateTime<Utc>) -> CreateBookingInput {
+    CreateBookingInput {
+        service_id: service_id.clone(),
+        host_user_ids: None,
+        timestamp,
+        duration: DURATION,
+        interval: INTERVAL,
+        calendar_key: None,
+        title: Some("Booking".into()),
+        description: None,
+        event_type: None,
+        location: None,
+        metadata: None,
+    }
+}
+
+#[cfg(test)]
+fn hold_input(service_id: &ID, timestamp: DateTime<Utc>) -> CreateHoldInput {
+    CreateHoldInput {
+        service_id: service_id.clone(),
+        host_user_ids: None,
+        timestamp,
+        duration: DURATION,
+        interval: INTERVAL,
+        ttl_minutes: 10,
+    }
+}
+
+#[cfg(test)]
+fn reschedule_input(
+    service_id: &ID,
+    event_id: &ID,
+    timestamp: DateTime<Utc>,
+) -> RescheduleBookingInput {
+    RescheduleBookingInput {
+        service_id: service_id.clone(),
+        event_id: event_id.clone(),
+        timestamp,
+        interval: INTERVAL,
+    }
+}
+
+#[cfg(test)]
+async fn get_first_date_slots(admin_client: &NitteiSDK, service_id: &ID) -> Vec<DateTime<Utc>> {
+    let tomorrow = Utc::now() + Duration::days(1);
+    admin_client
+        .service
+        .bookingslots(GetServiceBookingSlotsInput {
+            duration: DURATION,
+            interval: INTERVAL,
+            service_id: service_id.clone(),
+            timezone: Some(chrono_tz::UTC),
+            end_date: format_datetime(&(tomorrow + Duration::days(7))),
+            start_date: format_datetime(&tomorrow),
+            host_user_ids: None,
+        })
+        .await
+        .expect("To get bookingslots")
+        .dates[0]
+        .slots
+        .iter()
+        .map(|slot| slot.start)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_create_service_booking() {
+    let (admin_client, service_id, host, calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+
+    let events = admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking")
+        .events;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].user_id, host.id);
+    assert_eq!(events[0].calendar_id, calendar.id);
+    assert_eq!(events[0].start_time, slots[0]);
+    assert_eq!(events[0].title, Some("Booking".into()));
+    assert!(events[0].busy);
+
+    // The slot isn't available anymore
+    assert!(
+        admin_client
+            .service
+            .create_booking(booking_input(&service_id, slots[0]))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_service_bookings_of_the_same_slot() {
+    let (admin_client, service_id, _host, calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+
+    let results = futures::future::join_all((0..5).map(|_| {
+        admin_client
+            .service
+            .create_booking(booking_input(&service_id, slots[0]))
+    }))
+    .await;
+
+    // Only one of the bookings succeeds, the others either see that the slot isn't available,
+    // or are rejected with a conflict when the slot has been taken in the meantime
+    assert_eq!(results.iter().filter(|res| res.is_ok()).count(), 1);
+    for res in results.iter().filter_map(|res| res.as_ref().err()) {
+        assert!(matches!(
+            res.variant,
+            APIErrorVariant::Conflict | APIErrorVariant::UnexpectedStatusCode
+        ));
+    }
+
+    let events = admin_client
+        .calendar
+        .get_events(GetCalendarEventsInput {
+            calendar_id: calendar.id.clone(),
+            start_time: slots[0],
+            end_time: slots[0] + Duration::milliseconds(DURATION),
+        })
+        .await
+        .expect("To get calendar events")
+        .events;
+    assert_eq!(events.len(), 1);
+}
+
+#[tokio::test]
+async fn test_create_service_booking_for_group() {
+    let (admin_client, service_id, host, _calendar, slots) =
+        setup_service(ServiceMultiPersonOptions::Group(2)).await;
+
+    // The events are only created once the group is full
+    let events = admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking")
+        .events;
+    assert!(events.is_empty());
+
+    let events = admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking")
+        .events;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].user_id, host.id);
+
+    assert!(
+        admin_client
+            .service
+            .create_booking(booking_input(&service_id, slots[0]))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_hold_and_confirm_service_slot() {
+    let (admin_client, service_id, host, calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+
+    let hold = admin_client
+        .service
+        .create_hold(hold_input(&service_id, slots[0]))
+        .await
+        .expect("To create hold")
+        .hold;
+    assert_eq!(hold.host_user_ids, vec![host.id.clone()]);
+    assert_eq!(hold.start_time, slots[0]);
+
+    // The held slot isn't available anymore
+    let available_slots = get_first_date_slots(&admin_client, &service_id).await;
+    assert!(!available_slots.contains(&slots[0]));
+    let Err(err) = admin_client
+        .service
+        .create_hold(hold_input(&service_id, slots[0]))
+        .await
+    else {
+        panic!("The slot is already held");
+    };
+    assert!(matches!(
+        err.variant,
+        APIErrorVariant::Conflict | APIErrorVariant::UnexpectedStatusCode
+    ));
+    assert!(
+        admin_client
+            .service
+            .create_booking(booking_input(&service_id, slots[0]))
+            .await
+            .is_err()
+    );
+
+    let confirm_input = || ConfirmHoldInput {
+        service_id: service_id.clone(),
+        hold_id: hold.id.clone(),
+        calendar_key: None,
+        title: Some("Booking".into()),
+        description: None,
+        event_type: None,
+        location: None,
+        metadata: None,
+    };
+    let events = admin_client
+        .service
+        .confirm_hold(confirm_input())
+        .await
+        .expect("To confirm hold")
+        .events;
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].user_id, host.id);
+    assert_eq!(events[0].calendar_id, calendar.id);
+    assert_eq!(events[0].start_time, slots[0]);
+
+    // The hold has been released by the booking
+    let Err(err) = admin_client.service.confirm_hold(confirm_input()).await else {
+        panic!("The hold has already been confirmed");
+    };
+    assert!(matches!(err.variant, APIErrorVariant::NotFound));
+}
+
+#[tokio::test]
+async fn test_release_service_hold() {
+    let (admin_client, service_id, _host, _calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+
+    let hold = admin_client
+        .service
+        .create_hold(hold_input(&service_id, slots[0]))
+        .await
+        .expect("To create hold")
+        .hold;
+    admin_client
+        .service
+        .release_hold(ReleaseHoldInput {
+            service_id: service_id.clone(),
+            hold_id: hold.id.clone(),
+        })
+        .await
+        .expect("To release hold");
+
+    // The slot is available again
+    let available_slots = get_first_date_slots(&admin_client, &service_id).await;
+    assert!(available_slots.contains(&slots[0]));
+    admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking");
+}
+
+#[tokio::test]
+async fn test_hold_with_buffers() {
+    let (admin_client, service_id, host, _calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+    admin_client
+        .service
+        .update_user(UpdateServiceUserInput {
+            service_id: service_id.clone(),
+            user_id: host.id.clone(),
+            availability: None,
+            buffer_after: Some(30),
+            buffer_before: Some(30),
+            closest_booking_time: None,
+            furthest_booking_time: None,
+            weight: None,
+            priority: None,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
+        })
+        .await
+        .expect("To update the host of the service");
+
+    admin_client
+        .service
+        .create_hold(hold_input(&service_id, slots[0]))
+        .await
+        .expect("To create hold");
+
+    // The next slot is in the buffer of the hold
+    let available_slots = get_first_date_slots(&admin_client, &service_id).await;
+    assert!(!available_slots.contains(&slots[1]));
+    assert!(
+        admin_client
+            .service
+            .create_booking(booking_input(&service_id, slots[1]))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_hold_group_service_slot() {
+    let (admin_client, service_id, _host, _calendar, slots) =
+        setup_service(ServiceMultiPersonOptions::Group(2)).await;
+
+    assert!(
+        admin_client
+            .service
+            .create_hold(hold_input(&service_id, slots[0]))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_cancel_service_booking() {
+    let (admin_client, service_id, _host, _calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+
+    let events = admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking")
+        .events;
+    let cancel_input = || CancelBookingInput {
+        service_id: service_id.clone(),
+        event_id: events[0].id.clone(),
+    };
+    let cancelled_events = admin_client
+        .service
+        .cancel_booking(cancel_input())
+        .await
+        .expect("To cancel booking")
+        .events;
+    assert_eq!(cancelled_events.len(), 1);
+    assert_eq!(cancelled_events[0].id, events[0].id);
+    assert_eq!(cancelled_events[0].status, CalendarEventStatus::Cancelled);
+
+    // The slot is available again
+    let available_slots = get_first_date_slots(&admin_client, &service_id).await;
+    assert!(available_slots.contains(&slots[0]));
+    admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking");
+
+    let Err(err) = admin_client.service.cancel_booking(cancel_input()).await else {
+        panic!("The booking has already been cancelled");
+    };
+    assert!(matches!(err.variant, APIErrorVariant::NotFound));
+}
+
+#[tokio::test]
+async fn test_service_booking_cutoffs() {
+    let (admin_client, service_id, _host, _calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+
+    let events = admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking")
+        .events;
+
+    // The slots of the first date are at most a few days away
+    admin_client
+        .service
+        .update(UpdateServiceInput {
+            service_id: service_id.clone(),
+            metadata: None,
+            multi_person: None,
+            cancellation_cutoff: Some(60 * 24 * 30),
+            reschedule_cutoff: Some(60 * 24 * 30),
+            distribution_window: None,
+        })
+        .await
+        .expect("To update service");
+
+    assert!(
+        admin_client
+            .service
+            .cancel_booking(CancelBookingInput {
+                service_id: service_id.clone(),
+                event_id: events[0].id.clone(),
+            })
+            .await
+            .is_err()
+    );
+    assert!(
+        admin_client
+            .service
+            .reschedule_booking(reschedule_input(&service_id, &events[0].id, slots[2]))
+            .await
+            .is_err()
+    );
+
+    admin_client
+        .service
+        .update(UpdateServiceInput {
+            service_id: service_id.clone(),
+            metadata: None,
+            multi_person: None,
+            cancellation_cutoff: Some(0),
+            reschedule_cutoff: Some(0),
+            distribution_window: None,
+        })
+        .await
+        .expect("To update service");
+    admin_client
+        .service
+        .cancel_booking(CancelBookingInput {
+            service_id: service_id.clone(),
+            event_id: events[0].id.clone(),
+        })
+        .await
+        .expect("To cancel booking");
+}
+
+#[tokio::test]
+async fn test_reschedule_service_booking() {
+    let (admin_client, service_id, host, calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+
+    let events = admin_client
+        .service
+        .create_booking(booking_input(&service_id, slots[0]))
+        .await
+        .expect("To create booking")
+        .events;
+
+    let res = admin_client
+        .service
+        .reschedule_booking(reschedule_input(&service_id, &events[0].id, slots[1]))
+        .await
+        .expect("To reschedule booking");
+    assert_eq!(res.cancelled_events.len(), 1);
+    assert_eq!(res.cancelled_events[0].id, events[0].id);
+    assert_eq!(
+        res.cancelled_events[0].status,
+        CalendarEventStatus::Cancelled
+    );
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].user_id, host.id);
+    assert_eq!(res.events[0].calendar_id, calendar.id);
+    assert_eq!(res.events[0].start_time, slots[1]);
+    assert_eq!(res.events[0].title, Some("Booking".into()));
+
+    let available_slots = get_first_date_slots(&admin_client, &service_id).await;
+    assert!(available_slots.contains(&slots[0]));
+    assert!(!available_slots.contains(&slots[1]));
+
+    // The previous booking can't be rescheduled anymore
+    let Err(err) = admin_client
+        .service
+        .reschedule_booking(reschedule_input(&service_id, &events[0].id, slots[3]))
+        .await
+    else {
+        panic!("The booking has already been rescheduled");
+    };
+    assert!(matches!(err.variant, APIErrorVariant::NotFound));
+}
+
+#[tokio::test]
+async fn test_reschedule_round_robin_service_booking_to_another_host() {
+    let (admin_client, service_id, host, _calendar, slots) = setup_service(
+        ServiceMultiPersonOptions::RoundRobinAlgorithm(RoundRobinAlgorithm::Availability),
+    )
+    .await;
+    let (other_host, other_calendar) = create_service_host(&admin_client, &service_id).await;
+
+    let events = admin_client
+        .service
+        .create_booking(CreateBookingInput {
+            host_user_ids: Some(vec![host.id.clone()]),
+            ..booking_input(&service_id, slots[0])
+        })
+        .await
+        .expect("To create booking")
+        .events;
+    // The host is no longer available at the new time
+    admin_client
+        .service
+        .create_booking(CreateBookingInput {
+            host_user_ids: Some(vec![host.id.clone()]),
+            ..booking_input(&service_id, slots[2])
+        })
+        .await
+        .expect("To create booking");
+
+    let res = admin_client
+        .service
+        .reschedule_booking(reschedule_input(&service_id, &events[0].id, slots[2]))
+        .await
+        .expect("To reschedule booking");
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].user_id, other_host.id);
+    assert_eq!(res.events[0].calendar_id, other_calendar.id);
+    assert_eq!(res.events[0].start_time, slots[2]);
+}
@@ -1,13 +1,28 @@
 import { IdempotentRequest, NitteiBaseClient } from './baseClient'
 import type {
   AccountSearchEventsRequestBody,
+  CalendarEventDTO,
+  GetOutboxMessagesAPIResponse,
+  GetWebhookDeliveriesAPIResponse,
+  ID,
+  OutboxMessageResponse,
+  OutboxMessageStatus,
+  RateLimit,
   SearchEventsAPIResponse,
+  SearchLanguage,
+  WebhookDeliveryResponse,
+  WebhookDeliveryStatus,
+  WebhookEventType,
 } from './gen_types'
 import type { AccountResponse } from './gen_types/AccountResponse'
 import type { AddAccountIntegrationRequestBody } from './gen_types/AddAccountIntegrationRequestBody'
 import type { CreateAccountRequestBody } from './gen_types/CreateAccountRequestBody'
 import type { CreateAccountResponseBody } from './gen_types/CreateAccountResponseBody'
-import { replaceEventStringsToDates } from './helpers/datesConverters'
+import {
+  replaceEventStringsToDates,
+  replaceOutboxMessageStringsToDates,
+  replaceWebhookDeliveryStringsToDates,
+} from './helpers/datesConverters'
 
 const ACCOUNT_SEARCH_EVENTS_ENDPOINT = '/account/events/search'
 
@@ -44,14 +59,54 @@ export class NitteiAccountClient extends NitteiBaseClient {
     return await this.setPublicSigningKey()
   }
 
+  /**
+   * Set the limits of the requests of the account and of each of its users
+   * The default limits of the server are used for the ones not set
+   * @param rateLimits.account - limit of the requests of the account (with its API key, and separately on its public routes)
+   * @param rateLimits.user - limit of the requests of each user of the account (with a JWT)
+   * @returns {@see AccountResponse} - updated account
+   */
+  public async setRateLimits(rateLimits: {
+    account?: RateLimit
+    user?: RateLimit
+  }) {
+    return await this.put<AccountResponse>('/account/rate-limits', rateLimits)
+  }
+
+  /**
+   * Set the language of the texts of the events, used by the text search
+   * The existing events are indexed again in this language
+   * @param searchLanguage - language of the texts (e.g. english), "simple" by default
+   * @returns {@see AccountResponse} - updated account
+   */
+  public async setSearchLanguage(searchLanguage: SearchLanguage) {
+    return await this.put<AccountResponse>('/account/search-language', {
+      searchLanguage,
+    })
+  }
+
   /**
    * Set the webhook for the account
    * @param url - url to set as webhook
+   * @param eventTypes - changes of the events to send to the webhook (unchanged if not provided)
    * @returns {@see AccountResponse} - updated account
    */
-  public async setWebhook(url: string) {
+  public async setWebhook(url: string, eventTypes?: WebhookEventType[]) {
     return await this.put<AccountResponse>('/account/webhook', {
       webhookUrl: url,
+      eventTypes,
+    })
+  }
+
+  /**
+   * Rotate the key used to sign the webhooks of the account
+   * The current key keeps being used to sign the webhooks during the grace period
+   * @param gracePeriodSecs - seconds during which the current key stays valid (default 24 hours)
+   * @returns {@see AccountResponse} - updated account
+   */
+  public async rotateWebhookKey(gracePeriodSecs?: number) {
+    return await this.post<AccountResponse>('/account/webhook/rotate-key', {
+      gracePeriodSecs,
     })
   }
 
@@ -98,4 +153,104 @@ export class NitteiAccountClient extends NitteiBaseClient {
 
     return res
   }
+
+  /**
+   * Iterate over all the events found by a search in the account, fetching them page by page (with the cursors of the pages)
+   * @param params - search parameters, check {@link AccountSearchEventsRequestBody} for more details, the limit being the size of the pages
+   * @returns - the events found, in the order of the sort
+   */
+  public async *searchAllEventsInAccount(
+    params: Omit<AccountSearchEventsRequestBody, 'cursor'>
+  ): AsyncGenerator<CalendarEventDTO> {
+    let cursor: string | undefined
+    do {
+      const res = await this.searchEventsInAccount({ ...params, cursor })
+      yield* res.events
+      cursor = res.nextCursor
+    } while (cursor)
+  }
+
+  /**
+   * Get the outbox messages of the account, oldest first
+   * The outbox contains the work done in the background after a change (e.g. generating the reminders of an event)
+   * @param params - optional status of the messages (e.g. deadLetter) and max number of messages (default 100)
+   * @returns - the outbox messages
+   */
+  public async getOutboxMessages(params?: {
+    status?: OutboxMessageStatus
+    limit?: number
+  }) {
+    const res = await this.get<GetOutboxMessagesAPIResponse>(
+      '/account/outbox',
+      params
+    )
+
+    for (const message of res.messages) {
+      replaceOutboxMessageStringsToDates(message)
+    }
+
+    return res
+  }
+
+  /**
+   * Replay an outbox message, e.g. a dead letter
+   * It is processed again as soon as possible
+   * @param messageId - id of the outbox message
+   * @returns - the replayed outbox message
+   */
+  public async replayOutboxMessage(messageId: ID) {
+    const res = await this.post<OutboxMessageResponse>(
+      `/account/outbox/${messageId}/replay`,
+      {}
+    )
+
+    replaceOutboxMessageStringsToDates(res.message)
+
+    return res
+  }
+
+  /**
+   * Get the webhook deliveries of the account, newest first, with their attempts
+   * Also returns the health of the webhook endpoint (e.g. if it is failing)
+   * @param params - optional status of the deliveries (e.g. failed) and max number of deliveries (default 100)
+   * @returns - the webhook deliveries
+   */
+  public async getWebhookDeliveries(params?: {
+    status?: WebhookDeliveryStatus
+    limit?: number
+  }) {
+    const res = await this.get<GetWebhookDeliveriesAPIResponse>(
+      '/account/webhook/deliveries',
+      params
+    )
+
+    if (res.endpoint) {
+      res.endpoint.lastAttemptAt = new Date(res.endpoint.lastAttemptAt)
+      res.endpoint.failingSince = res.endpoint.failingSince
+        ? new Date(res.endpoint.failingSince)
+        : res.endpoint.failingSince
+    }
+    for (const delivery of res.deliveries) {
+      replaceWebhookDeliveryStringsToDates(delivery)
+    }
+
+    return res
+  }
+
+  /**
+   * Redeliver a webhook, e.g. a failed one
+   * It is attempted again as soon as possible
+   * @param deliveryId - id of the webhook delivery
+   * @returns - the redelivered webhook delivery
+   */
+  public async redeliverWebhook(deliveryId: ID) {
+    const res = await this.post<WebhookDeliveryResponse>(
+      `/account/webhook/deliveries/${deliveryId}/redeliver`,
+      {}
+    )
+
+    replaceWebhookDeliveryStringsToDates(res.delivery)
+
+    return res
+  }
 }
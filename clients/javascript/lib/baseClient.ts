@@ -12,7 +12,9 @@ import {
   BadRequestError,
   ConflictError,
   NotFoundError,
+  PreconditionFailedError,
   sanitizeErrorData,
+  TooManyRequestsError,
   UnauthorizedError,
   UnprocessableEntityError,
 } from './helpers/errors'
@@ -110,6 +112,7 @@ export abstract class NitteiBaseClient {
    * @param path - path to the endpoint
    * @param data - data to send to the server
    * @param params - query parameters
+   * @param headers - additional headers to send to the server
    * @returns Axios response
    */
   private async callApi<T>({
@@ -117,15 +120,23 @@ export abstract class NitteiBaseClient {
     path,
     data,
     params,
+    headers,
   }: {
     method: 'GET' | 'POST' | 'PUT' | 'PATCH' | 'DELETE'
     path: string
     data?: unknown
     params?: Record<string, unknown>
+    headers?: Record<string, string>
   }): Promise<AxiosResponse<T>> {
     let res: AxiosResponse<T> | undefined
     try {
-      res = await this.axiosClient({ method, url: path, data, params })
+      res = await this.axiosClient({
+        method,
+        url: path,
+        data,
+        params,
+        headers,
+      })
     } catch (error) {
       // Technically this one shouldn't be triggered, because we are using validateStatus: () => true
       // We handle the errors ourselves in the `handleStatusCode` call below
@@ -199,14 +210,20 @@ export abstract class NitteiBaseClient {
    * @private
    * @param path - path to the endpoint
    * @param data - data to send to the server
+   * @param headers - additional headers to send to the server
    * @throws Error if the status code is 400 or higher
    * @returns response's data
    */
-  protected async put<T>(path: string, data: unknown): Promise<T> {
+  protected async put<T>(
+    path: string,
+    data: unknown,
+    headers?: Record<string, string>
+  ): Promise<T> {
     const res = await this.callApi<T>({
       method: 'PUT',
       path,
       data,
+      headers,
     })
 
     return res.data
@@ -217,31 +234,57 @@ export abstract class NitteiBaseClient {
    * @private
    * @param path - path to the endpoint
    * @param data - data to send to the server
+   * @param headers - additional headers to send to the server
    * @throws Error if the status code is 400 or higher
    * @returns response's data
    */
-  protected async patch<T>(path: string, data: unknown): Promise<T> {
+  protected async patch<T>(
+    path: string,
+    data: unknown,
+    headers?: Record<string, string>
+  ): Promise<T> {
     const res = await this.callApi<T>({
       method: 'PATCH',
       path,
       data,
+      headers,
     })
 
     return res.data
   }
 
+  /**
+   * Build the `If-Match` header for an update conditioned on the version of the resource
+   * @private
+   * @param expectedVersion - version of the resource, as returned by the API
+   * @returns headers to send to the server (none if no version is provided)
+   */
+  protected ifMatchHeaders(
+    expectedVersion?: number
+  ): Record<string, string> | undefined {
+    if (expectedVersion === undefined) {
+      return undefined
+    }
+    return { 'If-Match': `"${expectedVersion}"` }
+  }
+
   /**
    * Make a DELETE request to the API
    * Note: this one doesn't have a body
    * @private
    * @param path - path to the endpoint
+   * @param params - query parameters to send to the server
    * @throws Error if the status code is 400 or higher
    * @returns response's data
    */
-  protected async delete<T>(path: string): Promise<T> {
+  protected async delete<T>(
+    path: string,
+    params: Record<string, unknown> = {}
+  ): Promise<T> {
     const res = await this.callApi<T>({
       method: 'DELETE',
       path,
+      params,
     })
 
     return res.data
@@ -293,9 +336,19 @@ export abstract class NitteiBaseClient {
       if (res.status === 409) {
         throw new ConflictError(sanitizedErrorData)
       }
+      if (res.status === 412) {
+        throw new PreconditionFailedError(sanitizedErrorData)
+      }
       if (res.status === 422) {
         throw new UnprocessableEntityError(sanitizedErrorData)
       }
+      if (res.status === 429) {
+        const retryAfter = Number(res.headers['retry-after'])
+        throw new TooManyRequestsError(
+          sanitizedErrorData,
+          Number.isNaN(retryAfter) ? undefined : retryAfter
+        )
+      }
 
       throw new Error(
         `Request failed with status code ${res.status} (${sanitizedErrorData})`
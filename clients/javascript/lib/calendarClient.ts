@@ -2,19 +2,25 @@ import { NitteiBaseClient } from './baseClient'
 import type { Timespan } from './eventClient'
 import type { AddSyncCalendarPathParams } from './gen_types/AddSyncCalendarPathParams'
 import type { AddSyncCalendarRequestBody } from './gen_types/AddSyncCalendarRequestBody'
-import type { CalendarDTO } from './gen_types/CalendarDTO'
+import type { CalendarFeedResponse } from './gen_types/CalendarFeedResponse'
 import type { CalendarResponse } from './gen_types/CalendarResponse'
+import type { CreateCalendarFeedRequestBody } from './gen_types/CreateCalendarFeedRequestBody'
 import type { CreateCalendarRequestBody } from './gen_types/CreateCalendarRequestBody'
 import type { GetCalendarEventsAPIResponse } from './gen_types/GetCalendarEventsAPIResponse'
+import type { GetCalendarFeedsAPIResponse } from './gen_types/GetCalendarFeedsAPIResponse'
+import type { GetCalendarsByMetaAPIResponse } from './gen_types/GetCalendarsByMetaAPIResponse'
 import type { GetCalendarsByUserAPIResponse } from './gen_types/GetCalendarsByUserAPIResponse'
 import type { GoogleCalendarAccessRole } from './gen_types/GoogleCalendarAccessRole'
 import type { GoogleCalendarListEntry } from './gen_types/GoogleCalendarListEntry'
 import type { ID } from './gen_types/ID'
+import type { ImportCalendarIcalAPIResponse } from './gen_types/ImportCalendarIcalAPIResponse'
+import type { ImportCalendarIcalRequestBody } from './gen_types/ImportCalendarIcalRequestBody'
 import type { OutlookCalendar } from './gen_types/OutlookCalendar'
 import type { OutlookCalendarAccessRole } from './gen_types/OutlookCalendarAccessRole'
 import type { RemoveSyncCalendarPathParams } from './gen_types/RemoveSyncCalendarPathParams'
 import type { RemoveSyncCalendarRequestBody } from './gen_types/RemoveSyncCalendarRequestBody'
 import {
+  replaceCalendarFeedStringsToDates,
   replaceEventStringsToDates,
   replaceInstanceStringsToDates,
 } from './helpers/datesConverters'
@@ -79,7 +85,8 @@ export class NitteiCalendarClient extends NitteiBaseClient {
    * @param meta - metadata to search for
    * @param skip - number of calendars to skip
    * @param limit - number of calendars to return
-   * @returns CalendarResponse - found calendars
+   * @param cursor - cursor of the page to get (the nextCursor of the previous page)
+   * @returns CalendarResponse - found calendars (ordered by their ids), and the cursor of the next page (if any)
    */
   public async findByMeta(
     meta: {
@@ -87,11 +94,13 @@ export class NitteiCalendarClient extends NitteiBaseClient {
       value: string
     },
     skip: number,
-    limit: number
+    limit: number,
+    cursor?: string
   ) {
-    return await this.get<{ calendars: CalendarDTO[] }>('/calendar/meta', {
+    return await this.get<GetCalendarsByMetaAPIResponse>('/calendar/meta', {
       skip: skip,
       limit: limit,
+      cursor,
       key: meta.key,
       value: meta.value,
     })
@@ -141,16 +150,25 @@ export class NitteiCalendarClient extends NitteiBaseClient {
    * Update the calendar with the given id
    * @param calendarId - ID of the calendar to update
    * @param data - data to update the calendar with
+   * @param options.expectedVersion - only update the calendar if it is still at this version (otherwise a PreconditionFailedError is thrown)
    * @returns CalendarResponse - updated calendar
    */
-  public async update(calendarId: ID, data: UpdateCalendarRequest) {
-    return await this.put<CalendarResponse>(`/user/calendar/${calendarId}`, {
-      settings: {
-        timezone: data.timezone,
-        weekStart: data.weekStart,
+  public async update(
+    calendarId: ID,
+    data: UpdateCalendarRequest,
+    options?: { expectedVersion?: number }
+  ) {
+    return await this.put<CalendarResponse>(
+      `/user/calendar/${calendarId}`,
+      {
+        settings: {
+          timezone: data.timezone,
+          weekStart: data.weekStart,
+        },
+        metadata: data.metadata,
       },
-      metadata: data.metadata,
-    })
+      this.ifMatchHeaders(options?.expectedVersion)
+    )
   }
 
   /**
@@ -183,6 +201,85 @@ export class NitteiCalendarClient extends NitteiBaseClient {
     return res
   }
 
+  /**
+   * Import the events of iCalendar content (.ics) into a calendar
+   * Importing the same content again updates the previously imported events (matched by UID)
+   * @param userId - ID of the user owning the calendar
+   * @param calendarId - ID of the calendar to import the events into
+   * @param data - iCalendar content, and whether it is a dry run (nothing written)
+   * @returns ImportCalendarIcalAPIResponse - changes made (or which would be made) to the events
+   */
+  public async importIcal(
+    userId: ID,
+    calendarId: ID,
+    data: ImportCalendarIcalRequestBody
+  ): Promise<ImportCalendarIcalAPIResponse> {
+    const res = await this.post<ImportCalendarIcalAPIResponse>(
+      `/user/${userId}/calendar/${calendarId}/ical`,
+      data
+    )
+
+    for (const change of res.changes) {
+      if (change.recurrenceId) {
+        change.recurrenceId = new Date(change.recurrenceId)
+      }
+      replaceEventStringsToDates(change.event)
+    }
+
+    return res
+  }
+
+  /**
+   * Create a public iCalendar feed of a calendar, which calendar applications can subscribe to
+   * Anyone knowing the token of the feed can read it, until the feed is removed
+   * @param calendarId - ID of the calendar to create the feed for
+   * @param data - whether the events only show the user is busy (no titles, descriptions and locations)
+   * @returns CalendarFeedResponse - created feed, with its path
+   */
+  public async createFeed(
+    calendarId: ID,
+    data: CreateCalendarFeedRequestBody = {}
+  ): Promise<CalendarFeedResponse> {
+    const res = await this.post<CalendarFeedResponse>(
+      `/user/calendar/${calendarId}/ical/feeds`,
+      data
+    )
+    replaceCalendarFeedStringsToDates(res.feed)
+    return res
+  }
+
+  /**
+   * List the public iCalendar feeds of a calendar
+   * @param calendarId - ID of the calendar to list the feeds of
+   * @returns GetCalendarFeedsAPIResponse - feeds of the calendar
+   */
+  public async listFeeds(calendarId: ID): Promise<GetCalendarFeedsAPIResponse> {
+    const res = await this.get<GetCalendarFeedsAPIResponse>(
+      `/user/calendar/${calendarId}/ical/feeds`
+    )
+    for (const feed of res.feeds) {
+      replaceCalendarFeedStringsToDates(feed)
+    }
+    return res
+  }
+
+  /**
+   * Remove (revoke) a public iCalendar feed of a calendar
+   * @param calendarId - ID of the calendar of the feed
+   * @param feedId - ID of the feed to remove
+   * @returns CalendarFeedResponse - removed feed
+   */
+  public async removeFeed(
+    calendarId: ID,
+    feedId: ID
+  ): Promise<CalendarFeedResponse> {
+    const res = await this.delete<CalendarFeedResponse>(
+      `/user/calendar/${calendarId}/ical/feeds/${feedId}`
+    )
+    replaceCalendarFeedStringsToDates(res.feed)
+    return res
+  }
+
   /**
    * Enable automated sync of a calendar with an external calendar
    * @param input - data for syncing the calendar
@@ -288,10 +385,19 @@ export class NitteiCalendarUserClient extends NitteiBaseClient {
    * Update the calendar with the given id
    * @param calendarId - ID of the calendar to update
    * @param data - data to update the calendar with
+   * @param options.expectedVersion - only update the calendar if it is still at this version (otherwise a PreconditionFailedError is thrown)
    * @returns - updated calendar
    */
-  public async update(calendarId: ID, data: UpdateCalendarRequest) {
-    return await this.put<CalendarResponse>(`/calendar/${calendarId}`, data)
+  public async update(
+    calendarId: ID,
+    data: UpdateCalendarRequest,
+    options?: { expectedVersion?: number }
+  ) {
+    return await this.put<CalendarResponse>(
+      `/calendar/${calendarId}`,
+      data,
+      this.ifMatchHeaders(options?.expectedVersion)
+    )
   }
 
   /**
@@ -321,4 +427,55 @@ export class NitteiCalendarUserClient extends NitteiBaseClient {
 
     return res
   }
+
+  /**
+   * Create a public iCalendar feed of a calendar, which calendar applications can subscribe to
+   * Anyone knowing the token of the feed can read it, until the feed is removed
+   * @param calendarId - ID of the calendar to create the feed for
+   * @param data - whether the events only show the user is busy (no titles, descriptions and locations)
+   * @returns CalendarFeedResponse - created feed, with its path
+   */
+  public async createFeed(
+    calendarId: ID,
+    data: CreateCalendarFeedRequestBody = {}
+  ): Promise<CalendarFeedResponse> {
+    const res = await this.post<CalendarFeedResponse>(
+      `/calendar/${calendarId}/ical/feeds`,
+      data
+    )
+    replaceCalendarFeedStringsToDates(res.feed)
+    return res
+  }
+
+  /**
+   * List the public iCalendar feeds of a calendar
+   * @param calendarId - ID of the calendar to list the feeds of
+   * @returns GetCalendarFeedsAPIResponse - feeds of the calendar
+   */
+  public async listFeeds(calendarId: ID): Promise<GetCalendarFeedsAPIResponse> {
+    const res = await this.get<GetCalendarFeedsAPIResponse>(
+      `/calendar/${calendarId}/ical/feeds`
+    )
+    for (const feed of res.feeds) {
+      replaceCalendarFeedStringsToDates(feed)
+    }
+    return res
+  }
+
+  /**
+   * Remove (revoke) a public iCalendar feed of a calendar
+   * @param calendarId - ID of the calendar of the feed
+   * @param feedId - ID of the feed to remove
+   * @returns CalendarFeedResponse - removed feed
+   */
+  public async removeFeed(
+    calendarId: ID,
+    feedId: ID
+  ): Promise<CalendarFeedResponse> {
+    const res = await this.delete<CalendarFeedResponse>(
+      `/calendar/${calendarId}/ical/feeds/${feedId}`
+    )
+    replaceCalendarFeedStringsToDates(res.feed)
+    return res
+  }
 }
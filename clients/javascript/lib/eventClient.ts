@@ -4,10 +4,13 @@ import type {
   CreateBatchEventsRequestBody,
   DeleteManyEventsRequestBody,
   GetEventsByExternalIdAPIResponse,
+  GetEventsByMetaAPIResponse,
   GetEventsForUsersInTimeSpanAPIResponse,
   GetEventsForUsersInTimeSpanBody,
+  RecurringEventScope,
   SearchEventsAPIResponse,
   SearchEventsRequestBody,
+  UpdateEventAttendeeResponseRequestBody,
 } from './gen_types'
 import type { CalendarEventDTO } from './gen_types/CalendarEventDTO'
 import type { CalendarEventResponse } from './gen_types/CalendarEventResponse'
@@ -39,15 +42,18 @@ export class NitteiEventClient extends NitteiBaseClient {
    * Update an event
    * @param eventId - id of the event
    * @param data - data of the event
+   * @param options.expectedVersion - only update the event if it is still at this version (otherwise a PreconditionFailedError is thrown)
    * @returns - the updated event
    */
   public async update(
     eventId: ID,
-    data: UpdateEventRequestBody
+    data: UpdateEventRequestBody,
+    options?: { expectedVersion?: number }
   ): Promise<CalendarEventResponse> {
     const res = await this.patch<CalendarEventResponse>(
       `/user/events/${eventId}`,
-      data
+      data,
+      this.ifMatchHeaders(options?.expectedVersion)
     )
     replaceEventStringsToDates(res.event)
 
@@ -152,6 +158,22 @@ export class NitteiEventClient extends NitteiBaseClient {
     return res
   }
 
+  /**
+   * Iterate over all the events found by a search, fetching them page by page (with the cursors of the pages)
+   * @param options - options - see {@link SearchEventsRequestBody} for more details, the limit being the size of the pages
+   * @returns - the events found, in the order of the sort
+   */
+  public async *searchAllEvents(
+    options: Omit<SearchEventsRequestBody, 'cursor'>
+  ): AsyncGenerator<CalendarEventDTO> {
+    let cursor: string | undefined
+    do {
+      const res = await this.searchEvents({ ...options, cursor })
+      yield* res.events
+      cursor = res.nextCursor
+    } while (cursor)
+  }
+
   /**
    * Get events for users in a time range
    *
@@ -177,17 +199,27 @@ export class NitteiEventClient extends NitteiBaseClient {
     return res
   }
 
+  /**
+   * Find events by metadata, ordered by their ids
+   * @param meta - metadata to search for
+   * @param skip - number of events to skip
+   * @param limit - number of events to return
+   * @param cursor - cursor of the page to get (the nextCursor of the previous page)
+   * @returns - the events found, and the cursor of the next page (if any)
+   */
   public async findByMeta(
     meta: {
       key: string
       value: string
     },
     skip: number,
-    limit: number
-  ): Promise<{ events: CalendarEventDTO[] }> {
-    const res = await this.get<{ events: CalendarEventDTO[] }>('/events/meta', {
+    limit: number,
+    cursor?: string
+  ): Promise<GetEventsByMetaAPIResponse> {
+    const res = await this.get<GetEventsByMetaAPIResponse>('/events/meta', {
       skip,
       limit,
+      cursor,
       key: meta.key,
       value: meta.value,
     })
@@ -199,8 +231,29 @@ export class NitteiEventClient extends NitteiBaseClient {
     return res
   }
 
-  public async remove(eventId: ID) {
-    return await this.delete<CalendarEventResponse>(`/user/events/${eventId}`)
+  /**
+   * Remove an event
+   * For recurring events, a scope can be provided to only remove one occurrence,
+   * or one occurrence and the following ones
+   * @param eventId - id of the event to remove
+   * @param options.scope - scope of the deletion (default is all)
+   * @param options.occurrenceStartTime - start time of the targeted occurrence (required for this and thisAndFollowing)
+   * @returns the removed event (or the updated series when only some occurrences are removed)
+   */
+  public async remove(
+    eventId: ID,
+    options?: { scope: RecurringEventScope; occurrenceStartTime?: Date }
+  ) {
+    const res = await this.delete<CalendarEventResponse>(
+      `/user/events/${eventId}`,
+      {
+        scope: options?.scope,
+        occurrenceStartTime: options?.occurrenceStartTime?.toISOString(),
+      }
+    )
+    replaceEventStringsToDates(res.event)
+
+    return res
   }
 
   /**
@@ -243,15 +296,18 @@ export class NitteiEventUserClient extends NitteiBaseClient {
    * Update an event
    * @param eventId - id of the event
    * @param data - data of the event
+   * @param options.expectedVersion - only update the event if it is still at this version (otherwise a PreconditionFailedError is thrown)
    * @returns - the updated event
    */
   public async update(
     eventId: ID,
-    data: UpdateEventRequestBody
+    data: UpdateEventRequestBody,
+    options?: { expectedVersion?: number }
   ): Promise<CalendarEventResponse> {
     const res = await this.patch<CalendarEventResponse>(
       `/events/${eventId}`,
-      data
+      data,
+      this.ifMatchHeaders(options?.expectedVersion)
     )
 
     replaceEventStringsToDates(res.event)
@@ -297,8 +353,50 @@ export class NitteiEventUserClient extends NitteiBaseClient {
     return res
   }
 
-  public async remove(eventId: ID) {
-    return await this.delete<CalendarEventResponse>(`/events/${eventId}`)
+  /**
+   * Remove an event
+   * For recurring events, a scope can be provided to only remove one occurrence,
+   * or one occurrence and the following ones
+   * @param eventId - id of the event to remove
+   * @param options.scope - scope of the deletion (default is all)
+   * @param options.occurrenceStartTime - start time of the targeted occurrence (required for this and thisAndFollowing)
+   * @returns the removed event (or the updated series when only some occurrences are removed)
+   */
+  public async remove(
+    eventId: ID,
+    options?: { scope: RecurringEventScope; occurrenceStartTime?: Date }
+  ) {
+    const res = await this.delete<CalendarEventResponse>(
+      `/events/${eventId}`,
+      {
+        scope: options?.scope,
+        occurrenceStartTime: options?.occurrenceStartTime?.toISOString(),
+      }
+    )
+    replaceEventStringsToDates(res.event)
+
+    return res
+  }
+
+  /**
+   * Respond to an event as an attendee (RSVP)
+   * The user needs to be an attendee of the event
+   * @param eventId - id of the event
+   * @param data - response of the user
+   * @returns - the event, with the updated response
+   */
+  public async updateAttendeeResponse(
+    eventId: ID,
+    data: UpdateEventAttendeeResponseRequestBody
+  ): Promise<CalendarEventResponse> {
+    const res = await this.put<CalendarEventResponse>(
+      `/events/${eventId}/attendees/me`,
+      data
+    )
+
+    replaceEventStringsToDates(res.event)
+
+    return res
   }
 
   public async getInstances(
@@ -0,0 +1,172 @@
+import { NitteiBaseClient } from './baseClient'
+import type {
+  CreateEventGroupRequestBody,
+  EventGroupEventsResponse,
+  EventGroupResponse,
+  GetEventGroupsByParentIdAPIResponse,
+  ID,
+  UpdateEventGroupRequestBody,
+} from './gen_types'
+import { replaceEventStringsToDates } from './helpers/datesConverters'
+
+/**
+ * Client for the event groups' endpoints
+ * This is an admin client (usually backend)
+ */
+export class NitteiEventGroupClient extends NitteiBaseClient {
+  /**
+   * Create a new event group
+   * @param userId - id of the user
+   * @param data - data of the event group
+   * @returns - the created event group
+   */
+  public async create(
+    userId: ID,
+    data: CreateEventGroupRequestBody
+  ): Promise<EventGroupResponse> {
+    return await this.post<EventGroupResponse>(
+      `/user/${userId}/event_groups`,
+      data
+    )
+  }
+
+  /**
+   * Get an event group by its id
+   * @param groupId - id of the event group
+   * @returns - the event group
+   */
+  public async getById(groupId: ID): Promise<EventGroupResponse> {
+    return await this.get<EventGroupResponse>(`/user/event_groups/${groupId}`)
+  }
+
+  /**
+   * Get an event group by its external id
+   * @param externalId - external id of the event group
+   * @returns - the event group
+   */
+  public async getByExternalId(
+    externalId: string
+  ): Promise<EventGroupResponse> {
+    return await this.get<EventGroupResponse>(
+      `/user/event_groups/external_id/${externalId}`
+    )
+  }
+
+  /**
+   * Get the event groups sharing a parent id
+   * @param parentId - parent id of the event groups
+   * @returns - the event groups found
+   */
+  public async getByParentId(
+    parentId: string
+  ): Promise<GetEventGroupsByParentIdAPIResponse> {
+    return await this.get<GetEventGroupsByParentIdAPIResponse>(
+      `/user/event_groups/parent_id/${parentId}`
+    )
+  }
+
+  /**
+   * Update an event group
+   * @param groupId - id of the event group
+   * @param data - data of the event group
+   * @returns - the updated event group
+   */
+  public async update(
+    groupId: ID,
+    data: UpdateEventGroupRequestBody
+  ): Promise<EventGroupResponse> {
+    return await this.patch<EventGroupResponse>(
+      `/user/event_groups/${groupId}`,
+      data
+    )
+  }
+
+  /**
+   * Delete an event group
+   * The events of the group are kept, but detached from the group
+   * @param groupId - id of the event group
+   * @returns - the deleted event group
+   */
+  public async remove(groupId: ID): Promise<EventGroupResponse> {
+    return await this.delete<EventGroupResponse>(
+      `/user/event_groups/${groupId}`
+    )
+  }
+
+  /**
+   * Get the events of an event group
+   * @param groupId - id of the event group
+   * @returns - the events of the group
+   */
+  public async getEvents(groupId: ID): Promise<EventGroupEventsResponse> {
+    const res = await this.get<EventGroupEventsResponse>(
+      `/user/event_groups/${groupId}/events`
+    )
+
+    for (const event of res.events) {
+      replaceEventStringsToDates(event)
+    }
+
+    return res
+  }
+
+  /**
+   * Cancel all the events of an event group
+   * Either all events are cancelled or none are
+   * @param groupId - id of the event group
+   * @returns - the cancelled events
+   */
+  public async cancelEvents(groupId: ID): Promise<EventGroupEventsResponse> {
+    const res = await this.post<EventGroupEventsResponse>(
+      `/user/event_groups/${groupId}/cancel`,
+      {}
+    )
+
+    for (const event of res.events) {
+      replaceEventStringsToDates(event)
+    }
+
+    return res
+  }
+
+  /**
+   * Delete all the events of an event group
+   * The event group itself is kept
+   * @param groupId - id of the event group
+   * @returns - the deleted events
+   */
+  public async removeEvents(groupId: ID): Promise<EventGroupEventsResponse> {
+    const res = await this.delete<EventGroupEventsResponse>(
+      `/user/event_groups/${groupId}/events`
+    )
+
+    for (const event of res.events) {
+      replaceEventStringsToDates(event)
+    }
+
+    return res
+  }
+
+  /**
+   * Shift all the events of an event group by a number of minutes
+   * Either all events are shifted or none are
+   * @param groupId - id of the event group
+   * @param minutes - number of minutes to shift the events by (can be negative)
+   * @returns - the shifted events
+   */
+  public async shiftEvents(
+    groupId: ID,
+    minutes: number
+  ): Promise<EventGroupEventsResponse> {
+    const res = await this.post<EventGroupEventsResponse>(
+      `/user/event_groups/${groupId}/shift`,
+      { minutes }
+    )
+
+    for (const event of res.events) {
+      replaceEventStringsToDates(event)
+    }
+
+    return res
+  }
+}
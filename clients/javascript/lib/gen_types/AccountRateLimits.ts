@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RateLimit } from './RateLimit'
+
+/**
+ * Limits of the requests of an account, the default ones (`rate_limit` config) are used if not set
+ */
+export type AccountRateLimits = {
+  /**
+   * Limit of the requests of the account (with its API key, and separately on its public routes)
+   */
+  account?: RateLimit
+  /**
+   * Limit of the requests of each user of the account (with a JWT)
+   */
+  user?: RateLimit
+}
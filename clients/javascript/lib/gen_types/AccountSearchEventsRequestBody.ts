@@ -19,4 +19,9 @@ export type AccountSearchEventsRequestBody = {
    * Defaults to 200
    */
   limit?: number
+  /**
+   * Optional cursor of the page to get (the `nextCursor` of the previous page)
+   * It can only be used with the same sort as the previous page
+   */
+  cursor?: string
 }
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AttendeeQuery } from './AttendeeQuery'
 import type { DateTimeQuery } from './DateTimeQuery'
 import type { IDQuery } from './IDQuery'
 import type { RecurrenceQuery } from './RecurrenceQuery'
@@ -55,7 +56,7 @@ export type AccountSearchEventsRequestBodyFilter = {
   originalStartTime?: DateTimeQuery
   /**
    * Optional filter on the recurrence
-   * This allows to filter on the existence or not of a recurrence, or the existence of a recurrence at a specific date
+   * This allows to filter on the existence or not of a recurrence, the existence of a recurrence at a specific date, or its options
    */
   recurrence?: RecurrenceQuery
   /**
@@ -70,4 +71,14 @@ export type AccountSearchEventsRequestBodyFilter = {
    * Optional query on updated at - e.g. "lower than or equal", or "great than or equal" (UTC)
    */
   updatedAt?: DateTimeQuery
+  /**
+   * Optional query on the attendees of the event
+   * Events are returned if at least one attendee matches all the provided conditions
+   */
+  attendee?: AttendeeQuery
+  /**
+   * Optional text to search in the title, description and location, in the web search syntax
+   * (e.g. `"team meeting" or standup -cancelled`), using the search language of the account
+   */
+  text?: string
 }
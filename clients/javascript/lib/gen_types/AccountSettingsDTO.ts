@@ -1,5 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountRateLimits } from './AccountRateLimits'
 import type { AccountWebhookSettingsDTO } from './AccountWebhookSettingsDTO'
+import type { SearchLanguage } from './SearchLanguage'
 
 /**
  * Account settings
@@ -9,4 +11,13 @@ export type AccountSettingsDTO = {
    * Optional webhook settings
    */
   webhook: AccountWebhookSettingsDTO | null
+  /**
+   * Language of the texts of the events, used by the text search
+   */
+  searchLanguage: SearchLanguage
+  /**
+   * Limits of the requests of the account and of its users, the default ones are used if not set
+   * The limits are applied by each instance of the application, not across all the instances
+   */
+  rateLimits: AccountRateLimits
 }
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookEventType } from './WebhookEventType'
 
 /**
  * Account webhook settings
@@ -12,4 +13,13 @@ export type AccountWebhookSettingsDTO = {
    * Webhook key
    */
   key: string
+  /**
+   * Changes of events sent to the webhook
+   */
+  eventTypes: Array<WebhookEventType>
+  /**
+   * Expiration of the key replaced by the last rotation
+   * Until then, the webhooks are also signed with it
+   */
+  previousKeyExpiresAt?: string
 }
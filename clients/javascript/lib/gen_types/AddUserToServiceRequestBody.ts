@@ -9,4 +9,22 @@ export type AddUserToServiceRequestBody = {
   bufferBefore?: number
   closestBookingTime?: number
   furthestBookingTime?: number
+  /**
+   * Relative share of the bookings assigned to the user by the equal distribution round robin
+   * (1 by default, ignored by the availability round robin)
+   */
+  weight?: number
+  /**
+   * Priority tier of the user in a round robin service, the users of a tier are only assigned
+   * a booking when no user of a higher tier is available (1 by default, being the highest one)
+   */
+  priority?: number
+  /**
+   * Maximum number of bookings of the user per day, in the timezone of its availability
+   */
+  maxBookingsPerDay?: number
+  /**
+   * Maximum number of bookings of the user per week, in the timezone of its availability
+   */
+  maxBookingsPerWeek?: number
 }
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IDQuery } from './IDQuery'
+import type { StringQuery } from './StringQuery'
+
+/**
+ * Query parameters for searching on the attendees of an event
+ * An event matches if at least one of its attendees matches all the provided conditions
+ */
+export type AttendeeQuery = {
+  /**
+   * Optional query on the UUID of the internal user
+   */
+  userId?: IDQuery
+  /**
+   * Optional query on the email of the external attendee
+   */
+  email?: StringQuery
+  /**
+   * Optional query on the response status of the attendee
+   * e.g. "accepted", "declined", "tentative", "needsAction"
+   */
+  responseStatus?: StringQuery
+}
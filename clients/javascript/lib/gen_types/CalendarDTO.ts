@@ -32,4 +32,9 @@ export type CalendarDTO = {
    * Metadata (e.g. {"key": "value"})
    */
   metadata?: JsonValue
+  /**
+   * Version of the calendar, incremented on each update
+   * Can be sent back in the `If-Match` header to only update the calendar if it hasn't changed in the meantime
+   */
+  version: number
 }
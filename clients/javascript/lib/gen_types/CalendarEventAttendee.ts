@@ -0,0 +1,29 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventAttendeeResponseStatus } from './CalendarEventAttendeeResponseStatus'
+import type { CalendarEventAttendeeRole } from './CalendarEventAttendeeRole'
+import type { ID } from './ID'
+
+/**
+ * Attendee of a calendar event
+ * An attendee is either an internal user (`userId`) or an external person (`email`)
+ */
+export type CalendarEventAttendee = {
+  /**
+   * UUID of the internal user
+   * Mutually exclusive with `email`
+   */
+  userId?: ID
+  /**
+   * Email of the external attendee
+   * Mutually exclusive with `userId`
+   */
+  email?: string
+  /**
+   * Role of the attendee, default is required
+   */
+  role?: CalendarEventAttendeeRole
+  /**
+   * Response of the attendee, default is needsAction
+   */
+  responseStatus?: CalendarEventAttendeeResponseStatus
+}
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response of an attendee to a calendar event (RSVP)
+ */
+export type CalendarEventAttendeeResponseStatus =
+  | 'needsAction'
+  | 'accepted'
+  | 'declined'
+  | 'tentative'
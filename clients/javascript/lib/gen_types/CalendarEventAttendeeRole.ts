@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Role of an attendee in a calendar event
+ */
+export type CalendarEventAttendeeRole =
+  | 'required'
+  | 'optional'
+  | 'chair'
+  | 'nonParticipant'
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventAttendee } from './CalendarEventAttendee'
 import type { CalendarEventReminder } from './CalendarEventReminder'
 import type { CalendarEventStatus } from './CalendarEventStatus'
 import type { ID } from './ID'
@@ -81,6 +82,11 @@ export type CalendarEventDTO = {
    * Recurrence rule
    */
   recurrence?: RRuleOptions
+  /**
+   * Optional timezone of the event (e.g. "Europe/Paris")
+   * The recurrence is expanded in this timezone, or in the one of the calendar if not set
+   */
+  timezone?: string
   /**
    * Optional recurring until date
    * This is the date until which the event will recur
@@ -103,6 +109,10 @@ export type CalendarEventDTO = {
    * Default is None
    */
   originalStartTime?: Date
+  /**
+   * Optional UUID of the event group this event is part of
+   */
+  groupId?: ID
   /**
    * UUID of the calendar
    */
@@ -115,8 +125,17 @@ export type CalendarEventDTO = {
    * List of reminders
    */
   reminders: Array<CalendarEventReminder>
+  /**
+   * List of attendees, with their response status
+   */
+  attendees: Array<CalendarEventAttendee>
   /**
    * Metadata (e.g. {"key": "value"})
    */
   metadata?: JsonValue
+  /**
+   * Version of the event, incremented on each update
+   * Can be sent back in the `If-Match` header to only update the event if it hasn't changed in the meantime
+   */
+  version: number
 }
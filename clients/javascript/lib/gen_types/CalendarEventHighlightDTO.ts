@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+
+/**
+ * Fragments of the texts of an event matching the text searched
+ * The matching words are between `<b>` and `</b>`, and the texts are HTML escaped
+ */
+export type CalendarEventHighlightDTO = {
+  /**
+   * UUID of the event
+   */
+  eventId: ID
+  /**
+   * Fragment of the title, if it matches
+   */
+  title?: string
+  /**
+   * Fragments of the description, if it matches
+   */
+  description?: string
+  /**
+   * Fragment of the location, if it matches
+   */
+  location?: string
+}
@@ -14,3 +14,4 @@ export type CalendarEventSort =
   | 'updatedDesc'
   | 'eventUidAsc'
   | 'eventUidDesc'
+  | 'relevanceDesc'
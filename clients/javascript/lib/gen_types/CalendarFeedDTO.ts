@@ -0,0 +1,36 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+
+/**
+ * Public iCalendar feed of a calendar
+ */
+export type CalendarFeedDTO = {
+  /**
+   * UUID of the feed
+   */
+  id: ID
+  /**
+   * UUID of the calendar of the feed
+   */
+  calendarId: ID
+  /**
+   * UUID of the user that owns the calendar
+   */
+  userId: ID
+  /**
+   * Secret token of the feed, anyone knowing it can read the feed
+   */
+  token: string
+  /**
+   * Path of the feed (e.g. "/api/v1/ical/{token}.ics"), to subscribe to from calendar applications
+   */
+  path: string
+  /**
+   * If true, the events only show the user is busy (titles replaced by "Busy", no descriptions and locations)
+   */
+  maskEvents: boolean
+  /**
+   * Date of creation of the feed
+   */
+  created: Date
+}
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarFeedDTO } from './CalendarFeedDTO'
+
+/**
+ * Calendar feed object
+ */
+export type CalendarFeedResponse = {
+  /**
+   * Calendar feed
+   */
+  feed: CalendarFeedDTO
+}
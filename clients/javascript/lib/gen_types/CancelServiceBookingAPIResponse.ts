@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventDTO } from './CalendarEventDTO'
+
+export type CancelServiceBookingAPIResponse = {
+  /**
+   * Events of the hosts cancelled
+   */
+  events: Array<CalendarEventDTO>
+}
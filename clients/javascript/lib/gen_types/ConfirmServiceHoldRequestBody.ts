@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from './serde_json/JsonValue'
+
+export type ConfirmServiceHoldRequestBody = {
+  /**
+   * Key of the calendar of the hosts where the events are created
+   * By default, the events are created in the first busy calendar of the host for the service
+   */
+  calendarKey?: string
+  title?: string
+  description?: string
+  eventType?: string
+  location?: string
+  metadata?: JsonValue
+}
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for creating a public iCalendar feed of a calendar
+ */
+export type CreateCalendarFeedRequestBody = {
+  /**
+   * If true, the events only show the user is busy (titles replaced by "Busy", no descriptions and locations)
+   * Default is false
+   */
+  maskEvents?: boolean
+}
@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+
+/**
+ * Request body for creating an event group
+ */
+export type CreateEventGroupRequestBody = {
+  /**
+   * UUID of the calendar where the group will be created
+   */
+  calendarId: ID
+  /**
+   * Optional parent ID
+   * This is useful for external applications that need to link groups of events to a wider data model (e.g. a project, an order, etc.)
+   */
+  parentId?: string
+  /**
+   * Optional external ID
+   * This is useful for external applications that need to link a group of events to their own data models
+   * It needs to be unique per account
+   */
+  externalId?: string
+}
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventAttendee } from './CalendarEventAttendee'
 import type { CalendarEventReminder } from './CalendarEventReminder'
 import type { CalendarEventStatus } from './CalendarEventStatus'
 import type { ID } from './ID'
@@ -73,6 +74,12 @@ export type CreateEventRequestBody = {
    * Optional recurrence rule
    */
   recurrence?: RRuleOptions
+  /**
+   * Optional timezone of the event (e.g. "Europe/Paris")
+   * The recurrence is expanded in this timezone, so that the occurrences stay at the same local time across DST changes
+   * Default is None (the timezone of the calendar is used)
+   */
+  timezone?: string
   /**
    * Optional list of exclusion dates for the recurrence rule
    */
@@ -93,11 +100,22 @@ export type CreateEventRequestBody = {
    * Optional list of reminders
    */
   reminders?: Array<CalendarEventReminder>
+  /**
+   * Optional list of attendees
+   * An attendee is either an internal user (`userId`) of the same account, or an external person (`email`)
+   */
+  attendees?: Array<CalendarEventAttendee>
   /**
    * Optional service UUID
    * This is automatically set when the event is created from a service
    */
   serviceId?: ID
+  /**
+   * Optional event group UUID
+   * This allows to attach the event to a group of events (e.g. a course of multiple sessions)
+   * The group needs to belong to the same user
+   */
+  groupId?: ID
   /**
    * Optional metadata (e.g. {"key": "value"})
    */
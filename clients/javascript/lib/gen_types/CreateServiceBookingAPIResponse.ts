@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventDTO } from './CalendarEventDTO'
+
+export type CreateServiceBookingAPIResponse = {
+  /**
+   * Events created for the hosts
+   * For group services, they are only created by the booking filling the slot
+   */
+  events: Array<CalendarEventDTO>
+}
@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+
+export type CreateServiceHoldRequestBody = {
+  /**
+   * Hosts to hold, they are assigned by the service when not provided
+   */
+  hostUserIds?: Array<ID>
+  /**
+   * Start of the slot to hold
+   */
+  timestamp: string
+  duration: number
+  interval: number
+  /**
+   * Number of minutes before the hold expires (between 1 and 60)
+   */
+  ttlMinutes: number
+}
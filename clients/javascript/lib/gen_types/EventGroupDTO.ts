@@ -0,0 +1,32 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+
+/**
+ * Event group object
+ * A group of calendar events, e.g. a course of multiple sessions
+ */
+export type EventGroupDTO = {
+  /**
+   * UUID of the event group
+   */
+  id: ID
+  /**
+   * UUID of the calendar
+   */
+  calendarId: ID
+  /**
+   * UUID of the user
+   */
+  userId: ID
+  /**
+   * Optional parent ID
+   * This is useful for external applications that need to link groups of events to a wider data model (e.g. a project, an order, etc.)
+   */
+  parentId?: string
+  /**
+   * Optional external ID
+   * This is useful for external applications that need to link a group of events to their own data models
+   * It is unique per account
+   */
+  externalId?: string
+}
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventDTO } from './CalendarEventDTO'
+
+/**
+ * Response object containing the events of an event group
+ */
+export type EventGroupEventsResponse = {
+  /**
+   * Calendar events of the group
+   */
+  events: Array<CalendarEventDTO>
+}
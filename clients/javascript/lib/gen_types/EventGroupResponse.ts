@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventGroupDTO } from './EventGroupDTO'
+
+/**
+ * Event group response object
+ */
+export type EventGroupResponse = {
+  /**
+   * Event group retrieved
+   */
+  eventGroup: EventGroupDTO
+}
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventDTO } from './CalendarEventDTO'
+import type { ID } from './ID'
+import type { WebhookEventType } from './WebhookEventType'
+
+/**
+ * Payload sent to the account webhook when an event is created, updated, cancelled or deleted
+ */
+export type EventWebhookPayload = {
+  /**
+   * Unique ID of the webhook, the same when the delivery is retried
+   */
+  id: ID
+  /**
+   * Type of the change
+   */
+  eventType: WebhookEventType
+  /**
+   * When the change happened
+   */
+  created: string
+  /**
+   * Calendar event after the change (before the deletion, for deleted events)
+   */
+  event: CalendarEventDTO
+}
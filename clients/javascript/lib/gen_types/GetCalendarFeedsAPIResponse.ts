@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarFeedDTO } from './CalendarFeedDTO'
+
+/**
+ * API response for listing the public iCalendar feeds of a calendar
+ */
+export type GetCalendarFeedsAPIResponse = {
+  /**
+   * Feeds of the calendar
+   */
+  feeds: Array<CalendarFeedDTO>
+}
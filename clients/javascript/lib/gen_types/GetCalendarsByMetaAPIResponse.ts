@@ -1,4 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { CalendarDTO } from './CalendarDTO'
 
-export type GetCalendarsByMetaAPIResponse = { calendars: Array<CalendarDTO> }
+export type GetCalendarsByMetaAPIResponse = {
+  calendars: Array<CalendarDTO>
+  /**
+   * Cursor of the next page, if there are more calendars than the limit
+   */
+  nextCursor?: string
+}
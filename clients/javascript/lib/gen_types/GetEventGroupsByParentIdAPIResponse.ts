@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EventGroupDTO } from './EventGroupDTO'
+
+/**
+ * API response for getting event groups by parent id
+ */
+export type GetEventGroupsByParentIdAPIResponse = {
+  /**
+   * List of event groups retrieved
+   */
+  eventGroups: Array<EventGroupDTO>
+}
@@ -9,4 +9,8 @@ export type GetEventsByMetaAPIResponse = {
    * List of calendar events retrieved
    */
   events: Array<CalendarEventDTO>
+  /**
+   * Cursor of the next page, if there are more events than the limit
+   */
+  nextCursor?: string
 }
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OutboxMessageDTO } from './OutboxMessageDTO'
+
+/**
+ * API response for getting the outbox messages of an account
+ */
+export type GetOutboxMessagesAPIResponse = {
+  /**
+   * Outbox messages, oldest first
+   */
+  messages: Array<OutboxMessageDTO>
+}
@@ -1,4 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ServiceDTO } from './ServiceDTO'
 
-export type GetServicesByMetaAPIResponse = { services: Array<ServiceDTO> }
+export type GetServicesByMetaAPIResponse = {
+  services: Array<ServiceDTO>
+  /**
+   * Cursor of the next page, if there are more services than the limit
+   */
+  nextCursor?: string
+}
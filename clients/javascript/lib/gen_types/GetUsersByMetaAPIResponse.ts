@@ -9,4 +9,8 @@ export type GetUsersByMetaAPIResponse = {
    * List of users matching the metadata query
    */
   users: Array<UserDTO>
+  /**
+   * Cursor of the next page, if there are more users than the limit
+   */
+  nextCursor?: string
 }
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookDeliveryDTO } from './WebhookDeliveryDTO'
+import type { WebhookEndpointStatusDTO } from './WebhookEndpointStatusDTO'
+
+/**
+ * API response for getting the webhook deliveries of an account
+ */
+export type GetWebhookDeliveriesAPIResponse = {
+  /**
+   * Health of the current webhook endpoint of the account
+   * Not set if the account has no webhook, or if nothing has been sent to it yet
+   */
+  endpoint?: WebhookEndpointStatusDTO
+  /**
+   * Webhook deliveries, newest first
+   */
+  deliveries: Array<WebhookDeliveryDTO>
+}
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Change made (or which would be made) to an event by the import
+ */
+export type IcalImportAction = 'created' | 'updated' | 'unchanged'
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventDTO } from './CalendarEventDTO'
+import type { IcalImportAction } from './IcalImportAction'
+
+/**
+ * Event of the iCalendar content, with the change made to it
+ */
+export type IcalImportChange = {
+  action: IcalImportAction
+  /**
+   * UID of the event in the iCalendar content (stored as the `externalId` of the event)
+   */
+  uid: string
+  /**
+   * Original start time of the occurrence (RECURRENCE-ID), if this is a modified occurrence of a recurring event
+   */
+  recurrenceId?: Date
+  /**
+   * Fields changed by the import (only for updated events)
+   */
+  changedFields: Array<string>
+  /**
+   * Event, as it is after the import
+   */
+  event: CalendarEventDTO
+}
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Event of the iCalendar content which can't be imported
+ */
+export type IcalImportSkippedEvent = {
+  /**
+   * UID of the event, if it has one
+   */
+  uid?: string
+  /**
+   * Why the event is skipped
+   */
+  reason: string
+}
@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { IcalImportChange } from './IcalImportChange'
+import type { IcalImportSkippedEvent } from './IcalImportSkippedEvent'
+
+/**
+ * API response for importing iCalendar content into a calendar
+ */
+export type ImportCalendarIcalAPIResponse = {
+  /**
+   * Whether this was a dry run (nothing has been written)
+   */
+  dryRun: boolean
+  /**
+   * Events of the iCalendar content, with the change made to each of them
+   */
+  changes: Array<IcalImportChange>
+  /**
+   * Events of the iCalendar content which can't be imported
+   */
+  skipped: Array<IcalImportSkippedEvent>
+}
@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for importing iCalendar content into a calendar
+ */
+export type ImportCalendarIcalRequestBody = {
+  /**
+   * iCalendar content (.ics)
+   */
+  ical: string
+  /**
+   * If true, nothing is written and the response only contains the changes which would be made
+   * Default is false
+   */
+  dryRun?: boolean
+}
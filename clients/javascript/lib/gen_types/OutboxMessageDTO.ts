@@ -0,0 +1,39 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+import type { OutboxMessageStatus } from './OutboxMessageStatus'
+import type { OutboxTask } from './OutboxTask'
+
+/**
+ * Outbox message object
+ * Work of a subscriber (e.g. generating the reminders of an event), processed in the background
+ */
+export type OutboxMessageDTO = {
+  /**
+   * UUID of the message
+   */
+  id: ID
+  /**
+   * Work to do
+   */
+  task: OutboxTask
+  /**
+   * Status of the message
+   */
+  status: OutboxMessageStatus
+  /**
+   * Number of times the message has been picked up for processing
+   */
+  attempts: number
+  /**
+   * When the message will be processed (again)
+   */
+  nextAttemptAt: Date
+  /**
+   * Error of the last failed attempt
+   */
+  lastError?: string
+  /**
+   * Creation date
+   */
+  created: Date
+}
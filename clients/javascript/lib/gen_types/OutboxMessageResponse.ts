@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { OutboxMessageDTO } from './OutboxMessageDTO'
+
+/**
+ * Outbox message response object
+ */
+export type OutboxMessageResponse = {
+  /**
+   * Outbox message retrieved
+   */
+  message: OutboxMessageDTO
+}
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Status of a message in the outbox
+ */
+export type OutboxMessageStatus = 'pending' | 'deadLetter'
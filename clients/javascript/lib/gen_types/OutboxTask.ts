@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+import type { WebhookEventType } from './WebhookEventType'
+import type { JsonValue } from './serde_json/JsonValue'
+
+/**
+ * Work of a subscriber of a use case
+ * It is recorded in the outbox in the same transaction as the write that triggered it
+ */
+export type OutboxTask =
+  | { type: 'createEventReminders'; eventId: ID }
+  | { type: 'syncEventReminders'; eventId: ID }
+  | { type: 'createSyncedEvents'; eventId: ID }
+  | { type: 'updateSyncedEvents'; eventId: ID }
+  | { type: 'refreshEventInstances'; eventId: ID }
+  | { type: 'sendWebhook'; eventType: WebhookEventType; payload: JsonValue }
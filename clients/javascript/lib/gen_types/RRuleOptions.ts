@@ -30,10 +30,12 @@ export type RRuleOptions = {
   /**
    * Select specific weekdays
    * E.g. `["Mon"]`, `["Mon", "Tue"]`, `["1Mon"]`, `["-1Sun"]`
+   * Without a position, every matching weekday of the period is selected (e.g. every Monday of the month)
    */
   byweekday?: Array<WeekDayRecurrence>
   /**
    * Select specific month days
+   * The negative days are counted from the end of the month (e.g. `-1` is the last day)
    */
   bymonthday?: Array<number>
   /**
@@ -49,6 +51,20 @@ export type RRuleOptions = {
    * Select specific week numbers
    */
   byweekno?: Array<number>
+  /**
+   * Select specific hours (0-23)
+   * Default: the hour of the start time
+   */
+  byhour?: Array<number>
+  /**
+   * Select specific minutes (0-59)
+   * Default: the minute of the start time
+   */
+  byminute?: Array<number>
+  /**
+   * Additional dates of occurrences (RDATE), on top of the ones generated by the rule
+   */
+  rdates?: Array<Date>
   /**
    * Specify the week start day
    * Default: CalendarSettings.week_start (Week start configured in the calendar settings)
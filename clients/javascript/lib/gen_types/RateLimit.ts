@@ -0,0 +1,16 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Limit of the requests of a client (token bucket)
+ * The client can send `burst` requests at once, and then `requests_per_second` requests per second
+ */
+export type RateLimit = {
+  /**
+   * Number of requests per second
+   */
+  requestsPerSecond: number
+  /**
+   * Number of requests that can be sent at once
+   */
+  burst: number
+}
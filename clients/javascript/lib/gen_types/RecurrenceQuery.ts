@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from './serde_json/JsonValue'
 
 /**
  * Query parameters for searching on a recurrence
@@ -6,3 +7,4 @@
 export type RecurrenceQuery =
   | { exists: boolean }
   | { existsAndRecurringAt: Date }
+  | { matches: JsonValue }
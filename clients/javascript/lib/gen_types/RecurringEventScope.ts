@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Scope of a change (update or delete) made on a recurring event
+ */
+export type RecurringEventScope = 'this' | 'thisAndFollowing' | 'all'
@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventDTO } from './CalendarEventDTO'
+
+export type RescheduleServiceBookingAPIResponse = {
+  /**
+   * Events of the previous slot, cancelled
+   */
+  cancelledEvents: Array<CalendarEventDTO>
+  /**
+   * Events created for the hosts of the new slot
+   */
+  events: Array<CalendarEventDTO>
+}
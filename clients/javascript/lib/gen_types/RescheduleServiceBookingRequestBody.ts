@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type RescheduleServiceBookingRequestBody = {
+  /**
+   * Start of the new slot, the booking keeps its duration
+   */
+  timestamp: string
+  interval: number
+}
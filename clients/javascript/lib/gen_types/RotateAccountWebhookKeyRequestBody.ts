@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for rotating the key of the webhook of an account
+ */
+export type RotateAccountWebhookKeyRequestBody = {
+  /**
+   * Optional number of seconds during which the current key stays valid (max 7 days)
+   * Default is 24 hours
+   */
+  gracePeriodSecs?: number
+}
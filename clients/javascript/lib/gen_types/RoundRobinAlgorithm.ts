@@ -3,5 +3,10 @@
 /**
  * Round robin algorithm to decide which member should be assigned a
  * `Service Event` when there are multiple members of a `Service`
+ *
+ * With both algorithms, the members of a lower priority tier are only
+ * assigned a `Service Event` when no member of a higher tier is available,
+ * and the members who reached their maximum number of bookings for the day
+ * or the week of the `Service Event` are not available
  */
 export type RoundRobinAlgorithm = 'availability' | 'equalDistribution'
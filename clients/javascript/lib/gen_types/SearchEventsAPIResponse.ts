@@ -1,5 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { CalendarEventDTO } from './CalendarEventDTO'
+import type { CalendarEventHighlightDTO } from './CalendarEventHighlightDTO'
 
 /**
  * API response for getting events by calendars
@@ -9,4 +10,13 @@ export type SearchEventsAPIResponse = {
    * List of calendar events retrieved
    */
   events: Array<CalendarEventDTO>
+  /**
+   * Fragments of the texts matching the text searched, for the events with a matching text
+   * Only returned when searching on a text
+   */
+  highlights?: Array<CalendarEventHighlightDTO>
+  /**
+   * Cursor of the next page, if there are more events than the limit
+   */
+  nextCursor?: string
 }
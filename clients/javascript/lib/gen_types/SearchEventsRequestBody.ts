@@ -19,4 +19,9 @@ export type SearchEventsRequestBody = {
    * Default is 200
    */
   limit?: number
+  /**
+   * Optional cursor of the page to get (the `nextCursor` of the previous page)
+   * It can only be used with the same sort as the previous page
+   */
+  cursor?: string
 }
@@ -14,4 +14,16 @@ export type ServiceDTO = {
    * Metadata (e.g. {"key": "value"})
    */
   metadata?: JsonValue
+  /**
+   * Minimum number of minutes before the start of a booking to cancel it
+   */
+  cancellationCutoff: number
+  /**
+   * Minimum number of minutes before the start of a booking to reschedule it
+   */
+  rescheduleCutoff: number
+  /**
+   * Number of days of upcoming bookings counted by the equal distribution round robin
+   */
+  distributionWindow: number
 }
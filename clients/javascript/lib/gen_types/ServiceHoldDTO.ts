@@ -0,0 +1,37 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+
+/**
+ * Slot of a service held for some of its hosts
+ */
+export type ServiceHoldDTO = {
+  /**
+   * UUID of the hold
+   */
+  id: ID
+  /**
+   * UUID of the service
+   */
+  serviceId: ID
+  /**
+   * UUIDs of the users for whom the slot is held
+   */
+  hostUserIds: Array<ID>
+  /**
+   * Start time of the held slot (UTC)
+   */
+  startTime: Date
+  /**
+   * End time of the held slot (UTC)
+   */
+  endTime: Date
+  /**
+   * Expiration time of the hold (UTC)
+   * The slot is released if the hold isn't confirmed before it
+   */
+  expiresAt: Date
+  /**
+   * Created timestamp (UTC)
+   */
+  created: Date
+}
@@ -37,4 +37,21 @@ export type ServiceResourceDTO = {
    * Optional furthest booking time in minutes
    */
   furthestBookingTime?: bigint
+  /**
+   * Relative share of the bookings assigned to the user by the equal distribution round robin
+   * (ignored by the availability round robin)
+   */
+  weight: number
+  /**
+   * Priority tier of the user in a round robin service (1 being the highest one)
+   */
+  priority: number
+  /**
+   * Optional maximum number of bookings of the user per day, in the timezone of its availability
+   */
+  maxBookingsPerDay?: number
+  /**
+   * Optional maximum number of bookings of the user per week, in the timezone of its availability
+   */
+  maxBookingsPerWeek?: number
 }
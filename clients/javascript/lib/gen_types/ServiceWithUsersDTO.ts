@@ -7,4 +7,7 @@ export type ServiceWithUsersDTO = {
   id: ID
   users: Array<ServiceResourceDTO>
   metadata?: JsonValue
+  cancellationCutoff: number
+  rescheduleCutoff: number
+  distributionWindow: number
 }
@@ -0,0 +1,18 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { RateLimit } from './RateLimit'
+
+/**
+ * Request body for setting the limits of the requests of an account
+ */
+export type SetAccountRateLimitsRequestBody = {
+  /**
+   * Limit of the requests of the account (with its API key, and separately on its public routes)
+   * The default one is used if not set
+   */
+  account?: RateLimit
+  /**
+   * Limit of the requests of each user of the account (with a JWT)
+   * The default one is used if not set
+   */
+  user?: RateLimit
+}
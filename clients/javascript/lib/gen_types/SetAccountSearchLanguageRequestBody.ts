@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchLanguage } from './SearchLanguage'
+
+/**
+ * Request body for setting the language of the texts of the events of an account
+ */
+export type SetAccountSearchLanguageRequestBody = {
+  /**
+   * Language used by the text search of the events
+   */
+  searchLanguage: SearchLanguage
+}
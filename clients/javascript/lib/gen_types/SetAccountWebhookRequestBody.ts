@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookEventType } from './WebhookEventType'
 
 /**
  * Request body for setting the webhook of an account
@@ -8,4 +9,9 @@ export type SetAccountWebhookRequestBody = {
    * Webhook URL
    */
   webhookUrl: string
+  /**
+   * Optional changes of events to send to the webhook (opt-in)
+   * If not provided, the current ones are kept
+   */
+  eventTypes?: Array<WebhookEventType>
 }
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for shifting all the events of an event group
+ */
+export type ShiftEventGroupEventsRequestBody = {
+  /**
+   * Number of minutes to shift the events by
+   * Can be negative to move the events earlier
+   */
+  minutes: number
+}
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventAttendeeResponseStatus } from './CalendarEventAttendeeResponseStatus'
+
+/**
+ * Request body for responding to an event as an attendee (RSVP)
+ */
+export type UpdateEventAttendeeResponseRequestBody = {
+  /**
+   * Response of the attendee to the event
+   */
+  responseStatus: CalendarEventAttendeeResponseStatus
+}
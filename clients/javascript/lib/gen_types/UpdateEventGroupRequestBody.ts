@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Request body for updating an event group
+ */
+export type UpdateEventGroupRequestBody = {
+  /**
+   * Optional parent ID
+   * Default is None (don't update)
+   *
+   * Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+   * TS: undefined = don't update, null = set to NULL, string = set to value
+   */
+  parentId?: string | null
+  /**
+   * Optional external ID
+   * Default is None (don't update)
+   *
+   * Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+   * TS: undefined = don't update, null = set to NULL, string = set to value
+   */
+  externalId?: string | null
+}
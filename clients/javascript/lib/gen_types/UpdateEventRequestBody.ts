@@ -1,8 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CalendarEventAttendee } from './CalendarEventAttendee'
 import type { CalendarEventReminder } from './CalendarEventReminder'
 import type { CalendarEventStatus } from './CalendarEventStatus'
 import type { ID } from './ID'
 import type { RRuleOptions } from './RRuleOptions'
+import type { RecurringEventScope } from './RecurringEventScope'
 import type { JsonValue } from './serde_json/JsonValue'
 
 /**
@@ -92,6 +94,14 @@ export type UpdateEventRequestBody = {
    * TS: undefined = don't update, null = set to NULL, object = set to value
    */
   recurrence?: RRuleOptions | null
+  /**
+   * Optional new timezone of the event (e.g. "Europe/Paris"), in which the recurrence is expanded
+   * Default is None (don't update)
+   *
+   * Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+   * TS: undefined = don't update, null = set to NULL (the timezone of the calendar is used), string = set to value
+   */
+  timezone?: string | null
   /**
    * Optional service UUID
    * Default is None (don't update)
@@ -100,6 +110,14 @@ export type UpdateEventRequestBody = {
    * TS: undefined = don't update, null = set to NULL, string = set to value
    */
   serviceId?: ID | null
+  /**
+   * Optional event group UUID
+   * Default is None (don't update)
+   *
+   * Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+   * TS: undefined = don't update, null = set to NULL, string = set to value
+   */
+  groupId?: ID | null
   /**
    * Optional list of exclusion dates for the recurrence rule
    * Default is None (don't update)
@@ -134,6 +152,15 @@ export type UpdateEventRequestBody = {
    * TS: undefined = don't update, array = set to value
    */
   reminders?: Array<CalendarEventReminder>
+  /**
+   * Optional list of attendees
+   * Default is None (don't update)
+   * The response status of the attendees already present is kept, unless another one than needsAction is provided
+   *
+   * Rust: None = don't update, Some(value) = set to value
+   * TS: undefined = don't update, array = set to value
+   */
+  attendees?: Array<CalendarEventAttendee>
   /**
    * Optional metadata (e.g. {"key": "value"})
    * Default is None (don't update)
@@ -152,4 +179,18 @@ export type UpdateEventRequestBody = {
    * Default is None (don't update)
    */
   updated?: Date
+  /**
+   * Optional scope of the update, only used for recurring events
+   * Default is None (the whole series is updated)
+   *
+   * - this: only the occurrence starting at `occurrenceStartTime` is updated (an exception is created)
+   * - thisAndFollowing: the series is split at `occurrenceStartTime`, and the new series is updated
+   * - all: the whole series is updated
+   */
+  scope?: RecurringEventScope
+  /**
+   * Start time of the targeted occurrence (UTC)
+   * Required when the scope is `this` or `thisAndFollowing`
+   */
+  occurrenceStartTime?: Date
 }
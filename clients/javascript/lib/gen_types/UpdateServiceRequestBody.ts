@@ -5,4 +5,17 @@ import type { JsonValue } from './serde_json/JsonValue'
 export type UpdateServiceRequestBody = {
   metadata?: JsonValue
   multiPerson: ServiceMultiPersonOptions | null
+  /**
+   * Minimum number of minutes before the start of a booking to cancel it
+   */
+  cancellationCutoff?: number
+  /**
+   * Minimum number of minutes before the start of a booking to reschedule it
+   */
+  rescheduleCutoff?: number
+  /**
+   * Number of days of upcoming bookings counted by the equal distribution round robin
+   * (14 by default)
+   */
+  distributionWindow?: number
 }
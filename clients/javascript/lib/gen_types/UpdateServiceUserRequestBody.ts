@@ -7,4 +7,8 @@ export type UpdateServiceUserRequestBody = {
   bufferBefore: bigint | null
   closestBookingTime: bigint | null
   furthestBookingTime: bigint | null
+  weight?: number
+  priority?: number
+  maxBookingsPerDay?: number
+  maxBookingsPerWeek?: number
 }
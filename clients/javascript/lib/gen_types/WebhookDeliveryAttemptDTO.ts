@@ -0,0 +1,27 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Attempt of a webhook delivery
+ */
+export type WebhookDeliveryAttemptDTO = {
+  /**
+   * URL of the endpoint at the time of the attempt
+   */
+  url: string
+  /**
+   * When the attempt was made
+   */
+  attemptedAt: Date
+  /**
+   * Status code of the response, not set if there was no response (e.g. timeout)
+   */
+  statusCode?: number
+  /**
+   * Time taken by the endpoint to respond (in milliseconds)
+   */
+  latencyMillis: bigint
+  /**
+   * Beginning of the body of the response, or the error if there was no response
+   */
+  responseExcerpt?: string
+}
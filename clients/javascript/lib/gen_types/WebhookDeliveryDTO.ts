@@ -0,0 +1,44 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ID } from './ID'
+import type { WebhookDeliveryAttemptDTO } from './WebhookDeliveryAttemptDTO'
+import type { WebhookDeliveryStatus } from './WebhookDeliveryStatus'
+import type { WebhookEventType } from './WebhookEventType'
+
+/**
+ * Webhook delivery object
+ * Webhook sent to the account (reminders or change of an event), with its attempts
+ */
+export type WebhookDeliveryDTO = {
+  /**
+   * UUID of the delivery
+   */
+  id: ID
+  /**
+   * Type of the change of the event, not set for the reminders
+   */
+  eventType?: WebhookEventType
+  /**
+   * Body of the webhook
+   */
+  payload: Record<string, unknown>
+  /**
+   * Status of the delivery
+   */
+  status: WebhookDeliveryStatus
+  /**
+   * When the delivery will be attempted (again), if pending
+   */
+  nextAttemptAt: Date
+  /**
+   * Failed attempts are retried until then
+   */
+  retryUntil: Date
+  /**
+   * Creation date
+   */
+  created: Date
+  /**
+   * Attempts of the delivery, oldest first
+   */
+  attempts: Array<WebhookDeliveryAttemptDTO>
+}
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { WebhookDeliveryDTO } from './WebhookDeliveryDTO'
+
+/**
+ * Webhook delivery response object
+ */
+export type WebhookDeliveryResponse = {
+  /**
+   * Webhook delivery retrieved
+   */
+  delivery: WebhookDeliveryDTO
+}
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Health of the webhook endpoint of the account
+ */
+export type WebhookEndpointStatusDTO = {
+  /**
+   * URL of the endpoint
+   */
+  url: string
+  /**
+   * Number of failed attempts since the last successful one
+   */
+  consecutiveFailures: number
+  /**
+   * When the endpoint has been marked as failing (too many consecutive failed attempts)
+   * Not set if the endpoint is healthy
+   */
+  failingSince?: Date
+  /**
+   * When the last attempt was made
+   */
+  lastAttemptAt: Date
+}
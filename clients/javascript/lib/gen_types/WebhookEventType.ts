@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Type of the changes of events that can be sent to the account webhook
+ * Accounts opt in to each of them in their webhook settings
+ */
+export type WebhookEventType =
+  | 'eventCreated'
+  | 'eventUpdated'
+  | 'eventCancelled'
+  | 'eventDeleted'
@@ -1,5 +1,6 @@
 export * from './AccountDTO'
 export * from './AccountEventRemindersDTO'
+export * from './AccountRateLimits'
 export * from './AccountRemindersDTO'
 export * from './AccountResponse'
 export * from './AccountSearchEventsRequestBody'
@@ -12,34 +13,55 @@ export * from './AddBusyCalendarRequestBody'
 export * from './AddSyncCalendarPathParams'
 export * from './AddSyncCalendarRequestBody'
 export * from './AddUserToServiceRequestBody'
+export * from './AttendeeQuery'
 export * from './BookingSlot'
 export * from './BusyCalendarProvider'
 export * from './CalendarDTO'
+export * from './CalendarEventAttendee'
+export * from './CalendarEventAttendeeResponseStatus'
+export * from './CalendarEventAttendeeRole'
 export * from './CalendarEventDTO'
+export * from './CalendarEventHighlightDTO'
 export * from './CalendarEventReminder'
 export * from './CalendarEventResponse'
 export * from './CalendarEventSort'
 export * from './CalendarEventStatus'
+export * from './CalendarFeedDTO'
+export * from './CalendarFeedResponse'
 export * from './CalendarResponse'
 export * from './CalendarSettingsDTO'
+export * from './CancelServiceBookingAPIResponse'
+export * from './ConfirmServiceHoldRequestBody'
 export * from './CreateAccountRequestBody'
 export * from './CreateAccountResponseBody'
 export * from './CreateBatchEventsAPIResponse'
 export * from './CreateBatchEventsRequestBody'
+export * from './CreateCalendarFeedRequestBody'
 export * from './CreateCalendarRequestBody'
+export * from './CreateEventGroupRequestBody'
 export * from './CreateEventRequestBody'
+export * from './CreateServiceBookingAPIResponse'
+export * from './CreateServiceBookingRequestBody'
 export * from './CreateServiceEventIntendRequestBody'
+export * from './CreateServiceHoldAPIResponse'
+export * from './CreateServiceHoldRequestBody'
 export * from './CreateServiceRequestBody'
 export * from './CreateUserRequestBody'
 export * from './DateTimeQuery'
 export * from './DateTimeQueryRange'
 export * from './DeleteManyEventsRequestBody'
 export * from './EventGroup'
+export * from './EventGroupDTO'
+export * from './EventGroupEventsResponse'
+export * from './EventGroupResponse'
 export * from './EventInstance'
+export * from './EventWebhookPayload'
 export * from './EventWithInstancesDTO'
 export * from './GetCalendarEventsAPIResponse'
+export * from './GetCalendarFeedsAPIResponse'
 export * from './GetCalendarsByMetaAPIResponse'
 export * from './GetCalendarsByUserAPIResponse'
+export * from './GetEventGroupsByParentIdAPIResponse'
 export * from './GetEventInstancesAPIResponse'
 export * from './GetEventsByCalendarsAPIResponse'
 export * from './GetEventsByCalendarsQueryParams'
@@ -48,6 +70,7 @@ export * from './GetEventsByMetaAPIResponse'
 export * from './GetEventsForUsersInTimeSpanAPIResponse'
 export * from './GetEventsForUsersInTimeSpanBody'
 export * from './GetGoogleCalendarsAPIResponse'
+export * from './GetOutboxMessagesAPIResponse'
 export * from './GetOutlookCalendarsAPIResponse'
 export * from './GetServiceBookingSlotsAPIResponse'
 export * from './GetServiceBookingSlotsQueryParams'
@@ -55,15 +78,25 @@ export * from './GetServicesByMetaAPIResponse'
 export * from './GetUserFreeBusyAPIResponse'
 export * from './GetUserFreeBusyQueryParams'
 export * from './GetUsersByMetaAPIResponse'
+export * from './GetWebhookDeliveriesAPIResponse'
 export * from './GoogleCalendarAccessRole'
 export * from './GoogleCalendarListEntry'
+export * from './IcalImportAction'
+export * from './IcalImportChange'
+export * from './IcalImportSkippedEvent'
 export * from './ID'
 export * from './IDQuery'
+export * from './ImportCalendarIcalAPIResponse'
+export * from './ImportCalendarIcalRequestBody'
 export * from './IntegrationProvider'
 export * from './MultipleFreeBusyAPIResponse'
 export * from './MultipleFreeBusyRequestBody'
 export * from './OAuthIntegrationRequestBody'
 export * from './OAuthOutlookRequestBody'
+export * from './OutboxMessageDTO'
+export * from './OutboxMessageResponse'
+export * from './OutboxMessageStatus'
+export * from './OutboxTask'
 export * from './OutlookCalendar'
 export * from './OutlookCalendarAccessRole'
 export * from './OutlookCalendarEventBody'
@@ -72,11 +105,16 @@ export * from './OutlookCalendarEventTime'
 export * from './OutlookCalendarOwner'
 export * from './OutlookOnlineMeetingProvider'
 export * from './PEMKey'
+export * from './RateLimit'
 export * from './RecurrenceQuery'
+export * from './RecurringEventScope'
 export * from './RemoveBusyCalendarPathParams'
 export * from './RemoveBusyCalendarRequestBody'
 export * from './RemoveSyncCalendarPathParams'
 export * from './RemoveSyncCalendarRequestBody'
+export * from './RescheduleServiceBookingAPIResponse'
+export * from './RescheduleServiceBookingRequestBody'
+export * from './RotateAccountWebhookKeyRequestBody'
 export * from './RoundRobinAlgorithm'
 export * from './RRuleFrequency'
 export * from './RRuleOptions'
@@ -87,9 +125,11 @@ export * from './ScheduleRuleVariant'
 export * from './SearchEventsAPIResponse'
 export * from './SearchEventsRequestBody'
 export * from './SearchEventsRequestBodyFilter'
+export * from './SearchLanguage'
 export * from './ServiceBookingSlotDTO'
 export * from './ServiceBookingSlotsDateDTO'
 export * from './ServiceDTO'
+export * from './ServiceHoldDTO'
 export * from './ServiceMultiPersonOptions'
 export * from './ServiceResourceDTO'
 export * from './ServiceResourceResponse'
@@ -97,13 +137,18 @@ export * from './ServiceResponse'
 export * from './ServiceWithUsersDTO'
 export * from './ServiceWithUsersResponse'
 export * from './SetAccountPubKeyRequestBody'
+export * from './SetAccountRateLimitsRequestBody'
+export * from './SetAccountSearchLanguageRequestBody'
 export * from './SetAccountWebhookRequestBody'
+export * from './ShiftEventGroupEventsRequestBody'
 export * from './StringQuery'
 export * from './Time'
 export * from './TimePlan'
 export * from './TimeSpan'
 export * from './UpdateCalendarRequestBody'
 export * from './UpdateCalendarSettings'
+export * from './UpdateEventAttendeeResponseRequestBody'
+export * from './UpdateEventGroupRequestBody'
 export * from './UpdateEventRequestBody'
 export * from './UpdateServiceRequestBody'
 export * from './UpdateServiceUserRequestBody'
@@ -111,4 +156,10 @@ export * from './UpdateUserRequestBody'
 export * from './UserDTO'
 export * from './UserIntegration'
 export * from './UserResponse'
+export * from './WebhookDeliveryAttemptDTO'
+export * from './WebhookDeliveryDTO'
+export * from './WebhookDeliveryResponse'
+export * from './WebhookDeliveryStatus'
+export * from './WebhookEndpointStatusDTO'
+export * from './WebhookEventType'
 export * from './WeekDayRecurrence'
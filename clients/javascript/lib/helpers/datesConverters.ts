@@ -1,5 +1,8 @@
 import type { CalendarEventDTO } from '../gen_types/CalendarEventDTO'
+import type { CalendarFeedDTO } from '../gen_types/CalendarFeedDTO'
 import type { EventInstance } from '../gen_types/EventInstance'
+import type { OutboxMessageDTO } from '../gen_types/OutboxMessageDTO'
+import type { WebhookDeliveryDTO } from '../gen_types/WebhookDeliveryDTO'
 
 /**
  * Change in place the dates inside an event to Date objects
@@ -24,6 +27,18 @@ export function replaceEventStringsToDates(event: CalendarEventDTO): void {
   event.exdates = event.exdates?.map(date => new Date(date))
 }
 
+/**
+ * Change in place the dates inside a calendar feed to Date objects
+ * @param feed - calendar feed to change in place
+ * @returns nothing
+ */
+export function replaceCalendarFeedStringsToDates(feed: CalendarFeedDTO): void {
+  if (!feed) {
+    return
+  }
+  feed.created = new Date(feed.created)
+}
+
 /**
  * Change in place the dates inside an instance to Date objects
  * @param instance - instance to change in place
@@ -36,3 +51,37 @@ export function replaceInstanceStringsToDates(instance: EventInstance): void {
   instance.startTime = new Date(instance.startTime)
   instance.endTime = new Date(instance.endTime)
 }
+
+/**
+ * Change in place the dates inside an outbox message to Date objects
+ * @param message - outbox message to change in place
+ * @returns nothing
+ */
+export function replaceOutboxMessageStringsToDates(
+  message: OutboxMessageDTO
+): void {
+  if (!message) {
+    return
+  }
+  message.nextAttemptAt = new Date(message.nextAttemptAt)
+  message.created = new Date(message.created)
+}
+
+/**
+ * Change in place the dates inside a webhook delivery (and its attempts) to Date objects
+ * @param delivery - webhook delivery to change in place
+ * @returns nothing
+ */
+export function replaceWebhookDeliveryStringsToDates(
+  delivery: WebhookDeliveryDTO
+): void {
+  if (!delivery) {
+    return
+  }
+  delivery.nextAttemptAt = new Date(delivery.nextAttemptAt)
+  delivery.retryUntil = new Date(delivery.retryUntil)
+  delivery.created = new Date(delivery.created)
+  for (const attempt of delivery.attempts) {
+    attempt.attemptedAt = new Date(attempt.attemptedAt)
+  }
+}
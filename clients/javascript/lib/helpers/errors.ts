@@ -55,6 +55,21 @@ export class ConflictError extends Error {
   }
 }
 
+/**
+ * Error thrown when a request is made to the server and the server responds with a 412 status code
+ * This happens when the resource has been modified since the version provided (e.g. `expectedVersion`)
+ */
+export class PreconditionFailedError extends Error {
+  /**
+   *
+   * @param apiMessage - error message from the server
+   */
+  constructor(public apiMessage: string) {
+    super('Precondition failed')
+    this.name = 'PreconditionFailedError'
+  }
+}
+
 /**
  * Error thrown when a request is made to the server and the server responds with a 422 status code
  * This happens when the server can't process the request because the entity is invalid (e.g. invalid timezone)
@@ -70,6 +85,25 @@ export class UnprocessableEntityError extends Error {
   }
 }
 
+/**
+ * Error thrown when a request is made to the server and the server responds with a 429 status code
+ * This happens when the account (or the user) exceeded its rate limit
+ */
+export class TooManyRequestsError extends Error {
+  /**
+   *
+   * @param apiMessage - error message from the server
+   * @param retryAfterSecs - number of seconds to wait before retrying (`Retry-After` header)
+   */
+  constructor(
+    public apiMessage: string,
+    public retryAfterSecs?: number
+  ) {
+    super('Too many requests')
+    this.name = 'TooManyRequestsError'
+  }
+}
+
 /**
  * Sanitizes error response data to prevent leaking sensitive information
  * while preserving useful error details for debugging
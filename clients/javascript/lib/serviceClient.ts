@@ -2,12 +2,20 @@ import { NitteiBaseClient } from './baseClient'
 import type { AddBusyCalendarPathParams } from './gen_types/AddBusyCalendarPathParams'
 import type { AddBusyCalendarRequestBody } from './gen_types/AddBusyCalendarRequestBody'
 import type { AddUserToServiceRequestBody } from './gen_types/AddUserToServiceRequestBody'
+import type { CancelServiceBookingAPIResponse } from './gen_types/CancelServiceBookingAPIResponse'
+import type { ConfirmServiceHoldRequestBody } from './gen_types/ConfirmServiceHoldRequestBody'
+import type { CreateServiceBookingAPIResponse } from './gen_types/CreateServiceBookingAPIResponse'
+import type { CreateServiceBookingRequestBody } from './gen_types/CreateServiceBookingRequestBody'
+import type { CreateServiceHoldAPIResponse } from './gen_types/CreateServiceHoldAPIResponse'
+import type { CreateServiceHoldRequestBody } from './gen_types/CreateServiceHoldRequestBody'
 import type { CreateServiceRequestBody } from './gen_types/CreateServiceRequestBody'
 import type { GetServiceBookingSlotsAPIResponse } from './gen_types/GetServiceBookingSlotsAPIResponse'
 import type { GetServiceBookingSlotsQueryParams } from './gen_types/GetServiceBookingSlotsQueryParams'
 import type { ID } from './gen_types/ID'
 import type { RemoveBusyCalendarPathParams } from './gen_types/RemoveBusyCalendarPathParams'
 import type { RemoveBusyCalendarRequestBody } from './gen_types/RemoveBusyCalendarRequestBody'
+import type { RescheduleServiceBookingAPIResponse } from './gen_types/RescheduleServiceBookingAPIResponse'
+import type { RescheduleServiceBookingRequestBody } from './gen_types/RescheduleServiceBookingRequestBody'
 import type { ServiceResponse } from './gen_types/ServiceResponse'
 import type { ServiceWithUsersDTO } from './gen_types/ServiceWithUsersDTO'
 import type { UpdateServiceRequestBody } from './gen_types/UpdateServiceRequestBody'
@@ -69,6 +77,80 @@ export class NitteiServiceClient extends NitteiBaseClient {
     )
   }
 
+  /**
+   * Book a slot of the service, the events of the hosts are created in the same transaction
+   * Throws a `ConflictError` if the slot has been booked in the meantime
+   */
+  public async createBooking(
+    serviceId: ID,
+    data: CreateServiceBookingRequestBody
+  ) {
+    return await this.post<CreateServiceBookingAPIResponse>(
+      `/service/${serviceId}/bookings`,
+      data
+    )
+  }
+
+  /**
+   * Cancel a booking of the service, identified by one of the events of its hosts
+   * The events are cancelled and the slot is available again
+   * Throws a `BadRequestError` if the cancellation cut-off of the service has passed
+   */
+  public async cancelBooking(serviceId: ID, eventId: ID) {
+    return await this.post<CancelServiceBookingAPIResponse>(
+      `/service/${serviceId}/bookings/${eventId}/cancel`,
+      {}
+    )
+  }
+
+  /**
+   * Move a booking of the service to another slot, its events are replaced in the same transaction
+   * The hosts of round robin services are assigned again if they aren't available at the new time
+   * Throws a `BadRequestError` if the reschedule cut-off of the service has passed,
+   * or a `ConflictError` if the slot has been booked in the meantime
+   */
+  public async rescheduleBooking(
+    serviceId: ID,
+    eventId: ID,
+    data: RescheduleServiceBookingRequestBody
+  ) {
+    return await this.post<RescheduleServiceBookingAPIResponse>(
+      `/service/${serviceId}/bookings/${eventId}/reschedule`,
+      data
+    )
+  }
+
+  /**
+   * Hold a slot of the service for `ttlMinutes`, e.g. during the checkout of the customer
+   * The held slot isn't available anymore until the hold is confirmed, released or expired
+   * Throws a `ConflictError` if the slot has been booked or held in the meantime
+   */
+  public async createHold(serviceId: ID, data: CreateServiceHoldRequestBody) {
+    return await this.post<CreateServiceHoldAPIResponse>(
+      `/service/${serviceId}/holds`,
+      data
+    )
+  }
+
+  /**
+   * Confirm a hold into a booking, the events of the hosts are created in the same transaction
+   * Throws a `ConflictError` if the hold has expired or has been released
+   */
+  public async confirmHold(
+    serviceId: ID,
+    holdId: ID,
+    data?: ConfirmServiceHoldRequestBody
+  ) {
+    return await this.post<CreateServiceBookingAPIResponse>(
+      `/service/${serviceId}/holds/${holdId}/confirm`,
+      data ?? {}
+    )
+  }
+
+  public async releaseHold(serviceId: ID, holdId: ID) {
+    return await this.delete<string>(`/service/${serviceId}/holds/${holdId}`)
+  }
+
   public async addBusyCalendar(
     input: AddBusyCalendarRequestBody & AddBusyCalendarPathParams
   ) {
@@ -4,12 +4,12 @@ import type { GetEventsByCalendarsAPIResponse } from './gen_types/GetEventsByCal
 import type { GetEventsByCalendarsQueryParams } from './gen_types/GetEventsByCalendarsQueryParams'
 import type { GetUserFreeBusyAPIResponse } from './gen_types/GetUserFreeBusyAPIResponse'
 import type { GetUserFreeBusyQueryParams } from './gen_types/GetUserFreeBusyQueryParams'
+import type { GetUsersByMetaAPIResponse } from './gen_types/GetUsersByMetaAPIResponse'
 import type { ID } from './gen_types/ID'
 import type { IntegrationProvider } from './gen_types/IntegrationProvider'
 import type { MultipleFreeBusyAPIResponse } from './gen_types/MultipleFreeBusyAPIResponse'
 import type { MultipleFreeBusyRequestBody } from './gen_types/MultipleFreeBusyRequestBody'
 import type { UpdateUserRequestBody } from './gen_types/UpdateUserRequestBody'
-import type { UserDTO } from './gen_types/UserDTO'
 import type { UserResponse } from './gen_types/UserResponse'
 import {
   replaceEventStringsToDates,
@@ -63,7 +63,8 @@ export class NitteiUserClient extends NitteiBaseClient {
    * @param meta - meta data to search by
    * @param skip - number of users to skip
    * @param limit - number of users to return
-   * @returns - list of found users
+   * @param cursor - cursor of the page to get (the nextCursor of the previous page)
+   * @returns - list of found users (ordered by their ids), and the cursor of the next page (if any)
    */
   public async findByMeta(
     meta: {
@@ -71,11 +72,13 @@ export class NitteiUserClient extends NitteiBaseClient {
       value: string
     },
     skip: number,
-    limit: number
+    limit: number,
+    cursor?: string
   ) {
-    return await this.get<UserDTO[]>('/user/meta', {
+    return await this.get<GetUsersByMetaAPIResponse>('/user/meta', {
       skip,
       limit,
+      cursor,
       key: meta.key,
       value: meta.value,
     })
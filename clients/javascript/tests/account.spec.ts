@@ -1,4 +1,9 @@
-import { type INitteiClient, NitteiClient } from '../lib'
+import {
+  type INitteiClient,
+  NitteiClient,
+  NotFoundError,
+  TooManyRequestsError,
+} from '../lib'
 import {
   CREATE_ACCOUNT_CODE,
   setupAccount,
@@ -82,6 +87,22 @@ describe('Account API', () => {
     ).rejects.toThrow()
   })
 
+  it('should reject the requests over the rate limits of the account', async () => {
+    const { client } = await setupAccount()
+    const res = await client.account.setRateLimits({
+      account: { requestsPerSecond: 1, burst: 1 },
+    })
+    expect(res.account.settings.rateLimits.account).toEqual({
+      requestsPerSecond: 1,
+      burst: 1,
+    })
+
+    await client.user.create()
+    await expect(() => client.user.create()).rejects.toThrow(
+      TooManyRequestsError
+    )
+  })
+
   describe('accountSearchEvents', () => {
     let adminClient: INitteiClient
     let userId: string
@@ -431,5 +452,81 @@ describe('Account API', () => {
         ])
       )
     })
+
+    it('should be able to search on the texts, with highlights', async () => {
+      const accountRes = await adminClient.account.setSearchLanguage('english')
+      expect(accountRes.account.settings.searchLanguage).toBe('english')
+
+      const eventRes = await adminClient.events.create(userId, {
+        calendarId,
+        duration: 1000,
+        startTime: new Date(30000),
+        title: 'Weekly meetings of the team',
+        location: 'Room 12',
+      })
+
+      const res = await adminClient.account.searchEventsInAccount({
+        filter: {
+          text: 'meeting',
+        },
+        sort: 'relevanceDesc',
+      })
+
+      expect(res.events.length).toBe(1)
+      expect(res.events[0].id).toBe(eventRes.event.id)
+      expect(res.highlights).toEqual([
+        {
+          eventId: eventRes.event.id,
+          title: 'Weekly <b>meetings</b> of the team',
+          description: null,
+          location: null,
+        },
+      ])
+    })
+
+    it('should be able to iterate over all the events of the account, page by page', async () => {
+      const all = await adminClient.account.searchEventsInAccount({
+        filter: {},
+      })
+      expect(all.nextCursor).toBeNull()
+
+      const firstPage = await adminClient.account.searchEventsInAccount({
+        filter: {},
+        limit: 2,
+      })
+      expect(firstPage.events.length).toBe(2)
+      expect(firstPage.nextCursor).toEqual(expect.any(String))
+
+      const eventIds = []
+      for await (const event of adminClient.account.searchAllEventsInAccount({
+        filter: {},
+        limit: 2,
+      })) {
+        eventIds.push(event.id)
+      }
+      expect(eventIds).toEqual(all.events.map((e) => e.id))
+    })
+  })
+
+  describe('Outbox', () => {
+    it('should list the outbox messages of the account', async () => {
+      const { client: adminClient } = await setupAccount()
+
+      const res = await adminClient.account.getOutboxMessages({
+        status: 'deadLetter',
+      })
+
+      expect(res.messages).toEqual([])
+    })
+
+    it('should not replay an unknown outbox message', async () => {
+      const { client: adminClient } = await setupAccount()
+
+      await expect(() =>
+        adminClient.account.replayOutboxMessage(
+          '00000000-0000-0000-0000-000000000000'
+        )
+      ).rejects.toThrow(NotFoundError)
+    })
   })
 })
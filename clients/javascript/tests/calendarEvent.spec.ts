@@ -7,6 +7,7 @@ import {
   type INitteiUserClient,
   NitteiClient,
   NotFoundError,
+  PreconditionFailedError,
 } from '../lib'
 import { setupAccount, setupUserClient } from './helpers/fixtures'
 
@@ -561,6 +562,82 @@ describe('CalendarEvent API', () => {
       expect(res3.event.recurringUntil).toBeNull()
     })
 
+    it('should update and remove occurrences of a recurring event with a scope', async () => {
+      const day = 1000 * 60 * 60 * 24
+      const startTime = new Date('2025-01-01T10:00:00.000Z')
+      const occurrence = (n: number) => new Date(startTime.getTime() + n * day)
+      const res = await adminClient.events.create(userId, {
+        calendarId,
+        duration: 1000 * 60 * 60,
+        startTime,
+        title: 'Daily',
+        recurrence: {
+          freq: 'daily',
+          interval: 1,
+          count: 10,
+        },
+      })
+      const eventId = res.event.id
+
+      // Only one occurrence
+      const exception = await adminClient.events.update(eventId, {
+        title: 'Only this one',
+        scope: 'this',
+        occurrenceStartTime: occurrence(2),
+      })
+      expect(exception.event.id).not.toBe(eventId)
+      expect(exception.event.recurringEventId).toBe(eventId)
+      expect(exception.event.originalStartTime).toEqual(occurrence(2))
+      expect(exception.event.startTime).toEqual(occurrence(2))
+
+      // This and the following occurrences
+      const newSeries = await adminClient.events.update(eventId, {
+        title: 'Following',
+        scope: 'thisAndFollowing',
+        occurrenceStartTime: occurrence(4),
+      })
+      expect(newSeries.event.id).not.toBe(eventId)
+      expect(newSeries.event.title).toBe('Following')
+      expect(newSeries.event.startTime).toEqual(occurrence(4))
+      expect(newSeries.event.recurrence?.count).toBe(6)
+
+      const original = await adminClient.events.getInstances(eventId, {
+        startTime,
+        endTime: occurrence(20),
+      })
+      expect(original.event.title).toBe('Daily')
+      expect(original.instances.length).toBe(3)
+
+      // The scope requires the start time of the occurrence
+      await expect(() =>
+        adminClient.events.update(eventId, {
+          title: 'Invalid',
+          scope: 'this',
+        })
+      ).rejects.toThrow()
+
+      // Remove one occurrence
+      const removed = await adminClient.events.remove(newSeries.event.id, {
+        scope: 'this',
+        occurrenceStartTime: occurrence(5),
+      })
+      expect(removed.event.exdates).toEqual([occurrence(5)])
+
+      // Remove this and the following occurrences
+      await adminClient.events.remove(newSeries.event.id, {
+        scope: 'thisAndFollowing',
+        occurrenceStartTime: occurrence(7),
+      })
+      const truncated = await adminClient.events.getInstances(
+        newSeries.event.id,
+        {
+          startTime,
+          endTime: occurrence(20),
+        }
+      )
+      expect(truncated.instances.length).toBe(2)
+    })
+
     it('should handle recurring event fields', async () => {
       const recurringEventId = crypto.randomUUID()
       const recurringEventId2 = crypto.randomUUID()
@@ -725,6 +802,36 @@ describe('CalendarEvent API', () => {
       expect(getRes2.events[0].eventType).toBe('block')
     })
 
+    it('should only update event when the expected version matches', async () => {
+      const res = await adminClient.events.create(userId, {
+        calendarId,
+        duration: 1000,
+        startTime: new Date(1000),
+      })
+      const eventId = res.event.id
+      const version = res.event.version
+
+      const res2 = await adminClient.events.update(
+        eventId,
+        { title: 'first' },
+        { expectedVersion: version }
+      )
+      expect(res2.event.title).toBe('first')
+      expect(res2.event.version).toBe(version + 1)
+
+      // The version used is now stale
+      await expect(() =>
+        adminClient.events.update(
+          eventId,
+          { title: 'second' },
+          { expectedVersion: version }
+        )
+      ).rejects.toThrow(PreconditionFailedError)
+
+      const getRes = await adminClient.events.getById(eventId)
+      expect(getRes.event.title).toBe('first')
+    })
+
     it('should not overwrite externalId and parentId when updating event', async () => {
       const externalId = crypto.randomUUID()
       const externalParentId = crypto.randomUUID()
@@ -1244,4 +1351,94 @@ describe('CalendarEvent API', () => {
       )
     })
   })
+
+  describe('Attendees', () => {
+    let adminClient: INitteiClient
+    let attendeeClient: INitteiUserClient
+    let organizerId: string
+    let attendeeId: string
+    let eventId: string
+    beforeAll(async () => {
+      const data = await setupUserClient()
+      adminClient = data.accountClient
+      attendeeClient = data.userClient
+      attendeeId = data.userId
+
+      const userRes = await adminClient.user.create()
+      organizerId = userRes.user.id
+      const calendarRes = await adminClient.calendar.create(organizerId, {
+        timezone: 'UTC',
+      })
+
+      const eventRes = await adminClient.events.create(organizerId, {
+        calendarId: calendarRes.calendar.id,
+        duration: 1000 * 60 * 60,
+        startTime: new Date('2025-04-10T10:00:00.000Z'),
+        status: 'confirmed',
+        busy: true,
+        attendees: [{ userId: attendeeId }, { email: 'John@Example.com' }],
+      })
+      eventId = eventRes.event.id
+    })
+
+    it('should return the attendees of the event', async () => {
+      const res = await adminClient.events.getById(eventId)
+      expect(res.event.attendees).toEqual([
+        { userId: attendeeId, role: 'required', responseStatus: 'needsAction' },
+        {
+          email: 'john@example.com',
+          role: 'required',
+          responseStatus: 'needsAction',
+        },
+      ])
+    })
+
+    it('should make the attendee busy until the event is declined', async () => {
+      const timespan = {
+        startTime: new Date('2025-04-10T00:00:00.000Z'),
+        endTime: new Date('2025-04-11T00:00:00.000Z'),
+      }
+      let res = await adminClient.user.freebusy(attendeeId, timespan)
+      expect(res.busy.length).toBe(1)
+
+      const rsvp = await attendeeClient.events.updateAttendeeResponse(eventId, {
+        responseStatus: 'declined',
+      })
+      expect(rsvp.event.attendees[0].responseStatus).toBe('declined')
+
+      res = await adminClient.user.freebusy(attendeeId, timespan)
+      expect(res.busy.length).toBe(0)
+    })
+
+    it('should be able to search events on their attendees', async () => {
+      const res = await adminClient.events.searchEvents({
+        filter: {
+          userId: organizerId,
+          attendee: {
+            userId: { eq: attendeeId },
+            responseStatus: { eq: 'declined' },
+          },
+        },
+      })
+      expect(res.events.length).toBe(1)
+      expect(res.events[0].id).toBe(eventId)
+    })
+
+    it('should not let a user respond to an event it does not attend', async () => {
+      const userRes = await adminClient.user.create()
+      const calendarRes = await adminClient.calendar.create(userRes.user.id, {
+        timezone: 'UTC',
+      })
+      const eventRes = await adminClient.events.create(userRes.user.id, {
+        calendarId: calendarRes.calendar.id,
+        duration: 1000,
+        startTime: new Date(1000),
+      })
+      await expect(() =>
+        attendeeClient.events.updateAttendeeResponse(eventRes.event.id, {
+          responseStatus: 'accepted',
+        })
+      ).rejects.toThrow(NotFoundError)
+    })
+  })
 })
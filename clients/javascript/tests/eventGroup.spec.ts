@@ -0,0 +1,66 @@
+import type { INitteiClient } from '../lib'
+import { setupAccount } from './helpers/fixtures'
+
+describe('EventGroup API', () => {
+  let adminClient: INitteiClient
+  let userId: string
+  let calendarId: string
+
+  beforeAll(async () => {
+    const account = await setupAccount()
+    adminClient = account.client
+    const userRes = await adminClient.user.create()
+    userId = userRes.user.id
+    const calendarRes = await adminClient.calendar.create(userId, {
+      timezone: 'UTC',
+    })
+    calendarId = calendarRes.calendar.id
+  })
+
+  it('should create an event group and retrieve it by external id', async () => {
+    const res = await adminClient.eventGroups.create(userId, {
+      calendarId,
+      externalId: 'group_external_id',
+      parentId: 'group_parent_id',
+    })
+    expect(res.eventGroup.id).toBeDefined()
+
+    const byExternalId =
+      await adminClient.eventGroups.getByExternalId('group_external_id')
+    expect(byExternalId.eventGroup.id).toBe(res.eventGroup.id)
+
+    const byParentId =
+      await adminClient.eventGroups.getByParentId('group_parent_id')
+    expect(byParentId.eventGroups.length).toBe(1)
+  })
+
+  it('should shift and cancel all the events of a group', async () => {
+    const group = await adminClient.eventGroups.create(userId, {
+      calendarId,
+    })
+    const groupId = group.eventGroup.id
+
+    const startTime = new Date(Date.UTC(2030, 0, 1, 10))
+    await adminClient.events.create(userId, {
+      calendarId,
+      groupId,
+      duration: 1000 * 60 * 60,
+      startTime,
+    })
+
+    const shifted = await adminClient.eventGroups.shiftEvents(groupId, 30)
+    expect(shifted.events.length).toBe(1)
+    expect(shifted.events[0].startTime).toEqual(
+      new Date(Date.UTC(2030, 0, 1, 10, 30))
+    )
+
+    const cancelled = await adminClient.eventGroups.cancelEvents(groupId)
+    expect(cancelled.events[0].status).toBe('cancelled')
+
+    const removed = await adminClient.eventGroups.removeEvents(groupId)
+    expect(removed.events.length).toBe(1)
+
+    const events = await adminClient.eventGroups.getEvents(groupId)
+    expect(events.events.length).toBe(0)
+  })
+})
@@ -157,4 +157,187 @@ describe('Service API', () => {
 
     expect(dates2.length).toBe(0)
   })
+
+  it('should book a slot of the service', async () => {
+    const serviceRes = await client.service.create()
+    const serviceId = serviceRes.service.id
+
+    const scheduleRes = await userClient.schedule.create({
+      timezone: 'UTC',
+    })
+    const calendarRes = await userClient.calendar.create({
+      timezone: 'UTC',
+    })
+    await client.service.addUser(serviceId, {
+      userId,
+      availability: {
+        variant: 'Schedule',
+        id: scheduleRes.schedule.id,
+      },
+    })
+    await client.service.addBusyCalendar({
+      serviceId,
+      userId,
+      busy: {
+        provider: 'Nittei',
+        id: calendarRes.calendar.id,
+      },
+    })
+
+    const duration = 30 * 60 * 1000
+    const { dates } = await client.service.getBookingslots(serviceId, {
+      startDate: '2030-10-10',
+      endDate: '2030-10-10',
+      duration,
+      timezone: 'UTC',
+      interval: duration,
+    })
+    const timestamp = dates[0].slots[0].start
+
+    const { events } = await client.service.createBooking(serviceId, {
+      timestamp,
+      duration,
+      interval: duration,
+      title: 'Booking',
+    })
+    expect(events.length).toBe(1)
+    expect(events[0].userId).toBe(userId)
+    expect(events[0].calendarId).toBe(calendarRes.calendar.id)
+
+    // The slot is not available anymore
+    await expect(
+      client.service.createBooking(serviceId, {
+        timestamp,
+        duration,
+        interval: duration,
+      })
+    ).rejects.toThrow()
+  })
+
+  it('should hold a slot of the service and confirm it', async () => {
+    const serviceRes = await client.service.create()
+    const serviceId = serviceRes.service.id
+
+    const scheduleRes = await userClient.schedule.create({
+      timezone: 'UTC',
+    })
+    const calendarRes = await userClient.calendar.create({
+      timezone: 'UTC',
+    })
+    await client.service.addUser(serviceId, {
+      userId,
+      availability: {
+        variant: 'Schedule',
+        id: scheduleRes.schedule.id,
+      },
+    })
+    await client.service.addBusyCalendar({
+      serviceId,
+      userId,
+      busy: {
+        provider: 'Nittei',
+        id: calendarRes.calendar.id,
+      },
+    })
+
+    const duration = 30 * 60 * 1000
+    const bookingslotsQuery = {
+      startDate: '2030-10-11',
+      endDate: '2030-10-11',
+      duration,
+      timezone: 'UTC',
+      interval: duration,
+    }
+    const { dates } = await client.service.getBookingslots(
+      serviceId,
+      bookingslotsQuery
+    )
+    const timestamp = dates[0].slots[0].start
+
+    const { hold } = await client.service.createHold(serviceId, {
+      timestamp,
+      duration,
+      interval: duration,
+      ttlMinutes: 10,
+    })
+    expect(hold.hostUserIds).toEqual([userId])
+
+    // The held slot is not available anymore
+    const { dates: datesWithHold } = await client.service.getBookingslots(
+      serviceId,
+      bookingslotsQuery
+    )
+    expect(datesWithHold[0].slots[0].start).not.toEqual(timestamp)
+
+    const { events } = await client.service.confirmHold(serviceId, hold.id, {
+      title: 'Booking',
+    })
+    expect(events.length).toBe(1)
+    expect(events[0].userId).toBe(userId)
+
+    // The hold has been released by the booking
+    await expect(
+      client.service.confirmHold(serviceId, hold.id)
+    ).rejects.toThrow()
+  })
+
+  it('should reschedule and cancel a booking of the service', async () => {
+    const serviceRes = await client.service.create()
+    const serviceId = serviceRes.service.id
+
+    const scheduleRes = await userClient.schedule.create({
+      timezone: 'UTC',
+    })
+    const calendarRes = await userClient.calendar.create({
+      timezone: 'UTC',
+    })
+    await client.service.addUser(serviceId, {
+      userId,
+      availability: {
+        variant: 'Schedule',
+        id: scheduleRes.schedule.id,
+      },
+    })
+    await client.service.addBusyCalendar({
+      serviceId,
+      userId,
+      busy: {
+        provider: 'Nittei',
+        id: calendarRes.calendar.id,
+      },
+    })
+
+    const duration = 30 * 60 * 1000
+    const { dates } = await client.service.getBookingslots(serviceId, {
+      startDate: '2030-10-11',
+      endDate: '2030-10-11',
+      duration,
+      timezone: 'UTC',
+      interval: duration,
+    })
+    const { events } = await client.service.createBooking(serviceId, {
+      timestamp: dates[0].slots[0].start,
+      duration,
+      interval: duration,
+    })
+
+    const rescheduled = await client.service.rescheduleBooking(
+      serviceId,
+      events[0].id,
+      {
+        timestamp: dates[0].slots[2].start,
+        interval: duration,
+      }
+    )
+    expect(rescheduled.cancelledEvents[0].id).toBe(events[0].id)
+    expect(rescheduled.cancelledEvents[0].status).toBe('cancelled')
+    expect(rescheduled.events[0].userId).toBe(userId)
+    expect(rescheduled.events[0].startTime).toEqual(dates[0].slots[2].start)
+
+    const cancelled = await client.service.cancelBooking(
+      serviceId,
+      rescheduled.events[0].id
+    )
+    expect(cancelled.events[0].status).toBe('cancelled')
+  })
 })
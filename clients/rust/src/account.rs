@@ -1,6 +1,14 @@
 use std::sync::Arc;
 
 use nittei_api_structs::*;
+use nittei_domain::{
+    ID,
+    OutboxMessageStatus,
+    RateLimit,
+    SearchLanguage,
+    WebhookDeliveryStatus,
+    WebhookEventType,
+};
 use reqwest::StatusCode;
 
 use crate::{APIResponse, BaseClient};
@@ -29,15 +37,35 @@ impl AccountClient {
             .await
     }
 
-    pub async fn create_webhook(&self, url: &str) -> APIResponse<set_account_webhook::APIResponse> {
+    /// Set the webhook of the account
+    /// `event_types` are the changes of the events to send to the webhook (unchanged if None)
+    pub async fn create_webhook(
+        &self,
+        url: &str,
+        event_types: Option<Vec<WebhookEventType>>,
+    ) -> APIResponse<set_account_webhook::APIResponse> {
         let body = set_account_webhook::SetAccountWebhookRequestBody {
             webhook_url: url.into(),
+            event_types,
         };
         self.base
             .put(body, "account/webhook".into(), StatusCode::OK)
             .await
     }
 
+    /// Rotate the key used to sign the webhooks
+    /// The current key stays valid during the grace period (24 hours if None)
+    pub async fn rotate_webhook_key(
+        &self,
+        grace_period_secs: Option<u32>,
+    ) -> APIResponse<rotate_account_webhook_key::APIResponse> {
+        let body =
+            rotate_account_webhook_key::RotateAccountWebhookKeyRequestBody { grace_period_secs };
+        self.base
+            .post(body, "account/webhook/rotate-key".into(), StatusCode::OK)
+            .await
+    }
+
     pub async fn delete_webhook(&self) -> APIResponse<delete_account_webhook::APIResponse> {
         self.base
             .delete("account/webhook".into(), StatusCode::OK)
@@ -55,4 +83,104 @@ impl AccountClient {
             .put(body, "account/pubkey".into(), StatusCode::OK)
             .await
     }
+
+    /// Set the limits of the requests of the account and of each of its users
+    /// The default limits are used for the ones not set
+    pub async fn set_rate_limits(
+        &self,
+        account: Option<RateLimit>,
+        user: Option<RateLimit>,
+    ) -> APIResponse<set_account_rate_limits::APIResponse> {
+        let body = set_account_rate_limits::SetAccountRateLimitsRequestBody { account, user };
+        self.base
+            .put(body, "account/rate-limits".into(), StatusCode::OK)
+            .await
+    }
+
+    /// Set the language of the texts of the events, used by the text search
+    pub async fn set_search_language(
+        &self,
+        search_language: SearchLanguage,
+    ) -> APIResponse<set_account_search_language::APIResponse> {
+        let body =
+            set_account_search_language::SetAccountSearchLanguageRequestBody { search_language };
+        self.base
+            .put(body, "account/search-language".into(), StatusCode::OK)
+            .await
+    }
+
+    /// Search the events of the account
+    /// When more events are found than the limit, `next_cursor` is the `cursor` of the next page
+    pub async fn search_events(
+        &self,
+        body: account_search_events::AccountSearchEventsRequestBody,
+    ) -> APIResponse<account_search_events::SearchEventsAPIResponse> {
+        self.base
+            .post(body, "account/events/search".into(), StatusCode::OK)
+            .await
+    }
+
+    pub async fn get_outbox_messages(
+        &self,
+        status: Option<OutboxMessageStatus>,
+        limit: Option<u16>,
+    ) -> APIResponse<get_outbox_messages::APIResponse> {
+        let mut query = Vec::new();
+        if let Some(status) = status {
+            query.push(("status".to_string(), String::from(status)));
+        }
+        if let Some(limit) = limit {
+            query.push(("limit".to_string(), limit.to_string()));
+        }
+        self.base
+            .get("account/outbox".into(), Some(query), StatusCode::OK)
+            .await
+    }
+
+    pub async fn replay_outbox_message(
+        &self,
+        message_id: ID,
+    ) -> APIResponse<replay_outbox_message::APIResponse> {
+        self.base
+            .post(
+                (),
+                format!("account/outbox/{message_id}/replay"),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn get_webhook_deliveries(
+        &self,
+        status: Option<WebhookDeliveryStatus>,
+        limit: Option<u16>,
+    ) -> APIResponse<get_webhook_deliveries::APIResponse> {
+        let mut query = Vec::new();
+        if let Some(status) = status {
+            query.push(("status".to_string(), String::from(status)));
+        }
+        if let Some(limit) = limit {
+            query.push(("limit".to_string(), limit.to_string()));
+        }
+        self.base
+            .get(
+                "account/webhook/deliveries".into(),
+                Some(query),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn redeliver_webhook(
+        &self,
+        delivery_id: ID,
+    ) -> APIResponse<redeliver_webhook::APIResponse> {
+        self.base
+            .post(
+                (),
+                format!("account/webhook/deliveries/{delivery_id}/redeliver"),
+                StatusCode::OK,
+            )
+            .await
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, header};
 use serde::{Deserialize, Serialize};
 
 pub(crate) struct BaseClient {
@@ -16,6 +16,9 @@ pub enum APIErrorVariant {
     Unauthorized,
     NotFound,
     BadClientData,
+    Conflict,
+    PreconditionFailed,
+    TooManyRequests,
     UnexpectedStatusCode,
 }
 #[derive(Debug)]
@@ -51,7 +54,7 @@ impl BaseClient {
             Method::POST => self.client.post(&url),
             Method::PUT => self.client.put(&url),
             Method::PATCH => self.client.patch(&url),
-            Method::DELETE => self.client.delete(&url),
+            Method::DELETE => self.client.delete(&url).query(&query.unwrap_or_default()),
             _ => unimplemented!(),
         };
 
@@ -73,6 +76,9 @@ impl BaseClient {
                 StatusCode::UNAUTHORIZED => APIErrorVariant::Unauthorized,
                 StatusCode::NOT_FOUND => APIErrorVariant::NotFound,
                 StatusCode::UNPROCESSABLE_ENTITY => APIErrorVariant::BadClientData,
+                StatusCode::CONFLICT => APIErrorVariant::Conflict,
+                StatusCode::PRECONDITION_FAILED => APIErrorVariant::PreconditionFailed,
+                StatusCode::TOO_MANY_REQUESTS => APIErrorVariant::TooManyRequests,
                 _ => APIErrorVariant::UnexpectedStatusCode,
             };
             return Err(APIError {
@@ -162,6 +168,23 @@ impl BaseClient {
         self.handle_api_response(res, expected_status_code).await
     }
 
+    pub async fn delete_with_query<T: for<'de> Deserialize<'de>>(
+        &self,
+        path: String,
+        query_params: Option<Vec<(String, String)>>,
+        expected_status_code: StatusCode,
+    ) -> APIResponse<T> {
+        let res = match self
+            .get_client(Method::DELETE, path, query_params)
+            .send()
+            .await
+        {
+            Ok(res) => res,
+            Err(_) => return Err(self.network_error()),
+        };
+        self.handle_api_response(res, expected_status_code).await
+    }
+
     pub async fn delete_with_body<T: for<'de> Deserialize<'de>, S: Serialize>(
         &self,
         body: S,
@@ -186,12 +209,24 @@ impl BaseClient {
         path: String,
         expected_status_code: StatusCode,
     ) -> APIResponse<T> {
-        let res = match self
-            .get_client(Method::PUT, path, None)
-            .json(&body)
-            .send()
+        self.put_if_match(body, path, None, expected_status_code)
             .await
-        {
+    }
+
+    /// Same as `put`, with an `If-Match` header when a version is provided
+    /// The API then rejects the request (412) if the resource has been modified in the meantime
+    pub async fn put_if_match<T: for<'de> Deserialize<'de>, S: Serialize>(
+        &self,
+        body: S,
+        path: String,
+        version: Option<i64>,
+        expected_status_code: StatusCode,
+    ) -> APIResponse<T> {
+        let mut builder = self.get_client(Method::PUT, path, None).json(&body);
+        if let Some(version) = version {
+            builder = builder.header(header::IF_MATCH, format!("\"{version}\""));
+        }
+        let res = match builder.send().await {
             Ok(res) => res,
             Err(_) => return Err(self.network_error()),
         };
@@ -204,12 +239,24 @@ impl BaseClient {
         path: String,
         expected_status_code: StatusCode,
     ) -> APIResponse<T> {
-        let res = match self
-            .get_client(Method::PATCH, path, None)
-            .json(&body)
-            .send()
+        self.patch_if_match(body, path, None, expected_status_code)
             .await
-        {
+    }
+
+    /// Same as `patch`, with an `If-Match` header when a version is provided
+    /// The API then rejects the request (412) if the resource has been modified in the meantime
+    pub async fn patch_if_match<T: for<'de> Deserialize<'de>, S: Serialize>(
+        &self,
+        body: S,
+        path: String,
+        version: Option<i64>,
+        expected_status_code: StatusCode,
+    ) -> APIResponse<T> {
+        let mut builder = self.get_client(Method::PATCH, path, None).json(&body);
+        if let Some(version) = version {
+            builder = builder.header(header::IF_MATCH, format!("\"{version}\""));
+        }
+        let res = match builder.send().await {
             Ok(res) => res,
             Err(_) => return Err(self.network_error()),
         };
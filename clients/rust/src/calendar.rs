@@ -56,6 +56,8 @@ pub struct UpdateCalendarInput {
     pub name: Option<String>,
     pub timezone: Option<Tz>,
     pub metadata: Option<serde_json::Value>,
+    /// Only update the calendar if its version is still this one (sent as `If-Match`)
+    pub expected_version: Option<i64>,
 }
 
 pub struct GetGoogleCalendars {
@@ -78,6 +80,16 @@ pub struct ExportCalendarIcalInput {
     pub end_time: Option<DateTime<Utc>>,
 }
 
+/// Input for importing iCalendar content into a calendar
+pub struct ImportCalendarIcalInput {
+    pub user_id: ID,
+    pub calendar_id: ID,
+    /// iCalendar content (.ics)
+    pub ical: String,
+    /// If true, nothing is written and only the changes which would be made are returned
+    pub dry_run: Option<bool>,
+}
+
 impl CalendarClient {
     pub(crate) fn new(base: Arc<BaseClient>) -> Self {
         Self { base }
@@ -97,9 +109,10 @@ impl CalendarClient {
             metadata: input.metadata,
         };
         self.base
-            .put(
+            .put_if_match(
                 body,
                 format!("user/calendar/{}", input.calendar_id),
+                input.expected_version,
                 StatusCode::OK,
             )
             .await
@@ -267,11 +280,11 @@ impl CalendarClient {
         let mut query_params = Vec::new();
 
         if let Some(start_time) = input.start_time {
-            query_params.push(("startTime".to_string(), start_time.to_string()));
+            query_params.push(("startTime".to_string(), start_time.to_rfc3339()));
         }
 
         if let Some(end_time) = input.end_time {
-            query_params.push(("endTime".to_string(), end_time.to_string()));
+            query_params.push(("endTime".to_string(), end_time.to_rfc3339()));
         }
 
         self.base
@@ -286,4 +299,72 @@ impl CalendarClient {
             )
             .await
     }
+
+    pub async fn import_ical(
+        &self,
+        input: ImportCalendarIcalInput,
+    ) -> APIResponse<import_calendar_ical::APIResponse> {
+        let body = import_calendar_ical::ImportCalendarIcalRequestBody {
+            ical: input.ical,
+            dry_run: input.dry_run,
+        };
+        self.base
+            .post(
+                body,
+                format!("user/{}/calendar/{}/ical", input.user_id, input.calendar_id),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    /// Create a public iCalendar feed of a calendar
+    ///
+    /// The feed can be fetched by anyone knowing its token, see `get_feed_ical`
+    pub async fn create_feed(
+        &self,
+        calendar_id: ID,
+        mask_events: Option<bool>,
+    ) -> APIResponse<create_calendar_feed::APIResponse> {
+        let body = create_calendar_feed::CreateCalendarFeedRequestBody { mask_events };
+        self.base
+            .post(
+                body,
+                format!("user/calendar/{calendar_id}/ical/feeds"),
+                StatusCode::CREATED,
+            )
+            .await
+    }
+
+    pub async fn get_feeds(&self, calendar_id: ID) -> APIResponse<get_calendar_feeds::APIResponse> {
+        self.base
+            .get(
+                format!("user/calendar/{calendar_id}/ical/feeds"),
+                None,
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    /// Revoke a public iCalendar feed of a calendar
+    pub async fn delete_feed(
+        &self,
+        calendar_id: ID,
+        feed_id: ID,
+    ) -> APIResponse<delete_calendar_feed::APIResponse> {
+        self.base
+            .delete(
+                format!("user/calendar/{calendar_id}/ical/feeds/{feed_id}"),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    /// Get the public iCalendar feed with the token (no API key is needed)
+    ///
+    /// Returns a plain text response containing the iCal content.
+    pub async fn get_feed_ical(&self, token: &str) -> APIResponse<String> {
+        self.base
+            .get_text(format!("ical/{token}.ics"), None, StatusCode::OK)
+            .await
+    }
 }
@@ -2,16 +2,18 @@ use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use nittei_api_structs::*;
-use nittei_domain::CalendarEventStatus;
+use nittei_domain::{CalendarEventStatus, RecurringEventScope};
 use reqwest::StatusCode;
 use serde::Serialize;
 
 use crate::{
     APIResponse,
     BaseClient,
+    CalendarEventAttendee,
     CalendarEventReminder,
     ID,
     RRuleOptions,
+    Tz,
     shared::MetadataFindInput,
 };
 
@@ -52,6 +54,8 @@ pub struct CreateEventInput {
     #[serde(default)]
     pub recurrence: Option<RRuleOptions>,
     #[serde(default)]
+    pub timezone: Option<Tz>,
+    #[serde(default)]
     pub exdates: Option<Vec<DateTime<Utc>>>,
     #[serde(default)]
     pub recurring_event_id: Option<ID>,
@@ -60,8 +64,12 @@ pub struct CreateEventInput {
     #[serde(default)]
     pub reminders: Vec<CalendarEventReminder>,
     #[serde(default)]
+    pub attendees: Vec<CalendarEventAttendee>,
+    #[serde(default)]
     pub service_id: Option<ID>,
     #[serde(default)]
+    pub group_id: Option<ID>,
+    #[serde(default)]
     pub metadata: Option<serde_json::Value>,
 }
 
@@ -92,12 +100,25 @@ pub struct UpdateEventInput {
     pub duration: Option<i64>,
     pub busy: Option<bool>,
     pub reminders: Option<Vec<CalendarEventReminder>>,
+    pub attendees: Option<Vec<CalendarEventAttendee>>,
     pub recurrence: Option<Option<RRuleOptions>>,
+    pub timezone: Option<Option<Tz>>,
     pub recurring_event_id: Option<Option<ID>>,
     pub original_start_time: Option<Option<DateTime<Utc>>>,
     pub service_id: Option<Option<ID>>,
+    pub group_id: Option<Option<ID>>,
     pub exdates: Option<Vec<DateTime<Utc>>>,
     pub metadata: Option<Option<serde_json::Value>>,
+    pub scope: Option<RecurringEventScope>,
+    pub occurrence_start_time: Option<DateTime<Utc>>,
+    /// Only update the event if its version is still this one (sent as `If-Match`)
+    pub expected_version: Option<i64>,
+}
+
+pub struct DeleteEventInput {
+    pub event_id: ID,
+    pub scope: RecurringEventScope,
+    pub occurrence_start_time: Option<DateTime<Utc>>,
 }
 
 impl CalendarEventClient {
@@ -111,6 +132,32 @@ impl CalendarEventClient {
             .await
     }
 
+    /// Delete a recurring event with a scope (only one occurrence, this and following, or all)
+    pub async fn delete_with_scope(
+        &self,
+        input: DeleteEventInput,
+    ) -> APIResponse<delete_event::APIResponse> {
+        let scope = match input.scope {
+            RecurringEventScope::This => "this",
+            RecurringEventScope::ThisAndFollowing => "thisAndFollowing",
+            RecurringEventScope::All => "all",
+        };
+        let mut query_params = vec![("scope".to_string(), scope.to_string())];
+        if let Some(occurrence_start_time) = input.occurrence_start_time {
+            query_params.push((
+                "occurrenceStartTime".to_string(),
+                occurrence_start_time.to_rfc3339(),
+            ));
+        }
+        self.base
+            .delete_with_query(
+                format!("user/events/{}", input.event_id),
+                Some(query_params),
+                StatusCode::OK,
+            )
+            .await
+    }
+
     pub async fn get(&self, event_id: ID) -> APIResponse<get_event::APIResponse> {
         self.base
             .get(format!("user/events/{event_id}"), None, StatusCode::OK)
@@ -149,11 +196,14 @@ impl CalendarEventClient {
             duration: input.duration,
             busy: input.busy,
             recurrence: input.recurrence,
+            timezone: input.timezone,
             exdates: input.exdates,
             recurring_event_id: input.recurring_event_id,
             original_start_time: input.original_start_time,
             reminders: input.reminders,
+            attendees: input.attendees,
             service_id: input.service_id,
+            group_id: input.group_id,
             metadata: input.metadata,
             created: None,
             updated: None,
@@ -177,6 +227,17 @@ impl CalendarEventClient {
             .await
     }
 
+    /// Search the events of a user
+    /// When more events are found than the limit, `next_cursor` is the `cursor` of the next page
+    pub async fn search(
+        &self,
+        body: search_events::SearchEventsRequestBody,
+    ) -> APIResponse<search_events::SearchEventsAPIResponse> {
+        self.base
+            .post(body, "events/search".to_string(), StatusCode::OK)
+            .await
+    }
+
     pub async fn update(&self, input: UpdateEventInput) -> APIResponse<update_event::APIResponse> {
         let event_id = input.event_id.clone();
         let body = update_event::UpdateEventRequestBody {
@@ -193,16 +254,26 @@ impl CalendarEventClient {
             duration: input.duration,
             exdates: input.exdates,
             recurrence: input.recurrence,
+            timezone: input.timezone,
             recurring_event_id: input.recurring_event_id,
             original_start_time: input.original_start_time,
             reminders: input.reminders,
+            attendees: input.attendees,
             service_id: input.service_id,
+            group_id: input.group_id,
             metadata: input.metadata,
             created: None,
             updated: None,
+            scope: input.scope,
+            occurrence_start_time: input.occurrence_start_time,
         };
         self.base
-            .patch(body, format!("user/events/{event_id}"), StatusCode::OK)
+            .patch_if_match(
+                body,
+                format!("user/events/{event_id}"),
+                input.expected_version,
+                StatusCode::OK,
+            )
             .await
     }
 }
@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use nittei_api_structs::*;
+use reqwest::StatusCode;
+
+use crate::{APIResponse, BaseClient, ID};
+
+#[derive(Clone)]
+pub struct EventGroupClient {
+    base: Arc<BaseClient>,
+}
+
+pub struct CreateEventGroupInput {
+    pub user_id: ID,
+    pub calendar_id: ID,
+    pub parent_id: Option<String>,
+    pub external_id: Option<String>,
+}
+
+// UpdateEventGroupInput is the input for the update event group endpoint
+//
+// For fields that have a double `Option`, they are optional fields in the model, so
+// - If the wrapper is None, the field is not updated
+// - If the wrapper is Some(None), the field is set to NULL
+// - If the wrapper is Some(Some(value)), the field is set to the value
+pub struct UpdateEventGroupInput {
+    pub group_id: ID,
+    pub parent_id: Option<Option<String>>,
+    pub external_id: Option<Option<String>>,
+}
+
+pub struct ShiftEventGroupEventsInput {
+    pub group_id: ID,
+    pub minutes: i64,
+}
+
+impl EventGroupClient {
+    pub(crate) fn new(base: Arc<BaseClient>) -> Self {
+        Self { base }
+    }
+
+    pub async fn create(
+        &self,
+        input: CreateEventGroupInput,
+    ) -> APIResponse<create_event_group::APIResponse> {
+        let body = create_event_group::CreateEventGroupRequestBody {
+            calendar_id: input.calendar_id,
+            parent_id: input.parent_id,
+            external_id: input.external_id,
+        };
+
+        self.base
+            .post(
+                body,
+                format!("user/{}/event_groups", input.user_id),
+                StatusCode::CREATED,
+            )
+            .await
+    }
+
+    pub async fn get(&self, group_id: ID) -> APIResponse<get_event_group::APIResponse> {
+        self.base
+            .get(
+                format!("user/event_groups/{group_id}"),
+                None,
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn get_by_external_id(
+        &self,
+        external_id: String,
+    ) -> APIResponse<get_event_group_by_external_id::APIResponse> {
+        self.base
+            .get(
+                format!("user/event_groups/external_id/{external_id}"),
+                None,
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn get_by_parent_id(
+        &self,
+        parent_id: String,
+    ) -> APIResponse<get_event_groups_by_parent_id::GetEventGroupsByParentIdAPIResponse> {
+        self.base
+            .get(
+                format!("user/event_groups/parent_id/{parent_id}"),
+                None,
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn update(
+        &self,
+        input: UpdateEventGroupInput,
+    ) -> APIResponse<update_event_group::APIResponse> {
+        let body = update_event_group::UpdateEventGroupRequestBody {
+            parent_id: input.parent_id,
+            external_id: input.external_id,
+        };
+
+        self.base
+            .patch(
+                body,
+                format!("user/event_groups/{}", input.group_id),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn delete(&self, group_id: ID) -> APIResponse<delete_event_group::APIResponse> {
+        self.base
+            .delete(format!("user/event_groups/{group_id}"), StatusCode::OK)
+            .await
+    }
+
+    pub async fn get_events(
+        &self,
+        group_id: ID,
+    ) -> APIResponse<get_event_group_events::APIResponse> {
+        self.base
+            .get(
+                format!("user/event_groups/{group_id}/events"),
+                None,
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn cancel_events(
+        &self,
+        group_id: ID,
+    ) -> APIResponse<cancel_event_group_events::APIResponse> {
+        self.base
+            .post(
+                (),
+                format!("user/event_groups/{group_id}/cancel"),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn delete_events(
+        &self,
+        group_id: ID,
+    ) -> APIResponse<delete_event_group_events::APIResponse> {
+        self.base
+            .delete(
+                format!("user/event_groups/{group_id}/events"),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn shift_events(
+        &self,
+        input: ShiftEventGroupEventsInput,
+    ) -> APIResponse<shift_event_group_events::APIResponse> {
+        let body = shift_event_group_events::ShiftEventGroupEventsRequestBody {
+            minutes: input.minutes,
+        };
+
+        self.base
+            .post(
+                body,
+                format!("user/event_groups/{}/shift", input.group_id),
+                StatusCode::OK,
+            )
+            .await
+    }
+}
@@ -2,11 +2,13 @@ mod account;
 mod base;
 mod calendar;
 mod event;
+mod event_group;
 mod schedule;
 mod service;
 mod shared;
 mod status;
 mod user;
+mod webhook;
 
 use std::sync::Arc;
 
@@ -20,12 +22,15 @@ pub use calendar::{
     GetCalendarEventsInput,
     GetGoogleCalendars,
     GetOutlookCalendars,
+    ImportCalendarIcalInput,
     StopCalendarSyncInput,
     SyncCalendarInput,
     UpdateCalendarInput,
 };
 use event::CalendarEventClient;
-pub use event::{CreateEventInput, GetEventsInstancesInput, UpdateEventInput};
+pub use event::{CreateEventInput, DeleteEventInput, GetEventsInstancesInput, UpdateEventInput};
+use event_group::EventGroupClient;
+pub use event_group::{CreateEventGroupInput, ShiftEventGroupEventsInput, UpdateEventGroupInput};
 // Domain
 pub use nittei_api_structs::dtos::{
     AccountDTO as Account,
@@ -34,6 +39,7 @@ pub use nittei_api_structs::dtos::{
     CalendarDTO as Calendar,
     CalendarEventDTO as CalendarEvent,
     CalendarSettingsDTO as CalendarSettings,
+    EventGroupDTO as EventGroup,
     EventWithInstancesDTO as EventWithIInstances,
     ScheduleDTO as Schedule,
     ServiceResourceDTO as ServiceResource,
@@ -41,23 +47,42 @@ pub use nittei_api_structs::dtos::{
     UserDTO as User,
 };
 pub use nittei_api_structs::{
+    account_search_events::{AccountSearchEventsRequestBody, AccountSearchEventsRequestBodyFilter},
     dtos::*,
+    import_calendar_ical::IcalImportAction,
+    search_events::{SearchEventsRequestBody, SearchEventsRequestBodyFilter},
     send_event_reminders::AccountRemindersDTO as AccountReminders,
+    send_event_webhook::EventWebhookPayload,
 };
 pub use nittei_domain::{
     BusyCalendarProvider,
+    CalendarEventAttendee,
+    CalendarEventAttendeeResponseStatus,
+    CalendarEventAttendeeRole,
     CalendarEventReminder,
+    CalendarEventSort,
+    DateTimeQuery,
+    DateTimeQueryRange,
     ID,
     IntegrationProvider,
     Metadata,
     Month,
+    OutboxMessageStatus,
+    OutboxTask,
     RRuleFrequency,
     RRuleOptions,
+    RateLimit,
+    RecurringEventScope,
     ScheduleRule,
+    SearchLanguage,
     ServiceMultiPersonOptions,
     SyncedCalendar,
     TimePlan,
     Tz,
+    WEBHOOK_SIGNATURE_HEADER,
+    WebhookDeliveryStatus,
+    WebhookEventType,
+    WebhookSignatureError,
     WeekDayRecurrence,
     Weekday,
     providers::{google::*, outlook::*},
@@ -69,12 +94,18 @@ use service::ServiceClient;
 pub use service::{
     AddBusyCalendar,
     AddServiceUserInput,
+    CancelBookingInput,
+    ConfirmHoldInput,
+    CreateBookingInput,
     CreateBookingIntendInput,
+    CreateHoldInput,
     CreateServiceInput,
     GetServiceBookingSlotsInput,
+    ReleaseHoldInput,
     RemoveBookingIntendInput,
     RemoveBusyCalendar,
     RemoveServiceUserInput,
+    RescheduleBookingInput,
     UpdateServiceInput,
     UpdateServiceUserInput,
 };
@@ -90,6 +121,7 @@ pub use user::{
     RemoveUserIntegrationInput,
     UpdateUserInput,
 };
+pub use webhook::{DEFAULT_WEBHOOK_TOLERANCE_SECS, WebhookError, parse_webhook, verify_webhook};
 
 /// nittei Server SDK
 ///
@@ -100,6 +132,7 @@ pub struct NitteiSDK {
     pub account: AccountClient,
     pub calendar: CalendarClient,
     pub event: CalendarEventClient,
+    pub event_group: EventGroupClient,
     pub schedule: ScheduleClient,
     pub service: ServiceClient,
     pub status: StatusClient,
@@ -114,6 +147,7 @@ impl NitteiSDK {
         let account = AccountClient::new(base.clone());
         let calendar = CalendarClient::new(base.clone());
         let event = CalendarEventClient::new(base.clone());
+        let event_group = EventGroupClient::new(base.clone());
         let schedule = ScheduleClient::new(base.clone());
         let service = ServiceClient::new(base.clone());
         let status = StatusClient::new(base.clone());
@@ -123,6 +157,7 @@ impl NitteiSDK {
             account,
             calendar,
             event,
+            event_group,
             schedule,
             service,
             status,
@@ -20,6 +20,10 @@ pub struct AddServiceUserInput {
     pub buffer_before: Option<i64>,
     pub closest_booking_time: Option<i64>,
     pub furthest_booking_time: Option<i64>,
+    pub weight: Option<i64>,
+    pub priority: Option<i64>,
+    pub max_bookings_per_day: Option<i64>,
+    pub max_bookings_per_week: Option<i64>,
 }
 
 pub struct AddBusyCalendar {
@@ -42,6 +46,10 @@ pub struct UpdateServiceUserInput {
     pub buffer_before: Option<i64>,
     pub closest_booking_time: Option<i64>,
     pub furthest_booking_time: Option<i64>,
+    pub weight: Option<i64>,
+    pub priority: Option<i64>,
+    pub max_bookings_per_day: Option<i64>,
+    pub max_bookings_per_week: Option<i64>,
 }
 
 pub struct CreateBookingIntendInput {
@@ -52,6 +60,59 @@ pub struct CreateBookingIntendInput {
     pub interval: i64,
 }
 
+pub struct CreateBookingInput {
+    pub service_id: ID,
+    pub host_user_ids: Option<Vec<ID>>,
+    pub timestamp: DateTime<Utc>,
+    pub duration: i64,
+    pub interval: i64,
+    pub calendar_key: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub event_type: Option<String>,
+    pub location: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+pub struct CreateHoldInput {
+    pub service_id: ID,
+    pub host_user_ids: Option<Vec<ID>>,
+    pub timestamp: DateTime<Utc>,
+    pub duration: i64,
+    pub interval: i64,
+    pub ttl_minutes: i64,
+}
+
+pub struct ConfirmHoldInput {
+    pub service_id: ID,
+    pub hold_id: ID,
+    pub calendar_key: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub event_type: Option<String>,
+    pub location: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+pub struct ReleaseHoldInput {
+    pub service_id: ID,
+    pub hold_id: ID,
+}
+
+pub struct CancelBookingInput {
+    pub service_id: ID,
+    /// One of the events of the booking
+    pub event_id: ID,
+}
+
+pub struct RescheduleBookingInput {
+    pub service_id: ID,
+    /// One of the events of the booking
+    pub event_id: ID,
+    pub timestamp: DateTime<Utc>,
+    pub interval: i64,
+}
+
 pub struct RemoveBookingIntendInput {
     pub service_id: ID,
     pub timestamp: DateTime<Utc>,
@@ -77,11 +138,17 @@ pub struct UpdateServiceInput {
     pub service_id: ID,
     pub metadata: Option<serde_json::Value>,
     pub multi_person: Option<ServiceMultiPersonOptions>,
+    pub cancellation_cutoff: Option<i64>,
+    pub reschedule_cutoff: Option<i64>,
+    pub distribution_window: Option<i64>,
 }
 
 pub struct CreateServiceInput {
     pub metadata: Option<serde_json::Value>,
     pub multi_person: Option<ServiceMultiPersonOptions>,
+    pub cancellation_cutoff: Option<i64>,
+    pub reschedule_cutoff: Option<i64>,
+    pub distribution_window: Option<i64>,
 }
 
 impl ServiceClient {
@@ -146,6 +213,123 @@ impl ServiceClient {
             .await
     }
 
+    pub async fn create_booking(
+        &self,
+        input: CreateBookingInput,
+    ) -> APIResponse<create_service_booking::APIResponse> {
+        let body = create_service_booking::RequestBody {
+            host_user_ids: input.host_user_ids,
+            timestamp: input.timestamp,
+            duration: input.duration,
+            interval: input.interval,
+            calendar_key: input.calendar_key,
+            title: input.title,
+            description: input.description,
+            event_type: input.event_type,
+            location: input.location,
+            metadata: input.metadata,
+        };
+        self.base
+            .post(
+                body,
+                format!("service/{}/bookings", input.service_id),
+                StatusCode::CREATED,
+            )
+            .await
+    }
+
+    pub async fn cancel_booking(
+        &self,
+        input: CancelBookingInput,
+    ) -> APIResponse<cancel_service_booking::APIResponse> {
+        self.base
+            .post(
+                (),
+                format!(
+                    "service/{}/bookings/{}/cancel",
+                    input.service_id, input.event_id
+                ),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn reschedule_booking(
+        &self,
+        input: RescheduleBookingInput,
+    ) -> APIResponse<reschedule_service_booking::APIResponse> {
+        let body = reschedule_service_booking::RequestBody {
+            timestamp: input.timestamp,
+            interval: input.interval,
+        };
+        self.base
+            .post(
+                body,
+                format!(
+                    "service/{}/bookings/{}/reschedule",
+                    input.service_id, input.event_id
+                ),
+                StatusCode::OK,
+            )
+            .await
+    }
+
+    pub async fn create_hold(
+        &self,
+        input: CreateHoldInput,
+    ) -> APIResponse<create_service_hold::APIResponse> {
+        let body = create_service_hold::RequestBody {
+            host_user_ids: input.host_user_ids,
+            timestamp: input.timestamp,
+            duration: input.duration,
+            interval: input.interval,
+            ttl_minutes: input.ttl_minutes,
+        };
+        self.base
+            .post(
+                body,
+                format!("service/{}/holds", input.service_id),
+                StatusCode::CREATED,
+            )
+            .await
+    }
+
+    pub async fn confirm_hold(
+        &self,
+        input: ConfirmHoldInput,
+    ) -> APIResponse<confirm_service_hold::APIResponse> {
+        let body = confirm_service_hold::RequestBody {
+            calendar_key: input.calendar_key,
+            title: input.title,
+            description: input.description,
+            event_type: input.event_type,
+            location: input.location,
+            metadata: input.metadata,
+        };
+        self.base
+            .post(
+                body,
+                format!(
+                    "service/{}/holds/{}/confirm",
+                    input.service_id, input.hold_id
+                ),
+                StatusCode::CREATED,
+            )
+            .await
+    }
+
+    pub async fn release_hold(
+        &self,
+        input: ReleaseHoldInput,
+    ) -> APIResponse<release_service_hold::APIResponse> {
+        self.base
+            .delete(
+                format!("service/{}/holds/{}", input.service_id, input.hold_id),
+                StatusCode::OK,
+            )
+            .await
+    }
+
     pub async fn remove_booking_intend(
         &self,
         input: RemoveBookingIntendInput,
@@ -174,6 +358,9 @@ impl ServiceClient {
         let body = create_service::RequestBody {
             metadata: input.metadata,
             multi_person: input.multi_person,
+            cancellation_cutoff: input.cancellation_cutoff,
+            reschedule_cutoff: input.reschedule_cutoff,
+            distribution_window: input.distribution_window,
         };
         self.base
             .post(body, "service".into(), StatusCode::CREATED)
@@ -187,6 +374,9 @@ impl ServiceClient {
         let body = update_service::RequestBody {
             metadata: input.metadata,
             multi_person: input.multi_person,
+            cancellation_cutoff: input.cancellation_cutoff,
+            reschedule_cutoff: input.reschedule_cutoff,
+            distribution_window: input.distribution_window,
         };
         self.base
             .put(
@@ -234,6 +424,10 @@ impl ServiceClient {
             buffer_before: input.buffer_before,
             closest_booking_time: input.closest_booking_time,
             furthest_booking_time: input.furthest_booking_time,
+            weight: input.weight,
+            priority: input.priority,
+            max_bookings_per_day: input.max_bookings_per_day,
+            max_bookings_per_week: input.max_bookings_per_week,
         };
 
         self.base
@@ -257,6 +451,10 @@ impl ServiceClient {
             buffer_before: input.buffer_before,
             closest_booking_time: input.closest_booking_time,
             furthest_booking_time: input.furthest_booking_time,
+            weight: input.weight,
+            priority: input.priority,
+            max_bookings_per_day: input.max_bookings_per_day,
+            max_bookings_per_week: input.max_bookings_per_week,
         };
 
         self.base
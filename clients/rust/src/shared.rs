@@ -7,15 +7,21 @@ pub struct MetadataFindInput {
     pub limit: usize,
     pub skip: usize,
     pub metadata: KVMetadata,
+    /// Cursor of the page to get (the `next_cursor` of the previous page)
+    pub cursor: Option<String>,
 }
 
 impl MetadataFindInput {
     pub(crate) fn to_query(&self) -> Vec<(String, String)> {
-        vec![
+        let mut query = vec![
             ("skip".to_string(), self.skip.to_string()),
             ("limit".to_string(), self.limit.to_string()),
             ("key".to_string(), self.metadata.key.clone()),
             ("value".to_string(), self.metadata.value.clone()),
-        ]
+        ];
+        if let Some(cursor) = &self.cursor {
+            query.push(("cursor".to_string(), cursor.clone()));
+        }
+        query
     }
 }
@@ -0,0 +1,44 @@
+use chrono::{TimeDelta, Utc};
+use nittei_domain::WebhookSignatureError;
+use serde::de::DeserializeOwned;
+
+/// Default maximum age (in seconds) of the webhooks accepted by `verify_webhook`
+pub const DEFAULT_WEBHOOK_TOLERANCE_SECS: i64 = 5 * 60;
+
+/// Verify that a received webhook has been sent by the server
+///
+/// `signature_header` is the value of the `WEBHOOK_SIGNATURE_HEADER` header, `body` is the raw body
+/// of the request (before any parsing) and `key` is the webhook key of the account.
+/// Webhooks older than `DEFAULT_WEBHOOK_TOLERANCE_SECS` are rejected, to prevent replays.
+pub fn verify_webhook(
+    signature_header: &str,
+    body: &[u8],
+    key: &str,
+) -> Result<(), WebhookSignatureError> {
+    nittei_domain::verify_webhook_signature(
+        signature_header,
+        body,
+        key,
+        TimeDelta::seconds(DEFAULT_WEBHOOK_TOLERANCE_SECS),
+        Utc::now(),
+    )
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebhookError {
+    #[error(transparent)]
+    InvalidSignature(#[from] WebhookSignatureError),
+    #[error("Unable to parse the webhook payload: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+}
+
+/// Verify a received webhook (see `verify_webhook`) and parse its payload
+/// e.g. `EventWebhookPayload` for the changes of events, `AccountReminders` for the reminders
+pub fn parse_webhook<T: DeserializeOwned>(
+    signature_header: &str,
+    body: &[u8],
+    key: &str,
+) -> Result<T, WebhookError> {
+    verify_webhook(signature_header, body, key)?;
+    Ok(serde_json::from_slice(body)?)
+}
@@ -1,16 +1,28 @@
 use axum::{Extension, Json};
 use axum_valid::Valid;
-use nittei_api_structs::{account_search_events::*, dtos::CalendarEventDTO};
+use nittei_api_structs::{
+    account_search_events::*,
+    dtos::{CalendarEventDTO, CalendarEventHighlightDTO},
+};
 use nittei_domain::{
     Account,
+    AttendeeQuery,
+    CalendarEventCursor,
     CalendarEventSort,
+    Cursor,
     DateTimeQuery,
     ID,
     IDQuery,
     RecurrenceQuery,
     StringQuery,
 };
-use nittei_infra::{NitteiContext, SearchEventsForAccountParams, SearchEventsParams};
+use nittei_infra::{
+    NitteiContext,
+    SearchEventsForAccountParams,
+    SearchEventsParams,
+    SearchEventsResult,
+    TextSearchQuery,
+};
 use nittei_utils::config::APP_CONFIG;
 
 use crate::{
@@ -56,7 +68,13 @@ pub async fn account_search_events_controller(
         metadata: body.filter.metadata.take(),
         created_at: body.filter.created_at.take(),
         updated_at: body.filter.updated_at.take(),
+        attendee: body.filter.attendee.take(),
+        text: body.filter.text.take().map(|text| TextSearchQuery {
+            text,
+            language: account.settings.search_language,
+        }),
         sort: body.sort.take(),
+        cursor: body.cursor.take(),
         limit: body
             .limit
             .or(Some(APP_CONFIG.max_events_returned_by_search)),
@@ -64,7 +82,13 @@ pub async fn account_search_events_controller(
 
     execute(usecase, &ctx)
         .await
-        .map(|events| Json(SearchEventsAPIResponse::new(events.events)))
+        .map(|res| {
+            Json(SearchEventsAPIResponse::new(
+                res.events,
+                res.highlights,
+                res.next_cursor,
+            ))
+        })
         .map_err(NitteiError::from)
 }
 
@@ -104,7 +128,7 @@ pub struct AccountSearchEventsUseCase {
     pub original_start_time: Option<DateTimeQuery>,
 
     /// Optional recurrence query
-    /// This allows to filter on the existence or not of a recurrence, or the existence of a recurrence at a specific date
+    /// This allows to filter on the existence or not of a recurrence, the existence of a recurrence at a specific date, or its options
     pub recurrence: Option<RecurrenceQuery>,
 
     /// Optional list of metadata key-value pairs
@@ -116,9 +140,18 @@ pub struct AccountSearchEventsUseCase {
     /// Optional query on updated at - "lower than or equal", or "great than or equal" (UTC)
     pub updated_at: Option<DateTimeQuery>,
 
+    /// Optional query on the attendees
+    pub attendee: Option<AttendeeQuery>,
+
+    /// Optional text to search in the title, description and location
+    pub text: Option<TextSearchQuery>,
+
     /// Optional sort
     pub sort: Option<CalendarEventSort>,
 
+    /// Optional cursor of the page to get
+    pub cursor: Option<String>,
+
     /// Optional limit
     pub limit: Option<u16>,
 }
@@ -126,6 +159,8 @@ pub struct AccountSearchEventsUseCase {
 #[derive(Debug)]
 pub struct UseCaseResponse {
     pub events: Vec<CalendarEventDTO>,
+    pub highlights: Option<Vec<CalendarEventHighlightDTO>>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug)]
@@ -163,6 +198,22 @@ impl UseCase for AccountSearchEventsUseCase {
             }
         }
 
+        // The events are sorted by start time by default, so that the pages are stable
+        let sort = self.sort.take().unwrap_or_default();
+        let cursor = match &self.cursor {
+            Some(cursor) => {
+                let cursor = CalendarEventCursor::decode(cursor)
+                    .map_err(|e| UseCaseError::BadRequest(e.to_string()))?;
+                if !cursor.matches(&sort, self.text.is_some()) {
+                    return Err(UseCaseError::BadRequest(
+                        "The cursor doesn't match the sort of the search".into(),
+                    ));
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+
         let res = ctx
             .repos
             .events
@@ -183,20 +234,50 @@ impl UseCase for AccountSearchEventsUseCase {
                     metadata: self.metadata.take(),
                     created_at: self.created_at.take(),
                     updated_at: self.updated_at.take(),
+                    attendee: self.attendee.take(),
+                    text: self.text.clone(),
                 },
-                sort: self.sort.take(),
+                sort: Some(sort),
+                cursor,
                 limit: self.limit.take(),
             })
             .await;
 
-        match res {
-            Ok(events) => Ok(UseCaseResponse {
-                events: events.into_iter().map(CalendarEventDTO::new).collect(),
-            }),
-            Err(err) => {
-                tracing::error!("Error searching events for account: {:?}", err);
-                Err(UseCaseError::InternalError)
+        let SearchEventsResult {
+            events,
+            next_cursor,
+        } = res.map_err(|err| {
+            tracing::error!("Error searching events for account: {:?}", err);
+            UseCaseError::InternalError
+        })?;
+
+        // The fragments matching the text are only generated for the events returned
+        let highlights = match &self.text {
+            Some(text) => {
+                let event_ids = events.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+                let highlights = ctx
+                    .repos
+                    .events
+                    .find_text_highlights(&event_ids, text)
+                    .await
+                    .map_err(|err| {
+                        tracing::error!("Error finding the text highlights of events: {:?}", err);
+                        UseCaseError::InternalError
+                    })?;
+                Some(
+                    highlights
+                        .into_iter()
+                        .map(CalendarEventHighlightDTO::new)
+                        .collect(),
+                )
             }
-        }
+            None => None,
+        };
+
+        Ok(UseCaseResponse {
+            events: events.into_iter().map(CalendarEventDTO::new).collect(),
+            highlights,
+            next_cursor: next_cursor.map(|cursor| cursor.encode()),
+        })
     }
 }
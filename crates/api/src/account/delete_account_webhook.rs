@@ -25,6 +25,7 @@ pub async fn delete_account_webhook_controller(
     let usecase = SetAccountWebhookUseCase {
         account,
         webhook_url: None,
+        event_types: None,
     };
 
     execute(usecase, &ctx)
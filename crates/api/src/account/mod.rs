@@ -4,7 +4,10 @@ pub mod create_account;
 pub mod delete_account_webhook;
 pub mod get_account;
 pub mod remove_account_integration;
+pub mod rotate_account_webhook_key;
 pub mod set_account_pub_key;
+pub mod set_account_rate_limits;
+pub mod set_account_search_language;
 pub mod set_account_webhook;
 
 use account_search_events::account_search_events_controller;
@@ -17,7 +20,10 @@ use create_account::create_account_controller;
 use delete_account_webhook::delete_account_webhook_controller;
 use get_account::get_account_controller;
 use remove_account_integration::remove_account_integration_controller;
+use rotate_account_webhook_key::rotate_account_webhook_key_controller;
 use set_account_pub_key::set_account_pub_key_controller;
+use set_account_rate_limits::set_account_rate_limits_controller;
+use set_account_search_language::set_account_search_language_controller;
 use set_account_webhook::set_account_webhook_controller;
 use utoipa_axum::router::OpenApiRouter;
 
@@ -34,8 +40,23 @@ pub fn configure_routes() -> OpenApiRouter {
         .route("/account", get(get_account_controller))
         // Set the public key for the account
         .route("/account/pubkey", put(set_account_pub_key_controller))
+        // Set the limits of the requests of the account and of its users
+        .route(
+            "/account/rate-limits",
+            put(set_account_rate_limits_controller),
+        )
+        // Set the language of the texts of the events, for the text search
+        .route(
+            "/account/search-language",
+            put(set_account_search_language_controller),
+        )
         // Set the webhook for the account
         .route("/account/webhook", put(set_account_webhook_controller))
+        // Rotate the key used to sign the webhooks of the account
+        .route(
+            "/account/webhook/rotate-key",
+            post(rotate_account_webhook_key_controller),
+        )
         // Delete the webhook for the account
         .route(
             "/account/webhook",
@@ -0,0 +1,99 @@
+use axum::{Extension, Json};
+use axum_valid::Valid;
+use chrono::TimeDelta;
+use nittei_api_structs::rotate_account_webhook_key::{
+    APIResponse,
+    RotateAccountWebhookKeyRequestBody,
+};
+use nittei_domain::Account;
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+/// Default number of seconds during which the previous key stays valid
+const DEFAULT_GRACE_PERIOD_SECS: u32 = 60 * 60 * 24;
+
+#[utoipa::path(
+    post,
+    tag = "Account",
+    path = "/api/v1/account/webhook/rotate-key",
+    summary = "Rotate the key used to sign the webhooks of an account",
+    security(
+        ("api_key" = [])
+    ),
+    request_body(
+        content = RotateAccountWebhookKeyRequestBody,
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn rotate_account_webhook_key_controller(
+    Extension(account): Extension<Account>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Valid<Json<RotateAccountWebhookKeyRequestBody>>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = RotateAccountWebhookKeyUseCase {
+        account,
+        grace_period_secs: body.grace_period_secs.unwrap_or(DEFAULT_GRACE_PERIOD_SECS),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|account| Json(APIResponse::new(account)))
+        .map_err(NitteiError::from)
+}
+
+/// Replace the key of the webhook by a new one
+/// The current key is still used to sign the webhooks during the grace period
+#[derive(Debug)]
+pub struct RotateAccountWebhookKeyUseCase {
+    pub account: Account,
+    pub grace_period_secs: u32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UseCaseError {
+    WebhookNotFound,
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::WebhookNotFound => {
+                Self::NotFound("The account has no webhook".to_string())
+            }
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for RotateAccountWebhookKeyUseCase {
+    type Response = Account;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "RotateAccountWebhookKey";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let rotated = self.account.settings.rotate_webhook_key(
+            TimeDelta::seconds(self.grace_period_secs.into()),
+            ctx.sys.get_timestamp(),
+        );
+        if !rotated {
+            return Err(UseCaseError::WebhookNotFound);
+        }
+
+        ctx.repos
+            .accounts
+            .save(&self.account)
+            .await
+            .map(|_| self.account.clone())
+            .map_err(|_| UseCaseError::StorageError)
+    }
+}
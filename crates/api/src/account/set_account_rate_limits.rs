@@ -0,0 +1,85 @@
+use axum::{Extension, Json};
+use axum_valid::Valid;
+use nittei_api_structs::set_account_rate_limits::{APIResponse, SetAccountRateLimitsRequestBody};
+use nittei_domain::{Account, AccountRateLimits};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    put,
+    tag = "Account",
+    path = "/api/v1/account/rate-limits",
+    summary = "Set the limits of the requests of an account",
+    security(
+        ("api_key" = [])
+    ),
+    request_body(
+        content = SetAccountRateLimitsRequestBody,
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+/// Set the limits of the requests of an account, and of each of its users
+///
+/// They override the default limits of the server, which are used for the limits not set.
+/// The requests over the limits are rejected with a 429 status code, and a `Retry-After` header.
+pub async fn set_account_rate_limits_controller(
+    Extension(ctx): Extension<NitteiContext>,
+    Extension(account): Extension<Account>,
+    body: Valid<Json<SetAccountRateLimitsRequestBody>>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = SetAccountRateLimitsUseCase {
+        account,
+        rate_limits: AccountRateLimits {
+            account: body.account,
+            user: body.user,
+        },
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|account| Json(APIResponse::new(account)))
+        .map_err(NitteiError::from)
+}
+
+#[derive(Debug)]
+struct SetAccountRateLimitsUseCase {
+    pub account: Account,
+    pub rate_limits: AccountRateLimits,
+}
+
+#[derive(Debug)]
+enum UseCaseError {
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for SetAccountRateLimitsUseCase {
+    type Response = Account;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "SetAccountRateLimits";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        self.account.settings.rate_limits = self.rate_limits;
+
+        match ctx.repos.accounts.save(&self.account).await {
+            Ok(_) => Ok(self.account.clone()),
+            Err(_) => Err(UseCaseError::StorageError),
+        }
+    }
+}
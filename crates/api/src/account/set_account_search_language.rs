@@ -0,0 +1,86 @@
+use axum::{Extension, Json};
+use axum_valid::Valid;
+use nittei_api_structs::set_account_search_language::{
+    APIResponse,
+    SetAccountSearchLanguageRequestBody,
+};
+use nittei_domain::{Account, SearchLanguage};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    put,
+    tag = "Account",
+    path = "/api/v1/account/search-language",
+    summary = "Set the language of the texts of the events of an account",
+    security(
+        ("api_key" = [])
+    ),
+    request_body(
+        content = SetAccountSearchLanguageRequestBody,
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+/// Set the language of the texts (title, description and location) of the events of an account
+///
+/// The text search removes the stop words and stems the words of this language.
+/// The existing events of the account are indexed again in the new language.
+pub async fn set_account_search_language_controller(
+    Extension(ctx): Extension<NitteiContext>,
+    Extension(account): Extension<Account>,
+    body: Valid<Json<SetAccountSearchLanguageRequestBody>>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = SetAccountSearchLanguageUseCase {
+        account,
+        search_language: body.search_language,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|account| Json(APIResponse::new(account)))
+        .map_err(NitteiError::from)
+}
+
+#[derive(Debug)]
+struct SetAccountSearchLanguageUseCase {
+    pub account: Account,
+    pub search_language: SearchLanguage,
+}
+
+#[derive(Debug)]
+enum UseCaseError {
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for SetAccountSearchLanguageUseCase {
+    type Response = Account;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "SetAccountSearchLanguage";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        self.account.settings.search_language = self.search_language;
+
+        // The events of the account are indexed again by the database
+        match ctx.repos.accounts.save(&self.account).await {
+            Ok(_) => Ok(self.account.clone()),
+            Err(_) => Err(UseCaseError::StorageError),
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use axum::{Extension, Json};
 use axum_valid::Valid;
 use nittei_api_structs::set_account_webhook::{APIResponse, SetAccountWebhookRequestBody};
-use nittei_domain::Account;
+use nittei_domain::{Account, WebhookEventType};
 use nittei_infra::NitteiContext;
 
 use crate::{
@@ -32,6 +32,7 @@ pub async fn set_account_webhook_controller(
     let usecase = SetAccountWebhookUseCase {
         account,
         webhook_url: Some(body.webhook_url.clone()),
+        event_types: body.event_types.clone(),
     };
 
     execute(usecase, &ctx)
@@ -44,6 +45,8 @@ pub async fn set_account_webhook_controller(
 pub struct SetAccountWebhookUseCase {
     pub account: Account,
     pub webhook_url: Option<String>,
+    /// Changes of events sent to the webhook, the current ones are kept if not provided
+    pub event_types: Option<Vec<WebhookEventType>>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -84,6 +87,10 @@ impl UseCase for SetAccountWebhookUseCase {
             )));
         }
 
+        if let Some(event_types) = self.event_types.clone() {
+            self.account.settings.set_webhook_event_types(event_types);
+        }
+
         match ctx.repos.accounts.save(&self.account).await {
             Ok(_) => Ok(self.account.clone()),
             Err(_) => Err(UseCaseError::StorageError),
@@ -105,6 +112,7 @@ mod tests {
         for bad_uri in bad_uris {
             let mut use_case = SetAccountWebhookUseCase {
                 webhook_url: Some(bad_uri.to_string()),
+                event_types: None,
                 account: Default::default(),
             };
             let res = use_case.execute(&ctx).await;
@@ -129,6 +137,7 @@ mod tests {
         for valid_uri in valid_uris {
             let mut use_case = SetAccountWebhookUseCase {
                 webhook_url: Some(valid_uri.to_string()),
+                event_types: None,
                 account: Default::default(),
             };
             let res = use_case.execute(&ctx).await;
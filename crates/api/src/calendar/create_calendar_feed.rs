@@ -0,0 +1,147 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use nittei_api_structs::create_calendar_feed::{
+    APIResponse,
+    CreateCalendarFeedRequestBody,
+    PathParams,
+};
+use nittei_domain::{Account, CalendarFeed, ID, User};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::{
+        auth::{Permission, Policy, account_can_modify_calendar},
+        usecase::{PermissionBoundary, UseCase, execute, execute_with_policy},
+    },
+};
+
+#[utoipa::path(
+    post,
+    tag = "Calendar",
+    path = "/api/v1/user/calendar/{calendar_id}/ical/feeds",
+    summary = "Create a public iCalendar feed of a calendar (admin only)",
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("calendar_id" = ID, Path, description = "The id of the calendar to create a feed for"),
+    ),
+    request_body(
+        content = CreateCalendarFeedRequestBody,
+    ),
+    responses(
+        (status = 201, body = APIResponse)
+    )
+)]
+/// Create a public iCalendar feed of a calendar
+///
+/// The feed can be subscribed to from calendar applications, without authentication,
+/// through the URL containing its token. Deleting the feed revokes the access.
+pub async fn create_calendar_feed_admin_controller(
+    Extension(account): Extension<Account>,
+    path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Option<Json<CreateCalendarFeedRequestBody>>,
+) -> Result<(StatusCode, Json<APIResponse>), NitteiError> {
+    let cal = account_can_modify_calendar(&account, &path.calendar_id, &ctx).await?;
+
+    let usecase = CreateCalendarFeedUseCase {
+        user_id: cal.user_id,
+        calendar_id: cal.id,
+        mask_events: body.and_then(|body| body.0.mask_events).unwrap_or(false),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|feed| (StatusCode::CREATED, Json(APIResponse::new(feed))))
+        .map_err(NitteiError::from)
+}
+
+#[utoipa::path(
+    post,
+    tag = "Calendar",
+    path = "/api/v1/calendar/{calendar_id}/ical/feeds",
+    summary = "Create a public iCalendar feed of a calendar",
+    params(
+        ("calendar_id" = ID, Path, description = "The id of the calendar to create a feed for"),
+    ),
+    request_body(
+        content = CreateCalendarFeedRequestBody,
+    ),
+    responses(
+        (status = 201, body = APIResponse)
+    )
+)]
+pub async fn create_calendar_feed_controller(
+    Extension((user, policy)): Extension<(User, Policy)>,
+    path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Option<Json<CreateCalendarFeedRequestBody>>,
+) -> Result<(StatusCode, Json<APIResponse>), NitteiError> {
+    let usecase = CreateCalendarFeedUseCase {
+        user_id: user.id,
+        calendar_id: path.calendar_id.clone(),
+        mask_events: body.and_then(|body| body.0.mask_events).unwrap_or(false),
+    };
+
+    execute_with_policy(usecase, &policy, &ctx)
+        .await
+        .map(|feed| (StatusCode::CREATED, Json(APIResponse::new(feed))))
+        .map_err(NitteiError::from)
+}
+
+#[derive(Debug)]
+pub struct CreateCalendarFeedUseCase {
+    pub user_id: ID,
+    pub calendar_id: ID,
+    pub mask_events: bool,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(calendar_id) => Self::NotFound(format!(
+                "The calendar with id: {calendar_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for CreateCalendarFeedUseCase {
+    type Response = CalendarFeed;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "CreateCalendarFeed";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let calendar = match ctx.repos.calendars.find(&self.calendar_id).await {
+            Ok(Some(calendar)) if calendar.user_id == self.user_id => calendar,
+            Ok(_) => return Err(UseCaseError::NotFound(self.calendar_id.clone())),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        let feed = CalendarFeed::new(&calendar, self.mask_events, ctx.sys.get_timestamp());
+        ctx.repos
+            .calendar_feeds
+            .insert(&feed)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        Ok(feed)
+    }
+}
+
+impl PermissionBoundary for CreateCalendarFeedUseCase {
+    fn permissions(&self) -> Vec<Permission> {
+        vec![Permission::UpdateCalendar]
+    }
+}
@@ -0,0 +1,136 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::delete_calendar_feed::{APIResponse, PathParams};
+use nittei_domain::{Account, CalendarFeed, ID, User};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::{
+        auth::{Permission, Policy, account_can_modify_calendar},
+        usecase::{PermissionBoundary, UseCase, execute, execute_with_policy},
+    },
+};
+
+#[utoipa::path(
+    delete,
+    tag = "Calendar",
+    path = "/api/v1/user/calendar/{calendar_id}/ical/feeds/{feed_id}",
+    summary = "Revoke a public iCalendar feed of a calendar (admin only)",
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("calendar_id" = ID, Path, description = "The id of the calendar of the feed"),
+        ("feed_id" = ID, Path, description = "The id of the feed to revoke"),
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn delete_calendar_feed_admin_controller(
+    Extension(account): Extension<Account>,
+    path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let cal = account_can_modify_calendar(&account, &path.calendar_id, &ctx).await?;
+
+    let usecase = DeleteCalendarFeedUseCase {
+        user_id: cal.user_id,
+        calendar_id: cal.id,
+        feed_id: path.feed_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|feed| Json(APIResponse::new(feed)))
+        .map_err(NitteiError::from)
+}
+
+#[utoipa::path(
+    delete,
+    tag = "Calendar",
+    path = "/api/v1/calendar/{calendar_id}/ical/feeds/{feed_id}",
+    summary = "Revoke a public iCalendar feed of a calendar",
+    params(
+        ("calendar_id" = ID, Path, description = "The id of the calendar of the feed"),
+        ("feed_id" = ID, Path, description = "The id of the feed to revoke"),
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn delete_calendar_feed_controller(
+    Extension((user, policy)): Extension<(User, Policy)>,
+    path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = DeleteCalendarFeedUseCase {
+        user_id: user.id,
+        calendar_id: path.calendar_id.clone(),
+        feed_id: path.feed_id.clone(),
+    };
+
+    execute_with_policy(usecase, &policy, &ctx)
+        .await
+        .map(|feed| Json(APIResponse::new(feed)))
+        .map_err(NitteiError::from)
+}
+
+#[derive(Debug)]
+pub struct DeleteCalendarFeedUseCase {
+    pub user_id: ID,
+    pub calendar_id: ID,
+    pub feed_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(feed_id) => Self::NotFound(format!(
+                "The calendar feed with id: {feed_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for DeleteCalendarFeedUseCase {
+    type Response = CalendarFeed;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "DeleteCalendarFeed";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let feed = match ctx.repos.calendar_feeds.find(&self.feed_id).await {
+            Ok(Some(feed))
+                if feed.calendar_id == self.calendar_id && feed.user_id == self.user_id =>
+            {
+                feed
+            }
+            Ok(_) => return Err(UseCaseError::NotFound(self.feed_id.clone())),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        ctx.repos
+            .calendar_feeds
+            .delete(&feed.id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        Ok(feed)
+    }
+}
+
+impl PermissionBoundary for DeleteCalendarFeedUseCase {
+    fn permissions(&self) -> Vec<Permission> {
+        vec![Permission::UpdateCalendar]
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::anyhow;
 use axum::{
@@ -9,7 +9,7 @@ use axum::{
 };
 use chrono::{DateTime, Months, Utc};
 use nittei_api_structs::get_calendar_events_ical::{PathParams, QueryParams};
-use nittei_domain::{Account, ID, TimeSpan, User, generate_ical_content};
+use nittei_domain::{Account, Calendar, CalendarEvent, ID, TimeSpan, User, generate_ical_content};
 use nittei_infra::NitteiContext;
 use tracing::error;
 
@@ -22,9 +22,8 @@ use crate::{
 };
 
 /// Notes
-/// - Right now it doesn't fetch all the ongoing recurring events (even though it should)
 /// - It doesn't limit the number of events to export - ideally it should
-/// - The endpoints are not public - ideally users should be able to expose the calendars to the public via a public link (but hard to find)
+/// - These endpoints are not public, calendars are exposed publicly through their feeds (see `calendar_feed`)
 
 #[utoipa::path(
     get,
@@ -36,8 +35,8 @@ use crate::{
     ),
     params(
         ("calendar_id" = ID, Path, description = "The id of the calendar to export"),
-        ("start_time" = Option<DateTime<Utc>>, Query, description = "The start time of the events to export"),
-        ("end_time" = Option<DateTime<Utc>>, Query, description = "The end time of the events to export"),
+        ("startTime" = Option<DateTime<Utc>>, Query, description = "The start time of the events to export"),
+        ("endTime" = Option<DateTime<Utc>>, Query, description = "The end time of the events to export"),
         ("limit" = Option<usize>, Query, description = "The limit for the number of events to export"),
         ("offset" = Option<usize>, Query, description = "The offset for the events to export"),
     ),
@@ -52,8 +51,8 @@ use crate::{
 ///
 /// # Parameters
 /// - `calendar_id`: The ID of the calendar to export
-/// - `start_time`: The start time for the export range (UTC)
-/// - `end_time`: The end time for the export range (UTC)
+/// - `startTime`: The start time for the export range (UTC)
+/// - `endTime`: The end time for the export range (UTC)
 ///
 /// # Returns
 /// Returns an iCalendar file with Content-Type: text/calendar
@@ -98,8 +97,8 @@ pub async fn export_calendar_ical_admin_controller(
     summary = "Export calendar events as iCalendar format",
     params(
         ("calendar_id" = ID, Path, description = "The id of the calendar to export"),
-        ("start_time" = Option<DateTime<Utc>>, Query, description = "The start time of the events to export"),
-        ("end_time" = Option<DateTime<Utc>>, Query, description = "The end time of the events to export"),
+        ("startTime" = Option<DateTime<Utc>>, Query, description = "The start time of the events to export"),
+        ("endTime" = Option<DateTime<Utc>>, Query, description = "The end time of the events to export"),
     ),
     responses(
         (status = 200, description = "iCalendar file", content_type = "text/calendar")
@@ -112,8 +111,8 @@ pub async fn export_calendar_ical_admin_controller(
 ///
 /// # Parameters
 /// - `calendar_id`: The ID of the calendar to export (must belong to the authenticated user)
-/// - `start_time`: The start time for the export range (UTC)
-/// - `end_time`: The end time for the export range (UTC)
+/// - `startTime`: The start time for the export range (UTC)
+/// - `endTime`: The end time for the export range (UTC)
 ///
 /// # Returns
 /// Returns an iCalendar file with Content-Type: text/calendar
@@ -242,11 +241,7 @@ impl UseCase for ExportCalendarIcalUseCase {
             ),
         );
 
-        // Get events for the calendar in the specified time range
-        let events = ctx
-            .repos
-            .events
-            .find_by_calendar(&self.calendar_id, Some(timespan.clone()))
+        let events = find_ical_events(&self.calendar_id, timespan, ctx)
             .await
             .map_err(|err| {
                 error!(
@@ -256,41 +251,89 @@ impl UseCase for ExportCalendarIcalUseCase {
                 UseCaseError::InternalError
             })?;
 
-        // Separate normal events, recurring events, and exceptions
-        let (normal_events, recurring_events, exceptions) = events.into_iter().fold(
-            (Vec::new(), Vec::new(), Vec::new()),
-            |(mut normal, mut recurring, mut exceptions), event| {
-                if event.recurring_event_id.is_some() {
-                    exceptions.push(event);
-                } else if event.recurrence.is_some() {
-                    recurring.push(event);
-                } else {
-                    normal.push(event);
-                }
-                (normal, recurring, exceptions)
-            },
+        let ical_content = calendar_ical_content(&calendar, events);
+
+        Ok(UseCaseResponse { ical_content })
+    }
+}
+
+/// Find the events of a calendar to export for the timespan
+///
+/// This includes the recurring events started before the timespan and still ongoing,
+/// and the exceptions of the recurring events, even when they have been moved outside of the timespan
+pub(crate) async fn find_ical_events(
+    calendar_id: &ID,
+    timespan: TimeSpan,
+    ctx: &NitteiContext,
+) -> anyhow::Result<Vec<CalendarEvent>> {
+    let mut events = ctx
+        .repos
+        .events
+        .find_by_calendar(calendar_id, Some(timespan.clone()))
+        .await?;
+
+    let recurring_event_ids = events
+        .iter()
+        .filter(|e| e.recurring_event_id.is_none() && e.recurrence.is_some())
+        .map(|e| e.id.clone())
+        .collect::<Vec<_>>();
+    if !recurring_event_ids.is_empty() {
+        let found_ids = events.iter().map(|e| e.id.clone()).collect::<HashSet<_>>();
+        let exceptions = ctx
+            .repos
+            .events
+            .find_by_recurring_event_ids_for_timespan(&recurring_event_ids, timespan)
+            .await?;
+        events.extend(
+            exceptions
+                .into_iter()
+                .filter(|e| !found_ids.contains(&e.id)),
         );
+    }
 
-        // Generate map of exceptions for recurring events
-        let mut map_recurring_event_id_to_exceptions = HashMap::new();
+    Ok(events)
+}
 
-        for event in &exceptions {
-            if let Some(recurring_event_id) = &event.recurring_event_id {
-                map_recurring_event_id_to_exceptions
-                    .entry(recurring_event_id)
-                    .or_insert_with(Vec::new)
-                    .push(event.clone());
+/// Generate the iCalendar content of the events of a calendar
+pub(crate) fn calendar_ical_content(calendar: &Calendar, mut events: Vec<CalendarEvent>) -> String {
+    // Stable order, so that the same events always give the same content
+    events.sort_by(|a, b| {
+        a.start_time
+            .cmp(&b.start_time)
+            .then_with(|| a.id.as_ref().cmp(b.id.as_ref()))
+    });
+
+    // Separate normal events, recurring events, and exceptions
+    let (normal_events, recurring_events, exceptions) = events.into_iter().fold(
+        (Vec::new(), Vec::new(), Vec::new()),
+        |(mut normal, mut recurring, mut exceptions), event| {
+            if event.recurring_event_id.is_some() {
+                exceptions.push(event);
+            } else if event.recurrence.is_some() {
+                recurring.push(event);
+            } else {
+                normal.push(event);
             }
-        }
+            (normal, recurring, exceptions)
+        },
+    );
 
-        // Generate iCalendar content
-        let ical_content = generate_ical_content(
-            &calendar,
-            &normal_events,
-            &recurring_events,
-            &map_recurring_event_id_to_exceptions,
-        );
+    // Generate map of exceptions for recurring events
+    let mut map_recurring_event_id_to_exceptions = HashMap::new();
 
-        Ok(UseCaseResponse { ical_content })
+    for event in &exceptions {
+        if let Some(recurring_event_id) = &event.recurring_event_id {
+            map_recurring_event_id_to_exceptions
+                .entry(recurring_event_id)
+                .or_insert_with(Vec::new)
+                .push(event.clone());
+        }
     }
+
+    generate_ical_content(
+        calendar,
+        &normal_events,
+        &recurring_events,
+        &map_recurring_event_id_to_exceptions,
+    )
 }
@@ -1,4 +1,10 @@
-use axum::{Extension, Json, extract::Path};
+use axum::{
+    Extension,
+    Json,
+    extract::Path,
+    http::header,
+    response::{IntoResponse, Response},
+};
 use nittei_api_structs::get_calendar::{APIResponse, PathParams};
 use nittei_domain::{Account, Calendar, ID, User};
 use nittei_infra::NitteiContext;
@@ -7,6 +13,7 @@ use crate::{
     error::NitteiError,
     shared::{
         auth::{Policy, account_can_modify_calendar},
+        etag::etag,
         usecase::{UseCase, execute},
     },
 };
@@ -23,14 +30,16 @@ use crate::{
         ("calendar_id" = ID, Path, description = "The id of the calendar to get"),
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "Version of the calendar, to use in the If-Match header when updating it")
+        ))
     )
 )]
 pub async fn get_calendar_admin_controller(
     Extension(account): Extension<Account>,
     path: Path<PathParams>,
     Extension(ctx): Extension<NitteiContext>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let cal = account_can_modify_calendar(&account, &path.calendar_id, &ctx).await?;
 
     let usecase = GetCalendarUseCase {
@@ -40,7 +49,10 @@ pub async fn get_calendar_admin_controller(
 
     execute(usecase, &ctx)
         .await
-        .map(|calendar| Json(APIResponse::new(calendar)))
+        .map(|calendar| {
+            let etag = etag(calendar.version);
+            ([(header::ETAG, etag)], Json(APIResponse::new(calendar))).into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -53,14 +65,16 @@ pub async fn get_calendar_admin_controller(
         ("calendar_id" = ID, Path, description = "The id of the calendar to get"),
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "Version of the calendar, to use in the If-Match header when updating it")
+        ))
     )
 )]
 pub async fn get_calendar_controller(
     Extension((user, _policy)): Extension<(User, Policy)>,
     path: Path<PathParams>,
     Extension(ctx): Extension<NitteiContext>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let usecase = GetCalendarUseCase {
         user_id: user.id.clone(),
         calendar_id: path.calendar_id.clone(),
@@ -68,7 +82,10 @@ pub async fn get_calendar_controller(
 
     execute(usecase, &ctx)
         .await
-        .map(|calendar| Json(APIResponse::new(calendar)))
+        .map(|calendar| {
+            let etag = etag(calendar.version);
+            ([(header::ETAG, etag)], Json(APIResponse::new(calendar))).into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -0,0 +1,144 @@
+use axum::{
+    Extension,
+    extract::Path,
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Duration;
+use nittei_api_structs::get_calendar_feed_ical::PathParams;
+use nittei_domain::{CalendarFeed, TimeSpan};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+use tracing::error;
+
+use super::export_ical::{calendar_ical_content, find_ical_events};
+use crate::{
+    error::NitteiError,
+    shared::{
+        etag::if_none_match,
+        usecase::{UseCase, execute},
+    },
+};
+
+#[utoipa::path(
+    get,
+    tag = "Calendar",
+    path = "/api/v1/ical/{token}.ics",
+    summary = "Get the public iCalendar feed of a calendar",
+    params(
+        ("token" = String, Path, description = "The token of the feed"),
+    ),
+    responses(
+        (status = 200, description = "iCalendar file", content_type = "text/calendar", headers(
+            ("ETag" = String, description = "Version of the content, to use in the If-None-Match header")
+        )),
+        (status = 304, description = "The content matching the If-None-Match header didn't change"),
+    )
+)]
+/// Get the public iCalendar feed of a calendar
+///
+/// This endpoint doesn't require authentication, the token of the feed grants the access.
+/// It returns the events from `ical_feed.past_days` ago to `ical_feed.future_days` ahead (see the config),
+/// including the recurring events still ongoing.
+pub async fn get_calendar_feed_ical_controller(
+    path: Path<PathParams>,
+    headers: HeaderMap,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Response, NitteiError> {
+    // Calendar applications expect the URL to end with the extension of the file
+    let Some(token) = path.token.strip_suffix(".ics") else {
+        return Err(UseCaseError::NotFound.into());
+    };
+
+    let usecase = GetCalendarFeedIcalUseCase {
+        token: token.to_string(),
+    };
+
+    let ical_content = execute(usecase, &ctx).await.map_err(NitteiError::from)?;
+
+    let etag = CalendarFeed::etag(&ical_content);
+    let etag_header = HeaderValue::from_str(&etag).map_err(|_| NitteiError::InternalError)?;
+    if if_none_match(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag_header)]).into_response());
+    }
+
+    let headers = [
+        (
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/calendar; charset=utf-8"),
+        ),
+        (header::ETAG, etag_header),
+        (header::CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+    ];
+
+    Ok((StatusCode::OK, headers, ical_content).into_response())
+}
+
+#[derive(Debug)]
+pub struct GetCalendarFeedIcalUseCase {
+    pub token: String,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound,
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            // Unknown and revoked feeds look the same
+            UseCaseError::NotFound => Self::NotFound("The calendar feed was not found.".into()),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for GetCalendarFeedIcalUseCase {
+    type Response = String;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "GetCalendarFeedIcal";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let feed = ctx
+            .repos
+            .calendar_feeds
+            .find_by_token(&self.token)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or(UseCaseError::NotFound)?;
+
+        let calendar = ctx
+            .repos
+            .calendars
+            .find(&feed.calendar_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or(UseCaseError::NotFound)?;
+
+        let now = ctx.sys.get_timestamp();
+        let timespan = TimeSpan::new(
+            now - Duration::days(APP_CONFIG.ical_feed.past_days),
+            now + Duration::days(APP_CONFIG.ical_feed.future_days),
+        );
+
+        let mut events = find_ical_events(&calendar.id, timespan, ctx)
+            .await
+            .map_err(|err| {
+                error!(
+                    "[get_calendar_feed_ical] Got an error while getting the events: {:?}",
+                    err
+                );
+                UseCaseError::StorageError
+            })?;
+        for event in &mut events {
+            feed.mask(event);
+        }
+
+        Ok(calendar_ical_content(&calendar, events))
+    }
+}
@@ -0,0 +1,119 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::get_calendar_feeds::{APIResponse, PathParams};
+use nittei_domain::{Account, CalendarFeed, ID, User};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::{
+        auth::{Policy, account_can_modify_calendar},
+        usecase::{UseCase, execute},
+    },
+};
+
+#[utoipa::path(
+    get,
+    tag = "Calendar",
+    path = "/api/v1/user/calendar/{calendar_id}/ical/feeds",
+    summary = "List the public iCalendar feeds of a calendar (admin only)",
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("calendar_id" = ID, Path, description = "The id of the calendar to list the feeds of"),
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn get_calendar_feeds_admin_controller(
+    Extension(account): Extension<Account>,
+    path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let cal = account_can_modify_calendar(&account, &path.calendar_id, &ctx).await?;
+
+    let usecase = GetCalendarFeedsUseCase {
+        user_id: cal.user_id,
+        calendar_id: cal.id,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|feeds| Json(APIResponse::new(feeds)))
+        .map_err(NitteiError::from)
+}
+
+#[utoipa::path(
+    get,
+    tag = "Calendar",
+    path = "/api/v1/calendar/{calendar_id}/ical/feeds",
+    summary = "List the public iCalendar feeds of a calendar",
+    params(
+        ("calendar_id" = ID, Path, description = "The id of the calendar to list the feeds of"),
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn get_calendar_feeds_controller(
+    Extension((user, _policy)): Extension<(User, Policy)>,
+    path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = GetCalendarFeedsUseCase {
+        user_id: user.id,
+        calendar_id: path.calendar_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|feeds| Json(APIResponse::new(feeds)))
+        .map_err(NitteiError::from)
+}
+
+#[derive(Debug)]
+pub struct GetCalendarFeedsUseCase {
+    pub user_id: ID,
+    pub calendar_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(calendar_id) => Self::NotFound(format!(
+                "The calendar with id: {calendar_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for GetCalendarFeedsUseCase {
+    type Response = Vec<CalendarFeed>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "GetCalendarFeeds";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        match ctx.repos.calendars.find(&self.calendar_id).await {
+            Ok(Some(calendar)) if calendar.user_id == self.user_id => (),
+            Ok(_) => return Err(UseCaseError::NotFound(self.calendar_id.clone())),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        ctx.repos
+            .calendar_feeds
+            .find_by_calendar(&self.calendar_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)
+    }
+}
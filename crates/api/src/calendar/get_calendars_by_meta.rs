@@ -1,6 +1,6 @@
 use axum::{Extension, Json, extract::Query};
 use nittei_api_structs::get_calendars_by_meta::*;
-use nittei_domain::{Account, Metadata};
+use nittei_domain::{Account, Cursor, IDCursor, Metadata, paginate_by_id};
 use nittei_infra::{MetadataFindQuery, NitteiContext};
 
 use crate::error::NitteiError;
@@ -18,6 +18,7 @@ use crate::error::NitteiError;
         ("value" = String, Query, description = "The value of the metadata to search for"),
         ("skip" = Option<usize>, Query, description = "The number of calendars to skip"),
         ("limit" = Option<usize>, Query, description = "The number of calendars to return"),
+        ("cursor" = Option<String>, Query, description = "The cursor of the page to get (the nextCursor of the previous page)"),
     ),
     responses(
         (status = 200, body = GetCalendarsByMetaAPIResponse)
@@ -28,11 +29,22 @@ pub async fn get_calendars_by_meta_controller(
     query_params: Query<QueryParams>,
     Extension(ctx): Extension<NitteiContext>,
 ) -> Result<Json<GetCalendarsByMetaAPIResponse>, NitteiError> {
+    let limit = query_params.0.limit.unwrap_or(20);
+    let after = query_params
+        .0
+        .cursor
+        .as_deref()
+        .map(IDCursor::decode)
+        .transpose()
+        .map_err(|e| NitteiError::BadClientData(e.to_string()))?
+        .map(|cursor| cursor.id);
+    // One more is fetched, to know if there is a next page
     let query = MetadataFindQuery {
         account_id: account.id,
         metadata: Metadata::new_kv(query_params.0.key, query_params.0.value),
-        limit: query_params.0.limit.unwrap_or(20),
+        limit: limit.saturating_add(1),
         skip: query_params.0.skip.unwrap_or(0),
+        after,
     };
     let calendars = ctx
         .repos
@@ -40,5 +52,9 @@ pub async fn get_calendars_by_meta_controller(
         .find_by_metadata(query)
         .await
         .map_err(|_| NitteiError::InternalError)?;
-    Ok(Json(GetCalendarsByMetaAPIResponse::new(calendars)))
+    let (calendars, next_cursor) = paginate_by_id(calendars, limit);
+    Ok(Json(GetCalendarsByMetaAPIResponse::new(
+        calendars,
+        next_cursor,
+    )))
 }
@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+
+use axum::{Extension, Json, extract::Path};
+use axum_valid::Valid;
+use chrono::{DateTime, Utc};
+use nittei_api_structs::{dtos::CalendarEventDTO, import_calendar_ical::*};
+use nittei_domain::{Account, CalendarEvent, ID, IcalEvent, User, parse_ical_events};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+
+use crate::{
+    error::NitteiError,
+    event::subscribers::{
+        CreateRemindersOnEventCreated,
+        CreateSyncedEventsOnEventCreated,
+        RefreshInstancesOnEventChanged,
+        SendEventWebhook,
+        SyncRemindersOnEventUpdated,
+        UpdateSyncedEventsOnEventUpdated,
+    },
+    shared::{
+        auth::account_can_modify_user,
+        usecase::{Subscriber, UseCase, execute, outbox_messages},
+    },
+};
+
+#[utoipa::path(
+    post,
+    tag = "Calendar",
+    path = "/api/v1/user/{user_id}/calendar/{calendar_id}/ical",
+    summary = "Import iCalendar content into a calendar (admin only)",
+    security(
+        ("api_key" = [])
+    ),
+    params(
+        ("user_id" = ID, Path, description = "The id of the user owning the calendar"),
+        ("calendar_id" = ID, Path, description = "The id of the calendar to import the events into"),
+    ),
+    request_body(
+        content = ImportCalendarIcalRequestBody,
+    ),
+    responses(
+        (status = 200, body = ImportCalendarIcalAPIResponse)
+    )
+)]
+/// Import the events of iCalendar content (.ics) into a calendar
+///
+/// The UID of each event is stored as its `externalId`, so importing the same content again
+/// updates the previously imported events instead of duplicating them.
+/// Events missing from the content are not deleted.
+pub async fn import_calendar_ical_admin_controller(
+    Extension(account): Extension<Account>,
+    path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    Valid(Json(body)): Valid<Json<ImportCalendarIcalRequestBody>>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let user = account_can_modify_user(&account, &path.user_id, &ctx).await?;
+
+    let usecase = ImportCalendarIcalUseCase {
+        user,
+        calendar_id: path.calendar_id.clone(),
+        ical: body.ical,
+        dry_run: body.dry_run.unwrap_or(false),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|res| {
+            Json(ImportCalendarIcalAPIResponse {
+                dry_run: res.dry_run,
+                changes: res
+                    .changes
+                    .into_iter()
+                    .map(|change| IcalImportChange {
+                        action: change.action,
+                        uid: change.uid,
+                        recurrence_id: change.recurrence_id,
+                        changed_fields: change
+                            .changed_fields
+                            .into_iter()
+                            .map(String::from)
+                            .collect(),
+                        event: CalendarEventDTO::new(change.event),
+                    })
+                    .collect(),
+                skipped: res
+                    .skipped
+                    .into_iter()
+                    .map(|skipped| IcalImportSkippedEvent {
+                        uid: skipped.uid,
+                        reason: skipped.reason,
+                    })
+                    .collect(),
+            })
+        })
+        .map_err(NitteiError::from)
+}
+
+#[derive(Debug)]
+pub struct ImportCalendarIcalUseCase {
+    pub user: User,
+    pub calendar_id: ID,
+    pub ical: String,
+    pub dry_run: bool,
+}
+
+/// Event of the iCalendar content, with the change made to it
+#[derive(Debug)]
+pub struct ImportedEvent {
+    pub action: IcalImportAction,
+    pub uid: String,
+    pub recurrence_id: Option<DateTime<Utc>>,
+    pub changed_fields: Vec<&'static str>,
+    pub event: CalendarEvent,
+}
+
+#[derive(Debug)]
+pub struct SkippedEvent {
+    pub uid: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug)]
+pub struct UseCaseResponse {
+    pub dry_run: bool,
+    pub changes: Vec<ImportedEvent>,
+    pub skipped: Vec<SkippedEvent>,
+}
+
+impl UseCaseResponse {
+    /// Events written with the given action (nothing is written during a dry run)
+    pub fn written_events(&self, action: IcalImportAction) -> Vec<CalendarEvent> {
+        if self.dry_run {
+            return Vec::new();
+        }
+        self.changes
+            .iter()
+            .filter(|change| change.action == action)
+            .map(|change| change.event.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UseCaseError {
+    NotFound(ID),
+    InvalidIcal(String),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(calendar_id) => Self::NotFound(format!(
+                "The calendar with id: {calendar_id}, was not found."
+            )),
+            UseCaseError::InvalidIcal(reason) => {
+                Self::BadClientData(format!("Invalid iCalendar content: {reason}"))
+            }
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for ImportCalendarIcalUseCase {
+    type Response = UseCaseResponse;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "ImportCalendarIcal";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let calendar = match ctx.repos.calendars.find(&self.calendar_id).await {
+            Ok(Some(calendar)) if calendar.user_id == self.user.id => calendar,
+            Ok(_) => return Err(UseCaseError::NotFound(self.calendar_id.clone())),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        // Floating times (without timezone) are in the timezone of the calendar
+        let parsed = parse_ical_events(&self.ical, calendar.settings.timezone)
+            .map_err(|e| UseCaseError::InvalidIcal(e.to_string()))?;
+        let mut skipped = parsed
+            .skipped
+            .into_iter()
+            .map(|skipped| SkippedEvent {
+                uid: skipped.uid,
+                reason: skipped.reason,
+            })
+            .collect::<Vec<_>>();
+
+        // Events previously imported into this calendar, by UID and original start time (modified occurrences)
+        let uids = parsed
+            .events
+            .iter()
+            .map(|e| e.uid.clone())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut existing_events = ctx
+            .repos
+            .events
+            .find_many_by_external_ids(&self.user.account_id, &uids)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .into_iter()
+            .filter(|e| e.calendar_id == calendar.id)
+            .filter_map(|e| Some(((e.external_id.clone()?, e.original_start_time), e)))
+            .collect::<HashMap<_, _>>();
+
+        // The recurring events are imported first, so that their modified occurrences can reference them
+        let (recurring_events, occurrences): (Vec<IcalEvent>, Vec<IcalEvent>) = parsed
+            .events
+            .into_iter()
+            .partition(|e| e.recurrence_id.is_none());
+
+        let now = ctx.sys.get_timestamp();
+        let mut changes: Vec<ImportedEvent> = Vec::new();
+        let mut seen = HashSet::new();
+        for ical_event in recurring_events.iter().chain(occurrences.iter()) {
+            let key = (ical_event.uid.clone(), ical_event.recurrence_id);
+            if !seen.insert(key.clone()) {
+                skipped.push(SkippedEvent {
+                    uid: Some(ical_event.uid.clone()),
+                    reason: "Duplicate event".into(),
+                });
+                continue;
+            }
+
+            // A modified occurrence needs its recurring event
+            let recurring_event_id = match ical_event.recurrence_id {
+                Some(_) => {
+                    let recurring_event = changes
+                        .iter()
+                        .map(|change| &change.event)
+                        .chain(existing_events.values())
+                        .find(|e| {
+                            e.external_id.as_ref() == Some(&ical_event.uid)
+                                && e.original_start_time.is_none()
+                                && e.recurrence.is_some()
+                        });
+                    match recurring_event {
+                        Some(recurring_event) => Some(recurring_event.id.clone()),
+                        None => {
+                            skipped.push(SkippedEvent {
+                                uid: Some(ical_event.uid.clone()),
+                                reason: "The recurring event of this occurrence is not imported"
+                                    .into(),
+                            });
+                            continue;
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            let (mut event, action) = match existing_events.remove(&key) {
+                Some(event) => (event, IcalImportAction::Updated),
+                None => (
+                    CalendarEvent {
+                        external_id: Some(ical_event.uid.clone()),
+                        calendar_id: calendar.id.clone(),
+                        user_id: self.user.id.clone(),
+                        account_id: self.user.account_id.clone(),
+                        created: now,
+                        updated: now,
+                        ..Default::default()
+                    },
+                    IcalImportAction::Created,
+                ),
+            };
+
+            let Some(mut changed_fields) = ical_event.apply(&mut event) else {
+                skipped.push(SkippedEvent {
+                    uid: Some(ical_event.uid.clone()),
+                    reason: "Invalid recurrence rule".into(),
+                });
+                continue;
+            };
+            if event.recurring_event_id != recurring_event_id {
+                event.recurring_event_id = recurring_event_id;
+                changed_fields.push("recurringEventId");
+            }
+
+            let action = match action {
+                IcalImportAction::Created => {
+                    changed_fields.clear();
+                    IcalImportAction::Created
+                }
+                _ if changed_fields.is_empty() => IcalImportAction::Unchanged,
+                _ => {
+                    event.updated = now;
+                    IcalImportAction::Updated
+                }
+            };
+            changes.push(ImportedEvent {
+                action,
+                uid: ical_event.uid.clone(),
+                recurrence_id: ical_event.recurrence_id,
+                changed_fields,
+                event,
+            });
+        }
+
+        let mut res = UseCaseResponse {
+            dry_run: self.dry_run,
+            changes,
+            skipped,
+        };
+        if self.dry_run {
+            return Ok(res);
+        }
+
+        // All the changes are written at once
+        let outbox = outbox_messages(self, &res, ctx).await;
+        ctx.repos
+            .events
+            .insert_and_save_many_with_outbox(
+                &res.written_events(IcalImportAction::Created),
+                &res.written_events(IcalImportAction::Updated),
+                &outbox,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("[import_calendar_ical] Error writing events: {:?}", e);
+                UseCaseError::StorageError
+            })?;
+
+        // Saving the events incremented their versions
+        for change in &mut res.changes {
+            if change.action == IcalImportAction::Updated {
+                change.event.version += 1;
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(CreateRemindersOnEventCreated));
+            subscribers.push(Box::new(CreateSyncedEventsOnEventCreated));
+            subscribers.push(Box::new(SyncRemindersOnEventUpdated));
+            subscribers.push(Box::new(UpdateSyncedEventsOnEventUpdated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
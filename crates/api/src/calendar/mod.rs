@@ -1,23 +1,38 @@
 pub mod add_sync_calendar;
 pub mod create_calendar;
+pub mod create_calendar_feed;
 pub mod delete_calendar;
+pub mod delete_calendar_feed;
 pub mod export_ical;
 pub mod get_calendar;
 pub mod get_calendar_events;
+pub mod get_calendar_feed_ical;
+pub mod get_calendar_feeds;
 pub mod get_calendars;
 pub mod get_calendars_by_meta;
 pub mod get_google_calendars;
 pub mod get_outlook_calendars;
+pub mod import_ical;
 pub mod remove_sync_calendar;
 pub mod update_calendar;
 
 use add_sync_calendar::add_sync_calendar_admin_controller;
 use axum::routing::{delete, get, post, put};
 use create_calendar::{create_calendar_admin_controller, create_calendar_controller};
+use create_calendar_feed::{
+    create_calendar_feed_admin_controller,
+    create_calendar_feed_controller,
+};
 use delete_calendar::{delete_calendar_admin_controller, delete_calendar_controller};
+use delete_calendar_feed::{
+    delete_calendar_feed_admin_controller,
+    delete_calendar_feed_controller,
+};
 use export_ical::{export_calendar_ical_admin_controller, export_calendar_ical_controller};
 use get_calendar::{get_calendar_admin_controller, get_calendar_controller};
 use get_calendar_events::{get_calendar_events_admin_controller, get_calendar_events_controller};
+use get_calendar_feed_ical::get_calendar_feed_ical_controller;
+use get_calendar_feeds::{get_calendar_feeds_admin_controller, get_calendar_feeds_controller};
 use get_calendars_by_meta::get_calendars_by_meta_controller;
 use get_google_calendars::{
     get_google_calendars_admin_controller,
@@ -27,6 +42,7 @@ use get_outlook_calendars::{
     get_outlook_calendars_admin_controller,
     get_outlook_calendars_controller,
 };
+use import_ical::import_calendar_ical_admin_controller;
 use remove_sync_calendar::remove_sync_calendar_admin_controller;
 use update_calendar::{update_calendar_admin_controller, update_calendar_controller};
 use utoipa_axum::router::OpenApiRouter;
@@ -75,6 +91,24 @@ pub fn configure_routes() -> OpenApiRouter {
             "/user/calendar/{calendar_id}/ical",
             get(export_calendar_ical_admin_controller),
         )
+        // Public iCalendar feeds of a calendar (admin route)
+        .route(
+            "/user/calendar/{calendar_id}/ical/feeds",
+            post(create_calendar_feed_admin_controller),
+        )
+        .route(
+            "/user/calendar/{calendar_id}/ical/feeds",
+            get(get_calendar_feeds_admin_controller),
+        )
+        .route(
+            "/user/calendar/{calendar_id}/ical/feeds/{feed_id}",
+            delete(delete_calendar_feed_admin_controller),
+        )
+        // Import iCalendar content into a calendar (admin route)
+        .route(
+            "/user/{user_id}/calendar/{calendar_id}/ical",
+            post(import_calendar_ical_admin_controller),
+        )
         .route(
             "/user/{user_id}/calendar/provider/google",
             get(get_google_calendars_admin_controller),
@@ -119,6 +153,19 @@ pub fn configure_routes() -> OpenApiRouter {
             "/calendar/{calendar_id}/ical",
             get(export_calendar_ical_controller),
         )
+        // Public iCalendar feeds of a calendar
+        .route(
+            "/calendar/{calendar_id}/ical/feeds",
+            post(create_calendar_feed_controller),
+        )
+        .route(
+            "/calendar/{calendar_id}/ical/feeds",
+            get(get_calendar_feeds_controller),
+        )
+        .route(
+            "/calendar/{calendar_id}/ical/feeds/{feed_id}",
+            delete(delete_calendar_feed_controller),
+        )
         // Calendar providers
         .route(
             "/calendar/provider/google",
@@ -130,5 +177,13 @@ pub fn configure_routes() -> OpenApiRouter {
         )
         .route_layer(axum::middleware::from_fn(auth::protect_route_middleware));
 
-    OpenApiRouter::new().merge(admin_router).merge(user_router)
+    // Public routes, the token of the feed grants the access
+    let public_router = OpenApiRouter::new()
+        // Get the public iCalendar feed of a calendar ("{token}.ics")
+        .route("/ical/{token}", get(get_calendar_feed_ical_controller));
+
+    OpenApiRouter::new()
+        .merge(admin_router)
+        .merge(user_router)
+        .merge(public_router)
 }
@@ -1,4 +1,10 @@
-use axum::{Extension, Json, extract::Path};
+use axum::{
+    Extension,
+    Json,
+    extract::Path,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
 use axum_valid::Valid;
 use chrono::Weekday;
 use chrono_tz::Tz;
@@ -10,6 +16,7 @@ use crate::{
     error::NitteiError,
     shared::{
         auth::{Permission, Policy, account_can_modify_calendar, account_can_modify_user},
+        etag::{etag, parse_if_match},
         usecase::{PermissionBoundary, UseCase, execute, execute_with_policy},
     },
 };
@@ -21,6 +28,7 @@ use crate::{
     summary = "Update a calendar (admin only)",
     params(
         ("calendar_id" = ID, Path, description = "The id of the calendar to update"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the calendar, the update is rejected (412) if the calendar has been modified in the meantime"),
     ),
     security(
         ("api_key" = [])
@@ -29,15 +37,18 @@ use crate::{
         content = UpdateCalendarRequestBody,
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "New version of the calendar")
+        ))
     )
 )]
 pub async fn update_calendar_admin_controller(
     Extension(account): Extension<Account>,
     Extension(ctx): Extension<NitteiContext>,
     path: Path<PathParams>,
+    headers: HeaderMap,
     mut body: Valid<Json<UpdateCalendarRequestBody>>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let cal = account_can_modify_calendar(&account, &path.calendar_id, &ctx).await?;
     let user = account_can_modify_user(&account, &cal.user_id, &ctx).await?;
 
@@ -48,11 +59,15 @@ pub async fn update_calendar_admin_controller(
         week_start: body.0.settings.week_start,
         timezone: body.0.settings.timezone,
         metadata: body.0.metadata.take(),
+        expected_version: parse_if_match(&headers)?,
     };
 
     execute(usecase, &ctx)
         .await
-        .map(|calendar| Json(APIResponse::new(calendar)))
+        .map(|calendar| {
+            let etag = etag(calendar.version);
+            ([(header::ETAG, etag)], Json(APIResponse::new(calendar))).into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -63,20 +78,24 @@ pub async fn update_calendar_admin_controller(
     summary = "Update a calendar",
     params(
         ("calendar_id" = ID, Path, description = "The id of the calendar to update"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the calendar, the update is rejected (412) if the calendar has been modified in the meantime"),
     ),
     request_body(
         content = UpdateCalendarRequestBody,
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "New version of the calendar")
+        ))
     )
 )]
 pub async fn update_calendar_controller(
     Extension((user, policy)): Extension<(User, Policy)>,
     Extension(ctx): Extension<NitteiContext>,
     mut path: Path<PathParams>,
+    headers: HeaderMap,
     mut body: Valid<Json<UpdateCalendarRequestBody>>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let usecase = UpdateCalendarUseCase {
         user,
         calendar_id: std::mem::take(&mut path.calendar_id),
@@ -84,11 +103,15 @@ pub async fn update_calendar_controller(
         week_start: body.0.settings.week_start,
         timezone: body.0.settings.timezone,
         metadata: body.0.metadata.take(),
+        expected_version: parse_if_match(&headers)?,
     };
 
     execute_with_policy(usecase, &policy, &ctx)
         .await
-        .map(|calendar| Json(APIResponse::new(calendar)))
+        .map(|calendar| {
+            let etag = etag(calendar.version);
+            ([(header::ETAG, etag)], Json(APIResponse::new(calendar))).into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -100,11 +123,14 @@ struct UpdateCalendarUseCase {
     pub week_start: Option<Weekday>,
     pub timezone: Option<Tz>,
     pub metadata: Option<serde_json::Value>,
+    /// Version expected by the client (If-Match header)
+    pub expected_version: Option<i64>,
 }
 
 #[derive(Debug)]
 enum UseCaseError {
     CalendarNotFound,
+    VersionMismatch,
     StorageError,
 }
 
@@ -113,6 +139,9 @@ impl From<UseCaseError> for NitteiError {
         match e {
             UseCaseError::StorageError => Self::InternalError,
             UseCaseError::CalendarNotFound => Self::NotFound("The calendar was not found.".into()),
+            UseCaseError::VersionMismatch => Self::PreconditionFailed(
+                "The calendar has been modified since it was fetched".into(),
+            ),
         }
     }
 }
@@ -137,6 +166,12 @@ impl UseCase for UpdateCalendarUseCase {
             _ => return Err(UseCaseError::CalendarNotFound),
         };
 
+        if let Some(expected_version) = self.expected_version
+            && expected_version != calendar.version
+        {
+            return Err(UseCaseError::VersionMismatch);
+        }
+
         if let Some(wkst) = self.week_start {
             calendar.settings.week_start = wkst;
         }
@@ -153,12 +188,29 @@ impl UseCase for UpdateCalendarUseCase {
             calendar.name = Some(name.clone());
         }
 
-        ctx.repos
-            .calendars
-            .save(&calendar)
-            .await
-            .map(|_| calendar)
-            .map_err(|_| UseCaseError::StorageError)
+        match self.expected_version {
+            // The version is checked again when saving, in case of a concurrent update
+            Some(expected_version) => {
+                let saved = ctx
+                    .repos
+                    .calendars
+                    .save_if_version(&calendar, expected_version)
+                    .await
+                    .map_err(|_| UseCaseError::StorageError)?;
+                if !saved {
+                    return Err(UseCaseError::VersionMismatch);
+                }
+            }
+            None => ctx
+                .repos
+                .calendars
+                .save(&calendar)
+                .await
+                .map_err(|_| UseCaseError::StorageError)?,
+        }
+        calendar.version += 1;
+
+        Ok(calendar)
     }
 }
 
@@ -194,6 +246,7 @@ mod test {
             week_start: Some(new_wkst),
             timezone: None,
             metadata: Some(serde_json::json!({})),
+            expected_version: None,
         };
         let res = usecase.execute(&ctx).await;
         assert!(res.is_ok());
@@ -1,5 +1,5 @@
 use axum::{
-    http::{StatusCode, header},
+    http::{HeaderValue, StatusCode, header},
     response::IntoResponse,
 };
 use thiserror::Error;
@@ -13,6 +13,8 @@ pub enum NitteiError {
     BadClientData(String),
     #[error("There was a conflict with the request. Error message: `{0}`")]
     Conflict(String),
+    #[error("The resource has been modified in the meantime. Error message: `{0}`")]
+    PreconditionFailed(String),
     #[error("Unauthorized request. Error message: `{0}`")]
     Unauthorized(String),
     #[error(
@@ -21,6 +23,8 @@ pub enum NitteiError {
     UnidentifiableClient(String),
     #[error("404 Not found. Error message: `{0}`")]
     NotFound(String),
+    #[error("Too many requests, retry after {0} seconds")]
+    TooManyRequests(u64),
 }
 
 impl NitteiError {
@@ -30,8 +34,10 @@ impl NitteiError {
             Self::BadClientData(_) => StatusCode::BAD_REQUEST,
             Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
             Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
             Self::NotFound(_) => StatusCode::NOT_FOUND,
             Self::UnidentifiableClient(_) => StatusCode::UNAUTHORIZED,
+            Self::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 
@@ -48,6 +54,12 @@ impl NitteiError {
 /// This allows to automatically convert the error types to HTTP responses
 impl IntoResponse for NitteiError {
     fn into_response(self) -> axum::response::Response {
-        self.error_response().into_response()
+        let mut response = self.error_response().into_response();
+        if let Self::TooManyRequests(retry_after_secs) = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        }
+        response
     }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use axum::{Extension, Json, extract::Path, http::StatusCode};
 use axum_valid::Valid;
 use chrono::{DateTime, TimeDelta, Utc};
@@ -5,22 +7,31 @@ use nittei_api_structs::create_batch_events::*;
 use nittei_domain::{
     Account,
     CalendarEvent,
+    CalendarEventAttendee,
     CalendarEventReminder,
     CalendarEventStatus,
     ID,
     RRuleOptions,
+    Tz,
     User,
 };
 use nittei_infra::NitteiContext;
 use nittei_utils::config::APP_CONFIG;
 
-use super::subscribers::CreateRemindersOnEventCreated;
+use super::subscribers::{
+    CreateRemindersOnEventCreated,
+    RefreshInstancesOnEventChanged,
+    SendEventWebhook,
+};
 use crate::{
     error::NitteiError,
-    event::subscribers::CreateSyncedEventsOnEventCreated,
+    event::{
+        create_event::attendees_belong_to_account,
+        subscribers::CreateSyncedEventsOnEventCreated,
+    },
     shared::{
         auth::{Permission, account_can_modify_user},
-        usecase::{PermissionBoundary, Subscriber, UseCase, execute},
+        usecase::{PermissionBoundary, Subscriber, UseCase, execute, outbox_messages},
     },
 };
 
@@ -69,11 +80,14 @@ pub async fn create_batch_events_admin_controller(
                 user: user.clone(),
                 calendar_id: e.calendar_id.clone(),
                 recurrence: e.recurrence.take(),
+                timezone: e.timezone,
                 exdates: e.exdates.clone().unwrap_or_default(),
                 recurring_event_id: e.recurring_event_id.take(),
                 original_start_time: e.original_start_time,
                 reminders: e.reminders.clone(),
+                attendees: std::mem::take(&mut e.attendees),
                 service_id: e.service_id.take(),
+                group_id: e.group_id.take(),
                 metadata: e.metadata.take(),
                 created: e.created,
                 updated: e.updated,
@@ -113,11 +127,14 @@ pub struct CreateEventUseCase {
     pub duration: i64,
     pub busy: bool,
     pub recurrence: Option<RRuleOptions>,
+    pub timezone: Option<Tz>,
     pub exdates: Vec<DateTime<Utc>>,
     pub recurring_event_id: Option<ID>,
     pub original_start_time: Option<DateTime<Utc>>,
     pub reminders: Vec<CalendarEventReminder>,
+    pub attendees: Vec<CalendarEventAttendee>,
     pub service_id: Option<ID>,
+    pub group_id: Option<ID>,
     pub metadata: Option<serde_json::Value>,
     pub created: Option<DateTime<Utc>>,
     pub updated: Option<DateTime<Utc>>,
@@ -127,7 +144,9 @@ pub struct CreateEventUseCase {
 pub enum UseCaseError {
     InvalidRecurrenceRule,
     InvalidReminder,
+    InvalidAttendees,
     NotFound(ID),
+    EventGroupNotFound(ID),
     StorageError,
 }
 
@@ -137,12 +156,18 @@ impl From<UseCaseError> for NitteiError {
             UseCaseError::NotFound(calendar_id) => Self::NotFound(format!(
                 "The calendar with id: {calendar_id}, was not found."
             )),
+            UseCaseError::EventGroupNotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
             UseCaseError::InvalidRecurrenceRule => {
                 Self::BadClientData("Invalid recurrence rule specified for the event".into())
             }
             UseCaseError::InvalidReminder => {
                 Self::BadClientData("Invalid reminder specified for the event".into())
             }
+            UseCaseError::InvalidAttendees => {
+                Self::BadClientData("Invalid attendees specified for the event".into())
+            }
             UseCaseError::StorageError => Self::InternalError,
         }
     }
@@ -179,6 +204,30 @@ impl UseCase for CreateBatchEventsUseCase {
             .await
             .map_err(|_| UseCaseError::StorageError)?;
 
+        // Collect the event groups the events are attached to
+        let group_ids: HashSet<&ID> = self
+            .events
+            .iter()
+            .filter_map(|e| e.group_id.as_ref())
+            .collect();
+
+        // Check that the groups exist and belong to the user and the calendar of the events
+        for group_id in group_ids {
+            let group = ctx
+                .repos
+                .event_groups
+                .find(group_id)
+                .await
+                .map_err(|_| UseCaseError::StorageError)?;
+            let group = group.ok_or_else(|| UseCaseError::EventGroupNotFound(group_id.clone()))?;
+            if self.events.iter().any(|e| {
+                e.group_id.as_ref() == Some(group_id)
+                    && (e.user.id != group.user_id || e.calendar_id != group.calendar_id)
+            }) {
+                return Err(UseCaseError::EventGroupNotFound(group_id.clone()));
+            }
+        }
+
         let mut events = Vec::new();
         // Create events
         for event in &self.events {
@@ -204,6 +253,7 @@ impl UseCase for CreateBatchEventsUseCase {
                 start_time: event.start_time,
                 duration: event.duration,
                 recurrence: None,
+                timezone: event.timezone,
                 end_time: event.start_time + TimeDelta::milliseconds(event.duration),
                 exdates: event.exdates.clone(),
                 recurring_until: None,
@@ -213,8 +263,11 @@ impl UseCase for CreateBatchEventsUseCase {
                 user_id: event.user.id.clone(),
                 account_id: event.user.account_id.clone(),
                 reminders: event.reminders.clone(),
+                attendees: Vec::new(),
                 service_id: event.service_id.clone(),
+                group_id: event.group_id.clone(),
                 metadata: event.metadata.clone(),
+                version: 0,
                 created: event.created.unwrap_or_else(Utc::now),
                 updated: event.updated.unwrap_or_else(Utc::now),
             };
@@ -237,27 +290,39 @@ impl UseCase for CreateBatchEventsUseCase {
                 }
             }
 
+            if !e.set_attendees(event.attendees.clone())
+                || !attendees_belong_to_account(ctx, &e.account_id, &e.attendees).await?
+            {
+                return Err(UseCaseError::InvalidAttendees);
+            }
+
             events.push(e);
         }
 
         // Create events
-        ctx.repos.events.insert_many(&events).await.map_err(|e| {
-            tracing::error!("[create_batch_events] Error inserting events: {:?}", e);
-            UseCaseError::StorageError
-        })?;
+        let outbox = outbox_messages(self, &events, ctx).await;
+        ctx.repos
+            .events
+            .insert_many_with_outbox(&events, &outbox)
+            .await
+            .map_err(|e| {
+                tracing::error!("[create_batch_events] Error inserting events: {:?}", e);
+                UseCaseError::StorageError
+            })?;
 
         Ok(events)
     }
 
     fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
-        if APP_CONFIG.disable_reminders {
-            vec![]
-        } else {
-            vec![
-                Box::new(CreateRemindersOnEventCreated),
-                Box::new(CreateSyncedEventsOnEventCreated),
-            ]
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(CreateRemindersOnEventCreated));
+            subscribers.push(Box::new(CreateSyncedEventsOnEventCreated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
         }
+        subscribers
     }
 }
 
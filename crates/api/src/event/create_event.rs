@@ -5,22 +5,35 @@ use nittei_api_structs::create_event::*;
 use nittei_domain::{
     Account,
     CalendarEvent,
+    CalendarEventAttendee,
     CalendarEventReminder,
     CalendarEventStatus,
     ID,
     RRuleOptions,
+    Tz,
     User,
 };
 use nittei_infra::NitteiContext;
 use nittei_utils::config::APP_CONFIG;
 
-use super::subscribers::CreateRemindersOnEventCreated;
+use super::subscribers::{
+    CreateRemindersOnEventCreated,
+    RefreshInstancesOnEventChanged,
+    SendEventWebhook,
+};
 use crate::{
     error::NitteiError,
     event::subscribers::CreateSyncedEventsOnEventCreated,
     shared::{
         auth::{Permission, Policy, account_can_modify_user},
-        usecase::{PermissionBoundary, Subscriber, UseCase, execute, execute_with_policy},
+        usecase::{
+            PermissionBoundary,
+            Subscriber,
+            UseCase,
+            execute,
+            execute_with_policy,
+            outbox_messages,
+        },
     },
 };
 
@@ -66,11 +79,14 @@ pub async fn create_event_admin_controller(
         user,
         calendar_id: body.calendar_id.clone(),
         recurrence: body.recurrence.take(),
+        timezone: body.timezone,
         exdates: body.exdates.clone().unwrap_or_default(),
         recurring_event_id: body.recurring_event_id.take(),
         original_start_time: body.original_start_time,
         reminders: body.reminders.clone(),
+        attendees: std::mem::take(&mut body.attendees),
         service_id: body.service_id.take(),
+        group_id: body.group_id.take(),
         metadata: body.metadata.take(),
         created: body.created,
         updated: body.updated,
@@ -114,12 +130,15 @@ pub async fn create_event_controller(
         duration: body.duration,
         calendar_id: body.calendar_id.clone(),
         recurrence: body.recurrence.take(),
+        timezone: body.timezone,
         exdates: body.exdates.clone().unwrap_or_default(),
         recurring_event_id: body.recurring_event_id.take(),
         original_start_time: body.original_start_time,
         user,
         reminders: body.reminders.clone(),
+        attendees: std::mem::take(&mut body.attendees),
         service_id: body.service_id.take(),
+        group_id: body.group_id.take(),
         metadata: body.metadata.take(),
         created: body.created,
         updated: body.updated,
@@ -147,11 +166,14 @@ pub struct CreateEventUseCase {
     pub duration: i64,
     pub busy: bool,
     pub recurrence: Option<RRuleOptions>,
+    pub timezone: Option<Tz>,
     pub exdates: Vec<DateTime<Utc>>,
     pub recurring_event_id: Option<ID>,
     pub original_start_time: Option<DateTime<Utc>>,
     pub reminders: Vec<CalendarEventReminder>,
+    pub attendees: Vec<CalendarEventAttendee>,
     pub service_id: Option<ID>,
+    pub group_id: Option<ID>,
     pub metadata: Option<serde_json::Value>,
     pub created: Option<DateTime<Utc>>,
     pub updated: Option<DateTime<Utc>>,
@@ -161,7 +183,9 @@ pub struct CreateEventUseCase {
 pub enum UseCaseError {
     InvalidRecurrenceRule,
     InvalidReminder,
+    InvalidAttendees,
     NotFound(ID),
+    EventGroupNotFound(ID),
     StorageError,
 }
 
@@ -171,12 +195,18 @@ impl From<UseCaseError> for NitteiError {
             UseCaseError::NotFound(calendar_id) => Self::NotFound(format!(
                 "The calendar with id: {calendar_id}, was not found."
             )),
+            UseCaseError::EventGroupNotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
             UseCaseError::InvalidRecurrenceRule => {
                 Self::BadClientData("Invalid recurrence rule specified for the event".into())
             }
             UseCaseError::InvalidReminder => {
                 Self::BadClientData("Invalid reminder specified for the event".into())
             }
+            UseCaseError::InvalidAttendees => {
+                Self::BadClientData("Invalid attendees specified for the event".into())
+            }
             UseCaseError::StorageError => Self::InternalError,
         }
     }
@@ -209,6 +239,21 @@ impl UseCase for CreateEventUseCase {
             _ => return Err(UseCaseError::NotFound(self.calendar_id.clone())),
         };
 
+        // If the event is attached to a group, the group needs to belong to the same user and calendar
+        if let Some(group_id) = &self.group_id {
+            let group = ctx
+                .repos
+                .event_groups
+                .find(group_id)
+                .await
+                .map_err(|_| UseCaseError::StorageError)?;
+            match group {
+                Some(group)
+                    if group.user_id == self.user.id && group.calendar_id == calendar.id => {}
+                _ => return Err(UseCaseError::EventGroupNotFound(group_id.clone())),
+            }
+        }
+
         let mut e = CalendarEvent {
             id: Default::default(),
             external_parent_id: self.external_parent_id.clone(),
@@ -223,6 +268,7 @@ impl UseCase for CreateEventUseCase {
             start_time: self.start_time,
             duration: self.duration,
             recurrence: None,
+            timezone: self.timezone,
             end_time: self.start_time + TimeDelta::milliseconds(self.duration),
             exdates: self.exdates.clone(),
             recurring_until: None,
@@ -232,8 +278,11 @@ impl UseCase for CreateEventUseCase {
             user_id: self.user.id.clone(),
             account_id: self.user.account_id.clone(),
             reminders: self.reminders.clone(),
+            attendees: Vec::new(),
             service_id: self.service_id.take(),
+            group_id: self.group_id.take(),
             metadata: self.metadata.take(),
+            version: 0,
             created: self.created.unwrap_or_else(Utc::now),
             updated: self.updated.unwrap_or_else(Utc::now),
         };
@@ -258,21 +307,50 @@ impl UseCase for CreateEventUseCase {
             }
         }
 
-        ctx.repos.events.insert(&e).await?;
+        if !e.set_attendees(std::mem::take(&mut self.attendees))
+            || !attendees_belong_to_account(ctx, &e.account_id, &e.attendees).await?
+        {
+            tracing::error!("[create_event] Invalid attendees");
+            return Err(UseCaseError::InvalidAttendees);
+        }
+
+        ctx.repos
+            .events
+            .insert_with_outbox(&e, &outbox_messages(self, &e, ctx).await)
+            .await?;
 
         Ok(e)
     }
 
     fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
-        if APP_CONFIG.disable_reminders {
-            vec![]
-        } else {
-            vec![
-                Box::new(CreateRemindersOnEventCreated),
-                Box::new(CreateSyncedEventsOnEventCreated),
-            ]
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(CreateRemindersOnEventCreated));
+            subscribers.push(Box::new(CreateSyncedEventsOnEventCreated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
         }
+        subscribers
+    }
+}
+
+/// Check that the internal users attending an event belong to the account of the event
+pub(crate) async fn attendees_belong_to_account(
+    ctx: &NitteiContext,
+    account_id: &ID,
+    attendees: &[CalendarEventAttendee],
+) -> anyhow::Result<bool> {
+    let user_ids = attendees
+        .iter()
+        .filter_map(|a| a.user_id.clone())
+        .collect::<Vec<_>>();
+    if user_ids.is_empty() {
+        return Ok(true);
     }
+
+    let users = ctx.repos.users.find_many(&user_ids).await?;
+    Ok(users.len() == user_ids.len() && users.iter().all(|u| &u.account_id == account_id))
 }
 
 impl PermissionBoundary for CreateEventUseCase {
@@ -1,6 +1,11 @@
-use axum::{Extension, Json, extract::Path};
+use axum::{
+    Extension,
+    Json,
+    extract::{Path, Query},
+};
+use chrono::{DateTime, Utc};
 use nittei_api_structs::delete_event::*;
-use nittei_domain::{Account, CalendarEvent, ID, IntegrationProvider, User};
+use nittei_domain::{Account, CalendarEvent, ID, IntegrationProvider, RecurringEventScope, User};
 use nittei_infra::{
     NitteiContext,
     google_calendar::GoogleCalendarProvider,
@@ -11,9 +16,17 @@ use tracing::error;
 
 use crate::{
     error::NitteiError,
+    event::subscribers::{RefreshInstancesOnEventChanged, SendEventWebhook},
     shared::{
         auth::{Permission, Policy, account_can_modify_user},
-        usecase::{PermissionBoundary, UseCase, execute, execute_with_policy},
+        usecase::{
+            PermissionBoundary,
+            Subscriber,
+            UseCase,
+            execute,
+            execute_with_policy,
+            outbox_messages,
+        },
     },
 };
 
@@ -24,6 +37,8 @@ use crate::{
     summary = "Delete an event (admin only)",
     params(
         ("event_id" = ID, Path, description = "The id of the event to delete"),
+        ("scope" = Option<RecurringEventScope>, Query, description = "Scope of the deletion for recurring events (default is all)"),
+        ("occurrenceStartTime" = Option<DateTime<Utc>>, Query, description = "Start time of the targeted occurrence, required for the this and thisAndFollowing scopes"),
     ),
     security(
         ("api_key" = [])
@@ -35,6 +50,7 @@ use crate::{
 pub async fn delete_event_admin_controller(
     Extension(account): Extension<Account>,
     Extension(event): Extension<CalendarEvent>,
+    query_params: Query<QueryParams>,
     Extension(ctx): Extension<NitteiContext>,
 ) -> Result<Json<APIResponse>, NitteiError> {
     let user = account_can_modify_user(&account, &event.user_id, &ctx).await?;
@@ -42,6 +58,8 @@ pub async fn delete_event_admin_controller(
     let usecase = DeleteEventUseCase {
         user,
         event_id: event.id.clone(),
+        scope: query_params.scope,
+        occurrence_start_time: query_params.occurrence_start_time,
         prefetched_calendar_event: Some(event),
     };
 
@@ -58,6 +76,8 @@ pub async fn delete_event_admin_controller(
     summary = "Delete an event (user only)",
     params(
         ("event_id" = ID, Path, description = "The id of the event to delete"),
+        ("scope" = Option<RecurringEventScope>, Query, description = "Scope of the deletion for recurring events (default is all)"),
+        ("occurrenceStartTime" = Option<DateTime<Utc>>, Query, description = "Start time of the targeted occurrence, required for the this and thisAndFollowing scopes"),
     ),
     responses(
         (status = 200, body = APIResponse)
@@ -66,11 +86,14 @@ pub async fn delete_event_admin_controller(
 pub async fn delete_event_controller(
     Extension((user, policy)): Extension<(User, Policy)>,
     path_params: Path<PathParams>,
+    query_params: Query<QueryParams>,
     Extension(ctx): Extension<NitteiContext>,
 ) -> Result<Json<APIResponse>, NitteiError> {
     let usecase = DeleteEventUseCase {
         user,
         event_id: path_params.event_id.clone(),
+        scope: query_params.scope,
+        occurrence_start_time: query_params.occurrence_start_time,
         prefetched_calendar_event: None,
     };
 
@@ -86,6 +109,11 @@ pub struct DeleteEventUseCase {
     pub user: User,
     pub event_id: ID,
 
+    /// Scope of the deletion for recurring events (default is the whole series)
+    pub scope: Option<RecurringEventScope>,
+    /// Start time of the occurrence targeted by the scope
+    pub occurrence_start_time: Option<DateTime<Utc>>,
+
     /// Event that has been potentially prefetched by the route guard
     /// Only happens in the admin controller
     pub prefetched_calendar_event: Option<CalendarEvent>,
@@ -94,6 +122,7 @@ pub struct DeleteEventUseCase {
 #[derive(Debug)]
 pub enum UseCaseError {
     NotFound(ID),
+    InvalidScope(String),
     StorageError,
 }
 
@@ -101,6 +130,7 @@ impl From<UseCaseError> for NitteiError {
     fn from(e: UseCaseError) -> Self {
         match e {
             UseCaseError::StorageError => Self::InternalError,
+            UseCaseError::InvalidScope(reason) => Self::BadClientData(reason),
             UseCaseError::NotFound(event_id) => Self::NotFound(format!(
                 "The calendar event with id: {event_id}, was not found."
             )),
@@ -125,22 +155,43 @@ impl UseCase for DeleteEventUseCase {
                 UseCaseError::StorageError
             })?,
         };
-        let e = match event {
+        let mut e = match event {
             Some(e) if e.user_id == self.user.id => e,
             _ => return Err(UseCaseError::NotFound(self.event_id.clone())),
         };
 
+        if let Some(occurrence_start_time) = self.targeted_occurrence(&e)? {
+            self.delete_occurrences(&mut e, occurrence_start_time, ctx)
+                .await?;
+            return Ok(e);
+        }
+
         if !APP_CONFIG.disable_reminders {
-            self.delete_synced_events(&e, ctx).await;
+            delete_synced_events(&e, ctx).await;
         }
 
-        ctx.repos.events.delete(&e.id).await.map_err(|e| {
-            tracing::error!("[delete_event] Error deleting event: {:?}", e);
-            UseCaseError::StorageError
-        })?;
+        ctx.repos
+            .events
+            .delete_many_with_outbox(
+                std::slice::from_ref(&e.id),
+                &outbox_messages(self, &e, ctx).await,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("[delete_event] Error deleting event: {:?}", e);
+                UseCaseError::StorageError
+            })?;
 
         Ok(e)
     }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
 }
 
 impl PermissionBoundary for DeleteEventUseCase {
@@ -150,77 +201,192 @@ impl PermissionBoundary for DeleteEventUseCase {
 }
 
 impl DeleteEventUseCase {
-    pub async fn delete_synced_events(&self, e: &CalendarEvent, ctx: &NitteiContext) {
-        let synced_events = match ctx.repos.event_synced.find_by_event(&e.id).await {
-            Ok(synced_events) => synced_events,
-            Err(e) => {
-                error!("Unable to query synced events from repo: {:?}", e);
-                return;
+    /// Start time of the occurrence targeted by the scope
+    /// None if the whole event is deleted
+    pub fn targeted_occurrence(
+        &self,
+        e: &CalendarEvent,
+    ) -> Result<Option<DateTime<Utc>>, UseCaseError> {
+        match (self.scope, self.occurrence_start_time) {
+            (None | Some(RecurringEventScope::All), _) => Ok(None),
+            // Targeting the first occurrence with "this and following" is the same as targeting the whole series
+            (Some(RecurringEventScope::ThisAndFollowing), Some(occurrence_start_time))
+                if occurrence_start_time == e.start_time =>
+            {
+                Ok(None)
             }
-        };
+            (Some(_), Some(occurrence_start_time)) => Ok(Some(occurrence_start_time)),
+            (Some(_), None) => Err(UseCaseError::InvalidScope(
+                "The start time of the occurrence is required".into(),
+            )),
+        }
+    }
 
-        let synced_outlook_events = synced_events
-            .iter()
-            .filter(|o_event| o_event.provider == IntegrationProvider::Outlook)
-            .collect::<Vec<_>>();
-        let synced_google_events = synced_events
-            .iter()
-            .filter(|g_event| g_event.provider == IntegrationProvider::Google)
+    /// Delete the targeted occurrence (scope "this"), or the targeted occurrence and the following ones
+    /// (scope "thisAndFollowing") of a recurring event
+    /// The series is kept, with an exdate or truncated, and the exceptions of the deleted occurrences are removed
+    async fn delete_occurrences(
+        &self,
+        e: &mut CalendarEvent,
+        occurrence_start_time: DateTime<Utc>,
+        ctx: &NitteiContext,
+    ) -> Result<(), UseCaseError> {
+        if e.recurrence.is_none() {
+            return Err(UseCaseError::InvalidScope(
+                "The event is not a recurring event".into(),
+            ));
+        }
+        let calendar = ctx
+            .repos
+            .calendars
+            .find(&e.calendar_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("[delete_event] Error finding calendar: {:?}", e);
+                UseCaseError::StorageError
+            })?
+            .ok_or_else(|| UseCaseError::NotFound(e.calendar_id.clone()))?;
+        let is_occurrence = e
+            .is_occurrence(occurrence_start_time, &calendar.settings)
+            .map_err(|e| {
+                tracing::error!("[delete_event] Failed to expand the recurrence {:?}", e);
+                UseCaseError::StorageError
+            })?;
+        if !is_occurrence {
+            return Err(UseCaseError::InvalidScope(
+                "The start time doesn't match any occurrence of the event".into(),
+            ));
+        }
+
+        let this_and_following = self.scope == Some(RecurringEventScope::ThisAndFollowing);
+        if this_and_following {
+            // The new series is discarded, only the truncated one is kept
+            e.split_at(occurrence_start_time, &calendar.settings)
+                .map_err(|e| {
+                    tracing::error!("[delete_event] Failed to split the series {:?}", e);
+                    UseCaseError::StorageError
+                })?;
+        } else {
+            e.exdates.push(occurrence_start_time);
+        }
+        e.updated = Utc::now();
+        // The saved event has the next version
+        e.version += 1;
+
+        // The exceptions of the deleted occurrences are removed with them
+        let deleted_exception_ids = ctx
+            .repos
+            .events
+            .find_by_id_and_recurring_event_id(&e.id)
+            .await
+            .map_err(|e| {
+                tracing::error!("[delete_event] Error finding exceptions: {:?}", e);
+                UseCaseError::StorageError
+            })?
+            .into_iter()
+            .filter(|exception| {
+                exception.id != e.id
+                    && exception
+                        .original_start_time
+                        .is_some_and(|original_start_time| {
+                            if this_and_following {
+                                original_start_time >= occurrence_start_time
+                            } else {
+                                original_start_time == occurrence_start_time
+                            }
+                        })
+            })
+            .map(|exception| exception.id)
             .collect::<Vec<_>>();
 
-        if synced_google_events.is_empty() && synced_outlook_events.is_empty() {
+        ctx.repos
+            .events
+            .save_and_delete_many_with_outbox(
+                e,
+                &deleted_exception_ids,
+                &outbox_messages(self, e, ctx).await,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("[delete_event] Error saving event: {:?}", e);
+                UseCaseError::StorageError
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Delete the events synced to the Google and Outlook calendars of the user from a deleted event
+pub async fn delete_synced_events(e: &CalendarEvent, ctx: &NitteiContext) {
+    let synced_events = match ctx.repos.event_synced.find_by_event(&e.id).await {
+        Ok(synced_events) => synced_events,
+        Err(e) => {
+            error!("Unable to query synced events from repo: {:?}", e);
             return;
         }
+    };
 
-        let user = ctx.repos.users.find(&e.user_id).await;
-        let user = match user {
-            Ok(Some(u)) => u,
-            Ok(None) => {
-                error!("Unable to find user when deleting sync events");
-                return;
-            }
-            Err(e) => {
-                error!("Unable to find user when deleting sync events {:?}", e);
+    let synced_outlook_events = synced_events
+        .iter()
+        .filter(|o_event| o_event.provider == IntegrationProvider::Outlook)
+        .collect::<Vec<_>>();
+    let synced_google_events = synced_events
+        .iter()
+        .filter(|g_event| g_event.provider == IntegrationProvider::Google)
+        .collect::<Vec<_>>();
+
+    if synced_google_events.is_empty() && synced_outlook_events.is_empty() {
+        return;
+    }
+
+    let user = ctx.repos.users.find(&e.user_id).await;
+    let user = match user {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            error!("Unable to find user when deleting sync events");
+            return;
+        }
+        Err(e) => {
+            error!("Unable to find user when deleting sync events {:?}", e);
+            return;
+        }
+    };
+
+    if !synced_outlook_events.is_empty() {
+        let provider = match OutlookCalendarProvider::new(&user, ctx).await {
+            Ok(p) => p,
+            Err(_) => {
+                error!("Unable to create outlook calendar provider");
                 return;
             }
         };
-
-        if !synced_outlook_events.is_empty() {
-            let provider = match OutlookCalendarProvider::new(&user, ctx).await {
-                Ok(p) => p,
-                Err(_) => {
-                    error!("Unable to create outlook calendar provider");
-                    return;
-                }
+        for cal in synced_outlook_events {
+            if provider
+                .delete_event(cal.ext_calendar_id.clone(), cal.ext_event_id.clone())
+                .await
+                .is_err()
+            {
+                error!("Unable to delete external outlook calendar event");
             };
-            for cal in synced_outlook_events {
-                if provider
-                    .delete_event(cal.ext_calendar_id.clone(), cal.ext_event_id.clone())
-                    .await
-                    .is_err()
-                {
-                    error!("Unable to delete external outlook calendar event");
-                };
-            }
         }
+    }
 
-        if !synced_google_events.is_empty() {
-            let provider = match GoogleCalendarProvider::new(&user, ctx).await {
-                Ok(p) => p,
-                Err(_) => {
-                    error!("Unable to create google calendar provider");
-                    return;
-                }
-            };
-            for cal in synced_google_events {
-                if provider
-                    .delete_event(cal.ext_calendar_id.clone(), cal.ext_event_id.clone())
-                    .await
-                    .is_err()
-                {
-                    error!("Unable to delete google external calendar event");
-                };
+    if !synced_google_events.is_empty() {
+        let provider = match GoogleCalendarProvider::new(&user, ctx).await {
+            Ok(p) => p,
+            Err(_) => {
+                error!("Unable to create google calendar provider");
+                return;
             }
+        };
+        for cal in synced_google_events {
+            if provider
+                .delete_event(cal.ext_calendar_id.clone(), cal.ext_event_id.clone())
+                .await
+                .is_err()
+            {
+                error!("Unable to delete google external calendar event");
+            };
         }
     }
 }
@@ -2,14 +2,16 @@ use axum::{Extension, Json, http::StatusCode};
 use axum_valid::Valid;
 use futures::future::{self, try_join};
 use nittei_api_structs::delete_many_events::DeleteManyEventsRequestBody;
-use nittei_domain::{Account, ID};
+use nittei_domain::{Account, CalendarEvent, ID};
 use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
 
 use crate::{
     error::NitteiError,
+    event::subscribers::{RefreshInstancesOnEventChanged, SendEventWebhook},
     shared::{
         auth::Permission,
-        usecase::{PermissionBoundary, UseCase, execute},
+        usecase::{PermissionBoundary, Subscriber, UseCase, execute, outbox_messages},
     },
 };
 
@@ -69,7 +71,8 @@ impl From<UseCaseError> for NitteiError {
 
 #[async_trait::async_trait]
 impl UseCase for DeleteManyEventsUseCase {
-    type Response = ();
+    /// The deleted events
+    type Response = Vec<CalendarEvent>;
 
     type Error = UseCaseError;
 
@@ -130,25 +133,39 @@ impl UseCase for DeleteManyEventsUseCase {
             return Err(UseCaseError::BadRequest);
         }
 
-        // Merge event ids and remove duplicates
-        let event_ids_to_delete = events_by_ids
+        // Merge events and remove duplicates
+        let mut seen_event_ids = std::collections::HashSet::new();
+        let events_to_delete = events_by_ids
+            .into_iter()
+            .chain(events_by_external_ids)
+            .filter(|event| seen_event_ids.insert(event.id.clone()))
+            .collect::<Vec<_>>();
+        let event_ids_to_delete = events_to_delete
             .iter()
-            .chain(events_by_external_ids.iter())
             .map(|event| event.id.clone())
-            .collect::<std::collections::HashSet<ID>>()
-            .into_iter()
             .collect::<Vec<ID>>();
 
         ctx.repos
             .events
-            .delete_many(event_ids_to_delete.as_slice())
+            .delete_many_with_outbox(
+                event_ids_to_delete.as_slice(),
+                &outbox_messages(self, &events_to_delete, ctx).await,
+            )
             .await
             .map_err(|e| {
                 tracing::error!("[delete_many_events] Error deleting events: {:?}", e);
                 UseCaseError::StorageError
             })?;
 
-        Ok(())
+        Ok(events_to_delete)
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
     }
 }
 
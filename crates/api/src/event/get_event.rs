@@ -1,4 +1,10 @@
-use axum::{Extension, Json, extract::Path};
+use axum::{
+    Extension,
+    Json,
+    extract::Path,
+    http::header,
+    response::{IntoResponse, Response},
+};
 use nittei_api_structs::get_event::*;
 use nittei_domain::{CalendarEvent, ID, User};
 use nittei_infra::NitteiContext;
@@ -7,6 +13,7 @@ use crate::{
     error::NitteiError,
     shared::{
         auth::Policy,
+        etag::etag,
         usecase::{UseCase, execute},
     },
 };
@@ -23,13 +30,15 @@ use crate::{
         ("api_key" = [])
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "Version of the event, to use in the If-Match header when updating it")
+        ))
     )
 )]
 pub async fn get_event_admin_controller(
     Extension(event): Extension<CalendarEvent>,
     Extension(ctx): Extension<NitteiContext>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let usecase = GetEventUseCase {
         user_id: event.user_id.clone(),
         event_id: event.id.clone(),
@@ -38,7 +47,10 @@ pub async fn get_event_admin_controller(
 
     execute(usecase, &ctx)
         .await
-        .map(|event| Json(APIResponse::new(event)))
+        .map(|event| {
+            let etag = etag(event.version);
+            ([(header::ETAG, etag)], Json(APIResponse::new(event))).into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -51,14 +63,16 @@ pub async fn get_event_admin_controller(
         ("event_id" = ID, Path, description = "The id of the event to get"),
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "Version of the event, to use in the If-Match header when updating it")
+        ))
     )
 )]
 pub async fn get_event_controller(
     Extension((user, _policy)): Extension<(User, Policy)>,
     path_params: Path<PathParams>,
     Extension(ctx): Extension<NitteiContext>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let usecase = GetEventUseCase {
         event_id: path_params.event_id.clone(),
         user_id: user.id.clone(),
@@ -67,7 +81,14 @@ pub async fn get_event_controller(
 
     execute(usecase, &ctx)
         .await
-        .map(|calendar_event| Json(APIResponse::new(calendar_event)))
+        .map(|calendar_event| {
+            let etag = etag(calendar_event.version);
+            (
+                [(header::ETAG, etag)],
+                Json(APIResponse::new(calendar_event)),
+            )
+                .into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -160,12 +160,13 @@ mod tests {
     use std::sync::Arc;
 
     use chrono::{DateTime, Utc};
-    use nittei_domain::{Calendar, CalendarEventReminder, User};
+    use nittei_domain::{Calendar, CalendarEventReminder, ID, OutboxMessageStatus, User};
     use nittei_infra::{ISys, setup_context as _setup_ctx};
 
     use super::{super::create_event::CreateEventUseCase, *};
     use crate::{
         event::{delete_event::DeleteEventUseCase, update_event::UpdateEventUseCase},
+        outbox::process_outbox_messages::handle_outbox_message,
         shared::usecase::execute,
     };
 
@@ -210,6 +211,20 @@ mod tests {
         }
     }
 
+    /// The reminders are generated from the outbox, so process its pending messages for the account
+    async fn process_outbox(ctx: &NitteiContext, account_id: &ID) {
+        let messages = ctx
+            .repos
+            .outbox
+            .find_by_account(account_id, Some(OutboxMessageStatus::Pending), 100)
+            .await
+            .unwrap();
+        for message in messages {
+            handle_outbox_message(&message, ctx).await.unwrap();
+            ctx.repos.outbox.delete(&message.id).await.unwrap();
+        }
+    }
+
     async fn insert_common_data(ctx: &NitteiContext) -> (User, Calendar) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
@@ -250,6 +265,7 @@ mod tests {
         };
 
         execute(usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
 
         let sys3 = StaticTimeSys3 {};
         let usecase = CreateEventUseCase {
@@ -265,6 +281,7 @@ mod tests {
         };
 
         execute(usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
 
         ctx.sys = Arc::new(StaticTimeSys1 {});
         let usecase = GetUpcomingRemindersUseCase {
@@ -329,6 +346,7 @@ mod tests {
         };
 
         let calendar_event = execute(usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
         let old_reminders = ctx
             .repos
             .reminders
@@ -356,6 +374,7 @@ mod tests {
             ..Default::default()
         };
         execute(update_event_usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
         let new_reminders = ctx
             .repos
             .reminders
@@ -400,6 +419,7 @@ mod tests {
         };
 
         let calendar_event = execute(usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
         let old_reminders = ctx
             .repos
             .reminders
@@ -425,6 +445,7 @@ mod tests {
             ..Default::default()
         };
         execute(update_event_usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
         let new_reminders = ctx
             .repos
             .reminders
@@ -464,6 +485,7 @@ mod tests {
         };
 
         let calendar_event = execute(usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
         let old_reminders = ctx
             .repos
             .reminders
@@ -485,9 +507,12 @@ mod tests {
         let update_event_usecase = DeleteEventUseCase {
             user,
             event_id: calendar_event.id,
+            scope: None,
+            occurrence_start_time: None,
             prefetched_calendar_event: None,
         };
         execute(update_event_usecase, &ctx).await.unwrap();
+        process_outbox(&ctx, &calendar.account_id).await;
         let new_reminders = ctx
             .repos
             .reminders
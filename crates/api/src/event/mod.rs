@@ -9,12 +9,14 @@ pub mod get_events_by_calendars;
 pub mod get_events_by_meta;
 pub mod get_events_for_users_in_time_range;
 pub mod get_upcoming_reminders;
+pub mod refresh_event_instances;
 pub mod search_events;
 pub mod subscribers;
 pub mod sync_event_reminders;
 pub mod update_event;
+pub mod update_event_attendee_response;
 
-use axum::routing::{delete, get, patch, post};
+use axum::routing::{delete, get, patch, post, put};
 use create_event::{create_event_admin_controller, create_event_controller};
 use delete_event::{delete_event_admin_controller, delete_event_controller};
 use delete_many_events::delete_many_events_admin_controller;
@@ -23,6 +25,7 @@ use get_event_instances::{get_event_instances_admin_controller, get_event_instan
 use get_events_by_meta::get_events_by_meta_controller;
 use search_events::search_events_controller;
 use update_event::{update_event_admin_controller, update_event_controller};
+use update_event_attendee_response::update_event_attendee_response_controller;
 use utoipa_axum::router::OpenApiRouter;
 
 use crate::{event::create_batch_events::create_batch_events_admin_controller, shared::auth};
@@ -111,6 +114,11 @@ pub fn configure_routes() -> OpenApiRouter {
             "/events/{event_id}/instances",
             get(get_event_instances_controller),
         )
+        // Respond to an event as an attendee (RSVP)
+        .route(
+            "/events/{event_id}/attendees/me",
+            put(update_event_attendee_response_controller),
+        )
         .route_layer(axum::middleware::from_fn(auth::protect_route_middleware));
 
     OpenApiRouter::new().merge(admin_router).merge(user_router)
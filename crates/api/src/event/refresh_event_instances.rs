@@ -0,0 +1,216 @@
+use chrono::TimeDelta;
+use futures::future;
+use nittei_domain::{CalendarEvent, MaterializedEventInstances, TimeSpan};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+use tracing::error;
+
+use crate::shared::usecase::UseCase;
+
+/// Materializes the instances of recurring `CalendarEvent`s, for the freebusy and booking slots queries
+/// The instances are materialized for the window of time set in the config (see `event_instances`)
+#[derive(Debug)]
+pub struct RefreshEventInstancesUseCase<'a> {
+    pub request: RefreshEventInstancesTrigger<'a>,
+}
+
+#[derive(Debug)]
+pub enum RefreshEventInstancesTrigger<'a> {
+    /// A recurring `CalendarEvent` (or one of its exceptions) has been modified
+    EventModified(&'a CalendarEvent),
+    /// Periodic Job Scheduler that triggers this use case to materialize the instances of the
+    /// recurring `CalendarEvent`s without instances, or with instances refreshed more than a day ago
+    JobScheduler,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    StorageError,
+    CalendarNotFound,
+    InvalidRecurrence,
+}
+
+#[async_trait::async_trait]
+impl UseCase for RefreshEventInstancesUseCase<'_> {
+    /// Number of events with materialized instances
+    type Response = usize;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "RefreshEventInstances";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        match &self.request {
+            RefreshEventInstancesTrigger::EventModified(event) => {
+                refresh_event_instances(event, ctx).await.map(usize::from)
+            }
+            RefreshEventInstancesTrigger::JobScheduler => {
+                let config = &APP_CONFIG.event_instances;
+                let now = ctx.sys.get_timestamp();
+
+                let mut count = 0;
+                let mut after = None;
+                loop {
+                    let event_ids = ctx
+                        .repos
+                        .event_instances
+                        .find_events_to_refresh(
+                            now - TimeDelta::days(1),
+                            now - TimeDelta::days(config.past_days),
+                            after.take(),
+                            config.batch_size,
+                        )
+                        .await
+                        .map_err(|_| UseCaseError::StorageError)?;
+                    let Some(last_event_id) = event_ids.last() else {
+                        break;
+                    };
+                    after = Some(last_event_id.clone());
+
+                    let events = ctx
+                        .repos
+                        .events
+                        .find_many(&event_ids)
+                        .await
+                        .map_err(|_| UseCaseError::StorageError)?;
+                    // The errors are logged, the events are materialized again by the next run
+                    count += future::join_all(
+                        events
+                            .iter()
+                            .map(|event| refresh_event_instances(event, ctx)),
+                    )
+                    .await
+                    .into_iter()
+                    .filter(|res| matches!(res, Ok(true)))
+                    .count();
+
+                    if (event_ids.len() as i64) < config.batch_size {
+                        break;
+                    }
+                }
+
+                Ok(count)
+            }
+        }
+    }
+}
+
+/// Materialize the instances of the recurring event, returns false if it isn't recurring
+/// or if it changed while materializing them
+async fn refresh_event_instances(
+    event: &CalendarEvent,
+    ctx: &NitteiContext,
+) -> Result<bool, UseCaseError> {
+    let config = &APP_CONFIG.event_instances;
+    if event.recurrence.is_none() {
+        return Ok(false);
+    }
+
+    let calendar = ctx
+        .repos
+        .calendars
+        .find(&event.calendar_id)
+        .await
+        .map_err(|_| UseCaseError::StorageError)?
+        .ok_or(UseCaseError::CalendarNotFound)?;
+    let exceptions = ctx
+        .repos
+        .events
+        .find_by_id_and_recurring_event_id(&event.id)
+        .await
+        .map_err(|_| UseCaseError::StorageError)?
+        .into_iter()
+        .filter(|e| e.recurring_event_id.as_ref() == Some(&event.id))
+        .filter_map(|e| e.original_start_time)
+        .collect::<Vec<_>>();
+
+    let now = ctx.sys.get_timestamp();
+    let window = TimeSpan::new(
+        now - TimeDelta::days(config.past_days),
+        now + TimeDelta::days(config.future_days),
+    );
+    let materialized = MaterializedEventInstances::new(
+        &calendar,
+        event,
+        exceptions,
+        window,
+        config.max_instances_per_event,
+    )
+    .map_err(|e| {
+        error!(
+            event_id = %event.id,
+            error = ?e,
+            "Unable to expand the recurrence of the event"
+        );
+        UseCaseError::InvalidRecurrence
+    })?;
+
+    ctx.repos
+        .event_instances
+        .replace(&materialized, now)
+        .await
+        .map_err(|_| UseCaseError::StorageError)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{DurationRound, Utc};
+    use nittei_domain::{Account, Calendar, RRuleOptions, User};
+    use nittei_infra::setup_context;
+
+    use super::*;
+    use crate::shared::usecase::execute;
+
+    #[tokio::test]
+    async fn it_materializes_the_instances_of_the_recurring_events() {
+        let ctx = setup_context().await.unwrap();
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let calendar = Calendar::new(&user.id, &account.id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        let mut event = CalendarEvent {
+            account_id: account.id.clone(),
+            calendar_id: calendar.id.clone(),
+            user_id: user.id.clone(),
+            start_time: Utc::now().duration_trunc(TimeDelta::hours(1)).unwrap(),
+            duration: 1000 * 60 * 60,
+            busy: true,
+            ..Default::default()
+        };
+        event.set_recurrence(RRuleOptions::default()).unwrap();
+        ctx.repos.events.insert(&event).await.unwrap();
+
+        let timespan = TimeSpan::new(
+            event.start_time + TimeDelta::days(10),
+            event.start_time + TimeDelta::days(20),
+        );
+        let usecase = RefreshEventInstancesUseCase {
+            request: RefreshEventInstancesTrigger::EventModified(&event),
+        };
+        assert_eq!(execute(usecase, &ctx).await.unwrap(), 1);
+        let instances = ctx
+            .repos
+            .event_instances
+            .find_by_events(std::slice::from_ref(&event.id), timespan.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            instances.get(&event.id),
+            Some(&event.expand(Some(timespan), &calendar.settings).unwrap())
+        );
+
+        // Nothing to materialize for the events which aren't recurring
+        let single_event = CalendarEvent {
+            id: Default::default(),
+            recurrence: None,
+            ..event.clone()
+        };
+        let usecase = RefreshEventInstancesUseCase {
+            request: RefreshEventInstancesTrigger::EventModified(&single_event),
+        };
+        assert_eq!(execute(usecase, &ctx).await.unwrap(), 0);
+    }
+}
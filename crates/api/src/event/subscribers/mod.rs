@@ -1,112 +1,310 @@
-use nittei_domain::{CalendarEvent, IntegrationProvider, SyncedCalendarEvent};
+use chrono::{DateTime, TimeDelta, Utc};
+use nittei_api_structs::{
+    import_calendar_ical::IcalImportAction,
+    send_event_webhook::EventWebhookPayload,
+};
+use nittei_domain::{
+    CalendarEvent,
+    CalendarEventStatus,
+    IntegrationProvider,
+    OutboxMessage,
+    OutboxTask,
+    SyncedCalendarEvent,
+    WebhookDelivery,
+    WebhookEventType,
+};
 use nittei_infra::{
+    NitteiContext,
     google_calendar::GoogleCalendarProvider,
     outlook_calendar::OutlookCalendarProvider,
 };
+use nittei_utils::config::APP_CONFIG;
 use tracing::{error, info};
 
 use super::{
     create_event::CreateEventUseCase,
+    delete_event::DeleteEventUseCase,
+    delete_many_events::DeleteManyEventsUseCase,
+    refresh_event_instances::{RefreshEventInstancesTrigger, RefreshEventInstancesUseCase},
     sync_event_reminders::{EventOperation, SyncEventRemindersTrigger, SyncEventRemindersUseCase},
     update_event::UpdateEventUseCase,
 };
 use crate::{
+    calendar::import_ical::ImportCalendarIcalUseCase,
     event::create_batch_events::CreateBatchEventsUseCase,
-    shared::usecase::{Subscriber, execute},
+    event_group::{
+        cancel_event_group_events::CancelEventGroupEventsUseCase,
+        delete_event_group_events::DeleteEventGroupEventsUseCase,
+        shift_event_group_events::ShiftEventGroupEventsUseCase,
+    },
+    service::{
+        cancel_service_booking::CancelServiceBookingUseCase,
+        create_service_booking::CreateServiceBookingUseCase,
+        reschedule_service_booking::RescheduleServiceBookingUseCase,
+    },
+    shared::usecase::{Subscriber, UseCase, execute},
+    webhook::process_webhook_deliveries::deliver_webhook,
 };
 
 pub struct CreateRemindersOnEventCreated;
 
-#[async_trait::async_trait]
-impl Subscriber<CreateEventUseCase> for CreateRemindersOnEventCreated {
-    async fn notify(&self, e: &CalendarEvent, ctx: &nittei_infra::NitteiContext) {
+impl CreateRemindersOnEventCreated {
+    fn outbox_message(e: &CalendarEvent, now: DateTime<Utc>) -> OutboxMessage {
+        OutboxMessage::new(
+            e.account_id.clone(),
+            OutboxTask::CreateEventReminders {
+                event_id: e.id.clone(),
+            },
+            now,
+        )
+    }
+
+    /// Generate the reminders of the created event (processed from the outbox)
+    pub async fn process(e: &CalendarEvent, ctx: &NitteiContext) -> anyhow::Result<()> {
         let sync_event_reminders = SyncEventRemindersUseCase {
             request: SyncEventRemindersTrigger::EventModified(e, EventOperation::Created),
         };
 
-        // Sideeffect, ignore result
-        let _ = execute(sync_event_reminders, ctx).await;
+        execute(sync_event_reminders, ctx)
+            .await
+            .map_err(|e| anyhow::anyhow!("Unable to create the event reminders: {e:?}"))
+    }
+}
+
+impl Subscriber<CreateEventUseCase> for CreateRemindersOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        vec![Self::outbox_message(e, now)]
     }
 }
 
-#[async_trait::async_trait]
 impl Subscriber<CreateBatchEventsUseCase> for CreateRemindersOnEventCreated {
-    async fn notify(&self, events: &Vec<CalendarEvent>, ctx: &nittei_infra::NitteiContext) {
-        for event in events {
-            let sync_event_reminders = SyncEventRemindersUseCase {
-                request: SyncEventRemindersTrigger::EventModified(event, EventOperation::Created),
-            };
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateBatchEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
 
-            // Sideeffect, ignore result
-            let _ = execute(sync_event_reminders, ctx).await;
-        }
+impl Subscriber<CreateServiceBookingUseCase> for CreateRemindersOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<RescheduleServiceBookingUseCase> for CreateRemindersOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &RescheduleServiceBookingUseCase,
+        res: &<RescheduleServiceBookingUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<ImportCalendarIcalUseCase> for CreateRemindersOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &ImportCalendarIcalUseCase,
+        res: &<ImportCalendarIcalUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.written_events(IcalImportAction::Created)
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
     }
 }
+
 pub struct SyncRemindersOnEventUpdated;
 
-#[async_trait::async_trait]
-impl Subscriber<UpdateEventUseCase> for SyncRemindersOnEventUpdated {
-    async fn notify(&self, e: &CalendarEvent, ctx: &nittei_infra::NitteiContext) {
+impl SyncRemindersOnEventUpdated {
+    fn outbox_message(e: &CalendarEvent, now: DateTime<Utc>) -> OutboxMessage {
+        OutboxMessage::new(
+            e.account_id.clone(),
+            OutboxTask::SyncEventReminders {
+                event_id: e.id.clone(),
+            },
+            now,
+        )
+    }
+
+    /// Regenerate the reminders of the updated event (processed from the outbox)
+    pub async fn process(e: &CalendarEvent, ctx: &NitteiContext) -> anyhow::Result<()> {
         let sync_event_reminders = SyncEventRemindersUseCase {
             request: SyncEventRemindersTrigger::EventModified(e, EventOperation::Updated),
         };
 
-        // Sideeffect, ignore result
-        let _ = execute(sync_event_reminders, ctx).await;
+        execute(sync_event_reminders, ctx)
+            .await
+            .map_err(|e| anyhow::anyhow!("Unable to sync the event reminders: {e:?}"))
+    }
+}
+
+impl Subscriber<UpdateEventUseCase> for SyncRemindersOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &UpdateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        vec![Self::outbox_message(e, now)]
+    }
+}
+
+impl Subscriber<CancelEventGroupEventsUseCase> for SyncRemindersOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &CancelEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<ShiftEventGroupEventsUseCase> for SyncRemindersOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &ShiftEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<CancelServiceBookingUseCase> for SyncRemindersOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &CancelServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<RescheduleServiceBookingUseCase> for SyncRemindersOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &RescheduleServiceBookingUseCase,
+        res: &<RescheduleServiceBookingUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.cancelled_events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<ImportCalendarIcalUseCase> for SyncRemindersOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &ImportCalendarIcalUseCase,
+        res: &<ImportCalendarIcalUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.written_events(IcalImportAction::Updated)
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
     }
 }
 
 pub struct CreateSyncedEventsOnEventCreated;
 
-#[async_trait::async_trait]
-impl Subscriber<CreateEventUseCase> for CreateSyncedEventsOnEventCreated {
-    async fn notify(&self, e: &CalendarEvent, ctx: &nittei_infra::NitteiContext) {
-        let synced_calendars = match ctx
+impl CreateSyncedEventsOnEventCreated {
+    fn outbox_message(e: &CalendarEvent, now: DateTime<Utc>) -> OutboxMessage {
+        OutboxMessage::new(
+            e.account_id.clone(),
+            OutboxTask::CreateSyncedEvents {
+                event_id: e.id.clone(),
+            },
+            now,
+        )
+    }
+
+    /// Create the event in the external calendars synced with its calendar (processed from the outbox)
+    /// The calendars where the event has already been created (previous attempt) are skipped
+    pub async fn process(e: &CalendarEvent, ctx: &NitteiContext) -> anyhow::Result<()> {
+        let synced_calendars = ctx
             .repos
             .calendar_synced
             .find_by_calendar(&e.calendar_id)
             .await
-        {
-            Ok(synced_calendars) => synced_calendars,
-            Err(e) => {
-                error!("Unable to query synced calendars from repo: {:?}", e);
-                return;
-            }
+            .map_err(|e| anyhow::anyhow!("Unable to query synced calendars from repo: {e:?}"))?;
+        let already_synced_events = ctx
+            .repos
+            .event_synced
+            .find_by_event(&e.id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Unable to query synced events from repo: {e:?}"))?;
+        let is_not_synced_yet = |ext_calendar_id: &String| {
+            !already_synced_events
+                .iter()
+                .any(|synced_event| &synced_event.ext_calendar_id == ext_calendar_id)
         };
 
         let synced_outlook_calendars = synced_calendars
             .iter()
             .filter(|cal| cal.provider == IntegrationProvider::Outlook)
+            .filter(|cal| is_not_synced_yet(&cal.ext_calendar_id))
             .collect::<Vec<_>>();
         let synced_google_calendars = synced_calendars
             .iter()
             .filter(|cal| cal.provider == IntegrationProvider::Google)
+            .filter(|cal| is_not_synced_yet(&cal.ext_calendar_id))
             .collect::<Vec<_>>();
 
         if synced_google_calendars.is_empty() && synced_outlook_calendars.is_empty() {
-            return;
+            return Ok(());
         }
-        let user = ctx.repos.users.find(&e.user_id).await;
-        let user = match user {
+        let user = match ctx.repos.users.find(&e.user_id).await {
             Ok(Some(u)) => u,
-            Ok(None) => {
-                error!("Unable to find user when creating sync events");
-                return;
-            }
-            Err(e) => {
-                error!("Unable to find user when creating sync events {:?}", e);
-                return;
-            }
+            Ok(None) => anyhow::bail!("Unable to find user when creating sync events"),
+            Err(e) => anyhow::bail!("Unable to find user when creating sync events {e:?}"),
         };
 
+        // Keep going with the other calendars on failure, the message is retried afterwards
+        let mut failed = false;
+
         if !synced_outlook_calendars.is_empty() {
-            let provider = match OutlookCalendarProvider::new(&user, ctx).await {
-                Ok(p) => p,
-                Err(_) => {
-                    error!("Unable to create outlook calendar provider");
-                    return;
-                }
-            };
+            let provider = OutlookCalendarProvider::new(&user, ctx)
+                .await
+                .map_err(|_| anyhow::anyhow!("Unable to create outlook calendar provider"))?;
             for synced_o_cal in synced_outlook_calendars {
                 let ext_event = match provider
                     .create_event(synced_o_cal.ext_calendar_id.clone(), e.clone())
@@ -115,6 +313,7 @@ impl Subscriber<CreateEventUseCase> for CreateSyncedEventsOnEventCreated {
                     Ok(e) => e,
                     Err(_) => {
                         error!("Unable to create external outlook calendar event");
+                        failed = true;
                         continue;
                     }
                 };
@@ -129,18 +328,15 @@ impl Subscriber<CreateEventUseCase> for CreateSyncedEventsOnEventCreated {
                 };
                 if ctx.repos.event_synced.insert(&synced_event).await.is_err() {
                     error!("Unable to insert outlook synced calendar event into repo");
+                    failed = true;
                 }
             }
         }
 
         if !synced_google_calendars.is_empty() {
-            let provider = match GoogleCalendarProvider::new(&user, ctx).await {
-                Ok(p) => p,
-                Err(_) => {
-                    error!("Unable to create google calendar provider");
-                    return;
-                }
-            };
+            let provider = GoogleCalendarProvider::new(&user, ctx)
+                .await
+                .map_err(|_| anyhow::anyhow!("Unable to create google calendar provider"))?;
             for synced_g_cal in synced_google_calendars {
                 let ext_event = match provider
                     .create_event(synced_g_cal.ext_calendar_id.clone(), e.clone())
@@ -149,6 +345,7 @@ impl Subscriber<CreateEventUseCase> for CreateSyncedEventsOnEventCreated {
                     Ok(e) => e,
                     Err(_) => {
                         error!("Unable to create google external calendar event");
+                        failed = true;
                         continue;
                     }
                 };
@@ -163,35 +360,108 @@ impl Subscriber<CreateEventUseCase> for CreateSyncedEventsOnEventCreated {
                 };
                 if ctx.repos.event_synced.insert(&synced_event).await.is_err() {
                     error!("Unable to insert google synced calendar event into repo");
+                    failed = true;
                 } else {
                     info!("Inserted google synced events ");
                 }
             }
         }
+
+        if failed {
+            anyhow::bail!("Unable to create the event in some of the synced calendars");
+        }
+        Ok(())
+    }
+}
+
+impl Subscriber<CreateEventUseCase> for CreateSyncedEventsOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        vec![Self::outbox_message(e, now)]
     }
 }
 
-#[async_trait::async_trait]
 impl Subscriber<CreateBatchEventsUseCase> for CreateSyncedEventsOnEventCreated {
-    async fn notify(&self, events: &Vec<CalendarEvent>, ctx: &nittei_infra::NitteiContext) {
-        for event in events {
-            // Re-use the same subscriber for single events and many events
-            <Self as Subscriber<CreateEventUseCase>>::notify(self, event, ctx).await;
-        }
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateBatchEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<CreateServiceBookingUseCase> for CreateSyncedEventsOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
     }
 }
+
+impl Subscriber<RescheduleServiceBookingUseCase> for CreateSyncedEventsOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &RescheduleServiceBookingUseCase,
+        res: &<RescheduleServiceBookingUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<ImportCalendarIcalUseCase> for CreateSyncedEventsOnEventCreated {
+    fn outbox_messages(
+        &self,
+        _usecase: &ImportCalendarIcalUseCase,
+        res: &<ImportCalendarIcalUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.written_events(IcalImportAction::Created)
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
 pub struct UpdateSyncedEventsOnEventUpdated;
 
-#[async_trait::async_trait]
-impl Subscriber<UpdateEventUseCase> for UpdateSyncedEventsOnEventUpdated {
-    async fn notify(&self, e: &CalendarEvent, ctx: &nittei_infra::NitteiContext) {
-        let synced_events = match ctx.repos.event_synced.find_by_event(&e.id).await {
-            Ok(synced_calendars) => synced_calendars,
-            Err(e) => {
-                error!("Unable to query synced events from repo: {:?}", e);
-                return;
-            }
-        };
+impl UpdateSyncedEventsOnEventUpdated {
+    fn outbox_message(e: &CalendarEvent, now: DateTime<Utc>) -> OutboxMessage {
+        OutboxMessage::new(
+            e.account_id.clone(),
+            OutboxTask::UpdateSyncedEvents {
+                event_id: e.id.clone(),
+            },
+            now,
+        )
+    }
+
+    /// Update the event in the external calendars it has been synced to (processed from the outbox)
+    pub async fn process(e: &CalendarEvent, ctx: &NitteiContext) -> anyhow::Result<()> {
+        let synced_events = ctx
+            .repos
+            .event_synced
+            .find_by_event(&e.id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Unable to query synced events from repo: {e:?}"))?;
 
         let synced_outlook_events = synced_events
             .iter()
@@ -203,28 +473,21 @@ impl Subscriber<UpdateEventUseCase> for UpdateSyncedEventsOnEventUpdated {
             .collect::<Vec<_>>();
 
         if synced_google_events.is_empty() && synced_outlook_events.is_empty() {
-            return;
+            return Ok(());
         }
         let user = match ctx.repos.users.find(&e.user_id).await {
             Ok(Some(u)) => u,
-            Ok(None) => {
-                error!("Unable to find user when updating sync events");
-                return;
-            }
-            Err(e) => {
-                error!("Unable to find user when updating sync events {:?}", e);
-                return;
-            }
+            Ok(None) => anyhow::bail!("Unable to find user when updating sync events"),
+            Err(e) => anyhow::bail!("Unable to find user when updating sync events {e:?}"),
         };
 
+        // Keep going with the other events on failure, the message is retried afterwards
+        let mut failed = false;
+
         if !synced_outlook_events.is_empty() {
-            let provider = match OutlookCalendarProvider::new(&user, ctx).await {
-                Ok(p) => p,
-                Err(_) => {
-                    error!("Unable to create outlook calendar provider");
-                    return;
-                }
-            };
+            let provider = OutlookCalendarProvider::new(&user, ctx)
+                .await
+                .map_err(|_| anyhow::anyhow!("Unable to create outlook calendar provider"))?;
             for synced_o_event in synced_outlook_events {
                 if provider
                     .update_event(
@@ -236,18 +499,15 @@ impl Subscriber<UpdateEventUseCase> for UpdateSyncedEventsOnEventUpdated {
                     .is_err()
                 {
                     error!("Unable to update external outlook calendar event");
+                    failed = true;
                 };
             }
         }
 
         if !synced_google_events.is_empty() {
-            let provider = match GoogleCalendarProvider::new(&user, ctx).await {
-                Ok(p) => p,
-                Err(_) => {
-                    error!("Unable to create google calendar provider");
-                    return;
-                }
-            };
+            let provider = GoogleCalendarProvider::new(&user, ctx)
+                .await
+                .map_err(|_| anyhow::anyhow!("Unable to create google calendar provider"))?;
             for synced_g_event in synced_google_events {
                 if provider
                     .update_event(
@@ -259,8 +519,497 @@ impl Subscriber<UpdateEventUseCase> for UpdateSyncedEventsOnEventUpdated {
                     .is_err()
                 {
                     error!("Unable to update google external calendar event");
+                    failed = true;
                 };
             }
         }
+
+        if failed {
+            anyhow::bail!("Unable to update the event in some of the synced calendars");
+        }
+        Ok(())
+    }
+}
+
+impl Subscriber<UpdateEventUseCase> for UpdateSyncedEventsOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &UpdateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        vec![Self::outbox_message(e, now)]
+    }
+}
+
+impl Subscriber<CancelServiceBookingUseCase> for UpdateSyncedEventsOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &CancelServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<RescheduleServiceBookingUseCase> for UpdateSyncedEventsOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &RescheduleServiceBookingUseCase,
+        res: &<RescheduleServiceBookingUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.cancelled_events
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+impl Subscriber<ImportCalendarIcalUseCase> for UpdateSyncedEventsOnEventUpdated {
+    fn outbox_messages(
+        &self,
+        _usecase: &ImportCalendarIcalUseCase,
+        res: &<ImportCalendarIcalUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        res.written_events(IcalImportAction::Updated)
+            .iter()
+            .map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+}
+
+pub struct RefreshInstancesOnEventChanged;
+
+impl RefreshInstancesOnEventChanged {
+    /// The instances of a recurring event are refreshed when it changes, or when one of its exceptions changes
+    fn outbox_message(e: &CalendarEvent, now: DateTime<Utc>) -> Option<OutboxMessage> {
+        let event_id = match (&e.recurring_event_id, &e.recurrence) {
+            (Some(recurring_event_id), _) => recurring_event_id,
+            (None, Some(_)) => &e.id,
+            (None, None) => return None,
+        };
+        Some(OutboxMessage::new(
+            e.account_id.clone(),
+            OutboxTask::RefreshEventInstances {
+                event_id: event_id.clone(),
+            },
+            now,
+        ))
+    }
+
+    fn outbox_messages_for_events(
+        events: &[CalendarEvent],
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .filter_map(|e| Self::outbox_message(e, now))
+            .collect()
+    }
+
+    /// Materialize the instances of the recurring event (processed from the outbox)
+    pub async fn process(e: &CalendarEvent, ctx: &NitteiContext) -> anyhow::Result<()> {
+        let refresh_event_instances = RefreshEventInstancesUseCase {
+            request: RefreshEventInstancesTrigger::EventModified(e),
+        };
+
+        execute(refresh_event_instances, ctx)
+            .await
+            .map(|_| ())
+            .map_err(|e| anyhow::anyhow!("Unable to refresh the event instances: {e:?}"))
+    }
+}
+
+impl Subscriber<CreateEventUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_message(e, now).into_iter().collect()
+    }
+}
+
+impl Subscriber<CreateBatchEventsUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateBatchEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, now)
+    }
+}
+
+impl Subscriber<CreateServiceBookingUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, now)
+    }
+}
+
+impl Subscriber<CancelServiceBookingUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &CancelServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, now)
+    }
+}
+
+impl Subscriber<RescheduleServiceBookingUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &RescheduleServiceBookingUseCase,
+        res: &<RescheduleServiceBookingUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        let mut messages = Self::outbox_messages_for_events(&res.cancelled_events, now);
+        messages.extend(Self::outbox_messages_for_events(&res.events, now));
+        messages
+    }
+}
+
+impl Subscriber<ImportCalendarIcalUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &ImportCalendarIcalUseCase,
+        res: &<ImportCalendarIcalUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        let mut events = res.written_events(IcalImportAction::Created);
+        events.extend(res.written_events(IcalImportAction::Updated));
+        Self::outbox_messages_for_events(&events, now)
+    }
+}
+
+impl Subscriber<UpdateEventUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &UpdateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_message(e, now).into_iter().collect()
+    }
+}
+
+impl Subscriber<DeleteEventUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &DeleteEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_message(e, now).into_iter().collect()
+    }
+}
+
+impl Subscriber<DeleteManyEventsUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &DeleteManyEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, now)
+    }
+}
+
+impl Subscriber<CancelEventGroupEventsUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &CancelEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, now)
+    }
+}
+
+impl Subscriber<ShiftEventGroupEventsUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &ShiftEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, now)
+    }
+}
+
+impl Subscriber<DeleteEventGroupEventsUseCase> for RefreshInstancesOnEventChanged {
+    fn outbox_messages(
+        &self,
+        _usecase: &DeleteEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, now)
+    }
+}
+
+pub struct SendEventWebhook;
+
+impl SendEventWebhook {
+    fn outbox_message(
+        e: &CalendarEvent,
+        event_type: WebhookEventType,
+        now: DateTime<Utc>,
+    ) -> Option<OutboxMessage> {
+        let payload = EventWebhookPayload::new(event_type, e.clone(), now);
+        match serde_json::to_value(payload) {
+            Ok(payload) => Some(OutboxMessage::new(
+                e.account_id.clone(),
+                OutboxTask::SendWebhook {
+                    event_type,
+                    payload,
+                },
+                now,
+            )),
+            Err(err) => {
+                error!("Unable to serialize the webhook payload: {:?}", err);
+                None
+            }
+        }
+    }
+
+    fn outbox_messages_for_events(
+        events: &[CalendarEvent],
+        event_type: WebhookEventType,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        events
+            .iter()
+            .filter_map(|e| Self::outbox_message(e, event_type, now))
+            .collect()
+    }
+
+    /// Send the change of the event to the account webhook (processed from the outbox)
+    /// Nothing is sent if the account has no webhook, or hasn't opted in to this type of change
+    ///
+    /// The webhook is recorded as a delivery with the ID of the outbox message, so that processing
+    /// the message again doesn't record it twice. If it fails, the delivery is retried by the webhook worker.
+    pub async fn process(
+        message: &OutboxMessage,
+        event_type: WebhookEventType,
+        payload: &serde_json::Value,
+        ctx: &NitteiContext,
+    ) -> anyhow::Result<()> {
+        let account = ctx
+            .repos
+            .accounts
+            .find(&message.account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Unable to find account when sending webhook {e:?}"))?;
+        let Some(webhook) = account.and_then(|account| account.settings.webhook) else {
+            return Ok(());
+        };
+        if !webhook.is_subscribed_to(event_type) {
+            return Ok(());
+        }
+
+        let mut delivery = WebhookDelivery::new(
+            message.account_id.clone(),
+            Some(event_type),
+            payload.clone(),
+            TimeDelta::seconds(APP_CONFIG.webhook.retry_horizon_secs),
+            ctx.sys.get_timestamp(),
+        );
+        delivery.id = message.id.clone();
+        deliver_webhook(delivery, &webhook, ctx).await
+    }
+}
+
+impl Subscriber<CreateEventUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_message(e, WebhookEventType::EventCreated, now)
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Subscriber<CreateBatchEventsUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateBatchEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, WebhookEventType::EventCreated, now)
+    }
+}
+
+impl Subscriber<CreateServiceBookingUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &CreateServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, WebhookEventType::EventCreated, now)
+    }
+}
+
+impl Subscriber<CancelServiceBookingUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &CancelServiceBookingUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, WebhookEventType::EventCancelled, now)
+    }
+}
+
+impl Subscriber<RescheduleServiceBookingUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &RescheduleServiceBookingUseCase,
+        res: &<RescheduleServiceBookingUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        let mut messages = Self::outbox_messages_for_events(
+            &res.cancelled_events,
+            WebhookEventType::EventCancelled,
+            now,
+        );
+        messages.extend(Self::outbox_messages_for_events(
+            &res.events,
+            WebhookEventType::EventCreated,
+            now,
+        ));
+        messages
+    }
+}
+
+impl Subscriber<ImportCalendarIcalUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &ImportCalendarIcalUseCase,
+        res: &<ImportCalendarIcalUseCase as UseCase>::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        let mut messages = Self::outbox_messages_for_events(
+            &res.written_events(IcalImportAction::Created),
+            WebhookEventType::EventCreated,
+            now,
+        );
+        for change in res
+            .changes
+            .iter()
+            .filter(|change| !res.dry_run && change.action == IcalImportAction::Updated)
+        {
+            let event_type = if change.changed_fields.contains(&"status")
+                && change.event.status == CalendarEventStatus::Cancelled
+            {
+                WebhookEventType::EventCancelled
+            } else {
+                WebhookEventType::EventUpdated
+            };
+            messages.extend(Self::outbox_message(&change.event, event_type, now));
+        }
+        messages
+    }
+}
+
+impl Subscriber<UpdateEventUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        usecase: &UpdateEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        // Updating a single occurrence or the following ones can create a new event (exception or series)
+        let event_type = if e.version == 0 {
+            WebhookEventType::EventCreated
+        } else if usecase.status == Some(CalendarEventStatus::Cancelled) {
+            WebhookEventType::EventCancelled
+        } else {
+            WebhookEventType::EventUpdated
+        };
+        Self::outbox_message(e, event_type, now)
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Subscriber<DeleteEventUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        usecase: &DeleteEventUseCase,
+        e: &CalendarEvent,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        // Deleting some occurrences of a recurring event updates it
+        let event_type = match usecase.targeted_occurrence(e) {
+            Ok(Some(_)) => WebhookEventType::EventUpdated,
+            _ => WebhookEventType::EventDeleted,
+        };
+        Self::outbox_message(e, event_type, now)
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Subscriber<DeleteManyEventsUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &DeleteManyEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, WebhookEventType::EventDeleted, now)
+    }
+}
+
+impl Subscriber<CancelEventGroupEventsUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &CancelEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, WebhookEventType::EventCancelled, now)
+    }
+}
+
+impl Subscriber<ShiftEventGroupEventsUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &ShiftEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, WebhookEventType::EventUpdated, now)
+    }
+}
+
+impl Subscriber<DeleteEventGroupEventsUseCase> for SendEventWebhook {
+    fn outbox_messages(
+        &self,
+        _usecase: &DeleteEventGroupEventsUseCase,
+        events: &Vec<CalendarEvent>,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage> {
+        Self::outbox_messages_for_events(events, WebhookEventType::EventDeleted, now)
     }
 }
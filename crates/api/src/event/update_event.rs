@@ -1,15 +1,29 @@
-use axum::{Extension, Json, extract::Path};
+use axum::{
+    Extension,
+    Json,
+    extract::Path,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+};
 use axum_valid::Valid;
 use chrono::{DateTime, TimeDelta, Utc};
-use event::subscribers::SyncRemindersOnEventUpdated;
+use event::subscribers::{
+    RefreshInstancesOnEventChanged,
+    SendEventWebhook,
+    SyncRemindersOnEventUpdated,
+};
 use nittei_api_structs::update_event::*;
 use nittei_domain::{
     Account,
     CalendarEvent,
+    CalendarEventAttendee,
     CalendarEventReminder,
     CalendarEventStatus,
+    CalendarSettings,
     ID,
     RRuleOptions,
+    RecurringEventScope,
+    Tz,
     User,
 };
 use nittei_infra::NitteiContext;
@@ -17,10 +31,22 @@ use nittei_utils::config::APP_CONFIG;
 
 use crate::{
     error::NitteiError,
-    event::{self, subscribers::UpdateSyncedEventsOnEventUpdated},
+    event::{
+        self,
+        create_event::attendees_belong_to_account,
+        subscribers::UpdateSyncedEventsOnEventUpdated,
+    },
     shared::{
         auth::{Permission, Policy, account_can_modify_user},
-        usecase::{PermissionBoundary, Subscriber, UseCase, execute, execute_with_policy},
+        etag::{etag, parse_if_match},
+        usecase::{
+            PermissionBoundary,
+            Subscriber,
+            UseCase,
+            execute,
+            execute_with_policy,
+            outbox_messages,
+        },
     },
 };
 
@@ -31,6 +57,7 @@ use crate::{
     summary = "Update an event (admin only)",
     params(
         ("event_id" = ID, Path, description = "The id of the event to update"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the event, the update is rejected (412) if the event has been modified in the meantime"),
     ),
     security(
         ("api_key" = [])
@@ -39,15 +66,18 @@ use crate::{
         content = UpdateEventRequestBody,
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "New version of the event")
+        ))
     )
 )]
 pub async fn update_event_admin_controller(
     Extension(account): Extension<Account>,
     Extension(event): Extension<CalendarEvent>,
     Extension(ctx): Extension<NitteiContext>,
+    headers: HeaderMap,
     body: Valid<Json<UpdateEventRequestBody>>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let user = account_can_modify_user(&account, &event.user_id, &ctx).await?;
 
     let mut body = body.0;
@@ -65,15 +95,21 @@ pub async fn update_event_admin_controller(
         duration: body.duration,
         start_time: body.start_time,
         reminders: body.reminders.take(),
+        attendees: body.attendees.take(),
         busy: body.busy,
         service_id: body.service_id.take(),
+        group_id: body.group_id.take(),
         recurrence: body.recurrence.take(),
+        timezone: body.timezone,
         exdates: body.exdates.take(),
         recurring_event_id: body.recurring_event_id.take(),
         original_start_time: body.original_start_time,
         metadata: body.metadata.take(),
         created: body.created,
         updated: body.updated,
+        scope: body.scope,
+        occurrence_start_time: body.occurrence_start_time,
+        expected_version: parse_if_match(&headers)?,
 
         // Prefetched event by the route guard
         prefetched_calendar_event: Some(event),
@@ -81,7 +117,10 @@ pub async fn update_event_admin_controller(
 
     execute(usecase, &ctx)
         .await
-        .map(|event| Json(APIResponse::new(event)))
+        .map(|event| {
+            let etag = etag(event.version);
+            ([(header::ETAG, etag)], Json(APIResponse::new(event))).into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -92,20 +131,24 @@ pub async fn update_event_admin_controller(
     summary = "Update an event (user only)",
     params(
         ("event_id" = ID, Path, description = "The id of the event to update"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the event, the update is rejected (412) if the event has been modified in the meantime"),
     ),
     request_body(
         content = UpdateEventRequestBody,
     ),
     responses(
-        (status = 200, body = APIResponse)
+        (status = 200, body = APIResponse, headers(
+            ("ETag" = String, description = "New version of the event")
+        ))
     )
 )]
 pub async fn update_event_controller(
     path_params: Path<PathParams>,
     Extension((user, policy)): Extension<(User, Policy)>,
     Extension(ctx): Extension<NitteiContext>,
+    headers: HeaderMap,
     body: Valid<Json<UpdateEventRequestBody>>,
-) -> Result<Json<APIResponse>, NitteiError> {
+) -> Result<Response, NitteiError> {
     let mut body = body.0;
     let usecase = UpdateEventUseCase {
         user,
@@ -121,21 +164,30 @@ pub async fn update_event_controller(
         duration: body.duration,
         start_time: body.start_time,
         reminders: body.reminders.take(),
+        attendees: body.attendees.take(),
         busy: body.busy,
         service_id: body.service_id.take(),
+        group_id: body.group_id.take(),
         recurrence: body.recurrence.take(),
+        timezone: body.timezone,
         exdates: body.exdates.take(),
         recurring_event_id: body.recurring_event_id.take(),
         original_start_time: body.original_start_time,
         metadata: body.metadata.take(),
         created: body.created,
         updated: body.updated,
+        scope: body.scope,
+        occurrence_start_time: body.occurrence_start_time,
+        expected_version: parse_if_match(&headers)?,
         prefetched_calendar_event: None,
     };
 
     execute_with_policy(usecase, &policy, &ctx)
         .await
-        .map(|event| Json(APIResponse::new(event)))
+        .map(|event| {
+            let etag = etag(event.version);
+            ([(header::ETAG, etag)], Json(APIResponse::new(event))).into_response()
+        })
         .map_err(NitteiError::from)
 }
 
@@ -157,8 +209,11 @@ pub struct UpdateEventUseCase {
     pub busy: Option<bool>,
     pub duration: Option<i64>,
     pub reminders: Option<Vec<CalendarEventReminder>>,
+    pub attendees: Option<Vec<CalendarEventAttendee>>,
     pub service_id: Option<Option<ID>>,
+    pub group_id: Option<Option<ID>>,
     pub recurrence: Option<Option<RRuleOptions>>,
+    pub timezone: Option<Option<Tz>>,
     pub exdates: Option<Vec<DateTime<Utc>>>,
     pub recurring_event_id: Option<Option<ID>>,
     pub original_start_time: Option<Option<DateTime<Utc>>>,
@@ -166,6 +221,14 @@ pub struct UpdateEventUseCase {
     pub created: Option<DateTime<Utc>>,
     pub updated: Option<DateTime<Utc>>,
 
+    /// Scope of the update for recurring events (default is the whole series)
+    pub scope: Option<RecurringEventScope>,
+    /// Start time of the occurrence targeted by the scope
+    pub occurrence_start_time: Option<DateTime<Utc>>,
+
+    /// Version of the event expected by the client (If-Match header)
+    pub expected_version: Option<i64>,
+
     /// Event that has been potentially prefetched by the route guard
     /// Only happens in the admin controller
     pub prefetched_calendar_event: Option<CalendarEvent>,
@@ -175,8 +238,11 @@ pub struct UpdateEventUseCase {
 pub enum UseCaseError {
     NotFound(String, ID),
     InvalidReminder,
+    InvalidAttendees,
     StorageError,
     InvalidRecurrenceRule,
+    InvalidScope(String),
+    VersionMismatch,
 }
 
 impl From<UseCaseError> for NitteiError {
@@ -191,6 +257,13 @@ impl From<UseCaseError> for NitteiError {
             UseCaseError::InvalidReminder => {
                 Self::BadClientData("Invalid reminder specified for the event".into())
             }
+            UseCaseError::InvalidAttendees => {
+                Self::BadClientData("Invalid attendees specified for the event".into())
+            }
+            UseCaseError::InvalidScope(reason) => Self::BadClientData(reason),
+            UseCaseError::VersionMismatch => {
+                Self::PreconditionFailed("The event has been modified since it was fetched".into())
+            }
             UseCaseError::StorageError => Self::InternalError,
         }
     }
@@ -205,9 +278,248 @@ impl UseCase for UpdateEventUseCase {
     const NAME: &'static str = "UpdateEvent";
 
     async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let e = match &self.prefetched_calendar_event {
+            Some(event) => Some(event.clone()),
+            None => ctx.repos.events.find(&self.event_id).await.map_err(|e| {
+                tracing::error!("[update_event] Error finding event: {:?}", e);
+                UseCaseError::StorageError
+            })?,
+        };
+
+        let mut e = match e {
+            Some(event) if event.user_id == self.user.id => event,
+            Some(_) => {
+                return Err(UseCaseError::NotFound(
+                    "Calendar Event".into(),
+                    self.event_id.clone(),
+                ));
+            }
+            None => {
+                return Err(UseCaseError::NotFound(
+                    "Calendar Event".into(),
+                    self.event_id.clone(),
+                ));
+            }
+        };
+
+        if let Some(expected_version) = self.expected_version
+            && expected_version != e.version
+        {
+            return Err(UseCaseError::VersionMismatch);
+        }
+
+        let occurrence_start_time = match (self.scope, self.occurrence_start_time) {
+            (None | Some(RecurringEventScope::All), _) => None,
+            // Targeting the first occurrence with "this and following" is the same as targeting the whole series
+            (Some(RecurringEventScope::ThisAndFollowing), Some(occurrence_start_time))
+                if occurrence_start_time == e.start_time =>
+            {
+                None
+            }
+            (Some(_), Some(occurrence_start_time)) => Some(occurrence_start_time),
+            (Some(_), None) => {
+                return Err(UseCaseError::InvalidScope(
+                    "The start time of the occurrence is required".into(),
+                ));
+            }
+        };
+
+        let Some(occurrence_start_time) = occurrence_start_time else {
+            self.apply_changes(&mut e, ctx).await?;
+            self.save_event(&mut e, ctx).await?;
+
+            return Ok(e);
+        };
+
+        validate_occurrence(&e, occurrence_start_time, ctx).await?;
+
+        // The exceptions of the series, the series itself is filtered out
+        let exceptions = ctx
+            .repos
+            .events
+            .find_by_id_and_recurring_event_id(&e.id)
+            .await
+            .map_err(|e| {
+                tracing::error!("[update_event] Error finding exceptions: {:?}", e);
+                UseCaseError::StorageError
+            })?
+            .into_iter()
+            .filter(|exception| exception.id != e.id)
+            .collect::<Vec<_>>();
+
+        if self.scope == Some(RecurringEventScope::This) {
+            if matches!(self.recurrence, Some(Some(_))) {
+                return Err(UseCaseError::InvalidScope(
+                    "The recurrence can't be changed for a single occurrence".into(),
+                ));
+            }
+
+            // Update the existing exception for this occurrence, or create a new one
+            let existing_exception = exceptions
+                .into_iter()
+                .find(|exception| exception.original_start_time == Some(occurrence_start_time));
+            let is_new_exception = existing_exception.is_none();
+            let mut exception = existing_exception.unwrap_or_else(|| CalendarEvent {
+                id: Default::default(),
+                start_time: occurrence_start_time,
+                end_time: occurrence_start_time + TimeDelta::milliseconds(e.duration),
+                recurrence: None,
+                recurring_until: None,
+                exdates: Vec::new(),
+                recurring_event_id: Some(e.id.clone()),
+                original_start_time: Some(occurrence_start_time),
+                // The external id identifies the series only
+                external_id: None,
+                // The exception is a new event, it starts at the first version
+                version: 0,
+                created: Utc::now(),
+                updated: Utc::now(),
+                ..e.clone()
+            });
+
+            self.apply_changes(&mut exception, ctx).await?;
+            // The exception stays linked to its occurrence
+            exception.recurring_event_id = Some(e.id.clone());
+            exception.original_start_time = Some(occurrence_start_time);
+
+            if is_new_exception {
+                ctx.repos
+                    .events
+                    .insert_with_outbox(&exception, &outbox_messages(self, &exception, ctx).await)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("[update_event] Failed to insert exception {:?}", e);
+                        UseCaseError::StorageError
+                    })?;
+            } else {
+                // The saved exception has the next version
+                exception.version += 1;
+                // Attendees are stored separately, so only write them when they were provided
+                ctx.repos
+                    .events
+                    .save_with_outbox(
+                        &exception,
+                        None,
+                        self.attendees.is_some(),
+                        &outbox_messages(self, &exception, ctx).await,
+                    )
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("[update_event] Failed to save exception {:?}", e);
+                        UseCaseError::StorageError
+                    })?;
+            }
+
+            return Ok(exception);
+        }
+
+        // This and following: split the series and update the new one
+        let calendar_settings = find_calendar_settings(&e, ctx).await?;
+        let mut new_series = e
+            .split_at(occurrence_start_time, &calendar_settings)
+            .map_err(|err| {
+                tracing::warn!("[update_event] Failed to split the series {:?}", err);
+                UseCaseError::InvalidRecurrenceRule
+            })?;
+        e.updated = Utc::now();
+
+        self.apply_changes(&mut new_series, ctx).await?;
+
+        // The exceptions after the split now belong to the new series
+        let moved_exceptions = exceptions
+            .into_iter()
+            .filter(|exception| {
+                exception
+                    .original_start_time
+                    .is_some_and(|original_start_time| original_start_time >= occurrence_start_time)
+            })
+            .map(|exception| CalendarEvent {
+                recurring_event_id: Some(new_series.id.clone()),
+                updated: Utc::now(),
+                ..exception
+            })
+            .collect::<Vec<_>>();
+
+        // Both the truncated series and the new series need their reminders and synced events updated
+        // The saved series has the next version
+        e.version += 1;
+        let mut outbox = outbox_messages(self, &e, ctx).await;
+        outbox.extend(outbox_messages(self, &new_series, ctx).await);
+        let saved = ctx
+            .repos
+            .events
+            .split_series_with_outbox(
+                &e,
+                self.expected_version,
+                &new_series,
+                &moved_exceptions,
+                &outbox,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("[update_event] Failed to split the series {:?}", e);
+                UseCaseError::StorageError
+            })?;
+        if !saved {
+            return Err(UseCaseError::VersionMismatch);
+        }
+
+        Ok(new_series)
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(SyncRemindersOnEventUpdated));
+            subscribers.push(Box::new(UpdateSyncedEventsOnEventUpdated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
+
+impl UpdateEventUseCase {
+    /// Save the (series) event, only if it still has the version expected by the client (if any)
+    /// The version is checked again when saving, in case of a concurrent update
+    async fn save_event(
+        &self,
+        e: &mut CalendarEvent,
+        ctx: &NitteiContext,
+    ) -> Result<(), UseCaseError> {
+        // The saved event has the next version
+        e.version += 1;
+        let saved = ctx
+            .repos
+            .events
+            // Attendees are stored separately, so only write them when they were provided
+            .save_with_outbox(
+                e,
+                self.expected_version,
+                self.attendees.is_some(),
+                &outbox_messages(self, e, ctx).await,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("[update_event] Failed to save event {:?}", e);
+                UseCaseError::StorageError
+            })?;
+        if !saved {
+            return Err(UseCaseError::VersionMismatch);
+        }
+        Ok(())
+    }
+
+    /// Apply the requested changes to the given event
+    /// Depending on the scope, this is the event itself, an exception or a new series
+    async fn apply_changes(
+        &mut self,
+        event: &mut CalendarEvent,
+        ctx: &NitteiContext,
+    ) -> Result<(), UseCaseError> {
         let UpdateEventUseCase {
             user,
-            event_id,
             title,
             description,
             event_type,
@@ -220,60 +532,68 @@ impl UseCase for UpdateEventUseCase {
             busy,
             duration,
             recurrence,
+            timezone,
             exdates,
             recurring_event_id,
             original_start_time,
             reminders,
+            attendees,
             service_id,
+            group_id,
             metadata,
             created,
             updated,
-            prefetched_calendar_event,
+            ..
         } = self;
 
-        let e = match &prefetched_calendar_event {
-            Some(event) => Some(event.clone()),
-            None => ctx.repos.events.find(event_id).await.map_err(|e| {
-                tracing::error!("[update_event] Error finding event: {:?}", e);
-                UseCaseError::StorageError
-            })?,
-        };
-
-        let mut e = match e {
-            Some(event) if event.user_id == user.id => event,
-            Some(_) => {
-                return Err(UseCaseError::NotFound(
-                    "Calendar Event".into(),
-                    event_id.clone(),
-                ));
-            }
-            None => {
-                return Err(UseCaseError::NotFound(
-                    "Calendar Event".into(),
-                    event_id.clone(),
-                ));
-            }
-        };
-
         if let Some(service_id_value) = service_id {
             if service_id_value.is_some() {
-                e.service_id = service_id_value.take();
+                event.service_id = service_id_value.take();
             } else {
                 // Set to NULL
-                e.service_id = None;
+                event.service_id = None;
+            }
+        }
+
+        if let Some(group_id_value) = group_id {
+            if let Some(group_id_value) = group_id_value {
+                // The group needs to belong to the same user and calendar
+                let group = ctx
+                    .repos
+                    .event_groups
+                    .find(group_id_value)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("[update_event] Error finding event group: {:?}", e);
+                        UseCaseError::StorageError
+                    })?;
+                match group {
+                    Some(group)
+                        if group.user_id == user.id && group.calendar_id == event.calendar_id => {}
+                    _ => {
+                        return Err(UseCaseError::NotFound(
+                            "Event Group".into(),
+                            group_id_value.clone(),
+                        ));
+                    }
+                }
+                event.group_id = Some(group_id_value.clone());
+            } else {
+                // Set to NULL
+                event.group_id = None;
             }
         }
 
         if let Some(exdates_value) = exdates {
-            e.exdates = exdates_value.clone();
+            event.exdates = exdates_value.clone();
         }
 
         if let Some(metadata_value) = metadata {
             if metadata_value.is_some() {
-                e.metadata = metadata_value.take();
+                event.metadata = metadata_value.take();
             } else {
                 // Set to NULL
-                e.metadata = None;
+                event.metadata = None;
             }
         }
 
@@ -284,60 +604,80 @@ impl UseCase for UpdateEventUseCase {
                     return Err(UseCaseError::InvalidReminder);
                 }
             }
-            e.reminders.clone_from(reminders);
+            event.reminders.clone_from(reminders);
+        }
+
+        if let Some(attendees_value) = attendees {
+            let valid = event.set_attendees(attendees_value.clone())
+                && attendees_belong_to_account(ctx, &event.account_id, &event.attendees)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("[update_event] Error finding attendee users: {:?}", e);
+                        UseCaseError::StorageError
+                    })?;
+            if !valid {
+                tracing::warn!("[update_event] Invalid attendees");
+                return Err(UseCaseError::InvalidAttendees);
+            }
         }
 
         let mut start_or_duration_change = false;
 
         if let Some(start_time_value) = start_time {
             // Only change the exdates if the start time has actually changed
-            if e.start_time != *start_time_value {
-                e.exdates = Vec::new();
+            if event.start_time != *start_time_value {
+                event.exdates = Vec::new();
             }
-            e.start_time = *start_time_value;
+            event.start_time = *start_time_value;
             start_or_duration_change = true;
         }
         if let Some(duration_value) = duration {
-            e.duration = *duration_value;
+            event.duration = *duration_value;
             start_or_duration_change = true;
         }
 
         if start_or_duration_change {
-            e.end_time = e.start_time + TimeDelta::milliseconds(e.duration);
+            event.end_time = event.start_time + TimeDelta::milliseconds(event.duration);
         }
 
         if let Some(busy_value) = busy {
-            e.busy = *busy_value;
+            event.busy = *busy_value;
+        }
+
+        // The timezone in which the recurrence is expanded (None = the timezone of the calendar)
+        if let Some(timezone_value) = timezone {
+            event.timezone = *timezone_value;
         }
 
         // Handle the new recurrence
         let valid_recurrence = if let Some(recurrence_value) = &recurrence {
             if let Some(rrule_opts) = recurrence_value {
                 // ? should exdates be deleted when rrules are updated
-                e.set_recurrence(rrule_opts.clone()).map_err(|e| {
+                event.set_recurrence(rrule_opts.clone()).map_err(|e| {
                     tracing::error!("[update_event] Failed to set recurrence {:?}", e);
                     UseCaseError::InvalidRecurrenceRule
                 })?
             } else {
                 // Set to NULL
-                e.recurrence = None;
-                e.recurring_until = None;
+                event.recurrence = None;
+                event.recurring_until = None;
                 true
             }
         // Otherwise, we we don't have a new recurrence, but we have an existing one
         // And the start time or duration has changed, we need to update the recurrence
         } else if start_or_duration_change {
-            if e.recurrence.is_some() {
+            if event.recurrence.is_some() {
                 // This unwrap is safe as we have checked that recurrence "is_some"
                 #[allow(clippy::unwrap_used)]
-                e.set_recurrence(e.recurrence.clone().unwrap())
+                event
+                    .set_recurrence(event.recurrence.clone().unwrap())
                     .map_err(|e| {
                         tracing::error!("[update_event] Failed to set recurrence {:?}", e);
                         UseCaseError::InvalidRecurrenceRule
                     })?
             } else {
-                e.recurrence = None;
-                e.recurring_until = None;
+                event.recurrence = None;
+                event.recurring_until = None;
                 true
             }
         } else {
@@ -351,116 +691,140 @@ impl UseCase for UpdateEventUseCase {
         if let Some(recurring_event_id_value) = recurring_event_id {
             if recurring_event_id_value.is_some() {
                 // Check if the recurring event exists
-                e.recurring_event_id = recurring_event_id_value.take();
+                event.recurring_event_id = recurring_event_id_value.take();
             } else {
                 // Set to NULL
-                e.recurring_event_id = None;
+                event.recurring_event_id = None;
             }
         }
 
         if let Some(original_start_time_value) = original_start_time {
             if original_start_time_value.is_some() {
-                e.original_start_time = original_start_time_value.take();
+                event.original_start_time = original_start_time_value.take();
             } else {
                 // Set to NULL
-                e.original_start_time = None;
+                event.original_start_time = None;
             }
         }
 
         if let Some(title_value) = title {
             if title_value.is_some() {
-                e.title = title_value.take();
+                event.title = title_value.take();
             } else {
                 // Set to NULL
-                e.title = None;
+                event.title = None;
             }
         }
 
         if let Some(description_value) = description {
             if description_value.is_some() {
-                e.description = description_value.take();
+                event.description = description_value.take();
             } else {
                 // Set to NULL
-                e.description = None;
+                event.description = None;
             }
         }
 
         if let Some(event_type_value) = event_type {
             if event_type_value.is_some() {
-                e.event_type = event_type_value.take();
+                event.event_type = event_type_value.take();
             } else {
                 // Set to NULL
-                e.event_type = None;
+                event.event_type = None;
             }
         }
 
         if let Some(external_parent_id_value) = external_parent_id {
             if external_parent_id_value.is_some() {
-                e.external_parent_id = external_parent_id_value.take();
+                event.external_parent_id = external_parent_id_value.take();
             } else {
                 // Set to NULL
-                e.external_parent_id = None;
+                event.external_parent_id = None;
             }
         }
 
         if let Some(external_id_value) = external_id {
             if external_id_value.is_some() {
-                e.external_id = external_id_value.take();
+                event.external_id = external_id_value.take();
             } else {
                 // Set to NULL
-                e.external_id = None;
+                event.external_id = None;
             }
         }
 
         if let Some(location_value) = location {
             if location_value.is_some() {
-                e.location = location_value.take();
+                event.location = location_value.take();
             } else {
                 // Set to NULL
-                e.location = None;
+                event.location = None;
             }
         }
 
         if let Some(status_value) = status {
-            e.status = status_value.clone();
+            event.status = status_value.clone();
         }
 
         if let Some(all_day_value) = all_day {
-            e.all_day = *all_day_value;
+            event.all_day = *all_day_value;
         }
 
         if let Some(created_value) = created {
-            e.created = *created_value;
+            event.created = *created_value;
         }
 
         if let Some(updated_value) = updated {
-            e.updated = *updated_value;
+            event.updated = *updated_value;
         } else {
             // Set to current time
-            e.updated = Utc::now();
+            event.updated = Utc::now();
         }
 
-        ctx.repos
-            .events
-            .save(&e)
-            .await
-            .map(|_| e.clone())
-            .map_err(|e| {
-                tracing::error!("[update_event] Failed to save event {:?}", e);
-                UseCaseError::StorageError
-            })
+        Ok(())
     }
+}
 
-    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
-        if APP_CONFIG.disable_reminders {
-            vec![]
-        } else {
-            vec![
-                Box::new(SyncRemindersOnEventUpdated),
-                Box::new(UpdateSyncedEventsOnEventUpdated),
-            ]
-        }
+/// Find the settings of the calendar of the event, needed to expand its recurrence
+async fn find_calendar_settings(
+    e: &CalendarEvent,
+    ctx: &NitteiContext,
+) -> Result<CalendarSettings, UseCaseError> {
+    ctx.repos
+        .calendars
+        .find(&e.calendar_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("[update_event] Error finding calendar: {:?}", e);
+            UseCaseError::StorageError
+        })?
+        .map(|calendar| calendar.settings)
+        .ok_or_else(|| UseCaseError::NotFound("Calendar".into(), e.calendar_id.clone()))
+}
+
+/// Check that the occurrence targeted by the scope is part of the recurring event
+async fn validate_occurrence(
+    e: &CalendarEvent,
+    occurrence_start_time: DateTime<Utc>,
+    ctx: &NitteiContext,
+) -> Result<(), UseCaseError> {
+    if e.recurrence.is_none() {
+        return Err(UseCaseError::InvalidScope(
+            "The event is not a recurring event".into(),
+        ));
+    }
+    let calendar_settings = find_calendar_settings(e, ctx).await?;
+    let is_occurrence = e
+        .is_occurrence(occurrence_start_time, &calendar_settings)
+        .map_err(|err| {
+            tracing::error!("[update_event] Failed to expand the recurrence {:?}", err);
+            UseCaseError::InvalidRecurrenceRule
+        })?;
+    if !is_occurrence {
+        return Err(UseCaseError::InvalidScope(
+            "The start time doesn't match any occurrence of the event".into(),
+        ));
     }
+    Ok(())
 }
 
 impl PermissionBoundary for UpdateEventUseCase {
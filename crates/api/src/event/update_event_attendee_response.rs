@@ -0,0 +1,165 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::update_event_attendee_response::*;
+use nittei_domain::{CalendarEvent, CalendarEventAttendeeResponseStatus, ID, User};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::{
+        auth::Policy,
+        usecase::{UseCase, execute},
+    },
+};
+
+#[utoipa::path(
+    put,
+    tag = "Event",
+    path = "/api/v1/events/{event_id}/attendees/me",
+    summary = "Respond to an event as an attendee (user only)",
+    params(
+        ("event_id" = ID, Path, description = "The id of the event to respond to"),
+    ),
+    request_body(
+        content = UpdateEventAttendeeResponseRequestBody,
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn update_event_attendee_response_controller(
+    Extension((user, _policy)): Extension<(User, Policy)>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    Json(body): Json<UpdateEventAttendeeResponseRequestBody>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = UpdateEventAttendeeResponseUseCase {
+        event_id: path_params.event_id.clone(),
+        user_id: user.id.clone(),
+        response_status: body.response_status,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|event| Json(APIResponse::new(event)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for updating the response of an attendee to an event (RSVP)
+/// Only the attendee itself can update its response
+#[derive(Debug)]
+pub struct UpdateEventAttendeeResponseUseCase {
+    pub event_id: ID,
+    pub user_id: ID,
+    pub response_status: CalendarEventAttendeeResponseStatus,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    InternalError,
+    NotFound(ID),
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::InternalError => Self::InternalError,
+            UseCaseError::NotFound(event_id) => Self::NotFound(format!(
+                "The calendar event with id: {event_id}, was not found."
+            )),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for UpdateEventAttendeeResponseUseCase {
+    type Response = CalendarEvent;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "UpdateEventAttendeeResponse";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let updated = ctx
+            .repos
+            .events
+            .update_attendee_response_status(
+                &self.event_id,
+                &self.user_id,
+                self.response_status.clone(),
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "[update_event_attendee_response] Error updating response: {:?}",
+                    e
+                );
+                UseCaseError::InternalError
+            })?;
+
+        // The user is not an attendee of the event (or the event doesn't exist)
+        if !updated {
+            return Err(UseCaseError::NotFound(self.event_id.clone()));
+        }
+
+        ctx.repos
+            .events
+            .find(&self.event_id)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "[update_event_attendee_response] Error finding event: {:?}",
+                    e
+                );
+                UseCaseError::InternalError
+            })?
+            .ok_or_else(|| UseCaseError::NotFound(self.event_id.clone()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nittei_domain::{Account, Calendar, CalendarEventAttendee};
+    use nittei_infra::setup_context;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn attendee_can_respond_to_event() {
+        let ctx = setup_context().await.unwrap();
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let owner = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&owner).await.unwrap();
+        let attendee = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&attendee).await.unwrap();
+        let calendar = Calendar::new(&owner.id, &account.id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+        let event = CalendarEvent {
+            calendar_id: calendar.id.clone(),
+            user_id: owner.id.clone(),
+            account_id: account.id.clone(),
+            attendees: vec![CalendarEventAttendee {
+                user_id: Some(attendee.id.clone()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        ctx.repos.events.insert(&event).await.unwrap();
+
+        let mut usecase = UpdateEventAttendeeResponseUseCase {
+            event_id: event.id.clone(),
+            user_id: attendee.id.clone(),
+            response_status: CalendarEventAttendeeResponseStatus::Declined,
+        };
+        let res = usecase.execute(&ctx).await.unwrap();
+        assert!(res.is_declined_by(&attendee.id));
+
+        // The owner is not an attendee
+        let mut usecase = UpdateEventAttendeeResponseUseCase {
+            event_id: event.id.clone(),
+            user_id: owner.id.clone(),
+            response_status: CalendarEventAttendeeResponseStatus::Accepted,
+        };
+        assert!(usecase.execute(&ctx).await.is_err());
+    }
+}
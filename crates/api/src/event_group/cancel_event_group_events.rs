@@ -0,0 +1,135 @@
+use axum::{Extension, Json, extract::Path};
+use chrono::Utc;
+use nittei_api_structs::cancel_event_group_events::*;
+use nittei_domain::{Account, CalendarEvent, CalendarEventStatus, ID};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+
+use crate::{
+    error::NitteiError,
+    event::subscribers::{
+        RefreshInstancesOnEventChanged,
+        SendEventWebhook,
+        SyncRemindersOnEventUpdated,
+    },
+    shared::usecase::{Subscriber, UseCase, execute, outbox_messages},
+};
+
+#[utoipa::path(
+    post,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/{group_id}/cancel",
+    summary = "Cancel all the events of an event group (admin only)",
+    params(
+        ("group_id" = ID, Path, description = "The id of the event group"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn cancel_event_group_events_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = CancelEventGroupEventsUseCase {
+        account_id: account.id,
+        group_id: path_params.group_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|events| Json(APIResponse::new(events)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for cancelling all the events attached to an event group
+/// Either all the events are cancelled, or none of them
+#[derive(Debug)]
+pub struct CancelEventGroupEventsUseCase {
+    pub account_id: ID,
+    pub group_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for CancelEventGroupEventsUseCase {
+    type Response = Vec<CalendarEvent>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "CancelEventGroupEvents";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let group = ctx
+            .repos
+            .event_groups
+            .find(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        match group {
+            Some(group) if group.account_id == self.account_id => {}
+            _ => return Err(UseCaseError::NotFound(self.group_id.clone())),
+        };
+
+        let mut events = ctx
+            .repos
+            .events
+            .find_by_group(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        let now = Utc::now();
+        for event in events.iter_mut() {
+            event.status = CalendarEventStatus::Cancelled;
+            event.updated = now;
+            // The saved events have the next version
+            event.version += 1;
+        }
+
+        let outbox = outbox_messages(self, &events, ctx).await;
+        ctx.repos
+            .events
+            .save_many_with_outbox(&events, &outbox)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "[cancel_event_group_events] Error saving cancelled events: {:?}",
+                    e
+                );
+                UseCaseError::StorageError
+            })?;
+
+        Ok(events)
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(SyncRemindersOnEventUpdated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
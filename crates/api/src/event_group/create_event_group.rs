@@ -0,0 +1,195 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use axum_valid::Valid;
+use nittei_api_structs::create_event_group::*;
+use nittei_domain::{Account, ID, User, event_group::EventGroup};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::{
+        auth::account_can_modify_user,
+        usecase::{UseCase, execute},
+    },
+};
+
+#[utoipa::path(
+    post,
+    tag = "EventGroup",
+    path = "/api/v1/user/{user_id}/event_groups",
+    summary = "Create an event group (admin only)",
+    params(
+        ("user_id" = ID, Path, description = "The id of the user to create the event group for"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    request_body(
+        content = CreateEventGroupRequestBody,
+    ),
+    responses(
+        (status = 201, body = APIResponse)
+    )
+)]
+pub async fn create_event_group_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Valid<Json<CreateEventGroupRequestBody>>,
+) -> Result<(StatusCode, Json<APIResponse>), NitteiError> {
+    let user = account_can_modify_user(&account, &path_params.user_id, &ctx).await?;
+
+    let mut body = body.0;
+    let usecase = CreateEventGroupUseCase {
+        user,
+        calendar_id: body.calendar_id.clone(),
+        parent_id: body.parent_id.take(),
+        external_id: body.external_id.take(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|group| (StatusCode::CREATED, Json(APIResponse::new(group))))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for creating an event group
+#[derive(Debug, Default)]
+pub struct CreateEventGroupUseCase {
+    pub user: User,
+    pub calendar_id: ID,
+    pub parent_id: Option<String>,
+    pub external_id: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UseCaseError {
+    CalendarNotFound(ID),
+    ExternalIdAlreadyUsed(String),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::CalendarNotFound(calendar_id) => Self::NotFound(format!(
+                "The calendar with id: {calendar_id}, was not found."
+            )),
+            UseCaseError::ExternalIdAlreadyUsed(external_id) => Self::Conflict(format!(
+                "An event group with the external id: {external_id}, already exists."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for CreateEventGroupUseCase {
+    type Response = EventGroup;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "CreateEventGroup";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let calendar = ctx
+            .repos
+            .calendars
+            .find(&self.calendar_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        let calendar = match calendar {
+            Some(calendar) if calendar.user_id == self.user.id => calendar,
+            _ => return Err(UseCaseError::CalendarNotFound(self.calendar_id.clone())),
+        };
+
+        // The external id is unique per account
+        if let Some(external_id) = &self.external_id {
+            let existing_group = ctx
+                .repos
+                .event_groups
+                .get_by_external_id(&self.user.account_id, external_id)
+                .await
+                .map_err(|_| UseCaseError::StorageError)?;
+            if existing_group.is_some() {
+                return Err(UseCaseError::ExternalIdAlreadyUsed(external_id.clone()));
+            }
+        }
+
+        let group = EventGroup {
+            id: Default::default(),
+            calendar_id: calendar.id,
+            user_id: self.user.id.clone(),
+            account_id: self.user.account_id.clone(),
+            parent_id: self.parent_id.take(),
+            external_id: self.external_id.take(),
+        };
+
+        ctx.repos.event_groups.insert(&group).await.map_err(|e| {
+            tracing::error!("[create_event_group] Error inserting event group: {:?}", e);
+            UseCaseError::StorageError
+        })?;
+
+        Ok(group)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nittei_domain::{Account, Calendar, User};
+    use nittei_infra::setup_context;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn rejects_calendar_of_another_user() {
+        let ctx = setup_context().await.unwrap();
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let other_user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&other_user).await.unwrap();
+        let calendar = Calendar::new(&other_user.id, &account.id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        let mut usecase = CreateEventGroupUseCase {
+            user,
+            calendar_id: calendar.id.clone(),
+            ..Default::default()
+        };
+
+        let res = usecase.execute(&ctx).await;
+        assert_eq!(res, Err(UseCaseError::CalendarNotFound(calendar.id)));
+    }
+
+    #[tokio::test]
+    async fn rejects_duplicated_external_id() {
+        let ctx = setup_context().await.unwrap();
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let calendar = Calendar::new(&user.id, &account.id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        let mut usecase = CreateEventGroupUseCase {
+            user: user.clone(),
+            calendar_id: calendar.id.clone(),
+            external_id: Some("course".into()),
+            ..Default::default()
+        };
+        assert!(usecase.execute(&ctx).await.is_ok());
+
+        let mut usecase = CreateEventGroupUseCase {
+            user,
+            calendar_id: calendar.id.clone(),
+            external_id: Some("course".into()),
+            ..Default::default()
+        };
+        let res = usecase.execute(&ctx).await;
+        assert_eq!(
+            res,
+            Err(UseCaseError::ExternalIdAlreadyUsed("course".into()))
+        );
+    }
+}
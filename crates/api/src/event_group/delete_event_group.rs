@@ -0,0 +1,98 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::delete_event_group::*;
+use nittei_domain::{Account, ID, event_group::EventGroup};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    delete,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/{group_id}",
+    summary = "Delete an event group (admin only). The events of the group are kept, but detached from the group.",
+    params(
+        ("group_id" = ID, Path, description = "The id of the event group to delete"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn delete_event_group_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = DeleteEventGroupUseCase {
+        account_id: account.id,
+        group_id: path_params.group_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|group| Json(APIResponse::new(group)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for deleting an event group
+/// The events of the group are not deleted, see `DeleteEventGroupEventsUseCase` for that
+#[derive(Debug)]
+pub struct DeleteEventGroupUseCase {
+    pub account_id: ID,
+    pub group_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for DeleteEventGroupUseCase {
+    type Response = EventGroup;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "DeleteEventGroup";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let group = ctx
+            .repos
+            .event_groups
+            .find(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        let group = match group {
+            Some(group) if group.account_id == self.account_id => group,
+            _ => return Err(UseCaseError::NotFound(self.group_id.clone())),
+        };
+
+        ctx.repos
+            .event_groups
+            .delete(&group.id)
+            .await
+            .map_err(|e| {
+                tracing::error!("[delete_event_group] Error deleting event group: {:?}", e);
+                UseCaseError::StorageError
+            })?;
+
+        Ok(group)
+    }
+}
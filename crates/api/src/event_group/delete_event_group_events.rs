@@ -0,0 +1,124 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::delete_event_group_events::*;
+use nittei_domain::{Account, CalendarEvent, ID};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+
+use crate::{
+    error::NitteiError,
+    event::{
+        delete_event::delete_synced_events,
+        subscribers::{RefreshInstancesOnEventChanged, SendEventWebhook},
+    },
+    shared::usecase::{Subscriber, UseCase, execute, outbox_messages},
+};
+
+#[utoipa::path(
+    delete,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/{group_id}/events",
+    summary = "Delete all the events of an event group (admin only). The group itself is kept.",
+    params(
+        ("group_id" = ID, Path, description = "The id of the event group"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn delete_event_group_events_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = DeleteEventGroupEventsUseCase {
+        account_id: account.id,
+        group_id: path_params.group_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|events| Json(APIResponse::new(events)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for deleting all the events attached to an event group
+#[derive(Debug)]
+pub struct DeleteEventGroupEventsUseCase {
+    pub account_id: ID,
+    pub group_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for DeleteEventGroupEventsUseCase {
+    type Response = Vec<CalendarEvent>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "DeleteEventGroupEvents";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let group = ctx
+            .repos
+            .event_groups
+            .find(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        match group {
+            Some(group) if group.account_id == self.account_id => {}
+            _ => return Err(UseCaseError::NotFound(self.group_id.clone())),
+        };
+
+        let events = ctx
+            .repos
+            .events
+            .find_by_group(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        if !APP_CONFIG.disable_reminders {
+            for event in &events {
+                delete_synced_events(event, ctx).await;
+            }
+        }
+
+        let event_ids = events.iter().map(|e| e.id.clone()).collect::<Vec<_>>();
+        ctx.repos
+            .events
+            .delete_many_with_outbox(&event_ids, &outbox_messages(self, &events, ctx).await)
+            .await
+            .map_err(|e| {
+                tracing::error!("[delete_event_group_events] Error deleting events: {:?}", e);
+                UseCaseError::StorageError
+            })?;
+
+        Ok(events)
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
@@ -0,0 +1,82 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::get_event_group_by_external_id::*;
+use nittei_domain::{Account, ID, event_group::EventGroup};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    get,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/external_id/{external_id}",
+    summary = "Get an event group by its external id (admin only)",
+    params(
+        ("external_id" = String, Path, description = "The external id of the event group to fetch"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn get_event_group_by_external_id_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = GetEventGroupByExternalIdUseCase {
+        account_id: account.id,
+        external_id: path_params.external_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|group| Json(APIResponse::new(group)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for getting an event group by its external id
+#[derive(Debug)]
+pub struct GetEventGroupByExternalIdUseCase {
+    pub account_id: ID,
+    pub external_id: String,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(String),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(external_id) => Self::NotFound(format!(
+                "The event group with external id: {external_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for GetEventGroupByExternalIdUseCase {
+    type Response = EventGroup;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "GetEventGroupByExternalId";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        ctx.repos
+            .event_groups
+            .get_by_external_id(&self.account_id, &self.external_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or_else(|| UseCaseError::NotFound(self.external_id.clone()))
+    }
+}
@@ -0,0 +1,92 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::get_event_group_events::*;
+use nittei_domain::{Account, CalendarEvent, ID};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    get,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/{group_id}/events",
+    summary = "Get the events of an event group (admin only)",
+    params(
+        ("group_id" = ID, Path, description = "The id of the event group"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn get_event_group_events_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = GetEventGroupEventsUseCase {
+        account_id: account.id,
+        group_id: path_params.group_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|events| Json(APIResponse::new(events)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for getting the events attached to an event group
+#[derive(Debug)]
+pub struct GetEventGroupEventsUseCase {
+    pub account_id: ID,
+    pub group_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for GetEventGroupEventsUseCase {
+    type Response = Vec<CalendarEvent>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "GetEventGroupEvents";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let group = ctx
+            .repos
+            .event_groups
+            .find(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        match group {
+            Some(group) if group.account_id == self.account_id => {}
+            _ => return Err(UseCaseError::NotFound(self.group_id.clone())),
+        };
+
+        ctx.repos
+            .events
+            .find_by_group(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)
+    }
+}
@@ -0,0 +1,77 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::get_event_groups_by_parent_id::*;
+use nittei_domain::{Account, ID, event_group::EventGroup};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    get,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/parent_id/{parent_id}",
+    summary = "Get the event groups linked to a parent id (admin only)",
+    params(
+        ("parent_id" = String, Path, description = "The parent id of the event groups to fetch"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = GetEventGroupsByParentIdAPIResponse)
+    )
+)]
+pub async fn get_event_groups_by_parent_id_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<GetEventGroupsByParentIdAPIResponse>, NitteiError> {
+    let usecase = GetEventGroupsByParentIdUseCase {
+        account_id: account.id,
+        parent_id: path_params.parent_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|groups| Json(GetEventGroupsByParentIdAPIResponse::new(groups)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for getting the event groups linked to a parent id
+#[derive(Debug)]
+pub struct GetEventGroupsByParentIdUseCase {
+    pub account_id: ID,
+    pub parent_id: String,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for GetEventGroupsByParentIdUseCase {
+    type Response = Vec<EventGroup>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "GetEventGroupsByParentId";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        ctx.repos
+            .event_groups
+            .find_by_parent_id(&self.account_id, &self.parent_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)
+    }
+}
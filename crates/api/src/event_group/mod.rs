@@ -0,0 +1,85 @@
+pub mod cancel_event_group_events;
+pub mod create_event_group;
+pub mod delete_event_group;
+pub mod delete_event_group_events;
+pub mod get_event_group;
+pub mod get_event_group_by_external_id;
+pub mod get_event_group_events;
+pub mod get_event_groups_by_parent_id;
+pub mod shift_event_group_events;
+pub mod update_event_group;
+
+use axum::routing::{delete, get, patch, post};
+use cancel_event_group_events::cancel_event_group_events_admin_controller;
+use create_event_group::create_event_group_admin_controller;
+use delete_event_group::delete_event_group_admin_controller;
+use delete_event_group_events::delete_event_group_events_admin_controller;
+use get_event_group::get_event_group_admin_controller;
+use get_event_group_by_external_id::get_event_group_by_external_id_admin_controller;
+use get_event_group_events::get_event_group_events_admin_controller;
+use get_event_groups_by_parent_id::get_event_groups_by_parent_id_admin_controller;
+use shift_event_group_events::shift_event_group_events_admin_controller;
+use update_event_group::update_event_group_admin_controller;
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::shared::auth;
+
+// Configure the routes for the event group module
+pub fn configure_routes() -> OpenApiRouter {
+    let admin_router = OpenApiRouter::new()
+        // Create an event group for a user (admin route)
+        .route(
+            "/user/{user_id}/event_groups",
+            post(create_event_group_admin_controller),
+        )
+        // Get an event group by external id (admin route)
+        .route(
+            "/user/event_groups/external_id/{external_id}",
+            get(get_event_group_by_external_id_admin_controller),
+        )
+        // Get the event groups sharing a parent id (admin route)
+        .route(
+            "/user/event_groups/parent_id/{parent_id}",
+            get(get_event_groups_by_parent_id_admin_controller),
+        )
+        // Get an event group by uid (admin route)
+        .route(
+            "/user/event_groups/{group_id}",
+            get(get_event_group_admin_controller),
+        )
+        // Update an event group by uid (admin route)
+        .route(
+            "/user/event_groups/{group_id}",
+            patch(update_event_group_admin_controller),
+        )
+        // Delete an event group by uid, its events are detached (admin route)
+        .route(
+            "/user/event_groups/{group_id}",
+            delete(delete_event_group_admin_controller),
+        )
+        // Get the events of an event group (admin route)
+        .route(
+            "/user/event_groups/{group_id}/events",
+            get(get_event_group_events_admin_controller),
+        )
+        // Delete all the events of an event group (admin route)
+        .route(
+            "/user/event_groups/{group_id}/events",
+            delete(delete_event_group_events_admin_controller),
+        )
+        // Cancel all the events of an event group (admin route)
+        .route(
+            "/user/event_groups/{group_id}/cancel",
+            post(cancel_event_group_events_admin_controller),
+        )
+        // Shift all the events of an event group (admin route)
+        .route(
+            "/user/event_groups/{group_id}/shift",
+            post(shift_event_group_events_admin_controller),
+        )
+        .route_layer(axum::middleware::from_fn(
+            auth::protect_admin_route_middleware,
+        ));
+
+    OpenApiRouter::new().merge(admin_router)
+}
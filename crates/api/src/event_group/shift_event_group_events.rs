@@ -0,0 +1,148 @@
+use axum::{Extension, Json, extract::Path};
+use axum_valid::Valid;
+use chrono::{TimeDelta, Utc};
+use nittei_api_structs::shift_event_group_events::*;
+use nittei_domain::{Account, CalendarEvent, ID};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+
+use crate::{
+    error::NitteiError,
+    event::subscribers::{
+        RefreshInstancesOnEventChanged,
+        SendEventWebhook,
+        SyncRemindersOnEventUpdated,
+    },
+    shared::usecase::{Subscriber, UseCase, execute, outbox_messages},
+};
+
+#[utoipa::path(
+    post,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/{group_id}/shift",
+    summary = "Shift all the events of an event group by a number of minutes (admin only)",
+    params(
+        ("group_id" = ID, Path, description = "The id of the event group"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    request_body(
+        content = ShiftEventGroupEventsRequestBody,
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn shift_event_group_events_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Valid<Json<ShiftEventGroupEventsRequestBody>>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = ShiftEventGroupEventsUseCase {
+        account_id: account.id,
+        group_id: path_params.group_id.clone(),
+        minutes: body.minutes,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|events| Json(APIResponse::new(events)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for shifting all the events attached to an event group by a number of minutes
+/// Either all the events are shifted, or none of them
+#[derive(Debug)]
+pub struct ShiftEventGroupEventsUseCase {
+    pub account_id: ID,
+    pub group_id: ID,
+    pub minutes: i64,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    InvalidShift,
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
+            UseCaseError::InvalidShift => {
+                Self::BadClientData("Invalid number of minutes to shift the events by".into())
+            }
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for ShiftEventGroupEventsUseCase {
+    type Response = Vec<CalendarEvent>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "ShiftEventGroupEvents";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let delta = TimeDelta::try_minutes(self.minutes).ok_or(UseCaseError::InvalidShift)?;
+
+        let group = ctx
+            .repos
+            .event_groups
+            .find(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        match group {
+            Some(group) if group.account_id == self.account_id => {}
+            _ => return Err(UseCaseError::NotFound(self.group_id.clone())),
+        };
+
+        let mut events = ctx
+            .repos
+            .events
+            .find_by_group(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        let now = Utc::now();
+        for event in events.iter_mut() {
+            event.shift(delta);
+            event.updated = now;
+            // The saved events have the next version
+            event.version += 1;
+        }
+
+        let outbox = outbox_messages(self, &events, ctx).await;
+        ctx.repos
+            .events
+            .save_many_with_outbox(&events, &outbox)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    "[shift_event_group_events] Error saving shifted events: {:?}",
+                    e
+                );
+                UseCaseError::StorageError
+            })?;
+
+        Ok(events)
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(SyncRemindersOnEventUpdated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
@@ -0,0 +1,127 @@
+use axum::{Extension, Json, extract::Path};
+use axum_valid::Valid;
+use nittei_api_structs::update_event_group::*;
+use nittei_domain::{Account, ID, event_group::EventGroup};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    patch,
+    tag = "EventGroup",
+    path = "/api/v1/user/event_groups/{group_id}",
+    summary = "Update an event group (admin only)",
+    params(
+        ("group_id" = ID, Path, description = "The id of the event group to update"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    request_body(
+        content = UpdateEventGroupRequestBody,
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn update_event_group_admin_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Valid<Json<UpdateEventGroupRequestBody>>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let mut body = body.0;
+    let usecase = UpdateEventGroupUseCase {
+        account_id: account.id,
+        group_id: path_params.group_id.clone(),
+        parent_id: body.parent_id.take(),
+        external_id: body.external_id.take(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|group| Json(APIResponse::new(group)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for updating an event group
+#[derive(Debug, Default)]
+pub struct UpdateEventGroupUseCase {
+    pub account_id: ID,
+    pub group_id: ID,
+    pub parent_id: Option<Option<String>>,
+    pub external_id: Option<Option<String>>,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    ExternalIdAlreadyUsed(String),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(group_id) => Self::NotFound(format!(
+                "The event group with id: {group_id}, was not found."
+            )),
+            UseCaseError::ExternalIdAlreadyUsed(external_id) => Self::Conflict(format!(
+                "An event group with the external id: {external_id}, already exists."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for UpdateEventGroupUseCase {
+    type Response = EventGroup;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "UpdateEventGroup";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let group = ctx
+            .repos
+            .event_groups
+            .find(&self.group_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        let mut group = match group {
+            Some(group) if group.account_id == self.account_id => group,
+            _ => return Err(UseCaseError::NotFound(self.group_id.clone())),
+        };
+
+        if let Some(parent_id) = self.parent_id.take() {
+            group.parent_id = parent_id;
+        }
+
+        if let Some(external_id) = self.external_id.take() {
+            // The external id is unique per account
+            if let Some(external_id) = &external_id {
+                let existing_group = ctx
+                    .repos
+                    .event_groups
+                    .get_by_external_id(&self.account_id, external_id)
+                    .await
+                    .map_err(|_| UseCaseError::StorageError)?;
+                if existing_group.is_some_and(|g| g.id != group.id) {
+                    return Err(UseCaseError::ExternalIdAlreadyUsed(external_id.clone()));
+                }
+            }
+            group.external_id = external_id;
+        }
+
+        ctx.repos.event_groups.save(&group).await.map_err(|e| {
+            tracing::error!("[update_event_group] Error saving event group: {:?}", e);
+            UseCaseError::StorageError
+        })?;
+
+        Ok(group)
+    }
+}
@@ -1,19 +1,86 @@
-use std::time::Duration;
+use std::{sync::LazyLock, time::Duration};
 
+use chrono::TimeDelta;
+use futures::future::join_all;
 use nittei_api_structs::send_event_reminders::AccountRemindersDTO;
-use nittei_infra::NitteiContext;
-use reqwest::Client;
+use nittei_domain::{ID, WebhookDelivery};
+use nittei_infra::{NitteiContext, metrics::update_job_lease_metrics};
+use nittei_utils::config::APP_CONFIG;
 use tokio::time::{Instant, interval, sleep_until};
-use tracing::{debug, error};
+use tracing::{debug, error, info};
 
 use crate::{
     event::{
         get_upcoming_reminders::GetUpcomingRemindersUseCase,
+        refresh_event_instances::{RefreshEventInstancesTrigger, RefreshEventInstancesUseCase},
         sync_event_reminders::{SyncEventRemindersTrigger, SyncEventRemindersUseCase},
     },
+    outbox::process_outbox_messages::ProcessOutboxMessagesUseCase,
     shared::usecase::execute,
+    webhook::process_webhook_deliveries::{ProcessWebhookDeliveriesUseCase, deliver_webhook},
 };
 
+/// Identifier of the instance, used for holding the leases of the periodic jobs
+static INSTANCE_ID: LazyLock<String> = LazyLock::new(|| {
+    APP_CONFIG
+        .instance_id
+        .clone()
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| ID::default().to_string())
+});
+
+/// Lease of a periodic job, so that it runs on a single instance even with several of them
+/// The holder renews it each time the job runs, so it needs to last longer than the interval of the job.
+/// If the holder stops (e.g. crash or scale down), another instance acquires it once it expires
+struct JobLease {
+    job: &'static str,
+    duration: TimeDelta,
+    held: bool,
+}
+
+impl JobLease {
+    fn new(job: &'static str, duration: TimeDelta) -> Self {
+        Self {
+            job,
+            duration,
+            held: false,
+        }
+    }
+
+    /// Acquire (or renew) the lease, returns true if the job should run on this instance
+    async fn acquire(&mut self, ctx: &NitteiContext) -> bool {
+        let now = ctx.sys.get_timestamp();
+        let held = match ctx
+            .repos
+            .job_leases
+            .acquire(self.job, &INSTANCE_ID, now, now + self.duration)
+            .await
+        {
+            Ok(held) => held,
+            Err(e) => {
+                error!(
+                    job = self.job,
+                    "[job_schedulers] Unable to acquire the lease of the job: {:?}", e
+                );
+                false
+            }
+        };
+
+        if held != self.held {
+            info!(
+                job = self.job,
+                instance = %*INSTANCE_ID,
+                "[job_schedulers] {} the lease of the job",
+                if held { "Acquired" } else { "Lost" }
+            );
+        }
+        update_job_lease_metrics(self.job, &INSTANCE_ID, held, held && !self.held);
+        self.held = held;
+
+        held
+    }
+}
+
 /// Get the delay in seconds to the next minute
 pub fn get_start_delay(now_ts: usize, secs_before_min: usize) -> usize {
     let secs_to_next_minute = 60 - (now_ts / 1000) % 60;
@@ -25,11 +92,16 @@ pub fn get_start_delay(now_ts: usize, secs_before_min: usize) -> usize {
 }
 
 /// Start the job scheduler for generating reminders
+/// It only runs on the instance holding its lease
 pub fn start_reminder_generation_job(ctx: NitteiContext) {
     tokio::spawn(async move {
+        let mut lease = JobLease::new("reminder_generation", TimeDelta::minutes(31));
         let mut interval = interval(Duration::from_secs(30 * 60));
         loop {
             interval.tick().await;
+            if !lease.acquire(&ctx).await {
+                continue;
+            }
 
             let usecase = SyncEventRemindersUseCase {
                 request: SyncEventRemindersTrigger::JobScheduler,
@@ -39,9 +111,135 @@ pub fn start_reminder_generation_job(ctx: NitteiContext) {
     });
 }
 
+/// Start the job scheduler for materializing the instances of the recurring events
+/// It only runs on the instance holding its lease
+pub fn start_event_instances_refresh_job(ctx: NitteiContext) {
+    tokio::spawn(async move {
+        let mut lease = JobLease::new("event_instances_refresh", TimeDelta::minutes(61));
+        let mut interval = interval(Duration::from_secs(60 * 60));
+        loop {
+            interval.tick().await;
+            if !lease.acquire(&ctx).await {
+                continue;
+            }
+
+            let usecase = RefreshEventInstancesUseCase {
+                request: RefreshEventInstancesTrigger::JobScheduler,
+            };
+            let _ = execute(usecase, &ctx).await;
+        }
+    });
+}
+
+/// Start the worker processing the messages of the outbox
+/// The messages are claimed with a lease, so the worker runs on all the instances
+pub fn start_outbox_worker(ctx: NitteiContext) {
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_millis(
+            APP_CONFIG.outbox.poll_interval_millis,
+        ));
+        loop {
+            interval.tick().await;
+
+            let _ = execute(ProcessOutboxMessagesUseCase, &ctx).await;
+        }
+    });
+}
+
+/// Start the worker retrying the failed webhook deliveries
+/// It also removes the finished deliveries once they are past the retention (only on the instance holding the lease)
+///
+/// The deliveries are claimed with a lease, so the worker runs on all the instances
+pub fn start_webhook_delivery_worker(ctx: NitteiContext) {
+    let context = ctx.clone();
+    tokio::spawn(async move {
+        let mut interval = interval(Duration::from_millis(
+            APP_CONFIG.webhook.poll_interval_millis,
+        ));
+        loop {
+            interval.tick().await;
+
+            let _ = execute(ProcessWebhookDeliveriesUseCase, &context).await;
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut lease = JobLease::new("webhook_deliveries_cleanup", TimeDelta::minutes(61));
+        let mut hourly_interval = interval(Duration::from_secs(60 * 60));
+        loop {
+            hourly_interval.tick().await;
+            if !lease.acquire(&ctx).await {
+                continue;
+            }
+
+            let before =
+                ctx.sys.get_timestamp() - TimeDelta::days(APP_CONFIG.webhook.retention_days);
+            if let Err(e) = ctx
+                .repos
+                .webhook_deliveries
+                .delete_finished_before(before)
+                .await
+            {
+                error!("Error removing the finished webhook deliveries: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Start the job scheduler for removing the expired idempotency keys
+/// It only runs on the instance holding its lease
+pub fn start_idempotency_keys_cleanup_job(ctx: NitteiContext) {
+    tokio::spawn(async move {
+        let mut lease = JobLease::new("idempotency_keys_cleanup", TimeDelta::minutes(61));
+        let mut hourly_interval = interval(Duration::from_secs(60 * 60));
+        loop {
+            hourly_interval.tick().await;
+            if !lease.acquire(&ctx).await {
+                continue;
+            }
+
+            if let Err(e) = ctx
+                .repos
+                .idempotency_keys
+                .delete_expired(ctx.sys.get_timestamp())
+                .await
+            {
+                error!("Error removing the expired idempotency keys: {:?}", e);
+            }
+        }
+    });
+}
+
+/// Start the job scheduler for removing the expired service holds
+/// The expired holds are already ignored by the queries, this only keeps the table small
+/// It only runs on the instance holding its lease
+pub fn start_service_holds_expiration_job(ctx: NitteiContext) {
+    tokio::spawn(async move {
+        let mut lease = JobLease::new("service_holds_expiration", TimeDelta::seconds(90));
+        let mut minutely_interval = interval(Duration::from_secs(60));
+        loop {
+            minutely_interval.tick().await;
+            if !lease.acquire(&ctx).await {
+                continue;
+            }
+
+            if let Err(e) = ctx
+                .repos
+                .reservations
+                .delete_expired_holds(ctx.sys.get_timestamp())
+                .await
+            {
+                error!("Error removing the expired service holds: {:?}", e);
+            }
+        }
+    });
+}
+
 /// Start the job scheduler for sending reminders
+/// It only runs on the instance holding its lease
 pub fn start_send_reminders_job(ctx: NitteiContext) {
     tokio::spawn(async move {
+        let mut lease = JobLease::new("send_reminders", TimeDelta::seconds(90));
         let now = ctx.sys.get_timestamp_millis();
         let secs_to_next_run = get_start_delay(now as usize, 0);
         let start = Instant::now() + Duration::from_secs(secs_to_next_run as u64);
@@ -50,6 +248,9 @@ pub fn start_send_reminders_job(ctx: NitteiContext) {
         let mut minutely_interval = interval(Duration::from_secs(60));
         loop {
             minutely_interval.tick().await;
+            if !lease.acquire(&ctx).await {
+                continue;
+            }
             let context = ctx.clone();
             tokio::spawn(send_reminders(context));
         }
@@ -57,9 +258,8 @@ pub fn start_send_reminders_job(ctx: NitteiContext) {
 }
 
 /// Send reminders to clients
+/// Each webhook is recorded as a delivery, which is retried if it fails
 async fn send_reminders(context: NitteiContext) {
-    let client = Client::new();
-
     let usecase = GetUpcomingRemindersUseCase {
         reminders_interval: 1000 * 60,
     };
@@ -76,22 +276,38 @@ async fn send_reminders(context: NitteiContext) {
         account_reminders
     );
 
-    for (acc, reminders) in account_reminders.0 {
-        match acc.settings.webhook {
-            None => continue,
-            Some(webhook) => {
-                if let Err(e) = client
-                    .post(webhook.url)
-                    .header("nittei-scheduler-webhook-key", webhook.key)
-                    .json(&AccountRemindersDTO::new(reminders))
-                    .send()
-                    .await
-                {
-                    error!("Error informing client of reminders: {:?}", e);
+    let retry_horizon = TimeDelta::seconds(APP_CONFIG.webhook.retry_horizon_secs);
+    join_all(
+        account_reminders
+            .0
+            .into_iter()
+            .filter_map(|(acc, reminders)| {
+                let webhook = acc.settings.webhook?;
+                match serde_json::to_value(AccountRemindersDTO::new(reminders)) {
+                    Ok(payload) => Some((acc.id, webhook, payload)),
+                    Err(e) => {
+                        error!("Error serializing the reminders: {:?}", e);
+                        None
+                    }
                 }
-            }
-        }
-    }
+            })
+            .map(|(account_id, webhook, payload)| {
+                let context = &context;
+                async move {
+                    let delivery = WebhookDelivery::new(
+                        account_id,
+                        None,
+                        payload,
+                        retry_horizon,
+                        context.sys.get_timestamp(),
+                    );
+                    if let Err(e) = deliver_webhook(delivery, &webhook, context).await {
+                        error!("Error informing client of reminders: {:?}", e);
+                    }
+                }
+            }),
+    )
+    .await;
 }
 
 #[cfg(test)]
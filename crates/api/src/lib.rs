@@ -2,20 +2,31 @@ mod account;
 mod calendar;
 mod error;
 mod event;
+mod event_group;
 mod http_logger;
 mod job_schedulers;
+mod outbox;
 mod schedule;
 mod service;
 mod shared;
 mod status;
 mod user;
+mod webhook;
 
 use std::sync::Arc;
 
 use axum::{Extension, Router, http::header};
 use futures::lock::Mutex;
 use http_logger::metadata_middleware;
-use job_schedulers::{start_reminder_generation_job, start_send_reminders_job};
+use job_schedulers::{
+    start_event_instances_refresh_job,
+    start_idempotency_keys_cleanup_job,
+    start_outbox_worker,
+    start_reminder_generation_job,
+    start_send_reminders_job,
+    start_service_holds_expiration_job,
+    start_webhook_delivery_worker,
+};
 use nittei_domain::{
     Account,
     AccountIntegration,
@@ -52,7 +63,7 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::{
     http_logger::{NitteiTracingOnFailure, NitteiTracingOnResponse, NitteiTracingSpanBuilder},
-    shared::auth::NITTEI_X_API_KEY_HEADER,
+    shared::{auth::NITTEI_X_API_KEY_HEADER, idempotency_middleware},
 };
 
 /// Configure the Actix server API
@@ -62,10 +73,13 @@ pub fn configure_server_api() -> OpenApiRouter {
         .merge(account::configure_routes())
         .merge(calendar::configure_routes())
         .merge(event::configure_routes())
+        .merge(event_group::configure_routes())
+        .merge(outbox::configure_routes())
         .merge(schedule::configure_routes())
         .merge(service::configure_routes())
         .merge(status::configure_routes())
         .merge(user::configure_routes())
+        .merge(webhook::configure_routes())
 }
 
 /// Struct for storing the main application state
@@ -112,8 +126,21 @@ impl Application {
     }
 
     /// Start the background jobs of the application
-    /// Note that the jobs are only started if the environment variable NITTEI_REMINDERS_JOB_ENABLED is set to true
+    /// Note that the reminders jobs are only started if the environment variable NITTEI_REMINDERS_JOB_ENABLED is set to true,
+    /// and the job materializing the event instances if `event_instances.enabled` is set
+    /// The outbox and webhook delivery workers are always started, as they process the work recorded by the use cases,
+    /// as well as the jobs removing the expired idempotency keys and service holds
+    ///
+    /// All the instances start the jobs: the periodic ones only run on the instance holding their lease,
+    /// and the workers claim the work they process
     fn start_jobs(context: NitteiContext) {
+        start_outbox_worker(context.clone());
+        start_webhook_delivery_worker(context.clone());
+        start_idempotency_keys_cleanup_job(context.clone());
+        start_service_holds_expiration_job(context.clone());
+        if nittei_utils::config::APP_CONFIG.event_instances.enabled {
+            start_event_instances_refresh_job(context.clone());
+        }
         if !nittei_utils::config::APP_CONFIG.disable_reminders {
             start_send_reminders_job(context.clone());
             start_reminder_generation_job(context);
@@ -124,6 +151,7 @@ impl Application {
     /// This function creates the server and adds all the routes to it
     ///
     /// This adds the following middleware:
+    /// - Idempotency keys (replaying the responses of the retried requests)
     /// - CORS (permissive)
     /// - Compression
     /// - Tracing logger
@@ -143,6 +171,7 @@ impl Application {
 
         let (router, api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
             .nest("/api/v1", api_router)
+            .layer(axum::middleware::from_fn(idempotency_middleware))
             .layer(axum::middleware::from_fn(metadata_middleware))
             .layer(
                 ServiceBuilder::new()
@@ -274,6 +303,9 @@ impl Application {
                 .map(|url| AccountWebhookSettings {
                     url,
                     key: Default::default(),
+                    event_types: Vec::new(),
+                    previous_key: None,
+                    previous_key_expires_at: None,
                 });
 
             if let Some(mut verification_key) = nittei_utils::config::APP_CONFIG
@@ -448,6 +480,7 @@ impl Application {
         {name = "Account", description = "Account API endpoints"}, 
         {name = "Calendar", description = "Calendar API endpoints"},
         {name = "Event", description = "Event API endpoints"},
+        {name = "EventGroup", description = "Event group API endpoints"},
         {name = "Schedule", description = "Schedule API endpoints"},
         {name = "Service", description = "Service API endpoints"},
         {name = "Status", description = "Status API endpoints"},
@@ -460,7 +493,10 @@ impl Application {
         account::create_account::create_account_controller,
         account::get_account::get_account_controller,
         account::set_account_pub_key::set_account_pub_key_controller,
+        account::set_account_rate_limits::set_account_rate_limits_controller,
+        account::set_account_search_language::set_account_search_language_controller,
         account::set_account_webhook::set_account_webhook_controller,
+        account::rotate_account_webhook_key::rotate_account_webhook_key_controller,
         account::delete_account_webhook::delete_account_webhook_controller,
         account::add_account_integration::add_account_integration_controller,
         account::remove_account_integration::remove_account_integration_controller,
@@ -483,6 +519,14 @@ impl Application {
         calendar::get_outlook_calendars::get_outlook_calendars_admin_controller,
         calendar::remove_sync_calendar::remove_sync_calendar_admin_controller,
         calendar::add_sync_calendar::add_sync_calendar_admin_controller,
+        calendar::import_ical::import_calendar_ical_admin_controller,
+        calendar::create_calendar_feed::create_calendar_feed_admin_controller,
+        calendar::create_calendar_feed::create_calendar_feed_controller,
+        calendar::get_calendar_feeds::get_calendar_feeds_admin_controller,
+        calendar::get_calendar_feeds::get_calendar_feeds_controller,
+        calendar::delete_calendar_feed::delete_calendar_feed_admin_controller,
+        calendar::delete_calendar_feed::delete_calendar_feed_controller,
+        calendar::get_calendar_feed_ical::get_calendar_feed_ical_controller,
 
         // Event
         event::create_event::create_event_controller,
@@ -500,6 +544,26 @@ impl Application {
         event::search_events::search_events_controller,
         event::update_event::update_event_controller,
         event::update_event::update_event_admin_controller,
+        event::update_event_attendee_response::update_event_attendee_response_controller,
+
+        // Event group
+        event_group::create_event_group::create_event_group_admin_controller,
+        event_group::get_event_group::get_event_group_admin_controller,
+        event_group::get_event_group_by_external_id::get_event_group_by_external_id_admin_controller,
+        event_group::get_event_groups_by_parent_id::get_event_groups_by_parent_id_admin_controller,
+        event_group::update_event_group::update_event_group_admin_controller,
+        event_group::delete_event_group::delete_event_group_admin_controller,
+        event_group::get_event_group_events::get_event_group_events_admin_controller,
+        event_group::delete_event_group_events::delete_event_group_events_admin_controller,
+        event_group::cancel_event_group_events::cancel_event_group_events_admin_controller,
+        event_group::shift_event_group_events::shift_event_group_events_admin_controller,
+
+        // Outbox
+        outbox::get_outbox_messages::get_outbox_messages_controller,
+        outbox::replay_outbox_message::replay_outbox_message_controller,
+        // Webhook
+        webhook::get_webhook_deliveries::get_webhook_deliveries_controller,
+        webhook::redeliver_webhook::redeliver_webhook_controller,
 
         // User
         user::create_user::create_user_controller,
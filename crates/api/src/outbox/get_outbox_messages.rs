@@ -0,0 +1,84 @@
+use axum::{Extension, Json, extract::Query};
+use nittei_api_structs::get_outbox_messages::*;
+use nittei_domain::{Account, ID, OutboxMessage, OutboxMessageStatus};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    get,
+    tag = "Account",
+    path = "/api/v1/account/outbox",
+    summary = "Get the outbox messages of the account (admin only)",
+    params(
+        ("status" = Option<OutboxMessageStatus>, Query, description = "Optional status of the messages to get (e.g. deadLetter)"),
+        ("limit" = Option<u16>, Query, description = "Optional max number of messages to get (default 100)"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = GetOutboxMessagesAPIResponse)
+    )
+)]
+pub async fn get_outbox_messages_controller(
+    Extension(account): Extension<Account>,
+    query_params: Query<QueryParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = GetOutboxMessagesUseCase {
+        account_id: account.id,
+        status: query_params.0.status,
+        limit: query_params.0.limit,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|messages| Json(APIResponse::new(messages)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for getting the outbox messages of an account
+#[derive(Debug)]
+pub struct GetOutboxMessagesUseCase {
+    pub account_id: ID,
+    pub status: Option<OutboxMessageStatus>,
+    pub limit: Option<u16>,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for GetOutboxMessagesUseCase {
+    type Response = Vec<OutboxMessage>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "GetOutboxMessages";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        ctx.repos
+            .outbox
+            .find_by_account(
+                &self.account_id,
+                self.status,
+                self.limit.unwrap_or(100).into(),
+            )
+            .await
+            .map_err(|_| UseCaseError::StorageError)
+    }
+}
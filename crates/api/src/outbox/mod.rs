@@ -0,0 +1,26 @@
+pub mod get_outbox_messages;
+pub mod process_outbox_messages;
+pub mod replay_outbox_message;
+
+use axum::{
+    middleware::from_fn,
+    routing::{get, post},
+};
+use get_outbox_messages::get_outbox_messages_controller;
+use replay_outbox_message::replay_outbox_message_controller;
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::shared::auth::protect_admin_route_middleware;
+
+/// Configure the routes for the outbox module
+pub fn configure_routes() -> OpenApiRouter {
+    OpenApiRouter::new()
+        // Get the outbox messages of the account
+        .route("/account/outbox", get(get_outbox_messages_controller))
+        // Replay an outbox message of the account (e.g. a dead letter)
+        .route(
+            "/account/outbox/{message_id}/replay",
+            post(replay_outbox_message_controller),
+        )
+        .route_layer(from_fn(protect_admin_route_middleware))
+}
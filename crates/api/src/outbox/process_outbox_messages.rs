@@ -0,0 +1,150 @@
+use chrono::TimeDelta;
+use futures::future::join_all;
+use nittei_domain::{OutboxMessage, OutboxTask};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+use tracing::{error, warn};
+
+use crate::{
+    event::subscribers::{
+        CreateRemindersOnEventCreated,
+        CreateSyncedEventsOnEventCreated,
+        RefreshInstancesOnEventChanged,
+        SendEventWebhook,
+        SyncRemindersOnEventUpdated,
+        UpdateSyncedEventsOnEventUpdated,
+    },
+    shared::usecase::UseCase,
+};
+
+/// Use case for processing the due messages of the outbox
+/// Processed messages are removed, failed ones are retried later (with an exponential backoff)
+/// until they reach the max number of attempts, after which they are dead letters
+#[derive(Debug)]
+pub struct ProcessOutboxMessagesUseCase;
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    StorageError,
+}
+
+#[async_trait::async_trait]
+impl UseCase for ProcessOutboxMessagesUseCase {
+    /// Number of messages processed
+    type Response = usize;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "ProcessOutboxMessages";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let config = &APP_CONFIG.outbox;
+        let now = ctx.sys.get_timestamp();
+
+        // The messages are leased, so that other instances don't process them at the same time
+        let messages = ctx
+            .repos
+            .outbox
+            .claim_due(
+                now,
+                now + TimeDelta::seconds(config.lease_duration_secs),
+                config.batch_size,
+            )
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        let count = messages.len();
+        join_all(
+            messages
+                .into_iter()
+                .map(|message| process_outbox_message(message, ctx)),
+        )
+        .await;
+
+        Ok(count)
+    }
+}
+
+/// Process a claimed message, and record the outcome in the outbox
+async fn process_outbox_message(message: OutboxMessage, ctx: &NitteiContext) {
+    let config = &APP_CONFIG.outbox;
+
+    let res = match handle_outbox_message(&message, ctx).await {
+        Ok(()) => ctx.repos.outbox.delete(&message.id).await,
+        Err(e) => {
+            let retry_at = if message.attempts < config.max_attempts {
+                Some(
+                    ctx.sys.get_timestamp()
+                        + message.retry_delay(
+                            TimeDelta::seconds(config.retry_base_delay_secs),
+                            TimeDelta::seconds(config.retry_max_delay_secs),
+                        ),
+                )
+            } else {
+                warn!(
+                    message_id = %message.id,
+                    "[process_outbox_messages] Outbox message moved to the dead letters after {} attempts",
+                    message.attempts
+                );
+                None
+            };
+            ctx.repos
+                .outbox
+                .fail(&message.id, &format!("{e:#}"), retry_at)
+                .await
+        }
+    };
+
+    // The message is processed again once its lease expires
+    if let Err(e) = res {
+        error!(
+            message_id = %message.id,
+            "[process_outbox_messages] Unable to record the outcome of the outbox message: {:?}",
+            e
+        );
+    }
+}
+
+/// Do the work of an outbox message
+/// The work on an event is skipped if the event doesn't exist anymore
+pub async fn handle_outbox_message(
+    message: &OutboxMessage,
+    ctx: &NitteiContext,
+) -> anyhow::Result<()> {
+    let event_id = match &message.task {
+        OutboxTask::CreateEventReminders { event_id }
+        | OutboxTask::SyncEventReminders { event_id }
+        | OutboxTask::CreateSyncedEvents { event_id }
+        | OutboxTask::UpdateSyncedEvents { event_id }
+        | OutboxTask::RefreshEventInstances { event_id } => event_id,
+        // The payload is a snapshot of the event, so it can be sent even if the event was deleted
+        OutboxTask::SendWebhook {
+            event_type,
+            payload,
+        } => {
+            return SendEventWebhook::process(message, *event_type, payload, ctx).await;
+        }
+    };
+    let Some(event) = ctx.repos.events.find(event_id).await? else {
+        return Ok(());
+    };
+
+    match &message.task {
+        OutboxTask::CreateEventReminders { .. } => {
+            CreateRemindersOnEventCreated::process(&event, ctx).await
+        }
+        OutboxTask::SyncEventReminders { .. } => {
+            SyncRemindersOnEventUpdated::process(&event, ctx).await
+        }
+        OutboxTask::CreateSyncedEvents { .. } => {
+            CreateSyncedEventsOnEventCreated::process(&event, ctx).await
+        }
+        OutboxTask::UpdateSyncedEvents { .. } => {
+            UpdateSyncedEventsOnEventUpdated::process(&event, ctx).await
+        }
+        OutboxTask::RefreshEventInstances { .. } => {
+            RefreshInstancesOnEventChanged::process(&event, ctx).await
+        }
+        OutboxTask::SendWebhook { .. } => Ok(()),
+    }
+}
@@ -0,0 +1,100 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::replay_outbox_message::*;
+use nittei_domain::{Account, ID, OutboxMessage};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    post,
+    tag = "Account",
+    path = "/api/v1/account/outbox/{message_id}/replay",
+    summary = "Replay an outbox message, e.g. a dead letter (admin only)",
+    params(
+        ("message_id" = ID, Path, description = "The id of the outbox message to replay"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn replay_outbox_message_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = ReplayOutboxMessageUseCase {
+        account_id: account.id,
+        message_id: path_params.message_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|message| Json(APIResponse::new(message)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for replaying an outbox message
+/// The message is pending again, and is processed by the outbox worker as soon as possible
+#[derive(Debug)]
+pub struct ReplayOutboxMessageUseCase {
+    pub account_id: ID,
+    pub message_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(message_id) => Self::NotFound(format!(
+                "The outbox message with id: {message_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for ReplayOutboxMessageUseCase {
+    type Response = OutboxMessage;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "ReplayOutboxMessage";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        match ctx.repos.outbox.find(&self.message_id).await {
+            Ok(Some(message)) if message.account_id == self.account_id => (),
+            Ok(_) => return Err(UseCaseError::NotFound(self.message_id.clone())),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        let replayed = ctx
+            .repos
+            .outbox
+            .replay(&self.message_id, ctx.sys.get_timestamp())
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        if !replayed {
+            // Processed (and removed) in the meantime
+            return Err(UseCaseError::NotFound(self.message_id.clone()));
+        }
+
+        ctx.repos
+            .outbox
+            .find(&self.message_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or_else(|| UseCaseError::NotFound(self.message_id.clone()))
+    }
+}
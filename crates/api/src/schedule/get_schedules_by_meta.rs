@@ -1,6 +1,6 @@
 use axum::{Extension, Json, extract::Query};
 use nittei_api_structs::get_schedules_by_meta::*;
-use nittei_domain::{Account, Metadata};
+use nittei_domain::{Account, Cursor, IDCursor, Metadata, paginate_by_id};
 use nittei_infra::{MetadataFindQuery, NitteiContext};
 
 use crate::error::NitteiError;
@@ -10,11 +10,22 @@ pub async fn get_schedules_by_meta_controller(
     query_params: Query<QueryParams>,
     Extension(ctx): Extension<NitteiContext>,
 ) -> Result<Json<APIResponse>, NitteiError> {
+    let limit = query_params.0.limit.unwrap_or(20);
+    let after = query_params
+        .0
+        .cursor
+        .as_deref()
+        .map(IDCursor::decode)
+        .transpose()
+        .map_err(|e| NitteiError::BadClientData(e.to_string()))?
+        .map(|cursor| cursor.id);
+    // One more is fetched, to know if there is a next page
     let query = MetadataFindQuery {
         account_id: account.id,
         metadata: Metadata::new_kv(query_params.0.key, query_params.0.value),
-        limit: query_params.0.limit.unwrap_or(20),
+        limit: limit.saturating_add(1),
         skip: query_params.0.skip.unwrap_or(0),
+        after,
     };
     let schedules = ctx
         .repos
@@ -22,5 +33,6 @@ pub async fn get_schedules_by_meta_controller(
         .find_by_metadata(query)
         .await
         .map_err(|_| NitteiError::InternalError)?;
-    Ok(Json(APIResponse::new(schedules)))
+    let (schedules, next_cursor) = paginate_by_id(schedules, limit);
+    Ok(Json(APIResponse::new(schedules, next_cursor)))
 }
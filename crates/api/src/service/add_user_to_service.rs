@@ -23,6 +23,10 @@ pub async fn add_user_to_service_controller(
         buffer_after: body.buffer_after,
         closest_booking_time: body.closest_booking_time,
         furthest_booking_time: body.furthest_booking_time,
+        weight: body.weight,
+        priority: body.priority,
+        max_bookings_per_day: body.max_bookings_per_day,
+        max_bookings_per_week: body.max_bookings_per_week,
     };
 
     execute(usecase, &ctx)
@@ -41,6 +45,10 @@ struct AddUserToServiceUseCase {
     pub buffer_after: Option<i64>,
     pub closest_booking_time: Option<i64>,
     pub furthest_booking_time: Option<i64>,
+    pub weight: Option<i64>,
+    pub priority: Option<i64>,
+    pub max_bookings_per_day: Option<i64>,
+    pub max_bookings_per_week: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -106,6 +114,10 @@ impl UseCase for AddUserToServiceUseCase {
                 buffer_before: self.buffer_before,
                 closest_booking_time: self.closest_booking_time,
                 furthest_booking_time: self.furthest_booking_time,
+                weight: self.weight,
+                priority: self.priority,
+                max_bookings_per_day: self.max_bookings_per_day,
+                max_bookings_per_week: self.max_bookings_per_week,
             },
             ctx,
         )
@@ -130,12 +142,19 @@ pub struct ServiceResourceUpdate {
     pub buffer_before: Option<i64>,
     pub closest_booking_time: Option<i64>,
     pub furthest_booking_time: Option<i64>,
+    pub weight: Option<i64>,
+    pub priority: Option<i64>,
+    pub max_bookings_per_day: Option<i64>,
+    pub max_bookings_per_week: Option<i64>,
 }
 
 #[derive(Debug)]
 pub enum UpdateServiceResourceError {
     InternalError,
     InvalidBuffer,
+    InvalidWeight,
+    InvalidPriority,
+    InvalidMaxBookings,
     CalendarNotOwnedByUser(String),
     ScheduleNotOwnedByUser(String),
     InvalidBookingTimespan(String),
@@ -148,6 +167,15 @@ impl UpdateServiceResourceError {
             Self::InvalidBuffer => {
                 NitteiError::BadClientData("The provided buffer was invalid, it should be between 0 and 12 hours specified in minutes.".into())
             }
+            Self::InvalidWeight => {
+                NitteiError::BadClientData("The provided weight was invalid, it should be between 1 and 100.".into())
+            }
+            Self::InvalidPriority => {
+                NitteiError::BadClientData("The provided priority was invalid, it should be at least 1.".into())
+            }
+            Self::InvalidMaxBookings => {
+                NitteiError::BadClientData("The provided maximum number of bookings was invalid, it should be at least 1.".into())
+            }
             Self::CalendarNotOwnedByUser(calendar_id) => NitteiError::NotFound(format!("The calendar: {calendar_id}, was not found among the calendars for the specified user")),
             Self::ScheduleNotOwnedByUser(schedule_id) => {
                 NitteiError::NotFound(format!(
@@ -208,6 +236,17 @@ pub async fn update_resource_values(
         return Err(UpdateServiceResourceError::InvalidBuffer);
     }
 
+    if let Some(weight) = update.weight
+        && !user_resource.set_weight(weight)
+    {
+        return Err(UpdateServiceResourceError::InvalidWeight);
+    }
+    if let Some(priority) = update.priority
+        && !user_resource.set_priority(priority)
+    {
+        return Err(UpdateServiceResourceError::InvalidPriority);
+    }
+
     if let Some(closest_booking_time) = update.closest_booking_time {
         if closest_booking_time < 0 {
             return Err(UpdateServiceResourceError::InvalidBookingTimespan(
@@ -226,5 +265,11 @@ pub async fn update_resource_values(
     }
     user_resource.furthest_booking_time = update.furthest_booking_time;
 
+    if !user_resource.set_max_bookings_per_day(update.max_bookings_per_day)
+        || !user_resource.set_max_bookings_per_week(update.max_bookings_per_week)
+    {
+        return Err(UpdateServiceResourceError::InvalidMaxBookings);
+    }
+
     Ok(())
 }
@@ -0,0 +1,212 @@
+use axum::{Extension, Json, extract::Path};
+use chrono::{DateTime, Utc};
+use nittei_api_structs::cancel_service_booking::*;
+use nittei_domain::{
+    Account,
+    CalendarEvent,
+    CalendarEventStatus,
+    ID,
+    ServiceMultiPersonOptions,
+    ServiceWithUsers,
+};
+use nittei_infra::{NitteiContext, ServiceBookingCancellation};
+use nittei_utils::config::APP_CONFIG;
+
+use crate::{
+    error::NitteiError,
+    event::subscribers::{
+        RefreshInstancesOnEventChanged,
+        SendEventWebhook,
+        SyncRemindersOnEventUpdated,
+        UpdateSyncedEventsOnEventUpdated,
+    },
+    shared::usecase::{Subscriber, UseCase, execute, outbox_messages},
+};
+
+pub async fn cancel_service_booking_controller(
+    Extension(account): Extension<Account>,
+    mut path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = CancelServiceBookingUseCase {
+        account,
+        service_id: std::mem::take(&mut path.service_id),
+        event_id: std::mem::take(&mut path.event_id),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|events| Json(APIResponse::new(events)))
+        .map_err(NitteiError::from)
+}
+
+/// Cancel a booking of a service, identified by one of the events of its hosts
+///
+/// The events of the hosts are cancelled and the reservation of the slot is freed,
+/// unless the cancellation cut-off of the service has passed
+#[derive(Debug)]
+pub struct CancelServiceBookingUseCase {
+    pub account: Account,
+    pub service_id: ID,
+    pub event_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    ServiceNotFound,
+    BookingNotFound(ID),
+    GroupService,
+    CutoffPassed(i64),
+    BookingChanged,
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::ServiceNotFound => {
+                Self::NotFound("The requested service was not found".into())
+            }
+            UseCaseError::BookingNotFound(event_id) => Self::NotFound(format!(
+                "No booking of the service was found with the event with id: {event_id}"
+            )),
+            UseCaseError::GroupService => Self::BadClientData(
+                "The reservations of group services are removed with the booking intend".into(),
+            ),
+            UseCaseError::CutoffPassed(cutoff) => Self::BadClientData(format!(
+                "The booking can't be cancelled less than {cutoff} minutes before its start"
+            )),
+            UseCaseError::BookingChanged => {
+                Self::Conflict("The booking has been modified in the meantime".into())
+            }
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+/// The (non cancelled) events of the hosts of the booking containing the event
+///
+/// A booking of a collective service has one event per host, all at the same time,
+/// the other services have a single host per booking
+pub(super) async fn find_booking_events(
+    ctx: &NitteiContext,
+    service: &ServiceWithUsers,
+    event_id: &ID,
+) -> anyhow::Result<Option<Vec<CalendarEvent>>> {
+    let event = match ctx.repos.events.find(event_id).await? {
+        Some(event)
+            if event.service_id.as_ref() == Some(&service.id)
+                && event.status != CalendarEventStatus::Cancelled =>
+        {
+            event
+        }
+        _ => return Ok(None),
+    };
+    if service.multi_person != ServiceMultiPersonOptions::Collective {
+        return Ok(Some(vec![event]));
+    }
+
+    let host_user_ids = service
+        .users
+        .iter()
+        .map(|resource| resource.user_id.clone())
+        .collect::<Vec<_>>();
+    let events = ctx
+        .repos
+        .events
+        .find_by_service(
+            &service.id,
+            &host_user_ids,
+            event.start_time,
+            event.end_time,
+        )
+        .await?
+        .into_iter()
+        .filter(|e| {
+            e.start_time == event.start_time
+                && e.end_time == event.end_time
+                && e.status != CalendarEventStatus::Cancelled
+        })
+        .collect();
+    Ok(Some(events))
+}
+
+/// The events of a booking, cancelled at `now`, which don't make their hosts busy anymore
+/// They have the version they get when saved
+pub(super) fn cancelled_booking(
+    service: &ServiceWithUsers,
+    mut events: Vec<CalendarEvent>,
+    timestamp: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> ServiceBookingCancellation {
+    for event in events.iter_mut() {
+        event.status = CalendarEventStatus::Cancelled;
+        event.busy = false;
+        event.updated = now;
+        event.version += 1;
+    }
+    ServiceBookingCancellation {
+        service_id: service.id.clone(),
+        timestamp,
+        events,
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for CancelServiceBookingUseCase {
+    type Response = Vec<CalendarEvent>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "CancelServiceBooking";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let service = match ctx.repos.services.find_with_users(&self.service_id).await {
+            Ok(Some(service)) if service.account_id == self.account.id => service,
+            Ok(_) => return Err(UseCaseError::ServiceNotFound),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+        if let ServiceMultiPersonOptions::Group(_) = service.multi_person {
+            return Err(UseCaseError::GroupService);
+        }
+
+        let events = find_booking_events(ctx, &service, &self.event_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or_else(|| UseCaseError::BookingNotFound(self.event_id.clone()))?;
+        let Some(timestamp) = events.first().map(|e| e.start_time) else {
+            return Err(UseCaseError::BookingNotFound(self.event_id.clone()));
+        };
+
+        let now = ctx.sys.get_timestamp();
+        if !service.can_cancel_booking(timestamp, now) {
+            return Err(UseCaseError::CutoffPassed(service.cancellation_cutoff));
+        }
+
+        let cancellation = cancelled_booking(&service, events, timestamp, now);
+        let outbox = outbox_messages(self, &cancellation.events, ctx).await;
+        let cancelled = ctx
+            .repos
+            .events
+            .cancel_service_booking(&cancellation, &outbox)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        if !cancelled {
+            return Err(UseCaseError::BookingChanged);
+        }
+
+        Ok(cancellation.events)
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(SyncRemindersOnEventUpdated));
+            subscribers.push(Box::new(UpdateSyncedEventsOnEventUpdated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
@@ -0,0 +1,33 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use nittei_api_structs::confirm_service_hold::*;
+use nittei_domain::Account;
+use nittei_infra::NitteiContext;
+
+use super::create_service_booking::{BookedSlot, CreateServiceBookingUseCase};
+use crate::{error::NitteiError, shared::usecase::execute};
+
+/// Confirm a hold of a service into a booking, which creates the events of its hosts
+pub async fn confirm_service_hold_controller(
+    Extension(account): Extension<Account>,
+    mut path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Json<RequestBody>,
+) -> Result<(StatusCode, Json<APIResponse>), NitteiError> {
+    let mut body = body.0;
+    let usecase = CreateServiceBookingUseCase {
+        account,
+        service_id: std::mem::take(&mut path.service_id),
+        slot: BookedSlot::Hold(std::mem::take(&mut path.hold_id)),
+        calendar_key: body.calendar_key.take(),
+        title: body.title.take(),
+        description: body.description.take(),
+        event_type: body.event_type.take(),
+        location: body.location.take(),
+        metadata: body.metadata.take(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|events| (StatusCode::CREATED, Json(APIResponse::new(events))))
+        .map_err(NitteiError::from)
+}
@@ -18,6 +18,9 @@ pub async fn create_service_controller(
         account,
         metadata: body.metadata.take(),
         multi_person: body.multi_person.take().unwrap_or_default(),
+        cancellation_cutoff: body.cancellation_cutoff.unwrap_or(0),
+        reschedule_cutoff: body.reschedule_cutoff.unwrap_or(0),
+        distribution_window: body.distribution_window,
     };
 
     execute(usecase, &ctx)
@@ -36,6 +39,9 @@ struct CreateServiceUseCase {
     account: Account,
     multi_person: ServiceMultiPersonOptions,
     metadata: Option<serde_json::Value>,
+    cancellation_cutoff: i64,
+    reschedule_cutoff: i64,
+    distribution_window: Option<i64>,
 }
 #[derive(Debug)]
 struct UseCaseRes {
@@ -44,12 +50,21 @@ struct UseCaseRes {
 
 #[derive(Debug)]
 enum UseCaseError {
+    InvalidCutoff,
+    InvalidDistributionWindow,
     StorageError,
 }
 
 impl From<UseCaseError> for NitteiError {
     fn from(e: UseCaseError) -> Self {
         match e {
+            UseCaseError::InvalidCutoff => Self::BadClientData(
+                "The cancellation and reschedule cut-offs should be positive numbers of minutes"
+                    .into(),
+            ),
+            UseCaseError::InvalidDistributionWindow => Self::BadClientData(
+                "The distribution window should be between 1 and 365 days".into(),
+            ),
             UseCaseError::StorageError => Self::InternalError,
         }
     }
@@ -64,9 +79,20 @@ impl UseCase for CreateServiceUseCase {
     const NAME: &'static str = "CreateService";
 
     async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        if self.cancellation_cutoff < 0 || self.reschedule_cutoff < 0 {
+            return Err(UseCaseError::InvalidCutoff);
+        }
+
         let mut service = Service::new(self.account.id.clone());
         service.metadata = self.metadata.clone();
         service.multi_person = self.multi_person.clone();
+        service.cancellation_cutoff = self.cancellation_cutoff;
+        service.reschedule_cutoff = self.reschedule_cutoff;
+        if let Some(distribution_window) = self.distribution_window
+            && !service.set_distribution_window(distribution_window)
+        {
+            return Err(UseCaseError::InvalidDistributionWindow);
+        }
 
         ctx.repos
             .services
@@ -0,0 +1,345 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use chrono::{DateTime, Duration, TimeDelta, Utc};
+use nittei_api_structs::create_service_booking::*;
+use nittei_domain::{
+    Account,
+    BusyCalendarProvider,
+    CalendarEvent,
+    CalendarEventStatus,
+    ID,
+    ServiceMultiPersonOptions,
+    ServiceResource,
+    format_date,
+};
+use nittei_infra::{NitteiContext, ServiceBooking, ServiceBookingHost, ServiceBookingOutcome};
+use nittei_utils::config::APP_CONFIG;
+
+use super::{
+    create_service_event_intend::{self, select_hosts},
+    get_service_bookingslots::{self, GetServiceBookingSlotsUseCase, find_booking_limits},
+};
+use crate::{
+    error::NitteiError,
+    event::subscribers::{
+        CreateRemindersOnEventCreated,
+        CreateSyncedEventsOnEventCreated,
+        RefreshInstancesOnEventChanged,
+        SendEventWebhook,
+    },
+    shared::usecase::{Subscriber, UseCase, execute, outbox_messages},
+};
+
+pub async fn create_service_booking_controller(
+    Extension(account): Extension<Account>,
+    mut path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Json<RequestBody>,
+) -> Result<(StatusCode, Json<APIResponse>), NitteiError> {
+    let mut body = body.0;
+    let usecase = CreateServiceBookingUseCase {
+        account,
+        service_id: std::mem::take(&mut path.service_id),
+        slot: BookedSlot::Slot {
+            host_user_ids: body.host_user_ids.take(),
+            timestamp: body.timestamp,
+            duration: body.duration,
+            interval: body.interval,
+        },
+        calendar_key: body.calendar_key.take(),
+        title: body.title.take(),
+        description: body.description.take(),
+        event_type: body.event_type.take(),
+        location: body.location.take(),
+        metadata: body.metadata.take(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|events| (StatusCode::CREATED, Json(APIResponse::new(events))))
+        .map_err(NitteiError::from)
+}
+
+/// Book a slot of a service
+///
+/// Unlike the booking intend, the slot is validated and the events of the hosts are created
+/// in the same transaction, so that the same slot can't be booked twice
+#[derive(Debug)]
+pub struct CreateServiceBookingUseCase {
+    pub account: Account,
+    pub service_id: ID,
+    pub slot: BookedSlot,
+    pub calendar_key: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub event_type: Option<String>,
+    pub location: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// The slot booked by a `CreateServiceBookingUseCase`
+#[derive(Debug)]
+pub enum BookedSlot {
+    /// A slot which must be available in the booking slots of the service
+    Slot {
+        host_user_ids: Option<Vec<ID>>,
+        timestamp: DateTime<Utc>,
+        duration: i64,
+        interval: i64,
+    },
+    /// The slot of a (non expired) hold of the service, released by the booking
+    Hold(ID),
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    ServiceNotFound,
+    HoldNotFound,
+    HoldExpired,
+    CalendarNotFound(ID),
+    SlotTaken,
+    StorageError,
+    BookingSlotsQuery(get_service_bookingslots::UseCaseError),
+    HostSelection(create_service_event_intend::UseCaseError),
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::ServiceNotFound => {
+                Self::NotFound("The requested service was not found".into())
+            }
+            UseCaseError::HoldNotFound => Self::NotFound("The requested hold was not found".into()),
+            UseCaseError::HoldExpired => {
+                Self::Conflict("The hold has expired or has been released".into())
+            }
+            UseCaseError::CalendarNotFound(user_id) => Self::BadClientData(format!(
+                "No calendar was found to create the booking of the host with id: {user_id}"
+            )),
+            UseCaseError::SlotTaken => {
+                Self::Conflict("The slot has been booked in the meantime".into())
+            }
+            UseCaseError::StorageError => Self::InternalError,
+            UseCaseError::BookingSlotsQuery(e) => e.into(),
+            UseCaseError::HostSelection(e) => e.into(),
+        }
+    }
+}
+
+/// The calendar of the host where its booking event is created:
+/// the one with the `calendar_key` if provided, the first busy calendar of the host for the service otherwise
+pub(super) async fn find_host_calendar_id(
+    ctx: &NitteiContext,
+    service_id: &ID,
+    user_id: &ID,
+    calendar_key: Option<&str>,
+) -> anyhow::Result<Option<ID>> {
+    if let Some(calendar_key) = calendar_key {
+        return Ok(ctx
+            .repos
+            .calendars
+            .find_by_user_and_key(user_id, calendar_key)
+            .await?
+            .map(|calendar| calendar.id));
+    }
+
+    let busy_calendars = ctx
+        .repos
+        .service_user_busy_calendars
+        .find(service_id, user_id)
+        .await?;
+    Ok(busy_calendars
+        .into_iter()
+        .find_map(|busy_calendar| match busy_calendar {
+            BusyCalendarProvider::Nittei(calendar_id) => Some(calendar_id),
+            _ => None,
+        }))
+}
+
+/// The host of a booking from `start_time` to `end_time`, which is busy during the buffers around it
+/// and limited in the day and week of the booking
+pub(super) async fn booking_host(
+    ctx: &NitteiContext,
+    resource: &ServiceResource,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+) -> anyhow::Result<ServiceBookingHost> {
+    let limits = find_booking_limits(resource, &[], ctx).await?;
+    Ok(ServiceBookingHost {
+        user_id: resource.user_id.clone(),
+        busy_start: start_time - TimeDelta::minutes(resource.buffer_before),
+        busy_end: end_time + TimeDelta::minutes(resource.buffer_after),
+        limits: limits.periods_of(start_time),
+    })
+}
+
+#[async_trait::async_trait]
+impl UseCase for CreateServiceBookingUseCase {
+    type Response = Vec<CalendarEvent>;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "CreateServiceBooking";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        match ctx.repos.services.find(&self.service_id).await {
+            Ok(Some(s)) if s.account_id == self.account.id => (),
+            Ok(_) => return Err(UseCaseError::ServiceNotFound),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        let now = ctx.sys.get_timestamp();
+        let (service, selected_host_user_ids, timestamp, duration, hold_id) = match &self.slot {
+            BookedSlot::Slot {
+                host_user_ids,
+                timestamp,
+                duration,
+                interval,
+            } => {
+                let get_bookingslots_usecase = GetServiceBookingSlotsUseCase {
+                    duration: *duration,
+                    service_id: self.service_id.clone(),
+                    start_date: format_date(timestamp),
+                    end_date: format_date(&(*timestamp + Duration::days(1))),
+                    timezone: Some(chrono_tz::UTC),
+                    interval: *interval,
+                    host_user_ids: host_user_ids.clone(),
+                    ignored_event_ids: Vec::new(),
+                };
+                let res = execute(get_bookingslots_usecase, ctx)
+                    .await
+                    .map_err(UseCaseError::BookingSlotsQuery)?;
+
+                let selected_host_user_ids = select_hosts(
+                    ctx,
+                    &res.service,
+                    res.booking_slots.dates,
+                    host_user_ids.as_deref(),
+                    *timestamp,
+                )
+                .await
+                .map_err(UseCaseError::HostSelection)?;
+                (
+                    res.service,
+                    selected_host_user_ids,
+                    *timestamp,
+                    *duration,
+                    None,
+                )
+            }
+            // The slot has been validated when holding it
+            BookedSlot::Hold(hold_id) => {
+                let hold = match ctx.repos.reservations.find_hold(hold_id).await {
+                    Ok(Some(hold)) if hold.service_id == self.service_id => hold,
+                    Ok(_) => return Err(UseCaseError::HoldNotFound),
+                    Err(_) => return Err(UseCaseError::StorageError),
+                };
+                if hold.is_expired(now) {
+                    return Err(UseCaseError::HoldExpired);
+                }
+                let service = match ctx.repos.services.find_with_users(&self.service_id).await {
+                    Ok(Some(service)) => service,
+                    Ok(None) => return Err(UseCaseError::ServiceNotFound),
+                    Err(_) => return Err(UseCaseError::StorageError),
+                };
+                let duration = (hold.end_time - hold.start_time).num_milliseconds();
+                (
+                    service,
+                    hold.host_user_ids,
+                    hold.start_time,
+                    duration,
+                    Some(hold.id),
+                )
+            }
+        };
+
+        let end_time = timestamp + TimeDelta::milliseconds(duration);
+        let mut hosts = Vec::with_capacity(selected_host_user_ids.len());
+        let mut events = Vec::with_capacity(selected_host_user_ids.len());
+        for user_id in selected_host_user_ids {
+            let Some(resource) = service.users.iter().find(|r| r.user_id == user_id) else {
+                return Err(UseCaseError::HostSelection(
+                    create_service_event_intend::UseCaseError::UserNotAvailable,
+                ));
+            };
+            let calendar_id = find_host_calendar_id(
+                ctx,
+                &self.service_id,
+                &user_id,
+                self.calendar_key.as_deref(),
+            )
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or_else(|| UseCaseError::CalendarNotFound(user_id.clone()))?;
+
+            events.push(CalendarEvent {
+                calendar_id,
+                user_id,
+                account_id: service.account_id.clone(),
+                title: self.title.clone(),
+                description: self.description.clone(),
+                event_type: self.event_type.clone(),
+                location: self.location.clone(),
+                status: CalendarEventStatus::Confirmed,
+                busy: true,
+                start_time: timestamp,
+                duration,
+                end_time,
+                service_id: Some(service.id.clone()),
+                metadata: self.metadata.clone(),
+                created: now,
+                updated: now,
+                ..Default::default()
+            });
+            hosts.push(
+                booking_host(ctx, resource, timestamp, end_time)
+                    .await
+                    .map_err(|_| UseCaseError::StorageError)?,
+            );
+        }
+
+        let booking = ServiceBooking {
+            service_id: service.id.clone(),
+            timestamp,
+            hosts,
+            events,
+            max_reservations: match &service.multi_person {
+                ServiceMultiPersonOptions::Group(max_count) => Some(*max_count),
+                _ => None,
+            },
+            hold_id: hold_id.clone(),
+            now,
+            cancelled_booking: None,
+        };
+        // Only recorded if the events are created by the booking
+        let outbox = outbox_messages(self, &booking.events, ctx).await;
+        let outcome = ctx
+            .repos
+            .events
+            .insert_service_booking(&booking, &outbox)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        match outcome {
+            ServiceBookingOutcome::Booked {
+                events_created: true,
+            } => Ok(booking.events),
+            ServiceBookingOutcome::Booked {
+                events_created: false,
+            } => Ok(Vec::new()),
+            ServiceBookingOutcome::Conflict if hold_id.is_some() => Err(UseCaseError::HoldExpired),
+            ServiceBookingOutcome::Conflict => Err(UseCaseError::SlotTaken),
+        }
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(CreateRemindersOnEventCreated));
+            subscribers.push(Box::new(CreateSyncedEventsOnEventCreated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
@@ -5,12 +5,16 @@ use nittei_api_structs::create_service_event_intend::*;
 use nittei_domain::{
     ID,
     ServiceMultiPersonOptions,
+    ServiceWithUsers,
+    TimeSpan,
     User,
+    booking_slots::ServiceBookingSlotsDate,
     format_date,
     scheduling::{
         RoundRobinAlgorithm,
         RoundRobinAvailabilityAssignment,
         RoundRobinEqualDistributionAssignment,
+        highest_priority_tier,
     },
 };
 use nittei_infra::NitteiContext;
@@ -63,7 +67,7 @@ struct UseCaseRes {
 }
 
 #[derive(Debug)]
-enum UseCaseError {
+pub(crate) enum UseCaseError {
     UserNotAvailable,
     StorageError,
     BookingSlotsQuery(get_service_bookingslots::UseCaseError),
@@ -103,192 +107,215 @@ impl UseCase for CreateServiceEventIntendUseCase {
             timezone: Some(chrono_tz::UTC),
             interval: self.interval,
             host_user_ids: self.host_user_ids.clone(),
+            ignored_event_ids: Vec::new(),
         };
         let res = execute(get_bookingslots_usecase, ctx)
             .await
             .map_err(UseCaseError::BookingSlotsQuery)?;
         let service = res.service;
-        let booking_slots_dates = res.booking_slots.dates;
+
+        let selected_host_user_ids = select_hosts(
+            ctx,
+            &service,
+            res.booking_slots.dates,
+            self.host_user_ids.as_deref(),
+            self.timestamp,
+        )
+        .await?;
 
         let mut create_event_for_hosts = true;
-        let selected_host_user_ids = if let Some(host_user_ids) = &self.host_user_ids {
-            let mut found_slot = false;
-            for date in booking_slots_dates {
-                for slot in date.slots {
-                    if slot.start == self.timestamp {
-                        // Check that all host users are available
-                        for host_user_id in host_user_ids {
-                            if !slot.user_ids.contains(host_user_id) {
-                                return Err(UseCaseError::UserNotAvailable);
-                            }
+        if self.host_user_ids.is_none()
+            && let ServiceMultiPersonOptions::Group(max_count) = &service.multi_person
+        {
+            let reservations = ctx
+                .repos
+                .reservations
+                .count(&service.id, self.timestamp)
+                .await
+                .map_err(|_| UseCaseError::StorageError)?;
+            if reservations + 1 < *max_count {
+                // Client do not need to create service event yet
+                create_event_for_hosts = false;
+            }
+
+            ctx.repos
+                .reservations
+                .increment(&service.id, self.timestamp)
+                .await
+                .map_err(|_| UseCaseError::StorageError)?;
+        }
+
+        let selected_hosts = ctx
+            .repos
+            .users
+            .find_many(&selected_host_user_ids)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        Ok(UseCaseRes {
+            selected_hosts,
+            create_event_for_hosts,
+        })
+    }
+}
+
+/// Find the slot starting at `timestamp` and select the hosts of the service for it,
+/// either the given hosts (if they are all available) or the ones assigned by the `ServiceMultiPersonOptions`
+pub(super) async fn select_hosts(
+    ctx: &NitteiContext,
+    service: &ServiceWithUsers,
+    booking_slots_dates: Vec<ServiceBookingSlotsDate>,
+    host_user_ids: Option<&[ID]>,
+    timestamp: DateTime<Utc>,
+) -> Result<Vec<ID>, UseCaseError> {
+    let selected_host_user_ids = if let Some(host_user_ids) = host_user_ids {
+        let mut found_slot = false;
+        for date in booking_slots_dates {
+            for slot in date.slots {
+                if slot.start == timestamp {
+                    // Check that all host users are available
+                    for host_user_id in host_user_ids {
+                        if !slot.user_ids.contains(host_user_id) {
+                            return Err(UseCaseError::UserNotAvailable);
                         }
-                        found_slot = true;
-                        break;
-                    }
-                    if slot.start > self.timestamp {
-                        break;
                     }
+                    found_slot = true;
+                    break;
                 }
-                if found_slot {
+                if slot.start > timestamp {
                     break;
                 }
             }
-            if !found_slot {
-                return Err(UseCaseError::UserNotAvailable);
+            if found_slot {
+                break;
             }
-            host_user_ids.clone()
-        } else {
-            let mut hosts_at_slot = Vec::new();
-            for date in booking_slots_dates {
-                for slot in date.slots {
-                    if slot.start == self.timestamp {
-                        hosts_at_slot.clone_from(&slot.user_ids);
-                        break;
-                    }
-                    if slot.start > self.timestamp {
-                        return Err(UseCaseError::UserNotAvailable);
-                    }
-                }
-                if !hosts_at_slot.is_empty() {
+        }
+        if !found_slot {
+            return Err(UseCaseError::UserNotAvailable);
+        }
+        host_user_ids.to_vec()
+    } else {
+        let mut hosts_at_slot = Vec::new();
+        for date in booking_slots_dates {
+            for slot in date.slots {
+                if slot.start == timestamp {
+                    hosts_at_slot.clone_from(&slot.user_ids);
                     break;
                 }
+                if slot.start > timestamp {
+                    return Err(UseCaseError::UserNotAvailable);
+                }
             }
-            let hosts_at_slot = service
-                .users
+            if !hosts_at_slot.is_empty() {
+                break;
+            }
+        }
+        let hosts_at_slot = service
+            .users
+            .iter()
+            .filter(|member| hosts_at_slot.contains(&member.user_id))
+            .collect::<Vec<_>>();
+
+        if hosts_at_slot.is_empty() {
+            return Err(UseCaseError::UserNotAvailable);
+        } else {
+            let user_ids_at_slot = hosts_at_slot
                 .iter()
-                .filter(|member| hosts_at_slot.contains(&member.user_id))
+                .map(|h| h.user_id.clone())
                 .collect::<Vec<_>>();
+            // Do round robin to get host
+            match &service.multi_person {
+                ServiceMultiPersonOptions::RoundRobinAlgorithm(round_robin) => {
+                    // The hosts of a lower priority tier are only assigned when no host of a higher tier is available
+                    let hosts_at_slot = highest_priority_tier(&hosts_at_slot);
+                    let user_ids_at_slot = hosts_at_slot
+                        .iter()
+                        .map(|h| h.user_id.clone())
+                        .collect::<Vec<_>>();
+                    match round_robin {
+                        RoundRobinAlgorithm::Availability => {
+                            if hosts_at_slot.len() == 1 {
+                                vec![hosts_at_slot[0].user_id.clone()]
+                            } else {
+                                let events = ctx
+                                    .repos
+                                    .events
+                                    .find_most_recently_created_service_events(
+                                        &service.id,
+                                        &user_ids_at_slot,
+                                    )
+                                    .await
+                                    .map_err(|_| UseCaseError::StorageError)?;
 
-            if hosts_at_slot.is_empty() {
-                return Err(UseCaseError::UserNotAvailable);
-            } else {
-                let user_ids_at_slot = hosts_at_slot
-                    .iter()
-                    .map(|h| h.user_id.clone())
-                    .collect::<Vec<_>>();
-                // Do round robin to get host
-                match &service.multi_person {
-                    ServiceMultiPersonOptions::RoundRobinAlgorithm(round_robin) => {
-                        match round_robin {
-                            RoundRobinAlgorithm::Availability => {
-                                if hosts_at_slot.len() == 1 {
-                                    vec![hosts_at_slot[0].user_id.clone()]
-                                } else {
-                                    let events = ctx
-                                        .repos
-                                        .events
-                                        .find_most_recently_created_service_events(
-                                            &service.id,
-                                            &user_ids_at_slot,
-                                        )
-                                        .await
-                                        .map_err(|_| UseCaseError::StorageError)?;
-
-                                    let query = RoundRobinAvailabilityAssignment {
-                                        members: events
-                                            .into_iter()
-                                            .map(|e| (e.user_id, e.created))
-                                            .collect::<Vec<(ID, Option<DateTime<Utc>>)>>(),
-                                    };
-                                    let selected_user_id = query.assign().ok_or_else(|| {
-                                        warn!("At least one host can be picked when there are at least one host available");
-                                        UseCaseError::UserNotAvailable
-                                    })?;
-                                    vec![selected_user_id]
-                                }
+                                let query = RoundRobinAvailabilityAssignment {
+                                    members: events
+                                        .into_iter()
+                                        .map(|e| (e.user_id, e.created))
+                                        .collect::<Vec<(ID, Option<DateTime<Utc>>)>>(),
+                                };
+                                let selected_user_id = query.assign().ok_or_else(|| {
+                                    warn!("At least one host can be picked when there are at least one host available");
+                                    UseCaseError::UserNotAvailable
+                                })?;
+                                vec![selected_user_id]
                             }
-                            RoundRobinAlgorithm::EqualDistribution => {
-                                if hosts_at_slot.len() == 1 {
-                                    vec![hosts_at_slot[0].user_id.clone()]
-                                } else {
-                                    let now = Utc::now();
-                                    let timestamp_in_two_months =
-                                        now + TimeDelta::milliseconds(1000 * 60 * 60 * 24 * 61);
+                        }
+                        RoundRobinAlgorithm::EqualDistribution => {
+                            if hosts_at_slot.len() == 1 {
+                                vec![hosts_at_slot[0].user_id.clone()]
+                            } else {
+                                let now = ctx.sys.get_timestamp();
+                                let window = TimeSpan::new(
+                                    now,
+                                    now + TimeDelta::days(service.distribution_window),
+                                );
 
-                                    let service_events = ctx
-                                        .repos
-                                        .events
-                                        .find_by_service(
-                                            &service.id,
-                                            &user_ids_at_slot,
-                                            now,
-                                            timestamp_in_two_months,
-                                        )
-                                        .await
-                                        .map_err(|_| UseCaseError::StorageError)?;
+                                let service_events = ctx
+                                    .repos
+                                    .events
+                                    .find_by_service(
+                                        &service.id,
+                                        &user_ids_at_slot,
+                                        window.start(),
+                                        window.end(),
+                                    )
+                                    .await
+                                    .map_err(|_| UseCaseError::StorageError)?;
 
-                                    let query = RoundRobinEqualDistributionAssignment {
-                                        events: service_events,
-                                        user_ids: user_ids_at_slot,
-                                    };
-                                    let selected_user_id = query.assign().ok_or_else(|| {
-                                        warn!("At least one host can be picked when there are at least one host available");
-                                        UseCaseError::UserNotAvailable
-                                    })?;
-                                    vec![selected_user_id]
-                                }
+                                let query = RoundRobinEqualDistributionAssignment {
+                                    events: service_events,
+                                    members: hosts_at_slot
+                                        .iter()
+                                        .map(|h| (h.user_id.clone(), h.weight))
+                                        .collect(),
+                                    window,
+                                };
+                                let selected_user_id = query.assign().ok_or_else(|| {
+                                    warn!("At least one host can be picked when there are at least one host available");
+                                    UseCaseError::UserNotAvailable
+                                })?;
+                                vec![selected_user_id]
                             }
                         }
                     }
-                    ServiceMultiPersonOptions::Collective => {
-                        let all_hosts_user_ids: Vec<_> = service
-                            .users
-                            .iter()
-                            .map(|resource| resource.user_id.clone())
-                            .collect();
-
-                        // Check that all the hosts are available
-                        if user_ids_at_slot.len() < all_hosts_user_ids.len() {
-                            return Err(UseCaseError::UserNotAvailable);
-                        }
+                }
+                ServiceMultiPersonOptions::Collective | ServiceMultiPersonOptions::Group(_) => {
+                    let all_hosts_user_ids: Vec<_> = service
+                        .users
+                        .iter()
+                        .map(|resource| resource.user_id.clone())
+                        .collect();
 
-                        all_hosts_user_ids
+                    // Check that all the hosts are available
+                    if user_ids_at_slot.len() < all_hosts_user_ids.len() {
+                        return Err(UseCaseError::UserNotAvailable);
                     }
-                    ServiceMultiPersonOptions::Group(max_count) => {
-                        let all_hosts_user_ids: Vec<_> = service
-                            .users
-                            .iter()
-                            .map(|resource| resource.user_id.clone())
-                            .collect();
-
-                        // Check that all the hosts are available
-                        if user_ids_at_slot.len() < all_hosts_user_ids.len() {
-                            return Err(UseCaseError::UserNotAvailable);
-                        }
-
-                        let reservations = ctx
-                            .repos
-                            .reservations
-                            .count(&service.id, self.timestamp)
-                            .await
-                            .map_err(|_| UseCaseError::StorageError)?;
-                        if reservations + 1 < *max_count {
-                            // Client do not need to create service event yet
-                            create_event_for_hosts = false;
-                        }
-
-                        ctx.repos
-                            .reservations
-                            .increment(&service.id, self.timestamp)
-                            .await
-                            .map_err(|_| UseCaseError::StorageError)?;
 
-                        all_hosts_user_ids
-                    }
+                    all_hosts_user_ids
                 }
             }
-        };
-
-        let selected_hosts = ctx
-            .repos
-            .users
-            .find_many(&selected_host_user_ids)
-            .await
-            .map_err(|_| UseCaseError::StorageError)?;
+        }
+    };
 
-        Ok(UseCaseRes {
-            selected_hosts,
-            create_event_for_hosts,
-        })
-    }
+    Ok(selected_host_user_ids)
 }
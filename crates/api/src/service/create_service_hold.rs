@@ -0,0 +1,175 @@
+use axum::{Extension, Json, extract::Path, http::StatusCode};
+use chrono::{DateTime, Duration, TimeDelta, Utc};
+use nittei_api_structs::create_service_hold::*;
+use nittei_domain::{Account, ID, ServiceHold, ServiceMultiPersonOptions, format_date};
+use nittei_infra::NitteiContext;
+
+use super::{
+    create_service_booking::booking_host,
+    create_service_event_intend::{self, select_hosts},
+    get_service_bookingslots::{self, GetServiceBookingSlotsUseCase},
+};
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+pub async fn create_service_hold_controller(
+    Extension(account): Extension<Account>,
+    mut path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Json<RequestBody>,
+) -> Result<(StatusCode, Json<APIResponse>), NitteiError> {
+    let mut body = body.0;
+    let usecase = CreateServiceHoldUseCase {
+        account,
+        service_id: std::mem::take(&mut path.service_id),
+        host_user_ids: body.host_user_ids.take(),
+        timestamp: body.timestamp,
+        duration: body.duration,
+        interval: body.interval,
+        ttl_minutes: body.ttl_minutes,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|hold| (StatusCode::CREATED, Json(APIResponse::new(hold))))
+        .map_err(NitteiError::from)
+}
+
+/// Hold a slot of a service for its hosts during `ttl_minutes`, e.g. while the customer is checking out
+///
+/// The held slot isn't available for the hosts anymore, until the hold is confirmed into a booking,
+/// released or expired
+#[derive(Debug)]
+struct CreateServiceHoldUseCase {
+    pub account: Account,
+    pub service_id: ID,
+    pub host_user_ids: Option<Vec<ID>>,
+    pub timestamp: DateTime<Utc>,
+    pub duration: i64,
+    pub interval: i64,
+    pub ttl_minutes: i64,
+}
+
+#[derive(Debug)]
+enum UseCaseError {
+    ServiceNotFound,
+    InvalidTTL,
+    GroupService,
+    SlotTaken,
+    StorageError,
+    BookingSlotsQuery(get_service_bookingslots::UseCaseError),
+    HostSelection(create_service_event_intend::UseCaseError),
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::ServiceNotFound => {
+                Self::NotFound("The requested service was not found".into())
+            }
+            UseCaseError::InvalidTTL => Self::BadClientData(format!(
+                "Invalid ttlMinutes. It should be between {MIN_TTL_MINUTES} - {MAX_TTL_MINUTES} minutes inclusively."
+            )),
+            UseCaseError::GroupService => {
+                Self::BadClientData("The slots of group services can't be held".into())
+            }
+            UseCaseError::SlotTaken => {
+                Self::Conflict("The slot has been booked or held in the meantime".into())
+            }
+            UseCaseError::StorageError => Self::InternalError,
+            UseCaseError::BookingSlotsQuery(e) => e.into(),
+            UseCaseError::HostSelection(e) => e.into(),
+        }
+    }
+}
+
+const MIN_TTL_MINUTES: i64 = 1;
+const MAX_TTL_MINUTES: i64 = 60;
+
+#[async_trait::async_trait]
+impl UseCase for CreateServiceHoldUseCase {
+    type Response = ServiceHold;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "CreateServiceHold";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        if !(MIN_TTL_MINUTES..=MAX_TTL_MINUTES).contains(&self.ttl_minutes) {
+            return Err(UseCaseError::InvalidTTL);
+        }
+        match ctx.repos.services.find(&self.service_id).await {
+            Ok(Some(s)) if s.account_id == self.account.id => (),
+            Ok(_) => return Err(UseCaseError::ServiceNotFound),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        let get_bookingslots_usecase = GetServiceBookingSlotsUseCase {
+            duration: self.duration,
+            service_id: self.service_id.clone(),
+            start_date: format_date(&self.timestamp),
+            end_date: format_date(&(self.timestamp + Duration::days(1))),
+            timezone: Some(chrono_tz::UTC),
+            interval: self.interval,
+            host_user_ids: self.host_user_ids.clone(),
+            ignored_event_ids: Vec::new(),
+        };
+        let res = execute(get_bookingslots_usecase, ctx)
+            .await
+            .map_err(UseCaseError::BookingSlotsQuery)?;
+        let service = res.service;
+        // The hosts are shared by all the reservations of a group slot, so they can't be held by one of them
+        if let ServiceMultiPersonOptions::Group(_) = service.multi_person {
+            return Err(UseCaseError::GroupService);
+        }
+
+        let host_user_ids = select_hosts(
+            ctx,
+            &service,
+            res.booking_slots.dates,
+            self.host_user_ids.as_deref(),
+            self.timestamp,
+        )
+        .await
+        .map_err(UseCaseError::HostSelection)?;
+
+        let end_time = self.timestamp + TimeDelta::milliseconds(self.duration);
+        let mut hosts = Vec::with_capacity(host_user_ids.len());
+        for user_id in &host_user_ids {
+            let Some(resource) = service.users.iter().find(|r| &r.user_id == user_id) else {
+                return Err(UseCaseError::HostSelection(
+                    create_service_event_intend::UseCaseError::UserNotAvailable,
+                ));
+            };
+            hosts.push(
+                booking_host(ctx, resource, self.timestamp, end_time)
+                    .await
+                    .map_err(|_| UseCaseError::StorageError)?,
+            );
+        }
+
+        let now = ctx.sys.get_timestamp();
+        let hold = ServiceHold {
+            id: Default::default(),
+            service_id: service.id.clone(),
+            host_user_ids,
+            start_time: self.timestamp,
+            end_time,
+            expires_at: now + TimeDelta::minutes(self.ttl_minutes),
+            created: now,
+        };
+        let inserted = ctx
+            .repos
+            .reservations
+            .insert_hold(&hold, &hosts, now)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        if !inserted {
+            return Err(UseCaseError::SlotTaken);
+        }
+
+        Ok(hold)
+    }
+}
@@ -9,6 +9,7 @@ use nittei_api_structs::get_service_bookingslots::*;
 use nittei_domain::{
     BusyCalendarProvider,
     Calendar,
+    CalendarEventStatus,
     CompatibleInstances,
     EventInstance,
     ID,
@@ -18,6 +19,7 @@ use nittei_domain::{
     TimePlan,
     TimeSpan,
     Tz,
+    Weekday,
     booking_slots::{
         BookingQueryError,
         BookingSlotsOptions,
@@ -29,6 +31,7 @@ use nittei_domain::{
         validate_slots_interval,
     },
     get_free_busy,
+    scheduling::BookingLimits,
 };
 use nittei_infra::{
     FreeBusyProviderQuery,
@@ -41,7 +44,10 @@ use tracing::{error, warn};
 
 use crate::{
     error::NitteiError,
-    shared::usecase::{UseCase, execute},
+    shared::{
+        event_instances::find_materialized_instances,
+        usecase::{UseCase, execute},
+    },
     user::parse_vec_query_value,
 };
 
@@ -62,6 +68,7 @@ pub async fn get_service_bookingslots_controller(
         duration: query_params.duration,
         interval: query_params.interval,
         host_user_ids,
+        ignored_event_ids: Vec::new(),
     };
 
     execute(usecase, &ctx)
@@ -79,6 +86,8 @@ pub(crate) struct GetServiceBookingSlotsUseCase {
     pub duration: i64,
     pub interval: i64,
     pub host_user_ids: Option<Vec<ID>>,
+    /// Events which don't make their hosts busy, e.g. the ones of a booking being rescheduled
+    pub ignored_event_ids: Vec<ID>,
 }
 
 impl From<UseCaseError> for NitteiError {
@@ -128,6 +137,39 @@ impl From<BookingQueryError> for UseCaseError {
     }
 }
 
+/// The booking limits of the user, counted in the timezone of its availability
+///
+/// The calendar of the availability is searched in `user_calendars` first
+pub(super) async fn find_booking_limits(
+    user: &ServiceResource,
+    user_calendars: &[Calendar],
+    ctx: &NitteiContext,
+) -> anyhow::Result<BookingLimits> {
+    let mut limits = BookingLimits::new(user, chrono_tz::UTC, Weekday::Mon);
+    if limits.is_unlimited() {
+        return Ok(limits);
+    }
+    match &user.availability {
+        TimePlan::Calendar(id) => {
+            let calendar = match user_calendars.iter().find(|cal| cal.id == *id) {
+                Some(calendar) => Some(calendar.clone()),
+                None => ctx.repos.calendars.find(id).await?,
+            };
+            if let Some(calendar) = calendar {
+                limits.timezone = calendar.settings.timezone;
+                limits.week_start = calendar.settings.week_start;
+            }
+        }
+        TimePlan::Schedule(id) => {
+            if let Some(schedule) = ctx.repos.schedules.find(id).await? {
+                limits.timezone = schedule.timezone;
+            }
+        }
+        TimePlan::Empty => (),
+    }
+    Ok(limits)
+}
+
 #[async_trait::async_trait]
 impl UseCase for GetServiceBookingSlotsUseCase {
     type Response = UseCaseRes;
@@ -250,14 +292,20 @@ impl GetServiceBookingSlotsUseCase {
                     .find_by_calendar(id, Some(timespan.clone()))
                     .await
                     .unwrap_or_default();
+                let mut materialized_instances =
+                    find_materialized_instances(ctx, &all_calendar_events, timespan.clone())
+                        .await?;
 
                 let all_event_instances = all_calendar_events
                     .iter()
                     // Todo: handle error
-                    .flat_map(|e| {
-                        let timespan = timespan.clone();
-                        e.expand(Some(timespan), &calendar.settings)
-                            .unwrap_or_default()
+                    .flat_map(|e| match materialized_instances.remove(&e.id) {
+                        Some(instances) => instances,
+                        None => {
+                            let timespan = timespan.clone();
+                            e.expand(Some(timespan), &calendar.settings)
+                                .unwrap_or_default()
+                        }
                     })
                     .collect::<Vec<_>>();
 
@@ -340,6 +388,34 @@ impl GetServiceBookingSlotsUseCase {
             .collect::<Vec<_>>();
         busy_events.append(&mut busy_service_events);
 
+        // The slots held for the user (by any service) are busy until the holds expire,
+        // with the buffers of the user in the service of the hold, as the service events
+        let mut held_slots = ctx
+            .repos
+            .reservations
+            .find_holds_for_user(
+                &user.user_id,
+                timespan.start(),
+                timespan.end(),
+                ctx.sys.get_timestamp(),
+            )
+            .await?
+            .into_iter()
+            .map(|hold| {
+                let (buffer_before, buffer_after) = all_service_resources
+                    .iter()
+                    .find(|s| s.service_id == hold.service_id)
+                    .map(|s| (s.buffer_before, s.buffer_after))
+                    .unwrap_or_default();
+                EventInstance {
+                    busy: true,
+                    start_time: hold.start_time - TimeDelta::minutes(buffer_before),
+                    end_time: hold.end_time + TimeDelta::minutes(buffer_after),
+                }
+            })
+            .collect::<Vec<_>>();
+        busy_events.append(&mut held_slots);
+
         for cal in nittei_busy_calendars {
             match ctx
                 .repos
@@ -348,12 +424,20 @@ impl GetServiceBookingSlotsUseCase {
                 .await
             {
                 Ok(calendar_events) => {
+                    let calendar_events = calendar_events
+                        .into_iter()
+                        .filter(|e| e.busy && !self.ignored_event_ids.contains(&e.id))
+                        .collect::<Vec<_>>();
+                    let mut materialized_instances =
+                        find_materialized_instances(ctx, &calendar_events, timespan.clone())
+                            .await?;
                     let mut calendar_busy_events = calendar_events
                         .into_iter()
-                        .filter(|e| e.busy)
                         .map(|e| -> anyhow::Result<Vec<EventInstance>> {
-                            let timespan = timespan.clone();
-                            let mut instances = e.expand(Some(timespan), &cal.settings)?;
+                            let mut instances = match materialized_instances.remove(&e.id) {
+                                Some(instances) => instances,
+                                None => e.expand(Some(timespan.clone()), &cal.settings)?,
+                            };
 
                             // Add buffer to instances if event is a service event
                             if let Some(service_id) = e.service_id
@@ -439,6 +523,60 @@ impl GetServiceBookingSlotsUseCase {
         Ok(CompatibleInstances::new(busy_events))
     }
 
+    /// The days and weeks in which the user has reached its maximum numbers of bookings,
+    /// counted in the timezone of its availability
+    async fn get_user_booking_limits_reached(
+        &self,
+        user: &ServiceResource,
+        user_calendars: &[Calendar],
+        timespan: &TimeSpan,
+        ctx: &NitteiContext,
+    ) -> anyhow::Result<CompatibleInstances> {
+        let limits = find_booking_limits(user, user_calendars, ctx).await?;
+        if limits.is_unlimited() {
+            return Ok(CompatibleInstances::new(Vec::new()));
+        }
+
+        let counted_timespan = limits.counted_timespan(timespan);
+        let mut bookings = Vec::new();
+        // The bookings of all the services of the user are counted, whether they are busy or not
+        for busy in [true, false] {
+            let events = ctx
+                .repos
+                .events
+                .find_user_service_events(
+                    &user.user_id,
+                    busy,
+                    counted_timespan.start(),
+                    counted_timespan.end(),
+                )
+                .await?;
+            bookings.extend(
+                events
+                    .into_iter()
+                    .filter(|e| {
+                        e.status != CalendarEventStatus::Cancelled
+                            && !self.ignored_event_ids.contains(&e.id)
+                    })
+                    .map(|e| e.start_time),
+            );
+        }
+        // The held slots are counted as they are confirmed without querying the booking slots again
+        let holds = ctx
+            .repos
+            .reservations
+            .find_holds_for_user(
+                &user.user_id,
+                counted_timespan.start(),
+                counted_timespan.end(),
+                ctx.sys.get_timestamp(),
+            )
+            .await?;
+        bookings.extend(holds.into_iter().map(|hold| hold.start_time));
+
+        Ok(limits.reached_periods(&bookings))
+    }
+
     /// Ensure that calendar timespan fits within user settings for when
     /// it should be bookable
     fn parse_calendar_timespan(
@@ -509,11 +647,16 @@ impl GetServiceBookingSlotsUseCase {
             .get_user_availability(service_resource, &user_calendars, timespan.clone(), ctx)
             .await?;
 
+        let limits_reached = self
+            .get_user_booking_limits_reached(service_resource, &user_calendars, &timespan, ctx)
+            .await?;
+
         let busy_events = self
             .get_user_busy(service_resource, &user_calendars, timespan, ctx)
             .await?;
 
         free_events.remove_instances(&busy_events, 0);
+        free_events.remove_instances(&limits_reached, 0);
 
         Ok(UserFreeEvents {
             free_events,
@@ -586,6 +729,10 @@ mod test {
             availability: TimePlan::Empty,
             closest_booking_time: 0,
             furthest_booking_time: None,
+            weight: 1,
+            priority: 1,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
         };
         let mut resource2 = ServiceResource {
             user_id: user2.id.clone(),
@@ -595,6 +742,10 @@ mod test {
             availability: TimePlan::Empty,
             closest_booking_time: 0,
             furthest_booking_time: None,
+            weight: 1,
+            priority: 1,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
         };
 
         let calendar_user_1 = Calendar::new(&resource1.user_id, account_id, None, None);
@@ -659,6 +810,7 @@ mod test {
             interval: 1000 * 60 * 15,
             service_id: service.id,
             host_user_ids: None,
+            ignored_event_ids: Vec::new(),
         };
 
         let res = usecase.execute(&ctx).await;
@@ -683,6 +835,7 @@ mod test {
             interval: 1000 * 60 * 15,
             service_id: service.id.clone(),
             host_user_ids: None,
+            ignored_event_ids: Vec::new(),
         };
 
         let res = usecase.execute(&ctx).await;
@@ -710,6 +863,7 @@ mod test {
             interval: 1000 * 60 * 15,
             service_id: service.id,
             host_user_ids: None,
+            ignored_event_ids: Vec::new(),
         };
 
         let res = usecase.execute(&ctx).await;
@@ -1,29 +1,41 @@
 mod add_busy_calendar;
 mod add_user_to_service;
+pub mod cancel_service_booking;
+mod confirm_service_hold;
 mod create_service;
+pub mod create_service_booking;
 mod create_service_event_intend;
+mod create_service_hold;
 mod delete_service;
 mod get_service;
 mod get_service_bookingslots;
 mod get_services_by_meta;
+mod release_service_hold;
 mod remove_busy_calendar;
 mod remove_service_event_intend;
 mod remove_user_from_service;
+pub mod reschedule_service_booking;
 mod update_service;
 mod update_service_user;
 
 use add_busy_calendar::add_busy_calendar_controller;
 use add_user_to_service::add_user_to_service_controller;
 use axum::routing::{delete, get, post, put};
+use cancel_service_booking::cancel_service_booking_controller;
+use confirm_service_hold::confirm_service_hold_controller;
 use create_service::create_service_controller;
+use create_service_booking::create_service_booking_controller;
 use create_service_event_intend::create_service_event_intend_controller;
+use create_service_hold::create_service_hold_controller;
 use delete_service::delete_service_controller;
 use get_service::get_service_controller;
 use get_service_bookingslots::get_service_bookingslots_controller;
 use get_services_by_meta::get_services_by_meta_controller;
+use release_service_hold::release_service_hold_controller;
 use remove_busy_calendar::remove_busy_calendar_controller;
 use remove_service_event_intend::remove_service_event_intend_controller;
 use remove_user_from_service::remove_user_from_service_controller;
+use reschedule_service_booking::reschedule_service_booking_controller;
 use update_service::update_service_controller;
 use update_service_user::update_service_user_controller;
 use utoipa_axum::router::OpenApiRouter;
@@ -61,6 +73,30 @@ pub fn configure_routes() -> OpenApiRouter {
             "/service/{service_id}/booking",
             get(get_service_bookingslots_controller),
         )
+        .route(
+            "/service/{service_id}/bookings",
+            post(create_service_booking_controller),
+        )
+        .route(
+            "/service/{service_id}/bookings/{event_id}/cancel",
+            post(cancel_service_booking_controller),
+        )
+        .route(
+            "/service/{service_id}/bookings/{event_id}/reschedule",
+            post(reschedule_service_booking_controller),
+        )
+        .route(
+            "/service/{service_id}/holds",
+            post(create_service_hold_controller),
+        )
+        .route(
+            "/service/{service_id}/holds/{hold_id}",
+            delete(release_service_hold_controller),
+        )
+        .route(
+            "/service/{service_id}/holds/{hold_id}/confirm",
+            post(confirm_service_hold_controller),
+        )
         .route(
             "/service/{service_id}/booking-intend",
             post(create_service_event_intend_controller),
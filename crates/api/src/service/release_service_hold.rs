@@ -0,0 +1,88 @@
+use axum::{Extension, Json, extract::Path};
+use nittei_api_structs::release_service_hold::*;
+use nittei_domain::{Account, ID};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+pub async fn release_service_hold_controller(
+    Extension(account): Extension<Account>,
+    mut path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = ReleaseServiceHoldUseCase {
+        account,
+        service_id: std::mem::take(&mut path.service_id),
+        hold_id: std::mem::take(&mut path.hold_id),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|_| Json(APIResponse::from("Service hold released")))
+        .map_err(NitteiError::from)
+}
+
+/// Release a hold of a service before it expires, which makes the slot available again
+#[derive(Debug)]
+struct ReleaseServiceHoldUseCase {
+    pub account: Account,
+    pub service_id: ID,
+    pub hold_id: ID,
+}
+
+#[derive(Debug)]
+enum UseCaseError {
+    ServiceNotFound,
+    HoldNotFound,
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::ServiceNotFound => {
+                Self::NotFound("The requested service was not found".into())
+            }
+            UseCaseError::HoldNotFound => Self::NotFound("The requested hold was not found".into()),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for ReleaseServiceHoldUseCase {
+    type Response = ();
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "ReleaseServiceHold";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        match ctx.repos.services.find(&self.service_id).await {
+            Ok(Some(s)) if s.account_id == self.account.id => (),
+            Ok(_) => return Err(UseCaseError::ServiceNotFound),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+        match ctx.repos.reservations.find_hold(&self.hold_id).await {
+            Ok(Some(hold)) if hold.service_id == self.service_id => (),
+            Ok(_) => return Err(UseCaseError::HoldNotFound),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        // The hold may have been confirmed or expired in the meantime
+        let deleted = ctx
+            .repos
+            .reservations
+            .delete_hold(&self.hold_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        if !deleted {
+            return Err(UseCaseError::HoldNotFound);
+        }
+
+        Ok(())
+    }
+}
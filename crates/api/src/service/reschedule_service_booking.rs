@@ -0,0 +1,287 @@
+use axum::{Extension, Json, extract::Path};
+use chrono::{DateTime, Duration, TimeDelta, Utc};
+use nittei_api_structs::reschedule_service_booking::*;
+use nittei_domain::{
+    Account,
+    CalendarEvent,
+    CalendarEventStatus,
+    ID,
+    ServiceMultiPersonOptions,
+    format_date,
+};
+use nittei_infra::{NitteiContext, ServiceBooking, ServiceBookingOutcome};
+use nittei_utils::config::APP_CONFIG;
+
+use super::{
+    cancel_service_booking::{cancelled_booking, find_booking_events},
+    create_service_booking::{booking_host, find_host_calendar_id},
+    create_service_event_intend::{self, select_hosts},
+    get_service_bookingslots::{self, GetServiceBookingSlotsUseCase},
+};
+use crate::{
+    error::NitteiError,
+    event::subscribers::{
+        CreateRemindersOnEventCreated,
+        CreateSyncedEventsOnEventCreated,
+        RefreshInstancesOnEventChanged,
+        SendEventWebhook,
+        SyncRemindersOnEventUpdated,
+        UpdateSyncedEventsOnEventUpdated,
+    },
+    shared::usecase::{Subscriber, UseCase, execute, outbox_messages},
+};
+
+pub async fn reschedule_service_booking_controller(
+    Extension(account): Extension<Account>,
+    mut path: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+    body: Json<RequestBody>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = RescheduleServiceBookingUseCase {
+        account,
+        service_id: std::mem::take(&mut path.service_id),
+        event_id: std::mem::take(&mut path.event_id),
+        timestamp: body.timestamp,
+        interval: body.interval,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|res| Json(APIResponse::new(res.cancelled_events, res.events)))
+        .map_err(NitteiError::from)
+}
+
+/// Move a booking of a service, identified by one of the events of its hosts, to another slot
+///
+/// The events of the booking are cancelled and the events at the new time are created in the same transaction.
+/// The hosts are kept if they are available at the new time, otherwise the hosts of round robin services
+/// are assigned again
+#[derive(Debug)]
+pub struct RescheduleServiceBookingUseCase {
+    pub account: Account,
+    pub service_id: ID,
+    pub event_id: ID,
+    /// Start of the new slot
+    pub timestamp: DateTime<Utc>,
+    pub interval: i64,
+}
+
+#[derive(Debug)]
+pub struct UseCaseRes {
+    /// The events of the previous slot
+    pub cancelled_events: Vec<CalendarEvent>,
+    /// The events of the new slot
+    pub events: Vec<CalendarEvent>,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    ServiceNotFound,
+    BookingNotFound(ID),
+    GroupService,
+    CutoffPassed(i64),
+    CalendarNotFound(ID),
+    SlotTaken,
+    StorageError,
+    BookingSlotsQuery(get_service_bookingslots::UseCaseError),
+    HostSelection(create_service_event_intend::UseCaseError),
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::ServiceNotFound => {
+                Self::NotFound("The requested service was not found".into())
+            }
+            UseCaseError::BookingNotFound(event_id) => Self::NotFound(format!(
+                "No booking of the service was found with the event with id: {event_id}"
+            )),
+            UseCaseError::GroupService => {
+                Self::BadClientData("The bookings of group services can't be rescheduled".into())
+            }
+            UseCaseError::CutoffPassed(cutoff) => Self::BadClientData(format!(
+                "The booking can't be rescheduled less than {cutoff} minutes before its start"
+            )),
+            UseCaseError::CalendarNotFound(user_id) => Self::BadClientData(format!(
+                "No calendar was found to create the booking of the host with id: {user_id}"
+            )),
+            UseCaseError::SlotTaken => Self::Conflict(
+                "The slot has been booked or the booking has been modified in the meantime".into(),
+            ),
+            UseCaseError::StorageError => Self::InternalError,
+            UseCaseError::BookingSlotsQuery(e) => e.into(),
+            UseCaseError::HostSelection(e) => e.into(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for RescheduleServiceBookingUseCase {
+    type Response = UseCaseRes;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "RescheduleServiceBooking";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let service = match ctx.repos.services.find_with_users(&self.service_id).await {
+            Ok(Some(service)) if service.account_id == self.account.id => service,
+            Ok(_) => return Err(UseCaseError::ServiceNotFound),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+        if let ServiceMultiPersonOptions::Group(_) = service.multi_person {
+            return Err(UseCaseError::GroupService);
+        }
+
+        let previous_events = find_booking_events(ctx, &service, &self.event_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or_else(|| UseCaseError::BookingNotFound(self.event_id.clone()))?;
+        let Some(previous_event) = previous_events.first().cloned() else {
+            return Err(UseCaseError::BookingNotFound(self.event_id.clone()));
+        };
+
+        let now = ctx.sys.get_timestamp();
+        if !service.can_reschedule_booking(previous_event.start_time, now) {
+            return Err(UseCaseError::CutoffPassed(service.reschedule_cutoff));
+        }
+
+        let duration = previous_event.duration;
+        let get_bookingslots_usecase = GetServiceBookingSlotsUseCase {
+            duration,
+            service_id: self.service_id.clone(),
+            start_date: format_date(&self.timestamp),
+            end_date: format_date(&(self.timestamp + Duration::days(1))),
+            timezone: Some(chrono_tz::UTC),
+            interval: self.interval,
+            host_user_ids: None,
+            ignored_event_ids: previous_events.iter().map(|e| e.id.clone()).collect(),
+        };
+        let res = execute(get_bookingslots_usecase, ctx)
+            .await
+            .map_err(UseCaseError::BookingSlotsQuery)?;
+        let service = res.service;
+
+        // The hosts of the booking are kept when they are all available at the new time
+        let previous_host_user_ids = previous_events
+            .iter()
+            .map(|e| e.user_id.clone())
+            .collect::<Vec<_>>();
+        let selected_host_user_ids = match select_hosts(
+            ctx,
+            &service,
+            res.booking_slots.dates.clone(),
+            Some(&previous_host_user_ids),
+            self.timestamp,
+        )
+        .await
+        {
+            Ok(host_user_ids) => host_user_ids,
+            Err(create_service_event_intend::UseCaseError::UserNotAvailable)
+                if matches!(
+                    service.multi_person,
+                    ServiceMultiPersonOptions::RoundRobinAlgorithm(_)
+                ) =>
+            {
+                select_hosts(ctx, &service, res.booking_slots.dates, None, self.timestamp)
+                    .await
+                    .map_err(UseCaseError::HostSelection)?
+            }
+            Err(e) => return Err(UseCaseError::HostSelection(e)),
+        };
+
+        let end_time = self.timestamp + TimeDelta::milliseconds(duration);
+        let mut hosts = Vec::with_capacity(selected_host_user_ids.len());
+        let mut events = Vec::with_capacity(selected_host_user_ids.len());
+        for user_id in selected_host_user_ids {
+            let Some(resource) = service.users.iter().find(|r| r.user_id == user_id) else {
+                return Err(UseCaseError::HostSelection(
+                    create_service_event_intend::UseCaseError::UserNotAvailable,
+                ));
+            };
+            // The events of the hosts kept are created in the same calendars
+            let calendar_id = match previous_events.iter().find(|e| e.user_id == user_id) {
+                Some(previous_event) => previous_event.calendar_id.clone(),
+                None => find_host_calendar_id(ctx, &service.id, &user_id, None)
+                    .await
+                    .map_err(|_| UseCaseError::StorageError)?
+                    .ok_or_else(|| UseCaseError::CalendarNotFound(user_id.clone()))?,
+            };
+
+            hosts.push(
+                booking_host(ctx, resource, self.timestamp, end_time)
+                    .await
+                    .map_err(|_| UseCaseError::StorageError)?,
+            );
+            events.push(CalendarEvent {
+                calendar_id,
+                user_id,
+                account_id: service.account_id.clone(),
+                title: previous_event.title.clone(),
+                description: previous_event.description.clone(),
+                event_type: previous_event.event_type.clone(),
+                location: previous_event.location.clone(),
+                status: CalendarEventStatus::Confirmed,
+                busy: true,
+                start_time: self.timestamp,
+                duration,
+                end_time,
+                service_id: Some(service.id.clone()),
+                metadata: previous_event.metadata.clone(),
+                created: now,
+                updated: now,
+                ..Default::default()
+            });
+        }
+
+        let booking = ServiceBooking {
+            service_id: service.id.clone(),
+            timestamp: self.timestamp,
+            hosts,
+            events,
+            max_reservations: None,
+            hold_id: None,
+            now,
+            cancelled_booking: Some(cancelled_booking(
+                &service,
+                previous_events,
+                previous_event.start_time,
+                now,
+            )),
+        };
+        let mut res = UseCaseRes {
+            cancelled_events: Vec::new(),
+            events: booking.events.clone(),
+        };
+        if let Some(cancelled_booking) = &booking.cancelled_booking {
+            res.cancelled_events.clone_from(&cancelled_booking.events);
+        }
+
+        let outbox = outbox_messages(self, &res, ctx).await;
+        let outcome = ctx
+            .repos
+            .events
+            .insert_service_booking(&booking, &outbox)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        match outcome {
+            ServiceBookingOutcome::Booked { .. } => Ok(res),
+            ServiceBookingOutcome::Conflict => Err(UseCaseError::SlotTaken),
+        }
+    }
+
+    fn subscribers() -> Vec<Box<dyn Subscriber<Self>>> {
+        let mut subscribers: Vec<Box<dyn Subscriber<Self>>> = vec![Box::new(SendEventWebhook)];
+        if !APP_CONFIG.disable_reminders {
+            subscribers.push(Box::new(SyncRemindersOnEventUpdated));
+            subscribers.push(Box::new(UpdateSyncedEventsOnEventUpdated));
+            subscribers.push(Box::new(CreateRemindersOnEventCreated));
+            subscribers.push(Box::new(CreateSyncedEventsOnEventCreated));
+        }
+        if APP_CONFIG.event_instances.enabled {
+            subscribers.push(Box::new(RefreshInstancesOnEventChanged));
+        }
+        subscribers
+    }
+}
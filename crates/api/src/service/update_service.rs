@@ -20,6 +20,9 @@ pub async fn update_service_controller(
         service_id: std::mem::take(&mut path.service_id),
         metadata: body.metadata.take(),
         multi_person: body.multi_person.take(),
+        cancellation_cutoff: body.cancellation_cutoff,
+        reschedule_cutoff: body.reschedule_cutoff,
+        distribution_window: body.distribution_window,
     };
 
     execute(usecase, &ctx)
@@ -34,6 +37,9 @@ struct UpdateServiceUseCase {
     service_id: ID,
     metadata: Option<serde_json::Value>,
     multi_person: Option<ServiceMultiPersonOptions>,
+    cancellation_cutoff: Option<i64>,
+    reschedule_cutoff: Option<i64>,
+    distribution_window: Option<i64>,
 }
 #[derive(Debug)]
 struct UseCaseRes {
@@ -42,6 +48,8 @@ struct UseCaseRes {
 
 #[derive(Debug)]
 enum UseCaseError {
+    InvalidCutoff,
+    InvalidDistributionWindow,
     StorageError,
     ServiceNotFound(ID),
 }
@@ -52,6 +60,13 @@ impl From<UseCaseError> for NitteiError {
             UseCaseError::ServiceNotFound(id) => {
                 Self::NotFound(format!("Service with id: {id} was not found."))
             }
+            UseCaseError::InvalidCutoff => Self::BadClientData(
+                "The cancellation and reschedule cut-offs should be positive numbers of minutes"
+                    .into(),
+            ),
+            UseCaseError::InvalidDistributionWindow => Self::BadClientData(
+                "The distribution window should be between 1 and 365 days".into(),
+            ),
             UseCaseError::StorageError => Self::InternalError,
         }
     }
@@ -66,6 +81,12 @@ impl UseCase for UpdateServiceUseCase {
     const NAME: &'static str = "UpdateService";
 
     async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        if self.cancellation_cutoff.is_some_and(|cutoff| cutoff < 0)
+            || self.reschedule_cutoff.is_some_and(|cutoff| cutoff < 0)
+        {
+            return Err(UseCaseError::InvalidCutoff);
+        }
+
         let mut service = match ctx.repos.services.find(&self.service_id).await {
             Ok(Some(service)) if service.account_id == self.account_id => service,
             Ok(_) => return Err(UseCaseError::ServiceNotFound(self.service_id.clone())),
@@ -90,6 +111,17 @@ impl UseCase for UpdateServiceUseCase {
             }
             service.multi_person = opts.clone();
         }
+        if let Some(cancellation_cutoff) = self.cancellation_cutoff {
+            service.cancellation_cutoff = cancellation_cutoff;
+        }
+        if let Some(reschedule_cutoff) = self.reschedule_cutoff {
+            service.reschedule_cutoff = reschedule_cutoff;
+        }
+        if let Some(distribution_window) = self.distribution_window
+            && !service.set_distribution_window(distribution_window)
+        {
+            return Err(UseCaseError::InvalidDistributionWindow);
+        }
 
         ctx.repos
             .services
@@ -28,6 +28,10 @@ pub async fn update_service_user_controller(
         buffer_before: body.buffer_before,
         closest_booking_time: body.closest_booking_time,
         furthest_booking_time: body.furthest_booking_time,
+        weight: body.weight,
+        priority: body.priority,
+        max_bookings_per_day: body.max_bookings_per_day,
+        max_bookings_per_week: body.max_bookings_per_week,
     };
 
     execute(usecase, &ctx)
@@ -46,6 +50,10 @@ struct UpdateServiceUserUseCase {
     pub buffer_before: Option<i64>,
     pub closest_booking_time: Option<i64>,
     pub furthest_booking_time: Option<i64>,
+    pub weight: Option<i64>,
+    pub priority: Option<i64>,
+    pub max_bookings_per_day: Option<i64>,
+    pub max_bookings_per_week: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -108,6 +116,10 @@ impl UseCase for UpdateServiceUserUseCase {
                 buffer_before: self.buffer_before,
                 closest_booking_time: self.closest_booking_time,
                 furthest_booking_time: self.furthest_booking_time,
+                weight: self.weight,
+                priority: self.priority,
+                max_bookings_per_day: self.max_bookings_per_day,
+                max_bookings_per_week: self.max_bookings_per_week,
             },
             ctx,
         )
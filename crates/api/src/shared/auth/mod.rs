@@ -9,6 +9,8 @@ pub use route_guards::{
     account_can_modify_schedule,
     account_can_modify_user,
     account_can_modify_user_middleware,
+    auth_user_req,
+    get_client_account,
     protect_admin_route_middleware,
     protect_public_account_route,
     protect_route_middleware,
@@ -13,7 +13,11 @@ use tracing::{instrument, log::warn};
 
 use crate::{
     error::NitteiError,
-    shared::{Guard, auth::Policy},
+    shared::{
+        Guard,
+        auth::Policy,
+        rate_limit::{check_account_rate_limit, check_public_rate_limit, check_user_rate_limit},
+    },
 };
 
 /// The header name for the API key (used for admin routes)
@@ -170,6 +174,8 @@ async fn protect_route_inner(
         NitteiError::Unauthorized("Unable to find user from the given credentials".into())
     })?;
 
+    check_user_rate_limit(&account, &user.id)?;
+
     Ok((user, policy))
 }
 
@@ -184,6 +190,7 @@ pub async fn protect_admin_route_middleware(
     next: Next,
 ) -> Result<Response, NitteiError> {
     let account = protect_admin_route_inner(&headers, &ctx).await?;
+    check_account_rate_limit(&account)?;
 
     // Inject the account into the request extensions
     let mut request = request;
@@ -231,7 +238,7 @@ pub async fn protect_public_account_route(
     headers: &HeaderMap,
     ctx: &NitteiContext,
 ) -> Result<Account, NitteiError> {
-    match get_nittei_account_header(headers) {
+    let account = match get_nittei_account_header(headers) {
         Some(res) => {
             let account_id = res?;
 
@@ -244,11 +251,18 @@ pub async fn protect_public_account_route(
                     NitteiError::UnidentifiableClient(
                         "Could not find out which account the client belongs to".into(),
                     )
-                })
+                })?
         }
         // No nittei-account header, then check if this is an admin client
-        None => protect_admin_route_inner(headers, ctx).await,
-    }
+        None => {
+            let account = protect_admin_route_inner(headers, ctx).await?;
+            check_account_rate_limit(&account)?;
+            return Ok(account);
+        }
+    };
+    check_public_rate_limit(&account)?;
+
+    Ok(account)
 }
 
 /// Used for account admin routes by checking that account
@@ -362,7 +376,7 @@ pub async fn account_can_modify_schedule(
 mod test {
     use axum::{body::Body, http::Request};
     use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
-    use nittei_domain::PEMKey;
+    use nittei_domain::{PEMKey, RateLimit};
     use nittei_infra::setup_context;
 
     use super::*;
@@ -417,6 +431,74 @@ mod test {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn rejects_requests_over_the_rate_limit_of_the_user() {
+        let ctx = setup_context().await.unwrap();
+        let mut account = get_account();
+        account.settings.rate_limits.user = Some(RateLimit {
+            requests_per_second: 1,
+            burst: 1,
+        });
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let other_user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&other_user).await.unwrap();
+
+        let request = |user_id: ID| {
+            Request::builder()
+                .header("nittei-account", account.id.to_string())
+                .header(
+                    "Authorization",
+                    format!("Bearer {}", get_token(false, user_id)),
+                )
+                .body(Body::empty())
+                .unwrap()
+        };
+        let req = request(user.id.clone());
+        assert!(protect_route_inner(&ctx, req.headers()).await.is_ok());
+        let res = protect_route_inner(&ctx, req.headers()).await;
+        assert!(matches!(res, Err(NitteiError::TooManyRequests(1))));
+
+        // The limit is per user
+        let req = request(other_user.id.clone());
+        assert!(protect_route_inner(&ctx, req.headers()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn public_routes_have_their_own_rate_limit() {
+        let ctx = setup_context().await.unwrap();
+        let mut account = get_account();
+        account.settings.rate_limits.account = Some(RateLimit {
+            requests_per_second: 1,
+            burst: 1,
+        });
+        ctx.repos.accounts.insert(&account).await.unwrap();
+
+        let public_req = Request::builder()
+            .header("nittei-account", account.id.to_string())
+            .body(Body::empty())
+            .unwrap();
+        assert!(
+            protect_public_account_route(public_req.headers(), &ctx)
+                .await
+                .is_ok()
+        );
+        let res = protect_public_account_route(public_req.headers(), &ctx).await;
+        assert!(matches!(res, Err(NitteiError::TooManyRequests(1))));
+
+        // The requests with the API key aren't limited by the public ones
+        let admin_req = Request::builder()
+            .header("x-api-key", account.secret_api_key.clone())
+            .body(Body::empty())
+            .unwrap();
+        assert!(
+            protect_public_account_route(admin_req.headers(), &ctx)
+                .await
+                .is_ok()
+        );
+    }
+
     #[tokio::test]
     async fn decodes_valid_token_and_rejects_if_user_is_in_different_account() {
         let ctx = setup_context().await.unwrap();
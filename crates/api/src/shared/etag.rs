@@ -0,0 +1,96 @@
+use axum::http::{HeaderMap, header};
+
+use crate::error::NitteiError;
+
+/// Format the version of a resource as a (strong) ETag
+pub fn etag(version: i64) -> String {
+    format!("\"{version}\"")
+}
+
+/// Parse the `If-Match` header into the version expected by the client
+/// Returns None when the header is absent, or when it matches any version (`*`)
+pub fn parse_if_match(headers: &HeaderMap) -> Result<Option<i64>, NitteiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| NitteiError::BadClientData("Invalid If-Match header".into()))?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    // Weak ETags are accepted as well, as the version is the same for both
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse::<i64>()
+        .map(Some)
+        .map_err(|_| {
+            NitteiError::BadClientData(format!(
+                "Invalid If-Match header, expected an ETag returned by the API but got: {value}"
+            ))
+        })
+}
+
+/// Check if the `If-None-Match` header matches the ETag of the current content,
+/// in which case the client already has it
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(value) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    // Weak comparison, as required for If-None-Match
+    value
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag.trim_start_matches("W/"))
+}
+
+#[cfg(test)]
+mod test {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn parses_if_match_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_if_match(&headers).unwrap(), None);
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(&etag(3)).unwrap());
+        assert_eq!(parse_if_match(&headers).unwrap(), Some(3));
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("W/\"4\""));
+        assert_eq!(parse_if_match(&headers).unwrap(), Some(4));
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("*"));
+        assert_eq!(parse_if_match(&headers).unwrap(), None);
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"abc\""));
+        assert!(parse_if_match(&headers).is_err());
+    }
+
+    #[test]
+    fn matches_if_none_match_header() {
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, "\"abc\""));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"abc\""));
+        assert!(if_none_match(&headers, "\"abc\""));
+        assert!(!if_none_match(&headers, "\"def\""));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            HeaderValue::from_static("\"def\", W/\"abc\""),
+        );
+        assert!(if_none_match(&headers, "\"abc\""));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(if_none_match(&headers, "\"def\""));
+    }
+}
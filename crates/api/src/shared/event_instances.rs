@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use nittei_domain::{
+    Calendar,
+    CalendarEvent,
+    EventInstance,
+    ID,
+    TimeSpan,
+    expand_all_events_and_remove_exceptions,
+};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+
+/// Find the materialized instances of the recurring events starting in the timespan (see `event_instances` config)
+/// The events without materialized instances for the whole timespan (e.g. changed recently) are not returned
+///
+/// Nothing is returned when the instances aren't materialized
+pub async fn find_materialized_instances(
+    ctx: &NitteiContext,
+    events: &[CalendarEvent],
+    timespan: TimeSpan,
+) -> anyhow::Result<HashMap<ID, Vec<EventInstance>>> {
+    if !APP_CONFIG.event_instances.enabled {
+        return Ok(HashMap::new());
+    }
+    find_materialized_instances_of_events(ctx, events, timespan).await
+}
+
+async fn find_materialized_instances_of_events(
+    ctx: &NitteiContext,
+    events: &[CalendarEvent],
+    timespan: TimeSpan,
+) -> anyhow::Result<HashMap<ID, Vec<EventInstance>>> {
+    let recurring_event_ids = events
+        .iter()
+        .filter(|e| e.recurrence.is_some())
+        .map(|e| e.id.clone())
+        .collect::<Vec<_>>();
+    if recurring_event_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut instances = ctx
+        .repos
+        .event_instances
+        .find_by_events(&recurring_event_ids, timespan)
+        .await?;
+    for event in events {
+        if let Some(event_instances) = instances.get_mut(&event.id) {
+            for instance in event_instances.iter_mut() {
+                instance.busy = event.busy;
+            }
+        }
+    }
+
+    Ok(instances)
+}
+
+/// Expand the events and remove the exceptions (see `expand_all_events_and_remove_exceptions`)
+/// The materialized instances of the recurring events are used instead of expanding them, when available
+pub async fn expand_events_and_remove_exceptions(
+    ctx: &NitteiContext,
+    calendars: &HashMap<String, &Calendar>,
+    events: &Vec<CalendarEvent>,
+    timespan: TimeSpan,
+) -> anyhow::Result<Vec<EventInstance>> {
+    let materialized = find_materialized_instances(ctx, events, timespan.clone()).await?;
+    with_materialized_instances(calendars, events, materialized, timespan)
+}
+
+/// Expand the events and remove the exceptions, using the given materialized instances when available
+pub fn with_materialized_instances(
+    calendars: &HashMap<String, &Calendar>,
+    events: &Vec<CalendarEvent>,
+    mut materialized: HashMap<ID, Vec<EventInstance>>,
+    timespan: TimeSpan,
+) -> anyhow::Result<Vec<EventInstance>> {
+    if materialized.is_empty() {
+        return expand_all_events_and_remove_exceptions(calendars, events, timespan);
+    }
+
+    // The exceptions are already removed from the materialized instances
+    let events_to_expand = events
+        .iter()
+        .filter(|e| !materialized.contains_key(&e.id))
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut instances =
+        expand_all_events_and_remove_exceptions(calendars, &events_to_expand, timespan)?;
+    for event in events {
+        if let Some(event_instances) = materialized.remove(&event.id) {
+            instances.extend(event_instances);
+        }
+    }
+
+    Ok(instances)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::DateTime;
+    use nittei_domain::{
+        Account,
+        CalendarEventStatus,
+        MaterializedEventInstances,
+        RRuleFrequency,
+        RRuleOptions,
+        User,
+    };
+    use nittei_infra::setup_context;
+
+    use super::*;
+
+    /// The materialized instances are the same as the instances expanded in memory
+    #[tokio::test]
+    async fn materialized_instances_are_equivalent_to_the_expanded_ones() {
+        let ctx = setup_context().await.unwrap();
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let mut calendar = Calendar::new(&user.id, &account.id, None, None);
+        calendar.settings.timezone = chrono_tz::Europe::Oslo;
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        let utc = |datetime: &str| DateTime::parse_from_rfc3339(datetime).unwrap().to_utc();
+        let one_hour = 1000 * 60 * 60;
+        let new_event = |start_time: &str, recurrence: Option<RRuleOptions>| {
+            let mut event = CalendarEvent {
+                account_id: account.id.clone(),
+                calendar_id: calendar.id.clone(),
+                user_id: user.id.clone(),
+                start_time: utc(start_time),
+                duration: one_hour,
+                busy: true,
+                status: CalendarEventStatus::Confirmed,
+                ..Default::default()
+            };
+            if let Some(recurrence) = recurrence {
+                event.set_recurrence(recurrence).unwrap();
+            }
+            event
+        };
+
+        // Daily event over the change to summer time, without its 2nd occurrence, and with its 3rd one moved
+        let mut daily = new_event(
+            "2030-03-28T08:00:00Z",
+            Some(RRuleOptions {
+                freq: RRuleFrequency::Daily,
+                ..Default::default()
+            }),
+        );
+        daily.exdates = vec![utc("2030-03-29T08:00:00Z")];
+        let moved = CalendarEvent {
+            recurring_event_id: Some(daily.id.clone()),
+            original_start_time: Some(utc("2030-03-30T08:00:00Z")),
+            ..new_event("2030-03-30T12:00:00Z", None)
+        };
+        // Weekly event not busy, with a limited number of occurrences
+        let mut weekly = new_event(
+            "2030-03-27T14:00:00Z",
+            Some(RRuleOptions {
+                freq: RRuleFrequency::Weekly,
+                count: Some(3),
+                ..Default::default()
+            }),
+        );
+        weekly.busy = false;
+        // Single event
+        let single = new_event("2030-04-02T10:00:00Z", None);
+        let events = vec![daily.clone(), moved, weekly.clone(), single];
+        for event in &events {
+            ctx.repos.events.insert(event).await.unwrap();
+        }
+
+        let window = TimeSpan::new(utc("2030-03-25T00:00:00Z"), utc("2030-05-01T00:00:00Z"));
+        for event in [&daily, &weekly] {
+            let exceptions = if event.id == daily.id {
+                vec![utc("2030-03-30T08:00:00Z")]
+            } else {
+                Vec::new()
+            };
+            let materialized =
+                MaterializedEventInstances::new(&calendar, event, exceptions, window.clone(), 1000)
+                    .unwrap();
+            assert!(
+                ctx.repos
+                    .event_instances
+                    .replace(&materialized, utc("2030-03-25T00:00:00Z"))
+                    .await
+                    .unwrap()
+            );
+        }
+
+        let calendars = HashMap::from([(calendar.id.to_string(), &calendar)]);
+        let sorted = |mut instances: Vec<EventInstance>| {
+            instances.sort_by_key(|i| (i.start_time, i.end_time, i.busy));
+            instances
+        };
+        for timespan in [
+            TimeSpan::new(utc("2030-03-27T00:00:00Z"), utc("2030-04-03T00:00:00Z")),
+            TimeSpan::new(utc("2030-03-30T08:00:00Z"), utc("2030-04-15T00:00:00Z")),
+            // Partly outside of the window, the recurrences are expanded
+            TimeSpan::new(utc("2030-03-20T00:00:00Z"), utc("2030-04-03T00:00:00Z")),
+        ] {
+            let materialized =
+                find_materialized_instances_of_events(&ctx, &events, timespan.clone())
+                    .await
+                    .unwrap();
+            assert_eq!(
+                materialized.len(),
+                if timespan.start() < window.start() {
+                    0
+                } else {
+                    2
+                }
+            );
+
+            let instances =
+                with_materialized_instances(&calendars, &events, materialized, timespan.clone())
+                    .unwrap();
+            let expanded =
+                expand_all_events_and_remove_exceptions(&calendars, &events, timespan).unwrap();
+            assert_eq!(sorted(instances), sorted(expanded));
+        }
+
+        // The instances of a changed event aren't used anymore
+        daily.duration = one_hour * 2;
+        ctx.repos.events.save(&daily).await.unwrap();
+        let events = vec![daily, weekly];
+        let timespan = TimeSpan::new(utc("2030-03-27T00:00:00Z"), utc("2030-04-03T00:00:00Z"));
+        let materialized = find_materialized_instances_of_events(&ctx, &events, timespan)
+            .await
+            .unwrap();
+        assert_eq!(materialized.len(), 1);
+        assert!(materialized.contains_key(&events[1].id));
+        assert!(
+            materialized[&events[1].id]
+                .iter()
+                .all(|instance| !instance.busy)
+        );
+    }
+}
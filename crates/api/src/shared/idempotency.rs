@@ -0,0 +1,389 @@
+use axum::{
+    Extension,
+    body::{Body, Bytes, to_bytes},
+    http::{HeaderMap, HeaderValue, Method, Request, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::TimeDelta;
+use nittei_domain::{ID, IdempotencyKey, IdempotentResponse};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+use sha2::{Digest, Sha256};
+use tracing::{Instrument, error};
+
+use crate::{
+    error::NitteiError,
+    shared::auth::{NITTEI_X_API_KEY_HEADER, auth_user_req, get_client_account},
+};
+
+/// Header of the idempotency key sent by the clients
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header added to the responses replayed for a retried request
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Max length of an idempotency key
+const MAX_KEY_LENGTH: usize = 255;
+
+/// Max size of the body of the requests sent with an idempotency key
+/// Same as the default limit of the extractors (e.g. `Json`)
+const MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// Middleware for the `POST` requests sent with an `Idempotency-Key` header
+///
+/// The key is recorded with the hash of the request, and with its response once the request completes.
+/// The retries of the request (same key and same request) get the recorded response, instead of running it again,
+/// until the key expires (`idempotency.ttl_secs`). Reusing the key for another request is a conflict (409),
+/// as is retrying while the request is still in progress.
+///
+/// The server errors (5xx) and the rate limited requests (429) aren't recorded, so that the request can be retried
+pub async fn idempotency_middleware(
+    Extension(ctx): Extension<NitteiContext>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let key = match key.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+        _ => {
+            return NitteiError::BadClientData(format!(
+                "The {IDEMPOTENCY_KEY_HEADER} header must be a string of 1 to {MAX_KEY_LENGTH} characters"
+            ))
+            .into_response();
+        }
+    };
+    // The requests without valid credentials are rejected by the route guards
+    let scope = match authenticated_scope(request.headers(), &ctx).await {
+        Ok(Some(scope)) => scope,
+        Ok(None) => return next.run(request).await,
+        Err(e) => {
+            error!("[idempotency] Unable to authenticate the client: {:?}", e);
+            return NitteiError::InternalError.into_response();
+        }
+    };
+
+    let (parts, body) = request.into_parts();
+    let body = match to_bytes(body, MAX_BODY_SIZE).await {
+        Ok(body) => body,
+        Err(e) => {
+            return NitteiError::BadClientData(format!(
+                "Unable to read the body of the request: {e}"
+            ))
+            .into_response();
+        }
+    };
+
+    let now = ctx.sys.get_timestamp();
+    let idempotency_key = IdempotencyKey {
+        scope,
+        key,
+        request_hash: request_hash(&parts.method, parts.uri.to_string().as_str(), &body),
+        response: None,
+        created_at: now,
+        expires_at: now + TimeDelta::seconds(APP_CONFIG.idempotency.ttl_secs),
+    };
+    match ctx.repos.idempotency_keys.insert(&idempotency_key).await {
+        Ok(true) => {}
+        Ok(false) => return replay_response(&ctx, &idempotency_key).await,
+        Err(e) => {
+            error!(
+                "[idempotency] Unable to insert the idempotency key: {:?}",
+                e
+            );
+            return NitteiError::InternalError.into_response();
+        }
+    }
+
+    // The request runs in its own task, so that it completes (and its response is recorded)
+    // even if the client disconnects before, e.g. after a timeout. Its retry then gets the response
+    let request = Request::from_parts(parts, Body::from(body));
+    let context = ctx.clone();
+    let key = idempotency_key.clone();
+    let handle = tokio::spawn(
+        async move {
+            let response = next.run(request).await;
+            record_response(&context, &key, response).await
+        }
+        .in_current_span(),
+    );
+
+    match handle.await {
+        Ok(response) => response,
+        Err(e) => {
+            error!("[idempotency] The request failed: {:?}", e);
+            delete_key(&ctx, &idempotency_key).await;
+            NitteiError::InternalError.into_response()
+        }
+    }
+}
+
+/// Scope of the idempotency keys of a client, which is its account, and its user when authenticated with a token
+/// The scope doesn't depend on the credentials themselves, so that a retry with refreshed credentials gets the same scope
+/// Returns None if the request doesn't have valid credentials
+async fn authenticated_scope(
+    headers: &HeaderMap,
+    ctx: &NitteiContext,
+) -> anyhow::Result<Option<String>> {
+    let mut account = None;
+    if let Some(api_key) = headers
+        .get(NITTEI_X_API_KEY_HEADER)
+        .and_then(|api_key| api_key.to_str().ok())
+    {
+        account = ctx.repos.accounts.find_by_apikey(api_key).await?;
+    }
+    let is_admin = account.is_some();
+    let has_token = headers.contains_key(header::AUTHORIZATION);
+    if !is_admin && has_token {
+        account = get_client_account(headers, ctx).await?;
+    }
+    let Some(account) = account else {
+        return Ok(None);
+    };
+
+    let user = if has_token {
+        auth_user_req(headers, &account, ctx).await?
+    } else {
+        None
+    };
+    match user {
+        Some((user, _)) => Ok(Some(scope(&account.id, Some(&user.id)))),
+        None if is_admin => Ok(Some(scope(&account.id, None))),
+        // The token is invalid
+        None => Ok(None),
+    }
+}
+
+/// Scope of the idempotency keys of an account, or of one of its users
+fn scope(account_id: &ID, user_id: Option<&ID>) -> String {
+    match user_id {
+        Some(user_id) => format!("{account_id}:{user_id}"),
+        None => account_id.to_string(),
+    }
+}
+
+/// Hash of the request, a retry needs to have the same method, path (and query) and body
+fn request_hash(method: &Method, uri: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_str());
+    hasher.update(b"\n");
+    hasher.update(uri);
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+/// Response to a request sent with a key already used
+async fn replay_response(ctx: &NitteiContext, idempotency_key: &IdempotencyKey) -> Response {
+    let existing = match ctx
+        .repos
+        .idempotency_keys
+        .find(&idempotency_key.scope, &idempotency_key.key)
+        .await
+    {
+        Ok(existing) => existing,
+        Err(e) => {
+            error!("[idempotency] Unable to find the idempotency key: {:?}", e);
+            return NitteiError::InternalError.into_response();
+        }
+    };
+
+    // The key is removed if the previous request failed in the meantime
+    let Some(existing) = existing else {
+        return NitteiError::Conflict(
+            "A request with the same idempotency key was in progress, please retry".into(),
+        )
+        .into_response();
+    };
+    if existing.request_hash != idempotency_key.request_hash {
+        return NitteiError::Conflict(
+            "The idempotency key was already used for a different request".into(),
+        )
+        .into_response();
+    }
+    let Some(response) = existing.response else {
+        return NitteiError::Conflict(
+            "A request with the same idempotency key is in progress".into(),
+        )
+        .into_response();
+    };
+
+    let Ok(status) = StatusCode::from_u16(response.status) else {
+        return NitteiError::InternalError.into_response();
+    };
+    let mut replayed = (status, Body::from(response.body)).into_response();
+    let headers = replayed.headers_mut();
+    if let Some(content_type) = response
+        .content_type
+        .and_then(|content_type| HeaderValue::from_str(&content_type).ok())
+    {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    replayed
+}
+
+/// Record the response of the request, or remove the key if it failed (server error)
+/// or has been rate limited
+async fn record_response(
+    ctx: &NitteiContext,
+    idempotency_key: &IdempotencyKey,
+    response: Response,
+) -> Response {
+    let (parts, body) = response.into_parts();
+    let body: Bytes = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            error!(
+                "[idempotency] Unable to read the body of the response: {:?}",
+                e
+            );
+            delete_key(ctx, idempotency_key).await;
+            return NitteiError::InternalError.into_response();
+        }
+    };
+
+    if parts.status.is_server_error() || parts.status == StatusCode::TOO_MANY_REQUESTS {
+        delete_key(ctx, idempotency_key).await;
+    } else {
+        let recorded = IdempotentResponse {
+            status: parts.status.as_u16(),
+            content_type: parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|content_type| content_type.to_str().ok())
+                .map(ToString::to_string),
+            body: body.to_vec(),
+        };
+        if let Err(e) = ctx
+            .repos
+            .idempotency_keys
+            .save_response(&idempotency_key.scope, &idempotency_key.key, &recorded)
+            .await
+        {
+            error!(
+                "[idempotency] Unable to save the response of the idempotency key: {:?}",
+                e
+            );
+            delete_key(ctx, idempotency_key).await;
+        }
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Remove the key, so that the request can be retried
+async fn delete_key(ctx: &NitteiContext, idempotency_key: &IdempotencyKey) {
+    if let Err(e) = ctx
+        .repos
+        .idempotency_keys
+        .delete(&idempotency_key.scope, &idempotency_key.key)
+        .await
+    {
+        error!(
+            "[idempotency] Unable to delete the idempotency key: {:?}",
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use axum::http::HeaderName;
+    use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+    use nittei_domain::{Account, PEMKey, User};
+    use nittei_infra::setup_context;
+    use serde_json::json;
+
+    use super::*;
+
+    fn headers(values: &[(&str, String)]) -> HeaderMap {
+        values
+            .iter()
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                    HeaderValue::from_str(value).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn request_hash_depends_on_the_whole_request() {
+        let hash = request_hash(&Method::POST, "/api/v1/user", b"{}");
+        assert_eq!(hash, request_hash(&Method::POST, "/api/v1/user", b"{}"));
+        assert_ne!(hash, request_hash(&Method::POST, "/api/v1/user", b"{ }"));
+        assert_ne!(hash, request_hash(&Method::POST, "/api/v1/users", b"{}"));
+        assert_ne!(hash, request_hash(&Method::PUT, "/api/v1/user", b"{}"));
+    }
+
+    #[tokio::test]
+    async fn scope_is_the_authenticated_client() {
+        let ctx = setup_context().await.unwrap();
+        let pub_key = std::fs::read_to_string("./config/test_public_rsa_key.crt").unwrap();
+        let account = Account {
+            public_jwt_key: Some(PEMKey::new(pub_key).unwrap()),
+            ..Default::default()
+        };
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+
+        let api_key = account.secret_api_key.clone();
+        let scope = authenticated_scope(&headers(&[("x-api-key", api_key)]), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(scope, Some(account.id.to_string()));
+
+        // Invalid credentials have no scope, nothing is recorded for them
+        let scope = authenticated_scope(&headers(&[("x-api-key", "invalid".into())]), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(scope, None);
+
+        // A refreshed token of the same user has the same scope
+        let priv_key = std::fs::read("./config/test_private_rsa_key.pem").unwrap();
+        let token = |iat: usize| {
+            let claims = json!({ "exp": 5609418990073_usize, "iat": iat, "nitteiUserId": user.id });
+            let enc_key = EncodingKey::from_rsa_pem(&priv_key).unwrap();
+            encode(&Header::new(Algorithm::RS256), &claims, &enc_key).unwrap()
+        };
+        let user_headers = |token: String| {
+            headers(&[
+                ("nittei-account", account.id.to_string()),
+                ("authorization", format!("Bearer {token}")),
+            ])
+        };
+        let scope = authenticated_scope(&user_headers(token(1)), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(scope, Some(format!("{}:{}", account.id, user.id)));
+        let refreshed_scope = authenticated_scope(&user_headers(token(2)), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(refreshed_scope, scope);
+        let scope = authenticated_scope(&user_headers("invalid".into()), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(scope, None);
+    }
+
+    #[test]
+    fn scope_depends_on_the_account_and_the_user() {
+        let account_id = ID::default();
+        let user_id = ID::default();
+        assert_eq!(scope(&account_id, None), scope(&account_id, None));
+        assert_ne!(scope(&account_id, None), scope(&ID::default(), None));
+        assert_ne!(scope(&account_id, None), scope(&account_id, Some(&user_id)));
+        assert_ne!(
+            scope(&account_id, Some(&user_id)),
+            scope(&account_id, Some(&ID::default()))
+        );
+    }
+}
@@ -1,4 +1,9 @@
 pub mod auth;
+pub mod etag;
+pub mod event_instances;
 mod guard;
+mod idempotency;
+mod rate_limit;
 pub mod usecase;
 pub use guard::Guard;
+pub use idempotency::idempotency_middleware;
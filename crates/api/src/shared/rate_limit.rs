@@ -0,0 +1,212 @@
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+
+use nittei_domain::{Account, ID, RateLimit};
+use nittei_infra::metrics::increment_rate_limited_requests;
+use nittei_utils::config::APP_CONFIG;
+use tracing::warn;
+
+use crate::error::NitteiError;
+
+/// Interval between the removals of the full buckets (clients without recent requests)
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Rate limiter of the instance, the limits are applied per instance of the application
+static RATE_LIMITER: LazyLock<RateLimiter> = LazyLock::new(RateLimiter::default);
+
+/// Token bucket of a client
+/// It has `burst` tokens when full, and is refilled with `requests_per_second` tokens per second
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+    /// When the bucket is full again, it can then be removed (a new bucket is full)
+    full_at: Instant,
+}
+
+/// Token buckets of the clients, kept in memory
+#[derive(Debug, Default)]
+struct Buckets {
+    by_client: HashMap<String, TokenBucket>,
+    pruned_at: Option<Instant>,
+}
+
+#[derive(Debug, Default)]
+struct RateLimiter {
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    /// Take a token from the bucket of the client
+    /// Returns the time to wait before the next token if the bucket is empty
+    fn acquire(&self, key: &str, limit: RateLimit, now: Instant) -> Result<(), Duration> {
+        let rate = f64::from(limit.requests_per_second);
+        let burst = f64::from(limit.burst);
+
+        // A poisoned lock only means that another request panicked, the buckets are still valid
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if buckets
+            .pruned_at
+            .is_none_or(|pruned_at| now.duration_since(pruned_at) >= PRUNE_INTERVAL)
+        {
+            buckets.by_client.retain(|_, bucket| bucket.full_at > now);
+            buckets.pruned_at = Some(now);
+        }
+
+        let bucket = buckets
+            .by_client
+            .entry(key.to_string())
+            .or_insert(TokenBucket {
+                tokens: burst,
+                updated_at: now,
+                full_at: now,
+            });
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.updated_at = now;
+
+        let res = if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        };
+        bucket.full_at = now + Duration::from_secs_f64((burst - bucket.tokens) / rate);
+        res
+    }
+}
+
+/// Default limit, from the number of requests per second and the burst configured
+fn default_limit(requests_per_second: Option<u32>, burst: Option<u32>) -> Option<RateLimit> {
+    requests_per_second.map(|requests_per_second| RateLimit {
+        requests_per_second,
+        burst: burst.unwrap_or(requests_per_second),
+    })
+}
+
+/// Check the limit of the requests of the account (with its API key)
+pub fn check_account_rate_limit(account: &Account) -> Result<(), NitteiError> {
+    check_rate_limit(&account.id, "account", &account.id, account_limit(account))
+}
+
+/// Check the limit of the requests on the public routes of the account
+/// They have their own bucket, as anyone can send them, so that they can't exhaust the one of the API key
+pub fn check_public_rate_limit(account: &Account) -> Result<(), NitteiError> {
+    check_rate_limit(&account.id, "public", &account.id, account_limit(account))
+}
+
+fn account_limit(account: &Account) -> Option<RateLimit> {
+    account.settings.rate_limits.account.or_else(|| {
+        default_limit(
+            APP_CONFIG.rate_limit.account_requests_per_second,
+            APP_CONFIG.rate_limit.account_burst,
+        )
+    })
+}
+
+/// Check the limit of the requests of a user of the account (with a JWT)
+pub fn check_user_rate_limit(account: &Account, user_id: &ID) -> Result<(), NitteiError> {
+    let limit = account.settings.rate_limits.user.or_else(|| {
+        default_limit(
+            APP_CONFIG.rate_limit.user_requests_per_second,
+            APP_CONFIG.rate_limit.user_burst,
+        )
+    });
+    check_rate_limit(&account.id, "user", user_id, limit)
+}
+
+fn check_rate_limit(
+    account_id: &ID,
+    scope: &str,
+    client_id: &ID,
+    limit: Option<RateLimit>,
+) -> Result<(), NitteiError> {
+    let Some(limit) = limit else {
+        return Ok(());
+    };
+
+    RATE_LIMITER
+        .acquire(&format!("{scope}:{client_id}"), limit, Instant::now())
+        .map_err(|retry_after| {
+            warn!(
+                account_id = %account_id,
+                scope,
+                client_id = %client_id,
+                "[rate_limit] The client exceeded its rate limit"
+            );
+            increment_rate_limited_requests(&account_id.to_string(), scope);
+            NitteiError::TooManyRequests(retry_after.as_secs_f64().ceil().max(1.0) as u64)
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn token_bucket_allows_bursts_and_refills() {
+        let limiter = RateLimiter::default();
+        let limit = RateLimit {
+            requests_per_second: 2,
+            burst: 3,
+        };
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.acquire("client_1", limit, now).is_ok());
+        }
+        assert_eq!(
+            limiter.acquire("client_1", limit, now),
+            Err(Duration::from_millis(500))
+        );
+        // The buckets are per client
+        assert!(limiter.acquire("client_2", limit, now).is_ok());
+
+        // Refilled with 2 tokens per second, up to the burst
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.acquire("client_1", limit, later).is_ok());
+        assert!(limiter.acquire("client_1", limit, later).is_err());
+        let much_later = later + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert!(limiter.acquire("client_1", limit, much_later).is_ok());
+        }
+        assert!(limiter.acquire("client_1", limit, much_later).is_err());
+    }
+
+    #[test]
+    fn full_buckets_are_removed_periodically() {
+        let limiter = RateLimiter::default();
+        let limit = RateLimit {
+            requests_per_second: 1,
+            burst: 100,
+        };
+        let now = Instant::now();
+        let clients = |limiter: &RateLimiter| {
+            let buckets = limiter.buckets.lock().unwrap();
+            let mut clients = buckets.by_client.keys().cloned().collect::<Vec<_>>();
+            clients.sort();
+            clients
+        };
+
+        assert!(limiter.acquire("client_1", limit, now).is_ok());
+        for _ in 0..70 {
+            assert!(limiter.acquire("client_2", limit, now).is_ok());
+        }
+
+        // Not removed before the interval, even if full
+        let later = now + Duration::from_secs(30);
+        assert!(limiter.acquire("client_3", limit, later).is_ok());
+        assert_eq!(clients(&limiter), vec!["client_1", "client_2", "client_3"]);
+
+        // The buckets of the clients 1 and 3 are full again, the one of the client 2 is still refilling
+        let much_later = now + PRUNE_INTERVAL;
+        assert!(limiter.acquire("client_4", limit, much_later).is_ok());
+        assert_eq!(clients(&limiter), vec!["client_2", "client_4"]);
+    }
+}
@@ -1,6 +1,10 @@
-use std::fmt::Debug;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+};
 
-use futures::future::join_all;
+use chrono::{DateTime, Utc};
+use nittei_domain::{OutboxMessage, OutboxTask};
 use nittei_infra::NitteiContext;
 use tracing::{debug, warn};
 
@@ -10,10 +14,18 @@ use crate::error::NitteiError;
 /// Subscriber is a side effect to a `UseCase`
 ///
 /// It is going to act upon the response of the execution
-/// of the `UseCase` if the execution was a success.
-#[async_trait::async_trait]
+/// of the `UseCase` (and the `UseCase` itself) if the execution was a success.
+///
+/// Its work is recorded in the outbox, in the same transaction as the write
+/// of the `UseCase` (see `outbox_messages`), and processed by the outbox worker.
+/// This way, it isn't lost if the process crashes or if it fails (it is retried).
 pub trait Subscriber<U: UseCase>: Send + Sync {
-    async fn notify(&self, e: &U::Response, ctx: &NitteiContext);
+    fn outbox_messages(
+        &self,
+        usecase: &U,
+        res: &U::Response,
+        now: DateTime<Utc>,
+    ) -> Vec<OutboxMessage>;
 }
 
 #[async_trait::async_trait]
@@ -31,6 +43,61 @@ pub trait UseCase: Debug + Send {
     }
 }
 
+/// Build the messages to record in the outbox for the subscribers of the `UseCase`
+///
+/// The `UseCase` is responsible for recording them in the same transaction as its write
+pub async fn outbox_messages<U: UseCase>(
+    usecase: &U,
+    res: &U::Response,
+    ctx: &NitteiContext,
+) -> Vec<OutboxMessage> {
+    let now = ctx.sys.get_timestamp();
+    let messages = U::subscribers()
+        .iter()
+        .flat_map(|subscriber| subscriber.outbox_messages(usecase, res, now))
+        .collect();
+    retain_subscribed_webhooks(messages, ctx).await
+}
+
+/// Remove the webhooks of the accounts without webhook, or which haven't opted in to their type of change,
+/// so that nothing is recorded for them
+/// The subscription is checked again when the messages are processed, as it can change in between
+async fn retain_subscribed_webhooks(
+    mut messages: Vec<OutboxMessage>,
+    ctx: &NitteiContext,
+) -> Vec<OutboxMessage> {
+    let account_ids = messages
+        .iter()
+        .filter(|message| matches!(message.task, OutboxTask::SendWebhook { .. }))
+        .map(|message| message.account_id.clone())
+        .collect::<HashSet<_>>();
+
+    let mut subscriptions = HashMap::new();
+    for account_id in account_ids {
+        match ctx.repos.accounts.find(&account_id).await {
+            Ok(account) => {
+                let webhook = account.and_then(|account| account.settings.webhook);
+                subscriptions.insert(account_id, webhook);
+            }
+            // The webhooks are still recorded, the subscription is checked when they are processed
+            Err(e) => warn!("Unable to find the account of the webhooks: {:?}", e),
+        }
+    }
+
+    messages.retain(|message| match &message.task {
+        OutboxTask::SendWebhook { event_type, .. } => {
+            match subscriptions.get(&message.account_id) {
+                Some(webhook) => webhook
+                    .as_ref()
+                    .is_some_and(|webhook| webhook.is_subscribed_to(*event_type)),
+                None => true,
+            }
+        }
+        _ => true,
+    });
+    messages
+}
+
 /// Restrict what `Permission`s are needed for a `User`
 /// to be able to execute the `UseCase`
 pub trait PermissionBoundary: UseCase {
@@ -97,20 +164,85 @@ where
     debug!("{:?}", usecase);
     let res = usecase.execute(ctx).await;
 
-    match &res {
-        Ok(res) => {
-            let subscribers = U::subscribers();
-            let mut subscriber_promises = Vec::with_capacity(subscribers.len());
-            for subscriber in &subscribers {
-                subscriber_promises.push(subscriber.notify(res, ctx));
-            }
-            join_all(subscriber_promises).await;
-        }
-        Err(e) => {
-            // This is debug because the error is usually already logged deeper in the stack
-            debug!("Error: {:?}", e);
-        }
+    if let Err(e) = &res {
+        // This is debug because the error is usually already logged deeper in the stack
+        debug!("Error: {:?}", e);
     }
 
     res
 }
+
+#[cfg(test)]
+mod test {
+    use chrono::DateTime;
+    use nittei_domain::{Account, ID, WebhookEventType};
+    use nittei_infra::setup_context;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn webhooks_are_only_recorded_for_the_subscribed_accounts() {
+        let ctx = setup_context().await.unwrap();
+        let mut account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+
+        let now = DateTime::from_timestamp_millis(0).unwrap();
+        let messages = || {
+            vec![
+                OutboxMessage::new(
+                    account.id.clone(),
+                    OutboxTask::SendWebhook {
+                        event_type: WebhookEventType::EventCreated,
+                        payload: serde_json::Value::Null,
+                    },
+                    now,
+                ),
+                OutboxMessage::new(
+                    account.id.clone(),
+                    OutboxTask::SendWebhook {
+                        event_type: WebhookEventType::EventDeleted,
+                        payload: serde_json::Value::Null,
+                    },
+                    now,
+                ),
+                OutboxMessage::new(
+                    account.id.clone(),
+                    OutboxTask::SyncEventReminders {
+                        event_id: ID::default(),
+                    },
+                    now,
+                ),
+            ]
+        };
+
+        // No webhook
+        let retained = retain_subscribed_webhooks(messages(), &ctx).await;
+        assert_eq!(retained.len(), 1);
+        assert!(matches!(
+            retained[0].task,
+            OutboxTask::SyncEventReminders { .. }
+        ));
+
+        // Only the changes the account has opted in to
+        assert!(
+            account
+                .settings
+                .set_webhook_url(Some("https://example.com/webhook".into()))
+        );
+        assert!(
+            account
+                .settings
+                .set_webhook_event_types(vec![WebhookEventType::EventCreated])
+        );
+        ctx.repos.accounts.save(&account).await.unwrap();
+        let retained = retain_subscribed_webhooks(messages(), &ctx).await;
+        assert_eq!(retained.len(), 2);
+        assert!(matches!(
+            retained[0].task,
+            OutboxTask::SendWebhook {
+                event_type: WebhookEventType::EventCreated,
+                ..
+            }
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use axum::{Extension, Json, http::HeaderMap};
 use chrono::{DateTime, Utc};
@@ -7,14 +7,7 @@ use nittei_api_structs::multiple_freebusy::{
     MultipleFreeBusyAPIResponse,
     MultipleFreeBusyRequestBody,
 };
-use nittei_domain::{
-    Calendar,
-    CalendarEvent,
-    EventInstance,
-    ID,
-    TimeSpan,
-    expand_all_events_and_remove_exceptions,
-};
+use nittei_domain::{Calendar, CalendarEvent, EventInstance, ID, TimeSpan};
 use nittei_infra::NitteiContext;
 use nittei_utils::config::APP_CONFIG;
 use tracing::error;
@@ -23,6 +16,11 @@ use crate::{
     error::NitteiError,
     shared::{
         auth::protect_public_account_route,
+        event_instances::{
+            expand_events_and_remove_exceptions,
+            find_materialized_instances,
+            with_materialized_instances,
+        },
         usecase::{UseCase, execute},
     },
 };
@@ -166,12 +164,21 @@ impl GetMultipleFreeBusyUseCase {
             let timespan = timespan.clone();
 
             async move {
-                let events = ctx
+                let mut events = ctx
                     .repos
                     .events
                     .find_by_calendar(&calendar.id, Some(timespan))
                     .await
                     .unwrap_or_default(); // TODO: Handle error
+
+                // Events declined by the user (as an attendee) do not make the user busy
+                for event in events
+                    .iter_mut()
+                    .filter(|e| e.is_declined_by(&calendar.user_id))
+                {
+                    event.busy = false;
+                }
+
                 Ok((calendar.user_id.clone(), events))
                     as Result<(ID, Vec<CalendarEvent>), UseCaseError>
             }
@@ -189,11 +196,13 @@ impl GetMultipleFreeBusyUseCase {
                 match event_result {
                     Ok((user_id, events)) => {
                         let timespan = timespan.clone();
-                        let expanded_events = expand_all_events_and_remove_exceptions(
+                        let expanded_events = expand_events_and_remove_exceptions(
+                            ctx,
                             &calendars_lookup,
                             &events,
                             timespan.clone(),
                         )
+                        .await
                         .map_err(|e| {
                             error!("Got an error when expanding events {:?}", e);
                             UseCaseError::InternalError
@@ -205,9 +214,90 @@ impl GetMultipleFreeBusyUseCase {
             }
         }
 
+        // Events of other users that the users attend (and have not declined) also make them busy
+        let attended_events = ctx
+            .repos
+            .events
+            .find_busy_events_and_recurring_events_for_attendees(
+                &self.user_ids,
+                timespan.clone(),
+                true,
+            )
+            .await
+            .map_err(|e| {
+                error!("Got an error when fetching attended events {:?}", e);
+                UseCaseError::InternalError
+            })?;
+        if !attended_events.is_empty() {
+            // The calendars of the attended events are needed for expanding them
+            let attended_calendar_ids = attended_events
+                .iter()
+                .map(|e| &e.calendar_id)
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect::<Vec<_>>();
+            let attended_calendars = ctx
+                .repos
+                .calendars
+                .find_multiple(attended_calendar_ids)
+                .await
+                .map_err(|e| {
+                    error!("Got an error when fetching calendars {:?}", e);
+                    UseCaseError::InternalError
+                })?;
+            let attended_calendars_lookup = attended_calendars
+                .iter()
+                .map(|cal| (cal.id.to_string(), cal))
+                .collect::<HashMap<_, _>>();
+            let materialized = find_materialized_instances(ctx, &attended_events, timespan.clone())
+                .await
+                .map_err(|e| {
+                    error!("Got an error when fetching materialized instances {:?}", e);
+                    UseCaseError::InternalError
+                })?;
+
+            for user_id in &self.user_ids {
+                let user_attended_events = attended_events
+                    .iter()
+                    .filter(|e| {
+                        &e.user_id != user_id
+                            && e.find_attendee_by_user(user_id).is_some()
+                            && !e.is_declined_by(user_id)
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if user_attended_events.is_empty() {
+                    continue;
+                }
+                let user_materialized = user_attended_events
+                    .iter()
+                    .filter_map(|e| {
+                        materialized
+                            .get(&e.id)
+                            .map(|instances| (e.id.clone(), instances.clone()))
+                    })
+                    .collect();
+
+                let expanded_events = with_materialized_instances(
+                    &attended_calendars_lookup,
+                    &user_attended_events,
+                    user_materialized,
+                    timespan.clone(),
+                )
+                .map_err(|e| {
+                    error!("Got an error when expanding events {:?}", e);
+                    UseCaseError::InternalError
+                })?;
+                events_per_user
+                    .entry(user_id.clone())
+                    .or_default()
+                    .extend(expanded_events);
+            }
+        }
+
         // Sort the events by start time
         for (_, events) in events_per_user.iter_mut() {
-            events.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+            events.sort_by_key(|e| e.start_time);
         }
 
         Ok(events_per_user)
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use axum::{
     Extension,
@@ -12,13 +12,7 @@ use nittei_api_structs::get_user_freebusy::{
     GetUserFreeBusyQueryParams,
     PathParams,
 };
-use nittei_domain::{
-    CompatibleInstances,
-    EventInstance,
-    ID,
-    TimeSpan,
-    expand_all_events_and_remove_exceptions,
-};
+use nittei_domain::{CompatibleInstances, EventInstance, ID, TimeSpan};
 use nittei_infra::NitteiContext;
 use nittei_utils::config::APP_CONFIG;
 
@@ -26,6 +20,7 @@ use crate::{
     error::NitteiError,
     shared::{
         auth::protect_public_account_route,
+        event_instances::expand_events_and_remove_exceptions,
         usecase::{UseCase, execute},
     },
 };
@@ -154,21 +149,16 @@ impl GetFreeBusyUseCase {
         // can probably make query to event repo instead
         let mut calendars = ctx.repos.calendars.find_by_user(&self.user_id).await?;
 
-        let calendar_ids = match &self.calendar_ids {
-            Some(ids) if !ids.is_empty() => ids.to_owned(),
-            _ => calendars.iter().map(|cal| cal.id.clone()).collect(),
+        let (calendar_ids, include_attended_events) = match &self.calendar_ids {
+            Some(ids) if !ids.is_empty() => (ids.to_owned(), false),
+            _ => (calendars.iter().map(|cal| cal.id.clone()).collect(), true),
         };
 
         if !calendar_ids.is_empty() {
             calendars.retain(|cal| calendar_ids.contains(&cal.id));
         }
 
-        let calendars_lookup: HashMap<_, _> = calendars
-            .iter()
-            .map(|cal| (cal.id.to_string(), cal))
-            .collect();
-
-        let events = ctx
+        let mut events = ctx
             .repos
             .events
             .find_busy_events_and_recurring_events_for_calendars(
@@ -178,17 +168,58 @@ impl GetFreeBusyUseCase {
             )
             .await?;
 
+        // Events of other users that the user attends (and has not declined) also make the user busy
+        // They are only taken into account when no specific calendars are requested
+        // The events of the calendars of the user make the user busy, whatever their attendees (not loaded)
+        if include_attended_events {
+            let mut attended_events = ctx
+                .repos
+                .events
+                .find_busy_events_and_recurring_events_for_attendees(
+                    std::slice::from_ref(&self.user_id),
+                    timespan.clone(),
+                    self.include_tentative.unwrap_or(false),
+                )
+                .await?;
+
+            // The attended events in the calendars of the user are already in the events
+            let event_ids = events.iter().map(|e| &e.id).collect::<HashSet<_>>();
+            attended_events.retain(|e| !event_ids.contains(&e.id));
+
+            if !attended_events.is_empty() {
+                // The calendars of the attended events are needed for expanding them
+                let attended_calendar_ids = attended_events
+                    .iter()
+                    .map(|e| &e.calendar_id)
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                calendars.extend(
+                    ctx.repos
+                        .calendars
+                        .find_multiple(attended_calendar_ids)
+                        .await?,
+                );
+                events.extend(attended_events);
+            }
+        }
+
         // If we have no events, return early
         if events.is_empty() {
             return Ok(Vec::new());
         }
 
+        let calendars_lookup: HashMap<_, _> = calendars
+            .iter()
+            .map(|cal| (cal.id.to_string(), cal))
+            .collect();
+
         // Expand the events, remove the exceptions and return the expanded events
         let mut events =
-            expand_all_events_and_remove_exceptions(&calendars_lookup, &events, timespan)?;
+            expand_events_and_remove_exceptions(ctx, &calendars_lookup, &events, timespan).await?;
 
         // Sort the events by start_time
-        events.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+        events.sort_by_key(|e| e.start_time);
 
         Ok(events)
     }
@@ -196,7 +227,15 @@ impl GetFreeBusyUseCase {
 
 #[cfg(test)]
 mod test {
-    use nittei_domain::{Account, Calendar, CalendarEvent, Entity, RRuleOptions, User};
+    use nittei_domain::{
+        Account,
+        Calendar,
+        CalendarEvent,
+        CalendarEventAttendee,
+        Entity,
+        RRuleOptions,
+        User,
+    };
     use nittei_infra::setup_context;
 
     use super::*;
@@ -428,4 +467,57 @@ mod test {
             }
         );
     }
+
+    #[tokio::test]
+    async fn test_freebusy_of_own_attended_event() {
+        let ctx = setup_context().await.unwrap();
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let calendar = Calendar::new(&user.id(), &user.account_id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        // The owner of the event is also one of its attendees
+        let event = CalendarEvent {
+            calendar_id: calendar.id.clone(),
+            user_id: user.id.clone(),
+            account_id: user.account_id.clone(),
+            busy: true,
+            status: nittei_domain::CalendarEventStatus::Confirmed,
+            start_time: DateTime::parse_from_rfc3339("2025-01-09T11:00:00Z")
+                .unwrap()
+                .to_utc(),
+            end_time: DateTime::parse_from_rfc3339("2025-01-09T12:00:00Z")
+                .unwrap()
+                .to_utc(),
+            duration: 1000 * 60 * 60,
+            attendees: vec![CalendarEventAttendee {
+                user_id: Some(user.id.clone()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        ctx.repos.events.insert(&event).await.unwrap();
+
+        let freebusy_params = GetFreeBusyUseCase {
+            user_id: user.id().clone(),
+            calendar_ids: None,
+            start_time: DateTime::parse_from_rfc3339("2025-01-09T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+            end_time: DateTime::parse_from_rfc3339("2025-01-10T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+            include_tentative: None,
+        };
+        let timespan = TimeSpan::new(freebusy_params.start_time, freebusy_params.end_time);
+
+        let instances = freebusy_params
+            .get_event_instances_from_calendars(timespan, &ctx)
+            .await
+            .unwrap();
+
+        assert_eq!(instances.len(), 1);
+    }
 }
@@ -0,0 +1,126 @@
+use axum::{Extension, Json, extract::Query};
+use nittei_api_structs::get_webhook_deliveries::*;
+use nittei_domain::{
+    Account,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+    WebhookEndpointStatus,
+};
+use nittei_infra::NitteiContext;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    get,
+    tag = "Account",
+    path = "/api/v1/account/webhook/deliveries",
+    summary = "Get the webhook deliveries of the account, with their attempts (admin only)",
+    params(
+        ("status" = Option<WebhookDeliveryStatus>, Query, description = "Optional status of the deliveries to get (e.g. failed)"),
+        ("limit" = Option<u16>, Query, description = "Optional max number of deliveries to get (default 100)"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = GetWebhookDeliveriesAPIResponse)
+    )
+)]
+pub async fn get_webhook_deliveries_controller(
+    Extension(account): Extension<Account>,
+    query_params: Query<QueryParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = GetWebhookDeliveriesUseCase {
+        account,
+        status: query_params.0.status,
+        limit: query_params.0.limit,
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|res| Json(APIResponse::new(res.endpoint, res.deliveries)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for getting the webhook deliveries of an account, and the health of its endpoint
+#[derive(Debug)]
+pub struct GetWebhookDeliveriesUseCase {
+    pub account: Account,
+    pub status: Option<WebhookDeliveryStatus>,
+    pub limit: Option<u16>,
+}
+
+#[derive(Debug)]
+pub struct UseCaseResponse {
+    pub endpoint: Option<WebhookEndpointStatus>,
+    pub deliveries: Vec<(WebhookDelivery, Vec<WebhookDeliveryAttempt>)>,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for GetWebhookDeliveriesUseCase {
+    type Response = UseCaseResponse;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "GetWebhookDeliveries";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let repo = &ctx.repos.webhook_deliveries;
+
+        let endpoint = match &self.account.settings.webhook {
+            Some(webhook) => repo
+                .find_endpoint_status(&self.account.id, &webhook.url)
+                .await
+                .map_err(|_| UseCaseError::StorageError)?,
+            None => None,
+        };
+
+        let deliveries = repo
+            .find_by_account(
+                &self.account.id,
+                self.status,
+                self.limit.unwrap_or(100).into(),
+            )
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        let delivery_ids = deliveries.iter().map(|d| d.id.clone()).collect::<Vec<_>>();
+        let mut attempts = repo
+            .find_attempts(&delivery_ids)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        let deliveries = deliveries
+            .into_iter()
+            .map(|delivery| {
+                let (delivery_attempts, others) = attempts
+                    .drain(..)
+                    .partition(|attempt| attempt.delivery_id == delivery.id);
+                attempts = others;
+                (delivery, delivery_attempts)
+            })
+            .collect();
+
+        Ok(UseCaseResponse {
+            endpoint,
+            deliveries,
+        })
+    }
+}
@@ -0,0 +1,29 @@
+pub mod get_webhook_deliveries;
+pub mod process_webhook_deliveries;
+pub mod redeliver_webhook;
+
+use axum::{
+    middleware::from_fn,
+    routing::{get, post},
+};
+use get_webhook_deliveries::get_webhook_deliveries_controller;
+use redeliver_webhook::redeliver_webhook_controller;
+use utoipa_axum::router::OpenApiRouter;
+
+use crate::shared::auth::protect_admin_route_middleware;
+
+/// Configure the routes for the webhook module
+pub fn configure_routes() -> OpenApiRouter {
+    OpenApiRouter::new()
+        // Get the webhook deliveries of the account
+        .route(
+            "/account/webhook/deliveries",
+            get(get_webhook_deliveries_controller),
+        )
+        // Redeliver a webhook of the account (e.g. a failed one)
+        .route(
+            "/account/webhook/deliveries/{delivery_id}/redeliver",
+            post(redeliver_webhook_controller),
+        )
+        .route_layer(from_fn(protect_admin_route_middleware))
+}
@@ -0,0 +1,246 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::LazyLock,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use futures::future::join_all;
+use nittei_domain::{
+    AccountWebhookSettings,
+    ID,
+    WEBHOOK_SIGNATURE_HEADER,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+use reqwest::{Client, Response, header::CONTENT_TYPE};
+use tracing::{error, warn};
+
+use crate::shared::usecase::UseCase;
+
+/// Client used for delivering the webhooks
+static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(APP_CONFIG.webhook.timeout_secs))
+        .build()
+        .unwrap_or_default()
+});
+
+/// Use case for retrying the due webhook deliveries
+/// Failed attempts are retried (with an exponential backoff) until the retry horizon of the delivery,
+/// after which the delivery is failed
+#[derive(Debug)]
+pub struct ProcessWebhookDeliveriesUseCase;
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    StorageError,
+}
+
+#[async_trait::async_trait]
+impl UseCase for ProcessWebhookDeliveriesUseCase {
+    /// Number of deliveries attempted
+    type Response = usize;
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "ProcessWebhookDeliveries";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let config = &APP_CONFIG.webhook;
+        let now = ctx.sys.get_timestamp();
+
+        // The deliveries are leased, so that other instances don't attempt them at the same time
+        let deliveries = ctx
+            .repos
+            .webhook_deliveries
+            .claim_due(
+                now,
+                now + TimeDelta::seconds(config.lease_duration_secs),
+                config.batch_size,
+            )
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        if deliveries.is_empty() {
+            return Ok(0);
+        }
+
+        let account_ids = deliveries
+            .iter()
+            .map(|d| d.account_id.clone())
+            .collect::<HashSet<ID>>()
+            .into_iter()
+            .collect::<Vec<ID>>();
+        let webhooks = ctx
+            .repos
+            .accounts
+            .find_many(&account_ids)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .into_iter()
+            .filter_map(|account| account.settings.webhook.map(|w| (account.id, w)))
+            .collect::<HashMap<ID, AccountWebhookSettings>>();
+
+        let count = deliveries.len();
+        join_all(deliveries.iter().map(|delivery| async {
+            match webhooks.get(&delivery.account_id) {
+                Some(webhook) => attempt_delivery(delivery, webhook, ctx).await,
+                // The account removed its webhook in the meantime
+                None => {
+                    if let Err(e) = ctx.repos.webhook_deliveries.fail(&delivery.id).await {
+                        error!(
+                            delivery_id = %delivery.id,
+                            "[process_webhook_deliveries] Unable to fail the webhook delivery: {:?}",
+                            e
+                        );
+                    }
+                }
+            }
+        }))
+        .await;
+
+        Ok(count)
+    }
+}
+
+/// Record a new delivery to the webhook of the account, and attempt it right away
+/// If the attempt fails, the delivery is retried by the webhook worker
+///
+/// Recording a delivery that is already recorded (same ID) is ignored, but it is still attempted
+pub async fn deliver_webhook(
+    mut delivery: WebhookDelivery,
+    webhook: &AccountWebhookSettings,
+    ctx: &NitteiContext,
+) -> anyhow::Result<()> {
+    // Leased, so that the worker doesn't attempt it at the same time
+    delivery.claim(
+        ctx.sys.get_timestamp() + TimeDelta::seconds(APP_CONFIG.webhook.lease_duration_secs),
+    );
+    ctx.repos
+        .webhook_deliveries
+        .insert(std::slice::from_ref(&delivery))
+        .await?;
+
+    attempt_delivery(&delivery, webhook, ctx).await;
+
+    Ok(())
+}
+
+/// Send the payload of a (claimed) delivery to the webhook, and record the attempt
+/// If the attempt can't be recorded, the delivery is attempted again once its lease expires
+async fn attempt_delivery(
+    delivery: &WebhookDelivery,
+    webhook: &AccountWebhookSettings,
+    ctx: &NitteiContext,
+) {
+    let config = &APP_CONFIG.webhook;
+
+    let attempted_at = ctx.sys.get_timestamp();
+    let started = Instant::now();
+    let res = send(webhook, &delivery.payload, attempted_at).await;
+    let latency_millis = i64::try_from(started.elapsed().as_millis()).unwrap_or(i64::MAX);
+
+    let (status_code, response) = match res {
+        Ok((status_code, body)) => (Some(status_code), body),
+        Err(e) => (None, format!("{e:#}")),
+    };
+    let attempt = WebhookDeliveryAttempt {
+        id: Default::default(),
+        delivery_id: delivery.id.clone(),
+        url: webhook.url.clone(),
+        attempted_at,
+        status_code,
+        latency_millis,
+        response_excerpt: WebhookDeliveryAttempt::excerpt(&response),
+    };
+
+    let (status, retry_at) = if attempt.is_success() {
+        (WebhookDeliveryStatus::Succeeded, None)
+    } else {
+        match delivery.next_retry_at(
+            TimeDelta::seconds(config.retry_base_delay_secs),
+            TimeDelta::seconds(config.retry_max_delay_secs),
+            attempted_at,
+        ) {
+            Some(retry_at) => (WebhookDeliveryStatus::Pending, Some(retry_at)),
+            None => {
+                warn!(
+                    delivery_id = %delivery.id,
+                    "[process_webhook_deliveries] Webhook delivery failed after {} attempts",
+                    delivery.attempts
+                );
+                (WebhookDeliveryStatus::Failed, None)
+            }
+        }
+    };
+
+    match ctx
+        .repos
+        .webhook_deliveries
+        .record_attempt(
+            &delivery.account_id,
+            &attempt,
+            status,
+            retry_at,
+            config.failing_threshold,
+        )
+        .await
+    {
+        Ok(endpoint_status) if endpoint_status.consecutive_failures == config.failing_threshold => {
+            warn!(
+                account_id = %delivery.account_id,
+                "[process_webhook_deliveries] Webhook endpoint marked as failing after {} consecutive failed attempts",
+                endpoint_status.consecutive_failures
+            );
+        }
+        Ok(_) => (),
+        Err(e) => {
+            error!(
+                delivery_id = %delivery.id,
+                "[process_webhook_deliveries] Unable to record the attempt of the webhook delivery: {:?}",
+                e
+            );
+        }
+    }
+}
+
+/// Send the payload to the webhook, signed with its valid keys (see `WEBHOOK_SIGNATURE_HEADER`)
+/// Returns the status code and the beginning of the body of the response
+async fn send(
+    webhook: &AccountWebhookSettings,
+    payload: &serde_json::Value,
+    now: DateTime<Utc>,
+) -> anyhow::Result<(u16, String)> {
+    let body = serde_json::to_vec(payload)?;
+
+    let response = CLIENT
+        .post(&webhook.url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_SIGNATURE_HEADER, webhook.sign_payload(&body, now))
+        .body(body)
+        .send()
+        .await?;
+    let status_code = response.status().as_u16();
+    let body = read_excerpt(response).await;
+
+    Ok((status_code, body))
+}
+
+/// Read the beginning of the body of the response, only what is needed for its excerpt
+/// (see `WebhookDeliveryAttempt::excerpt`), so that a large response isn't loaded in memory
+async fn read_excerpt(mut response: Response) -> String {
+    // A character is at most 4 bytes long
+    let max_len = WebhookDeliveryAttempt::RESPONSE_EXCERPT_LEN * 4;
+    let mut body = Vec::new();
+    while body.len() < max_len {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            Ok(None) | Err(_) => break,
+        }
+    }
+    body.truncate(max_len);
+    String::from_utf8_lossy(&body).into_owned()
+}
@@ -0,0 +1,113 @@
+use axum::{Extension, Json, extract::Path};
+use chrono::TimeDelta;
+use nittei_api_structs::redeliver_webhook::*;
+use nittei_domain::{Account, ID, WebhookDelivery, WebhookDeliveryAttempt};
+use nittei_infra::NitteiContext;
+use nittei_utils::config::APP_CONFIG;
+
+use crate::{
+    error::NitteiError,
+    shared::usecase::{UseCase, execute},
+};
+
+#[utoipa::path(
+    post,
+    tag = "Account",
+    path = "/api/v1/account/webhook/deliveries/{delivery_id}/redeliver",
+    summary = "Redeliver a webhook, e.g. a failed one (admin only)",
+    params(
+        ("delivery_id" = ID, Path, description = "The id of the webhook delivery to redeliver"),
+    ),
+    security(
+        ("api_key" = [])
+    ),
+    responses(
+        (status = 200, body = APIResponse)
+    )
+)]
+pub async fn redeliver_webhook_controller(
+    Extension(account): Extension<Account>,
+    path_params: Path<PathParams>,
+    Extension(ctx): Extension<NitteiContext>,
+) -> Result<Json<APIResponse>, NitteiError> {
+    let usecase = RedeliverWebhookUseCase {
+        account_id: account.id,
+        delivery_id: path_params.delivery_id.clone(),
+    };
+
+    execute(usecase, &ctx)
+        .await
+        .map(|(delivery, attempts)| Json(APIResponse::new(delivery, attempts)))
+        .map_err(NitteiError::from)
+}
+
+/// Use case for redelivering a webhook
+/// The delivery is pending again (with a new retry horizon), and is attempted by the webhook worker
+/// as soon as possible, with the current keys of the webhook
+#[derive(Debug)]
+pub struct RedeliverWebhookUseCase {
+    pub account_id: ID,
+    pub delivery_id: ID,
+}
+
+#[derive(Debug)]
+pub enum UseCaseError {
+    NotFound(ID),
+    StorageError,
+}
+
+impl From<UseCaseError> for NitteiError {
+    fn from(e: UseCaseError) -> Self {
+        match e {
+            UseCaseError::NotFound(delivery_id) => Self::NotFound(format!(
+                "The webhook delivery with id: {delivery_id}, was not found."
+            )),
+            UseCaseError::StorageError => Self::InternalError,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl UseCase for RedeliverWebhookUseCase {
+    type Response = (WebhookDelivery, Vec<WebhookDeliveryAttempt>);
+
+    type Error = UseCaseError;
+
+    const NAME: &'static str = "RedeliverWebhook";
+
+    async fn execute(&mut self, ctx: &NitteiContext) -> Result<Self::Response, Self::Error> {
+        let repo = &ctx.repos.webhook_deliveries;
+
+        match repo.find(&self.delivery_id).await {
+            Ok(Some(delivery)) if delivery.account_id == self.account_id => (),
+            Ok(_) => return Err(UseCaseError::NotFound(self.delivery_id.clone())),
+            Err(_) => return Err(UseCaseError::StorageError),
+        };
+
+        let now = ctx.sys.get_timestamp();
+        let redelivered = repo
+            .redeliver(
+                &self.delivery_id,
+                now,
+                now + TimeDelta::seconds(APP_CONFIG.webhook.retry_horizon_secs),
+            )
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+        if !redelivered {
+            // Removed in the meantime
+            return Err(UseCaseError::NotFound(self.delivery_id.clone()));
+        }
+
+        let delivery = repo
+            .find(&self.delivery_id)
+            .await
+            .map_err(|_| UseCaseError::StorageError)?
+            .ok_or_else(|| UseCaseError::NotFound(self.delivery_id.clone()))?;
+        let attempts = repo
+            .find_attempts(std::slice::from_ref(&self.delivery_id))
+            .await
+            .map_err(|_| UseCaseError::StorageError)?;
+
+        Ok((delivery, attempts))
+    }
+}
@@ -1,4 +1,4 @@
-use nittei_domain::Account;
+use nittei_domain::{Account, WebhookEventType};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
@@ -80,6 +80,49 @@ pub mod set_account_pub_key {
     pub type APIResponse = AccountResponse;
 }
 
+pub mod set_account_search_language {
+    use nittei_domain::SearchLanguage;
+
+    use super::*;
+
+    /// Request body for setting the language of the texts of the events of an account
+    #[derive(Debug, Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct SetAccountSearchLanguageRequestBody {
+        /// Language used by the text search of the events
+        pub search_language: SearchLanguage,
+    }
+
+    pub type APIResponse = AccountResponse;
+}
+
+pub mod set_account_rate_limits {
+    use nittei_domain::RateLimit;
+
+    use super::*;
+
+    /// Request body for setting the limits of the requests of an account
+    #[derive(Debug, Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct SetAccountRateLimitsRequestBody {
+        /// Limit of the requests of the account (with its API key, and separately on its public routes)
+        /// The default one is used if not set
+        #[validate(nested)]
+        #[ts(optional)]
+        pub account: Option<RateLimit>,
+
+        /// Limit of the requests of each user of the account (with a JWT)
+        /// The default one is used if not set
+        #[validate(nested)]
+        #[ts(optional)]
+        pub user: Option<RateLimit>,
+    }
+
+    pub type APIResponse = AccountResponse;
+}
+
 pub mod set_account_webhook {
 
     use super::*;
@@ -92,6 +135,31 @@ pub mod set_account_webhook {
         /// Webhook URL
         #[validate(url)]
         pub webhook_url: String,
+
+        /// Optional changes of events to send to the webhook (opt-in)
+        /// If not provided, the current ones are kept
+        #[serde(default)]
+        #[ts(optional)]
+        pub event_types: Option<Vec<WebhookEventType>>,
+    }
+
+    pub type APIResponse = AccountResponse;
+}
+
+pub mod rotate_account_webhook_key {
+    use super::*;
+
+    /// Request body for rotating the key of the webhook of an account
+    #[derive(Debug, Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct RotateAccountWebhookKeyRequestBody {
+        /// Optional number of seconds during which the current key stays valid (max 7 days)
+        /// Default is 24 hours
+        #[serde(default)]
+        #[ts(optional)]
+        #[validate(range(max = 604800))]
+        pub grace_period_secs: Option<u32>,
     }
 
     pub type APIResponse = AccountResponse;
@@ -149,13 +217,20 @@ pub mod remove_account_integration {
 
 /// Request body for searching events for a whole account (across all users)
 pub mod account_search_events {
-    use nittei_domain::{CalendarEventSort, DateTimeQuery, IDQuery, RecurrenceQuery, StringQuery};
+    use nittei_domain::{
+        AttendeeQuery,
+        CalendarEventSort,
+        DateTimeQuery,
+        IDQuery,
+        RecurrenceQuery,
+        StringQuery,
+    };
 
     use super::*;
-    use crate::dtos::CalendarEventDTO;
+    use crate::dtos::{CalendarEventDTO, CalendarEventHighlightDTO};
 
     /// Request body for searching events for a whole account (across all users)
-    #[derive(Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[derive(Default, Deserialize, Serialize, Validate, TS, ToSchema)]
     #[serde(rename_all = "camelCase", deny_unknown_fields)]
     #[ts(export, rename_all = "camelCase")]
     pub struct AccountSearchEventsRequestBody {
@@ -170,10 +245,15 @@ pub mod account_search_events {
         /// Defaults to 200
         #[ts(optional)]
         pub limit: Option<u16>,
+
+        /// Optional cursor of the page to get (the `nextCursor` of the previous page)
+        /// It can only be used with the same sort as the previous page
+        #[ts(optional)]
+        pub cursor: Option<String>,
     }
 
     /// Request body for searching events for a whole account (across all users)
-    #[derive(Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[derive(Default, Deserialize, Serialize, Validate, TS, ToSchema)]
     #[serde(rename_all = "camelCase", deny_unknown_fields)]
     #[ts(export, rename_all = "camelCase")]
     pub struct AccountSearchEventsRequestBodyFilter {
@@ -222,7 +302,7 @@ pub mod account_search_events {
         pub original_start_time: Option<DateTimeQuery>,
 
         /// Optional filter on the recurrence
-        /// This allows to filter on the existence or not of a recurrence, or the existence of a recurrence at a specific date
+        /// This allows to filter on the existence or not of a recurrence, the existence of a recurrence at a specific date, or its options
         #[ts(optional)]
         pub recurrence: Option<RecurrenceQuery>,
 
@@ -237,20 +317,48 @@ pub mod account_search_events {
         /// Optional query on updated at - e.g. "lower than or equal", or "great than or equal" (UTC)
         #[ts(optional)]
         pub updated_at: Option<DateTimeQuery>,
+
+        /// Optional query on the attendees of the event
+        /// Events are returned if at least one attendee matches all the provided conditions
+        #[ts(optional)]
+        pub attendee: Option<AttendeeQuery>,
+
+        /// Optional text to search in the title, description and location, in the web search syntax
+        /// (e.g. `"team meeting" or standup -cancelled`), using the search language of the account
+        #[validate(length(min = 1))]
+        #[ts(optional)]
+        pub text: Option<String>,
     }
 
     /// API response for getting events by calendars
-    #[derive(Serialize, TS, ToSchema)]
+    #[derive(Deserialize, Serialize, TS, ToSchema)]
     #[serde(rename_all = "camelCase")]
     #[ts(export)]
     pub struct SearchEventsAPIResponse {
         /// List of calendar events retrieved
         pub events: Vec<CalendarEventDTO>,
+
+        /// Fragments of the texts matching the text searched, for the events with a matching text
+        /// Only returned when searching on a text
+        #[ts(optional)]
+        pub highlights: Option<Vec<CalendarEventHighlightDTO>>,
+
+        /// Cursor of the next page, if there are more events than the limit
+        #[ts(optional)]
+        pub next_cursor: Option<String>,
     }
 
     impl SearchEventsAPIResponse {
-        pub fn new(events: Vec<CalendarEventDTO>) -> Self {
-            Self { events }
+        pub fn new(
+            events: Vec<CalendarEventDTO>,
+            highlights: Option<Vec<CalendarEventHighlightDTO>>,
+            next_cursor: Option<String>,
+        ) -> Self {
+            Self {
+                events,
+                highlights,
+                next_cursor,
+            }
         }
     }
 }
@@ -1,4 +1,14 @@
-use nittei_domain::{Account, AccountSettings, AccountWebhookSettings, ID, PEMKey};
+use chrono::{DateTime, Utc};
+use nittei_domain::{
+    Account,
+    AccountRateLimits,
+    AccountSettings,
+    AccountWebhookSettings,
+    ID,
+    PEMKey,
+    SearchLanguage,
+    WebhookEventType,
+};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
@@ -34,6 +44,11 @@ impl AccountDTO {
 pub struct AccountSettingsDTO {
     /// Optional webhook settings
     pub webhook: Option<AccountWebhookSettingsDTO>,
+    /// Language of the texts of the events, used by the text search
+    pub search_language: SearchLanguage,
+    /// Limits of the requests of the account and of its users, the default ones are used if not set
+    /// The limits are applied by each instance of the application, not across all the instances
+    pub rate_limits: AccountRateLimits,
 }
 
 impl AccountSettingsDTO {
@@ -45,6 +60,8 @@ impl AccountSettingsDTO {
 
         Self {
             webhook: webhook_settings,
+            search_language: settings.search_language,
+            rate_limits: settings.rate_limits,
         }
     }
 }
@@ -58,6 +75,12 @@ pub struct AccountWebhookSettingsDTO {
     pub url: String,
     /// Webhook key
     pub key: String,
+    /// Changes of events sent to the webhook
+    pub event_types: Vec<WebhookEventType>,
+    /// Expiration of the key replaced by the last rotation
+    /// Until then, the webhooks are also signed with it
+    #[ts(optional)]
+    pub previous_key_expires_at: Option<DateTime<Utc>>,
 }
 
 impl AccountWebhookSettingsDTO {
@@ -65,6 +88,8 @@ impl AccountWebhookSettingsDTO {
         Self {
             url: settings.url.clone(),
             key: settings.key.clone(),
+            event_types: settings.event_types.clone(),
+            previous_key_expires_at: settings.previous_key_expires_at,
         }
     }
 }
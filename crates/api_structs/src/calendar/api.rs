@@ -1,11 +1,11 @@
-use nittei_domain::{Calendar, EventInstance, ID, Tz, Weekday};
+use nittei_domain::{Calendar, CalendarFeed, EventInstance, ID, Tz, Weekday};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
 use validator::Validate;
 
 use crate::{
-    dtos::{CalendarDTO, EventWithInstancesDTO},
+    dtos::{CalendarDTO, CalendarFeedDTO, EventWithInstancesDTO},
     helpers::deserialize_uuids_list::deserialize_stringified_uuids_list,
 };
 
@@ -194,6 +194,7 @@ pub mod get_calendar_events_ical {
     }
 
     #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
     pub struct QueryParams {
         /// Optional start time for the query (UTC)
         ///
@@ -209,6 +210,182 @@ pub mod get_calendar_events_ical {
     pub type APIResponse = String;
 }
 
+/// Calendar feed object
+#[derive(Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CalendarFeedResponse {
+    /// Calendar feed
+    pub feed: CalendarFeedDTO,
+}
+
+impl CalendarFeedResponse {
+    pub fn new(feed: CalendarFeed) -> Self {
+        Self {
+            feed: CalendarFeedDTO::new(feed),
+        }
+    }
+}
+
+pub mod create_calendar_feed {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    pub struct PathParams {
+        pub calendar_id: ID,
+    }
+
+    /// Request body for creating a public iCalendar feed of a calendar
+    #[derive(Debug, Default, Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct CreateCalendarFeedRequestBody {
+        /// If true, the events only show the user is busy (titles replaced by "Busy", no descriptions and locations)
+        /// Default is false
+        #[ts(optional)]
+        pub mask_events: Option<bool>,
+    }
+
+    pub type APIResponse = CalendarFeedResponse;
+}
+
+pub mod get_calendar_feeds {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    pub struct PathParams {
+        pub calendar_id: ID,
+    }
+
+    /// API response for listing the public iCalendar feeds of a calendar
+    #[derive(Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct GetCalendarFeedsAPIResponse {
+        /// Feeds of the calendar
+        pub feeds: Vec<CalendarFeedDTO>,
+    }
+
+    impl GetCalendarFeedsAPIResponse {
+        pub fn new(feeds: Vec<CalendarFeed>) -> Self {
+            Self {
+                feeds: feeds.into_iter().map(CalendarFeedDTO::new).collect(),
+            }
+        }
+    }
+
+    pub type APIResponse = GetCalendarFeedsAPIResponse;
+}
+
+pub mod delete_calendar_feed {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    pub struct PathParams {
+        pub calendar_id: ID,
+        pub feed_id: ID,
+    }
+
+    pub type APIResponse = CalendarFeedResponse;
+}
+
+pub mod get_calendar_feed_ical {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    pub struct PathParams {
+        /// Token of the feed, followed by ".ics"
+        pub token: String,
+    }
+
+    pub type APIResponse = String;
+}
+
+pub mod import_calendar_ical {
+    use chrono::{DateTime, Utc};
+
+    use super::*;
+    use crate::dtos::CalendarEventDTO;
+
+    #[derive(Debug, Deserialize)]
+    pub struct PathParams {
+        pub user_id: ID,
+        pub calendar_id: ID,
+    }
+
+    /// Request body for importing iCalendar content into a calendar
+    #[derive(Debug, Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct ImportCalendarIcalRequestBody {
+        /// iCalendar content (.ics)
+        #[validate(length(min = 1))]
+        pub ical: String,
+
+        /// If true, nothing is written and the response only contains the changes which would be made
+        /// Default is false
+        #[ts(optional)]
+        pub dry_run: Option<bool>,
+    }
+
+    /// Change made (or which would be made) to an event by the import
+    #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub enum IcalImportAction {
+        /// The event didn't exist, it is created
+        Created,
+        /// The event was previously imported, and has changed
+        Updated,
+        /// The event was previously imported, and hasn't changed
+        Unchanged,
+    }
+
+    /// Event of the iCalendar content, with the change made to it
+    #[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct IcalImportChange {
+        pub action: IcalImportAction,
+        /// UID of the event in the iCalendar content (stored as the `externalId` of the event)
+        pub uid: String,
+        /// Original start time of the occurrence (RECURRENCE-ID), if this is a modified occurrence of a recurring event
+        #[ts(type = "Date", optional)]
+        pub recurrence_id: Option<DateTime<Utc>>,
+        /// Fields changed by the import (only for updated events)
+        pub changed_fields: Vec<String>,
+        /// Event, as it is after the import
+        pub event: CalendarEventDTO,
+    }
+
+    /// Event of the iCalendar content which can't be imported
+    #[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct IcalImportSkippedEvent {
+        /// UID of the event, if it has one
+        #[ts(optional)]
+        pub uid: Option<String>,
+        /// Why the event is skipped
+        pub reason: String,
+    }
+
+    /// API response for importing iCalendar content into a calendar
+    #[derive(Debug, Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct ImportCalendarIcalAPIResponse {
+        /// Whether this was a dry run (nothing has been written)
+        pub dry_run: bool,
+        /// Events of the iCalendar content, with the change made to each of them
+        pub changes: Vec<IcalImportChange>,
+        /// Events of the iCalendar content which can't be imported
+        pub skipped: Vec<IcalImportSkippedEvent>,
+    }
+
+    pub type APIResponse = ImportCalendarIcalAPIResponse;
+}
+
 pub mod get_calendar_events {
     use chrono::{DateTime, Utc};
     use nittei_domain::EventWithInstances;
@@ -277,6 +454,9 @@ pub mod get_calendars_by_meta {
         #[serde(default)]
         pub skip: Option<usize>,
         pub limit: Option<usize>,
+        /// Cursor of the page to get (the `nextCursor` of the previous page)
+        #[serde(default)]
+        pub cursor: Option<String>,
     }
 
     #[derive(Deserialize, Serialize, TS, ToSchema)]
@@ -284,12 +464,16 @@ pub mod get_calendars_by_meta {
     #[ts(export)]
     pub struct GetCalendarsByMetaAPIResponse {
         pub calendars: Vec<CalendarDTO>,
+        /// Cursor of the next page, if there are more calendars than the limit
+        #[ts(optional)]
+        pub next_cursor: Option<String>,
     }
 
     impl GetCalendarsByMetaAPIResponse {
-        pub fn new(calendars: Vec<Calendar>) -> Self {
+        pub fn new(calendars: Vec<Calendar>, next_cursor: Option<String>) -> Self {
             Self {
                 calendars: calendars.into_iter().map(CalendarDTO::new).collect(),
+                next_cursor,
             }
         }
     }
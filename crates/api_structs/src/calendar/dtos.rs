@@ -1,4 +1,5 @@
-use nittei_domain::{Calendar, CalendarSettings, ID, Weekday};
+use chrono::{DateTime, Utc};
+use nittei_domain::{Calendar, CalendarFeed, CalendarSettings, ID, Weekday};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
@@ -29,6 +30,12 @@ pub struct CalendarDTO {
     /// Metadata (e.g. {"key": "value"})
     #[ts(optional)]
     pub metadata: Option<serde_json::Value>,
+
+    /// Version of the calendar, incremented on each update
+    /// Can be sent back in the `If-Match` header to only update the calendar if it hasn't changed in the meantime
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub version: i64,
 }
 
 /// Calendar settings
@@ -52,6 +59,7 @@ impl CalendarDTO {
             key: calendar.key,
             settings: CalendarSettingsDTO::new(&calendar.settings),
             metadata: calendar.metadata,
+            version: calendar.version,
         }
     }
 }
@@ -64,3 +72,45 @@ impl CalendarSettingsDTO {
         }
     }
 }
+
+/// Public iCalendar feed of a calendar
+#[derive(Debug, Deserialize, Serialize, Clone, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CalendarFeedDTO {
+    /// UUID of the feed
+    pub id: ID,
+
+    /// UUID of the calendar of the feed
+    pub calendar_id: ID,
+
+    /// UUID of the user that owns the calendar
+    pub user_id: ID,
+
+    /// Secret token of the feed, anyone knowing it can read the feed
+    pub token: String,
+
+    /// Path of the feed (e.g. "/api/v1/ical/{token}.ics"), to subscribe to from calendar applications
+    pub path: String,
+
+    /// If true, the events only show the user is busy (titles replaced by "Busy", no descriptions and locations)
+    pub mask_events: bool,
+
+    /// Date of creation of the feed
+    #[ts(type = "Date")]
+    pub created: DateTime<Utc>,
+}
+
+impl CalendarFeedDTO {
+    pub fn new(feed: CalendarFeed) -> Self {
+        Self {
+            id: feed.id,
+            calendar_id: feed.calendar_id,
+            user_id: feed.user_id,
+            path: format!("/api/v1/ical/{}.ics", feed.token),
+            token: feed.token,
+            mask_events: feed.mask_events,
+            created: feed.created,
+        }
+    }
+}
@@ -1,10 +1,18 @@
-use nittei_domain::{CalendarEvent, CalendarEventReminder, EventInstance, ID, RRuleOptions};
+use nittei_domain::{
+    CalendarEvent,
+    CalendarEventAttendee,
+    CalendarEventReminder,
+    EventInstance,
+    ID,
+    RRuleOptions,
+    Tz,
+};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
 use validator::{Validate, ValidationError};
 
-use crate::dtos::CalendarEventDTO;
+use crate::dtos::{CalendarEventDTO, CalendarEventHighlightDTO};
 
 /// Calendar event response object
 #[derive(Deserialize, Serialize, TS, ToSchema)]
@@ -133,6 +141,14 @@ pub mod create_event {
         #[ts(optional)]
         pub recurrence: Option<RRuleOptions>,
 
+        /// Optional timezone of the event (e.g. "Europe/Paris")
+        /// The recurrence is expanded in this timezone, so that the occurrences stay at the same local time across DST changes
+        /// Default is None (the timezone of the calendar is used)
+        #[serde(default)]
+        #[ts(type = "string", optional)]
+        #[schema(value_type = Option<String>)]
+        pub timezone: Option<Tz>,
+
         /// Optional list of exclusion dates for the recurrence rule
         #[serde(default)]
         #[ts(type = "Array<Date>")]
@@ -158,12 +174,25 @@ pub mod create_event {
         #[ts(as = "Option<_>", optional)]
         pub reminders: Vec<CalendarEventReminder>,
 
+        /// Optional list of attendees
+        /// An attendee is either an internal user (`userId`) of the same account, or an external person (`email`)
+        #[serde(default)]
+        #[ts(as = "Option<_>", optional)]
+        pub attendees: Vec<CalendarEventAttendee>,
+
         /// Optional service UUID
         /// This is automatically set when the event is created from a service
         #[serde(default)]
         #[ts(optional)]
         pub service_id: Option<ID>,
 
+        /// Optional event group UUID
+        /// This allows to attach the event to a group of events (e.g. a course of multiple sessions)
+        /// The group needs to belong to the same user
+        #[serde(default)]
+        #[ts(optional)]
+        pub group_id: Option<ID>,
+
         /// Optional metadata (e.g. {"key": "value"})
         #[serde(default)]
         #[ts(optional)]
@@ -218,6 +247,9 @@ pub mod create_batch_events {
 }
 
 pub mod delete_event {
+    use chrono::{DateTime, Utc};
+    use nittei_domain::RecurringEventScope;
+
     use super::*;
 
     #[derive(Deserialize)]
@@ -225,6 +257,21 @@ pub mod delete_event {
         pub event_id: ID,
     }
 
+    /// Query parameters for deleting an event
+    #[derive(Serialize, Deserialize, Default, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QueryParams {
+        /// Optional scope of the deletion, only used for recurring events
+        /// Default is all (the whole series is deleted)
+        #[serde(default)]
+        pub scope: Option<RecurringEventScope>,
+
+        /// Start time of the targeted occurrence (UTC)
+        /// Required when the scope is `this` or `thisAndFollowing`
+        #[serde(default)]
+        pub occurrence_start_time: Option<DateTime<Utc>>,
+    }
+
     pub type APIResponse = CalendarEventResponse;
 }
 
@@ -439,12 +486,19 @@ pub mod get_events_for_users_in_time_range {
 }
 
 pub mod search_events {
-    use nittei_domain::{CalendarEventSort, DateTimeQuery, IDQuery, RecurrenceQuery, StringQuery};
+    use nittei_domain::{
+        AttendeeQuery,
+        CalendarEventSort,
+        DateTimeQuery,
+        IDQuery,
+        RecurrenceQuery,
+        StringQuery,
+    };
 
     use super::*;
 
     /// Request body for searching events for one user
-    #[derive(Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[derive(Default, Deserialize, Serialize, Validate, TS, ToSchema)]
     #[serde(rename_all = "camelCase", deny_unknown_fields)]
     #[ts(export)]
     pub struct SearchEventsRequestBody {
@@ -459,11 +513,16 @@ pub mod search_events {
         /// Default is 200
         #[ts(optional)]
         pub limit: Option<u16>,
+
+        /// Optional cursor of the page to get (the `nextCursor` of the previous page)
+        /// It can only be used with the same sort as the previous page
+        #[ts(optional)]
+        pub cursor: Option<String>,
     }
 
     /// Part of the Request body for searching events for a user
     /// This is the filter
-    #[derive(Deserialize, Serialize, Validate, TS, ToSchema)]
+    #[derive(Default, Deserialize, Serialize, Validate, TS, ToSchema)]
     #[serde(rename_all = "camelCase", deny_unknown_fields)]
     #[ts(export, rename_all = "camelCase")]
     pub struct SearchEventsRequestBodyFilter {
@@ -513,7 +572,7 @@ pub mod search_events {
         pub original_start_time: Option<DateTimeQuery>,
 
         /// Optional filter on the recurrence (existence)
-        /// This allows to filter on the existence or not of a recurrence, or the existence of a recurrence at a specific date
+        /// This allows to filter on the existence or not of a recurrence, the existence of a recurrence at a specific date, or its options
         #[ts(optional)]
         pub recurrence: Option<RecurrenceQuery>,
 
@@ -528,20 +587,48 @@ pub mod search_events {
         /// Optional query on updated at - "lower than or equal", or "great than or equal" (UTC)
         #[ts(optional)]
         pub updated_at: Option<DateTimeQuery>,
+
+        /// Optional query on the attendees of the event
+        /// Events are returned if at least one attendee matches all the provided conditions
+        #[ts(optional)]
+        pub attendee: Option<AttendeeQuery>,
+
+        /// Optional text to search in the title, description and location, in the web search syntax
+        /// (e.g. `"team meeting" or standup -cancelled`), using the search language of the account
+        #[validate(length(min = 1))]
+        #[ts(optional)]
+        pub text: Option<String>,
     }
 
     /// API response for searching events for one user
-    #[derive(Serialize, TS, ToSchema)]
+    #[derive(Deserialize, Serialize, TS, ToSchema)]
     #[serde(rename_all = "camelCase")]
     #[ts(export)]
     pub struct SearchEventsAPIResponse {
         /// List of calendar events retrieved
         pub events: Vec<CalendarEventDTO>,
+
+        /// Fragments of the texts matching the text searched, for the events with a matching text
+        /// Only returned when searching on a text
+        #[ts(optional)]
+        pub highlights: Option<Vec<CalendarEventHighlightDTO>>,
+
+        /// Cursor of the next page, if there are more events than the limit
+        #[ts(optional)]
+        pub next_cursor: Option<String>,
     }
 
     impl SearchEventsAPIResponse {
-        pub fn new(events: Vec<CalendarEventDTO>) -> Self {
-            Self { events }
+        pub fn new(
+            events: Vec<CalendarEventDTO>,
+            highlights: Option<Vec<CalendarEventHighlightDTO>>,
+            next_cursor: Option<String>,
+        ) -> Self {
+            Self {
+                events,
+                highlights,
+                next_cursor,
+            }
         }
     }
 }
@@ -557,6 +644,9 @@ pub mod get_events_by_meta {
         #[serde(default)]
         pub skip: Option<usize>,
         pub limit: Option<usize>,
+        /// Cursor of the page to get (the `nextCursor` of the previous page)
+        #[serde(default)]
+        pub cursor: Option<String>,
     }
 
     /// API response for getting events by metadata
@@ -566,12 +656,16 @@ pub mod get_events_by_meta {
     pub struct GetEventsByMetaAPIResponse {
         /// List of calendar events retrieved
         pub events: Vec<CalendarEventDTO>,
+        /// Cursor of the next page, if there are more events than the limit
+        #[ts(optional)]
+        pub next_cursor: Option<String>,
     }
 
     impl GetEventsByMetaAPIResponse {
-        pub fn new(events: Vec<CalendarEvent>) -> Self {
+        pub fn new(events: Vec<CalendarEvent>, next_cursor: Option<String>) -> Self {
             Self {
                 events: events.into_iter().map(CalendarEventDTO::new).collect(),
+                next_cursor,
             }
         }
     }
@@ -579,7 +673,7 @@ pub mod get_events_by_meta {
 
 pub mod update_event {
     use chrono::{DateTime, Utc};
-    use nittei_domain::CalendarEventStatus;
+    use nittei_domain::{CalendarEventStatus, RecurringEventScope};
 
     use super::*;
 
@@ -599,18 +693,39 @@ pub mod update_event {
         Ok(())
     }
 
+    /// Validate that occurrence_start_time is provided when the scope targets an occurrence
+    fn validate_scope_and_occurrence_start_time(
+        body: &UpdateEventRequestBody,
+    ) -> Result<(), ValidationError> {
+        if matches!(
+            body.scope,
+            Some(RecurringEventScope::This | RecurringEventScope::ThisAndFollowing)
+        ) && body.occurrence_start_time.is_none()
+        {
+            return Err(ValidationError::new(
+                "occurrence_start_time must be provided when the scope is this or thisAndFollowing",
+            ));
+        }
+        Ok(())
+    }
+
     /// Request body for updating an event
     #[derive(Deserialize, Serialize, Validate, TS, ToSchema)]
     #[serde(rename_all = "camelCase")]
     #[ts(export)]
     #[validate(schema(function = "validate_recurring_event_id_and_original_start_time"))]
+    #[validate(schema(function = "validate_scope_and_occurrence_start_time"))]
     pub struct UpdateEventRequestBody {
         /// Optional title of the event
         /// Default is None (don't update)
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[validate(length(min = 1))]
         #[ts(optional, as = "_")]
         pub title: Option<Option<String>>,
@@ -620,7 +735,11 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[validate(length(min = 1))]
         #[ts(optional, as = "_")]
         pub description: Option<Option<String>>,
@@ -631,7 +750,11 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[validate(length(min = 1))]
         #[ts(optional, as = "_")]
         pub event_type: Option<Option<String>>,
@@ -642,7 +765,11 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[validate(length(min = 1))]
         #[ts(optional, as = "_")]
         pub external_parent_id: Option<Option<String>>,
@@ -653,7 +780,11 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[validate(length(min = 1))]
         #[ts(optional, as = "_")]
         pub external_id: Option<Option<String>>,
@@ -663,7 +794,11 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[validate(length(min = 1))]
         #[ts(optional, as = "_")]
         pub location: Option<Option<String>>,
@@ -703,19 +838,54 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, object = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[ts(optional, as = "_")]
         pub recurrence: Option<Option<RRuleOptions>>,
 
+        /// Optional new timezone of the event (e.g. "Europe/Paris"), in which the recurrence is expanded
+        /// Default is None (don't update)
+        ///
+        /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+        /// TS: undefined = don't update, null = set to NULL (the timezone of the calendar is used), string = set to value
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        #[ts(type = "string | null", optional)]
+        #[schema(value_type = Option<String>)]
+        pub timezone: Option<Option<Tz>>,
+
         /// Optional service UUID
         /// Default is None (don't update)
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[ts(optional, as = "_")]
         pub service_id: Option<Option<ID>>,
 
+        /// Optional event group UUID
+        /// Default is None (don't update)
+        ///
+        /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+        /// TS: undefined = don't update, null = set to NULL, string = set to value
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        #[ts(optional, as = "_")]
+        pub group_id: Option<Option<ID>>,
+
         /// Optional list of exclusion dates for the recurrence rule
         /// Default is None (don't update)
         ///
@@ -732,7 +902,11 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, string = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[ts(optional, as = "_")]
         pub recurring_event_id: Option<Option<ID>>,
 
@@ -742,7 +916,11 @@ pub mod update_event {
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, date = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[ts(type = "Date | null", optional)]
         pub original_start_time: Option<Option<DateTime<Utc>>>,
 
@@ -755,12 +933,26 @@ pub mod update_event {
         #[ts(optional)]
         pub reminders: Option<Vec<CalendarEventReminder>>,
 
+        /// Optional list of attendees
+        /// Default is None (don't update)
+        /// The response status of the attendees already present is kept, unless another one than needsAction is provided
+        ///
+        /// Rust: None = don't update, Some(value) = set to value
+        /// TS: undefined = don't update, array = set to value
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        pub attendees: Option<Vec<CalendarEventAttendee>>,
+
         /// Optional metadata (e.g. {"key": "value"})
         /// Default is None (don't update)
         ///
         /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
         /// TS: undefined = don't update, null = set to NULL, object = set to value
-        #[serde(default, with = "::serde_with::rust::double_option")]
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
         #[ts(optional, as = "_")]
         pub metadata: Option<Option<serde_json::Value>>,
 
@@ -775,6 +967,44 @@ pub mod update_event {
         #[serde(default)]
         #[ts(type = "Date", optional)]
         pub updated: Option<DateTime<Utc>>,
+
+        /// Optional scope of the update, only used for recurring events
+        /// Default is None (the whole series is updated)
+        ///
+        /// - this: only the occurrence starting at `occurrenceStartTime` is updated (an exception is created)
+        /// - thisAndFollowing: the series is split at `occurrenceStartTime`, and the new series is updated
+        /// - all: the whole series is updated
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        pub scope: Option<RecurringEventScope>,
+
+        /// Start time of the targeted occurrence (UTC)
+        /// Required when the scope is `this` or `thisAndFollowing`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(type = "Date", optional)]
+        pub occurrence_start_time: Option<DateTime<Utc>>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub event_id: ID,
+    }
+
+    pub type APIResponse = CalendarEventResponse;
+}
+
+pub mod update_event_attendee_response {
+    use nittei_domain::CalendarEventAttendeeResponseStatus;
+
+    use super::*;
+
+    /// Request body for responding to an event as an attendee (RSVP)
+    #[derive(Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct UpdateEventAttendeeResponseRequestBody {
+        /// Response of the attendee to the event
+        pub response_status: CalendarEventAttendeeResponseStatus,
     }
 
     #[derive(Deserialize)]
@@ -838,3 +1068,36 @@ pub mod send_event_reminders {
         }
     }
 }
+
+pub mod send_event_webhook {
+    use chrono::{DateTime, Utc};
+    use nittei_domain::WebhookEventType;
+
+    use super::*;
+
+    /// Payload sent to the account webhook when an event is created, updated, cancelled or deleted
+    #[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct EventWebhookPayload {
+        /// Unique ID of the webhook, the same when the delivery is retried
+        pub id: ID,
+        /// Type of the change
+        pub event_type: WebhookEventType,
+        /// When the change happened
+        pub created: DateTime<Utc>,
+        /// Calendar event after the change (before the deletion, for deleted events)
+        pub event: CalendarEventDTO,
+    }
+
+    impl EventWebhookPayload {
+        pub fn new(event_type: WebhookEventType, event: CalendarEvent, now: DateTime<Utc>) -> Self {
+            Self {
+                id: Default::default(),
+                event_type,
+                created: now,
+                event: CalendarEventDTO::new(event),
+            }
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use chrono::{DateTime, Utc};
 use nittei_domain::{
     CalendarEvent,
+    CalendarEventAttendee,
+    CalendarEventHighlight,
     CalendarEventReminder,
     CalendarEventStatus,
     EventInstance,
     ID,
     RRuleOptions,
+    Tz,
 };
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -84,6 +87,12 @@ pub struct CalendarEventDTO {
     #[ts(optional)]
     pub recurrence: Option<RRuleOptions>,
 
+    /// Optional timezone of the event (e.g. "Europe/Paris")
+    /// The recurrence is expanded in this timezone, or in the one of the calendar if not set
+    #[ts(type = "string", optional)]
+    #[schema(value_type = Option<String>)]
+    pub timezone: Option<Tz>,
+
     /// Optional recurring until date
     /// This is the date until which the event will recur
     /// This is calculated by adding the duration to the until date
@@ -106,6 +115,10 @@ pub struct CalendarEventDTO {
     #[ts(type = "Date", optional)]
     pub original_start_time: Option<DateTime<Utc>>,
 
+    /// Optional UUID of the event group this event is part of
+    #[ts(optional)]
+    pub group_id: Option<ID>,
+
     /// UUID of the calendar
     pub calendar_id: ID,
 
@@ -115,9 +128,19 @@ pub struct CalendarEventDTO {
     /// List of reminders
     pub reminders: Vec<CalendarEventReminder>,
 
+    /// List of attendees, with their response status
+    #[serde(default)]
+    pub attendees: Vec<CalendarEventAttendee>,
+
     /// Metadata (e.g. {"key": "value"})
     #[ts(optional)]
     pub metadata: Option<serde_json::Value>,
+
+    /// Version of the event, incremented on each update
+    /// Can be sent back in the `If-Match` header to only update the event if it hasn't changed in the meantime
+    #[serde(default)]
+    #[ts(type = "number")]
+    pub version: i64,
 }
 
 impl CalendarEventDTO {
@@ -139,14 +162,18 @@ impl CalendarEventDTO {
             updated: event.updated,
             created: event.created,
             recurrence: event.recurrence,
+            timezone: event.timezone,
             recurring_until: event.recurring_until,
             exdates: event.exdates,
             recurring_event_id: event.recurring_event_id,
             original_start_time: event.original_start_time,
+            group_id: event.group_id,
             calendar_id: event.calendar_id,
             user_id: event.user_id,
             reminders: event.reminders,
+            attendees: event.attendees,
             metadata: event.metadata,
+            version: event.version,
         }
     }
 }
@@ -170,3 +197,33 @@ impl EventWithInstancesDTO {
         }
     }
 }
+
+/// Fragments of the texts of an event matching the text searched
+/// The matching words are between `<b>` and `</b>`, and the texts are HTML escaped
+#[derive(Serialize, Deserialize, Debug, Clone, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CalendarEventHighlightDTO {
+    /// UUID of the event
+    pub event_id: ID,
+    /// Fragment of the title, if it matches
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// Fragments of the description, if it matches
+    #[ts(optional)]
+    pub description: Option<String>,
+    /// Fragment of the location, if it matches
+    #[ts(optional)]
+    pub location: Option<String>,
+}
+
+impl CalendarEventHighlightDTO {
+    pub fn new(highlight: CalendarEventHighlight) -> Self {
+        Self {
+            event_id: highlight.event_id,
+            title: highlight.title,
+            description: highlight.description,
+            location: highlight.location,
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use nittei_domain::{CalendarEvent, ID, event_group::EventGroup};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::dtos::{CalendarEventDTO, EventGroupDTO};
+
+/// Event group response object
+#[derive(Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct EventGroupResponse {
+    /// Event group retrieved
+    pub event_group: EventGroupDTO,
+}
+
+impl EventGroupResponse {
+    pub fn new(group: EventGroup) -> Self {
+        Self {
+            event_group: EventGroupDTO::new(group),
+        }
+    }
+}
+
+/// Response object containing the events of an event group
+#[derive(Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct EventGroupEventsResponse {
+    /// Calendar events of the group
+    pub events: Vec<CalendarEventDTO>,
+}
+
+impl EventGroupEventsResponse {
+    pub fn new(events: Vec<CalendarEvent>) -> Self {
+        Self {
+            events: events.into_iter().map(CalendarEventDTO::new).collect(),
+        }
+    }
+}
+
+pub mod create_event_group {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    pub struct PathParams {
+        pub user_id: ID,
+    }
+
+    /// Request body for creating an event group
+    #[derive(Serialize, Deserialize, Validate, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct CreateEventGroupRequestBody {
+        /// UUID of the calendar where the group will be created
+        pub calendar_id: ID,
+
+        /// Optional parent ID
+        /// This is useful for external applications that need to link groups of events to a wider data model (e.g. a project, an order, etc.)
+        #[serde(default)]
+        #[validate(length(min = 1))]
+        #[ts(optional)]
+        pub parent_id: Option<String>,
+
+        /// Optional external ID
+        /// This is useful for external applications that need to link a group of events to their own data models
+        /// It needs to be unique per account
+        #[serde(default)]
+        #[validate(length(min = 1))]
+        #[ts(optional)]
+        pub external_id: Option<String>,
+    }
+
+    pub type APIResponse = EventGroupResponse;
+}
+
+pub mod get_event_group {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub group_id: ID,
+    }
+
+    pub type APIResponse = EventGroupResponse;
+}
+
+pub mod get_event_group_by_external_id {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub external_id: String,
+    }
+
+    pub type APIResponse = EventGroupResponse;
+}
+
+pub mod get_event_groups_by_parent_id {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub parent_id: String,
+    }
+
+    /// API response for getting event groups by parent id
+    #[derive(Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct GetEventGroupsByParentIdAPIResponse {
+        /// List of event groups retrieved
+        pub event_groups: Vec<EventGroupDTO>,
+    }
+
+    impl GetEventGroupsByParentIdAPIResponse {
+        pub fn new(groups: Vec<EventGroup>) -> Self {
+            Self {
+                event_groups: groups.into_iter().map(EventGroupDTO::new).collect(),
+            }
+        }
+    }
+}
+
+pub mod update_event_group {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub group_id: ID,
+    }
+
+    /// Request body for updating an event group
+    #[derive(Serialize, Deserialize, Validate, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct UpdateEventGroupRequestBody {
+        /// Optional parent ID
+        /// Default is None (don't update)
+        ///
+        /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+        /// TS: undefined = don't update, null = set to NULL, string = set to value
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        #[validate(length(min = 1))]
+        #[ts(optional, as = "_")]
+        pub parent_id: Option<Option<String>>,
+
+        /// Optional external ID
+        /// Default is None (don't update)
+        ///
+        /// Rust: None = don't update, Some(None) = set to NULL, Some(Some(value)) = set to value
+        /// TS: undefined = don't update, null = set to NULL, string = set to value
+        #[serde(
+            default,
+            with = "::serde_with::rust::double_option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        #[validate(length(min = 1))]
+        #[ts(optional, as = "_")]
+        pub external_id: Option<Option<String>>,
+    }
+
+    pub type APIResponse = EventGroupResponse;
+}
+
+pub mod delete_event_group {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub group_id: ID,
+    }
+
+    pub type APIResponse = EventGroupResponse;
+}
+
+pub mod get_event_group_events {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub group_id: ID,
+    }
+
+    pub type APIResponse = EventGroupEventsResponse;
+}
+
+pub mod cancel_event_group_events {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub group_id: ID,
+    }
+
+    pub type APIResponse = EventGroupEventsResponse;
+}
+
+pub mod delete_event_group_events {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub group_id: ID,
+    }
+
+    pub type APIResponse = EventGroupEventsResponse;
+}
+
+pub mod shift_event_group_events {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub group_id: ID,
+    }
+
+    /// Request body for shifting all the events of an event group
+    #[derive(Serialize, Deserialize, Validate, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct ShiftEventGroupEventsRequestBody {
+        /// Number of minutes to shift the events by
+        /// Can be negative to move the events earlier
+        #[ts(type = "number")]
+        pub minutes: i64,
+    }
+
+    pub type APIResponse = EventGroupEventsResponse;
+}
@@ -0,0 +1,43 @@
+use nittei_domain::{ID, event_group::EventGroup};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// Event group object
+/// A group of calendar events, e.g. a course of multiple sessions
+#[derive(Debug, Deserialize, Serialize, Clone, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct EventGroupDTO {
+    /// UUID of the event group
+    pub id: ID,
+
+    /// UUID of the calendar
+    pub calendar_id: ID,
+
+    /// UUID of the user
+    pub user_id: ID,
+
+    /// Optional parent ID
+    /// This is useful for external applications that need to link groups of events to a wider data model (e.g. a project, an order, etc.)
+    #[ts(optional)]
+    pub parent_id: Option<String>,
+
+    /// Optional external ID
+    /// This is useful for external applications that need to link a group of events to their own data models
+    /// It is unique per account
+    #[ts(optional)]
+    pub external_id: Option<String>,
+}
+
+impl EventGroupDTO {
+    pub fn new(group: EventGroup) -> Self {
+        Self {
+            id: group.id,
+            calendar_id: group.calendar_id,
+            user_id: group.user_id,
+            parent_id: group.parent_id,
+            external_id: group.external_id,
+        }
+    }
+}
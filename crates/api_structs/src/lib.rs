@@ -1,28 +1,37 @@
 mod account;
 mod calendar;
 mod event;
+mod event_group;
 mod helpers;
+mod outbox;
 mod schedule;
 mod service;
 mod status;
 mod user;
+mod webhook;
 
 pub mod dtos {
     pub use crate::{
         account::dtos::*,
         calendar::dtos::*,
         event::dtos::*,
+        event_group::dtos::*,
+        outbox::dtos::*,
         schedule::dtos::*,
         service::dtos::*,
         user::dtos::*,
+        webhook::dtos::*,
     };
 }
 pub use crate::{
     account::api::*,
     calendar::api::*,
     event::api::*,
+    event_group::api::*,
+    outbox::api::*,
     schedule::api::*,
     service::api::*,
     status::api::*,
     user::api::*,
+    webhook::api::*,
 };
@@ -0,0 +1,72 @@
+use nittei_domain::{ID, OutboxMessage, OutboxMessageStatus};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::dtos::OutboxMessageDTO;
+
+/// Outbox message response object
+#[derive(Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct OutboxMessageResponse {
+    /// Outbox message retrieved
+    pub message: OutboxMessageDTO,
+}
+
+impl OutboxMessageResponse {
+    pub fn new(message: OutboxMessage) -> Self {
+        Self {
+            message: OutboxMessageDTO::new(message),
+        }
+    }
+}
+
+pub mod get_outbox_messages {
+    use super::*;
+
+    /// Query parameters for getting the outbox messages of an account
+    #[derive(Serialize, Deserialize, Default, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QueryParams {
+        /// Optional status of the messages to get
+        /// If not provided, the messages with any status are returned
+        #[serde(default)]
+        pub status: Option<OutboxMessageStatus>,
+
+        /// Optional max number of messages to get
+        /// Default is 100
+        #[serde(default)]
+        pub limit: Option<u16>,
+    }
+
+    /// API response for getting the outbox messages of an account
+    #[derive(Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct GetOutboxMessagesAPIResponse {
+        /// Outbox messages, oldest first
+        pub messages: Vec<OutboxMessageDTO>,
+    }
+
+    impl GetOutboxMessagesAPIResponse {
+        pub fn new(messages: Vec<OutboxMessage>) -> Self {
+            Self {
+                messages: messages.into_iter().map(OutboxMessageDTO::new).collect(),
+            }
+        }
+    }
+
+    pub type APIResponse = GetOutboxMessagesAPIResponse;
+}
+
+pub mod replay_outbox_message {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub message_id: ID,
+    }
+
+    pub type APIResponse = OutboxMessageResponse;
+}
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{ID, OutboxMessage, OutboxMessageStatus, OutboxTask};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// Outbox message object
+/// Work of a subscriber (e.g. generating the reminders of an event), processed in the background
+#[derive(Debug, Deserialize, Serialize, Clone, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct OutboxMessageDTO {
+    /// UUID of the message
+    pub id: ID,
+
+    /// Work to do
+    pub task: OutboxTask,
+
+    /// Status of the message
+    pub status: OutboxMessageStatus,
+
+    /// Number of times the message has been picked up for processing
+    pub attempts: i32,
+
+    /// When the message will be processed (again)
+    #[ts(type = "Date")]
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// Error of the last failed attempt
+    #[ts(optional)]
+    pub last_error: Option<String>,
+
+    /// Creation date
+    #[ts(type = "Date")]
+    pub created: DateTime<Utc>,
+}
+
+impl OutboxMessageDTO {
+    pub fn new(message: OutboxMessage) -> Self {
+        Self {
+            id: message.id,
+            task: message.task,
+            status: message.status,
+            attempts: message.attempts,
+            next_attempt_at: message.next_attempt_at,
+            last_error: message.last_error,
+            created: message.created,
+        }
+    }
+}
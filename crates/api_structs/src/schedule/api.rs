@@ -92,18 +92,24 @@ pub mod get_schedules_by_meta {
         #[serde(default)]
         pub skip: Option<usize>,
         pub limit: Option<usize>,
+        /// Cursor of the page to get (the `nextCursor` of the previous page)
+        #[serde(default)]
+        pub cursor: Option<String>,
     }
 
     #[derive(Deserialize, Serialize)]
     #[serde(rename_all = "camelCase")]
     pub struct APIResponse {
         pub schedules: Vec<ScheduleDTO>,
+        /// Cursor of the next page, if there are more schedules than the limit
+        pub next_cursor: Option<String>,
     }
 
     impl APIResponse {
-        pub fn new(schedules: Vec<Schedule>) -> Self {
+        pub fn new(schedules: Vec<Schedule>, next_cursor: Option<String>) -> Self {
             Self {
                 schedules: schedules.into_iter().map(ScheduleDTO::new).collect(),
+                next_cursor,
             }
         }
     }
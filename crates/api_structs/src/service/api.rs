@@ -82,6 +82,24 @@ pub mod add_user_to_service {
         pub closest_booking_time: Option<i64>,
         #[ts(type = "number", optional)]
         pub furthest_booking_time: Option<i64>,
+        /// Relative share of the bookings assigned to the user by the equal distribution round robin
+        /// (1 by default, ignored by the availability round robin)
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub weight: Option<i64>,
+        /// Priority tier of the user in a round robin service, the users of a tier are only assigned
+        /// a booking when no user of a higher tier is available (1 by default, being the highest one)
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub priority: Option<i64>,
+        /// Maximum number of bookings of the user per day, in the timezone of its availability
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub max_bookings_per_day: Option<i64>,
+        /// Maximum number of bookings of the user per week, in the timezone of its availability
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub max_bookings_per_week: Option<i64>,
     }
 
     pub type APIResponse = ServiceResourceDTO;
@@ -162,6 +180,103 @@ pub mod remove_service_event_intend {
     }
 }
 
+pub mod create_service_hold {
+    use chrono::{DateTime, Utc};
+    use nittei_domain::ServiceHold;
+
+    use super::*;
+    use crate::dtos::ServiceHoldDTO;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub service_id: ID,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "CreateServiceHoldRequestBody")]
+    pub struct RequestBody {
+        /// Hosts to hold, they are assigned by the service when not provided
+        #[serde(default)]
+        #[ts(optional)]
+        pub host_user_ids: Option<Vec<ID>>,
+        /// Start of the slot to hold
+        pub timestamp: DateTime<Utc>,
+        #[ts(type = "number")]
+        pub duration: i64,
+        #[ts(type = "number")]
+        pub interval: i64,
+        /// Number of minutes before the hold expires (between 1 and 60)
+        #[ts(type = "number")]
+        pub ttl_minutes: i64,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "CreateServiceHoldAPIResponse")]
+    pub struct APIResponse {
+        pub hold: ServiceHoldDTO,
+    }
+
+    impl APIResponse {
+        pub fn new(hold: ServiceHold) -> Self {
+            Self {
+                hold: ServiceHoldDTO::new(hold),
+            }
+        }
+    }
+}
+
+pub mod confirm_service_hold {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub service_id: ID,
+        pub hold_id: ID,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "ConfirmServiceHoldRequestBody")]
+    pub struct RequestBody {
+        /// Key of the calendar of the hosts where the events are created
+        /// By default, the events are created in the first busy calendar of the host for the service
+        #[serde(default)]
+        #[ts(optional)]
+        pub calendar_key: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub title: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub description: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub event_type: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub location: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub metadata: Option<serde_json::Value>,
+    }
+
+    pub type APIResponse = super::create_service_booking::APIResponse;
+}
+
+pub mod release_service_hold {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub service_id: ID,
+        pub hold_id: ID,
+    }
+
+    pub type APIResponse = String;
+}
+
 pub mod create_service_event_intend {
     use chrono::{DateTime, Utc};
     use nittei_domain::User;
@@ -202,6 +317,149 @@ pub mod create_service_event_intend {
     }
 }
 
+pub mod create_service_booking {
+    use chrono::{DateTime, Utc};
+    use nittei_domain::CalendarEvent;
+
+    use super::*;
+    use crate::dtos::CalendarEventDTO;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub service_id: ID,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "CreateServiceBookingRequestBody")]
+    pub struct RequestBody {
+        /// Hosts to book, they are assigned by the service when not provided
+        #[serde(default)]
+        #[ts(optional)]
+        pub host_user_ids: Option<Vec<ID>>,
+        /// Start of the slot to book
+        pub timestamp: DateTime<Utc>,
+        #[ts(type = "number")]
+        pub duration: i64,
+        #[ts(type = "number")]
+        pub interval: i64,
+        /// Key of the calendar of the hosts where the events are created
+        /// By default, the events are created in the first busy calendar of the host for the service
+        #[serde(default)]
+        #[ts(optional)]
+        pub calendar_key: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub title: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub description: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub event_type: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub location: Option<String>,
+        #[serde(default)]
+        #[ts(optional)]
+        pub metadata: Option<serde_json::Value>,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "CreateServiceBookingAPIResponse")]
+    pub struct APIResponse {
+        /// Events created for the hosts
+        /// For group services, they are only created by the booking filling the slot
+        pub events: Vec<CalendarEventDTO>,
+    }
+
+    impl APIResponse {
+        pub fn new(events: Vec<CalendarEvent>) -> Self {
+            Self {
+                events: events.into_iter().map(CalendarEventDTO::new).collect(),
+            }
+        }
+    }
+}
+
+pub mod cancel_service_booking {
+    use nittei_domain::CalendarEvent;
+
+    use super::*;
+    use crate::dtos::CalendarEventDTO;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub service_id: ID,
+        /// One of the events of the booking
+        pub event_id: ID,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "CancelServiceBookingAPIResponse")]
+    pub struct APIResponse {
+        /// Events of the hosts cancelled
+        pub events: Vec<CalendarEventDTO>,
+    }
+
+    impl APIResponse {
+        pub fn new(events: Vec<CalendarEvent>) -> Self {
+            Self {
+                events: events.into_iter().map(CalendarEventDTO::new).collect(),
+            }
+        }
+    }
+}
+
+pub mod reschedule_service_booking {
+    use chrono::{DateTime, Utc};
+    use nittei_domain::CalendarEvent;
+
+    use super::*;
+    use crate::dtos::CalendarEventDTO;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub service_id: ID,
+        /// One of the events of the booking
+        pub event_id: ID,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "RescheduleServiceBookingRequestBody")]
+    pub struct RequestBody {
+        /// Start of the new slot, the booking keeps its duration
+        pub timestamp: DateTime<Utc>,
+        #[ts(type = "number")]
+        pub interval: i64,
+    }
+
+    #[derive(Deserialize, Serialize, TS)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export, rename = "RescheduleServiceBookingAPIResponse")]
+    pub struct APIResponse {
+        /// Events of the previous slot, cancelled
+        pub cancelled_events: Vec<CalendarEventDTO>,
+        /// Events created for the hosts of the new slot
+        pub events: Vec<CalendarEventDTO>,
+    }
+
+    impl APIResponse {
+        pub fn new(cancelled_events: Vec<CalendarEvent>, events: Vec<CalendarEvent>) -> Self {
+            Self {
+                cancelled_events: cancelled_events
+                    .into_iter()
+                    .map(CalendarEventDTO::new)
+                    .collect(),
+                events: events.into_iter().map(CalendarEventDTO::new).collect(),
+            }
+        }
+    }
+}
+
 pub mod create_service {
     use nittei_domain::ServiceMultiPersonOptions;
 
@@ -216,6 +474,19 @@ pub mod create_service {
         pub metadata: Option<serde_json::Value>,
         #[serde(default)]
         pub multi_person: Option<ServiceMultiPersonOptions>,
+        /// Minimum number of minutes before the start of a booking to cancel it
+        #[serde(default)]
+        #[ts(optional, type = "number")]
+        pub cancellation_cutoff: Option<i64>,
+        /// Minimum number of minutes before the start of a booking to reschedule it
+        #[serde(default)]
+        #[ts(optional, type = "number")]
+        pub reschedule_cutoff: Option<i64>,
+        /// Number of days of upcoming bookings counted by the equal distribution round robin
+        /// (14 by default)
+        #[serde(default)]
+        #[ts(optional, type = "number")]
+        pub distribution_window: Option<i64>,
     }
 
     pub type APIResponse = ServiceResponse;
@@ -235,6 +506,19 @@ pub mod update_service {
         pub metadata: Option<serde_json::Value>,
         #[serde(default)]
         pub multi_person: Option<ServiceMultiPersonOptions>,
+        /// Minimum number of minutes before the start of a booking to cancel it
+        #[serde(default)]
+        #[ts(optional, type = "number")]
+        pub cancellation_cutoff: Option<i64>,
+        /// Minimum number of minutes before the start of a booking to reschedule it
+        #[serde(default)]
+        #[ts(optional, type = "number")]
+        pub reschedule_cutoff: Option<i64>,
+        /// Number of days of upcoming bookings counted by the equal distribution round robin
+        /// (14 by default)
+        #[serde(default)]
+        #[ts(optional, type = "number")]
+        pub distribution_window: Option<i64>,
     }
 
     #[derive(Debug, Deserialize)]
@@ -360,6 +644,9 @@ pub mod get_services_by_meta {
         #[serde(default)]
         pub skip: Option<usize>,
         pub limit: Option<usize>,
+        /// Cursor of the page to get (the `nextCursor` of the previous page)
+        #[serde(default)]
+        pub cursor: Option<String>,
     }
 
     #[derive(Deserialize, Serialize, TS)]
@@ -367,12 +654,16 @@ pub mod get_services_by_meta {
     #[ts(export, rename = "GetServicesByMetaAPIResponse")]
     pub struct APIResponse {
         pub services: Vec<ServiceDTO>,
+        /// Cursor of the next page, if there are more services than the limit
+        #[ts(optional)]
+        pub next_cursor: Option<String>,
     }
 
     impl APIResponse {
-        pub fn new(services: Vec<Service>) -> Self {
+        pub fn new(services: Vec<Service>, next_cursor: Option<String>) -> Self {
             Self {
                 services: services.into_iter().map(ServiceDTO::new).collect(),
+                next_cursor,
             }
         }
     }
@@ -419,6 +710,18 @@ pub mod update_service_user {
         pub buffer_before: Option<i64>,
         pub closest_booking_time: Option<i64>,
         pub furthest_booking_time: Option<i64>,
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub weight: Option<i64>,
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub priority: Option<i64>,
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub max_bookings_per_day: Option<i64>,
+        #[serde(default)]
+        #[ts(type = "number", optional)]
+        pub max_bookings_per_week: Option<i64>,
     }
 
     pub type APIResponse = ServiceResourceDTO;
@@ -1,4 +1,5 @@
-use nittei_domain::{ID, Service, ServiceResource, ServiceWithUsers, TimePlan};
+use chrono::{DateTime, Utc};
+use nittei_domain::{ID, Service, ServiceHold, ServiceResource, ServiceWithUsers, TimePlan};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
@@ -25,6 +26,19 @@ pub struct ServiceResourceDTO {
     /// Optional furthest booking time in minutes
     #[ts(optional)]
     pub furthest_booking_time: Option<i64>,
+    /// Relative share of the bookings assigned to the user by the equal distribution round robin
+    /// (ignored by the availability round robin)
+    #[ts(type = "number")]
+    pub weight: i64,
+    /// Priority tier of the user in a round robin service (1 being the highest one)
+    #[ts(type = "number")]
+    pub priority: i64,
+    /// Optional maximum number of bookings of the user per day, in the timezone of its availability
+    #[ts(type = "number", optional)]
+    pub max_bookings_per_day: Option<i64>,
+    /// Optional maximum number of bookings of the user per week, in the timezone of its availability
+    #[ts(type = "number", optional)]
+    pub max_bookings_per_week: Option<i64>,
 }
 
 impl ServiceResourceDTO {
@@ -37,6 +51,10 @@ impl ServiceResourceDTO {
             buffer_before: resource.buffer_before,
             closest_booking_time: resource.closest_booking_time,
             furthest_booking_time: resource.furthest_booking_time,
+            weight: resource.weight,
+            priority: resource.priority,
+            max_bookings_per_day: resource.max_bookings_per_day,
+            max_bookings_per_week: resource.max_bookings_per_week,
         }
     }
 }
@@ -51,6 +69,15 @@ pub struct ServiceDTO {
     /// Metadata (e.g. {"key": "value"})
     #[ts(optional)]
     pub metadata: Option<serde_json::Value>,
+    /// Minimum number of minutes before the start of a booking to cancel it
+    #[ts(type = "number")]
+    pub cancellation_cutoff: i64,
+    /// Minimum number of minutes before the start of a booking to reschedule it
+    #[ts(type = "number")]
+    pub reschedule_cutoff: i64,
+    /// Number of days of upcoming bookings counted by the equal distribution round robin
+    #[ts(type = "number")]
+    pub distribution_window: i64,
 }
 
 impl ServiceDTO {
@@ -58,6 +85,9 @@ impl ServiceDTO {
         Self {
             id: service.id,
             metadata: service.metadata,
+            cancellation_cutoff: service.cancellation_cutoff,
+            reschedule_cutoff: service.reschedule_cutoff,
+            distribution_window: service.distribution_window,
         }
     }
 }
@@ -70,6 +100,12 @@ pub struct ServiceWithUsersDTO {
     pub users: Vec<ServiceResourceDTO>,
     #[ts(optional)]
     pub metadata: Option<serde_json::Value>,
+    #[ts(type = "number")]
+    pub cancellation_cutoff: i64,
+    #[ts(type = "number")]
+    pub reschedule_cutoff: i64,
+    #[ts(type = "number")]
+    pub distribution_window: i64,
 }
 
 impl ServiceWithUsersDTO {
@@ -82,6 +118,49 @@ impl ServiceWithUsersDTO {
                 .map(ServiceResourceDTO::new)
                 .collect(),
             metadata: service.metadata,
+            cancellation_cutoff: service.cancellation_cutoff,
+            reschedule_cutoff: service.reschedule_cutoff,
+            distribution_window: service.distribution_window,
+        }
+    }
+}
+
+/// Slot of a service held for some of its hosts
+#[derive(Deserialize, Serialize, Debug, Clone, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ServiceHoldDTO {
+    /// UUID of the hold
+    pub id: ID,
+    /// UUID of the service
+    pub service_id: ID,
+    /// UUIDs of the users for whom the slot is held
+    pub host_user_ids: Vec<ID>,
+    /// Start time of the held slot (UTC)
+    #[ts(type = "Date")]
+    pub start_time: DateTime<Utc>,
+    /// End time of the held slot (UTC)
+    #[ts(type = "Date")]
+    pub end_time: DateTime<Utc>,
+    /// Expiration time of the hold (UTC)
+    /// The slot is released if the hold isn't confirmed before it
+    #[ts(type = "Date")]
+    pub expires_at: DateTime<Utc>,
+    /// Created timestamp (UTC)
+    #[ts(type = "Date")]
+    pub created: DateTime<Utc>,
+}
+
+impl ServiceHoldDTO {
+    pub fn new(hold: ServiceHold) -> Self {
+        Self {
+            id: hold.id,
+            service_id: hold.service_id,
+            host_user_ids: hold.host_user_ids,
+            start_time: hold.start_time,
+            end_time: hold.end_time,
+            expires_at: hold.expires_at,
+            created: hold.created,
         }
     }
 }
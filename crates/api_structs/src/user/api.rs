@@ -188,6 +188,9 @@ pub mod get_users_by_meta {
         #[serde(default)]
         pub skip: Option<usize>,
         pub limit: Option<usize>,
+        /// Cursor of the page to get (the `nextCursor` of the previous page)
+        #[serde(default)]
+        pub cursor: Option<String>,
     }
 
     /// API response for getting users by metadata
@@ -197,12 +200,16 @@ pub mod get_users_by_meta {
     pub struct GetUsersByMetaAPIResponse {
         /// List of users matching the metadata query
         pub users: Vec<UserDTO>,
+        /// Cursor of the next page, if there are more users than the limit
+        #[ts(optional)]
+        pub next_cursor: Option<String>,
     }
 
     impl GetUsersByMetaAPIResponse {
-        pub fn new(users: Vec<User>) -> Self {
+        pub fn new(users: Vec<User>, next_cursor: Option<String>) -> Self {
             Self {
                 users: users.into_iter().map(UserDTO::new).collect(),
+                next_cursor,
             }
         }
     }
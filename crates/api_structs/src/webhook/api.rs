@@ -0,0 +1,90 @@
+use nittei_domain::{
+    ID,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+    WebhookEndpointStatus,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::dtos::{WebhookDeliveryDTO, WebhookEndpointStatusDTO};
+
+/// Webhook delivery response object
+#[derive(Deserialize, Serialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookDeliveryResponse {
+    /// Webhook delivery retrieved
+    pub delivery: WebhookDeliveryDTO,
+}
+
+impl WebhookDeliveryResponse {
+    pub fn new(delivery: WebhookDelivery, attempts: Vec<WebhookDeliveryAttempt>) -> Self {
+        Self {
+            delivery: WebhookDeliveryDTO::new(delivery, attempts),
+        }
+    }
+}
+
+pub mod get_webhook_deliveries {
+    use super::*;
+
+    /// Query parameters for getting the webhook deliveries of an account
+    #[derive(Serialize, Deserialize, Default, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct QueryParams {
+        /// Optional status of the deliveries to get
+        /// If not provided, the deliveries with any status are returned
+        #[serde(default)]
+        pub status: Option<WebhookDeliveryStatus>,
+
+        /// Optional max number of deliveries to get
+        /// Default is 100
+        #[serde(default)]
+        pub limit: Option<u16>,
+    }
+
+    /// API response for getting the webhook deliveries of an account
+    #[derive(Deserialize, Serialize, TS, ToSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[ts(export)]
+    pub struct GetWebhookDeliveriesAPIResponse {
+        /// Health of the current webhook endpoint of the account
+        /// Not set if the account has no webhook, or if nothing has been sent to it yet
+        #[ts(optional)]
+        pub endpoint: Option<WebhookEndpointStatusDTO>,
+
+        /// Webhook deliveries, newest first
+        pub deliveries: Vec<WebhookDeliveryDTO>,
+    }
+
+    impl GetWebhookDeliveriesAPIResponse {
+        pub fn new(
+            endpoint: Option<WebhookEndpointStatus>,
+            deliveries: Vec<(WebhookDelivery, Vec<WebhookDeliveryAttempt>)>,
+        ) -> Self {
+            Self {
+                endpoint: endpoint.map(WebhookEndpointStatusDTO::new),
+                deliveries: deliveries
+                    .into_iter()
+                    .map(|(delivery, attempts)| WebhookDeliveryDTO::new(delivery, attempts))
+                    .collect(),
+            }
+        }
+    }
+
+    pub type APIResponse = GetWebhookDeliveriesAPIResponse;
+}
+
+pub mod redeliver_webhook {
+    use super::*;
+
+    #[derive(Deserialize)]
+    pub struct PathParams {
+        pub delivery_id: ID,
+    }
+
+    pub type APIResponse = WebhookDeliveryResponse;
+}
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{
+    ID,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+    WebhookEndpointStatus,
+    WebhookEventType,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// Webhook delivery object
+/// Webhook sent to the account (reminders or change of an event), with its attempts
+#[derive(Debug, Deserialize, Serialize, Clone, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookDeliveryDTO {
+    /// UUID of the delivery
+    pub id: ID,
+
+    /// Type of the change of the event, not set for the reminders
+    #[ts(optional)]
+    pub event_type: Option<WebhookEventType>,
+
+    /// Body of the webhook
+    #[ts(type = "Record<string, unknown>")]
+    pub payload: serde_json::Value,
+
+    /// Status of the delivery
+    pub status: WebhookDeliveryStatus,
+
+    /// When the delivery will be attempted (again), if pending
+    #[ts(type = "Date")]
+    pub next_attempt_at: DateTime<Utc>,
+
+    /// Failed attempts are retried until then
+    #[ts(type = "Date")]
+    pub retry_until: DateTime<Utc>,
+
+    /// Creation date
+    #[ts(type = "Date")]
+    pub created: DateTime<Utc>,
+
+    /// Attempts of the delivery, oldest first
+    pub attempts: Vec<WebhookDeliveryAttemptDTO>,
+}
+
+impl WebhookDeliveryDTO {
+    pub fn new(delivery: WebhookDelivery, attempts: Vec<WebhookDeliveryAttempt>) -> Self {
+        Self {
+            id: delivery.id,
+            event_type: delivery.event_type,
+            payload: delivery.payload,
+            status: delivery.status,
+            next_attempt_at: delivery.next_attempt_at,
+            retry_until: delivery.retry_until,
+            created: delivery.created,
+            attempts: attempts
+                .into_iter()
+                .map(WebhookDeliveryAttemptDTO::new)
+                .collect(),
+        }
+    }
+}
+
+/// Attempt of a webhook delivery
+#[derive(Debug, Deserialize, Serialize, Clone, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookDeliveryAttemptDTO {
+    /// URL of the endpoint at the time of the attempt
+    pub url: String,
+
+    /// When the attempt was made
+    #[ts(type = "Date")]
+    pub attempted_at: DateTime<Utc>,
+
+    /// Status code of the response, not set if there was no response (e.g. timeout)
+    #[ts(optional)]
+    pub status_code: Option<u16>,
+
+    /// Time taken by the endpoint to respond (in milliseconds)
+    pub latency_millis: i64,
+
+    /// Beginning of the body of the response, or the error if there was no response
+    #[ts(optional)]
+    pub response_excerpt: Option<String>,
+}
+
+impl WebhookDeliveryAttemptDTO {
+    pub fn new(attempt: WebhookDeliveryAttempt) -> Self {
+        Self {
+            url: attempt.url,
+            attempted_at: attempt.attempted_at,
+            status_code: attempt.status_code,
+            latency_millis: attempt.latency_millis,
+            response_excerpt: attempt.response_excerpt,
+        }
+    }
+}
+
+/// Health of the webhook endpoint of the account
+#[derive(Debug, Deserialize, Serialize, Clone, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct WebhookEndpointStatusDTO {
+    /// URL of the endpoint
+    pub url: String,
+
+    /// Number of failed attempts since the last successful one
+    pub consecutive_failures: i32,
+
+    /// When the endpoint has been marked as failing (too many consecutive failed attempts)
+    /// Not set if the endpoint is healthy
+    #[ts(type = "Date", optional)]
+    pub failing_since: Option<DateTime<Utc>>,
+
+    /// When the last attempt was made
+    #[ts(type = "Date")]
+    pub last_attempt_at: DateTime<Utc>,
+}
+
+impl WebhookEndpointStatusDTO {
+    pub fn new(status: WebhookEndpointStatus) -> Self {
+        Self {
+            url: status.url,
+            consecutive_failures: status.consecutive_failures,
+            failing_since: status.failing_since,
+            last_attempt_at: status.last_attempt_at,
+        }
+    }
+}
@@ -1,11 +1,15 @@
+use chrono::{DateTime, TimeDelta, Utc};
 use nittei_utils::create_random_secret;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 use utoipa::ToSchema;
+use validator::Validate;
 
 use crate::{
     IntegrationProvider,
+    WebhookEventType,
     shared::entity::{Entity, ID},
+    sign_webhook_payload,
 };
 
 const API_KEY_LEN: usize = 30;
@@ -43,12 +47,138 @@ impl PEMKey {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AccountSettings {
     pub webhook: Option<AccountWebhookSettings>,
+    /// Language of the texts of the events (title, description and location), used by the text search
+    #[serde(default)]
+    pub search_language: SearchLanguage,
+    /// Limits of the requests of the account and of its users, overriding the default ones
+    /// The limits are applied by each instance of the application, not across all the instances
+    #[serde(default)]
+    pub rate_limits: AccountRateLimits,
+}
+
+/// Limit of the requests of a client (token bucket)
+/// The client can send `burst` requests at once, and then `requests_per_second` requests per second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Validate, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct RateLimit {
+    /// Number of requests per second
+    #[validate(range(min = 1))]
+    pub requests_per_second: u32,
+    /// Number of requests that can be sent at once
+    #[validate(range(min = 1))]
+    pub burst: u32,
+}
+
+/// Limits of the requests of an account, the default ones (`rate_limit` config) are used if not set
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Validate, TS, ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct AccountRateLimits {
+    /// Limit of the requests of the account (with its API key, and separately on its public routes)
+    #[validate(nested)]
+    #[ts(optional)]
+    pub account: Option<RateLimit>,
+    /// Limit of the requests of each user of the account (with a JWT)
+    #[validate(nested)]
+    #[ts(optional)]
+    pub user: Option<RateLimit>,
+}
+
+/// Language used to search the texts of the events
+/// Each language is a text search configuration of Postgres, which removes the stop words and stems the words
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum SearchLanguage {
+    /// No stop words and no stemming, the words are only lowercased (works for any language)
+    #[default]
+    Simple,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Turkish,
+}
+
+impl SearchLanguage {
+    /// Name of the text search configuration in Postgres
+    pub fn config_name(&self) -> &'static str {
+        match self {
+            Self::Simple => "simple",
+            Self::Danish => "danish",
+            Self::Dutch => "dutch",
+            Self::English => "english",
+            Self::Finnish => "finnish",
+            Self::French => "french",
+            Self::German => "german",
+            Self::Hungarian => "hungarian",
+            Self::Italian => "italian",
+            Self::Norwegian => "norwegian",
+            Self::Portuguese => "portuguese",
+            Self::Romanian => "romanian",
+            Self::Russian => "russian",
+            Self::Spanish => "spanish",
+            Self::Swedish => "swedish",
+            Self::Turkish => "turkish",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountWebhookSettings {
     pub url: String,
     pub key: String,
+    /// Changes of events sent to the webhook (opt-in)
+    #[serde(default)]
+    pub event_types: Vec<WebhookEventType>,
+    /// Key replaced by the last rotation, still used to sign the webhooks until it expires
+    #[serde(default)]
+    pub previous_key: Option<String>,
+    #[serde(default)]
+    pub previous_key_expires_at: Option<DateTime<Utc>>,
+}
+
+impl AccountWebhookSettings {
+    pub fn is_subscribed_to(&self, event_type: WebhookEventType) -> bool {
+        self.event_types.contains(&event_type)
+    }
+
+    /// Replace the key by a new one
+    /// The current key stays valid for the grace period, so that receivers have time to switch
+    pub fn rotate_key(&mut self, grace_period: TimeDelta, now: DateTime<Utc>) {
+        let previous_key = std::mem::replace(&mut self.key, Account::generate_secret_api_key());
+        self.previous_key = Some(previous_key);
+        self.previous_key_expires_at = Some(now + grace_period);
+    }
+
+    /// Keys valid at `now` (the current key, and the previous one during its grace period)
+    pub fn valid_keys(&self, now: DateTime<Utc>) -> Vec<&str> {
+        let previous_key = match (&self.previous_key, self.previous_key_expires_at) {
+            (Some(key), Some(expires_at)) if expires_at > now => Some(key.as_str()),
+            _ => None,
+        };
+        std::iter::once(self.key.as_str())
+            .chain(previous_key)
+            .collect()
+    }
+
+    /// Value of the signature header for a webhook with this body, sent at `now`
+    pub fn sign_payload(&self, body: &[u8], now: DateTime<Utc>) -> String {
+        sign_webhook_payload(&self.valid_keys(now), now, body)
+    }
 }
 
 impl AccountSettings {
@@ -71,6 +201,9 @@ impl AccountSettings {
                     self.webhook = Some(AccountWebhookSettings {
                         url,
                         key: Account::generate_secret_api_key(),
+                        event_types: Vec::new(),
+                        previous_key: None,
+                        previous_key_expires_at: None,
                     });
                 }
             }
@@ -80,6 +213,30 @@ impl AccountSettings {
         };
         true
     }
+
+    /// Rotate the key of the webhook, see `AccountWebhookSettings::rotate_key`
+    /// Returns false if the account has no webhook
+    pub fn rotate_webhook_key(&mut self, grace_period: TimeDelta, now: DateTime<Utc>) -> bool {
+        match self.webhook.as_mut() {
+            Some(webhook_settings) => {
+                webhook_settings.rotate_key(grace_period, now);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the changes of events sent to the webhook
+    /// Returns false if the account has no webhook
+    pub fn set_webhook_event_types(&mut self, event_types: Vec<WebhookEventType>) -> bool {
+        match self.webhook.as_mut() {
+            Some(webhook_settings) => {
+                webhook_settings.event_types = event_types;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -146,4 +303,27 @@ mod test {
 
         assert!(PEMKey::new(pub_key).is_ok());
     }
+
+    #[test]
+    fn it_keeps_previous_webhook_key_valid_during_grace_period() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut settings = AccountSettings::default();
+        assert!(!settings.rotate_webhook_key(TimeDelta::hours(1), now));
+        assert!(settings.set_webhook_url(Some("https://example.com".into())));
+
+        let webhook = settings.webhook.clone().unwrap();
+        assert_eq!(webhook.valid_keys(now), vec![webhook.key.as_str()]);
+
+        assert!(settings.rotate_webhook_key(TimeDelta::hours(1), now));
+        let rotated = settings.webhook.unwrap();
+        assert_ne!(rotated.key, webhook.key);
+        assert_eq!(
+            rotated.valid_keys(now + TimeDelta::minutes(59)),
+            vec![rotated.key.as_str(), webhook.key.as_str()]
+        );
+        assert_eq!(
+            rotated.valid_keys(now + TimeDelta::hours(1)),
+            vec![rotated.key.as_str()]
+        );
+    }
 }
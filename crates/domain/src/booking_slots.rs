@@ -40,7 +40,7 @@ pub struct UserFreeEvents {
     pub user_id: ID,
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub struct ServiceBookingSlot {
     pub start: DateTime<Utc>,
     pub duration: i64,
@@ -65,7 +65,7 @@ impl ServiceBookingSlots {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ServiceBookingSlotsDate {
     pub date: String,
     pub slots: Vec<ServiceBookingSlot>,
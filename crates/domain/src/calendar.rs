@@ -17,6 +17,8 @@ pub struct Calendar {
     pub key: Option<String>,
     pub settings: CalendarSettings,
     pub metadata: Option<serde_json::Value>,
+    /// Incremented each time the calendar is saved, used for optimistic concurrency control
+    pub version: i64,
 }
 
 impl Meta<ID> for Calendar {
@@ -58,6 +60,7 @@ impl Calendar {
             key,
             settings: Default::default(),
             metadata: Default::default(),
+            version: 0,
         }
     }
 }
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use nittei_utils::create_random_secret;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Calendar,
+    CalendarEvent,
+    shared::entity::{Entity, ID},
+};
+
+const FEED_TOKEN_LEN: usize = 40;
+
+/// Title of the events in a feed masking them
+pub const MASKED_EVENT_TITLE: &str = "Busy";
+
+/// Public iCalendar feed of a calendar, which calendar applications can subscribe to
+///
+/// The feed is only accessible with its token, so revoking it (deleting the feed) stops the access
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarFeed {
+    pub id: ID,
+    pub calendar_id: ID,
+    pub user_id: ID,
+    pub account_id: ID,
+    /// Unguessable token used in the URL of the feed
+    pub token: String,
+    /// If true, the events only show the user is busy: their titles are replaced by "Busy",
+    /// and their descriptions and locations are removed
+    pub mask_events: bool,
+    pub created: DateTime<Utc>,
+}
+
+impl CalendarFeed {
+    pub fn new(calendar: &Calendar, mask_events: bool, now: DateTime<Utc>) -> Self {
+        Self {
+            id: Default::default(),
+            calendar_id: calendar.id.clone(),
+            user_id: calendar.user_id.clone(),
+            account_id: calendar.account_id.clone(),
+            token: Self::generate_token(),
+            mask_events,
+            created: now,
+        }
+    }
+
+    pub fn generate_token() -> String {
+        let rand_secret = create_random_secret(FEED_TOKEN_LEN);
+        format!("cf_{rand_secret}")
+    }
+
+    /// Remove from the event what the feed shouldn't show
+    pub fn mask(&self, event: &mut CalendarEvent) {
+        if !self.mask_events {
+            return;
+        }
+        event.title = Some(MASKED_EVENT_TITLE.to_string());
+        event.description = None;
+        event.location = None;
+    }
+
+    /// Entity tag of the content of a feed, so that clients only download it again when it changes
+    pub fn etag(content: &str) -> String {
+        format!("\"{}\"", hex::encode(Sha256::digest(content.as_bytes())))
+    }
+}
+
+impl Entity<ID> for CalendarFeed {
+    fn id(&self) -> ID {
+        self.id.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_generates_unique_tokens() {
+        let calendar = Calendar::new(&ID::default(), &ID::default(), None, None);
+        let feed = CalendarFeed::new(&calendar, false, Utc::now());
+        let other_feed = CalendarFeed::new(&calendar, false, Utc::now());
+
+        assert!(feed.token.starts_with("cf_"));
+        assert_eq!(feed.token.len(), FEED_TOKEN_LEN + 3);
+        assert_ne!(feed.token, other_feed.token);
+    }
+
+    #[test]
+    fn it_masks_events() {
+        let calendar = Calendar::new(&ID::default(), &ID::default(), None, None);
+        let event = CalendarEvent {
+            title: Some("Doctor".into()),
+            description: Some("Checkup".into()),
+            location: Some("Hospital".into()),
+            ..Default::default()
+        };
+
+        let mut unmasked = event.clone();
+        CalendarFeed::new(&calendar, false, Utc::now()).mask(&mut unmasked);
+        assert_eq!(unmasked.title, event.title);
+        assert_eq!(unmasked.description, event.description);
+        assert_eq!(unmasked.location, event.location);
+
+        let mut masked = event.clone();
+        CalendarFeed::new(&calendar, true, Utc::now()).mask(&mut masked);
+        assert_eq!(masked.title.as_deref(), Some(MASKED_EVENT_TITLE));
+        assert!(masked.description.is_none());
+        assert!(masked.location.is_none());
+    }
+
+    #[test]
+    fn it_computes_the_etag_of_the_content() {
+        let etag = CalendarFeed::etag("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n");
+
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+        assert_eq!(
+            etag,
+            CalendarFeed::etag("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n")
+        );
+        assert_ne!(
+            etag,
+            CalendarFeed::etag("BEGIN:VCALENDAR\r\n\r\nEND:VCALENDAR\r\n")
+        );
+    }
+}
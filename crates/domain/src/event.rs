@@ -1,6 +1,7 @@
 use std::convert::TryFrom;
 
 use chrono::{TimeDelta, prelude::*};
+use chrono_tz::Tz;
 use rrule::RRuleSet;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
@@ -12,6 +13,7 @@ use crate::{
     calendar::CalendarSettings,
     event_instance::EventInstance,
     shared::{
+        cursor::Cursor,
         entity::{Entity, ID},
         recurrence::RRuleOptions,
     },
@@ -64,7 +66,7 @@ impl TryFrom<String> for CalendarEventStatus {
 }
 
 /// Enum used for know which sort to use when searching events
-#[derive(Default, Deserialize, Serialize, Debug, Clone, TS, ToSchema)]
+#[derive(Default, Deserialize, Serialize, Debug, Clone, PartialEq, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, rename = "CalendarEventSort")]
 pub enum CalendarEventSort {
@@ -89,6 +91,95 @@ pub enum CalendarEventSort {
     EventUidAsc,
     // Sort by event uid (desc)
     EventUidDesc,
+    /// Sort by relevance to the text searched, most relevant first
+    /// Without a text to search, the events are sorted by start time (asc)
+    RelevanceDesc,
+}
+
+/// Position after an event in the results of a search, for getting the next page of results
+///
+/// The events are ordered by the key of the sort, then by their ids (in the same direction),
+/// so the cursor holds the key of the event along with its id
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEventCursor {
+    pub sort: CalendarEventSort,
+    pub key: CalendarEventCursorKey,
+    pub id: ID,
+}
+
+/// Value of the sort key of an event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CalendarEventCursorKey {
+    /// The events are only ordered by their ids
+    None,
+    Time(DateTime<Utc>),
+    /// Rank of the event for the text searched
+    Rank(f32),
+}
+
+impl CalendarEventCursor {
+    /// Cursor after the event, `rank` being its rank for the text searched (if any)
+    pub fn after(event: &CalendarEvent, sort: CalendarEventSort, rank: Option<f32>) -> Self {
+        let key = match (&sort, rank) {
+            (CalendarEventSort::StartTimeAsc | CalendarEventSort::StartTimeDesc, _) => {
+                CalendarEventCursorKey::Time(event.start_time)
+            }
+            (CalendarEventSort::EndTimeAsc | CalendarEventSort::EndTimeDesc, _) => {
+                CalendarEventCursorKey::Time(event.end_time)
+            }
+            (CalendarEventSort::CreatedAsc | CalendarEventSort::CreatedDesc, _) => {
+                CalendarEventCursorKey::Time(event.created)
+            }
+            (CalendarEventSort::UpdatedAsc | CalendarEventSort::UpdatedDesc, _) => {
+                CalendarEventCursorKey::Time(event.updated)
+            }
+            (CalendarEventSort::EventUidAsc | CalendarEventSort::EventUidDesc, _) => {
+                CalendarEventCursorKey::None
+            }
+            (CalendarEventSort::RelevanceDesc, Some(rank)) => CalendarEventCursorKey::Rank(rank),
+            // Without a text searched, the events are sorted by start time
+            (CalendarEventSort::RelevanceDesc, None) => {
+                CalendarEventCursorKey::Time(event.start_time)
+            }
+        };
+        Self {
+            sort,
+            key,
+            id: event.id.clone(),
+        }
+    }
+
+    /// The cursor was generated for a search with this sort (and with a text searched or not)
+    pub fn matches(&self, sort: &CalendarEventSort, text_searched: bool) -> bool {
+        if &self.sort != sort {
+            return false;
+        }
+        match (sort, &self.key) {
+            (CalendarEventSort::EventUidAsc | CalendarEventSort::EventUidDesc, key) => {
+                key == &CalendarEventCursorKey::None
+            }
+            (CalendarEventSort::RelevanceDesc, CalendarEventCursorKey::Rank(_)) => text_searched,
+            (CalendarEventSort::RelevanceDesc, CalendarEventCursorKey::Time(_)) => !text_searched,
+            (_, key) => matches!(key, CalendarEventCursorKey::Time(_)),
+        }
+    }
+}
+
+impl Cursor for CalendarEventCursor {}
+
+/// Fragments of the texts of an event containing words of a text search, with these words between `<b>` and `</b>`
+/// The texts are HTML escaped, the `<b>` tags are the only markup
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarEventHighlight {
+    pub event_id: ID,
+    /// Fragment of the title, if it contains a word searched
+    pub title: Option<String>,
+    /// Fragments of the description, if it contains a word searched
+    pub description: Option<String>,
+    /// Fragment of the location, if it contains a word searched
+    pub location: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
@@ -110,6 +201,9 @@ pub struct CalendarEvent {
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
     pub recurrence: Option<RRuleOptions>,
+    /// Timezone in which the recurrence is expanded, instead of the one of the calendar
+    /// e.g. an event every Monday at 09:00 in `Europe/Paris` stays at 09:00 (Paris time) across DST changes
+    pub timezone: Option<Tz>,
     pub exdates: Vec<DateTime<Utc>>,
     pub recurring_until: Option<DateTime<Utc>>,
     pub recurring_event_id: Option<ID>,
@@ -118,8 +212,12 @@ pub struct CalendarEvent {
     pub user_id: ID,
     pub account_id: ID,
     pub reminders: Vec<CalendarEventReminder>,
+    pub attendees: Vec<CalendarEventAttendee>,
     pub service_id: Option<ID>,
+    pub group_id: Option<ID>,
     pub metadata: Option<serde_json::Value>,
+    /// Incremented each time the event is saved, used for optimistic concurrency control
+    pub version: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -162,7 +260,190 @@ impl CalendarEventReminder {
     }
 }
 
+/// Scope of a change (update or delete) made on a recurring event
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum RecurringEventScope {
+    /// Only the targeted occurrence
+    This,
+    /// The targeted occurrence and all the following ones
+    ThisAndFollowing,
+    /// The whole series (default)
+    #[default]
+    All,
+}
+
+/// Role of an attendee in a calendar event
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum CalendarEventAttendeeRole {
+    /// Participation is required (default)
+    #[default]
+    Required,
+    /// Participation is optional
+    Optional,
+    /// Chair of the event
+    Chair,
+    /// Attendee only kept for information purposes
+    NonParticipant,
+}
+
+impl From<CalendarEventAttendeeRole> for String {
+    fn from(e: CalendarEventAttendeeRole) -> Self {
+        match e {
+            CalendarEventAttendeeRole::Required => "required".into(),
+            CalendarEventAttendeeRole::Optional => "optional".into(),
+            CalendarEventAttendeeRole::Chair => "chair".into(),
+            CalendarEventAttendeeRole::NonParticipant => "nonParticipant".into(),
+        }
+    }
+}
+
+impl TryFrom<String> for CalendarEventAttendeeRole {
+    type Error = anyhow::Error;
+    fn try_from(e: String) -> anyhow::Result<CalendarEventAttendeeRole> {
+        Ok(match &e[..] {
+            "required" => CalendarEventAttendeeRole::Required,
+            "optional" => CalendarEventAttendeeRole::Optional,
+            "chair" => CalendarEventAttendeeRole::Chair,
+            "nonParticipant" => CalendarEventAttendeeRole::NonParticipant,
+            _ => Err(anyhow::anyhow!("Invalid attendee role"))?,
+        })
+    }
+}
+
+/// Response of an attendee to a calendar event (RSVP)
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum CalendarEventAttendeeResponseStatus {
+    /// The attendee has not responded yet (default)
+    #[default]
+    NeedsAction,
+    Accepted,
+    Declined,
+    Tentative,
+}
+
+impl From<CalendarEventAttendeeResponseStatus> for String {
+    fn from(e: CalendarEventAttendeeResponseStatus) -> Self {
+        match e {
+            CalendarEventAttendeeResponseStatus::NeedsAction => "needsAction".into(),
+            CalendarEventAttendeeResponseStatus::Accepted => "accepted".into(),
+            CalendarEventAttendeeResponseStatus::Declined => "declined".into(),
+            CalendarEventAttendeeResponseStatus::Tentative => "tentative".into(),
+        }
+    }
+}
+
+impl TryFrom<String> for CalendarEventAttendeeResponseStatus {
+    type Error = anyhow::Error;
+    fn try_from(e: String) -> anyhow::Result<CalendarEventAttendeeResponseStatus> {
+        Ok(match &e[..] {
+            "needsAction" => CalendarEventAttendeeResponseStatus::NeedsAction,
+            "accepted" => CalendarEventAttendeeResponseStatus::Accepted,
+            "declined" => CalendarEventAttendeeResponseStatus::Declined,
+            "tentative" => CalendarEventAttendeeResponseStatus::Tentative,
+            _ => Err(anyhow::anyhow!("Invalid attendee response status"))?,
+        })
+    }
+}
+
+/// Attendee of a calendar event
+/// An attendee is either an internal user (`userId`) or an external person (`email`)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct CalendarEventAttendee {
+    /// UUID of the internal user
+    /// Mutually exclusive with `email`
+    #[serde(default)]
+    #[ts(optional)]
+    pub user_id: Option<ID>,
+
+    /// Email of the external attendee
+    /// Mutually exclusive with `userId`
+    #[serde(default)]
+    #[ts(optional)]
+    pub email: Option<String>,
+
+    /// Role of the attendee, default is required
+    #[serde(default)]
+    #[ts(as = "Option<_>", optional)]
+    pub role: CalendarEventAttendeeRole,
+
+    /// Response of the attendee, default is needsAction
+    #[serde(default)]
+    #[ts(as = "Option<_>", optional)]
+    pub response_status: CalendarEventAttendeeResponseStatus,
+}
+
+impl CalendarEventAttendee {
+    /// An attendee is valid if it is either an internal user or an email, but not both
+    pub fn is_valid(&self) -> bool {
+        match (&self.user_id, &self.email) {
+            (Some(_), None) => true,
+            (None, Some(email)) => !email.trim().is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Check if both attendees refer to the same person
+    pub fn is_same_attendee(&self, other: &CalendarEventAttendee) -> bool {
+        match (&self.user_id, &other.user_id) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => match (&self.email, &other.email) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
 impl CalendarEvent {
+    /// Set the attendees of the event
+    /// The emails are normalized (trimmed and lowercased)
+    /// If an attendee was already present and the provided response status is the default one (needsAction),
+    /// the previous response of the attendee is kept
+    /// Returns false if one of the attendees is invalid or if an attendee is present twice
+    pub fn set_attendees(&mut self, attendees: Vec<CalendarEventAttendee>) -> bool {
+        let mut new_attendees: Vec<CalendarEventAttendee> = Vec::with_capacity(attendees.len());
+        for mut attendee in attendees {
+            attendee.email = attendee.email.map(|email| email.trim().to_lowercase());
+            if !attendee.is_valid() || new_attendees.iter().any(|a| a.is_same_attendee(&attendee)) {
+                return false;
+            }
+            if attendee.response_status == CalendarEventAttendeeResponseStatus::NeedsAction
+                && let Some(previous) = self
+                    .attendees
+                    .iter()
+                    .find(|a| a.is_same_attendee(&attendee))
+            {
+                attendee.response_status = previous.response_status.clone();
+            }
+            new_attendees.push(attendee);
+        }
+        self.attendees = new_attendees;
+        true
+    }
+
+    /// Find the attendee corresponding to an internal user
+    pub fn find_attendee_by_user(&self, user_id: &ID) -> Option<&CalendarEventAttendee> {
+        self.attendees
+            .iter()
+            .find(|a| a.user_id.as_ref() == Some(user_id))
+    }
+
+    /// Check if the internal user is an attendee who declined the event
+    /// Declined attendees are not considered busy during the event
+    pub fn is_declined_by(&self, user_id: &ID) -> bool {
+        self.find_attendee_by_user(user_id)
+            .is_some_and(|a| a.response_status == CalendarEventAttendeeResponseStatus::Declined)
+    }
+
     pub fn set_recurrence(&mut self, recurrence: RRuleOptions) -> anyhow::Result<bool> {
         let valid_recurrence = recurrence.is_valid();
         if !valid_recurrence {
@@ -170,19 +451,33 @@ impl CalendarEvent {
         }
 
         // Add duration to until if it is set
-        self.recurring_until = recurrence
-            .until
-            .map(|until| until + TimeDelta::milliseconds(self.duration));
+        // The additional dates (RDATE) can be after the end of the rule
+        self.recurring_until = recurrence.until.map(|until| {
+            recurrence
+                .last_rdate()
+                .map_or(until, |rdate| rdate.max(until))
+                + TimeDelta::milliseconds(self.duration)
+        });
 
         // Set the recurrence
         self.recurrence = Some(recurrence);
         Ok(true)
     }
 
+    /// Settings used for expanding the recurrence of the event
+    /// The timezone of the event, when set, takes precedence over the one of the calendar
+    pub fn recurrence_settings(&self, calendar_settings: &CalendarSettings) -> CalendarSettings {
+        CalendarSettings {
+            timezone: self.timezone.unwrap_or(calendar_settings.timezone),
+            ..calendar_settings.clone()
+        }
+    }
+
     pub fn get_rrule_set(
         &self,
         calendar_settings: &CalendarSettings,
     ) -> anyhow::Result<Option<RRuleSet>> {
+        let calendar_settings = &self.recurrence_settings(calendar_settings);
         if let Some(recurrence) = self.recurrence.clone() {
             let rrule_options =
                 recurrence.get_parsed_options(self.start_time, calendar_settings)?;
@@ -191,6 +486,9 @@ impl CalendarEvent {
             for exdate in &self.exdates {
                 rrule_set = rrule_set.exdate(exdate.with_timezone(&tzid));
             }
+            for rdate in rrule_options.get_rdate() {
+                rrule_set = rrule_set.rdate(*rdate);
+            }
             let rrule = rrule_options.get_rrule().first();
             match rrule {
                 Some(rrule) => {
@@ -208,9 +506,20 @@ impl CalendarEvent {
         &self,
         timespan: Option<TimeSpan>,
         calendar_settings: &CalendarSettings,
+    ) -> anyhow::Result<Vec<EventInstance>> {
+        self.expand_with_limit(timespan, calendar_settings, MAX_INSTANCES)
+    }
+
+    /// Expand the event, up to `max_instances` instances for a recurring event
+    pub fn expand_with_limit(
+        &self,
+        timespan: Option<TimeSpan>,
+        calendar_settings: &CalendarSettings,
+        max_instances: u16,
     ) -> anyhow::Result<Vec<EventInstance>> {
         match &self.recurrence {
             Some(_) => {
+                let calendar_settings = &self.recurrence_settings(calendar_settings);
                 let tzid = rrule::Tz::Tz(calendar_settings.timezone);
                 let rrule_set = match self.get_rrule_set(calendar_settings)? {
                     Some(rrule_set) => rrule_set,
@@ -225,9 +534,9 @@ impl CalendarEvent {
                         rrule_set
                             .after(start_with_timezone)
                             .before(end_with_timezone)
-                            .all(MAX_INSTANCES)
+                            .all(max_instances)
                     }
-                    None => rrule_set.all(MAX_INSTANCES),
+                    None => rrule_set.all(max_instances),
                 };
 
                 Ok(instances
@@ -255,6 +564,133 @@ impl CalendarEvent {
         }
     }
 
+    /// Check if the given time is the start of one of the occurrences of the recurring event
+    /// Excluded dates (exdates) are not considered as occurrences
+    pub fn is_occurrence(
+        &self,
+        time: DateTime<Utc>,
+        calendar_settings: &CalendarSettings,
+    ) -> anyhow::Result<bool> {
+        let calendar_settings = &self.recurrence_settings(calendar_settings);
+        let rrule_set = match self.get_rrule_set(calendar_settings)? {
+            Some(rrule_set) => rrule_set,
+            None => return Ok(false),
+        };
+        let time = time.with_timezone(&rrule::Tz::Tz(calendar_settings.timezone));
+        Ok(!rrule_set.after(time).before(time).all(1).dates.is_empty())
+    }
+
+    /// Split the recurring event at the given occurrence
+    /// The event is truncated so that its last occurrence is the one before `split_time`,
+    /// and the returned event is a new series starting at `split_time`, with the same recurrence
+    /// For count-based recurrences, the new series gets the remaining count
+    /// The exdates after the split are moved to the new series
+    /// The new series doesn't keep the external id of the event, which identifies the original series
+    pub fn split_at(
+        &mut self,
+        split_time: DateTime<Utc>,
+        calendar_settings: &CalendarSettings,
+    ) -> anyhow::Result<CalendarEvent> {
+        let calendar_settings = &self.recurrence_settings(calendar_settings);
+        let recurrence = self
+            .recurrence
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Only recurring events can be split"))?;
+        if split_time <= self.start_time {
+            return Err(anyhow::anyhow!(
+                "The split time needs to be after the start of the event"
+            ));
+        }
+
+        // The additional dates (RDATE) are split between the two series
+        let (rdates_before, rdates_after): (Vec<_>, Vec<_>) = recurrence
+            .rdates
+            .iter()
+            .flatten()
+            .partition(|rdate| **rdate < split_time);
+        let mut new_recurrence = RRuleOptions {
+            rdates: recurrence.rdates.as_ref().map(|_| rdates_after),
+            ..recurrence.clone()
+        };
+        if let Some(count) = recurrence.count {
+            // The count includes the excluded dates, so they are not removed here
+            // The additional dates (RDATE) are not part of the count
+            let without_exdates = CalendarEvent {
+                exdates: Vec::new(),
+                recurrence: Some(RRuleOptions {
+                    rdates: None,
+                    ..recurrence.clone()
+                }),
+                ..self.clone()
+            };
+            let occurrences_before = match without_exdates.get_rrule_set(calendar_settings)? {
+                Some(rrule_set) => {
+                    let before = (split_time - TimeDelta::milliseconds(1))
+                        .with_timezone(&rrule::Tz::Tz(calendar_settings.timezone));
+                    rrule_set.before(before).all(u16::MAX).dates.len()
+                }
+                None => 0,
+            };
+            new_recurrence.count = Some(count - occurrences_before as i32);
+        }
+
+        let mut new_series = CalendarEvent {
+            id: Default::default(),
+            start_time: split_time,
+            end_time: split_time + TimeDelta::milliseconds(self.duration),
+            exdates: self
+                .exdates
+                .iter()
+                .filter(|exdate| **exdate >= split_time)
+                .cloned()
+                .collect(),
+            // The external id identifies the original series only
+            external_id: None,
+            // The new series is a new event, it starts at the first version
+            version: 0,
+            created: Utc::now(),
+            updated: Utc::now(),
+            ..self.clone()
+        };
+        if !new_series.set_recurrence(new_recurrence)? {
+            return Err(anyhow::anyhow!("Invalid recurrence for the new series"));
+        }
+
+        // Truncate the current series, the last occurrence is the one just before the split
+        let truncated_recurrence = RRuleOptions {
+            count: None,
+            until: Some(split_time - TimeDelta::seconds(1)),
+            rdates: recurrence.rdates.as_ref().map(|_| rdates_before),
+            ..recurrence
+        };
+        if !self.set_recurrence(truncated_recurrence)? {
+            return Err(anyhow::anyhow!(
+                "Invalid recurrence for the truncated series"
+            ));
+        }
+        self.exdates.retain(|exdate| *exdate < split_time);
+
+        Ok(new_series)
+    }
+
+    /// Move the event (and its recurrence, if any) by the given delta
+    /// This shifts the start and end times, but also the exdates, the recurrence `until`
+    /// and the original start time (for exceptions), so that the event stays consistent
+    pub fn shift(&mut self, delta: TimeDelta) {
+        self.start_time += delta;
+        self.end_time += delta;
+        if let Some(recurrence) = self.recurrence.as_mut() {
+            recurrence.until = recurrence.until.map(|until| until + delta);
+            recurrence.rdates = recurrence
+                .rdates
+                .as_ref()
+                .map(|rdates| rdates.iter().map(|rdate| *rdate + delta).collect());
+        }
+        self.recurring_until = self.recurring_until.map(|until| until + delta);
+        self.original_start_time = self.original_start_time.map(|start| start + delta);
+        self.exdates = self.exdates.iter().map(|exdate| *exdate + delta).collect();
+    }
+
     /// Filters the instances based on the changed instances, and returns the instances that are not changed
     /// The changed instances are actual event calendars (contrary to normal instances)
     /// They need to be removed from the default instances list, as they are not part of the recurrence anymore
@@ -348,6 +784,51 @@ mod test {
         assert_eq!(oc.len(), 0);
     }
 
+    #[test]
+    fn shift_calendar_event() {
+        let settings = CalendarSettings {
+            timezone: UTC,
+            week_start: Weekday::Mon,
+        };
+        let start_time = DateTime::from_timestamp_millis(1521317491000).unwrap();
+        let mut event = CalendarEvent {
+            start_time,
+            duration: 1000 * 60 * 60,
+            end_time: start_time + TimeDelta::hours(1),
+            exdates: vec![start_time + TimeDelta::days(1)],
+            ..Default::default()
+        };
+        event
+            .set_recurrence(RRuleOptions {
+                freq: RRuleFrequency::Daily,
+                interval: 1,
+                until: Some(start_time + TimeDelta::days(3)),
+                ..Default::default()
+            })
+            .unwrap();
+
+        event.shift(TimeDelta::minutes(30));
+
+        assert_eq!(event.start_time, start_time + TimeDelta::minutes(30));
+        assert_eq!(
+            event.end_time,
+            start_time + TimeDelta::hours(1) + TimeDelta::minutes(30)
+        );
+        assert_eq!(
+            event.recurrence.as_ref().unwrap().until,
+            Some(start_time + TimeDelta::days(3) + TimeDelta::minutes(30))
+        );
+        assert_eq!(
+            event.exdates,
+            vec![start_time + TimeDelta::days(1) + TimeDelta::minutes(30)]
+        );
+
+        // Same number of instances as before (4 days - 1 exdate)
+        let oc = event.expand(None, &settings).unwrap();
+        assert_eq!(oc.len(), 3);
+        assert_eq!(oc[0].start_time, start_time + TimeDelta::minutes(30));
+    }
+
     #[test]
     fn rejects_event_with_invalid_recurrence() {
         let mut invalid_rrules = Vec::new();
@@ -483,4 +964,242 @@ mod test {
         // As they, by themselves, already "represent" these instances
         assert_eq!(oc_filtered.len(), 1);
     }
+
+    #[test]
+    fn attendees_validity_and_declines() {
+        let user_id = ID::default();
+        let internal = CalendarEventAttendee {
+            user_id: Some(user_id.clone()),
+            response_status: CalendarEventAttendeeResponseStatus::Declined,
+            ..Default::default()
+        };
+        let external = CalendarEventAttendee {
+            email: Some("John@example.com".into()),
+            ..Default::default()
+        };
+        assert!(internal.is_valid());
+        assert!(external.is_valid());
+        assert!(!CalendarEventAttendee::default().is_valid());
+        assert!(
+            !CalendarEventAttendee {
+                user_id: Some(user_id.clone()),
+                email: Some("john@example.com".into()),
+                ..Default::default()
+            }
+            .is_valid()
+        );
+
+        assert!(external.is_same_attendee(&CalendarEventAttendee {
+            email: Some("john@example.com".into()),
+            ..Default::default()
+        }));
+        assert!(!external.is_same_attendee(&internal));
+
+        let event = CalendarEvent {
+            attendees: vec![internal, external],
+            ..Default::default()
+        };
+        assert!(event.is_declined_by(&user_id));
+        assert!(!event.is_declined_by(&ID::default()));
+    }
+
+    #[test]
+    fn split_recurring_event() {
+        let settings = CalendarSettings {
+            timezone: UTC,
+            week_start: Weekday::Mon,
+        };
+        let start_time = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+        let mut event = CalendarEvent {
+            start_time,
+            duration: 1000 * 60 * 60,
+            end_time: start_time + TimeDelta::hours(1),
+            exdates: vec![
+                start_time + TimeDelta::days(1),
+                start_time + TimeDelta::days(6),
+            ],
+            external_id: Some("series".into()),
+            version: 3,
+            ..Default::default()
+        };
+        assert!(
+            event
+                .set_recurrence(RRuleOptions {
+                    freq: RRuleFrequency::Daily,
+                    interval: 1,
+                    count: Some(10),
+                    ..Default::default()
+                })
+                .unwrap()
+        );
+
+        let split_time = start_time + TimeDelta::days(4);
+        assert!(event.is_occurrence(split_time, &settings).unwrap());
+        assert!(
+            !event
+                .is_occurrence(split_time + TimeDelta::minutes(1), &settings)
+                .unwrap()
+        );
+        // Excluded dates are not occurrences
+        assert!(
+            !event
+                .is_occurrence(start_time + TimeDelta::days(1), &settings)
+                .unwrap()
+        );
+
+        let new_series = event.split_at(split_time, &settings).unwrap();
+
+        // 4 occurrences before the split, minus the excluded one
+        assert_eq!(event.expand(None, &settings).unwrap().len(), 3);
+        assert_eq!(event.exdates, vec![start_time + TimeDelta::days(1)]);
+        assert!(event.recurring_until.is_some());
+
+        // 6 remaining occurrences, minus the excluded one
+        assert_ne!(new_series.id, event.id);
+        assert_eq!(new_series.start_time, split_time);
+        assert_eq!(new_series.recurrence.as_ref().unwrap().count, Some(6));
+        assert_eq!(new_series.expand(None, &settings).unwrap().len(), 5);
+        assert_eq!(event.external_id, Some("series".into()));
+        assert_eq!(new_series.external_id, None);
+        assert_eq!(new_series.version, 0);
+
+        // Splitting at the start of the series is not allowed
+        assert!(event.split_at(start_time, &settings).is_err());
+    }
+
+    #[test]
+    fn set_attendees_keeps_previous_responses() {
+        let user_id = ID::default();
+        let mut event = CalendarEvent::default();
+        assert!(event.set_attendees(vec![CalendarEventAttendee {
+            user_id: Some(user_id.clone()),
+            response_status: CalendarEventAttendeeResponseStatus::Accepted,
+            ..Default::default()
+        }]));
+
+        // Response is kept for the existing attendee, emails are normalized
+        assert!(event.set_attendees(vec![
+            CalendarEventAttendee {
+                user_id: Some(user_id.clone()),
+                ..Default::default()
+            },
+            CalendarEventAttendee {
+                email: Some(" John@Example.com ".into()),
+                ..Default::default()
+            },
+        ]));
+        assert_eq!(event.attendees.len(), 2);
+        assert_eq!(
+            event.attendees[0].response_status,
+            CalendarEventAttendeeResponseStatus::Accepted
+        );
+        assert_eq!(event.attendees[1].email, Some("john@example.com".into()));
+
+        // Duplicated attendees are rejected
+        assert!(!event.set_attendees(vec![
+            CalendarEventAttendee {
+                email: Some("john@example.com".into()),
+                ..Default::default()
+            },
+            CalendarEventAttendee {
+                email: Some("JOHN@example.com".into()),
+                ..Default::default()
+            },
+        ]));
+        assert_eq!(event.attendees.len(), 2);
+    }
+
+    #[test]
+    fn weekly_calendar_event_in_its_own_timezone() {
+        let settings = CalendarSettings {
+            timezone: UTC,
+            week_start: Weekday::Mon,
+        };
+        // Every Monday at 09:00 in Paris, from before the DST change of March 2030
+        let paris = chrono_tz::Europe::Paris;
+        let start_time = paris
+            .with_ymd_and_hms(2030, 3, 18, 9, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut event = CalendarEvent {
+            start_time,
+            duration: 1000 * 60 * 60,
+            end_time: start_time + TimeDelta::hours(1),
+            timezone: Some(paris),
+            ..Default::default()
+        };
+        assert!(
+            event
+                .set_recurrence(RRuleOptions {
+                    freq: RRuleFrequency::Weekly,
+                    count: Some(3),
+                    ..Default::default()
+                })
+                .unwrap()
+        );
+
+        let local_times = event
+            .expand(None, &settings)
+            .unwrap()
+            .iter()
+            .map(|instance| instance.start_time.with_timezone(&paris).naive_local())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            local_times,
+            vec![
+                NaiveDate::from_ymd_opt(2030, 3, 18)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap(),
+                NaiveDate::from_ymd_opt(2030, 3, 25)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap(),
+                NaiveDate::from_ymd_opt(2030, 4, 1)
+                    .unwrap()
+                    .and_hms_opt(9, 0, 0)
+                    .unwrap(),
+            ]
+        );
+        let after_dst = paris
+            .with_ymd_and_hms(2030, 4, 1, 9, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(event.is_occurrence(after_dst, &settings).unwrap());
+
+        // Without its timezone, the event is expanded in the timezone of the calendar (UTC)
+        event.timezone = None;
+        let instances = event.expand(None, &settings).unwrap();
+        assert_eq!(instances[2].start_time, start_time + TimeDelta::weeks(2));
+        assert_ne!(instances[2].start_time, after_dst);
+    }
+
+    #[test]
+    fn cursors_hold_the_key_of_the_sort() {
+        let event = CalendarEvent {
+            start_time: DateTime::from_timestamp_millis(1521317491000).unwrap(),
+            end_time: DateTime::from_timestamp_millis(1521321091000).unwrap(),
+            ..Default::default()
+        };
+
+        let cursor = CalendarEventCursor::after(&event, CalendarEventSort::EndTimeDesc, None);
+        assert_eq!(cursor.key, CalendarEventCursorKey::Time(event.end_time));
+        assert_eq!(cursor.id, event.id);
+        assert_eq!(
+            CalendarEventCursor::decode(&cursor.encode()),
+            Ok(cursor.clone())
+        );
+        assert!(cursor.matches(&CalendarEventSort::EndTimeDesc, false));
+        assert!(!cursor.matches(&CalendarEventSort::EndTimeAsc, false));
+
+        // Sorted by relevance, the rank is the key when a text is searched
+        let cursor =
+            CalendarEventCursor::after(&event, CalendarEventSort::RelevanceDesc, Some(0.5));
+        assert_eq!(cursor.key, CalendarEventCursorKey::Rank(0.5));
+        assert!(cursor.matches(&CalendarEventSort::RelevanceDesc, true));
+        assert!(!cursor.matches(&CalendarEventSort::RelevanceDesc, false));
+
+        let cursor = CalendarEventCursor::after(&event, CalendarEventSort::EventUidAsc, None);
+        assert_eq!(cursor.key, CalendarEventCursorKey::None);
+    }
 }
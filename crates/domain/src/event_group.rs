@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use crate::ID;
+use crate::{Entity, ID, Meta};
 
 /// Group of calendar events
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TS)]
@@ -28,3 +28,15 @@ pub struct EventGroup {
     /// It allows to link a group of events to an outside entity
     pub external_id: Option<String>,
 }
+
+impl Entity<ID> for EventGroup {
+    fn id(&self) -> ID {
+        self.id.clone()
+    }
+}
+
+impl Meta<ID> for EventGroup {
+    fn account_id(&self) -> &ID {
+        &self.account_id
+    }
+}
@@ -35,7 +35,7 @@ pub struct CompatibleInstances {
 impl CompatibleInstances {
     pub fn new(mut events: Vec<EventInstance>) -> Self {
         // sort with least start_ts first
-        events.sort_by(|i1, i2| i1.start_time.cmp(&i2.start_time));
+        events.sort_by_key(|i| i.start_time);
 
         let mut compatible_events: VecDeque<EventInstance> = Default::default();
 
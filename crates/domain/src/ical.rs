@@ -1,4 +1,12 @@
-use std::collections::HashMap;
+mod import;
+mod timezone;
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Datelike, Utc, Weekday};
+use chrono_tz::{Tz, UTC};
+pub use import::{IcalEvent, IcalParseError, IcalSkippedEvent, ParsedIcal, parse_ical_events};
+use timezone::generate_vtimezone;
 
 use crate::{Calendar, CalendarEvent, CalendarEventStatus, ID, RRuleFrequency, RRuleOptions};
 
@@ -6,14 +14,21 @@ use crate::{Calendar, CalendarEvent, CalendarEventStatus, ID, RRuleFrequency, RR
 ///
 /// This function creates a complete iCalendar (.ics) file content including:
 /// - Calendar metadata (name, timezone)
+/// - The VTIMEZONE of each timezone used by the events
 /// - Event details (title, description, location, dates, status)
 /// - Recurrence rules for recurring events
 /// - Exception dates for modified recurring events
 ///
+/// Recurring events are written in the local time of their timezone, or of the calendar (`DTSTART;TZID=...`),
+/// so that calendar applications expand them at the same local time across DST transitions.
+/// The other events are written in the local time of their timezone if they have one, in UTC otherwise.
+/// Lines are folded at 75 octets, as required by RFC 5545.
+///
 /// # Arguments
 /// * `calendar` - The calendar containing the events
-/// * `instances` - Event instances with resolved start/end times
-/// * `events` - The original calendar events with full metadata
+/// * `normal_events` - The events which are not recurring
+/// * `recurring_events` - The recurring events
+/// * `map_recurring_event_id_to_exceptions` - The exceptions of the recurring events
 ///
 /// # Returns
 /// A string containing the complete iCalendar content
@@ -40,19 +55,56 @@ pub fn generate_ical_content(
     // Add timezone information
     ical.push_str(&format!("X-WR-TIMEZONE:{}\r\n", calendar.settings.timezone));
 
+    // Recurring events are in the local time of their timezone (or the one of the calendar),
+    // and the other events are in their timezone if they have one, in UTC otherwise
+    let recurring_tz = |event: &CalendarEvent| {
+        event
+            .timezone
+            .or(Some(calendar.settings.timezone))
+            .filter(|tz| *tz != UTC)
+    };
+    let normal_tz = |event: &CalendarEvent| event.timezone.filter(|tz| *tz != UTC);
+
+    // The timezones used need to be defined, for the years of their events
+    let mut timezone_years: BTreeMap<&str, (Tz, i32, i32)> = BTreeMap::new();
+    let events_tz = normal_events
+        .iter()
+        .filter_map(|event| Some((normal_tz(event)?, event)))
+        .chain(recurring_events.iter().flat_map(|recurring_event| {
+            let exceptions = map_recurring_event_id_to_exceptions
+                .get(&recurring_event.id)
+                .into_iter()
+                .flatten();
+            std::iter::once(recurring_event)
+                .chain(exceptions)
+                .filter_map(move |event| Some((recurring_tz(recurring_event)?, event)))
+        }));
+    for (tz, event) in events_tz {
+        let year = event.start_time.year();
+        let years = timezone_years.entry(tz.name()).or_insert((tz, year, year));
+        years.1 = years.1.min(year);
+        years.2 = years.2.max(year);
+    }
+    for (tz, first_year, last_year) in timezone_years.into_values() {
+        // The previous year defines the offset in effect before the first transition
+        ical.push_str(&generate_vtimezone(tz, first_year - 1, last_year));
+    }
+
     // Add events
     for event in normal_events {
-        ical.push_str(&generate_ical_content_for_event(event));
+        ical.push_str(&generate_ical_content_for_event(event, normal_tz(event)));
     }
 
     for recurring_event in recurring_events {
-        ical.push_str(&generate_ical_content_for_event(recurring_event));
+        let tz = recurring_tz(recurring_event);
+        ical.push_str(&generate_ical_content_for_event(recurring_event, tz));
 
         if let Some(exceptions) = map_recurring_event_id_to_exceptions.get(&recurring_event.id) {
             for exception in exceptions {
                 ical.push_str(&generate_ical_content_for_exception(
                     exception,
                     recurring_event,
+                    tz,
                 ));
             }
         }
@@ -61,11 +113,13 @@ pub fn generate_ical_content(
     // iCalendar footer
     ical.push_str("END:VCALENDAR\r\n");
 
-    ical
+    fold_lines(&ical)
 }
 
 /// Generates iCalendar content for a single event
-pub fn generate_ical_content_for_event(event: &CalendarEvent) -> String {
+///
+/// The times are written in the local time of the timezone if provided, in UTC otherwise
+pub fn generate_ical_content_for_event(event: &CalendarEvent, tz: Option<Tz>) -> String {
     let mut ical = String::new();
 
     ical.push_str("BEGIN:VEVENT\r\n");
@@ -89,35 +143,21 @@ pub fn generate_ical_content_for_event(event: &CalendarEvent) -> String {
     }
 
     // Start and end time
-    if event.all_day {
-        ical.push_str(&format!(
-            "DTSTART;VALUE=DATE:{}\r\n",
-            event.start_time.format("%Y%m%d")
-        ));
-        ical.push_str(&format!(
-            "DTEND;VALUE=DATE:{}\r\n",
-            event.end_time.format("%Y%m%d")
-        ));
-    } else {
-        ical.push_str(&format!(
-            "DTSTART:{}\r\n",
-            event.start_time.format("%Y%m%dT%H%M%SZ")
-        ));
-        ical.push_str(&format!(
-            "DTEND:{}\r\n",
-            event.end_time.format("%Y%m%dT%H%M%SZ")
-        ));
-    }
+    ical.push_str(&format_date_property(
+        "DTSTART",
+        &event.start_time,
+        event.all_day,
+        tz,
+    ));
+    ical.push_str(&format_date_property(
+        "DTEND",
+        &event.end_time,
+        event.all_day,
+        tz,
+    ));
 
     // Status
-    ical.push_str(&format!(
-        "STATUS:{}\r\n",
-        match event.status {
-            CalendarEventStatus::Confirmed => "CONFIRMED",
-            CalendarEventStatus::Tentative => "TENTATIVE",
-            CalendarEventStatus::Cancelled => "CANCELLED",
-        }
-    ));
+    ical.push_str(&format!("STATUS:{}\r\n", ical_status(&event.status)));
 
     // Created and modified dates
     ical.push_str(&format!(
@@ -138,7 +178,14 @@ pub fn generate_ical_content_for_event(event: &CalendarEvent) -> String {
 
     // Exception dates
     for exdate in &event.exdates {
-        ical.push_str(&format!("EXDATE:{}\r\n", exdate.format("%Y%m%dT%H%M%SZ")));
+        ical.push_str(&format_date_property("EXDATE", exdate, event.all_day, tz));
+    }
+
+    // Additional dates of the recurrence
+    if let Some(recurrence) = &event.recurrence {
+        for rdate in recurrence.rdates.iter().flatten() {
+            ical.push_str(&format_date_property("RDATE", rdate, event.all_day, tz));
+        }
     }
 
     // Busy status
@@ -154,9 +201,12 @@ pub fn generate_ical_content_for_event(event: &CalendarEvent) -> String {
 }
 
 /// Generate ical content for an exception of a recurring event
+///
+/// The times are written like the ones of the recurring event, in the local time of the timezone if provided
 pub fn generate_ical_content_for_exception(
     exception: &CalendarEvent,
     parent_event: &CalendarEvent,
+    tz: Option<Tz>,
 ) -> String {
     let mut ical = String::new();
     ical.push_str("BEGIN:VEVENT\r\n");
@@ -165,53 +215,50 @@ pub fn generate_ical_content_for_exception(
     ical.push_str(&format!("UID:{}\r\n", parent_event.id));
 
     // RECURRENCE-ID identifies which occurrence is being modified
-    if exception.all_day {
-        if let Some(original_start_time) = &exception.original_start_time {
-            ical.push_str(&format!(
-                "RECURRENCE-ID;VALUE=DATE:{}\r\n",
-                original_start_time.format("%Y%m%d")
-            ));
-        }
-    } else if let Some(original_start_time) = &exception.original_start_time {
-        ical.push_str(&format!(
-            "RECURRENCE-ID:{}\r\n",
-            original_start_time.format("%Y%m%dT%H%M%SZ")
+    if let Some(original_start_time) = &exception.original_start_time {
+        ical.push_str(&format_date_property(
+            "RECURRENCE-ID",
+            original_start_time,
+            exception.all_day,
+            tz,
         ));
     }
 
     // Modified properties
-    if let Some(title) = &exception.title {
-        ical.push_str(&format!("SUMMARY:{}\r\n", escape_text(title)));
-    } else if let Some(title) = &parent_event.title {
+    if let Some(title) = exception.title.as_ref().or(parent_event.title.as_ref()) {
         ical.push_str(&format!("SUMMARY:{}\r\n", escape_text(title)));
     }
-
-    // Similar pattern for description, location, etc.
+    if let Some(description) = exception
+        .description
+        .as_ref()
+        .or(parent_event.description.as_ref())
+    {
+        ical.push_str(&format!("DESCRIPTION:{}\r\n", escape_text(description)));
+    }
+    if let Some(location) = exception
+        .location
+        .as_ref()
+        .or(parent_event.location.as_ref())
+    {
+        ical.push_str(&format!("LOCATION:{}\r\n", escape_text(location)));
+    }
 
     // Modified start and end times
-    if exception.all_day {
-        ical.push_str(&format!(
-            "DTSTART;VALUE=DATE:{}\r\n",
-            exception.start_time.format("%Y%m%d")
-        ));
-        ical.push_str(&format!(
-            "DTEND;VALUE=DATE:{}\r\n",
-            exception.end_time.format("%Y%m%d")
-        ));
-    } else {
-        ical.push_str(&format!(
-            "DTSTART:{}\r\n",
-            exception.start_time.format("%Y%m%dT%H%M%SZ")
-        ));
-        ical.push_str(&format!(
-            "DTEND:{}\r\n",
-            exception.end_time.format("%Y%m%dT%H%M%SZ")
-        ));
-    }
+    ical.push_str(&format_date_property(
+        "DTSTART",
+        &exception.start_time,
+        exception.all_day,
+        tz,
+    ));
+    ical.push_str(&format_date_property(
+        "DTEND",
+        &exception.end_time,
+        exception.all_day,
+        tz,
+    ));
 
     // Status (important for cancelled occurrences)
-    let status = &exception.status;
-    ical.push_str(&format!("STATUS:{}\r\n", status));
+    ical.push_str(&format!("STATUS:{}\r\n", ical_status(&exception.status)));
 
     // Created and modified dates
     ical.push_str(&format!(
@@ -237,6 +284,68 @@ pub fn generate_ical_content_for_exception(
     ical
 }
 
+/// Formats a date property (e.g. `DTSTART`), as a date for all-day events,
+/// in the local time of the timezone if provided, and in UTC otherwise
+fn format_date_property(
+    name: &str,
+    datetime: &DateTime<Utc>,
+    all_day: bool,
+    tz: Option<Tz>,
+) -> String {
+    match tz {
+        _ if all_day => format!("{};VALUE=DATE:{}\r\n", name, datetime.format("%Y%m%d")),
+        Some(tz) => format!(
+            "{};TZID={}:{}\r\n",
+            name,
+            tz,
+            datetime.with_timezone(&tz).format("%Y%m%dT%H%M%S")
+        ),
+        None => format!("{}:{}\r\n", name, datetime.format("%Y%m%dT%H%M%SZ")),
+    }
+}
+
+fn ical_status(status: &CalendarEventStatus) -> &'static str {
+    match status {
+        CalendarEventStatus::Confirmed => "CONFIRMED",
+        CalendarEventStatus::Tentative => "TENTATIVE",
+        CalendarEventStatus::Cancelled => "CANCELLED",
+    }
+}
+
+fn ical_weekday(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+/// Folds the content lines longer than 75 octets (RFC 5545 3.1)
+///
+/// The continuation lines start with a space, and UTF-8 characters are never split
+fn fold_lines(content: &str) -> String {
+    const MAX_LINE_OCTETS: usize = 75;
+
+    let mut folded = String::with_capacity(content.len());
+    for line in content.split_terminator("\r\n") {
+        let mut line_octets = 0;
+        for c in line.chars() {
+            if line_octets + c.len_utf8() > MAX_LINE_OCTETS {
+                folded.push_str("\r\n ");
+                line_octets = 1;
+            }
+            folded.push(c);
+            line_octets += c.len_utf8();
+        }
+        folded.push_str("\r\n");
+    }
+    folded
+}
+
 /// Converts RRuleOptions to an iCalendar RRULE string
 ///
 /// This function transforms the internal recurrence rule representation into
@@ -285,8 +394,8 @@ fn recurrence_to_rrule_string(recurrence: &RRuleOptions) -> Option<String> {
         let weekdays: Vec<String> = byweekday
             .iter()
             .map(|wd| match wd.nth() {
-                None => format!("{}", wd.weekday()),
-                Some(n) => format!("{}{}", n, wd.weekday()),
+                None => ical_weekday(wd.weekday()).to_string(),
+                Some(n) => format!("{}{}", n, ical_weekday(wd.weekday())),
             })
             .collect();
         if !weekdays.is_empty() {
@@ -302,6 +411,22 @@ fn recurrence_to_rrule_string(recurrence: &RRuleOptions) -> Option<String> {
         }
     }
 
+    // Byyearday, byweekno, byhour, byminute and bysetpos
+    for (name, numbers) in [
+        ("BYYEARDAY", &recurrence.byyearday),
+        ("BYWEEKNO", &recurrence.byweekno),
+        ("BYHOUR", &recurrence.byhour),
+        ("BYMINUTE", &recurrence.byminute),
+        ("BYSETPOS", &recurrence.bysetpos),
+    ] {
+        if let Some(numbers) = numbers
+            && !numbers.is_empty()
+        {
+            let numbers: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+            rrule.push_str(&format!(";{}={}", name, numbers.join(",")));
+        }
+    }
+
     // Bymonth - convert chrono::Month to month number (1-12)
     if let Some(bymonth) = &recurrence.bymonth {
         let months: Vec<String> = bymonth
@@ -331,7 +456,7 @@ fn recurrence_to_rrule_string(recurrence: &RRuleOptions) -> Option<String> {
 
     // Weekstart
     if let Some(weekstart) = &recurrence.weekstart {
-        rrule.push_str(&format!(";WKST={}", weekstart));
+        rrule.push_str(&format!(";WKST={}", ical_weekday(*weekstart)));
     }
 
     Some(rrule)
@@ -362,8 +487,6 @@ fn escape_text(text: &str) -> String {
 #[cfg(test)]
 mod tests {
     use chrono::TimeZone;
-    use chrono_tz::UTC;
-    use rrule::Weekday;
 
     use super::*;
     use crate::CalendarSettings;
@@ -381,6 +504,7 @@ mod tests {
                 timezone: UTC,
             },
             metadata: None,
+            version: 0,
         };
 
         let events = vec![CalendarEvent {
@@ -412,6 +536,7 @@ mod tests {
                 .unwrap()
                 .with_timezone(&chrono::Utc),
             recurrence: None,
+            timezone: None,
             exdates: vec![],
             recurring_until: None,
             recurring_event_id: None,
@@ -420,8 +545,11 @@ mod tests {
             user_id: ID::default(),
             account_id: ID::default(),
             reminders: vec![],
+            attendees: vec![],
             service_id: None,
+            group_id: None,
             metadata: None,
+            version: 0,
         }];
 
         let ical_content = generate_ical_content(&calendar, &events, &[], &HashMap::new());
@@ -460,6 +588,9 @@ mod tests {
             bymonth: None,
             byyearday: None,
             byweekno: None,
+            byhour: None,
+            byminute: None,
+            rdates: None,
             weekstart: None,
         };
 
@@ -467,6 +598,199 @@ mod tests {
         assert_eq!(rrule, "FREQ=WEEKLY;INTERVAL=2;COUNT=10");
     }
 
+    #[test]
+    fn test_recurrence_to_rrule_string_with_weekdays() {
+        let recurrence = RRuleOptions {
+            freq: RRuleFrequency::Monthly,
+            byweekday: Some(vec!["Mon".parse().unwrap(), "-1Fri".parse().unwrap()]),
+            weekstart: Some(Weekday::Sun),
+            ..Default::default()
+        };
+
+        let rrule = recurrence_to_rrule_string(&recurrence).unwrap();
+        assert_eq!(rrule, "FREQ=MONTHLY;BYDAY=MO,-1FR;WKST=SU");
+    }
+
+    #[test]
+    fn test_recurrence_to_rrule_string_with_numbers() {
+        let recurrence = RRuleOptions {
+            freq: RRuleFrequency::Yearly,
+            byyearday: Some(vec![1, -1]),
+            byweekno: Some(vec![20]),
+            byhour: Some(vec![9, 17]),
+            byminute: Some(vec![0, 30]),
+            bysetpos: Some(vec![-1]),
+            ..Default::default()
+        };
+
+        let rrule = recurrence_to_rrule_string(&recurrence).unwrap();
+        assert_eq!(
+            rrule,
+            "FREQ=YEARLY;BYYEARDAY=1,-1;BYWEEKNO=20;BYHOUR=9,17;BYMINUTE=0,30;BYSETPOS=-1"
+        );
+    }
+
+    #[test]
+    fn test_fold_lines() {
+        let long_line = format!("DESCRIPTION:{}", "é".repeat(40));
+        let folded = fold_lines(&format!("BEGIN:VEVENT\r\n{long_line}\r\nEND:VEVENT\r\n"));
+
+        let lines = folded.split_terminator("\r\n").collect::<Vec<_>>();
+        assert_eq!(lines.len(), 4);
+        assert!(lines.iter().all(|line| line.len() <= 75));
+        assert!(lines[2].starts_with(' '));
+        assert_eq!(format!("{}{}", lines[1], &lines[2][1..]), long_line);
+    }
+
+    #[test]
+    fn test_round_trip_of_recurring_event_across_dst() {
+        let tz = chrono_tz::Europe::Oslo;
+        let local = |y, m, d, h| {
+            tz.with_ymd_and_hms(y, m, d, h, 0, 0)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let calendar = Calendar {
+            settings: CalendarSettings {
+                week_start: Weekday::Mon,
+                timezone: tz,
+            },
+            ..Calendar::new(&ID::default(), &ID::default(), None, None)
+        };
+
+        // Weekly at 09:00 in Oslo, from winter time to summer time (March 31, 2030)
+        let recurring_event = CalendarEvent {
+            title: Some("Weekly meeting".to_string()),
+            description: Some(
+                "A long description, which doesn't fit on a single line of the iCalendar content"
+                    .to_string(),
+            ),
+            start_time: local(2030, 3, 18, 9),
+            end_time: local(2030, 3, 18, 10),
+            duration: 1000 * 60 * 60,
+            recurrence: Some(RRuleOptions {
+                freq: RRuleFrequency::Weekly,
+                count: Some(4),
+                byhour: Some(vec![9]),
+                rdates: Some(vec![local(2030, 4, 10, 12)]),
+                ..Default::default()
+            }),
+            exdates: vec![local(2030, 3, 25, 9)],
+            ..Default::default()
+        };
+        let exception = CalendarEvent {
+            title: Some("Weekly meeting (moved)".to_string()),
+            start_time: local(2030, 4, 1, 14),
+            end_time: local(2030, 4, 1, 15),
+            duration: 1000 * 60 * 60,
+            recurring_event_id: Some(recurring_event.id.clone()),
+            original_start_time: Some(local(2030, 4, 1, 9)),
+            ..Default::default()
+        };
+        let exceptions = HashMap::from([(&recurring_event.id, vec![exception])]);
+
+        let ical_content = generate_ical_content(
+            &calendar,
+            &[],
+            std::slice::from_ref(&recurring_event),
+            &exceptions,
+        );
+        assert!(ical_content.contains("BEGIN:VTIMEZONE\r\nTZID:Europe/Oslo\r\n"));
+        assert!(ical_content.contains("DTSTART;TZID=Europe/Oslo:20300318T090000\r\n"));
+        assert!(ical_content.contains("EXDATE;TZID=Europe/Oslo:20300325T090000\r\n"));
+        assert!(ical_content.contains("RRULE:FREQ=WEEKLY;COUNT=4;BYHOUR=9\r\n"));
+        assert!(ical_content.contains("RDATE;TZID=Europe/Oslo:20300410T120000\r\n"));
+        assert!(ical_content.contains("RECURRENCE-ID;TZID=Europe/Oslo:20300401T090000\r\n"));
+        assert!(
+            ical_content
+                .split_terminator("\r\n")
+                .all(|line| line.len() <= 75)
+        );
+
+        // Parsed with the IANA timezone, and with the VTIMEZONE only (unknown TZID)
+        for ical_content in [
+            ical_content.clone(),
+            ical_content.replace("Europe/Oslo", "Custom Oslo"),
+        ] {
+            let parsed = parse_ical_events(&ical_content, UTC).unwrap();
+            assert!(parsed.skipped.is_empty());
+            assert_eq!(parsed.events.len(), 2);
+
+            let parsed_event = &parsed.events[0];
+            assert_eq!(parsed_event.uid, recurring_event.id.to_string());
+            assert_eq!(parsed_event.title, recurring_event.title);
+            assert_eq!(parsed_event.description, recurring_event.description);
+            assert_eq!(parsed_event.start_time, recurring_event.start_time);
+            assert_eq!(parsed_event.duration, recurring_event.duration);
+            assert_eq!(parsed_event.recurrence, recurring_event.recurrence);
+            assert_eq!(parsed_event.exdates, recurring_event.exdates);
+
+            let parsed_exception = &parsed.events[1];
+            assert_eq!(parsed_exception.recurrence_id, Some(local(2030, 4, 1, 9)));
+            assert_eq!(parsed_exception.start_time, local(2030, 4, 1, 14));
+        }
+    }
+
+    #[test]
+    fn test_events_in_their_own_timezone() {
+        let paris = chrono_tz::Europe::Paris;
+        let calendar = Calendar::new(&ID::default(), &ID::default(), None, None);
+        let start_time = paris
+            .with_ymd_and_hms(2030, 3, 18, 9, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let recurring_event = CalendarEvent {
+            start_time,
+            end_time: start_time + chrono::TimeDelta::hours(1),
+            duration: 1000 * 60 * 60,
+            recurrence: Some(RRuleOptions {
+                freq: RRuleFrequency::Weekly,
+                count: Some(4),
+                ..Default::default()
+            }),
+            timezone: Some(paris),
+            ..Default::default()
+        };
+        let utc_recurring_event = CalendarEvent {
+            id: ID::default(),
+            timezone: None,
+            ..recurring_event.clone()
+        };
+        let normal_event = CalendarEvent {
+            start_time,
+            end_time: start_time + chrono::TimeDelta::hours(1),
+            duration: 1000 * 60 * 60,
+            timezone: Some(chrono_tz::Asia::Tokyo),
+            ..Default::default()
+        };
+
+        // The calendar is in UTC, the events are written in their own timezones
+        let ical_content = generate_ical_content(
+            &calendar,
+            std::slice::from_ref(&normal_event),
+            &[recurring_event.clone(), utc_recurring_event.clone()],
+            &HashMap::new(),
+        );
+        assert_eq!(ical_content.matches("BEGIN:VTIMEZONE").count(), 2);
+        assert!(ical_content.contains("BEGIN:VTIMEZONE\r\nTZID:Asia/Tokyo\r\n"));
+        assert!(ical_content.contains("BEGIN:VTIMEZONE\r\nTZID:Europe/Paris\r\n"));
+        assert!(ical_content.contains("DTSTART;TZID=Asia/Tokyo:20300318T170000\r\n"));
+        assert!(ical_content.contains("DTSTART;TZID=Europe/Paris:20300318T090000\r\n"));
+        assert!(ical_content.contains("DTSTART:20300318T080000Z\r\n"));
+
+        let parsed = parse_ical_events(&ical_content, UTC).unwrap();
+        let timezones = parsed
+            .events
+            .iter()
+            .map(|event| event.timezone)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            timezones,
+            vec![Some(chrono_tz::Asia::Tokyo), Some(paris), None]
+        );
+    }
+
     #[test]
     fn test_escape_text() {
         let text = "Hello; World, with\nnewlines\r";
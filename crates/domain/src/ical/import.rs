@@ -0,0 +1,1169 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{
+    DateTime,
+    FixedOffset,
+    Month,
+    NaiveDate,
+    NaiveDateTime,
+    NaiveTime,
+    TimeDelta,
+    TimeZone,
+    Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use rrule::RRuleSet;
+use thiserror::Error;
+
+use crate::{CalendarEvent, CalendarEventStatus, RRuleFrequency, RRuleOptions, WeekDayRecurrence};
+
+/// Error making the whole iCalendar content unusable
+/// Errors specific to an event only skip this event (see `IcalSkippedEvent`)
+#[derive(Debug, Error, PartialEq)]
+pub enum IcalParseError {
+    #[error("The content is not an iCalendar (no VCALENDAR component)")]
+    MissingCalendar,
+    #[error("Malformed content line {0}")]
+    MalformedLine(usize),
+    #[error("Component {0} is not closed properly")]
+    UnbalancedComponent(String),
+}
+
+/// Event (VEVENT) parsed from an iCalendar file
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcalEvent {
+    /// UID of the event, shared by a recurring event and its modified occurrences
+    pub uid: String,
+    /// Original start time of the occurrence (RECURRENCE-ID), if this is a modified occurrence of a recurring event
+    pub recurrence_id: Option<DateTime<Utc>>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub status: CalendarEventStatus,
+    pub all_day: bool,
+    pub start_time: DateTime<Utc>,
+    /// Duration in milliseconds
+    pub duration: i64,
+    pub busy: bool,
+    pub recurrence: Option<RRuleOptions>,
+    /// IANA timezone of the start time (TZID), in which the recurrence is expanded
+    pub timezone: Option<Tz>,
+    pub exdates: Vec<DateTime<Utc>>,
+}
+
+impl IcalEvent {
+    /// Apply the event to a calendar event (new or previously imported)
+    /// Returns the names of the fields which changed, or None if the recurrence is invalid
+    pub fn apply(&self, event: &mut CalendarEvent) -> Option<Vec<&'static str>> {
+        let mut changed_fields = Vec::new();
+        let mut set = |changed: bool, field: &'static str| {
+            if changed {
+                changed_fields.push(field);
+            }
+        };
+
+        set(event.title != self.title, "title");
+        set(event.description != self.description, "description");
+        set(event.location != self.location, "location");
+        set(event.status != self.status, "status");
+        set(event.all_day != self.all_day, "allDay");
+        set(event.start_time != self.start_time, "startTime");
+        set(event.duration != self.duration, "duration");
+        set(event.busy != self.busy, "busy");
+        set(event.recurrence != self.recurrence, "recurrence");
+        set(event.timezone != self.timezone, "timezone");
+        set(event.exdates != self.exdates, "exdates");
+        set(
+            event.original_start_time != self.recurrence_id,
+            "originalStartTime",
+        );
+
+        event.title = self.title.clone();
+        event.description = self.description.clone();
+        event.location = self.location.clone();
+        event.status = self.status.clone();
+        event.all_day = self.all_day;
+        event.start_time = self.start_time;
+        event.duration = self.duration;
+        event.end_time = self.start_time + TimeDelta::milliseconds(self.duration);
+        event.busy = self.busy;
+        event.timezone = self.timezone;
+        event.exdates = self.exdates.clone();
+        event.original_start_time = self.recurrence_id;
+        match &self.recurrence {
+            Some(recurrence) => {
+                if !event.set_recurrence(recurrence.clone()).unwrap_or(false) {
+                    return None;
+                }
+            }
+            None => {
+                event.recurrence = None;
+                event.recurring_until = None;
+            }
+        }
+
+        Some(changed_fields)
+    }
+}
+
+/// Event (VEVENT) which can't be imported
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcalSkippedEvent {
+    /// UID of the event, if it has one
+    pub uid: Option<String>,
+    /// Why the event is skipped
+    pub reason: String,
+}
+
+/// Events parsed from an iCalendar file
+#[derive(Debug, Default, PartialEq)]
+pub struct ParsedIcal {
+    pub events: Vec<IcalEvent>,
+    pub skipped: Vec<IcalSkippedEvent>,
+}
+
+/// Parse the events of iCalendar content
+///
+/// Supported:
+/// - Single and recurring events (RRULE, RDATE, EXDATE), and modified occurrences (RECURRENCE-ID)
+/// - All-day events (`VALUE=DATE`), kept at midnight UTC like the events exported
+/// - Date-times in UTC, with a TZID (IANA name, or defined by a VTIMEZONE of the file), or floating
+///
+/// Floating date-times are in the timezone of the file (X-WR-TIMEZONE), or `default_timezone` if it has none.
+/// The events using something unsupported (e.g. RDATE periods, or an hourly RRULE) are skipped with the reason.
+pub fn parse_ical_events(
+    content: &str,
+    default_timezone: Tz,
+) -> Result<ParsedIcal, IcalParseError> {
+    let calendars = parse_components(content)?;
+    if calendars.is_empty() {
+        return Err(IcalParseError::MissingCalendar);
+    }
+
+    let mut parsed = ParsedIcal::default();
+    for calendar in calendars {
+        let timezones = Timezones {
+            default: calendar
+                .property("X-WR-TIMEZONE")
+                .and_then(|tz| iana_timezone(&tz.value))
+                .unwrap_or(default_timezone),
+            embedded: calendar
+                .components("VTIMEZONE")
+                .filter_map(|vtimezone| {
+                    let tzid = vtimezone.property("TZID")?.value.clone();
+                    Some((tzid, EmbeddedTimezone::new(vtimezone)))
+                })
+                .collect(),
+        };
+
+        for vevent in calendar.components("VEVENT") {
+            match parse_vevent(vevent, &timezones) {
+                Ok(event) => parsed.events.push(event),
+                Err(reason) => parsed.skipped.push(IcalSkippedEvent {
+                    uid: vevent.property("UID").map(|uid| uid.value.clone()),
+                    reason,
+                }),
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Property of a component, e.g. `DTSTART;TZID=Europe/Oslo:20240115T100000`
+#[derive(Debug, Clone, PartialEq)]
+struct Property {
+    /// Name of the property (uppercase)
+    name: String,
+    /// Parameters of the property, names in uppercase and values without quotes
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the value is a date (e.g. all-day event), instead of a date-time
+    fn is_date(&self) -> bool {
+        self.param("VALUE")
+            .is_some_and(|value| value.eq_ignore_ascii_case("DATE"))
+    }
+}
+
+/// Component, e.g. VCALENDAR, VEVENT or VTIMEZONE
+#[derive(Debug, Default)]
+struct Component {
+    /// Name of the component (uppercase)
+    name: String,
+    properties: Vec<Property>,
+    components: Vec<Component>,
+}
+
+impl Component {
+    fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.iter().find(|p| p.name == name)
+    }
+
+    fn properties<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties.iter().filter(move |p| p.name == name)
+    }
+
+    fn components<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Component> {
+        self.components.iter().filter(move |c| c.name == name)
+    }
+}
+
+/// Unfold the content lines (a line starting with a space or a tab continues the previous one)
+/// Returns the lines with their (first) line number
+fn unfold_lines(content: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if let Some(continuation) = line.strip_prefix([' ', '\t'])
+            && let Some((_, previous)) = lines.last_mut()
+        {
+            previous.push_str(continuation);
+        } else if !line.trim().is_empty() {
+            lines.push((index + 1, line.to_string()));
+        }
+    }
+    lines
+}
+
+/// Parse a content line: `NAME;PARAM=VALUE;PARAM="QUOTED VALUE":VALUE`
+fn parse_property(line: &str) -> Option<Property> {
+    let mut name = String::new();
+    let mut params = Vec::new();
+    let mut chars = line.char_indices().peekable();
+
+    // Name
+    let mut delimiter = None;
+    for (index, c) in chars.by_ref() {
+        if c == ';' || c == ':' {
+            delimiter = Some((index, c));
+            break;
+        }
+        name.push(c);
+    }
+    let (mut index, mut delimiter) = delimiter?;
+
+    // Parameters
+    while delimiter == ';' {
+        let mut param_name = String::new();
+        let mut param_value = String::new();
+        let mut in_value = false;
+        let mut in_quotes = false;
+        let mut next = None;
+        for (i, c) in chars.by_ref() {
+            match c {
+                '"' if in_value => in_quotes = !in_quotes,
+                '=' if !in_value => in_value = true,
+                ';' | ':' if !in_quotes => {
+                    next = Some((i, c));
+                    break;
+                }
+                _ if in_value => param_value.push(c),
+                _ => param_name.push(c),
+            }
+        }
+        (index, delimiter) = next?;
+        params.push((param_name.trim().to_uppercase(), param_value));
+    }
+
+    let name = name.trim().to_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+    Some(Property {
+        name,
+        params,
+        value: line[index + 1..].to_string(),
+    })
+}
+
+/// Parse the components of the content, and return the VCALENDAR ones
+fn parse_components(content: &str) -> Result<Vec<Component>, IcalParseError> {
+    let mut calendars = Vec::new();
+    let mut stack: Vec<Component> = Vec::new();
+
+    for (line_number, line) in unfold_lines(content) {
+        let property = parse_property(&line).ok_or(IcalParseError::MalformedLine(line_number))?;
+        match property.name.as_str() {
+            "BEGIN" => stack.push(Component {
+                name: property.value.trim().to_uppercase(),
+                ..Default::default()
+            }),
+            "END" => {
+                let name = property.value.trim().to_uppercase();
+                let component = stack
+                    .pop()
+                    .filter(|component| component.name == name)
+                    .ok_or_else(|| IcalParseError::UnbalancedComponent(name.clone()))?;
+                match stack.last_mut() {
+                    Some(parent) => parent.components.push(component),
+                    None if component.name == "VCALENDAR" => calendars.push(component),
+                    None => (),
+                }
+            }
+            _ => {
+                // Properties outside of a component are ignored
+                if let Some(component) = stack.last_mut() {
+                    component.properties.push(property);
+                }
+            }
+        }
+    }
+
+    if let Some(component) = stack.pop() {
+        return Err(IcalParseError::UnbalancedComponent(component.name));
+    }
+
+    Ok(calendars)
+}
+
+/// Unescape a text value (`\n`, `\,`, `\;` and `\\`)
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Find the IANA timezone of a TZID
+/// Some producers prefix it, e.g. `/mozilla.org/20050126_1/America/New_York`
+fn iana_timezone(tzid: &str) -> Option<Tz> {
+    let tzid = tzid.trim().trim_matches('"');
+    Tz::from_str(tzid).ok().or_else(|| {
+        tzid.match_indices('/')
+            .find_map(|(index, _)| Tz::from_str(&tzid[index + 1..]).ok())
+    })
+}
+
+/// Parse a UTC offset, e.g. `+0100`, `-0500` or `+053000`
+fn parse_utc_offset(value: &str) -> Option<FixedOffset> {
+    let value = value.trim();
+    let sign = match value.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let digits = value.get(1..)?;
+    if digits.len() != 4 && digits.len() != 6 {
+        return None;
+    }
+    let hours = digits.get(0..2)?.parse::<i32>().ok()?;
+    let minutes = digits.get(2..4)?.parse::<i32>().ok()?;
+    let seconds = digits
+        .get(4..)
+        .filter(|s| !s.is_empty())
+        .map_or(Some(0), |s| s.parse::<i32>().ok())?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+/// Parse a duration, e.g. `PT1H30M`, `P1D` or `P2W`
+fn parse_duration(value: &str) -> Option<TimeDelta> {
+    let value = value.trim();
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => (-1, value),
+        None => (1, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let value = value.strip_prefix('P')?;
+
+    let mut duration = TimeDelta::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' => number.push(c),
+            _ => {
+                let n = number.parse::<i64>().ok()?;
+                number.clear();
+                duration += match (c, in_time) {
+                    ('W', false) => TimeDelta::try_weeks(n)?,
+                    ('D', false) => TimeDelta::try_days(n)?,
+                    ('H', true) => TimeDelta::try_hours(n)?,
+                    ('M', true) => TimeDelta::try_minutes(n)?,
+                    ('S', true) => TimeDelta::try_seconds(n)?,
+                    _ => return None,
+                };
+            }
+        }
+    }
+    if !number.is_empty() {
+        return None;
+    }
+
+    Some(duration * sign)
+}
+
+/// Date or date-time value
+enum IcalDateValue {
+    Date(NaiveDate),
+    Utc(NaiveDateTime),
+    /// Local date-time (with a TZID, or floating)
+    Local(NaiveDateTime),
+}
+
+fn parse_date_value(value: &str) -> Option<IcalDateValue> {
+    let value = value.trim();
+    if value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d")
+            .ok()
+            .map(IcalDateValue::Date);
+    }
+    match value.strip_suffix('Z') {
+        Some(value) => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(IcalDateValue::Utc),
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(IcalDateValue::Local),
+    }
+}
+
+/// Observance (STANDARD or DAYLIGHT) of a VTIMEZONE
+struct Observance {
+    /// Local time of the first onset
+    start: NaiveDateTime,
+    offset_to: FixedOffset,
+    /// Recurrence of the onsets (e.g. `FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU`)
+    rrule: Option<String>,
+    rdates: Vec<NaiveDateTime>,
+}
+
+impl Observance {
+    fn new(component: &Component) -> Option<Self> {
+        let start = match parse_date_value(&component.property("DTSTART")?.value)? {
+            IcalDateValue::Date(date) => date.and_time(NaiveTime::MIN),
+            IcalDateValue::Utc(datetime) | IcalDateValue::Local(datetime) => datetime,
+        };
+        Some(Self {
+            start,
+            offset_to: parse_utc_offset(&component.property("TZOFFSETTO")?.value)?,
+            rrule: component.property("RRULE").map(|rrule| rrule.value.clone()),
+            rdates: component
+                .properties("RDATE")
+                .flat_map(|rdate| rdate.value.split(','))
+                .filter_map(|value| match parse_date_value(value)? {
+                    IcalDateValue::Date(date) => Some(date.and_time(NaiveTime::MIN)),
+                    IcalDateValue::Utc(datetime) | IcalDateValue::Local(datetime) => Some(datetime),
+                })
+                .collect(),
+        })
+    }
+
+    /// Latest onset of the observance at or before the local time
+    fn last_onset(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.start > local {
+            return None;
+        }
+
+        let mut last_onset = self.start;
+        for rdate in &self.rdates {
+            if *rdate <= local && *rdate > last_onset {
+                last_onset = *rdate;
+            }
+        }
+
+        // The onsets are computed on the local times (UTC is only used as a stand-in)
+        // `before` is exclusive, but an onset at the local time is in effect
+        if let Some(rrule) = &self.rrule
+            && let Ok(rrule_set) = format!(
+                "DTSTART:{}Z\nRRULE:{}",
+                self.start.format("%Y%m%dT%H%M%S"),
+                rrule
+            )
+            .parse::<RRuleSet>()
+            && let Some(onset) = rrule_set
+                .before(rrule::Tz::UTC.from_utc_datetime(&(local + TimeDelta::seconds(1))))
+                .all(u16::MAX)
+                .dates
+                .last()
+            && onset.naive_utc() > last_onset
+        {
+            last_onset = onset.naive_utc();
+        }
+
+        Some(last_onset)
+    }
+}
+
+/// Timezone defined by a VTIMEZONE of the file
+struct EmbeddedTimezone {
+    observances: Vec<Observance>,
+}
+
+impl EmbeddedTimezone {
+    fn new(vtimezone: &Component) -> Self {
+        Self {
+            observances: vtimezone
+                .components
+                .iter()
+                .filter(|c| c.name == "STANDARD" || c.name == "DAYLIGHT")
+                .filter_map(Observance::new)
+                .collect(),
+        }
+    }
+
+    /// Offset from UTC in effect at the local time
+    fn offset_at(&self, local: NaiveDateTime) -> Option<FixedOffset> {
+        self.observances
+            .iter()
+            .filter_map(|o| o.last_onset(local).map(|onset| (onset, o.offset_to)))
+            .max_by_key(|(onset, _)| *onset)
+            .map(|(_, offset)| offset)
+            // Before the first onset, use the earliest observance
+            .or_else(|| {
+                self.observances
+                    .iter()
+                    .min_by_key(|o| o.start)
+                    .map(|o| o.offset_to)
+            })
+    }
+}
+
+/// Timezones used for converting the local date-times of the file to UTC
+struct Timezones {
+    /// Timezone of the floating date-times
+    default: Tz,
+    /// Timezones defined by the VTIMEZONEs of the file, by TZID
+    embedded: HashMap<String, EmbeddedTimezone>,
+}
+
+impl Timezones {
+    fn to_utc(&self, local: NaiveDateTime, tzid: Option<&str>) -> Result<DateTime<Utc>, String> {
+        let Some(tzid) = tzid else {
+            return Ok(local_to_utc(self.default, local));
+        };
+
+        // An IANA timezone is preferred to its VTIMEZONE, as it knows all the past and future rules
+        if let Some(tz) = iana_timezone(tzid) {
+            return Ok(local_to_utc(tz, local));
+        }
+        let offset = self
+            .embedded
+            .get(tzid)
+            .and_then(|timezone| timezone.offset_at(local))
+            .ok_or_else(|| format!("Unknown timezone: {tzid}"))?;
+        Ok((local - offset).and_utc())
+    }
+
+    /// Convert a DTSTART, DTEND, RECURRENCE-ID, RDATE or EXDATE value to UTC
+    /// Returns whether the value is a date (all-day) as well
+    fn date_to_utc(
+        &self,
+        property: &Property,
+        value: &str,
+    ) -> Result<(DateTime<Utc>, bool), String> {
+        let invalid = || format!("Invalid {} value: {}", property.name, value);
+        let value = parse_date_value(value).ok_or_else(invalid)?;
+        match value {
+            IcalDateValue::Date(date) => Ok((date.and_time(NaiveTime::MIN).and_utc(), true)),
+            _ if property.is_date() => Err(invalid()),
+            IcalDateValue::Utc(datetime) => Ok((datetime.and_utc(), false)),
+            IcalDateValue::Local(datetime) => self
+                .to_utc(datetime, property.param("TZID"))
+                .map(|datetime| (datetime, false)),
+        }
+    }
+}
+
+/// Convert a local date-time to UTC
+/// A date-time skipped by a DST transition is moved forward by the transition (as per RFC 5545)
+fn local_to_utc(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    tz.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&(local + TimeDelta::hours(1)))
+                .earliest()
+        })
+        .map(|datetime| datetime.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    Some(match value {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+fn parse_numbers(value: &str) -> Option<Vec<isize>> {
+    value
+        .split(',')
+        .map(|n| n.trim().trim_start_matches('+').parse::<isize>().ok())
+        .collect()
+}
+
+/// Parse the value of a RRULE
+/// A floating (or date) UNTIL is in the timezone of the event
+fn parse_rrule(
+    value: &str,
+    dtstart: &Property,
+    timezones: &Timezones,
+) -> Result<RRuleOptions, String> {
+    let invalid = |part: &str| format!("Invalid RRULE part: {part}");
+    let mut rrule = RRuleOptions::default();
+    let mut freq = None;
+
+    for part in value.split(';').filter(|part| !part.trim().is_empty()) {
+        let (name, value) = part.split_once('=').ok_or_else(|| invalid(part))?;
+        let value = value.trim();
+        match name.trim().to_uppercase().as_str() {
+            "FREQ" => {
+                freq = Some(match value.to_uppercase().as_str() {
+                    "YEARLY" => RRuleFrequency::Yearly,
+                    "MONTHLY" => RRuleFrequency::Monthly,
+                    "WEEKLY" => RRuleFrequency::Weekly,
+                    "DAILY" => RRuleFrequency::Daily,
+                    freq => return Err(format!("Unsupported RRULE frequency: {freq}")),
+                })
+            }
+            "INTERVAL" => {
+                rrule.interval = value
+                    .parse::<isize>()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or_else(|| invalid(part))?
+            }
+            "COUNT" => rrule.count = Some(value.parse::<i32>().map_err(|_| invalid(part))?),
+            "UNTIL" => {
+                let until = match parse_date_value(value).ok_or_else(|| invalid(part))? {
+                    // The last day is included
+                    IcalDateValue::Date(date) => {
+                        let end_of_day = date.and_time(NaiveTime::MIN) + TimeDelta::days(1)
+                            - TimeDelta::seconds(1);
+                        if dtstart.is_date() || dtstart.value.trim().len() == 8 {
+                            end_of_day.and_utc()
+                        } else {
+                            timezones.to_utc(end_of_day, dtstart.param("TZID"))?
+                        }
+                    }
+                    IcalDateValue::Utc(datetime) => datetime.and_utc(),
+                    IcalDateValue::Local(datetime) => {
+                        timezones.to_utc(datetime, dtstart.param("TZID"))?
+                    }
+                };
+                rrule.until = Some(until);
+            }
+            "BYDAY" => {
+                rrule.byweekday = Some(
+                    value
+                        .split(',')
+                        .map(|day| {
+                            let day = day.trim().to_uppercase();
+                            let (n, weekday) = day.split_at(day.len().saturating_sub(2));
+                            let weekday = parse_weekday(weekday)?;
+                            if n.is_empty() {
+                                WeekDayRecurrence::new(weekday)
+                            } else {
+                                WeekDayRecurrence::new_nth(
+                                    weekday,
+                                    n.trim_start_matches('+').parse().ok()?,
+                                )
+                            }
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(|| invalid(part))?,
+                )
+            }
+            "BYMONTHDAY" => {
+                rrule.bymonthday = Some(parse_numbers(value).ok_or_else(|| invalid(part))?)
+            }
+            "BYYEARDAY" => {
+                rrule.byyearday = Some(parse_numbers(value).ok_or_else(|| invalid(part))?)
+            }
+            "BYWEEKNO" => rrule.byweekno = Some(parse_numbers(value).ok_or_else(|| invalid(part))?),
+            "BYSETPOS" => rrule.bysetpos = Some(parse_numbers(value).ok_or_else(|| invalid(part))?),
+            "BYHOUR" => rrule.byhour = Some(parse_numbers(value).ok_or_else(|| invalid(part))?),
+            "BYMINUTE" => rrule.byminute = Some(parse_numbers(value).ok_or_else(|| invalid(part))?),
+            "BYMONTH" => {
+                rrule.bymonth = Some(
+                    parse_numbers(value)
+                        .and_then(|months| {
+                            months
+                                .into_iter()
+                                .map(|m| u8::try_from(m).ok().and_then(|m| Month::try_from(m).ok()))
+                                .collect::<Option<Vec<_>>>()
+                        })
+                        .ok_or_else(|| invalid(part))?,
+                )
+            }
+            "WKST" => {
+                rrule.weekstart =
+                    Some(parse_weekday(&value.to_uppercase()).ok_or_else(|| invalid(part))?)
+            }
+            name => return Err(format!("Unsupported RRULE part: {name}")),
+        }
+    }
+
+    rrule.freq = freq.ok_or_else(|| "RRULE without FREQ".to_string())?;
+    Ok(rrule)
+}
+
+fn parse_vevent(vevent: &Component, timezones: &Timezones) -> Result<IcalEvent, String> {
+    let uid = vevent
+        .property("UID")
+        .map(|uid| uid.value.trim().to_string())
+        .filter(|uid| !uid.is_empty())
+        .ok_or("Missing UID")?;
+
+    let dtstart = vevent.property("DTSTART").ok_or("Missing DTSTART")?;
+    let (start_time, all_day) = timezones.date_to_utc(dtstart, &dtstart.value)?;
+    let timezone = dtstart
+        .param("TZID")
+        .filter(|_| !all_day)
+        .and_then(iana_timezone);
+
+    let duration = if let Some(dtend) = vevent.property("DTEND") {
+        let (end_time, _) = timezones.date_to_utc(dtend, &dtend.value)?;
+        end_time - start_time
+    } else if let Some(duration) = vevent.property("DURATION") {
+        parse_duration(&duration.value)
+            .ok_or_else(|| format!("Invalid DURATION value: {}", duration.value))?
+    } else if all_day {
+        TimeDelta::days(1)
+    } else {
+        TimeDelta::zero()
+    };
+    if duration < TimeDelta::zero() {
+        return Err("The event ends before it starts".into());
+    }
+
+    let recurrence_id = match vevent.property("RECURRENCE-ID") {
+        Some(recurrence_id) => {
+            if recurrence_id
+                .param("RANGE")
+                .is_some_and(|range| range.eq_ignore_ascii_case("THISANDFUTURE"))
+            {
+                return Err("RECURRENCE-ID with RANGE=THISANDFUTURE is not supported".into());
+            }
+            Some(
+                timezones
+                    .date_to_utc(recurrence_id, &recurrence_id.value)?
+                    .0,
+            )
+        }
+        None => None,
+    };
+
+    let mut rdates = Vec::new();
+    for rdate in vevent.properties("RDATE") {
+        if rdate
+            .param("VALUE")
+            .is_some_and(|value| value.eq_ignore_ascii_case("PERIOD"))
+        {
+            return Err("RDATE periods are not supported".into());
+        }
+        for value in rdate.value.split(',') {
+            rdates.push(timezones.date_to_utc(rdate, value)?.0);
+        }
+    }
+    rdates.sort();
+    rdates.dedup();
+
+    let mut rrules = vevent.properties("RRULE");
+    let mut recurrence = match (rrules.next(), rrules.next()) {
+        // The RRULE and RDATEs of a modified occurrence are ignored, it is only an occurrence of the recurring event
+        _ if recurrence_id.is_some() => None,
+        // An event with RDATEs only occurs at its start and at these dates
+        (None, _) if !rdates.is_empty() => Some(RRuleOptions {
+            count: Some(1),
+            ..Default::default()
+        }),
+        (None, _) => None,
+        (Some(rrule), None) => Some(parse_rrule(&rrule.value, dtstart, timezones)?),
+        (Some(_), Some(_)) => return Err("Multiple RRULEs are not supported".into()),
+    };
+    if let Some(recurrence) = &mut recurrence
+        && !rdates.is_empty()
+    {
+        recurrence.rdates = Some(rdates);
+    }
+
+    let mut exdates = Vec::new();
+    if recurrence.is_some() {
+        for exdate in vevent.properties("EXDATE") {
+            for value in exdate.value.split(',') {
+                exdates.push(timezones.date_to_utc(exdate, value)?.0);
+            }
+        }
+        exdates.sort();
+        exdates.dedup();
+    }
+
+    let status = match vevent
+        .property("STATUS")
+        .map(|status| status.value.trim().to_uppercase())
+        .as_deref()
+    {
+        Some("TENTATIVE") => CalendarEventStatus::Tentative,
+        Some("CANCELLED") => CalendarEventStatus::Cancelled,
+        _ => CalendarEventStatus::Confirmed,
+    };
+    let busy = !vevent
+        .property("TRANSP")
+        .is_some_and(|transp| transp.value.trim().eq_ignore_ascii_case("TRANSPARENT"));
+    let text = |name: &str| {
+        vevent
+            .property(name)
+            .map(|p| unescape_text(&p.value))
+            .filter(|text| !text.is_empty())
+    };
+
+    Ok(IcalEvent {
+        uid,
+        recurrence_id,
+        title: text("SUMMARY"),
+        description: text("DESCRIPTION"),
+        location: text("LOCATION"),
+        status,
+        all_day,
+        start_time,
+        duration: duration.num_milliseconds(),
+        busy,
+        recurrence,
+        timezone,
+        exdates,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> ParsedIcal {
+        parse_ical_events(content, chrono_tz::UTC).unwrap()
+    }
+
+    fn utc(datetime: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(datetime).unwrap().to_utc()
+    }
+
+    #[test]
+    fn it_rejects_content_without_calendar() {
+        assert_eq!(
+            parse_ical_events("BEGIN:VCALENDAR\r\nnot a content line\r\n", chrono_tz::UTC),
+            Err(IcalParseError::MalformedLine(2))
+        );
+        assert_eq!(
+            parse_ical_events("", chrono_tz::UTC),
+            Err(IcalParseError::MissingCalendar)
+        );
+        assert_eq!(
+            parse_ical_events(
+                "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nEND:VCALENDAR\r\n",
+                chrono_tz::UTC
+            ),
+            Err(IcalParseError::UnbalancedComponent("VCALENDAR".into()))
+        );
+    }
+
+    #[test]
+    fn it_parses_events() {
+        let parsed = parse(
+            "BEGIN:VCALENDAR\r\n\
+             VERSION:2.0\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:event-1@example.com\r\n\
+             SUMMARY:Team meeting\\, weekly\r\n\
+             DESCRIPTION:First line\\nSecond line with a very long text which is folded acro\r\n ss two lines\r\n\
+             LOCATION;LANGUAGE=en:Room \"A\": 1st floor\r\n\
+             DTSTART:20240115T100000Z\r\n\
+             DTEND:20240115T110000Z\r\n\
+             STATUS:TENTATIVE\r\n\
+             TRANSP:TRANSPARENT\r\n\
+             BEGIN:VALARM\r\n\
+             TRIGGER:-PT15M\r\n\
+             END:VALARM\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:event-2@example.com\r\n\
+             DTSTART;VALUE=DATE:20240120\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:event-3@example.com\r\n\
+             DTSTART:20240121T090000\r\n\
+             DURATION:PT1H30M\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        );
+        assert!(parsed.skipped.is_empty());
+        assert_eq!(parsed.events.len(), 3);
+
+        let event = &parsed.events[0];
+        assert_eq!(event.uid, "event-1@example.com");
+        assert_eq!(event.title.as_deref(), Some("Team meeting, weekly"));
+        assert_eq!(
+            event.description.as_deref(),
+            Some("First line\nSecond line with a very long text which is folded across two lines")
+        );
+        assert_eq!(event.location.as_deref(), Some("Room \"A\": 1st floor"));
+        assert_eq!(event.start_time, utc("2024-01-15T10:00:00Z"));
+        assert_eq!(event.duration, 1000 * 60 * 60);
+        assert_eq!(event.status, CalendarEventStatus::Tentative);
+        assert!(!event.busy);
+        assert!(!event.all_day);
+
+        let event = &parsed.events[1];
+        assert!(event.all_day);
+        assert_eq!(event.start_time, utc("2024-01-20T00:00:00Z"));
+        assert_eq!(event.duration, 1000 * 60 * 60 * 24);
+        assert_eq!(event.status, CalendarEventStatus::Confirmed);
+        assert!(event.busy);
+
+        // Floating time, in the default timezone
+        let event = &parsed.events[2];
+        assert_eq!(event.start_time, utc("2024-01-21T09:00:00Z"));
+        assert_eq!(event.duration, 1000 * 60 * 90);
+    }
+
+    #[test]
+    fn it_parses_recurring_events_and_modified_occurrences() {
+        let parsed = parse(
+            "BEGIN:VCALENDAR\n\
+             X-WR-TIMEZONE:Europe/Oslo\n\
+             BEGIN:VEVENT\n\
+             UID:recurring\n\
+             DTSTART;TZID=Europe/Oslo:20240101T090000\n\
+             DTEND;TZID=Europe/Oslo:20240101T093000\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,-1FR;UNTIL=20240331T000000Z;WKST=SU\n\
+             EXDATE;TZID=Europe/Oslo:20240115T090000,20240129T090000\n\
+             EXDATE:20240212T080000Z\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             UID:recurring\n\
+             RECURRENCE-ID;TZID=Europe/Oslo:20240226T090000\n\
+             DTSTART:20240226T100000\n\
+             DTEND:20240226T103000\n\
+             STATUS:CANCELLED\n\
+             END:VEVENT\n\
+             END:VCALENDAR\n",
+        );
+        assert!(parsed.skipped.is_empty());
+        assert_eq!(parsed.events.len(), 2);
+
+        let event = &parsed.events[0];
+        assert_eq!(event.start_time, utc("2024-01-01T08:00:00Z"));
+        assert_eq!(event.duration, 1000 * 60 * 30);
+        let recurrence = event.recurrence.clone().unwrap();
+        assert_eq!(recurrence.freq, RRuleFrequency::Weekly);
+        assert_eq!(recurrence.interval, 2);
+        assert_eq!(recurrence.until, Some(utc("2024-03-31T00:00:00Z")));
+        assert_eq!(
+            recurrence.byweekday,
+            Some(vec![
+                WeekDayRecurrence::new(Weekday::Mon).unwrap(),
+                WeekDayRecurrence::new_nth(Weekday::Fri, -1).unwrap(),
+            ])
+        );
+        assert_eq!(recurrence.weekstart, Some(Weekday::Sun));
+        assert_eq!(
+            event.exdates,
+            vec![
+                utc("2024-01-15T08:00:00Z"),
+                utc("2024-01-29T08:00:00Z"),
+                utc("2024-02-12T08:00:00Z"),
+            ]
+        );
+
+        // Floating times are in the timezone of the calendar (X-WR-TIMEZONE)
+        let occurrence = &parsed.events[1];
+        assert_eq!(occurrence.uid, "recurring");
+        assert_eq!(occurrence.recurrence_id, Some(utc("2024-02-26T08:00:00Z")));
+        assert_eq!(occurrence.start_time, utc("2024-02-26T09:00:00Z"));
+        assert_eq!(occurrence.status, CalendarEventStatus::Cancelled);
+        assert!(occurrence.recurrence.is_none());
+    }
+
+    #[test]
+    fn it_parses_rdates_and_time_rules() {
+        let parsed = parse(
+            "BEGIN:VCALENDAR\n\
+             BEGIN:VEVENT\n\
+             UID:recurring\n\
+             DTSTART;TZID=Europe/Oslo:20240101T090000\n\
+             RRULE:FREQ=WEEKLY;COUNT=4;BYHOUR=9,14;BYMINUTE=0,30;BYWEEKNO=1,-1;BYYEARDAY=1\n\
+             RDATE;TZID=Europe/Oslo:20240110T120000,20240103T120000\n\
+             RDATE:20240110T110000Z\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             UID:rdates\n\
+             DTSTART:20240101T090000Z\n\
+             RDATE:20240102T090000Z\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             UID:periods\n\
+             DTSTART:20240101T090000Z\n\
+             RDATE;VALUE=PERIOD:20240102T090000Z/PT1H\n\
+             END:VEVENT\n\
+             END:VCALENDAR\n",
+        );
+        assert_eq!(parsed.events.len(), 2);
+
+        let recurrence = parsed.events[0].recurrence.clone().unwrap();
+        assert_eq!(recurrence.byhour, Some(vec![9, 14]));
+        assert_eq!(recurrence.byminute, Some(vec![0, 30]));
+        assert_eq!(recurrence.byweekno, Some(vec![1, -1]));
+        assert_eq!(recurrence.byyearday, Some(vec![1]));
+        // The RDATEs are sorted and deduplicated
+        assert_eq!(
+            recurrence.rdates,
+            Some(vec![
+                utc("2024-01-03T11:00:00Z"),
+                utc("2024-01-10T11:00:00Z")
+            ])
+        );
+
+        // Without a RRULE, the event occurs once and at its RDATEs
+        let recurrence = parsed.events[1].recurrence.clone().unwrap();
+        assert_eq!(recurrence.count, Some(1));
+        assert_eq!(recurrence.rdates, Some(vec![utc("2024-01-02T09:00:00Z")]));
+
+        assert_eq!(
+            parsed.skipped,
+            vec![IcalSkippedEvent {
+                uid: Some("periods".into()),
+                reason: "RDATE periods are not supported".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn it_uses_the_embedded_timezones() {
+        let parsed = parse(
+            "BEGIN:VCALENDAR\r\n\
+             BEGIN:VTIMEZONE\r\n\
+             TZID:Pacific Standard Time\r\n\
+             BEGIN:STANDARD\r\n\
+             DTSTART:16011104T020000\r\n\
+             RRULE:FREQ=YEARLY;BYDAY=1SU;BYMONTH=11\r\n\
+             TZOFFSETFROM:-0700\r\n\
+             TZOFFSETTO:-0800\r\n\
+             END:STANDARD\r\n\
+             BEGIN:DAYLIGHT\r\n\
+             DTSTART:16010311T020000\r\n\
+             RRULE:FREQ=YEARLY;BYDAY=2SU;BYMONTH=3\r\n\
+             TZOFFSETFROM:-0800\r\n\
+             TZOFFSETTO:-0700\r\n\
+             END:DAYLIGHT\r\n\
+             END:VTIMEZONE\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:winter\r\n\
+             DTSTART;TZID=Pacific Standard Time:20240115T090000\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:summer\r\n\
+             DTSTART;TZID=\"Pacific Standard Time\":20240715T090000\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:prefixed\r\n\
+             DTSTART;TZID=/mozilla.org/20050126_1/America/New_York:20240715T090000\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:unknown\r\n\
+             DTSTART;TZID=Somewhere:20240715T090000\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        );
+        assert_eq!(parsed.events.len(), 3);
+        assert_eq!(parsed.events[0].start_time, utc("2024-01-15T17:00:00Z"));
+        assert_eq!(parsed.events[1].start_time, utc("2024-07-15T16:00:00Z"));
+        assert_eq!(parsed.events[2].start_time, utc("2024-07-15T13:00:00Z"));
+        assert_eq!(
+            parsed.skipped,
+            vec![IcalSkippedEvent {
+                uid: Some("unknown".into()),
+                reason: "Unknown timezone: Somewhere".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn it_skips_unsupported_events() {
+        let parsed = parse(
+            "BEGIN:VCALENDAR\r\n\
+             BEGIN:VEVENT\r\n\
+             DTSTART:20240115T100000Z\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:hourly\r\n\
+             DTSTART:20240115T100000Z\r\n\
+             RRULE:FREQ=HOURLY\r\n\
+             END:VEVENT\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:reversed\r\n\
+             DTSTART:20240115T100000Z\r\n\
+             DTEND:20240115T090000Z\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        );
+        assert!(parsed.events.is_empty());
+        assert_eq!(
+            parsed.skipped,
+            vec![
+                IcalSkippedEvent {
+                    uid: None,
+                    reason: "Missing UID".into(),
+                },
+                IcalSkippedEvent {
+                    uid: Some("hourly".into()),
+                    reason: "Unsupported RRULE frequency: HOURLY".into(),
+                },
+                IcalSkippedEvent {
+                    uid: Some("reversed".into()),
+                    reason: "The event ends before it starts".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_applies_the_changes_to_an_event() {
+        let parsed = parse(
+            "BEGIN:VCALENDAR\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:event\r\n\
+             SUMMARY:Meeting\r\n\
+             DTSTART:20240115T100000Z\r\n\
+             DTEND:20240115T110000Z\r\n\
+             RRULE:FREQ=DAILY;COUNT=3\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n",
+        );
+        let mut event = CalendarEvent::default();
+
+        let changed_fields = parsed.events[0].apply(&mut event).unwrap();
+        assert!(changed_fields.contains(&"title"));
+        assert!(changed_fields.contains(&"recurrence"));
+        assert_eq!(event.title.as_deref(), Some("Meeting"));
+        assert_eq!(event.end_time, utc("2024-01-15T11:00:00Z"));
+        assert_eq!(event.recurrence.as_ref().and_then(|r| r.count), Some(3));
+
+        // Nothing changes when applied again
+        assert!(parsed.events[0].apply(&mut event).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+use chrono::{
+    DateTime,
+    Datelike,
+    Months,
+    NaiveDate,
+    NaiveDateTime,
+    NaiveTime,
+    Offset,
+    TimeDelta,
+    TimeZone,
+    Utc,
+    Weekday,
+};
+use chrono_tz::{OffsetComponents, OffsetName, Tz};
+
+use super::ical_weekday;
+
+/// Number of years after the last exported year during which the transitions must follow the same yearly rules,
+/// for the rules to be written as recurrences (instead of listing the transitions)
+const RULE_CHECK_YEARS: i32 = 10;
+
+/// Offset of a timezone, as observed at some point in time
+#[derive(Debug, Clone, PartialEq)]
+struct Observance {
+    /// Offset from UTC in seconds
+    offset: i32,
+    /// Daylight saving time is in effect
+    dst: bool,
+    /// Abbreviation of the offset (e.g. "CEST")
+    name: Option<String>,
+}
+
+impl Observance {
+    fn at(tz: Tz, datetime: DateTime<Utc>) -> Self {
+        let offset = tz.offset_from_utc_datetime(&datetime.naive_utc());
+        Self {
+            offset: offset.fix().local_minus_utc(),
+            dst: !offset.dst_offset().is_zero(),
+            name: offset.abbreviation().map(String::from),
+        }
+    }
+}
+
+/// Change of the offset of a timezone
+#[derive(Debug, Clone)]
+struct Transition {
+    at: DateTime<Utc>,
+    from: Observance,
+    to: Observance,
+}
+
+impl Transition {
+    /// Local time of the transition, in the offset before it (the DTSTART of an observance)
+    fn onset(&self) -> NaiveDateTime {
+        self.at.naive_utc() + TimeDelta::seconds(self.from.offset.into())
+    }
+}
+
+/// Transitions of the timezone during the year
+fn transitions(tz: Tz, year: i32) -> Vec<Transition> {
+    let Some(start) = Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0).single() else {
+        return Vec::new();
+    };
+    let Some(end) = start.checked_add_months(Months::new(12)) else {
+        return Vec::new();
+    };
+
+    // Offsets are only changed once a day at most, so the days with a change are searched first
+    let mut transitions = Vec::new();
+    let mut previous = (start, Observance::at(tz, start));
+    let mut day = start;
+    while day < end {
+        day += TimeDelta::days(1);
+        let observance = Observance::at(tz, day);
+        if observance == previous.1 {
+            previous.0 = day;
+            continue;
+        }
+
+        let (mut before, mut after) = (previous.0, day);
+        while after - before > TimeDelta::seconds(1) {
+            let middle = before + (after - before) / 2;
+            if Observance::at(tz, middle) == previous.1 {
+                before = middle;
+            } else {
+                after = middle;
+            }
+        }
+        transitions.push(Transition {
+            at: after,
+            from: previous.1,
+            to: observance.clone(),
+        });
+        previous = (day, observance);
+    }
+    transitions
+}
+
+/// Yearly rule of a transition, e.g. the last Sunday of March at 02:00
+#[derive(Debug, Clone, PartialEq)]
+struct YearlyRule {
+    month: u32,
+    /// Occurrence of the weekday in the month, -1 being the last one
+    nth: i8,
+    weekday: Weekday,
+    time: NaiveTime,
+}
+
+impl YearlyRule {
+    /// Rules which the onset matches
+    fn candidates(onset: NaiveDateTime) -> Vec<Self> {
+        let date = onset.date();
+        let rule = |nth| Self {
+            month: date.month(),
+            nth,
+            weekday: date.weekday(),
+            time: onset.time(),
+        };
+        let mut candidates = vec![rule(((date.day() - 1) / 7 + 1) as i8)];
+        if (date + TimeDelta::days(7)).month() != date.month() {
+            candidates.insert(0, rule(-1));
+        }
+        candidates
+    }
+
+    fn onset(&self, year: i32) -> Option<NaiveDateTime> {
+        let date = if self.nth > 0 {
+            NaiveDate::from_weekday_of_month_opt(year, self.month, self.weekday, self.nth as u8)?
+        } else {
+            let last_day = NaiveDate::from_ymd_opt(year, self.month, 1)?
+                .checked_add_months(Months::new(1))?
+                .pred_opt()?;
+            let days_back = (7 + last_day.weekday().num_days_from_monday()
+                - self.weekday.num_days_from_monday())
+                % 7;
+            last_day - TimeDelta::days(days_back.into())
+        };
+        Some(date.and_time(self.time))
+    }
+
+    fn rrule(&self) -> String {
+        format!(
+            "FREQ=YEARLY;BYMONTH={};BYDAY={}{}",
+            self.month,
+            self.nth,
+            ical_weekday(self.weekday)
+        )
+    }
+}
+
+/// Find the yearly rules followed by the transitions of the year during the next years
+fn yearly_rules(tz: Tz, year: i32, transitions_of_year: &[Transition]) -> Option<Vec<YearlyRule>> {
+    if transitions_of_year.is_empty() {
+        return None;
+    }
+
+    let following_years = (year + 1..=year + RULE_CHECK_YEARS)
+        .map(|year| (year, transitions(tz, year)))
+        .collect::<Vec<_>>();
+    if following_years
+        .iter()
+        .any(|(_, transitions)| transitions.len() != transitions_of_year.len())
+    {
+        return None;
+    }
+
+    transitions_of_year
+        .iter()
+        .map(|transition| {
+            YearlyRule::candidates(transition.onset())
+                .into_iter()
+                .find(|rule| {
+                    following_years.iter().all(|(year, transitions)| {
+                        transitions.iter().any(|other| {
+                            other.from.offset == transition.from.offset
+                                && other.to == transition.to
+                                && rule.onset(*year) == Some(other.onset())
+                        })
+                    })
+                })
+        })
+        .collect()
+}
+
+fn format_offset(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    let (hours, minutes, seconds) = (offset / 3600, offset / 60 % 60, offset % 60);
+    if seconds == 0 {
+        format!("{sign}{hours:02}{minutes:02}")
+    } else {
+        format!("{sign}{hours:02}{minutes:02}{seconds:02}")
+    }
+}
+
+fn observance_component(
+    onset: NaiveDateTime,
+    from: &Observance,
+    to: &Observance,
+    rule: Option<&YearlyRule>,
+) -> String {
+    let kind = if to.dst { "DAYLIGHT" } else { "STANDARD" };
+    let mut ical = String::new();
+    ical.push_str(&format!("BEGIN:{kind}\r\n"));
+    ical.push_str(&format!("DTSTART:{}\r\n", onset.format("%Y%m%dT%H%M%S")));
+    ical.push_str(&format!("TZOFFSETFROM:{}\r\n", format_offset(from.offset)));
+    ical.push_str(&format!("TZOFFSETTO:{}\r\n", format_offset(to.offset)));
+    if let Some(name) = &to.name {
+        ical.push_str(&format!("TZNAME:{name}\r\n"));
+    }
+    if let Some(rule) = rule {
+        ical.push_str(&format!("RRULE:{}\r\n", rule.rrule()));
+    }
+    ical.push_str(&format!("END:{kind}\r\n"));
+    ical
+}
+
+/// Generates the VTIMEZONE component of a timezone, for events of the given years
+///
+/// The transitions of the years are listed, and the ones of the last year are written as yearly recurrences
+/// when the timezone keeps following them, so that recurring events stay correct in the following years
+pub fn generate_vtimezone(tz: Tz, first_year: i32, last_year: i32) -> String {
+    let mut observances = String::new();
+    for year in first_year..last_year {
+        for transition in transitions(tz, year) {
+            observances.push_str(&observance_component(
+                transition.onset(),
+                &transition.from,
+                &transition.to,
+                None,
+            ));
+        }
+    }
+
+    let transitions_of_last_year = transitions(tz, last_year);
+    match yearly_rules(tz, last_year, &transitions_of_last_year) {
+        Some(rules) => {
+            for (transition, rule) in transitions_of_last_year.iter().zip(&rules) {
+                observances.push_str(&observance_component(
+                    transition.onset(),
+                    &transition.from,
+                    &transition.to,
+                    Some(rule),
+                ));
+            }
+        }
+        None => {
+            for year in last_year..=last_year + RULE_CHECK_YEARS {
+                for transition in transitions(tz, year) {
+                    observances.push_str(&observance_component(
+                        transition.onset(),
+                        &transition.from,
+                        &transition.to,
+                        None,
+                    ));
+                }
+            }
+        }
+    }
+
+    // Timezones without transitions have a single observance
+    if observances.is_empty()
+        && let Some(start) = Utc.with_ymd_and_hms(first_year, 1, 1, 0, 0, 0).single()
+    {
+        let observance = Observance::at(tz, start);
+        observances.push_str(&observance_component(
+            start.naive_utc(),
+            &observance,
+            &observance,
+            None,
+        ));
+    }
+
+    format!("BEGIN:VTIMEZONE\r\nTZID:{tz}\r\n{observances}END:VTIMEZONE\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_generates_yearly_rules_for_dst() {
+        let vtimezone = generate_vtimezone(chrono_tz::Europe::Oslo, 2029, 2030);
+
+        // The transitions of 2029 are listed, and the ones of 2030 recur
+        assert!(vtimezone.starts_with("BEGIN:VTIMEZONE\r\nTZID:Europe/Oslo\r\n"));
+        assert!(vtimezone.contains(
+            "BEGIN:DAYLIGHT\r\nDTSTART:20290325T020000\r\nTZOFFSETFROM:+0100\r\nTZOFFSETTO:+0200\r\nTZNAME:CEST\r\nEND:DAYLIGHT\r\n"
+        ));
+        assert!(vtimezone.contains(
+            "BEGIN:STANDARD\r\nDTSTART:20291028T030000\r\nTZOFFSETFROM:+0200\r\nTZOFFSETTO:+0100\r\nTZNAME:CET\r\nEND:STANDARD\r\n"
+        ));
+        assert!(vtimezone.contains(
+            "BEGIN:DAYLIGHT\r\nDTSTART:20300331T020000\r\nTZOFFSETFROM:+0100\r\nTZOFFSETTO:+0200\r\nTZNAME:CEST\r\nRRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU\r\nEND:DAYLIGHT\r\n"
+        ));
+        assert!(vtimezone.contains(
+            "BEGIN:STANDARD\r\nDTSTART:20301027T030000\r\nTZOFFSETFROM:+0200\r\nTZOFFSETTO:+0100\r\nTZNAME:CET\r\nRRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU\r\nEND:STANDARD\r\n"
+        ));
+        assert_eq!(vtimezone.matches("BEGIN:DAYLIGHT").count(), 2);
+        assert!(vtimezone.ends_with("END:VTIMEZONE\r\n"));
+    }
+
+    #[test]
+    fn it_generates_nth_weekday_rules() {
+        let vtimezone = generate_vtimezone(chrono_tz::America::New_York, 2030, 2030);
+
+        assert!(vtimezone.contains("DTSTART:20300310T020000\r\n"));
+        assert!(vtimezone.contains("RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r\n"));
+        assert!(vtimezone.contains("DTSTART:20301103T020000\r\n"));
+        assert!(vtimezone.contains("RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r\n"));
+        assert!(vtimezone.contains("TZOFFSETTO:-0400\r\n"));
+    }
+
+    #[test]
+    fn it_generates_a_single_observance_without_transitions() {
+        let vtimezone = generate_vtimezone(chrono_tz::Asia::Tokyo, 2030, 2030);
+
+        assert_eq!(
+            vtimezone,
+            "BEGIN:VTIMEZONE\r\nTZID:Asia/Tokyo\r\nBEGIN:STANDARD\r\nDTSTART:20300101T000000\r\nTZOFFSETFROM:+0900\r\nTZOFFSETTO:+0900\r\nTZNAME:JST\r\nEND:STANDARD\r\nEND:VTIMEZONE\r\n"
+        );
+    }
+}
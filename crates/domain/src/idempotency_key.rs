@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+
+/// Idempotency key sent by a client with a request (`Idempotency-Key` header)
+/// The response is recorded, so that the retries of the request get it again instead of running it twice
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyKey {
+    /// Scope of the key (the account of the client, and its user for the user routes),
+    /// so that the clients can't see the responses of the others
+    pub scope: String,
+    /// Key sent by the client
+    pub key: String,
+    /// Hash of the request (method, path and body), a retry needs to send the same request
+    pub request_hash: String,
+    /// Response of the request, None while the request is in progress
+    pub response: Option<IdempotentResponse>,
+    /// When the request was received
+    pub created_at: DateTime<Utc>,
+    /// When the key expires, it can then be used for another request
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Response recorded for an idempotency key
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotentResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Content type of the body, if any
+    pub content_type: Option<String>,
+    /// Body of the response
+    pub body: Vec<u8>,
+}
@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+
+/// Lease of a periodic job, only the instance holding it runs the job
+/// The holder renews it each time it runs the job, and another instance can acquire it once it expires
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobLease {
+    /// Name of the job
+    pub job: String,
+    /// Identifier of the instance holding the lease
+    pub holder: String,
+    /// When the holder acquired the lease
+    pub acquired_at: DateTime<Utc>,
+    /// When the lease expires, if not renewed
+    pub expires_at: DateTime<Utc>,
+}
@@ -1,11 +1,15 @@
 mod account;
 pub mod booking_slots;
 mod calendar;
+mod calendar_feed;
 mod date;
 mod event;
 pub mod event_group;
 mod event_instance;
 pub mod ical;
+mod idempotency_key;
+mod job_lease;
+mod outbox;
 pub mod providers;
 mod reminder;
 mod schedule;
@@ -14,17 +18,36 @@ mod service;
 mod shared;
 mod timespan;
 mod user;
+mod webhook;
+mod webhook_delivery;
 
-pub use account::{Account, AccountIntegration, AccountSettings, AccountWebhookSettings, PEMKey};
+pub use account::{
+    Account,
+    AccountIntegration,
+    AccountRateLimits,
+    AccountSettings,
+    AccountWebhookSettings,
+    PEMKey,
+    RateLimit,
+    SearchLanguage,
+};
 pub use calendar::{Calendar, CalendarSettings, SyncedCalendar};
+pub use calendar_feed::{CalendarFeed, MASKED_EVENT_TITLE};
 pub use chrono::{Month, Weekday};
 pub use chrono_tz::Tz;
 pub use date::format_date;
 pub use event::{
     CalendarEvent,
+    CalendarEventAttendee,
+    CalendarEventAttendeeResponseStatus,
+    CalendarEventAttendeeRole,
+    CalendarEventCursor,
+    CalendarEventCursorKey,
+    CalendarEventHighlight,
     CalendarEventReminder,
     CalendarEventSort,
     CalendarEventStatus,
+    RecurringEventScope,
     SyncedCalendarEvent,
 };
 pub use event_instance::{
@@ -35,24 +58,36 @@ pub use event_instance::{
     get_free_busy,
 };
 pub use ical::{
+    IcalEvent,
+    IcalParseError,
+    IcalSkippedEvent,
+    ParsedIcal,
     generate_ical_content,
     generate_ical_content_for_event,
     generate_ical_content_for_exception,
+    parse_ical_events,
 };
+pub use idempotency_key::{IdempotencyKey, IdempotentResponse};
+pub use job_lease::JobLease;
+pub use outbox::{OutboxMessage, OutboxMessageStatus, OutboxTask};
 pub use reminder::{EventRemindersExpansionJob, Reminder};
 pub use schedule::{Schedule, ScheduleRule};
 pub use service::{
     BusyCalendarProvider,
     Service,
+    ServiceHold,
     ServiceMultiPersonOptions,
     ServiceResource,
     ServiceWithUsers,
     TimePlan,
 };
 pub use shared::{
+    attendee_query::AttendeeQuery,
+    cursor::{Cursor, IDCursor, InvalidCursorError, paginate_by_id},
     datetime_query::{DateTimeQuery, DateTimeQueryRange},
     entity::{Entity, ID},
     expand_events::{
+        MaterializedEventInstances,
         expand_all_events_and_remove_exceptions,
         expand_event_and_remove_exceptions,
         generate_map_exceptions_original_start_times,
@@ -65,3 +100,16 @@ pub use shared::{
 };
 pub use timespan::TimeSpan;
 pub use user::{IntegrationProvider, User, UserIntegration};
+pub use webhook::{
+    WEBHOOK_SIGNATURE_HEADER,
+    WebhookEventType,
+    WebhookSignatureError,
+    sign_webhook_payload,
+    verify_webhook_signature,
+};
+pub use webhook_delivery::{
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+    WebhookEndpointStatus,
+};
@@ -0,0 +1,169 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{Entity, ID, Meta, WebhookEventType, shared::backoff::exponential_backoff};
+
+/// Work of a subscriber of a use case
+/// It is recorded in the outbox in the same transaction as the write that triggered it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+#[ts(export)]
+pub enum OutboxTask {
+    /// Generate the reminders of a created event
+    CreateEventReminders { event_id: ID },
+    /// Regenerate the reminders of an updated event
+    SyncEventReminders { event_id: ID },
+    /// Create the event in the external calendars synced with its calendar
+    CreateSyncedEvents { event_id: ID },
+    /// Update the event in the external calendars it has been synced to
+    UpdateSyncedEvents { event_id: ID },
+    /// Materialize the instances of a recurring event, after a change of the event or of one of its exceptions
+    RefreshEventInstances { event_id: ID },
+    /// Send a change of an event to the account webhook (if the account opted in to this type of change)
+    /// The payload is a snapshot of the event at the time of the change
+    SendWebhook {
+        event_type: WebhookEventType,
+        payload: serde_json::Value,
+    },
+}
+
+/// Status of a message in the outbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum OutboxMessageStatus {
+    /// Waiting to be processed (or retried after a failure)
+    Pending,
+    /// Failed too many times, it is only processed again if replayed
+    DeadLetter,
+}
+
+impl From<OutboxMessageStatus> for String {
+    fn from(e: OutboxMessageStatus) -> Self {
+        match e {
+            OutboxMessageStatus::Pending => "pending".into(),
+            OutboxMessageStatus::DeadLetter => "deadLetter".into(),
+        }
+    }
+}
+
+impl TryFrom<String> for OutboxMessageStatus {
+    type Error = anyhow::Error;
+    fn try_from(e: String) -> anyhow::Result<OutboxMessageStatus> {
+        Ok(match &e[..] {
+            "pending" => OutboxMessageStatus::Pending,
+            "deadLetter" => OutboxMessageStatus::DeadLetter,
+            _ => Err(anyhow::anyhow!("Invalid outbox message status"))?,
+        })
+    }
+}
+
+/// Message of the (transactional) outbox
+/// Processed messages are removed from the outbox
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxMessage {
+    /// Unique ID
+    pub id: ID,
+    /// Account ID
+    pub account_id: ID,
+    /// Work to do
+    pub task: OutboxTask,
+    /// Status of the message
+    pub status: OutboxMessageStatus,
+    /// Number of times the message has been picked up for processing
+    pub attempts: i32,
+    /// When the message will be processed (again)
+    pub next_attempt_at: DateTime<Utc>,
+    /// Error of the last failed attempt
+    pub last_error: Option<String>,
+    /// Creation date
+    pub created: DateTime<Utc>,
+}
+
+impl OutboxMessage {
+    pub fn new(account_id: ID, task: OutboxTask, now: DateTime<Utc>) -> Self {
+        Self {
+            id: Default::default(),
+            account_id,
+            task,
+            status: OutboxMessageStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created: now,
+        }
+    }
+
+    /// Delay before retrying the message after its last failed attempt
+    /// The delay doubles with each attempt (exponential backoff), up to `max_delay`
+    pub fn retry_delay(&self, base_delay: TimeDelta, max_delay: TimeDelta) -> TimeDelta {
+        exponential_backoff(self.attempts, base_delay, max_delay)
+    }
+}
+
+impl Entity<ID> for OutboxMessage {
+    fn id(&self) -> ID {
+        self.id.clone()
+    }
+}
+
+impl Meta<ID> for OutboxMessage {
+    fn account_id(&self) -> &ID {
+        &self.account_id
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retry_delay_is_exponential_and_capped() {
+        let mut message = OutboxMessage::new(
+            ID::default(),
+            OutboxTask::SyncEventReminders {
+                event_id: ID::default(),
+            },
+            Utc::now(),
+        );
+        let base_delay = TimeDelta::seconds(5);
+        let max_delay = TimeDelta::minutes(1);
+
+        message.attempts = 1;
+        assert_eq!(message.retry_delay(base_delay, max_delay), base_delay);
+        message.attempts = 2;
+        assert_eq!(
+            message.retry_delay(base_delay, max_delay),
+            TimeDelta::seconds(10)
+        );
+        message.attempts = 4;
+        assert_eq!(
+            message.retry_delay(base_delay, max_delay),
+            TimeDelta::seconds(40)
+        );
+        message.attempts = 5;
+        assert_eq!(message.retry_delay(base_delay, max_delay), max_delay);
+        message.attempts = i32::MAX;
+        assert_eq!(message.retry_delay(base_delay, max_delay), max_delay);
+    }
+
+    #[test]
+    fn task_is_serialized_with_its_type() {
+        let event_id = ID::default();
+        let task = OutboxTask::CreateSyncedEvents {
+            event_id: event_id.clone(),
+        };
+        let json = serde_json::to_value(&task).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "createSyncedEvents", "eventId": event_id.to_string() })
+        );
+        assert_eq!(serde_json::from_value::<OutboxTask>(json).unwrap(), task);
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::DateTime;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use chrono_tz::{Tz, UTC};
 use serde::{Deserialize, Serialize};
 use tracing::error;
@@ -23,24 +23,38 @@ pub struct OutlookCalendarEventTime {
 }
 
 impl OutlookCalendarEventTime {
+    /// Time in the local time of the timezone (UTC if not provided)
+    pub fn new(date_time: DateTime<Utc>, timezone: Option<Tz>) -> Self {
+        let timezone = timezone.unwrap_or(UTC);
+        Self {
+            date_time: date_time
+                .with_timezone(&timezone)
+                .format("%FT%T")
+                .to_string(),
+            time_zone: timezone.name().to_string(),
+        }
+    }
+
     pub fn get_timestamp_millis(&self) -> i64 {
         let timezone = self.time_zone.parse::<Tz>().unwrap_or(UTC);
 
         let index = self.date_time.find('.');
 
+        // The date time is in the local time of the timezone (without offset)
         let date_time = if let Some(index) = index {
-            DateTime::parse_from_str(&self.date_time[..index], "%FT%T")
+            NaiveDateTime::parse_from_str(&self.date_time[..index], "%FT%T")
         } else {
-            DateTime::parse_from_str(&self.date_time, "%FT%T")
+            NaiveDateTime::parse_from_str(&self.date_time, "%FT%T")
         };
 
         date_time
             .inspect_err(|err| {
                 error!("Outlook parse error : {:?}", err);
             })
-            .unwrap_or(DateTime::UNIX_EPOCH.fixed_offset())
-            .with_timezone(&timezone)
-            .timestamp_millis()
+            .ok()
+            .and_then(|date_time| timezone.from_local_datetime(&date_time).earliest())
+            .map(|date_time| date_time.timestamp_millis())
+            .unwrap_or(0)
     }
 }
 
@@ -134,3 +148,31 @@ struct OutlookCalendarOwner {
     name: String,
     address: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_writes_and_reads_times_in_their_timezone() {
+        let date_time = DateTime::parse_from_rfc3339("2030-07-01T07:00:00Z")
+            .unwrap()
+            .to_utc();
+
+        let time = OutlookCalendarEventTime::new(date_time, Some(chrono_tz::Europe::Paris));
+        assert_eq!(time.date_time, "2030-07-01T09:00:00");
+        assert_eq!(time.time_zone, "Europe/Paris");
+        assert_eq!(time.get_timestamp_millis(), date_time.timestamp_millis());
+
+        let time = OutlookCalendarEventTime::new(date_time, None);
+        assert_eq!(time.date_time, "2030-07-01T07:00:00");
+        assert_eq!(time.time_zone, "UTC");
+
+        // Outlook adds fractional seconds
+        let time = OutlookCalendarEventTime {
+            date_time: "2030-07-01T09:00:00.0000000".into(),
+            time_zone: "Europe/Paris".into(),
+        };
+        assert_eq!(time.get_timestamp_millis(), date_time.timestamp_millis());
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::{CompatibleInstances, EventInstance, ServiceResource, TimeSpan};
+
+/// Maximum numbers of bookings of a `ServiceResource` per day and per week
+///
+/// The days start at midnight in the timezone of the user, and the weeks
+/// on the given week start
+#[derive(Debug, Clone)]
+pub struct BookingLimits {
+    pub max_per_day: Option<i64>,
+    pub max_per_week: Option<i64>,
+    pub timezone: Tz,
+    pub week_start: Weekday,
+}
+
+/// A day or a week in which at most `max` bookings can start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookingPeriod {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub max: i64,
+}
+
+impl BookingLimits {
+    pub fn new(resource: &ServiceResource, timezone: Tz, week_start: Weekday) -> Self {
+        Self {
+            max_per_day: resource.max_bookings_per_day,
+            max_per_week: resource.max_bookings_per_week,
+            timezone,
+            week_start,
+        }
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.max_per_day.is_none() && self.max_per_week.is_none()
+    }
+
+    /// The timespan of the whole days (or weeks) overlapping the given timespan,
+    /// in which the bookings should be counted
+    pub fn counted_timespan(&self, timespan: &TimeSpan) -> TimeSpan {
+        let last_date = self.local_date(timespan.end());
+        if self.max_per_week.is_some() {
+            let (start, _) = self.week_of(self.local_date(timespan.start()));
+            let (_, end) = self.week_of(last_date);
+            TimeSpan::new(start, end)
+        } else {
+            let (start, _) = self.day_of(self.local_date(timespan.start()));
+            let (_, end) = self.day_of(last_date);
+            TimeSpan::new(start, end)
+        }
+    }
+
+    /// The days and weeks in which the limits are reached by the bookings
+    /// starting at the given times, as busy instances
+    pub fn reached_periods(&self, bookings: &[DateTime<Utc>]) -> CompatibleInstances {
+        let mut periods = Vec::new();
+        if let Some(max) = self.max_per_day {
+            periods.append(&mut Self::periods_with_at_least(bookings, max, |t| {
+                self.day_of(self.local_date(t))
+            }));
+        }
+        if let Some(max) = self.max_per_week {
+            periods.append(&mut Self::periods_with_at_least(bookings, max, |t| {
+                self.week_of(self.local_date(t))
+            }));
+        }
+        CompatibleInstances::new(periods)
+    }
+
+    /// The limited day and week of a booking starting at the given time
+    pub fn periods_of(&self, booking: DateTime<Utc>) -> Vec<BookingPeriod> {
+        let date = self.local_date(booking);
+        [
+            (self.max_per_day, self.day_of(date)),
+            (self.max_per_week, self.week_of(date)),
+        ]
+        .into_iter()
+        .filter_map(|(max, (start_time, end_time))| {
+            max.map(|max| BookingPeriod {
+                start_time,
+                end_time,
+                max,
+            })
+        })
+        .collect()
+    }
+
+    fn periods_with_at_least(
+        bookings: &[DateTime<Utc>],
+        max: i64,
+        period_of: impl Fn(DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>),
+    ) -> Vec<EventInstance> {
+        let mut counts: BTreeMap<(DateTime<Utc>, DateTime<Utc>), i64> = BTreeMap::new();
+        for booking in bookings {
+            *counts.entry(period_of(*booking)).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count >= max)
+            .map(|((start_time, end_time), _)| EventInstance {
+                start_time,
+                end_time,
+                busy: true,
+            })
+            .collect()
+    }
+
+    fn local_date(&self, time: DateTime<Utc>) -> NaiveDate {
+        time.with_timezone(&self.timezone).date_naive()
+    }
+
+    fn day_of(&self, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        (
+            self.start_of_day(date),
+            self.start_of_day(date + TimeDelta::days(1)),
+        )
+    }
+
+    fn week_of(&self, date: NaiveDate) -> (DateTime<Utc>, DateTime<Utc>) {
+        let days_since_week_start = (7 + date.weekday().num_days_from_monday()
+            - self.week_start.num_days_from_monday())
+            % 7;
+        let first_date = date - TimeDelta::days(days_since_week_start.into());
+        (
+            self.start_of_day(first_date),
+            self.start_of_day(first_date + TimeDelta::days(7)),
+        )
+    }
+
+    /// Midnight of the given date in the timezone, or the first time of the date
+    /// when midnight is skipped by a DST transition
+    fn start_of_day(&self, date: NaiveDate) -> DateTime<Utc> {
+        let midnight = date.and_time(NaiveTime::MIN);
+        self.timezone
+            .from_local_datetime(&midnight)
+            .earliest()
+            .or_else(|| {
+                self.timezone
+                    .from_local_datetime(&(midnight + TimeDelta::hours(1)))
+                    .earliest()
+            })
+            .map(|datetime| datetime.with_timezone(&Utc))
+            .unwrap_or_else(|| midnight.and_utc())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::{ID, TimePlan};
+
+    fn limits(max_per_day: Option<i64>, max_per_week: Option<i64>, timezone: Tz) -> BookingLimits {
+        let mut resource = ServiceResource::new(ID::default(), ID::default(), TimePlan::Empty);
+        resource.max_bookings_per_day = max_per_day;
+        resource.max_bookings_per_week = max_per_week;
+        BookingLimits::new(&resource, timezone, Weekday::Mon)
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn unlimited_booking_limits() {
+        let limits = limits(None, None, chrono_tz::UTC);
+        assert!(limits.is_unlimited());
+        let bookings = vec![utc(2025, 3, 3, 9); 20];
+        assert!(limits.reached_periods(&bookings).is_empty());
+    }
+
+    #[test]
+    fn max_bookings_per_day_in_the_timezone_of_the_user() {
+        let limits = limits(Some(2), None, chrono_tz::Europe::Oslo);
+        // Monday 3rd of March, 09:00 and 22:30 in Oslo
+        let bookings = vec![
+            utc(2025, 3, 3, 8),
+            Utc.with_ymd_and_hms(2025, 3, 3, 21, 30, 0).unwrap(),
+        ];
+        let reached = limits.reached_periods(&bookings).inner();
+        assert_eq!(
+            reached,
+            vec![EventInstance {
+                start_time: Utc.with_ymd_and_hms(2025, 3, 2, 23, 0, 0).unwrap(),
+                end_time: Utc.with_ymd_and_hms(2025, 3, 3, 23, 0, 0).unwrap(),
+                busy: true,
+            }]
+        );
+
+        // Monday 3rd of March at 23:30 in UTC is already on Tuesday in Oslo
+        let bookings = vec![
+            utc(2025, 3, 3, 8),
+            Utc.with_ymd_and_hms(2025, 3, 3, 23, 30, 0).unwrap(),
+        ];
+        assert!(limits.reached_periods(&bookings).is_empty());
+        let limits = BookingLimits {
+            timezone: chrono_tz::UTC,
+            ..limits
+        };
+        assert_eq!(limits.reached_periods(&bookings).len(), 1);
+    }
+
+    #[test]
+    fn max_bookings_per_week() {
+        let limits = limits(Some(2), Some(3), chrono_tz::UTC);
+        // Monday, Wednesday and Sunday of the same week, and the next Monday
+        let bookings = vec![
+            utc(2025, 3, 3, 9),
+            utc(2025, 3, 5, 9),
+            utc(2025, 3, 9, 9),
+            utc(2025, 3, 10, 9),
+        ];
+        let reached = limits.reached_periods(&bookings).inner();
+        assert_eq!(
+            reached,
+            vec![EventInstance {
+                start_time: utc(2025, 3, 3, 0),
+                end_time: utc(2025, 3, 10, 0),
+                busy: true,
+            }]
+        );
+
+        // With weeks starting on Sunday, the bookings are split over two weeks
+        let limits = BookingLimits {
+            week_start: Weekday::Sun,
+            ..limits
+        };
+        assert!(limits.reached_periods(&bookings).is_empty());
+    }
+
+    #[test]
+    fn periods_of_a_booking() {
+        let limits = limits(Some(2), Some(3), chrono_tz::Europe::Oslo);
+        // Wednesday 5th of March at 23:30 in UTC is already on Thursday in Oslo
+        let booking = Utc.with_ymd_and_hms(2025, 3, 5, 23, 30, 0).unwrap();
+        assert_eq!(
+            limits.periods_of(booking),
+            vec![
+                BookingPeriod {
+                    start_time: Utc.with_ymd_and_hms(2025, 3, 5, 23, 0, 0).unwrap(),
+                    end_time: Utc.with_ymd_and_hms(2025, 3, 6, 23, 0, 0).unwrap(),
+                    max: 2,
+                },
+                BookingPeriod {
+                    start_time: Utc.with_ymd_and_hms(2025, 3, 2, 23, 0, 0).unwrap(),
+                    end_time: Utc.with_ymd_and_hms(2025, 3, 9, 23, 0, 0).unwrap(),
+                    max: 3,
+                },
+            ]
+        );
+
+        let limits = BookingLimits {
+            max_per_day: None,
+            max_per_week: None,
+            ..limits
+        };
+        assert!(limits.periods_of(booking).is_empty());
+    }
+
+    #[test]
+    fn counted_timespan_covers_whole_periods() {
+        let timespan = TimeSpan::new(utc(2025, 3, 5, 10), utc(2025, 3, 6, 10));
+
+        let limits = limits(Some(2), None, chrono_tz::UTC);
+        let counted = limits.counted_timespan(&timespan);
+        assert_eq!(counted.start(), utc(2025, 3, 5, 0));
+        assert_eq!(counted.end(), utc(2025, 3, 7, 0));
+
+        let limits = BookingLimits {
+            max_per_week: Some(5),
+            ..limits
+        };
+        let counted = limits.counted_timespan(&timespan);
+        assert_eq!(counted.start(), utc(2025, 3, 3, 0));
+        assert_eq!(counted.end(), utc(2025, 3, 10, 0));
+    }
+}
@@ -1,2 +1,4 @@
+mod booking_limits;
 mod round_robin;
+pub use booking_limits::*;
 pub use round_robin::*;
@@ -1,13 +1,19 @@
+use std::cmp::Ordering;
+
 use chrono::{DateTime, Utc};
-use itertools::Itertools;
-use rand::{RngExt, rng};
+use rand::{Rng, RngExt, rng};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
-use crate::{CalendarEvent, ID};
+use crate::{CalendarEvent, ID, ServiceResource, TimeSpan};
 
 /// Round robin algorithm to decide which member should be assigned a
 /// `Service Event` when there are multiple members of a `Service`
+///
+/// With both algorithms, the members of a lower priority tier are only
+/// assigned a `Service Event` when no member of a higher tier is available,
+/// and the members who reached their maximum number of bookings for the day
+/// or the week of the `Service Event` are not available
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
@@ -17,18 +23,30 @@ pub enum RoundRobinAlgorithm {
     ///
     /// This assigns the `Service Event` to the member which was
     /// least recently assigned a `Service Event` for the given
-    /// `Service`.
+    /// `Service`. The weights of the members are not used.
     #[default]
     Availability,
     /// Optimizes for equal distribution
     ///
-    /// This assigns the `Service Event` to the member which was
-    /// least number of assigned `Service Event`s for the next
-    /// time period. Time period in this context is hard coded to be
-    /// two weeks.
+    /// This assigns the `Service Event` to the member which has the
+    /// least number of assigned `Service Event`s relatively to its weight
+    /// for the next time period. Time period in this context is the
+    /// distribution window of the `Service` (two weeks by default).
     EqualDistribution,
 }
 
+/// The members of the highest priority tier (the lowest `priority`) among the given ones
+pub fn highest_priority_tier<'a>(members: &[&'a ServiceResource]) -> Vec<&'a ServiceResource> {
+    let Some(highest_priority) = members.iter().map(|m| m.priority).min() else {
+        return Vec::new();
+    };
+    members
+        .iter()
+        .filter(|m| m.priority == highest_priority)
+        .copied()
+        .collect()
+}
+
 #[derive(Debug, Clone)]
 pub struct RoundRobinAvailabilityAssignment {
     /// List of members with a corresponding timestamp stating
@@ -38,7 +56,13 @@ pub struct RoundRobinAvailabilityAssignment {
 }
 
 impl RoundRobinAvailabilityAssignment {
-    pub fn assign(mut self) -> Option<ID> {
+    pub fn assign(self) -> Option<ID> {
+        self.assign_with_rng(&mut rng())
+    }
+
+    /// Same as `assign`, with the random number generator used to pick
+    /// between the members which could be assigned (e.g. seeded in tests)
+    pub fn assign_with_rng<R: Rng + ?Sized>(mut self, rng: &mut R) -> Option<ID> {
         if self.members.is_empty() {
             return None;
         }
@@ -58,7 +82,6 @@ impl RoundRobinAvailabilityAssignment {
             Some(least_recently_booked_members[0].0.clone())
         } else {
             // Just pick random
-            let mut rng = rng();
             let rand_user_index = rng.random_range(0..least_recently_booked_members.len());
             Some(least_recently_booked_members[rand_user_index].0.clone())
         }
@@ -67,46 +90,65 @@ impl RoundRobinAvailabilityAssignment {
 
 #[derive(Debug, Clone)]
 pub struct RoundRobinEqualDistributionAssignment {
-    /// List of upcoming `Service Event`s they are assigned for the given `Service`
+    /// List of `Service Event`s they are assigned for the given `Service`
     pub events: Vec<CalendarEvent>,
-    /// List of user that can be assigned the new `Service Event`
-    pub user_ids: Vec<ID>,
+    /// List of members that can be assigned the new `Service Event`, with their weight
+    pub members: Vec<(ID, i64)>,
+    /// Only the `Service Event`s starting during this window are counted
+    pub window: TimeSpan,
 }
 
 #[derive(Debug)]
 struct UserWithEvents {
     pub user_id: ID,
     pub event_count: usize,
+    pub weight: i64,
+}
+
+impl UserWithEvents {
+    /// Compares the number of events of the users relatively to their weights
+    fn cmp_load(&self, other: &Self) -> Ordering {
+        let load = self.event_count as i64 * other.weight;
+        let other_load = other.event_count as i64 * self.weight;
+        load.cmp(&other_load)
+    }
 }
 
 impl RoundRobinEqualDistributionAssignment {
     pub fn assign(self) -> Option<ID> {
-        let mut prev: Option<usize> = None;
-        let users_with_least_upcoming_bookings = self
-            .user_ids
+        self.assign_with_rng(&mut rng())
+    }
+
+    /// Same as `assign`, with the random number generator used to pick
+    /// between the members which could be assigned (e.g. seeded in tests)
+    pub fn assign_with_rng<R: Rng + ?Sized>(self, rng: &mut R) -> Option<ID> {
+        let users = self
+            .members
             .iter()
-            .map(|user_id| UserWithEvents {
-                event_count: self.events.iter().filter(|e| &e.user_id == user_id).count(),
+            .map(|(user_id, weight)| UserWithEvents {
+                event_count: self
+                    .events
+                    .iter()
+                    .filter(|e| {
+                        &e.user_id == user_id
+                            && e.start_time >= self.window.start()
+                            && e.start_time < self.window.end()
+                    })
+                    .count(),
+                weight: (*weight).max(1),
                 user_id: user_id.clone(),
             })
-            .sorted_by_key(|u| u.event_count)
-            .take_while(|u| {
-                let take = match prev {
-                    Some(count) => count == u.event_count,
-                    None => true,
-                };
-                prev = Some(u.event_count);
-                take
-            })
+            .collect::<Vec<_>>();
+        let least_loaded_user = users.iter().min_by(|a, b| a.cmp_load(b))?;
+        let users_with_least_upcoming_bookings = users
+            .iter()
+            .filter(|u| u.cmp_load(least_loaded_user) == Ordering::Equal)
             .collect::<Vec<_>>();
 
-        if self.user_ids.is_empty() {
-            None
-        } else if users_with_least_upcoming_bookings.len() == 1 {
+        if users_with_least_upcoming_bookings.len() == 1 {
             Some(users_with_least_upcoming_bookings[0].user_id.clone())
         } else {
             // Just pick random
-            let mut rng = rng();
             let rand_user_index = rng.random_range(0..users_with_least_upcoming_bookings.len());
             Some(
                 users_with_least_upcoming_bookings[rand_user_index]
@@ -119,9 +161,11 @@ impl RoundRobinEqualDistributionAssignment {
 
 #[cfg(test)]
 mod tests {
-    use rand::prelude::SliceRandom;
+    use chrono::TimeDelta;
+    use rand::{SeedableRng, prelude::SliceRandom, rngs::StdRng};
 
     use super::*;
+    use crate::TimePlan;
 
     #[test]
     fn round_robin_availability_assignment_without_members() {
@@ -229,18 +273,36 @@ mod tests {
     fn round_robin_eq_distribution_assignment_without_members() {
         let query = RoundRobinEqualDistributionAssignment {
             events: Vec::new(),
-            user_ids: Vec::new(),
+            members: Vec::new(),
+            window: window(),
         };
         assert!(query.assign().is_none());
     }
 
+    /// Two weeks from the epoch
+    fn window() -> TimeSpan {
+        let start = DateTime::from_timestamp_millis(0).unwrap();
+        TimeSpan::new(start, start + TimeDelta::days(14))
+    }
+
     fn generate_default_event(user_id: &ID) -> CalendarEvent {
+        generate_event_starting_at(user_id, window().start() + TimeDelta::hours(1))
+    }
+
+    fn generate_event_starting_at(user_id: &ID, start_time: DateTime<Utc>) -> CalendarEvent {
         CalendarEvent {
             user_id: user_id.clone(),
+            start_time,
             ..Default::default()
         }
     }
 
+    fn generate_events(user_id: &ID, count: usize) -> Vec<CalendarEvent> {
+        (0..count)
+            .map(|_| generate_default_event(user_id))
+            .collect()
+    }
+
     struct UserWithEventsCount {
         pub user_id: ID,
         pub count: usize,
@@ -273,9 +335,9 @@ mod tests {
             .map(|u| u.user_id.clone())
             .collect::<Vec<_>>();
 
-        let user_ids = user_with_events_count
+        let members = user_with_events_count
             .iter()
-            .map(|u| u.user_id.clone())
+            .map(|u| (u.user_id.clone(), 1))
             .collect::<Vec<_>>();
         let mut events = user_with_events_count
             .iter()
@@ -289,7 +351,11 @@ mod tests {
             .collect::<Vec<_>>();
         events.shuffle(&mut rng());
 
-        let query = RoundRobinEqualDistributionAssignment { events, user_ids };
+        let query = RoundRobinEqualDistributionAssignment {
+            events,
+            members,
+            window: window(),
+        };
         assert!(query.clone().assign().is_some());
         let selected_member = query.clone().assign().unwrap();
         assert!(users_with_least_upcoming_bookings.contains(&selected_member));
@@ -306,4 +372,109 @@ mod tests {
         }
         assert!(found_other)
     }
+
+    #[test]
+    fn round_robin_eq_distribution_assignment_with_weights() {
+        let senior_user = ID::default();
+        let user = ID::default();
+        let query = |senior_user_events: usize, user_events: usize| {
+            let mut events = generate_events(&senior_user, senior_user_events);
+            events.extend(generate_events(&user, user_events));
+            RoundRobinEqualDistributionAssignment {
+                events,
+                members: vec![(senior_user.clone(), 2), (user.clone(), 1)],
+                window: window(),
+            }
+        };
+
+        // The senior user is assigned twice as many events
+        assert_eq!(query(1, 1).assign(), Some(senior_user.clone()));
+        assert_eq!(query(2, 0).assign(), Some(user.clone()));
+        assert_eq!(query(3, 2).assign(), Some(senior_user.clone()));
+        assert_eq!(query(3, 1).assign(), Some(user.clone()));
+        // Both have the same number of events relatively to their weights
+        let assigned_users = (0..100)
+            .map(|_| query(2, 1).assign().unwrap())
+            .collect::<Vec<_>>();
+        assert!(assigned_users.contains(&senior_user));
+        assert!(assigned_users.contains(&user));
+    }
+
+    #[test]
+    fn round_robin_eq_distribution_assignment_counts_events_in_window() {
+        let user_1 = ID::default();
+        let user_2 = ID::default();
+        let mut events = generate_events(&user_2, 1);
+        // Before and after the window
+        events.push(generate_event_starting_at(
+            &user_1,
+            window().start() - TimeDelta::hours(1),
+        ));
+        events.push(generate_event_starting_at(&user_1, window().end()));
+        events.push(generate_event_starting_at(
+            &user_1,
+            window().end() + TimeDelta::days(1),
+        ));
+
+        let query = RoundRobinEqualDistributionAssignment {
+            events: events.clone(),
+            members: vec![(user_1.clone(), 1), (user_2.clone(), 1)],
+            window: window(),
+        };
+        assert_eq!(query.assign(), Some(user_1.clone()));
+
+        // A longer window
+        let query = RoundRobinEqualDistributionAssignment {
+            events,
+            members: vec![(user_1.clone(), 1), (user_2.clone(), 1)],
+            window: TimeSpan::new(window().start(), window().end() + TimeDelta::days(14)),
+        };
+        assert_eq!(query.assign(), Some(user_2));
+    }
+
+    #[test]
+    fn round_robin_assignments_are_deterministic_under_a_seed() {
+        let members = (0..10).map(|_| ID::default()).collect::<Vec<_>>();
+        let availability_query = RoundRobinAvailabilityAssignment {
+            members: members.iter().map(|m| (m.clone(), None)).collect(),
+        };
+        let eq_distribution_query = RoundRobinEqualDistributionAssignment {
+            events: Vec::new(),
+            members: members.iter().map(|m| (m.clone(), 1)).collect(),
+            window: window(),
+        };
+
+        let assign = |seed: u64| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (
+                availability_query.clone().assign_with_rng(&mut rng),
+                eq_distribution_query.clone().assign_with_rng(&mut rng),
+            )
+        };
+        let assigned_members = assign(42);
+        for _ in 0..10 {
+            assert_eq!(assign(42), assigned_members);
+        }
+        // Another seed picks other members
+        assert!((0..100).any(|seed| assign(seed) != assigned_members));
+    }
+
+    #[test]
+    fn highest_priority_tier_of_members() {
+        let member = |priority: i64| ServiceResource {
+            priority,
+            ..ServiceResource::new(ID::default(), ID::default(), TimePlan::Empty)
+        };
+        assert!(highest_priority_tier(&[]).is_empty());
+
+        let members = [member(2), member(1), member(3), member(1)];
+        let tier = highest_priority_tier(&members.iter().collect::<Vec<_>>());
+        assert_eq!(tier.len(), 2);
+        assert!(tier.iter().all(|m| m.priority == 1));
+
+        // Only the members of lower tiers are available
+        let tier = highest_priority_tier(&[&members[0], &members[2]]);
+        assert_eq!(tier.len(), 1);
+        assert_eq!(tier[0].user_id, members[0].user_id);
+    }
 }
@@ -1,3 +1,4 @@
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
 
@@ -54,6 +55,21 @@ pub struct ServiceResource {
     /// time T then this `ServiceResource` will not have any available
     /// bookingslots after T + `furthest_booking_time`
     pub furthest_booking_time: Option<i64>,
+    /// Relative share of the bookings of a round robin `Service` assigned to this
+    /// `ServiceResource` with the `EqualDistribution` algorithm, e.g. a user with a weight of 2
+    /// is assigned twice as many bookings as a user with a weight of 1.
+    /// It is ignored by the `Availability` algorithm, which assigns the least recently booked user
+    pub weight: i64,
+    /// Priority tier of this `ServiceResource` in a round robin `Service`.
+    /// The users of a tier are only assigned a booking when no user of a higher tier
+    /// (1 being the highest one) is available
+    pub priority: i64,
+    /// Maximum number of bookings (of any `Service`) this `ServiceResource` can have
+    /// in a day, starting at midnight in the timezone of its availability
+    pub max_bookings_per_day: Option<i64>,
+    /// Maximum number of bookings (of any `Service`) this `ServiceResource` can have
+    /// in a week, starting on the week start of its availability
+    pub max_bookings_per_week: Option<i64>,
 }
 
 impl ServiceResource {
@@ -66,6 +82,10 @@ impl ServiceResource {
             buffer_before: 0,
             closest_booking_time: 0,
             furthest_booking_time: None,
+            weight: 1,
+            priority: 1,
+            max_bookings_per_day: None,
+            max_bookings_per_week: None,
         }
     }
 
@@ -98,6 +118,39 @@ impl ServiceResource {
         false
     }
 
+    pub fn set_weight(&mut self, weight: i64) -> bool {
+        // Up to 100x more bookings than the other users
+        if !(1..=100).contains(&weight) {
+            return false;
+        }
+        self.weight = weight;
+        true
+    }
+
+    pub fn set_priority(&mut self, priority: i64) -> bool {
+        if priority < 1 {
+            return false;
+        }
+        self.priority = priority;
+        true
+    }
+
+    pub fn set_max_bookings_per_day(&mut self, max_bookings: Option<i64>) -> bool {
+        if max_bookings.is_some_and(|max| max < 1) {
+            return false;
+        }
+        self.max_bookings_per_day = max_bookings;
+        true
+    }
+
+    pub fn set_max_bookings_per_week(&mut self, max_bookings: Option<i64>) -> bool {
+        if max_bookings.is_some_and(|max| max < 1) {
+            return false;
+        }
+        self.max_bookings_per_week = max_bookings;
+        true
+    }
+
     pub fn get_schedule_id(&self) -> Option<ID> {
         match &self.availability {
             TimePlan::Schedule(id) => Some(id.clone()),
@@ -112,6 +165,9 @@ impl Entity<String> for ServiceResource {
     }
 }
 
+/// Two weeks of upcoming bookings
+pub const DEFAULT_DISTRIBUTION_WINDOW: i64 = 14;
+
 #[derive(Clone, Debug)]
 pub struct Service {
     pub id: ID,
@@ -119,6 +175,13 @@ pub struct Service {
     // interval: usize,
     pub multi_person: ServiceMultiPersonOptions,
     pub metadata: Option<serde_json::Value>,
+    /// Minimum number of minutes before the start of a booking to cancel it
+    pub cancellation_cutoff: i64,
+    /// Minimum number of minutes before the start of a booking to reschedule it
+    pub reschedule_cutoff: i64,
+    /// Number of days of upcoming bookings counted by the `EqualDistribution`
+    /// round robin algorithm to balance the bookings of the users
+    pub distribution_window: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
@@ -155,8 +218,19 @@ impl Service {
             account_id,
             multi_person: Default::default(),
             metadata: Default::default(),
+            cancellation_cutoff: 0,
+            reschedule_cutoff: 0,
+            distribution_window: DEFAULT_DISTRIBUTION_WINDOW,
         }
     }
+
+    pub fn set_distribution_window(&mut self, days: i64) -> bool {
+        if !(1..=365).contains(&days) {
+            return false;
+        }
+        self.distribution_window = days;
+        true
+    }
 }
 
 #[derive(Debug)]
@@ -166,6 +240,50 @@ pub struct ServiceWithUsers {
     pub users: Vec<ServiceResource>,
     pub multi_person: ServiceMultiPersonOptions,
     pub metadata: Option<serde_json::Value>,
+    pub cancellation_cutoff: i64,
+    pub reschedule_cutoff: i64,
+    pub distribution_window: i64,
+}
+
+impl ServiceWithUsers {
+    /// Whether a booking starting at `start_time` can still be cancelled at `now`
+    pub fn can_cancel_booking(&self, start_time: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        now + TimeDelta::minutes(self.cancellation_cutoff) <= start_time
+    }
+
+    /// Whether a booking starting at `start_time` can still be rescheduled at `now`
+    pub fn can_reschedule_booking(&self, start_time: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        now + TimeDelta::minutes(self.reschedule_cutoff) <= start_time
+    }
+}
+
+/// A slot of a `Service` held for some of its hosts, e.g. during the checkout of a customer
+///
+/// The slot isn't bookable by the others until the hold expires,
+/// unless it is confirmed into a booking or released before
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceHold {
+    pub id: ID,
+    pub service_id: ID,
+    /// Ids of the `User`s for whom the slot is held
+    pub host_user_ids: Vec<ID>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// The slot is bookable again after this time
+    pub expires_at: DateTime<Utc>,
+    pub created: DateTime<Utc>,
+}
+
+impl ServiceHold {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+impl Entity<ID> for ServiceHold {
+    fn id(&self) -> ID {
+        self.id.clone()
+    }
 }
 
 /// Enum for the different provider for busy calendars
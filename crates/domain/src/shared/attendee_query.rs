@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{IDQuery, StringQuery};
+
+/// Query parameters for searching on the attendees of an event
+/// An event matches if at least one of its attendees matches all the provided conditions
+#[derive(Deserialize, Serialize, TS, Debug, Clone, Default, ToSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+#[ts(export, rename = "AttendeeQuery", rename_all = "camelCase")]
+pub struct AttendeeQuery {
+    /// Optional query on the UUID of the internal user
+    #[serde(default)]
+    #[ts(optional)]
+    pub user_id: Option<IDQuery>,
+
+    /// Optional query on the email of the external attendee
+    #[serde(default)]
+    #[ts(optional)]
+    pub email: Option<StringQuery>,
+
+    /// Optional query on the response status of the attendee
+    /// e.g. "accepted", "declined", "tentative", "needsAction"
+    #[serde(default)]
+    #[ts(optional)]
+    pub response_status: Option<StringQuery>,
+}
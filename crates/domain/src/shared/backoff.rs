@@ -0,0 +1,14 @@
+use chrono::TimeDelta;
+
+/// Delay before retrying after the `attempts`th failed attempt
+/// The delay doubles with each attempt (exponential backoff), up to `max_delay`
+pub fn exponential_backoff(
+    attempts: i32,
+    base_delay: TimeDelta,
+    max_delay: TimeDelta,
+) -> TimeDelta {
+    let exponent = attempts.saturating_sub(1).clamp(0, 30) as u32;
+    base_delay
+        .checked_mul(2_i32.saturating_pow(exponent))
+        .map_or(max_delay, |delay| delay.min(max_delay))
+}
@@ -0,0 +1,76 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use serde::{Serialize, de::DeserializeOwned};
+use thiserror::Error;
+
+use crate::{Entity, ID};
+
+#[derive(Error, Debug, PartialEq)]
+#[error("The cursor is invalid")]
+pub struct InvalidCursorError;
+
+/// Position in a list of results, for getting the next page of results (keyset pagination)
+///
+/// The cursors are opaque strings for the clients (base64url encoded JSON)
+pub trait Cursor: Serialize + DeserializeOwned {
+    fn encode(&self) -> String {
+        // Serializing these structs can't fail
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(cursor: &str) -> Result<Self, InvalidCursorError> {
+        let json = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .map_err(|_| InvalidCursorError)?;
+        serde_json::from_slice(&json).map_err(|_| InvalidCursorError)
+    }
+}
+
+/// Position after an entity, in a list of entities ordered by their ids
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct IDCursor {
+    pub id: ID,
+}
+
+impl Cursor for IDCursor {}
+
+/// Page of entities ordered by their ids, from a list of up to `limit + 1` entities
+/// Returns the cursor of the next page when there are more entities than the limit
+pub fn paginate_by_id<T: Entity<ID>>(
+    mut entities: Vec<T>,
+    limit: usize,
+) -> (Vec<T>, Option<String>) {
+    if entities.len() <= limit {
+        return (entities, None);
+    }
+    entities.truncate(limit);
+    let next_cursor = entities
+        .last()
+        .map(|entity| IDCursor { id: entity.id() }.encode());
+    (entities, next_cursor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_encodes_and_decodes_cursors() {
+        let cursor = IDCursor { id: ID::default() };
+        let encoded = cursor.encode();
+        assert!(
+            encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        );
+        assert_eq!(IDCursor::decode(&encoded), Ok(cursor));
+    }
+
+    #[test]
+    fn it_rejects_invalid_cursors() {
+        assert_eq!(IDCursor::decode("not a cursor"), Err(InvalidCursorError));
+        assert_eq!(
+            IDCursor::decode(&URL_SAFE_NO_PAD.encode(r#"{"other":1}"#)),
+            Err(InvalidCursorError)
+        );
+    }
+}
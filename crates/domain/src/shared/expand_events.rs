@@ -84,17 +84,62 @@ pub fn expand_all_events_and_remove_exceptions(
     Ok(all_expanded_events)
 }
 
+/// Instances of a recurring event, materialized for a window of time (the exceptions are removed)
+/// Used for getting the instances without expanding the recurrence of the event
+#[derive(Debug, Clone)]
+pub struct MaterializedEventInstances {
+    pub event_id: ID,
+    /// Version of the event the instances are materialized from
+    pub event_version: i64,
+    /// Original start times of the exceptions of the event, at the time of the materialization
+    pub exceptions: Vec<DateTime<Utc>>,
+    /// All the instances starting in the window are materialized
+    pub window: TimeSpan,
+    pub instances: Vec<EventInstance>,
+}
+
+impl MaterializedEventInstances {
+    /// Materialize up to `max_instances` instances of the event in the window
+    /// If there are more instances, the window is shortened to end at the start of the last instance materialized
+    pub fn new(
+        calendar: &Calendar,
+        event: &CalendarEvent,
+        exceptions: Vec<DateTime<Utc>>,
+        window: TimeSpan,
+        max_instances: u16,
+    ) -> anyhow::Result<Self> {
+        let instances =
+            event.expand_with_limit(Some(window.clone()), &calendar.settings, max_instances)?;
+        let window = match instances.last() {
+            Some(last) if instances.len() >= usize::from(max_instances) => {
+                TimeSpan::new(window.start(), last.start_time)
+            }
+            _ => window,
+        };
+        let instances = event.remove_changed_instances(instances, &exceptions);
+
+        Ok(Self {
+            event_id: event.id.clone(),
+            event_version: event.version,
+            exceptions,
+            window,
+            instances,
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
 
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
 
-    use super::expand_event_and_remove_exceptions;
+    use super::{MaterializedEventInstances, expand_event_and_remove_exceptions};
     use crate::{
         Calendar,
         CalendarEvent,
         ID,
+        RRuleOptions,
         TimeSpan,
         generate_map_exceptions_original_start_times,
     };
@@ -279,4 +324,46 @@ mod test {
 
         assert_eq!(instances.len(), 1);
     }
+
+    #[test]
+    fn materialized_instances_are_the_expanded_instances_without_the_exceptions() {
+        let calendar = Calendar::default();
+        let start_time = DateTime::from_timestamp_millis(1000 * 60 * 60 * 24 * 365).unwrap();
+        let one_day = chrono::Duration::days(1);
+        let mut event = CalendarEvent {
+            start_time,
+            duration: 1000 * 60 * 60,
+            ..Default::default()
+        };
+        event
+            .set_recurrence(RRuleOptions {
+                count: Some(10),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let window = TimeSpan::new(start_time, start_time + one_day * 30);
+        let exceptions = vec![start_time + one_day];
+        let materialized = MaterializedEventInstances::new(
+            &calendar,
+            &event,
+            exceptions.clone(),
+            window.clone(),
+            100,
+        )
+        .unwrap();
+        assert_eq!(materialized.window.end(), window.end());
+        assert_eq!(
+            materialized.instances,
+            expand_event_and_remove_exceptions(&calendar, &event, &exceptions, window.clone())
+                .unwrap()
+        );
+        assert_eq!(materialized.instances.len(), 9);
+
+        // The window ends at the last instance materialized when there are too many instances
+        let materialized =
+            MaterializedEventInstances::new(&calendar, &event, exceptions, window, 5).unwrap();
+        assert_eq!(materialized.window.end(), start_time + one_day * 4);
+        assert_eq!(materialized.instances.len(), 4);
+    }
 }
@@ -1,3 +1,6 @@
+pub mod attendee_query;
+pub mod backoff;
+pub mod cursor;
 pub mod datetime_query;
 pub mod entity;
 pub mod expand_events;
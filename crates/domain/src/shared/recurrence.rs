@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, fmt::Display, str::FromStr};
+use std::{fmt::Display, str::FromStr};
 
 use chrono::prelude::*;
 use rrule::{Frequency, RRule, RRuleSet};
@@ -21,7 +21,7 @@ pub enum RRuleFrequency {
 }
 
 /// Options for recurring events
-#[derive(Clone, Debug, Serialize, Deserialize, TS, ToSchema)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, TS, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[ts(export)]
 pub struct RRuleOptions {
@@ -46,10 +46,12 @@ pub struct RRuleOptions {
 
     /// Select specific weekdays
     /// E.g. `["Mon"]`, `["Mon", "Tue"]`, `["1Mon"]`, `["-1Sun"]`
+    /// Without a position, every matching weekday of the period is selected (e.g. every Monday of the month)
     #[ts(optional)]
     pub byweekday: Option<Vec<WeekDayRecurrence>>,
 
     /// Select specific month days
+    /// The negative days are counted from the end of the month (e.g. `-1` is the last day)
     #[ts(optional)]
     pub bymonthday: Option<Vec<isize>>,
 
@@ -67,6 +69,20 @@ pub struct RRuleOptions {
     #[ts(optional)]
     pub byweekno: Option<Vec<isize>>,
 
+    /// Select specific hours (0-23)
+    /// Default: the hour of the start time
+    #[ts(optional)]
+    pub byhour: Option<Vec<isize>>,
+
+    /// Select specific minutes (0-59)
+    /// Default: the minute of the start time
+    #[ts(optional)]
+    pub byminute: Option<Vec<isize>>,
+
+    /// Additional dates of occurrences (RDATE), on top of the ones generated by the rule
+    #[ts(type = "Array<Date>", optional)]
+    pub rdates: Option<Vec<DateTime<Utc>>>,
+
     /// Specify the week start day
     /// Default: CalendarSettings.week_start (Week start configured in the calendar settings)
     /// Possible values: "Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"
@@ -98,12 +114,24 @@ impl RRuleOptions {
                 && is_none_or_empty(&self.bymonth)
                 && is_none_or_empty(&self.bymonthday)
                 && is_none_or_empty(&self.byyearday)
+                && is_none_or_empty(&self.byhour)
+                && is_none_or_empty(&self.byminute)
             {
                 // No other by* rule was specified
                 return false;
             }
         }
 
+        let in_range = |values: &Option<Vec<isize>>, max: isize| {
+            values
+                .iter()
+                .flatten()
+                .all(|value| (0..=max).contains(value))
+        };
+        if !in_range(&self.byhour, 23) || !in_range(&self.byminute, 59) {
+            return false;
+        }
+
         true
     }
 
@@ -123,7 +151,7 @@ impl RRuleOptions {
             for wday in opts_byweekday {
                 match wday.nth() {
                     None => {
-                        bynweekday.push(rrule::NWeekday::Nth(1, wday.weekday()));
+                        bynweekday.push(rrule::NWeekday::Every(wday.weekday()));
                     }
                     Some(n) => {
                         bynweekday.push(rrule::NWeekday::Nth(n as i16, wday.weekday()));
@@ -132,21 +160,23 @@ impl RRuleOptions {
             }
         }
 
-        let mut bymonthday = Vec::new();
-        let mut bynmonthday = Vec::new();
-        if let Some(opts_bymonthday) = &self.bymonthday {
-            for monthday in opts_bymonthday {
-                match monthday.cmp(&0) {
-                    Ordering::Greater => bymonthday.push(monthday),
-                    Ordering::Less => bynmonthday.push(monthday),
-                    Ordering::Equal => {}
-                }
-            }
-        }
+        // The time of the occurrences is the one of the start time, unless specified
+        let time_parts = |values: &Option<Vec<isize>>, default: u32| match values {
+            Some(values) if !values.is_empty() => values.iter().map(|v| *v as u8).collect(),
+            _ => vec![default as u8],
+        };
 
         let mut rule = RRule::new(freq_convert(&self.freq))
             .by_month(&self.bymonth.clone().unwrap_or_default())
-            .by_month_day(bymonthday.into_iter().map(|d| *d as i8).collect::<Vec<_>>())
+            .by_month_day(
+                // The negative days (from the end of the month) are handled by the rrule crate
+                self.bymonthday
+                    .iter()
+                    .flatten()
+                    .filter(|d| **d != 0)
+                    .map(|d| *d as i8)
+                    .collect::<Vec<_>>(),
+            )
             .by_weekday(bynweekday)
             .by_year_day(
                 self.byyearday
@@ -172,8 +202,8 @@ impl RRuleOptions {
                     .map(|w| w as i8)
                     .collect::<Vec<_>>(),
             )
-            .by_hour(vec![dtstart.hour() as u8])
-            .by_minute(vec![dtstart.minute() as u8])
+            .by_hour(time_parts(&self.byhour, dtstart.hour()))
+            .by_minute(time_parts(&self.byminute, dtstart.minute()))
             .by_second(vec![dtstart.second() as u8])
             .week_start(self.weekstart.unwrap_or(calendar_settings.week_start))
             .interval(self.interval as u16);
@@ -186,7 +216,16 @@ impl RRuleOptions {
             rule = rule.until(until);
         }
 
-        Ok(rule.build(dtstart)?)
+        let mut rrule_set = rule.build(dtstart)?;
+        for rdate in self.rdates.iter().flatten() {
+            rrule_set = rrule_set.rdate(rdate.with_timezone(&rrule::Tz::Tz(timezone)));
+        }
+        Ok(rrule_set)
+    }
+
+    /// Last additional date (RDATE), if any
+    pub fn last_rdate(&self) -> Option<DateTime<Utc>> {
+        self.rdates.iter().flatten().max().copied()
     }
 }
 
@@ -203,6 +242,9 @@ impl Default for RRuleOptions {
             bymonth: None,
             byyearday: None,
             byweekno: None,
+            byhour: None,
+            byminute: None,
+            rdates: None,
             weekstart: None,
         }
     }
@@ -328,6 +370,10 @@ impl<'de> Deserialize<'de> for WeekDayRecurrence {
 
 #[cfg(test)]
 mod test {
+    use chrono::TimeDelta;
+    use chrono_tz::Tz;
+    use rand::{RngExt, SeedableRng, rngs::StdRng};
+
     use super::*;
 
     #[test]
@@ -413,4 +459,271 @@ mod test {
             "-1Sun"
         );
     }
+
+    /// Random recurrence, with its RRULE (and RDATE) written independently of the conversions of `RRuleOptions`
+    fn random_recurrence(
+        rng: &mut StdRng,
+        dtstart: DateTime<Utc>,
+    ) -> (RRuleOptions, String, Vec<String>) {
+        const WEEKDAYS: [(Weekday, &str); 7] = [
+            (Weekday::Mon, "MO"),
+            (Weekday::Tue, "TU"),
+            (Weekday::Wed, "WE"),
+            (Weekday::Thu, "TH"),
+            (Weekday::Fri, "FR"),
+            (Weekday::Sat, "SA"),
+            (Weekday::Sun, "SU"),
+        ];
+        let join = |values: &[isize]| {
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let numbers = |rng: &mut StdRng, range: std::ops::RangeInclusive<i64>, signed| {
+            let mut values = (0..rng.random_range(1..=3))
+                .map(|_| {
+                    let value = rng.random_range(range.clone()) as isize;
+                    if signed && rng.random_bool(0.3) {
+                        -value
+                    } else {
+                        value
+                    }
+                })
+                .collect::<Vec<_>>();
+            values.dedup();
+            values
+        };
+
+        let mut options = RRuleOptions::default();
+        let (freq, freq_name) = [
+            (RRuleFrequency::Yearly, "YEARLY"),
+            (RRuleFrequency::Monthly, "MONTHLY"),
+            (RRuleFrequency::Weekly, "WEEKLY"),
+            (RRuleFrequency::Daily, "DAILY"),
+        ][rng.random_range(0..4)]
+        .clone();
+        options.freq = freq.clone();
+        let mut rrule = vec![format!("FREQ={freq_name}")];
+
+        options.interval = rng.random_range(1..=3i64) as isize;
+        rrule.push(format!("INTERVAL={}", options.interval));
+
+        match rng.random_range(0..3) {
+            0 => {
+                let count = rng.random_range(1..=20);
+                options.count = Some(count);
+                rrule.push(format!("COUNT={count}"));
+            }
+            1 => {
+                let until = dtstart + TimeDelta::days(rng.random_range(10..=800));
+                options.until = Some(until);
+                rrule.push(format!("UNTIL={}", until.format("%Y%m%dT%H%M%SZ")));
+            }
+            _ => {}
+        }
+
+        let yearly = freq == RRuleFrequency::Yearly;
+        let monthly = freq == RRuleFrequency::Monthly;
+        let mut has_by_rule = false;
+
+        // BYDAY with a position is only allowed for monthly and yearly rules (without BYWEEKNO)
+        let byweekno = (yearly && rng.random_bool(0.2)).then(|| numbers(rng, 1..=52, true));
+        if rng.random_bool(0.4) {
+            let nth_allowed = (monthly || yearly) && byweekno.is_none();
+            let mut byweekday = Vec::new();
+            let mut byday = Vec::new();
+            for _ in 0..rng.random_range(1..=3) {
+                let (weekday, name) = WEEKDAYS[rng.random_range(0..7)];
+                if nth_allowed && rng.random_bool(0.5) {
+                    let n = rng.random_range(1..=4i64) as isize
+                        * if rng.random_bool(0.3) { -1 } else { 1 };
+                    byweekday.push(WeekDayRecurrence::new_nth(weekday, n).unwrap());
+                    byday.push(format!("{n}{name}"));
+                } else {
+                    byweekday.push(WeekDayRecurrence::new(weekday).unwrap());
+                    byday.push(name.to_string());
+                }
+            }
+            options.byweekday = Some(byweekday);
+            rrule.push(format!("BYDAY={}", byday.join(",")));
+            has_by_rule = true;
+        }
+        if let Some(byweekno) = byweekno {
+            rrule.push(format!("BYWEEKNO={}", join(&byweekno)));
+            options.byweekno = Some(byweekno);
+            has_by_rule = true;
+        }
+        if freq != RRuleFrequency::Weekly && rng.random_bool(0.3) {
+            let bymonthday = numbers(rng, 1..=28, true);
+            rrule.push(format!("BYMONTHDAY={}", join(&bymonthday)));
+            options.bymonthday = Some(bymonthday);
+            has_by_rule = true;
+        }
+        if yearly && rng.random_bool(0.3) {
+            let byyearday = numbers(rng, 1..=366, true);
+            rrule.push(format!("BYYEARDAY={}", join(&byyearday)));
+            options.byyearday = Some(byyearday);
+            has_by_rule = true;
+        }
+        if rng.random_bool(0.3) {
+            let bymonth = numbers(rng, 1..=12, false);
+            rrule.push(format!("BYMONTH={}", join(&bymonth)));
+            options.bymonth = Some(
+                bymonth
+                    .iter()
+                    .map(|m| Month::try_from(*m as u8).unwrap())
+                    .collect(),
+            );
+            has_by_rule = true;
+        }
+        if rng.random_bool(0.3) {
+            let byhour = numbers(rng, 0..=23, false);
+            rrule.push(format!("BYHOUR={}", join(&byhour)));
+            options.byhour = Some(byhour);
+        }
+        if rng.random_bool(0.3) {
+            let byminute = numbers(rng, 0..=59, false);
+            rrule.push(format!("BYMINUTE={}", join(&byminute)));
+            options.byminute = Some(byminute);
+        }
+        if has_by_rule && rng.random_bool(0.2) {
+            let bysetpos = numbers(rng, 1..=3, true);
+            rrule.push(format!("BYSETPOS={}", join(&bysetpos)));
+            options.bysetpos = Some(bysetpos);
+        }
+        if rng.random_bool(0.3) {
+            let (weekday, name) = WEEKDAYS[rng.random_range(0..7)];
+            options.weekstart = Some(weekday);
+            rrule.push(format!("WKST={name}"));
+        }
+
+        let rdates = (0..rng.random_range(0..=2))
+            .map(|_| dtstart + TimeDelta::minutes(rng.random_range(-60 * 24 * 30..=60 * 24 * 400)))
+            .collect::<Vec<_>>();
+        let rdate_lines = rdates
+            .iter()
+            .map(|rdate| format!("RDATE:{}", rdate.format("%Y%m%dT%H%M%SZ")))
+            .collect();
+        if !rdates.is_empty() {
+            options.rdates = Some(rdates);
+        }
+
+        (options, rrule.join(";"), rdate_lines)
+    }
+
+    #[test]
+    fn expands_like_the_rrule_crate() {
+        const TIMEZONES: [Tz; 4] = [
+            chrono_tz::UTC,
+            chrono_tz::Europe::Paris,
+            chrono_tz::America::New_York,
+            chrono_tz::Asia::Tokyo,
+        ];
+        let mut rng = StdRng::seed_from_u64(5545);
+
+        for _ in 0..500 {
+            let timezone = TIMEZONES[rng.random_range(0..TIMEZONES.len())];
+            let settings = CalendarSettings {
+                timezone,
+                week_start: [Weekday::Mon, Weekday::Sun][rng.random_range(0..2)],
+            };
+            let dtstart = Utc
+                .with_ymd_and_hms(
+                    rng.random_range(2020..=2035),
+                    rng.random_range(1..=12),
+                    rng.random_range(1..=28),
+                    rng.random_range(0..=23),
+                    rng.random_range(0..4) * 15,
+                    0,
+                )
+                .unwrap();
+            let (options, rrule, rdates) = random_recurrence(&mut rng, dtstart);
+            assert!(options.is_valid(), "{rrule}");
+
+            // The week start of the calendar is used when the rule doesn't have one
+            let rrule = match options.weekstart {
+                Some(_) => rrule,
+                None => format!(
+                    "{rrule};WKST={}",
+                    settings.week_start.to_string()[..2].to_uppercase()
+                ),
+            };
+            let local_start = dtstart.with_timezone(&timezone).format("%Y%m%dT%H%M%S");
+            let expected = format!(
+                "DTSTART;TZID={timezone}:{local_start}\nRRULE:{rrule}\n{}",
+                rdates.join("\n")
+            )
+            .trim_end()
+            .parse::<RRuleSet>()
+            .unwrap_or_else(|e| panic!("{rrule}: {e}"))
+            .all(100);
+
+            let actual = options
+                .get_parsed_options(dtstart, &settings)
+                .unwrap_or_else(|e| panic!("{rrule}: {e}"))
+                .all(100);
+
+            let to_utc = |dates: Vec<DateTime<rrule::Tz>>| {
+                dates
+                    .into_iter()
+                    .map(|date| date.with_timezone(&Utc))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                to_utc(actual.dates),
+                to_utc(expected.dates),
+                "DTSTART;TZID={timezone}:{local_start} RRULE:{rrule} {rdates:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn expands_the_stored_recurrences_as_rfc_5545() {
+        // 2025-01-06 is a Monday
+        let dtstart = Utc.with_ymd_and_hms(2025, 1, 6, 9, 0, 0).unwrap();
+        let settings = CalendarSettings {
+            timezone: chrono_tz::UTC,
+            week_start: Weekday::Mon,
+        };
+        let expand = |stored: &str| {
+            let options: RRuleOptions = serde_json::from_str(stored).unwrap();
+            options
+                .get_parsed_options(dtstart, &settings)
+                .unwrap()
+                .all(6)
+                .dates
+                .into_iter()
+                .map(|date| date.with_timezone(&Utc).date_naive())
+                .collect::<Vec<_>>()
+        };
+        let dates = |dates: &[(u32, u32)]| {
+            dates
+                .iter()
+                .map(|(month, day)| NaiveDate::from_ymd_opt(2025, *month, *day).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        // Every Tuesday and Friday, and not every day
+        assert_eq!(
+            expand(r#"{"freq":"weekly","interval":1,"byweekday":["Tue","Fri"]}"#),
+            dates(&[(1, 7), (1, 10), (1, 14), (1, 17), (1, 21), (1, 24)])
+        );
+        // Every Monday of the month, and not only the first one
+        assert_eq!(
+            expand(r#"{"freq":"monthly","interval":1,"byweekday":["Mon"]}"#),
+            dates(&[(1, 6), (1, 13), (1, 20), (1, 27), (2, 3), (2, 10)])
+        );
+        // The weekdays with a position are unchanged
+        assert_eq!(
+            expand(r#"{"freq":"monthly","interval":1,"byweekday":["1Mon"]}"#),
+            dates(&[(1, 6), (2, 3), (3, 3), (4, 7), (5, 5), (6, 2)])
+        );
+        // The last day of the month, and not the day of the start
+        assert_eq!(
+            expand(r#"{"freq":"monthly","interval":1,"bymonthday":[-1]}"#),
+            dates(&[(1, 31), (2, 28), (3, 31), (4, 30), (5, 31), (6, 30)])
+        );
+    }
 }
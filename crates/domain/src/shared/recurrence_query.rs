@@ -14,4 +14,8 @@ pub enum RecurrenceQuery {
     /// Exists and is recurring at a specific date
     #[ts(type = "Date")]
     ExistsAndRecurringAt(DateTime<Utc>),
+
+    /// Exists and contains the given options (e.g. `{ "freq": "weekly", "byhour": [9] }`)
+    /// The lists of the recurrence (e.g. `byhour` or `rdates`) need to contain the given values
+    Matches(serde_json::Value),
 }
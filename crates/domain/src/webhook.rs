@@ -0,0 +1,197 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// Type of the changes of events that can be sent to the account webhook
+/// Accounts opt in to each of them in their webhook settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum WebhookEventType {
+    /// An event has been created
+    EventCreated,
+    /// An event has been updated
+    EventUpdated,
+    /// An event has been cancelled (its status has been set to cancelled)
+    EventCancelled,
+    /// An event has been deleted
+    EventDeleted,
+}
+
+/// Header containing the signature of the webhooks sent to the accounts
+///
+/// Its value is `t=<timestamp>,v1=<signature>`, where the timestamp is the unix time (in seconds)
+/// of the sending, and the signature is the hex encoded HMAC-SHA256 of `<timestamp>.<body>`
+/// with the webhook key. While the previous key is still valid (after a rotation), there is one
+/// `v1` signature per valid key.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "nittei-signature";
+
+/// Version of the signature scheme
+const SIGNATURE_SCHEME: &str = "v1";
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum WebhookSignatureError {
+    #[error("The signature header is malformed")]
+    MalformedHeader,
+    #[error("The timestamp of the signature is outside of the tolerance")]
+    TimestampOutsideTolerance,
+    #[error("No signature matches the payload")]
+    NoMatchingSignature,
+}
+
+fn webhook_mac(key: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    // HMAC accepts keys of any size
+    #[allow(clippy::expect_used)]
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC can take a key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+/// Sign the body of a webhook sent at `timestamp` with each of the keys
+/// Returns the value of the `WEBHOOK_SIGNATURE_HEADER` header
+pub fn sign_webhook_payload(keys: &[&str], timestamp: DateTime<Utc>, body: &[u8]) -> String {
+    let timestamp = timestamp.timestamp();
+    let signatures = keys.iter().map(|key| {
+        let signature = hex::encode(webhook_mac(key, timestamp, body).finalize().into_bytes());
+        format!("{SIGNATURE_SCHEME}={signature}")
+    });
+    std::iter::once(format!("t={timestamp}"))
+        .chain(signatures)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Verify the `WEBHOOK_SIGNATURE_HEADER` header of a received webhook
+///
+/// The webhook is valid if one of its signatures matches the body signed with the key,
+/// and if it was sent at most `tolerance` before (or after) `now`, to prevent replays
+pub fn verify_webhook_signature(
+    header: &str,
+    body: &[u8],
+    key: &str,
+    tolerance: TimeDelta,
+    now: DateTime<Utc>,
+) -> Result<(), WebhookSignatureError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => {
+                timestamp = Some(
+                    value
+                        .parse::<i64>()
+                        .map_err(|_| WebhookSignatureError::MalformedHeader)?,
+                );
+            }
+            Some((SIGNATURE_SCHEME, value)) => {
+                signatures
+                    .push(hex::decode(value).map_err(|_| WebhookSignatureError::MalformedHeader)?);
+            }
+            // Other schemes are ignored
+            Some(_) => {}
+            None => return Err(WebhookSignatureError::MalformedHeader),
+        }
+    }
+    let timestamp = timestamp.ok_or(WebhookSignatureError::MalformedHeader)?;
+    if signatures.is_empty() {
+        return Err(WebhookSignatureError::MalformedHeader);
+    }
+
+    if (now.timestamp() - timestamp).abs() > tolerance.num_seconds() {
+        return Err(WebhookSignatureError::TimestampOutsideTolerance);
+    }
+
+    // `verify_slice` compares in constant time
+    let matches = signatures.iter().any(|signature| {
+        webhook_mac(key, timestamp, body)
+            .verify_slice(signature)
+            .is_ok()
+    });
+    if matches {
+        Ok(())
+    } else {
+        Err(WebhookSignatureError::NoMatchingSignature)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap()
+    }
+
+    #[test]
+    fn it_signs_and_verifies_payload() {
+        let body = br#"{"id":"1"}"#;
+        let header = sign_webhook_payload(&["key"], now(), body);
+        assert!(header.starts_with("t=1700000000,v1="));
+
+        assert_eq!(
+            verify_webhook_signature(&header, body, "key", TimeDelta::minutes(5), now()),
+            Ok(())
+        );
+        assert_eq!(
+            verify_webhook_signature(&header, body, "other", TimeDelta::minutes(5), now()),
+            Err(WebhookSignatureError::NoMatchingSignature)
+        );
+        assert_eq!(
+            verify_webhook_signature(&header, b"{}", "key", TimeDelta::minutes(5), now()),
+            Err(WebhookSignatureError::NoMatchingSignature)
+        );
+    }
+
+    #[test]
+    fn it_verifies_any_of_the_signatures() {
+        let body = b"body";
+        let header = sign_webhook_payload(&["new", "previous"], now(), body);
+        assert_eq!(header.matches("v1=").count(), 2);
+
+        for key in ["new", "previous"] {
+            assert_eq!(
+                verify_webhook_signature(&header, body, key, TimeDelta::minutes(5), now()),
+                Ok(())
+            );
+        }
+    }
+
+    #[test]
+    fn it_rejects_replayed_payload() {
+        let body = b"body";
+        let header = sign_webhook_payload(&["key"], now(), body);
+
+        assert_eq!(
+            verify_webhook_signature(
+                &header,
+                body,
+                "key",
+                TimeDelta::minutes(5),
+                now() + TimeDelta::minutes(6)
+            ),
+            Err(WebhookSignatureError::TimestampOutsideTolerance)
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_header() {
+        for header in [
+            "",
+            "t=1700000000",
+            "v1=abcd",
+            "t=abc,v1=abcd",
+            "t=1700000000,v1=xyz",
+        ] {
+            assert_eq!(
+                verify_webhook_signature(header, b"body", "key", TimeDelta::minutes(5), now()),
+                Err(WebhookSignatureError::MalformedHeader)
+            );
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+use crate::{Entity, ID, Meta, WebhookEventType, shared::backoff::exponential_backoff};
+
+/// Status of a webhook delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub enum WebhookDeliveryStatus {
+    /// Waiting to be attempted (or retried after a failed attempt)
+    Pending,
+    /// The endpoint answered with a success status code
+    Succeeded,
+    /// All the attempts failed until the retry horizon, it is only attempted again if redelivered
+    Failed,
+}
+
+impl From<WebhookDeliveryStatus> for String {
+    fn from(e: WebhookDeliveryStatus) -> Self {
+        match e {
+            WebhookDeliveryStatus::Pending => "pending".into(),
+            WebhookDeliveryStatus::Succeeded => "succeeded".into(),
+            WebhookDeliveryStatus::Failed => "failed".into(),
+        }
+    }
+}
+
+impl TryFrom<String> for WebhookDeliveryStatus {
+    type Error = anyhow::Error;
+    fn try_from(e: String) -> anyhow::Result<WebhookDeliveryStatus> {
+        Ok(match &e[..] {
+            "pending" => WebhookDeliveryStatus::Pending,
+            "succeeded" => WebhookDeliveryStatus::Succeeded,
+            "failed" => WebhookDeliveryStatus::Failed,
+            _ => Err(anyhow::anyhow!("Invalid webhook delivery status"))?,
+        })
+    }
+}
+
+/// Webhook to deliver to the endpoint of an account
+/// Failed attempts are retried (with an exponential backoff) until `retry_until`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookDelivery {
+    /// Unique ID
+    pub id: ID,
+    /// Account ID
+    pub account_id: ID,
+    /// Type of the change of the event, None for the reminders
+    pub event_type: Option<WebhookEventType>,
+    /// Body of the webhook
+    pub payload: serde_json::Value,
+    /// Status of the delivery
+    pub status: WebhookDeliveryStatus,
+    /// Number of times the delivery has been attempted
+    pub attempts: i32,
+    /// When the delivery will be attempted (again)
+    pub next_attempt_at: DateTime<Utc>,
+    /// Failed attempts are retried until then
+    pub retry_until: DateTime<Utc>,
+    /// Creation date
+    pub created: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn new(
+        account_id: ID,
+        event_type: Option<WebhookEventType>,
+        payload: serde_json::Value,
+        retry_horizon: TimeDelta,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Default::default(),
+            account_id,
+            event_type,
+            payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            retry_until: now + retry_horizon,
+            created: now,
+        }
+    }
+
+    /// Lease the delivery for an attempt, so that the worker doesn't attempt it at the same time
+    pub fn claim(&mut self, lease_until: DateTime<Utc>) {
+        self.attempts += 1;
+        self.next_attempt_at = lease_until;
+    }
+
+    /// When to retry the delivery after its last attempt failed at `now`
+    /// None if it is past the retry horizon
+    pub fn next_retry_at(
+        &self,
+        base_delay: TimeDelta,
+        max_delay: TimeDelta,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let retry_at = now + exponential_backoff(self.attempts, base_delay, max_delay);
+        (retry_at <= self.retry_until).then_some(retry_at)
+    }
+}
+
+impl Entity<ID> for WebhookDelivery {
+    fn id(&self) -> ID {
+        self.id.clone()
+    }
+}
+
+impl Meta<ID> for WebhookDelivery {
+    fn account_id(&self) -> &ID {
+        &self.account_id
+    }
+}
+
+/// Attempt to deliver a webhook
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookDeliveryAttempt {
+    /// Unique ID
+    pub id: ID,
+    /// Delivery ID
+    pub delivery_id: ID,
+    /// URL of the endpoint at the time of the attempt
+    pub url: String,
+    /// When the attempt was made
+    pub attempted_at: DateTime<Utc>,
+    /// Status code of the response, None if there was no response (e.g. timeout)
+    pub status_code: Option<u16>,
+    /// Time taken by the endpoint to respond (in milliseconds)
+    pub latency_millis: i64,
+    /// Beginning of the body of the response, or the error if there was no response
+    pub response_excerpt: Option<String>,
+}
+
+impl WebhookDeliveryAttempt {
+    /// Max length (in characters) of the excerpt of the response kept
+    pub const RESPONSE_EXCERPT_LEN: usize = 500;
+
+    /// Keep the beginning of the response (or error)
+    pub fn excerpt(response: &str) -> Option<String> {
+        let excerpt = response
+            .chars()
+            .take(Self::RESPONSE_EXCERPT_LEN)
+            .collect::<String>();
+        (!excerpt.is_empty()).then_some(excerpt)
+    }
+
+    /// The attempt succeeded if the endpoint answered with a 2xx status code
+    pub fn is_success(&self) -> bool {
+        self.status_code
+            .is_some_and(|status_code| (200..300).contains(&status_code))
+    }
+}
+
+/// Health of the webhook endpoint of an account
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookEndpointStatus {
+    /// Account ID
+    pub account_id: ID,
+    /// URL of the endpoint
+    pub url: String,
+    /// Number of failed attempts since the last successful one
+    pub consecutive_failures: i32,
+    /// When the endpoint has been marked as failing (too many consecutive failures)
+    /// None if the endpoint is healthy
+    pub failing_since: Option<DateTime<Utc>>,
+    /// When the last attempt was made
+    pub last_attempt_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_retries_until_the_horizon() {
+        let now = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let mut delivery = WebhookDelivery::new(
+            ID::default(),
+            None,
+            serde_json::json!({}),
+            TimeDelta::minutes(10),
+            now,
+        );
+        let base_delay = TimeDelta::minutes(1);
+        let max_delay = TimeDelta::hours(1);
+
+        delivery.claim(now + TimeDelta::minutes(1));
+        assert_eq!(delivery.attempts, 1);
+        assert_eq!(
+            delivery.next_retry_at(base_delay, max_delay, now),
+            Some(now + TimeDelta::minutes(1))
+        );
+        delivery.claim(now + TimeDelta::minutes(1));
+        delivery.claim(now + TimeDelta::minutes(1));
+        assert_eq!(
+            delivery.next_retry_at(base_delay, max_delay, now + TimeDelta::minutes(5)),
+            Some(now + TimeDelta::minutes(9))
+        );
+        delivery.claim(now + TimeDelta::minutes(1));
+        assert_eq!(
+            delivery.next_retry_at(base_delay, max_delay, now + TimeDelta::minutes(9)),
+            None
+        );
+    }
+
+    #[test]
+    fn it_keeps_an_excerpt_of_the_response() {
+        assert_eq!(WebhookDeliveryAttempt::excerpt(""), None);
+        assert_eq!(WebhookDeliveryAttempt::excerpt("ok"), Some("ok".into()));
+        let long_response = "é".repeat(WebhookDeliveryAttempt::RESPONSE_EXCERPT_LEN + 1);
+        assert_eq!(
+            WebhookDeliveryAttempt::excerpt(&long_response)
+                .unwrap()
+                .chars()
+                .count(),
+            WebhookDeliveryAttempt::RESPONSE_EXCERPT_LEN
+        );
+    }
+}
@@ -0,0 +1,33 @@
+-- Re-create the `events_groups` table
+-- Groups are now a first-class resource, scoped to an account (and a user)
+CREATE TABLE IF NOT EXISTS events_groups (
+  group_uid uuid PRIMARY KEY DEFAULT uuid_generate_v4() NOT NULL,
+  calendar_uid uuid NOT NULL REFERENCES calendars(calendar_uid) ON DELETE CASCADE,
+  user_uid uuid NOT NULL REFERENCES users(user_uid) ON DELETE CASCADE,
+  account_uid uuid NOT NULL REFERENCES accounts(account_uid) ON DELETE CASCADE,
+  /*
+   parent_id is only useful for linking this group to an external object outside Nittei ecosystem
+   it's indexed, it's not a foreign key, and it's a string as the external object's id can have any format
+   */
+  parent_id text,
+  external_id text
+);
+
+-- Add a unique constraint on `external_id` in `events_groups` (per account)
+ALTER TABLE
+  events_groups
+ADD
+  CONSTRAINT events_groups__account_uid__external_id__unique UNIQUE (account_uid, external_id);
+
+-- Add an index on `parent_id` in `events_groups` (per account)
+CREATE INDEX IF NOT EXISTS events_groups__account_uid__parent_id_idx ON events_groups (account_uid, parent_id);
+
+-- Add the `group_uid` column to the `calendar_events` table
+-- Deleting a group detaches its events, it doesn't delete them
+ALTER TABLE
+  calendar_events
+ADD
+  COLUMN IF NOT EXISTS group_uid uuid REFERENCES events_groups(group_uid) ON DELETE SET NULL;
+
+-- Add an index on `group_uid` in `calendar_events`
+CREATE INDEX IF NOT EXISTS calendar_events__group_uid_idx ON calendar_events (group_uid);
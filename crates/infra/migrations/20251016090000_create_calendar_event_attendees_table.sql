@@ -0,0 +1,21 @@
+-- Create the `calendar_event_attendees` table
+-- An attendee is either an internal user (`user_uid`) or an external person (`email`)
+CREATE TABLE IF NOT EXISTS calendar_event_attendees (
+  attendee_uid uuid PRIMARY KEY DEFAULT uuid_generate_v4() NOT NULL,
+  event_uid uuid NOT NULL REFERENCES calendar_events(event_uid) ON DELETE CASCADE,
+  user_uid uuid REFERENCES users(user_uid) ON DELETE CASCADE,
+  email text,
+  role text NOT NULL DEFAULT 'required',
+  response_status text NOT NULL DEFAULT 'needsAction',
+  -- Position of the attendee in the list of attendees of the event
+  position integer NOT NULL DEFAULT 0,
+  CONSTRAINT calendar_event_attendees__user_uid_xor_email CHECK ((user_uid IS NULL) <> (email IS NULL)),
+  CONSTRAINT calendar_event_attendees__event_uid__user_uid__unique UNIQUE (event_uid, user_uid),
+  CONSTRAINT calendar_event_attendees__event_uid__email__unique UNIQUE (event_uid, email)
+);
+
+-- Add an index on `user_uid` in `calendar_event_attendees`
+-- This is used for finding the events a user is attending (e.g. freebusy)
+CREATE INDEX IF NOT EXISTS calendar_event_attendees__user_uid_idx ON calendar_event_attendees (user_uid)
+WHERE
+  user_uid IS NOT NULL;
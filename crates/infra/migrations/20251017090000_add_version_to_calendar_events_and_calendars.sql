@@ -0,0 +1,7 @@
+-- Purpose: Add a version to the events and the calendars, for optimistic concurrency control
+-- The version is incremented each time the row is saved, and exposed as an ETag by the API
+ALTER TABLE "calendar_events"
+ADD COLUMN IF NOT EXISTS "version" BIGINT NOT NULL DEFAULT 0;
+
+ALTER TABLE "calendars"
+ADD COLUMN IF NOT EXISTS "version" BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,25 @@
+-- Create the `outbox_messages` table
+-- The work of the use cases' subscribers is recorded here, in the same transaction as the write
+-- that triggered it, and processed afterwards by the outbox worker
+CREATE TABLE IF NOT EXISTS outbox_messages (
+  message_uid uuid PRIMARY KEY DEFAULT uuid_generate_v4() NOT NULL,
+  account_uid uuid NOT NULL REFERENCES accounts(account_uid) ON DELETE CASCADE,
+  task JSONB NOT NULL,
+  -- Either 'pending' (waiting to be processed, or retried) or 'deadLetter' (too many failed attempts)
+  status text NOT NULL DEFAULT 'pending',
+  attempts integer NOT NULL DEFAULT 0,
+  -- When the message is processed next, this is pushed back when claimed (lease) and when failed (backoff)
+  next_attempt_at TIMESTAMPTZ NOT NULL,
+  last_error text,
+  created TIMESTAMPTZ NOT NULL
+);
+
+-- Add an index on `next_attempt_at` for the pending messages
+-- This is used by the worker for finding the messages to process
+CREATE INDEX IF NOT EXISTS outbox_messages__next_attempt_at_idx ON outbox_messages (next_attempt_at)
+WHERE
+  status = 'pending';
+
+-- Add an index on `account_uid` and `status`
+-- This is used for inspecting the messages of an account
+CREATE INDEX IF NOT EXISTS outbox_messages__account_uid__status_idx ON outbox_messages (account_uid, status);
@@ -0,0 +1,60 @@
+-- Create the `webhook_deliveries` table
+-- Each webhook sent to an account (reminders and changes of events) is recorded here, and retried
+-- by the webhook worker until it succeeds or its retry horizon is reached
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+  delivery_uid uuid PRIMARY KEY DEFAULT uuid_generate_v4() NOT NULL,
+  account_uid uuid NOT NULL REFERENCES accounts(account_uid) ON DELETE CASCADE,
+  -- Type of the change of the event, NULL for the reminders
+  event_type text,
+  payload JSONB NOT NULL,
+  -- Either 'pending' (waiting to be attempted, or retried), 'succeeded' or 'failed'
+  status text NOT NULL DEFAULT 'pending',
+  attempts integer NOT NULL DEFAULT 0,
+  -- When the delivery is attempted next, this is pushed back when claimed (lease) and when failed (backoff)
+  next_attempt_at TIMESTAMPTZ NOT NULL,
+  retry_until TIMESTAMPTZ NOT NULL,
+  created TIMESTAMPTZ NOT NULL
+);
+
+-- Add an index on `next_attempt_at` for the pending deliveries
+-- This is used by the worker for finding the deliveries to attempt
+CREATE INDEX IF NOT EXISTS webhook_deliveries__next_attempt_at_idx ON webhook_deliveries (next_attempt_at)
+WHERE
+  status = 'pending';
+
+-- Add an index on `account_uid` and `created`
+-- This is used for listing the deliveries of an account (newest first)
+CREATE INDEX IF NOT EXISTS webhook_deliveries__account_uid__created_idx ON webhook_deliveries (account_uid, created DESC);
+
+-- Add an index on `created` for the finished deliveries
+-- This is used for removing the old ones
+CREATE INDEX IF NOT EXISTS webhook_deliveries__created_idx ON webhook_deliveries (created)
+WHERE
+  status <> 'pending';
+
+-- Create the `webhook_delivery_attempts` table
+-- This is the log of the attempts of the deliveries
+CREATE TABLE IF NOT EXISTS webhook_delivery_attempts (
+  attempt_uid uuid PRIMARY KEY DEFAULT uuid_generate_v4() NOT NULL,
+  delivery_uid uuid NOT NULL REFERENCES webhook_deliveries(delivery_uid) ON DELETE CASCADE,
+  url text NOT NULL,
+  attempted_at TIMESTAMPTZ NOT NULL,
+  -- NULL if there was no response (e.g. timeout)
+  status_code integer,
+  latency_millis bigint NOT NULL,
+  response_excerpt text
+);
+
+CREATE INDEX IF NOT EXISTS webhook_delivery_attempts__delivery_uid_idx ON webhook_delivery_attempts (delivery_uid);
+
+-- Create the `webhook_endpoint_statuses` table
+-- This is the health of the webhook endpoints of the accounts
+CREATE TABLE IF NOT EXISTS webhook_endpoint_statuses (
+  account_uid uuid NOT NULL REFERENCES accounts(account_uid) ON DELETE CASCADE,
+  url text NOT NULL,
+  consecutive_failures integer NOT NULL DEFAULT 0,
+  -- Set when the endpoint is marked as failing, NULL if it is healthy
+  failing_since TIMESTAMPTZ,
+  last_attempt_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY (account_uid, url)
+);
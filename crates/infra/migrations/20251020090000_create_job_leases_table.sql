@@ -0,0 +1,12 @@
+-- Create the `job_leases` table
+-- The periodic jobs (e.g. sending the reminders) only run on the instance holding their lease,
+-- so that they run once even with several instances
+CREATE TABLE IF NOT EXISTS job_leases (
+  job_name text PRIMARY KEY NOT NULL,
+  -- Identifier of the instance holding the lease
+  holder text NOT NULL,
+  -- When the holder acquired the lease, this isn't changed when the holder renews it
+  acquired_at TIMESTAMPTZ NOT NULL,
+  -- The lease can be acquired by another instance after this (e.g. the holder crashed)
+  expires_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,17 @@
+-- Create the `calendar_feeds` table
+-- Each row is a public iCalendar feed of a calendar, only accessible with its (unguessable) token
+-- Revoking a feed deletes its row
+CREATE TABLE IF NOT EXISTS calendar_feeds (
+  feed_uid uuid PRIMARY KEY DEFAULT uuid_generate_v4() NOT NULL,
+  calendar_uid uuid NOT NULL REFERENCES calendars(calendar_uid) ON DELETE CASCADE,
+  user_uid uuid NOT NULL REFERENCES users(user_uid) ON DELETE CASCADE,
+  account_uid uuid NOT NULL REFERENCES accounts(account_uid) ON DELETE CASCADE,
+  token text NOT NULL UNIQUE,
+  -- If true, the events only show the user is busy (no titles, descriptions and locations)
+  mask_events boolean NOT NULL DEFAULT false,
+  created TIMESTAMPTZ NOT NULL
+);
+
+-- Add an index on `calendar_uid`
+-- This is used for listing the feeds of a calendar
+CREATE INDEX IF NOT EXISTS calendar_feeds__calendar_uid_idx ON calendar_feeds (calendar_uid);
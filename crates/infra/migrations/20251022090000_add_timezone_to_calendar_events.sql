@@ -0,0 +1,4 @@
+-- Purpose: Add the timezone of the events, in which their recurrence is expanded
+-- When NULL, the recurrence is expanded in the timezone of the calendar
+ALTER TABLE "calendar_events"
+ADD COLUMN IF NOT EXISTS "timezone" TEXT;
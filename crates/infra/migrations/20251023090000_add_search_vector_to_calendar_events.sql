@@ -0,0 +1,87 @@
+-- Purpose: Full-text search on the title, description and location of the events
+-- The words are weighted by field (title > description > location), for ranking the results
+-- The language (text search configuration) is the one of the account (`settings.search_language`), "simple" by default
+CREATE OR REPLACE FUNCTION calendar_event_search_vector(
+    _language regconfig,
+    _title text,
+    _description text,
+    _location text
+) RETURNS tsvector AS $$
+SELECT
+    setweight(to_tsvector(_language, coalesce(_title, '')), 'A')
+    || setweight(to_tsvector(_language, coalesce(_description, '')), 'B')
+    || setweight(to_tsvector(_language, coalesce(_location, '')), 'C');
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION account_search_language(_account_uid uuid) RETURNS regconfig AS $$
+SELECT
+    coalesce(
+        (
+            SELECT
+                settings ->> 'search_language'
+            FROM
+                accounts
+            WHERE
+                account_uid = _account_uid
+        ),
+        'simple'
+    )::regconfig;
+$$ LANGUAGE sql STABLE;
+
+ALTER TABLE "calendar_events"
+ADD COLUMN IF NOT EXISTS "search_vector" tsvector;
+
+-- The vector is kept up to date by the database, whatever the query writing the event
+CREATE OR REPLACE FUNCTION set_calendar_event_search_vector() RETURNS trigger AS $$ BEGIN
+    NEW.search_vector := calendar_event_search_vector(
+        account_search_language(NEW.account_uid),
+        NEW.title,
+        NEW.description,
+        NEW.location
+    );
+
+RETURN NEW;
+
+END;
+
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS set_search_vector ON calendar_events;
+
+CREATE TRIGGER set_search_vector BEFORE INSERT
+OR
+UPDATE OF title,
+description,
+location ON calendar_events FOR EACH ROW EXECUTE PROCEDURE set_calendar_event_search_vector();
+
+-- Changing the language of an account updates the vectors of its events
+CREATE OR REPLACE FUNCTION update_account_events_search_vectors() RETURNS trigger AS $$ BEGIN
+    UPDATE calendar_events
+    SET
+        search_vector = calendar_event_search_vector(
+            account_search_language(NEW.account_uid),
+            title,
+            description,
+            location
+        )
+    WHERE
+        account_uid = NEW.account_uid;
+
+RETURN NEW;
+
+END;
+
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS update_events_search_vectors ON accounts;
+
+CREATE TRIGGER update_events_search_vectors
+AFTER
+UPDATE OF settings ON accounts FOR EACH ROW WHEN (
+    OLD.settings ->> 'search_language' IS DISTINCT
+    FROM
+        NEW.settings ->> 'search_language'
+) EXECUTE PROCEDURE update_account_events_search_vectors();
+
+-- The vectors of the existing events are set in batches by the next migration, without locking the table
+CREATE INDEX IF NOT EXISTS calendar_events__search_vector_idx ON calendar_events USING GIN (search_vector);
@@ -0,0 +1,78 @@
+-- Purpose: Instances of the recurring events, materialized for a window of time (when `event_instances.enabled` is set)
+-- They are used by the freebusy and booking slots queries, instead of expanding the recurrences of the events
+CREATE TABLE IF NOT EXISTS "calendar_event_instance_windows" (
+    "event_uid" uuid PRIMARY KEY REFERENCES calendar_events(event_uid) ON DELETE CASCADE,
+    -- All the instances starting in the window are materialized
+    "window_start" timestamptz NOT NULL,
+    "window_end" timestamptz NOT NULL,
+    "refreshed_at" timestamptz NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS calendar_event_instance_windows__refreshed_at ON calendar_event_instance_windows (refreshed_at);
+
+CREATE TABLE IF NOT EXISTS "calendar_event_instances" (
+    "event_uid" uuid NOT NULL REFERENCES calendar_event_instance_windows(event_uid) ON DELETE CASCADE,
+    "start_time" timestamptz NOT NULL,
+    "end_time" timestamptz NOT NULL,
+    PRIMARY KEY (event_uid, start_time)
+);
+
+-- A change of the occurrences of an event (or of one of its exceptions) removes its materialized instances
+-- The recurrence of the event is expanded by the queries until its instances are materialized again
+CREATE OR REPLACE FUNCTION remove_calendar_event_instances() RETURNS trigger AS $$ BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        DELETE FROM calendar_event_instance_windows
+        WHERE
+            event_uid IN (OLD.event_uid, OLD.recurring_event_uid);
+    END IF;
+
+    IF TG_OP <> 'DELETE' THEN
+        DELETE FROM calendar_event_instance_windows
+        WHERE
+            event_uid IN (NEW.event_uid, NEW.recurring_event_uid);
+    END IF;
+
+RETURN NULL;
+
+END;
+
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS remove_instances ON calendar_events;
+
+CREATE TRIGGER remove_instances
+AFTER INSERT
+OR DELETE
+OR
+UPDATE OF calendar_uid,
+start_time,
+duration,
+recurrence_jsonb,
+exdates,
+timezone,
+recurring_event_uid,
+original_start_time ON calendar_events FOR EACH ROW EXECUTE PROCEDURE remove_calendar_event_instances();
+
+-- The settings of the calendar (timezone, week start) are used for expanding the recurrences of its events
+CREATE OR REPLACE FUNCTION remove_calendar_events_instances() RETURNS trigger AS $$ BEGIN
+    DELETE FROM calendar_event_instance_windows AS w
+    USING calendar_events AS e
+    WHERE
+        w.event_uid = e.event_uid
+        AND e.calendar_uid = NEW.calendar_uid;
+
+RETURN NULL;
+
+END;
+
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS remove_events_instances ON calendars;
+
+CREATE TRIGGER remove_events_instances
+AFTER
+UPDATE OF settings ON calendars FOR EACH ROW WHEN (
+    OLD.settings::text IS DISTINCT
+    FROM
+        NEW.settings::text
+) EXECUTE PROCEDURE remove_calendar_events_instances();
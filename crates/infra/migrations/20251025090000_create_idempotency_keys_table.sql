@@ -0,0 +1,22 @@
+-- Create the `idempotency_keys` table
+-- The responses of the requests sent with an `Idempotency-Key` header are recorded here,
+-- so that the retries of a request get its response again instead of running it twice
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+  -- Scope of the key (hash of the credentials of the client)
+  scope text NOT NULL,
+  key text NOT NULL,
+  -- Hash of the method, path and body of the request
+  request_hash text NOT NULL,
+  -- The response is NULL while the request is in progress
+  response_status smallint,
+  response_content_type text,
+  response_body bytea,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The key can be used for another request after this
+  expires_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY (scope, key)
+);
+
+-- Add an index on `expires_at`
+-- This is used for removing the expired keys
+CREATE INDEX IF NOT EXISTS idempotency_keys__expires_at_idx ON idempotency_keys (expires_at);
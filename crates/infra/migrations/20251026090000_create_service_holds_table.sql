@@ -0,0 +1,22 @@
+-- Create the `service_holds` table
+-- A hold keeps a slot of a service for some of its hosts (e.g. during a checkout),
+-- until it is confirmed into a booking, released or expired
+CREATE TABLE IF NOT EXISTS service_holds (
+  hold_uid uuid PRIMARY KEY,
+  service_uid uuid NOT NULL REFERENCES services(service_uid) ON DELETE CASCADE,
+  -- Hosts for whom the slot is held
+  host_user_uids uuid[] NOT NULL,
+  start_time TIMESTAMPTZ NOT NULL,
+  end_time TIMESTAMPTZ NOT NULL,
+  -- The slot is bookable again after this
+  expires_at TIMESTAMPTZ NOT NULL,
+  created TIMESTAMPTZ NOT NULL
+);
+
+-- Add a GIN index on `host_user_uids`
+-- This is used for finding the holds of the hosts when querying their bookingslots
+CREATE INDEX IF NOT EXISTS service_holds__host_user_uids_idx ON service_holds USING GIN (host_user_uids);
+
+-- Add an index on `expires_at`
+-- This is used for removing the expired holds
+CREATE INDEX IF NOT EXISTS service_holds__expires_at_idx ON service_holds (expires_at);
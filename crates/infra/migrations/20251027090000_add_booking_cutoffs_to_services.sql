@@ -0,0 +1,5 @@
+-- Purpose: Add the cut-off windows of the bookings of the services
+-- They are the minimum number of minutes before the start of a booking to cancel or reschedule it
+ALTER TABLE "services"
+ADD COLUMN IF NOT EXISTS "cancellation_cutoff" BIGINT NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS "reschedule_cutoff" BIGINT NOT NULL DEFAULT 0;
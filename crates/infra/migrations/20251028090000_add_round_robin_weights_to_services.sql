@@ -0,0 +1,8 @@
+-- Purpose: Add the weights and the priority tiers of the users of the round robin services,
+-- and the number of days of upcoming bookings counted by the equal distribution algorithm
+ALTER TABLE "service_users"
+ADD COLUMN IF NOT EXISTS "weight" BIGINT NOT NULL DEFAULT 1,
+ADD COLUMN IF NOT EXISTS "priority" BIGINT NOT NULL DEFAULT 1;
+
+ALTER TABLE "services"
+ADD COLUMN IF NOT EXISTS "distribution_window" BIGINT NOT NULL DEFAULT 14;
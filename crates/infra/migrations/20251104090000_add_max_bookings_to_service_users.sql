@@ -0,0 +1,4 @@
+-- Purpose: Add the maximum numbers of bookings per day and per week of the users of the services
+ALTER TABLE "service_users"
+ADD COLUMN IF NOT EXISTS "max_bookings_per_day" BIGINT,
+ADD COLUMN IF NOT EXISTS "max_bookings_per_week" BIGINT;
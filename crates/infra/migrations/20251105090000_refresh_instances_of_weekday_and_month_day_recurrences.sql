@@ -0,0 +1,11 @@
+-- Purpose: Remove the materialized instances of the recurring events with weekdays without position or negative month days
+-- They are now expanded as RFC 5545 (every matching weekday of the period, days counted from the end of the month)
+-- The recurrences of these events are expanded by the queries until their instances are materialized again by the job
+DELETE FROM calendar_event_instance_windows AS w
+USING calendar_events AS e
+WHERE
+    w.event_uid = e.event_uid
+    AND (
+        jsonb_path_exists(e.recurrence_jsonb, '$.byweekday[*] ? (@ like_regex "^[A-Za-z]")')
+        OR jsonb_path_exists(e.recurrence_jsonb, '$.bymonthday[*] ? (@ < 0)')
+    );
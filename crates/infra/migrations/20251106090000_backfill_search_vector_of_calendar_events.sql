@@ -0,0 +1,54 @@
+-- no-transaction
+-- Purpose: Set the search vectors of the events created before the full-text search
+-- The events are updated in batches (in the order of their uid), each one being committed on its own,
+-- so that the table is never locked for long. The events without a vector are not found by the search until then
+DO $$
+DECLARE
+    _last_event_uid uuid;
+    _batch_last_event_uid uuid;
+BEGIN
+    LOOP
+        WITH batch AS (
+            SELECT
+                event_uid
+            FROM
+                calendar_events
+            WHERE
+                _last_event_uid IS NULL
+                OR event_uid > _last_event_uid
+            ORDER BY
+                event_uid
+            LIMIT
+                10000
+        ), updated AS (
+            UPDATE calendar_events AS e
+            SET
+                search_vector = calendar_event_search_vector(
+                    account_search_language(e.account_uid),
+                    e.title,
+                    e.description,
+                    e.location
+                )
+            FROM
+                batch
+            WHERE
+                e.event_uid = batch.event_uid
+                AND e.search_vector IS NULL
+        )
+        SELECT
+            event_uid INTO _batch_last_event_uid
+        FROM
+            batch
+        ORDER BY
+            event_uid DESC
+        LIMIT
+            1;
+
+        EXIT WHEN _batch_last_event_uid IS NULL;
+
+        _last_event_uid := _batch_last_event_uid;
+
+        COMMIT;
+    END LOOP;
+END;
+$$;
@@ -7,6 +7,7 @@ mod system;
 use std::sync::Arc;
 
 pub use config::Config;
+use nittei_utils::config::{APP_CONFIG, ReposBackend};
 use repos::Repos;
 pub use repos::{
     BusyCalendarIdentifier,
@@ -15,6 +16,12 @@ pub use repos::{
     SearchEventsForAccountParams,
     SearchEventsForUserParams,
     SearchEventsParams,
+    SearchEventsResult,
+    ServiceBooking,
+    ServiceBookingCancellation,
+    ServiceBookingHost,
+    ServiceBookingOutcome,
+    TextSearchQuery,
 };
 pub use services::*;
 pub use system::ISys;
@@ -47,23 +54,69 @@ impl NitteiContext {
             sys: Arc::new(RealSys {}),
         })
     }
+
+    /// Context with in-memory repositories (nothing is persisted)
+    pub fn create_inmemory() -> Self {
+        Self {
+            repos: Repos::create_inmemory(),
+            config: Config::new(),
+            sys: Arc::new(RealSys {}),
+        }
+    }
 }
 
 /// Will setup the infrastructure context given the environment
+/// The backend of the repositories is the one configured (`repos_backend`)
 pub async fn setup_context() -> anyhow::Result<NitteiContext> {
+    match APP_CONFIG.repos_backend {
+        ReposBackend::Postgres => {
+            NitteiContext::create(ContextParams {
+                postgres_connection_string: APP_CONFIG.pg.database_url.clone(),
+            })
+            .await
+        }
+        ReposBackend::InMemory => Ok(NitteiContext::create_inmemory()),
+    }
+}
+
+/// Context with the Postgres repositories, whatever the configured backend
+#[cfg(test)]
+async fn setup_postgres_context() -> anyhow::Result<NitteiContext> {
     NitteiContext::create(ContextParams {
-        postgres_connection_string: nittei_utils::config::APP_CONFIG.pg.database_url.clone(),
+        postgres_connection_string: APP_CONFIG.pg.database_url.clone(),
     })
     .await
 }
 
+/// Generate a `<test>_postgres` and a `<test>_inmemory` test for each `async fn <test>(ctx: NitteiContext)`,
+/// for running the same tests against each backend of the repositories
+#[cfg(test)]
+macro_rules! backend_tests {
+    ($($test:ident),+ $(,)?) => {
+        $(
+            paste::paste! {
+                #[tokio::test]
+                async fn [<$test _postgres>]() {
+                    $test($crate::setup_postgres_context().await.unwrap()).await;
+                }
+
+                #[tokio::test]
+                async fn [<$test _inmemory>]() {
+                    $test($crate::NitteiContext::create_inmemory()).await;
+                }
+            }
+        )+
+    };
+}
+#[cfg(test)]
+pub(crate) use backend_tests;
+
 /// Run the migrations
 ///
 /// This is not run by the application itself, but is provided as a utility
 /// Usage is in bins/nittei/src/bin/migrate.rs
 pub async fn run_migration() -> anyhow::Result<()> {
-    let pool =
-        create_postgres_pool(nittei_utils::config::APP_CONFIG.pg.database_url.as_str()).await?;
+    let pool = create_postgres_pool(APP_CONFIG.pg.database_url.as_str()).await?;
 
     sqlx::migrate!().run(&pool).await.map_err(|e| e.into())
 }
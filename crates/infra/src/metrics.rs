@@ -2,7 +2,7 @@
 // Allow unwrap used because we are using lazy_static, and the only way to handle errors is to unwrap
 
 use lazy_static::lazy_static;
-use prometheus::{IntGauge, Registry};
+use prometheus::{IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry};
 
 lazy_static! {
     pub static ref INFRA_REGISTRY: Registry = Registry::new();
@@ -21,6 +21,30 @@ lazy_static! {
         "Number of busy connections in the pool"
     )
     .unwrap();
+    pub static ref JOB_LEASE_HELD: IntGaugeVec = IntGaugeVec::new(
+        Opts::new(
+            "job_lease_held",
+            "Whether the instance holds the lease of the job (1) or not (0)"
+        ),
+        &["job", "instance"]
+    )
+    .unwrap();
+    pub static ref JOB_LEASE_ACQUISITIONS_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new(
+            "job_lease_acquisitions_total",
+            "Number of times the instance acquired the lease of the job (after another instance, or after losing it)"
+        ),
+        &["job", "instance"]
+    )
+    .unwrap();
+    pub static ref RATE_LIMITED_REQUESTS_TOTAL: IntCounterVec = IntCounterVec::new(
+        Opts::new(
+            "rate_limited_requests_total",
+            "Number of requests rejected because the account (or one of its users) exceeded its rate limit"
+        ),
+        &["account_id", "scope"]
+    )
+    .unwrap();
 }
 
 pub fn register_metrics() -> anyhow::Result<()> {
@@ -28,6 +52,9 @@ pub fn register_metrics() -> anyhow::Result<()> {
     let _ = INFRA_REGISTRY.register(Box::new(DB_CONNECTION_POOL_TOTAL.clone()));
     let _ = INFRA_REGISTRY.register(Box::new(DB_CONNECTION_POOL_IDLE.clone()));
     let _ = INFRA_REGISTRY.register(Box::new(DB_CONNECTION_POOL_BUSY.clone()));
+    let _ = INFRA_REGISTRY.register(Box::new(JOB_LEASE_HELD.clone()));
+    let _ = INFRA_REGISTRY.register(Box::new(JOB_LEASE_ACQUISITIONS_TOTAL.clone()));
+    let _ = INFRA_REGISTRY.register(Box::new(RATE_LIMITED_REQUESTS_TOTAL.clone()));
 
     Ok(())
 }
@@ -37,3 +64,20 @@ pub fn update_connection_pool_metrics(total: i64, idle: i64, busy: i64) {
     DB_CONNECTION_POOL_IDLE.set(idle);
     DB_CONNECTION_POOL_BUSY.set(busy);
 }
+
+pub fn update_job_lease_metrics(job: &str, instance: &str, held: bool, acquired: bool) {
+    JOB_LEASE_HELD
+        .with_label_values(&[job, instance])
+        .set(i64::from(held));
+    if acquired {
+        JOB_LEASE_ACQUISITIONS_TOTAL
+            .with_label_values(&[job, instance])
+            .inc();
+    }
+}
+
+pub fn increment_rate_limited_requests(account_id: &str, scope: &str) {
+    RATE_LIMITED_REQUESTS_TOTAL
+        .with_label_values(&[account_id, scope])
+        .inc();
+}
@@ -0,0 +1,71 @@
+use nittei_domain::{Account, ID};
+
+use super::IAccountRepo;
+use crate::repos::shared::inmemory::InMemoryStore;
+
+#[derive(Debug)]
+pub struct InMemoryAccountRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryAccountRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IAccountRepo for InMemoryAccountRepo {
+    async fn insert(&self, account: &Account) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if tables.accounts.contains_key(account.id.as_ref())
+            || tables
+                .accounts
+                .values()
+                .any(|a| a.secret_api_key == account.secret_api_key)
+        {
+            anyhow::bail!("The account with id: {} already exists", account.id);
+        }
+        tables
+            .accounts
+            .insert(*account.id.as_ref(), account.clone());
+        Ok(())
+    }
+
+    async fn save(&self, account: &Account) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(existing) = tables.accounts.get_mut(account.id.as_ref()) {
+            *existing = account.clone();
+        }
+        Ok(())
+    }
+
+    async fn find(&self, account_id: &ID) -> anyhow::Result<Option<Account>> {
+        let tables = self.store.lock().await;
+        Ok(tables.accounts.get(account_id.as_ref()).cloned())
+    }
+
+    async fn find_many(&self, account_ids: &[ID]) -> anyhow::Result<Vec<Account>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .accounts
+            .values()
+            .filter(|a| account_ids.contains(&a.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, account_id: &ID) -> anyhow::Result<Option<Account>> {
+        let mut tables = self.store.lock().await;
+        Ok(tables.delete_account(account_id))
+    }
+
+    async fn find_by_apikey(&self, api_key: &str) -> anyhow::Result<Option<Account>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .accounts
+            .values()
+            .find(|a| a.secret_api_key == api_key)
+            .cloned())
+    }
+}
@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryAccountRepo;
 use nittei_domain::{Account, ID};
 pub use postgres::PostgresAccountRepo;
 
@@ -17,11 +19,11 @@ pub trait IAccountRepo: Send + Sync {
 mod tests {
     use nittei_domain::{Account, Entity, PEMKey};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn create_and_delete() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(create_and_delete, update);
+
+    async fn create_and_delete(ctx: NitteiContext) {
         let account = Account::default();
 
         // Insert
@@ -64,9 +66,7 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn update() {
-        let ctx = setup_context().await.unwrap();
+    async fn update(ctx: NitteiContext) {
         let mut account = Account::default();
 
         // Insert
@@ -0,0 +1,58 @@
+use nittei_domain::{AccountIntegration, ID, IntegrationProvider};
+
+use super::IAccountIntegrationRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryAccountIntegrationRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryAccountIntegrationRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IAccountIntegrationRepo for InMemoryAccountIntegrationRepo {
+    async fn insert(&self, integration: &AccountIntegration) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.accounts, &integration.account_id, "account")?;
+        if tables
+            .account_integrations
+            .iter()
+            .any(|i| i.account_id == integration.account_id && i.provider == integration.provider)
+        {
+            anyhow::bail!(
+                "The account with id: {} already has an integration for this provider",
+                integration.account_id
+            );
+        }
+        tables.account_integrations.push(integration.clone());
+        Ok(())
+    }
+
+    async fn find(&self, account_id: &ID) -> anyhow::Result<Vec<AccountIntegration>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .account_integrations
+            .iter()
+            .filter(|i| &i.account_id == account_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, account_id: &ID, provider: IntegrationProvider) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if !tables
+            .account_integrations
+            .iter()
+            .any(|i| &i.account_id == account_id && i.provider == provider)
+        {
+            return Err(anyhow::Error::msg("Unable to delete account integration"));
+        }
+        tables.delete_account_integration(account_id, &provider);
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryAccountIntegrationRepo;
 use nittei_domain::{AccountIntegration, ID, IntegrationProvider};
 pub use postgres::PostgresAccountIntegrationRepo;
 
@@ -14,12 +16,11 @@ pub trait IAccountIntegrationRepo: Send + Sync {
 mod tests {
     use nittei_domain::{Account, AccountIntegration, IntegrationProvider};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn test_account_integrations() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(test_account_integrations);
 
+    async fn test_account_integrations(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
@@ -0,0 +1,167 @@
+use nittei_domain::{Calendar, ID};
+
+use super::ICalendarRepo;
+use crate::repos::shared::{
+    inmemory::{self, InMemoryStore, InMemoryTables},
+    query_structs::MetadataFindQuery,
+};
+
+#[derive(Debug)]
+pub struct InMemoryCalendarRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryCalendarRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+/// Save the calendar and increment its version
+/// When `expected_version` is provided, the calendar is only saved if its current version matches
+/// Returns false if the calendar hasn't been updated
+fn save_calendar(
+    tables: &mut InMemoryTables,
+    calendar: &Calendar,
+    expected_version: Option<i64>,
+) -> anyhow::Result<bool> {
+    if tables.calendars.values().any(|c| {
+        c.id != calendar.id
+            && c.user_id == calendar.user_id
+            && calendar.key.is_some()
+            && c.key == calendar.key
+    }) {
+        anyhow::bail!("The user already has a calendar with this key");
+    }
+    let Some(existing) = tables.calendars.get_mut(calendar.id.as_ref()) else {
+        return Ok(false);
+    };
+    if expected_version.is_some_and(|version| version != existing.version) {
+        return Ok(false);
+    }
+    let settings_changed =
+        serde_json::to_value(&existing.settings)? != serde_json::to_value(&calendar.settings)?;
+
+    existing.name = calendar.name.clone();
+    existing.key = calendar.key.clone();
+    existing.settings = calendar.settings.clone();
+    existing.metadata = calendar.metadata.clone();
+    existing.version += 1;
+
+    // The settings are used for expanding the recurring events, so their instances are outdated
+    if settings_changed {
+        let event_ids = tables
+            .events
+            .values()
+            .filter(|e| e.calendar_id == calendar.id)
+            .map(|e| *e.id.as_ref())
+            .collect::<Vec<_>>();
+        for event_id in event_ids {
+            tables.event_instances.remove(&event_id);
+        }
+    }
+    Ok(true)
+}
+
+#[async_trait::async_trait]
+impl ICalendarRepo for InMemoryCalendarRepo {
+    async fn insert(&self, calendar: &Calendar) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.users, &calendar.user_id, "user")?;
+        if tables.calendars.contains_key(calendar.id.as_ref()) {
+            anyhow::bail!("The calendar with id: {} already exists", calendar.id);
+        }
+        if calendar.key.is_some()
+            && tables
+                .calendars
+                .values()
+                .any(|c| c.user_id == calendar.user_id && c.key == calendar.key)
+        {
+            anyhow::bail!("The user already has a calendar with this key");
+        }
+        tables.calendars.insert(
+            *calendar.id.as_ref(),
+            Calendar {
+                version: 0,
+                ..calendar.clone()
+            },
+        );
+        Ok(())
+    }
+
+    async fn save(&self, calendar: &Calendar) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        save_calendar(&mut tables, calendar, None)?;
+        Ok(())
+    }
+
+    async fn save_if_version(&self, calendar: &Calendar, version: i64) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        save_calendar(&mut tables, calendar, Some(version))
+    }
+
+    async fn find(&self, calendar_id: &ID) -> anyhow::Result<Option<Calendar>> {
+        let tables = self.store.lock().await;
+        Ok(tables.calendars.get(calendar_id.as_ref()).cloned())
+    }
+
+    async fn find_multiple(&self, calendar_ids: Vec<&ID>) -> anyhow::Result<Vec<Calendar>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .calendars
+            .values()
+            .filter(|c| calendar_ids.contains(&&c.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_user(&self, user_id: &ID) -> anyhow::Result<Vec<Calendar>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .calendars
+            .values()
+            .filter(|c| &c.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_user_and_key(
+        &self,
+        user_id: &ID,
+        key: &str,
+    ) -> anyhow::Result<Option<Calendar>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .calendars
+            .values()
+            .find(|c| &c.user_id == user_id && c.key.as_deref() == Some(key))
+            .cloned())
+    }
+
+    async fn find_for_users(&self, user_ids: &[ID]) -> anyhow::Result<Vec<Calendar>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .calendars
+            .values()
+            .filter(|c| user_ids.contains(&c.user_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, calendar_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.ensure_calendar_deletable(calendar_id)?;
+        tables.delete_calendar(calendar_id);
+        Ok(())
+    }
+
+    async fn find_by_metadata(&self, query: MetadataFindQuery) -> anyhow::Result<Vec<Calendar>> {
+        let tables = self.store.lock().await;
+        inmemory::find_by_metadata(
+            tables.calendars.iter(),
+            &query,
+            |c| &c.account_id,
+            |c| c.metadata.as_ref(),
+        )
+    }
+}
@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryCalendarRepo;
 use nittei_domain::{Calendar, ID};
 pub use postgres::PostgresCalendarRepo;
 
@@ -8,7 +10,11 @@ use super::shared::query_structs::MetadataFindQuery;
 #[async_trait::async_trait]
 pub trait ICalendarRepo: Send + Sync {
     async fn insert(&self, calendar: &Calendar) -> anyhow::Result<()>;
+    /// Save the calendar, and increment its version
     async fn save(&self, calendar: &Calendar) -> anyhow::Result<()>;
+    /// Save the calendar only if its current version is `version` (optimistic concurrency control)
+    /// Returns false if the calendar has been modified in the meantime, or doesn't exist
+    async fn save_if_version(&self, calendar: &Calendar, version: i64) -> anyhow::Result<bool>;
     async fn find(&self, calendar_id: &ID) -> anyhow::Result<Option<Calendar>>;
     async fn find_multiple(&self, calendar_ids: Vec<&ID>) -> anyhow::Result<Vec<Calendar>>;
     async fn find_by_user(&self, user_id: &ID) -> anyhow::Result<Vec<Calendar>>;
@@ -26,11 +32,11 @@ pub trait ICalendarRepo: Send + Sync {
 mod tests {
     use nittei_domain::{Account, Calendar, Entity, User};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn create_and_delete() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(create_and_delete, update, update_if_version, delete_by_user);
+
+    async fn create_and_delete(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
         let user = User::new(account.id.clone(), None);
@@ -67,9 +73,7 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn update() {
-        let ctx = setup_context().await.unwrap();
+    async fn update(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
         let user = User::new(account.id.clone(), None);
@@ -96,9 +100,52 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn delete_by_user() {
-        let ctx = setup_context().await.unwrap();
+    async fn update_if_version(ctx: NitteiContext) {
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let mut calendar = Calendar::new(&user.id, &account.id, None, None);
+        assert!(ctx.repos.calendars.insert(&calendar).await.is_ok());
+
+        // Each save increments the version
+        calendar.name = Some("First".into());
+        let saved = ctx
+            .repos
+            .calendars
+            .save_if_version(&calendar, calendar.version)
+            .await
+            .unwrap();
+        assert!(saved);
+        let found = ctx
+            .repos
+            .calendars
+            .find(&calendar.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.version, calendar.version + 1);
+
+        // Outdated version
+        calendar.name = Some("Second".into());
+        let saved = ctx
+            .repos
+            .calendars
+            .save_if_version(&calendar, calendar.version)
+            .await
+            .unwrap();
+        assert!(!saved);
+        let found = ctx
+            .repos
+            .calendars
+            .find(&calendar.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.name, Some("First".into()));
+    }
+
+    async fn delete_by_user(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
         let user = User::new(account.id.clone(), None);
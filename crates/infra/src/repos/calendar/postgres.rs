@@ -32,6 +32,7 @@ struct CalendarRaw {
     key: Option<String>,
     settings: Value,
     metadata: Value,
+    version: i64,
 }
 
 impl TryFrom<CalendarRaw> for Calendar {
@@ -46,10 +47,49 @@ impl TryFrom<CalendarRaw> for Calendar {
             key: c.key,
             settings: serde_json::from_value(c.settings)?,
             metadata: serde_json::from_value(c.metadata)?,
+            version: c.version,
         })
     }
 }
 
+/// Save the calendar and increment its version
+/// When `expected_version` is provided, the calendar is only saved if its current version matches
+/// Returns false if no row has been updated
+async fn save_calendar(
+    pool: &PgPool,
+    calendar: &Calendar,
+    expected_version: Option<i64>,
+) -> anyhow::Result<bool> {
+    let res = sqlx::query!(
+        r#"
+        UPDATE calendars
+        SET name = $2,
+            key = $3,
+            settings = $4,
+            metadata = $5,
+            version = version + 1
+        WHERE calendar_uid = $1 AND ($6::bigint IS NULL OR version = $6)
+        "#,
+        calendar.id.as_ref(),
+        calendar.name.as_ref(),
+        calendar.key.as_ref(),
+        Json(&calendar.settings) as _,
+        Json(&calendar.metadata) as _,
+        expected_version,
+    )
+    .execute(pool)
+    .await
+    .inspect_err(|e| {
+        error!(
+            calendar_id = %calendar.id,
+            key = ?calendar.key,
+            error = ?e,
+            "Failed to save calendar"
+        );
+    })?;
+    Ok(res.rows_affected() > 0)
+}
+
 #[async_trait::async_trait]
 impl ICalendarRepo for PostgresCalendarRepo {
     #[instrument(name = "calendar::insert")]
@@ -84,32 +124,12 @@ impl ICalendarRepo for PostgresCalendarRepo {
 
     #[instrument(name = "calendar::save")]
     async fn save(&self, calendar: &Calendar) -> anyhow::Result<()> {
-        sqlx::query!(
-            r#"
-            UPDATE calendars
-            SET name = $2,
-                key = $3,
-                settings = $4,
-                metadata = $5
-            WHERE calendar_uid = $1
-            "#,
-            calendar.id.as_ref(),
-            calendar.name.as_ref(),
-            calendar.key.as_ref(),
-            Json(&calendar.settings) as _,
-            Json(&calendar.metadata) as _,
-        )
-        .execute(&self.pool)
-        .await
-        .inspect_err(|e| {
-            error!(
-                calendar_id = %calendar.id,
-                key = ?calendar.key,
-                error = ?e,
-                "Failed to save calendar"
-            );
-        })?;
-        Ok(())
+        save_calendar(&self.pool, calendar, None).await.map(|_| ())
+    }
+
+    #[instrument(name = "calendar::save_if_version")]
+    async fn save_if_version(&self, calendar: &Calendar, version: i64) -> anyhow::Result<bool> {
+        save_calendar(&self.pool, calendar, Some(version)).await
     }
 
     #[instrument(name = "calendar::find")]
@@ -270,7 +290,8 @@ impl ICalendarRepo for PostgresCalendarRepo {
             CalendarRaw,
             r#"
             SELECT c.* FROM calendars AS c
-            WHERE c.account_uid = $1 AND c.metadata @> $2
+            WHERE c.account_uid = $1 AND c.metadata @> $2 AND ($5::uuid IS NULL OR c.calendar_uid > $5)
+            ORDER BY c.calendar_uid
             LIMIT $3
             OFFSET $4
             "#,
@@ -278,6 +299,7 @@ impl ICalendarRepo for PostgresCalendarRepo {
             Json(&query.metadata) as _,
             query.limit as i64,
             query.skip as i64,
+            query.after.as_ref().map(|id| *id.as_ref()),
         )
         .fetch_all(&self.pool)
         .await
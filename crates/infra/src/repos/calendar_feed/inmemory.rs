@@ -0,0 +1,67 @@
+use nittei_domain::{CalendarFeed, ID};
+
+use super::ICalendarFeedRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryCalendarFeedRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryCalendarFeedRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICalendarFeedRepo for InMemoryCalendarFeedRepo {
+    async fn insert(&self, feed: &CalendarFeed) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.calendars, &feed.calendar_id, "calendar")?;
+        if tables.calendar_feeds.contains_key(feed.id.as_ref())
+            || tables
+                .calendar_feeds
+                .values()
+                .any(|f| f.token == feed.token)
+        {
+            anyhow::bail!("The calendar feed with id: {} already exists", feed.id);
+        }
+        tables
+            .calendar_feeds
+            .insert(*feed.id.as_ref(), feed.clone());
+        Ok(())
+    }
+
+    async fn find(&self, feed_id: &ID) -> anyhow::Result<Option<CalendarFeed>> {
+        let tables = self.store.lock().await;
+        Ok(tables.calendar_feeds.get(feed_id.as_ref()).cloned())
+    }
+
+    async fn find_by_token(&self, token: &str) -> anyhow::Result<Option<CalendarFeed>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .calendar_feeds
+            .values()
+            .find(|f| f.token == token)
+            .cloned())
+    }
+
+    async fn find_by_calendar(&self, calendar_id: &ID) -> anyhow::Result<Vec<CalendarFeed>> {
+        let tables = self.store.lock().await;
+        let mut feeds = tables
+            .calendar_feeds
+            .values()
+            .filter(|f| &f.calendar_id == calendar_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        feeds.sort_by_key(|f| f.created);
+        Ok(feeds)
+    }
+
+    async fn delete(&self, feed_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.calendar_feeds.remove(feed_id.as_ref());
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+mod inmemory;
+mod postgres;
+
+pub use inmemory::InMemoryCalendarFeedRepo;
+use nittei_domain::{CalendarFeed, ID};
+pub use postgres::PostgresCalendarFeedRepo;
+
+#[async_trait::async_trait]
+pub trait ICalendarFeedRepo: Send + Sync {
+    async fn insert(&self, feed: &CalendarFeed) -> anyhow::Result<()>;
+    async fn find(&self, feed_id: &ID) -> anyhow::Result<Option<CalendarFeed>>;
+    async fn find_by_token(&self, token: &str) -> anyhow::Result<Option<CalendarFeed>>;
+    async fn find_by_calendar(&self, calendar_id: &ID) -> anyhow::Result<Vec<CalendarFeed>>;
+    async fn delete(&self, feed_id: &ID) -> anyhow::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use nittei_domain::{Account, Calendar, CalendarFeed, User};
+
+    use crate::{NitteiContext, backend_tests};
+
+    backend_tests!(create_find_and_revoke);
+
+    async fn create_find_and_revoke(ctx: NitteiContext) {
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let calendar = Calendar::new(&user.id, &account.id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        let feed = CalendarFeed::new(&calendar, true, Utc::now());
+        ctx.repos.calendar_feeds.insert(&feed).await.unwrap();
+        let other_feed = CalendarFeed::new(&calendar, false, Utc::now());
+        ctx.repos.calendar_feeds.insert(&other_feed).await.unwrap();
+
+        let found = ctx
+            .repos
+            .calendar_feeds
+            .find_by_token(&feed.token)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.id, feed.id);
+        assert_eq!(found.calendar_id, calendar.id);
+        assert!(found.mask_events);
+        assert!(
+            ctx.repos
+                .calendar_feeds
+                .find_by_token("cf_unknown")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            ctx.repos
+                .calendar_feeds
+                .find_by_calendar(&calendar.id)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        ctx.repos.calendar_feeds.delete(&feed.id).await.unwrap();
+        assert!(
+            ctx.repos
+                .calendar_feeds
+                .find(&feed.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            ctx.repos
+                .calendar_feeds
+                .find_by_token(&feed.token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // The feeds are removed with their calendar
+        ctx.repos.calendars.delete(&calendar.id).await.unwrap();
+        assert!(
+            ctx.repos
+                .calendar_feeds
+                .find(&other_feed.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
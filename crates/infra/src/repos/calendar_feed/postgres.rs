@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{CalendarFeed, ID};
+use sqlx::{FromRow, PgPool, types::Uuid};
+use tracing::{error, instrument};
+
+use super::ICalendarFeedRepo;
+
+#[derive(Debug)]
+pub struct PostgresCalendarFeedRepo {
+    pool: PgPool,
+}
+
+impl PostgresCalendarFeedRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct CalendarFeedRaw {
+    feed_uid: Uuid,
+    calendar_uid: Uuid,
+    user_uid: Uuid,
+    account_uid: Uuid,
+    token: String,
+    mask_events: bool,
+    created: DateTime<Utc>,
+}
+
+impl From<CalendarFeedRaw> for CalendarFeed {
+    fn from(e: CalendarFeedRaw) -> Self {
+        Self {
+            id: e.feed_uid.into(),
+            calendar_id: e.calendar_uid.into(),
+            user_id: e.user_uid.into(),
+            account_id: e.account_uid.into(),
+            token: e.token,
+            mask_events: e.mask_events,
+            created: e.created,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICalendarFeedRepo for PostgresCalendarFeedRepo {
+    #[instrument(skip_all, fields(feed_id = %feed.id))]
+    async fn insert(&self, feed: &CalendarFeed) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO calendar_feeds (feed_uid, calendar_uid, user_uid, account_uid, token, mask_events, created)
+            VALUES($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            feed.id.as_ref(),
+            feed.calendar_id.as_ref(),
+            feed.user_id.as_ref(),
+            feed.account_id.as_ref(),
+            feed.token,
+            feed.mask_events,
+            feed.created,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Unable to insert calendar feed: {}. DB returned error: {:?}",
+                feed.id, e
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument]
+    async fn find(&self, feed_id: &ID) -> anyhow::Result<Option<CalendarFeed>> {
+        let feed = sqlx::query_as!(
+            CalendarFeedRaw,
+            r#"
+            SELECT * FROM calendar_feeds
+            WHERE feed_uid = $1
+            "#,
+            feed_id.as_ref(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Unable to find calendar feed: {}. DB returned error: {:?}",
+                feed_id, e
+            );
+        })?;
+
+        Ok(feed.map(|feed| feed.into()))
+    }
+
+    // The token is a secret, so it isn't logged
+    #[instrument(skip_all)]
+    async fn find_by_token(&self, token: &str) -> anyhow::Result<Option<CalendarFeed>> {
+        let feed = sqlx::query_as!(
+            CalendarFeedRaw,
+            r#"
+            SELECT * FROM calendar_feeds
+            WHERE token = $1
+            "#,
+            token,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Unable to find calendar feed by token. DB returned error: {:?}",
+                e
+            );
+        })?;
+
+        Ok(feed.map(|feed| feed.into()))
+    }
+
+    #[instrument]
+    async fn find_by_calendar(&self, calendar_id: &ID) -> anyhow::Result<Vec<CalendarFeed>> {
+        let feeds = sqlx::query_as!(
+            CalendarFeedRaw,
+            r#"
+            SELECT * FROM calendar_feeds
+            WHERE calendar_uid = $1
+            ORDER BY created
+            "#,
+            calendar_id.as_ref(),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Unable to find calendar feeds of calendar: {}. DB returned error: {:?}",
+                calendar_id, e
+            );
+        })?;
+
+        Ok(feeds.into_iter().map(|feed| feed.into()).collect())
+    }
+
+    #[instrument]
+    async fn delete(&self, feed_id: &ID) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM calendar_feeds
+            WHERE feed_uid = $1
+            "#,
+            feed_id.as_ref(),
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Unable to delete calendar feed: {}. DB returned error: {:?}",
+                feed_id, e
+            );
+        })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use nittei_domain::{ID, SyncedCalendar};
+
+use super::ICalendarSyncedRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryCalendarSyncedRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryCalendarSyncedRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl ICalendarSyncedRepo for InMemoryCalendarSyncedRepo {
+    async fn insert(&self, c: &SyncedCalendar) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.calendars, &c.calendar_id, "calendar")?;
+        if !tables
+            .user_integrations
+            .iter()
+            .any(|i| i.user_id == c.user_id && i.provider == c.provider)
+        {
+            anyhow::bail!(
+                "The user with id: {} has no integration for this provider",
+                c.user_id
+            );
+        }
+        if tables.synced_calendars.iter().any(|s| {
+            s.calendar_id == c.calendar_id
+                && s.provider == c.provider
+                && s.ext_calendar_id == c.ext_calendar_id
+        }) {
+            anyhow::bail!("The calendar with id: {} is already synced", c.calendar_id);
+        }
+        tables.synced_calendars.push(c.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, c: &SyncedCalendar) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if !tables.delete_synced_calendar(c) {
+            return Err(anyhow::Error::msg("Synced calendar not found"));
+        }
+        Ok(())
+    }
+
+    async fn find_by_calendar(&self, calendar_id: &ID) -> anyhow::Result<Vec<SyncedCalendar>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .synced_calendars
+            .iter()
+            .filter(|c| &c.calendar_id == calendar_id)
+            .cloned()
+            .collect())
+    }
+}
@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryCalendarSyncedRepo;
 use nittei_domain::{ID, SyncedCalendar};
 pub use postgres::PostgresCalendarSyncedRepo;
 
@@ -22,12 +24,11 @@ mod tests {
         UserIntegration,
     };
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn test_calendar_synced_repo() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(test_calendar_synced_repo);
 
+    async fn test_calendar_synced_repo(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
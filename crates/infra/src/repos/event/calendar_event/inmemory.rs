@@ -0,0 +1,1113 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use nittei_domain::{
+    CalendarEvent,
+    CalendarEventAttendee,
+    CalendarEventAttendeeResponseStatus,
+    CalendarEventCursor,
+    CalendarEventCursorKey,
+    CalendarEventHighlight,
+    CalendarEventSort,
+    CalendarEventStatus,
+    ID,
+    OutboxMessage,
+    RecurrenceQuery,
+    TimeSpan,
+};
+
+use super::{
+    IEventRepo,
+    MostRecentCreatedServiceEvents,
+    SearchEventsForAccountParams,
+    SearchEventsForUserParams,
+    SearchEventsParams,
+    SearchEventsResult,
+    ServiceBooking,
+    ServiceBookingCancellation,
+    ServiceBookingOutcome,
+    TextSearchQuery,
+    text_search::{DESCRIPTION_WEIGHT, LOCATION_WEIGHT, SearchDocument, TITLE_WEIGHT, TextQuery},
+};
+use crate::repos::shared::{
+    inmemory::{self, InMemoryStore, InMemoryTables},
+    query_structs::MetadataFindQuery,
+};
+
+#[derive(Debug)]
+pub struct InMemoryEventRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryEventRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+/// An attendee is either an internal user or an external person, at most once per event
+fn ensure_valid_attendees(
+    tables: &InMemoryTables,
+    attendees: &[CalendarEventAttendee],
+) -> anyhow::Result<()> {
+    let mut user_ids = HashSet::new();
+    let mut emails = HashSet::new();
+    for attendee in attendees {
+        match (&attendee.user_id, &attendee.email) {
+            (Some(user_id), None) => {
+                InMemoryTables::ensure_exists(&tables.users, user_id, "user")?;
+                if !user_ids.insert(user_id) {
+                    anyhow::bail!("The user with id: {user_id} is already an attendee");
+                }
+            }
+            (None, Some(email)) => {
+                if !emails.insert(email) {
+                    anyhow::bail!("The email {email} is already an attendee");
+                }
+            }
+            _ => anyhow::bail!("An attendee must have either a user id or an email"),
+        }
+    }
+    Ok(())
+}
+
+/// The referenced service and group must exist
+fn ensure_valid_references(tables: &InMemoryTables, e: &CalendarEvent) -> anyhow::Result<()> {
+    if let Some(service_id) = &e.service_id {
+        InMemoryTables::ensure_exists(&tables.services, service_id, "service")?;
+    }
+    if let Some(group_id) = &e.group_id {
+        InMemoryTables::ensure_exists(&tables.event_groups, group_id, "group")?;
+    }
+    Ok(())
+}
+
+/// Insert a calendar event, including its attendees, with the version 0
+fn insert_event(tables: &mut InMemoryTables, e: &CalendarEvent) -> anyhow::Result<()> {
+    InMemoryTables::ensure_exists(&tables.calendars, &e.calendar_id, "calendar")?;
+    ensure_valid_references(tables, e)?;
+    ensure_valid_attendees(tables, &e.attendees)?;
+    if tables.events.contains_key(e.id.as_ref()) {
+        anyhow::bail!("The calendar event with id: {} already exists", e.id);
+    }
+
+    let mut event = e.clone();
+    event.created = inmemory::to_millis_precision(e.created);
+    event.updated = inmemory::to_millis_precision(e.updated);
+    event.version = 0;
+    tables.remove_event_instances(&event);
+    tables.events.insert(*event.id.as_ref(), event);
+    Ok(())
+}
+
+/// Save a calendar event (but its attendees) and increment its version
+/// When `expected_version` is provided, the event is only saved if its current version matches
+/// Returns false if the event hasn't been updated
+fn save_event(
+    tables: &mut InMemoryTables,
+    e: &CalendarEvent,
+    expected_version: Option<i64>,
+) -> anyhow::Result<bool> {
+    let Some(existing) = tables.events.get(e.id.as_ref()) else {
+        return Ok(false);
+    };
+    if expected_version.is_some_and(|version| version != existing.version) {
+        return Ok(false);
+    }
+    ensure_valid_references(tables, e)?;
+
+    let previous = existing.clone();
+    let event = CalendarEvent {
+        id: previous.id.clone(),
+        account_id: previous.account_id.clone(),
+        user_id: previous.user_id.clone(),
+        calendar_id: previous.calendar_id.clone(),
+        attendees: previous.attendees.clone(),
+        created: inmemory::to_millis_precision(e.created),
+        updated: inmemory::to_millis_precision(e.updated),
+        version: previous.version + 1,
+        ..e.clone()
+    };
+    tables.remove_event_instances(&previous);
+    tables.remove_event_instances(&event);
+    tables.events.insert(*event.id.as_ref(), event);
+    Ok(true)
+}
+
+/// Replace the attendees of a calendar event
+fn replace_attendees(
+    tables: &mut InMemoryTables,
+    event_id: &ID,
+    attendees: &[CalendarEventAttendee],
+) -> anyhow::Result<()> {
+    ensure_valid_attendees(tables, attendees)?;
+    if let Some(event) = tables.events.get_mut(event_id.as_ref()) {
+        event.attendees = attendees.to_vec();
+    } else if !attendees.is_empty() {
+        anyhow::bail!("The calendar event with id: {event_id} doesn't exist");
+    }
+    Ok(())
+}
+
+/// Whether the event (or one of its occurrences) is during the timespan
+/// With `strict`, the events only touching the timespan are excluded
+fn is_during(e: &CalendarEvent, timespan: &TimeSpan, strict: bool) -> bool {
+    let overlaps = if strict {
+        e.start_time < timespan.end() && e.end_time > timespan.start()
+    } else {
+        e.start_time <= timespan.end() && e.end_time >= timespan.start()
+    };
+    overlaps
+        || (e.start_time < timespan.end()
+            && e.recurrence.is_some()
+            && e.recurring_until
+                .is_none_or(|until| until > timespan.start()))
+}
+
+/// By default, the events need to be "confirmed"
+fn has_expected_status(e: &CalendarEvent, include_tentative: bool) -> bool {
+    match e.status {
+        CalendarEventStatus::Confirmed => true,
+        CalendarEventStatus::Tentative => include_tentative,
+        CalendarEventStatus::Cancelled => false,
+    }
+}
+
+/// The event without its attendees, for the queries which don't load them
+fn without_attendees(e: &CalendarEvent) -> CalendarEvent {
+    CalendarEvent {
+        attendees: Vec::new(),
+        ..e.clone()
+    }
+}
+
+/// Key on which the events are sorted, before their ids (which order the events with the same key)
+enum SortKey {
+    StartTime,
+    EndTime,
+    Created,
+    Updated,
+    /// Rank of the events for the text searched (the matches in the title first)
+    Rank,
+    /// The events are only sorted by their ids
+    Id,
+}
+
+/// Value of the sort key of an event
+#[derive(PartialEq, PartialOrd)]
+enum SortKeyValue {
+    Id,
+    Time(DateTime<Utc>),
+    Rank(f32),
+}
+
+/// Key of the sort, and whether the order is descending
+fn sort_key(sort: &CalendarEventSort, text: &Option<TextSearchQuery>) -> (SortKey, bool) {
+    match sort {
+        CalendarEventSort::StartTimeAsc => (SortKey::StartTime, false),
+        CalendarEventSort::StartTimeDesc => (SortKey::StartTime, true),
+        CalendarEventSort::EndTimeAsc => (SortKey::EndTime, false),
+        CalendarEventSort::EndTimeDesc => (SortKey::EndTime, true),
+        CalendarEventSort::CreatedAsc => (SortKey::Created, false),
+        CalendarEventSort::CreatedDesc => (SortKey::Created, true),
+        CalendarEventSort::UpdatedAsc => (SortKey::Updated, false),
+        CalendarEventSort::UpdatedDesc => (SortKey::Updated, true),
+        CalendarEventSort::EventUidAsc => (SortKey::Id, false),
+        CalendarEventSort::EventUidDesc => (SortKey::Id, true),
+        CalendarEventSort::RelevanceDesc => match text {
+            Some(_) => (SortKey::Rank, true),
+            // Without a text searched, there is no relevance
+            None => (SortKey::StartTime, false),
+        },
+    }
+}
+
+fn sort_key_value(key: &SortKey, e: &CalendarEvent, rank: f32) -> SortKeyValue {
+    match key {
+        SortKey::StartTime => SortKeyValue::Time(e.start_time),
+        SortKey::EndTime => SortKeyValue::Time(e.end_time),
+        SortKey::Created => SortKeyValue::Time(e.created),
+        SortKey::Updated => SortKeyValue::Time(e.updated),
+        SortKey::Rank => SortKeyValue::Rank(rank),
+        SortKey::Id => SortKeyValue::Id,
+    }
+}
+
+/// Value of the sort key of the event of a cursor
+fn cursor_key_value(key: &SortKey, cursor: &CalendarEventCursor) -> anyhow::Result<SortKeyValue> {
+    Ok(match (key, &cursor.key) {
+        (SortKey::Id, CalendarEventCursorKey::None) => SortKeyValue::Id,
+        (SortKey::Created | SortKey::Updated, CalendarEventCursorKey::Time(time)) => {
+            SortKeyValue::Time(inmemory::to_millis_precision(*time))
+        }
+        (SortKey::StartTime | SortKey::EndTime, CalendarEventCursorKey::Time(time)) => {
+            SortKeyValue::Time(*time)
+        }
+        (SortKey::Rank, CalendarEventCursorKey::Rank(rank)) => SortKeyValue::Rank(*rank),
+        _ => anyhow::bail!("The cursor doesn't match the sort"),
+    })
+}
+
+/// Whether the event matches the parameters of the search (but the user or the account)
+fn matches_search(
+    tables: &InMemoryTables,
+    e: &CalendarEvent,
+    params: &SearchEventsParams,
+    text: Option<&TextQuery>,
+) -> anyhow::Result<bool> {
+    if !params
+        .event_uid
+        .as_ref()
+        .is_none_or(|q| inmemory::matches_id_query(Some(&e.id), q))
+        || !params
+            .external_id
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_string_query(e.external_id.as_deref(), q))
+        || !params
+            .external_parent_id
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_string_query(e.external_parent_id.as_deref(), q))
+        || !params
+            .start_time
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_datetime_query(Some(e.start_time), q, false))
+        || !params
+            .end_time
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_datetime_query(Some(e.end_time), q, false))
+        || !params
+            .event_type
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_string_query(e.event_type.as_deref(), q))
+        || !params.status.as_ref().is_none_or(|q| {
+            inmemory::matches_string_query(Some(&String::from(e.status.clone())), q)
+        })
+        || !params
+            .recurring_event_uid
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_id_query(e.recurring_event_id.as_ref(), q))
+        || !params
+            .original_start_time
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_datetime_query(e.original_start_time, q, false))
+        || !params
+            .created_at
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_datetime_query(Some(e.created), q, true))
+        || !params
+            .updated_at
+            .as_ref()
+            .is_none_or(|q| inmemory::matches_datetime_query(Some(e.updated), q, true))
+    {
+        return Ok(false);
+    }
+
+    let matches_recurrence = match &params.recurrence {
+        None => true,
+        Some(RecurrenceQuery::ExistsAndRecurringAt(date)) => {
+            e.recurrence.is_some() && e.recurring_until.is_none_or(|until| until >= *date)
+        }
+        Some(RecurrenceQuery::Exists(exists)) => e.recurrence.is_some() == *exists,
+        Some(RecurrenceQuery::Matches(options)) => match &e.recurrence {
+            Some(recurrence) => {
+                inmemory::json_contains(&serde_json::to_value(recurrence)?, options)
+            }
+            None => false,
+        },
+    };
+    let matches_metadata = match &params.metadata {
+        Some(metadata) => inmemory::json_contains(&serde_json::to_value(&e.metadata)?, metadata),
+        None => true,
+    };
+    let matches_attendee = params.attendee.as_ref().is_none_or(|query| {
+        e.attendees.iter().any(|a| {
+            query
+                .user_id
+                .as_ref()
+                .is_none_or(|q| inmemory::matches_id_query(a.user_id.as_ref(), q))
+                && query
+                    .email
+                    .as_ref()
+                    .is_none_or(|q| inmemory::matches_string_query(a.email.as_deref(), q))
+                && query.response_status.as_ref().is_none_or(|q| {
+                    inmemory::matches_string_query(
+                        Some(&String::from(a.response_status.clone())),
+                        q,
+                    )
+                })
+        })
+    });
+    let matches_text = text.is_none_or(|text| text.matches(&search_document(tables, e)));
+
+    Ok(matches_recurrence && matches_metadata && matches_attendee && matches_text)
+}
+
+/// Vector of the texts of the event, in the language of its account (as the `search_vector` column)
+fn search_document(tables: &InMemoryTables, e: &CalendarEvent) -> SearchDocument {
+    let language = tables
+        .accounts
+        .get(e.account_id.as_ref())
+        .map(|account| account.settings.search_language)
+        .unwrap_or_default();
+    SearchDocument::new(
+        &language,
+        [
+            (e.title.as_deref(), TITLE_WEIGHT),
+            (e.description.as_deref(), DESCRIPTION_WEIGHT),
+            (e.location.as_deref(), LOCATION_WEIGHT),
+        ],
+    )
+}
+
+/// Page of the events matching the search, sorted and after the cursor, with the cursor of the next page
+fn search_events<'a>(
+    tables: &'a InMemoryTables,
+    events: impl Iterator<Item = &'a CalendarEvent>,
+    params: &SearchEventsParams,
+    sort: CalendarEventSort,
+    cursor: &Option<CalendarEventCursor>,
+    limit: Option<u16>,
+) -> anyhow::Result<SearchEventsResult> {
+    let text = params
+        .text
+        .as_ref()
+        .map(|text| TextQuery::parse(&text.language, &text.text));
+    let (key, descending) = sort_key(&sort, &params.text);
+    let after = cursor
+        .as_ref()
+        .map(|cursor| cursor_key_value(&key, cursor).map(|value| (value, *cursor.id.as_ref())))
+        .transpose()?;
+
+    let mut found = Vec::new();
+    for e in events {
+        if !matches_search(tables, e, params, text.as_ref())? {
+            continue;
+        }
+        let rank = match (&key, &text) {
+            (SortKey::Rank, Some(text)) => text.rank(&search_document(tables, e)),
+            _ => 0.0,
+        };
+        let value = (sort_key_value(&key, e, rank), *e.id.as_ref());
+        let is_after = after.as_ref().is_none_or(|after| {
+            if descending {
+                value < *after
+            } else {
+                value > *after
+            }
+        });
+        if is_after {
+            found.push((value, rank, e));
+        }
+    }
+    found.sort_by(|(a, _, _), (b, _, _)| {
+        let ordering = a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    let has_next_page = limit.is_some_and(|limit| found.len() > usize::from(limit));
+    if let Some(limit) = limit {
+        found.truncate(usize::from(limit));
+    }
+    let next_cursor = match found.last() {
+        Some((_, rank, e)) if has_next_page => Some(CalendarEventCursor::after(
+            e,
+            sort,
+            matches!(key, SortKey::Rank).then_some(*rank),
+        )),
+        _ => None,
+    };
+
+    Ok(SearchEventsResult {
+        events: found.into_iter().map(|(_, _, e)| e.clone()).collect(),
+        next_cursor,
+    })
+}
+
+/// Save the cancelled events of a service booking and decrement the reservations of its slot
+/// Returns false if one of the events has been modified in the meantime (the tables may be partially written)
+fn cancel_booking(
+    tables: &mut InMemoryTables,
+    cancellation: &ServiceBookingCancellation,
+) -> anyhow::Result<bool> {
+    for e in &cancellation.events {
+        if !save_event(tables, e, Some(e.version - 1))? {
+            return Ok(false);
+        }
+    }
+    let key = (*cancellation.service_id.as_ref(), cancellation.timestamp);
+    if let Some(count) = tables.reservations.get_mut(&key)
+        && *count > 0
+    {
+        *count -= 1;
+    }
+    Ok(true)
+}
+
+/// Book the slot of a service, as `PostgresEventRepo::insert_service_booking`
+/// The tables may be partially written when the outcome is a conflict
+fn book_service_slot(
+    tables: &mut InMemoryTables,
+    booking: &ServiceBooking,
+    outbox: &[OutboxMessage],
+) -> anyhow::Result<ServiceBookingOutcome> {
+    InMemoryTables::ensure_exists(&tables.services, &booking.service_id, "service")?;
+    if let Some(hold_id) = &booking.hold_id {
+        let released = tables
+            .service_holds
+            .remove(hold_id.as_ref())
+            .is_some_and(|hold| !hold.is_expired(booking.now));
+        if !released {
+            return Ok(ServiceBookingOutcome::Conflict);
+        }
+    }
+    if let Some(cancelled_booking) = &booking.cancelled_booking
+        && !cancel_booking(tables, cancelled_booking)?
+    {
+        return Ok(ServiceBookingOutcome::Conflict);
+    }
+
+    let key = (*booking.service_id.as_ref(), booking.timestamp);
+    let reservations = tables.reservations.entry(key).or_default();
+    *reservations += 1;
+    let reservations = usize::try_from(*reservations)?;
+
+    let create_events = match booking.max_reservations {
+        Some(max) if reservations > max => return Ok(ServiceBookingOutcome::Conflict),
+        Some(max) => reservations == max,
+        None => true,
+    };
+    if create_events {
+        let hosts_busy = booking.hosts.iter().any(|host| {
+            tables.has_service_booking_or_hold(
+                &host.user_id,
+                host.busy_start,
+                host.busy_end,
+                booking.now,
+            )
+        });
+        if hosts_busy {
+            return Ok(ServiceBookingOutcome::Conflict);
+        }
+        let limits_reached = booking.hosts.iter().any(|host| {
+            tables.has_reached_booking_limits(&host.user_id, &host.limits, booking.now)
+        });
+        if limits_reached {
+            return Ok(ServiceBookingOutcome::Conflict);
+        }
+
+        for e in &booking.events {
+            insert_event(tables, e)?;
+        }
+        tables.insert_outbox_messages(outbox)?;
+    }
+
+    Ok(ServiceBookingOutcome::Booked {
+        events_created: create_events,
+    })
+}
+
+#[async_trait::async_trait]
+impl IEventRepo for InMemoryEventRepo {
+    async fn insert(&self, event: &CalendarEvent) -> anyhow::Result<()> {
+        self.insert_with_outbox(event, &[]).await
+    }
+
+    async fn insert_with_outbox(
+        &self,
+        event: &CalendarEvent,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        self.insert_many_with_outbox(std::slice::from_ref(event), outbox)
+            .await
+    }
+
+    async fn insert_many(&self, events: &[CalendarEvent]) -> anyhow::Result<()> {
+        self.insert_many_with_outbox(events, &[]).await
+    }
+
+    async fn insert_many_with_outbox(
+        &self,
+        events: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        self.insert_and_save_many_with_outbox(events, &[], outbox)
+            .await
+    }
+
+    async fn save(&self, e: &CalendarEvent) -> anyhow::Result<()> {
+        self.save_with_outbox(e, None, false, &[]).await.map(|_| ())
+    }
+
+    async fn save_if_version(&self, e: &CalendarEvent, version: i64) -> anyhow::Result<bool> {
+        self.save_with_outbox(e, Some(version), false, &[]).await
+    }
+
+    async fn save_with_outbox(
+        &self,
+        e: &CalendarEvent,
+        expected_version: Option<i64>,
+        replace_attendees: bool,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        tables.transaction(|tables| {
+            // Nothing is recorded in the outbox if the event hasn't been saved
+            let saved = save_event(tables, e, expected_version)?;
+            if saved {
+                if replace_attendees {
+                    self::replace_attendees(tables, &e.id, &e.attendees)?;
+                }
+                tables.insert_outbox_messages(outbox)?;
+            }
+            Ok(saved)
+        })
+    }
+
+    async fn save_many(&self, events: &[CalendarEvent]) -> anyhow::Result<()> {
+        self.save_many_with_outbox(events, &[]).await
+    }
+
+    async fn save_many_with_outbox(
+        &self,
+        events: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        self.insert_and_save_many_with_outbox(&[], events, outbox)
+            .await
+    }
+
+    async fn insert_and_save_many_with_outbox(
+        &self,
+        inserted: &[CalendarEvent],
+        saved: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.transaction(|tables| {
+            for e in inserted {
+                insert_event(tables, e)?;
+            }
+            for e in saved {
+                save_event(tables, e, None)?;
+            }
+            tables.insert_outbox_messages(outbox)
+        })
+    }
+
+    async fn split_series_with_outbox(
+        &self,
+        series: &CalendarEvent,
+        expected_version: Option<i64>,
+        new_series: &CalendarEvent,
+        moved_exceptions: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        tables.transaction(|tables| {
+            if !save_event(tables, series, expected_version)? {
+                return Ok(false);
+            }
+            insert_event(tables, new_series)?;
+            for exception in moved_exceptions {
+                save_event(tables, exception, None)?;
+            }
+            tables.insert_outbox_messages(outbox)?;
+            Ok(true)
+        })
+    }
+
+    async fn insert_service_booking(
+        &self,
+        booking: &ServiceBooking,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<ServiceBookingOutcome> {
+        // The store is locked for the whole booking, which serializes the concurrent bookings
+        let mut tables = self.store.lock().await;
+        // Written on a copy of the tables, kept only if the slot has been booked
+        let mut draft = tables.clone();
+        let outcome = book_service_slot(&mut draft, booking, outbox)?;
+        if outcome != ServiceBookingOutcome::Conflict {
+            *tables = draft;
+        }
+        Ok(outcome)
+    }
+
+    async fn cancel_service_booking(
+        &self,
+        cancellation: &ServiceBookingCancellation,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        let mut draft = tables.clone();
+        if !cancel_booking(&mut draft, cancellation)? {
+            return Ok(false);
+        }
+        draft.insert_outbox_messages(outbox)?;
+        *tables = draft;
+        Ok(true)
+    }
+
+    async fn set_attendees(
+        &self,
+        event_id: &ID,
+        attendees: &[CalendarEventAttendee],
+    ) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        replace_attendees(&mut tables, event_id, attendees)
+    }
+
+    async fn update_attendee_response_status(
+        &self,
+        event_id: &ID,
+        user_id: &ID,
+        response_status: CalendarEventAttendeeResponseStatus,
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        let attendee = tables.events.get_mut(event_id.as_ref()).and_then(|e| {
+            e.attendees
+                .iter_mut()
+                .find(|a| a.user_id.as_ref() == Some(user_id))
+        });
+        Ok(match attendee {
+            Some(attendee) => {
+                attendee.response_status = response_status;
+                true
+            }
+            None => false,
+        })
+    }
+
+    async fn find(&self, event_id: &ID) -> anyhow::Result<Option<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables.events.get(event_id.as_ref()).cloned())
+    }
+
+    async fn find_by_id_and_recurring_event_id(
+        &self,
+        event_id: &ID,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| &e.id == event_id || e.recurring_event_id.as_ref() == Some(event_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_recurring_event_ids_for_timespan(
+        &self,
+        recurring_event_ids: &[ID],
+        timespan: TimeSpan,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                e.recurring_event_id
+                    .as_ref()
+                    .is_some_and(|id| recurring_event_ids.contains(id))
+                    && (e.original_start_time.is_some_and(|original_start_time| {
+                        original_start_time >= timespan.start()
+                            && original_start_time <= timespan.end()
+                    }) || (e.start_time <= timespan.end() && e.end_time >= timespan.start()))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_by_external_id(
+        &self,
+        account_id: &ID,
+        external_id: &str,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                &e.account_id == account_id && e.external_id.as_deref() == Some(external_id)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_many_by_external_ids(
+        &self,
+        account_id: &ID,
+        external_ids: &[String],
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                &e.account_id == account_id
+                    && e.external_id
+                        .as_ref()
+                        .is_some_and(|external_id| external_ids.contains(external_id))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_many(&self, event_ids: &[ID]) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| event_ids.contains(&e.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_group(&self, group_id: &ID) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        let mut events = tables
+            .events
+            .values()
+            .filter(|e| e.group_id.as_ref() == Some(group_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        events.sort_by_key(|e| e.start_time);
+        Ok(events)
+    }
+
+    async fn find_by_calendar(
+        &self,
+        calendar_id: &ID,
+        timespan: Option<TimeSpan>,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                &e.calendar_id == calendar_id
+                    && timespan
+                        .as_ref()
+                        .is_none_or(|timespan| is_during(e, timespan, false))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_events_for_users_for_timespan(
+        &self,
+        user_ids: &[ID],
+        timespan: TimeSpan,
+        include_tentative: bool,
+        include_non_busy: bool,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                user_ids.contains(&e.user_id)
+                    && e.start_time <= timespan.end()
+                    && e.end_time >= timespan.start()
+                    && (e.busy || include_non_busy)
+                    && has_expected_status(e, include_tentative)
+                    && e.recurrence.is_none()
+                    && e.original_start_time.is_none()
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_recurring_events_for_users_for_timespan(
+        &self,
+        user_ids: &[ID],
+        timespan: TimeSpan,
+        include_tentative: bool,
+        include_non_busy: bool,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                user_ids.contains(&e.user_id)
+                    && e.start_time <= timespan.end()
+                    && e.recurrence.is_some()
+                    && e.recurring_until
+                        .is_none_or(|until| until >= timespan.start())
+                    && (e.busy || include_non_busy)
+                    && has_expected_status(e, include_tentative)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_calendars(
+        &self,
+        calendar_ids: &[ID],
+        timespan: TimeSpan,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| calendar_ids.contains(&e.calendar_id) && is_during(e, &timespan, false))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_busy_events_and_recurring_events_for_calendars(
+        &self,
+        calendar_ids: &[ID],
+        timespan: TimeSpan,
+        include_tentative: bool,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                calendar_ids.contains(&e.calendar_id)
+                    && is_during(e, &timespan, true)
+                    && e.busy
+                    && has_expected_status(e, include_tentative)
+            })
+            .map(without_attendees)
+            .collect())
+    }
+
+    async fn find_busy_events_and_recurring_events_for_attendees(
+        &self,
+        user_ids: &[ID],
+        timespan: TimeSpan,
+        include_tentative: bool,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                e.attendees.iter().any(|a| {
+                    a.user_id
+                        .as_ref()
+                        .is_some_and(|user_id| user_ids.contains(user_id) && user_id != &e.user_id)
+                        && a.response_status != CalendarEventAttendeeResponseStatus::Declined
+                }) && is_during(e, &timespan, true)
+                    && e.busy
+                    && has_expected_status(e, include_tentative)
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn search_events_for_user(
+        &self,
+        params: SearchEventsForUserParams,
+    ) -> anyhow::Result<SearchEventsResult> {
+        let tables = self.store.lock().await;
+        let events = tables.events.values().filter(|e| {
+            e.user_id == params.user_id
+                && params
+                    .calendar_ids
+                    .as_ref()
+                    .is_none_or(|calendar_ids| calendar_ids.contains(&e.calendar_id))
+        });
+        search_events(
+            &tables,
+            events,
+            &params.search_events_params,
+            params.sort.unwrap_or_default(),
+            &params.cursor,
+            params.limit,
+        )
+    }
+
+    async fn search_events_for_account(
+        &self,
+        params: SearchEventsForAccountParams,
+    ) -> anyhow::Result<SearchEventsResult> {
+        let tables = self.store.lock().await;
+        let events = tables.events.values().filter(|e| {
+            e.account_id == params.account_id
+                && params
+                    .search_events_params
+                    .user_uid
+                    .as_ref()
+                    .is_none_or(|q| inmemory::matches_id_query(Some(&e.user_id), q))
+        });
+        search_events(
+            &tables,
+            events,
+            &params.search_events_params,
+            params.sort.unwrap_or_default(),
+            &params.cursor,
+            params.limit,
+        )
+    }
+
+    async fn find_text_highlights(
+        &self,
+        event_ids: &[ID],
+        text: &TextSearchQuery,
+    ) -> anyhow::Result<Vec<CalendarEventHighlight>> {
+        let tables = self.store.lock().await;
+        let query = TextQuery::parse(&text.language, &text.text);
+        let highlight = |field: &Option<String>| {
+            field
+                .as_deref()
+                .and_then(|field| query.highlight(&text.language, field))
+        };
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| event_ids.contains(&e.id))
+            .map(|e| CalendarEventHighlight {
+                event_id: e.id.clone(),
+                title: highlight(&e.title),
+                description: highlight(&e.description),
+                location: highlight(&e.location),
+            })
+            .filter(|h| h.title.is_some() || h.description.is_some() || h.location.is_some())
+            .collect())
+    }
+
+    async fn find_most_recently_created_service_events(
+        &self,
+        service_id: &ID,
+        user_ids: &[ID],
+    ) -> anyhow::Result<Vec<MostRecentCreatedServiceEvents>> {
+        let tables = self.store.lock().await;
+        let mut seen = HashSet::new();
+        Ok(user_ids
+            .iter()
+            .filter(|user_id| tables.users.contains_key(user_id.as_ref()) && seen.insert(*user_id))
+            .map(|user_id| MostRecentCreatedServiceEvents {
+                user_id: user_id.clone(),
+                created: tables
+                    .events
+                    .values()
+                    .filter(|e| &e.user_id == user_id && e.service_id.as_ref() == Some(service_id))
+                    .map(|e| e.created)
+                    .max(),
+            })
+            .collect())
+    }
+
+    async fn find_by_service(
+        &self,
+        service_id: &ID,
+        user_ids: &[ID],
+        min_time: DateTime<Utc>,
+        max_time: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                e.service_id.as_ref() == Some(service_id)
+                    && user_ids.contains(&e.user_id)
+                    && e.start_time <= max_time
+                    && e.end_time >= min_time
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_user_service_events(
+        &self,
+        user_id: &ID,
+        busy: bool,
+        min_time: DateTime<Utc>,
+        max_time: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| {
+                &e.user_id == user_id
+                    && e.busy == busy
+                    && e.service_id.is_some()
+                    && e.start_time <= max_time
+                    && e.end_time >= min_time
+            })
+            .map(without_attendees)
+            .collect())
+    }
+
+    async fn delete(&self, event_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if tables.delete_event(event_id).is_none() {
+            tracing::warn!(
+                "Tried to delete calendar event with id: {:?}, but it does not exist",
+                event_id
+            );
+        }
+        Ok(())
+    }
+
+    async fn delete_many(&self, event_ids: &[ID]) -> anyhow::Result<()> {
+        self.delete_many_with_outbox(event_ids, &[]).await
+    }
+
+    async fn delete_many_with_outbox(
+        &self,
+        event_ids: &[ID],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.transaction(|tables| {
+            for event_id in event_ids {
+                tables.delete_event(event_id);
+            }
+            tables.insert_outbox_messages(outbox)
+        })
+    }
+
+    async fn save_and_delete_many_with_outbox(
+        &self,
+        e: &CalendarEvent,
+        deleted_event_ids: &[ID],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.transaction(|tables| {
+            save_event(tables, e, None)?;
+            for event_id in deleted_event_ids {
+                tables.delete_event(event_id);
+            }
+            tables.insert_outbox_messages(outbox)
+        })
+    }
+
+    async fn delete_by_service(&self, service_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        let event_ids = tables
+            .events
+            .values()
+            .filter(|e| e.service_id.as_ref() == Some(service_id))
+            .map(|e| e.id.clone())
+            .collect::<Vec<_>>();
+        for event_id in event_ids {
+            tables.delete_event(&event_id);
+        }
+        Ok(())
+    }
+
+    async fn find_by_metadata(
+        &self,
+        query: MetadataFindQuery,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let tables = self.store.lock().await;
+        inmemory::find_by_metadata(
+            tables.events.iter(),
+            &query,
+            |e| &e.account_id,
+            |e| e.metadata.as_ref(),
+        )
+    }
+}
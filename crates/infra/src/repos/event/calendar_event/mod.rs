@@ -1,15 +1,26 @@
+mod inmemory;
 mod postgres;
+mod text_search;
 
 use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryEventRepo;
 use nittei_domain::{
+    AttendeeQuery,
     CalendarEvent,
+    CalendarEventAttendee,
+    CalendarEventAttendeeResponseStatus,
+    CalendarEventCursor,
+    CalendarEventHighlight,
     CalendarEventSort,
     DateTimeQuery,
     ID,
     IDQuery,
+    OutboxMessage,
     RecurrenceQuery,
+    SearchLanguage,
     StringQuery,
     TimeSpan,
+    scheduling::BookingPeriod,
 };
 pub use postgres::PostgresEventRepo;
 
@@ -27,6 +38,8 @@ pub struct SearchEventsForUserParams {
     pub calendar_ids: Option<Vec<ID>>,
     pub search_events_params: SearchEventsParams,
     pub sort: Option<CalendarEventSort>,
+    /// Only the events after this cursor (in the order of the sort)
+    pub cursor: Option<CalendarEventCursor>,
     pub limit: Option<u16>,
 }
 
@@ -35,6 +48,8 @@ pub struct SearchEventsForAccountParams {
     pub account_id: ID,
     pub search_events_params: SearchEventsParams,
     pub sort: Option<CalendarEventSort>,
+    /// Only the events after this cursor (in the order of the sort)
+    pub cursor: Option<CalendarEventCursor>,
     pub limit: Option<u16>,
 }
 
@@ -54,13 +69,177 @@ pub struct SearchEventsParams {
     pub metadata: Option<serde_json::Value>,
     pub created_at: Option<DateTimeQuery>,
     pub updated_at: Option<DateTimeQuery>,
+    pub attendee: Option<AttendeeQuery>,
+    pub text: Option<TextSearchQuery>,
+}
+
+/// Page of events found by a search
+#[derive(Debug, Clone)]
+pub struct SearchEventsResult {
+    pub events: Vec<CalendarEvent>,
+    /// Cursor after the last event, when there are more events than the limit
+    pub next_cursor: Option<CalendarEventCursor>,
+}
+
+/// Text searched in the title, description and location of the events
+#[derive(Debug, Clone)]
+pub struct TextSearchQuery {
+    /// Text to search, in the web search syntax (e.g. `"team meeting" -lunch`)
+    pub text: String,
+    /// Language of the texts of the events (the one of the account)
+    pub language: SearchLanguage,
+}
+
+/// A host of a service booking
+///
+/// The host must not have another (non cancelled) service event or (non expired) service hold
+/// between `busy_start` and `busy_end`, which is the time of the booking extended by the buffers of the host
+#[derive(Debug, Clone)]
+pub struct ServiceBookingHost {
+    pub user_id: ID,
+    pub busy_start: DateTime<Utc>,
+    pub busy_end: DateTime<Utc>,
+    /// The day and week of the booking, in which the host must have started less than their `max`
+    /// (non cancelled) service events and (non expired) service holds
+    pub limits: Vec<BookingPeriod>,
+}
+
+/// The booking of a slot of a service, see `IEventRepo::insert_service_booking`
+#[derive(Debug, Clone)]
+pub struct ServiceBooking {
+    pub service_id: ID,
+    /// Start of the booked slot
+    pub timestamp: DateTime<Utc>,
+    pub hosts: Vec<ServiceBookingHost>,
+    /// The events of the hosts for the booking
+    pub events: Vec<CalendarEvent>,
+    /// Max number of reservations of the slot (group services)
+    /// When provided, the events are only created by the reservation filling the slot
+    pub max_reservations: Option<usize>,
+    /// Hold of the slot confirmed by the booking, which is released by it
+    /// The booking is rejected if the hold has expired or has already been released
+    pub hold_id: Option<ID>,
+    /// Time of the booking, to ignore the expired holds of the hosts
+    pub now: DateTime<Utc>,
+    /// Booking replaced by this one (rescheduling), cancelled in the same transaction
+    /// Its events don't make the hosts busy anymore
+    pub cancelled_booking: Option<ServiceBookingCancellation>,
+}
+
+/// The cancellation of a service booking, see `IEventRepo::cancel_service_booking`
+#[derive(Debug, Clone)]
+pub struct ServiceBookingCancellation {
+    pub service_id: ID,
+    /// Start of the cancelled slot, whose reservations are decremented
+    pub timestamp: DateTime<Utc>,
+    /// The cancelled events of the hosts, saved only if their current version is the previous one
+    pub events: Vec<CalendarEvent>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceBookingOutcome {
+    /// The slot has been reserved, and the events have been created if `events_created`
+    Booked { events_created: bool },
+    /// The slot has been taken in the meantime, nothing has been written
+    Conflict,
 }
 
 #[async_trait::async_trait]
 pub trait IEventRepo: Send + Sync {
+    /// Insert the event, including its attendees
     async fn insert(&self, e: &CalendarEvent) -> anyhow::Result<()>;
+    /// Insert the event, and record the outbox messages in the same transaction
+    async fn insert_with_outbox(
+        &self,
+        e: &CalendarEvent,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()>;
+    /// Insert the events, including their attendees
     async fn insert_many(&self, events: &[CalendarEvent]) -> anyhow::Result<()>;
+    /// Insert the events, and record the outbox messages in the same transaction
+    async fn insert_many_with_outbox(
+        &self,
+        events: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()>;
+    /// Save the event, and increment its version
+    /// The attendees are not saved, use `set_attendees` for this
     async fn save(&self, e: &CalendarEvent) -> anyhow::Result<()>;
+    /// Save the event only if its current version is `version` (optimistic concurrency control)
+    /// Returns false if the event has been modified in the meantime, or doesn't exist
+    async fn save_if_version(&self, e: &CalendarEvent, version: i64) -> anyhow::Result<bool>;
+    /// Save the event (only if its current version is `expected_version`, when provided),
+    /// and record the outbox messages in the same transaction
+    /// With `replace_attendees`, the attendees are replaced by the ones of the event in the same transaction
+    /// Returns false if the event hasn't been saved, nothing is recorded in this case
+    async fn save_with_outbox(
+        &self,
+        e: &CalendarEvent,
+        expected_version: Option<i64>,
+        replace_attendees: bool,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool>;
+    /// Save the events, and increment their versions
+    /// The attendees are not saved, use `set_attendees` for this
+    async fn save_many(&self, events: &[CalendarEvent]) -> anyhow::Result<()>;
+    /// Save the events, and record the outbox messages in the same transaction
+    async fn save_many_with_outbox(
+        &self,
+        events: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()>;
+    /// Insert the new events (including their attendees) and save the existing ones (incrementing their versions),
+    /// and record the outbox messages in the same transaction
+    async fn insert_and_save_many_with_outbox(
+        &self,
+        inserted: &[CalendarEvent],
+        saved: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()>;
+    /// Split a recurring series in a single transaction: save the truncated series (only if it has the
+    /// expected version, if any), insert the new series (including its attendees), save the exceptions
+    /// moved to the new series, and record the outbox messages
+    ///
+    /// Returns false (and writes nothing) if the series doesn't have the expected version
+    async fn split_series_with_outbox(
+        &self,
+        series: &CalendarEvent,
+        expected_version: Option<i64>,
+        new_series: &CalendarEvent,
+        moved_exceptions: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool>;
+    /// Book the slot of a service: increment the reservations of the slot and create the events of the hosts,
+    /// and record the outbox messages (of the events) in the same transaction
+    ///
+    /// The hosts are locked during the transaction, so that concurrent bookings of the same hosts are serialized.
+    /// Nothing is written if one of the hosts has another service event or hold at the time of the booking,
+    /// if the slot is already full (group services), or if the confirmed hold isn't active anymore
+    async fn insert_service_booking(
+        &self,
+        booking: &ServiceBooking,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<ServiceBookingOutcome>;
+    /// Cancel a service booking: save the cancelled events of the hosts and decrement the reservations of the slot,
+    /// and record the outbox messages in the same transaction
+    /// Returns false if one of the events has been modified in the meantime, nothing is written in this case
+    async fn cancel_service_booking(
+        &self,
+        cancellation: &ServiceBookingCancellation,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool>;
+    async fn set_attendees(
+        &self,
+        event_id: &ID,
+        attendees: &[CalendarEventAttendee],
+    ) -> anyhow::Result<()>;
+    /// Returns false if the user is not an attendee of the event
+    async fn update_attendee_response_status(
+        &self,
+        event_id: &ID,
+        user_id: &ID,
+        response_status: CalendarEventAttendeeResponseStatus,
+    ) -> anyhow::Result<bool>;
     async fn find(&self, event_id: &ID) -> anyhow::Result<Option<CalendarEvent>>;
     async fn find_by_id_and_recurring_event_id(
         &self,
@@ -82,6 +261,7 @@ pub trait IEventRepo: Send + Sync {
         external_ids: &[String],
     ) -> anyhow::Result<Vec<CalendarEvent>>;
     async fn find_many(&self, event_ids: &[ID]) -> anyhow::Result<Vec<CalendarEvent>>;
+    async fn find_by_group(&self, group_id: &ID) -> anyhow::Result<Vec<CalendarEvent>>;
     async fn find_by_calendar(
         &self,
         calendar_id: &ID,
@@ -112,14 +292,29 @@ pub trait IEventRepo: Send + Sync {
         timespan: TimeSpan,
         include_tentative: bool,
     ) -> anyhow::Result<Vec<CalendarEvent>>;
+    async fn find_busy_events_and_recurring_events_for_attendees(
+        &self,
+        user_ids: &[ID],
+        timespan: TimeSpan,
+        include_tentative: bool,
+    ) -> anyhow::Result<Vec<CalendarEvent>>;
+    /// Search the events of a user, sorted by start time when no sort is given
     async fn search_events_for_user(
         &self,
         params: SearchEventsForUserParams,
-    ) -> anyhow::Result<Vec<CalendarEvent>>;
+    ) -> anyhow::Result<SearchEventsResult>;
+    /// Search the events of an account, sorted by start time when no sort is given
     async fn search_events_for_account(
         &self,
         params: SearchEventsForAccountParams,
-    ) -> anyhow::Result<Vec<CalendarEvent>>;
+    ) -> anyhow::Result<SearchEventsResult>;
+    /// Fragments of the texts of the events containing words of the text searched
+    /// Only the events with at least one matching text are returned
+    async fn find_text_highlights(
+        &self,
+        event_ids: &[ID],
+        text: &TextSearchQuery,
+    ) -> anyhow::Result<Vec<CalendarEventHighlight>>;
     async fn find_most_recently_created_service_events(
         &self,
         service_id: &ID,
@@ -141,7 +336,22 @@ pub trait IEventRepo: Send + Sync {
     ) -> anyhow::Result<Vec<CalendarEvent>>;
     async fn delete(&self, event_id: &ID) -> anyhow::Result<()>;
     async fn delete_many(&self, event_ids: &[ID]) -> anyhow::Result<()>;
+    /// Delete the events, and record the outbox messages in the same transaction
+    async fn delete_many_with_outbox(
+        &self,
+        event_ids: &[ID],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()>;
+    /// Save the event and delete the other events (e.g. the exceptions of its deleted occurrences),
+    /// and record the outbox messages in the same transaction
+    async fn save_and_delete_many_with_outbox(
+        &self,
+        e: &CalendarEvent,
+        deleted_event_ids: &[ID],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()>;
     async fn delete_by_service(&self, service_id: &ID) -> anyhow::Result<()>;
+    /// Find the events by their metadata, ordered by their ids
     async fn find_by_metadata(
         &self,
         query: MetadataFindQuery,
@@ -151,9 +361,34 @@ pub trait IEventRepo: Send + Sync {
 #[cfg(test)]
 mod tests {
     use chrono::{DateTime, TimeDelta, Utc};
-    use nittei_domain::{Account, Calendar, CalendarEvent, Entity, ID, Service, TimeSpan, User};
+    use nittei_domain::{
+        Account,
+        AttendeeQuery,
+        Calendar,
+        CalendarEvent,
+        CalendarEventAttendee,
+        CalendarEventAttendeeResponseStatus,
+        CalendarEventHighlight,
+        CalendarEventSort,
+        CalendarEventStatus,
+        Entity,
+        ID,
+        IDQuery,
+        OutboxMessage,
+        OutboxTask,
+        RRuleFrequency,
+        RRuleOptions,
+        RecurrenceQuery,
+        SearchLanguage,
+        Service,
+        StringQuery,
+        TimeSpan,
+        User,
+        scheduling::BookingPeriod,
+    };
 
-    use crate::{NitteiContext, setup_context};
+    use super::{SearchEventsForAccountParams, SearchEventsParams, TextSearchQuery};
+    use crate::{NitteiContext, backend_tests};
 
     fn generate_default_event(account_id: &ID, calendar_id: &ID, user_id: &ID) -> CalendarEvent {
         CalendarEvent {
@@ -171,8 +406,7 @@ mod tests {
         user: User,
     }
 
-    async fn setup() -> TestContext {
-        let ctx = setup_context().await.unwrap();
+    async fn setup(ctx: NitteiContext) -> TestContext {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
         let user = User::new(account.id.clone(), None);
@@ -188,14 +422,32 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn create_and_delete() {
+    backend_tests!(
+        create_and_delete,
+        update,
+        outbox_messages_are_recorded_with_the_write,
+        split_series_and_delete_exceptions_in_one_transaction,
+        attendees,
+        search_on_the_texts,
+        search_on_the_recurrence_options,
+        search_with_cursors,
+        delete_by_user,
+        delete_by_calendar,
+        find_by_calendar_and_timespan,
+        find_most_recently_created_service_events,
+        find_by_service_and_timespan,
+        test_find_user_service_events,
+        insert_service_booking,
+        cancel_service_booking
+    );
+
+    async fn create_and_delete(ctx: NitteiContext) {
         let TestContext {
             ctx,
             account,
             calendar,
             user,
-        } = setup().await;
+        } = setup(ctx).await;
         let event = generate_default_event(&account.id, &calendar.id, &user.id);
 
         // Insert
@@ -220,14 +472,13 @@ mod tests {
         assert!(ctx.repos.events.find(&event.id).await.unwrap().is_none());
     }
 
-    #[tokio::test]
-    async fn update() {
+    async fn update(ctx: NitteiContext) {
         let TestContext {
             ctx,
             account,
             calendar,
             user,
-        } = setup().await;
+        } = setup(ctx).await;
         let mut event = generate_default_event(&account.id, &calendar.id, &user.id);
 
         // Insert
@@ -253,14 +504,692 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn delete_by_user() {
+    async fn outbox_messages_are_recorded_with_the_write(ctx: NitteiContext) {
         let TestContext {
             ctx,
             account,
             calendar,
             user,
-        } = setup().await;
+        } = setup(ctx).await;
+        let event = generate_default_event(&account.id, &calendar.id, &user.id);
+        let generate_message = || {
+            OutboxMessage::new(
+                account.id.clone(),
+                OutboxTask::SyncEventReminders {
+                    event_id: event.id.clone(),
+                },
+                DateTime::from_timestamp_millis(0).unwrap(),
+            )
+        };
+
+        let message = generate_message();
+        ctx.repos
+            .events
+            .insert_with_outbox(&event, std::slice::from_ref(&message))
+            .await
+            .unwrap();
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_some());
+
+        let message = generate_message();
+        let saved = ctx
+            .repos
+            .events
+            .save_with_outbox(
+                &event,
+                Some(event.version),
+                false,
+                std::slice::from_ref(&message),
+            )
+            .await
+            .unwrap();
+        assert!(saved);
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_some());
+
+        // The event hasn't been saved (stale version), so nothing is recorded
+        let message = generate_message();
+        let saved = ctx
+            .repos
+            .events
+            .save_with_outbox(
+                &event,
+                Some(event.version),
+                false,
+                std::slice::from_ref(&message),
+            )
+            .await
+            .unwrap();
+        assert!(!saved);
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_none());
+
+        let new_event = generate_default_event(&account.id, &calendar.id, &user.id);
+        let message = generate_message();
+        ctx.repos
+            .events
+            .insert_and_save_many_with_outbox(
+                std::slice::from_ref(&new_event),
+                std::slice::from_ref(&event),
+                std::slice::from_ref(&message),
+            )
+            .await
+            .unwrap();
+        assert!(
+            ctx.repos
+                .events
+                .find(&new_event.id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            ctx.repos
+                .events
+                .find(&event.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .version,
+            event.version + 2
+        );
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_some());
+
+        let message = generate_message();
+        ctx.repos
+            .events
+            .delete_many_with_outbox(
+                std::slice::from_ref(&event.id),
+                std::slice::from_ref(&message),
+            )
+            .await
+            .unwrap();
+        assert!(ctx.repos.events.find(&event.id).await.unwrap().is_none());
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_some());
+    }
+
+    async fn split_series_and_delete_exceptions_in_one_transaction(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        } = setup(ctx).await;
+        let series = generate_default_event(&account.id, &calendar.id, &user.id);
+        ctx.repos.events.insert(&series).await.unwrap();
+        let exception = CalendarEvent {
+            recurring_event_id: Some(series.id.clone()),
+            original_start_time: Some(series.start_time),
+            ..generate_default_event(&account.id, &calendar.id, &user.id)
+        };
+        ctx.repos.events.insert(&exception).await.unwrap();
+
+        let new_series = generate_default_event(&account.id, &calendar.id, &user.id);
+        let moved_exception = CalendarEvent {
+            recurring_event_id: Some(new_series.id.clone()),
+            ..exception.clone()
+        };
+        let message = OutboxMessage::new(
+            account.id.clone(),
+            OutboxTask::SyncEventReminders {
+                event_id: new_series.id.clone(),
+            },
+            DateTime::from_timestamp_millis(0).unwrap(),
+        );
+
+        // The series doesn't have the expected version, so nothing is written
+        let split = ctx
+            .repos
+            .events
+            .split_series_with_outbox(
+                &series,
+                Some(series.version + 1),
+                &new_series,
+                std::slice::from_ref(&moved_exception),
+                std::slice::from_ref(&message),
+            )
+            .await
+            .unwrap();
+        assert!(!split);
+        assert!(
+            ctx.repos
+                .events
+                .find(&new_series.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            ctx.repos
+                .events
+                .find(&exception.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .recurring_event_id,
+            Some(series.id.clone())
+        );
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_none());
+
+        let split = ctx
+            .repos
+            .events
+            .split_series_with_outbox(
+                &series,
+                Some(series.version),
+                &new_series,
+                std::slice::from_ref(&moved_exception),
+                std::slice::from_ref(&message),
+            )
+            .await
+            .unwrap();
+        assert!(split);
+        assert!(
+            ctx.repos
+                .events
+                .find(&new_series.id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            ctx.repos
+                .events
+                .find(&exception.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .recurring_event_id,
+            Some(new_series.id.clone())
+        );
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_some());
+
+        // The exceptions of the deleted occurrences are deleted with the save of the series
+        ctx.repos
+            .events
+            .save_and_delete_many_with_outbox(&new_series, std::slice::from_ref(&exception.id), &[])
+            .await
+            .unwrap();
+        assert!(
+            ctx.repos
+                .events
+                .find(&exception.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            ctx.repos
+                .events
+                .find(&new_series.id)
+                .await
+                .unwrap()
+                .unwrap()
+                .version,
+            new_series.version + 1
+        );
+    }
+
+    async fn attendees(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        } = setup(ctx).await;
+        let attendee = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&attendee).await.unwrap();
+
+        let mut event = generate_default_event(&account.id, &calendar.id, &user.id);
+        event.busy = true;
+        event.status = CalendarEventStatus::Confirmed;
+        event.end_time = event.start_time + TimeDelta::hours(1);
+        event.duration = TimeDelta::hours(1).num_milliseconds();
+        event.attendees = vec![
+            CalendarEventAttendee {
+                user_id: Some(attendee.id.clone()),
+                ..Default::default()
+            },
+            CalendarEventAttendee {
+                email: Some("john@example.com".into()),
+                response_status: CalendarEventAttendeeResponseStatus::Accepted,
+                ..Default::default()
+            },
+        ];
+
+        // Insert with the attendees
+        ctx.repos.events.insert(&event).await.unwrap();
+        let found = ctx.repos.events.find(&event.id).await.unwrap().unwrap();
+        assert_eq!(found.attendees, event.attendees);
+
+        // Search on the attendees
+        let search = |attendee: AttendeeQuery| SearchEventsForAccountParams {
+            account_id: account.id.clone(),
+            search_events_params: SearchEventsParams {
+                event_uid: None,
+                user_uid: None,
+                external_id: None,
+                external_parent_id: None,
+                start_time: None,
+                end_time: None,
+                status: None,
+                event_type: None,
+                recurring_event_uid: None,
+                original_start_time: None,
+                recurrence: None,
+                metadata: None,
+                created_at: None,
+                updated_at: None,
+                attendee: Some(attendee),
+                text: None,
+            },
+            sort: None,
+            cursor: None,
+            limit: None,
+        };
+        let res = ctx
+            .repos
+            .events
+            .search_events_for_account(search(AttendeeQuery {
+                email: Some(StringQuery::Eq("john@example.com".into())),
+                response_status: Some(StringQuery::Eq("accepted".into())),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(res.events.len(), 1);
+        let res = ctx
+            .repos
+            .events
+            .search_events_for_account(search(AttendeeQuery {
+                user_id: Some(IDQuery::Eq(attendee.id.clone())),
+                response_status: Some(StringQuery::Eq("accepted".into())),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert!(res.events.is_empty());
+
+        // The event makes the attendee busy
+        let timespan = TimeSpan::new(
+            event.start_time - TimeDelta::hours(1),
+            event.end_time + TimeDelta::hours(1),
+        );
+        let attended = ctx
+            .repos
+            .events
+            .find_busy_events_and_recurring_events_for_attendees(
+                std::slice::from_ref(&attendee.id),
+                timespan.clone(),
+                false,
+            )
+            .await
+            .unwrap();
+        assert_eq!(attended.len(), 1);
+
+        // Until the attendee declines
+        assert!(
+            ctx.repos
+                .events
+                .update_attendee_response_status(
+                    &event.id,
+                    &attendee.id,
+                    CalendarEventAttendeeResponseStatus::Declined,
+                )
+                .await
+                .unwrap()
+        );
+        let attended = ctx
+            .repos
+            .events
+            .find_busy_events_and_recurring_events_for_attendees(
+                std::slice::from_ref(&attendee.id),
+                timespan,
+                false,
+            )
+            .await
+            .unwrap();
+        assert!(attended.is_empty());
+
+        // The owner is not an attendee
+        assert!(
+            !ctx.repos
+                .events
+                .update_attendee_response_status(
+                    &event.id,
+                    &user.id,
+                    CalendarEventAttendeeResponseStatus::Accepted,
+                )
+                .await
+                .unwrap()
+        );
+
+        // Saving the event doesn't touch the attendees, setting them replaces them
+        ctx.repos.events.save(&event).await.unwrap();
+        let found = ctx.repos.events.find(&event.id).await.unwrap().unwrap();
+        assert!(found.is_declined_by(&attendee.id));
+        ctx.repos
+            .events
+            .set_attendees(&event.id, &event.attendees[1..])
+            .await
+            .unwrap();
+        let found = ctx.repos.events.find(&event.id).await.unwrap().unwrap();
+        assert_eq!(found.attendees, event.attendees[1..].to_vec());
+
+        // Or with the event, in the same transaction
+        let mut saved = found.clone();
+        saved.attendees = event.attendees[..1].to_vec();
+        assert!(
+            ctx.repos
+                .events
+                .save_with_outbox(&saved, Some(found.version), true, &[])
+                .await
+                .unwrap()
+        );
+        let found = ctx.repos.events.find(&event.id).await.unwrap().unwrap();
+        assert_eq!(found.attendees, event.attendees[..1].to_vec());
+    }
+
+    async fn search_on_the_texts(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            mut account,
+            calendar,
+            user,
+        } = setup(ctx).await;
+
+        let mut meeting = generate_default_event(&account.id, &calendar.id, &user.id);
+        meeting.title = Some("Weekly meetings of the team".into());
+        meeting.location = Some("Room 12".into());
+        let mut lunch = generate_default_event(&account.id, &calendar.id, &user.id);
+        lunch.title = Some("Lunch".into());
+        lunch.description = Some("Lunch with the team, before the meeting with the client".into());
+        lunch.start_time = meeting.start_time - TimeDelta::days(1);
+        ctx.repos.events.insert(&meeting).await.unwrap();
+        ctx.repos.events.insert(&lunch).await.unwrap();
+
+        let search = |text: &str, language: SearchLanguage| SearchEventsForAccountParams {
+            account_id: account.id.clone(),
+            search_events_params: SearchEventsParams {
+                event_uid: None,
+                user_uid: None,
+                external_id: None,
+                external_parent_id: None,
+                start_time: None,
+                end_time: None,
+                status: None,
+                event_type: None,
+                recurring_event_uid: None,
+                original_start_time: None,
+                recurrence: None,
+                metadata: None,
+                created_at: None,
+                updated_at: None,
+                attendee: None,
+                text: Some(TextSearchQuery {
+                    text: text.into(),
+                    language,
+                }),
+            },
+            sort: Some(CalendarEventSort::RelevanceDesc),
+            cursor: None,
+            limit: None,
+        };
+        let found_ids =
+            |events: Vec<CalendarEvent>| events.into_iter().map(|e| e.id).collect::<Vec<_>>();
+
+        // Without a language, the words are only lowercased
+        let res = ctx
+            .repos
+            .events
+            .search_events_for_account(search("meeting", SearchLanguage::Simple))
+            .await
+            .unwrap();
+        assert_eq!(found_ids(res.events), vec![lunch.id.clone()]);
+        let res = ctx
+            .repos
+            .events
+            .search_events_for_account(search("TEAM -client", SearchLanguage::Simple))
+            .await
+            .unwrap();
+        assert_eq!(found_ids(res.events), vec![meeting.id.clone()]);
+
+        // In English, the words are stemmed, and the matches in the title come first
+        account.settings.search_language = SearchLanguage::English;
+        ctx.repos.accounts.save(&account).await.unwrap();
+        let res = ctx
+            .repos
+            .events
+            .search_events_for_account(search("meeting", SearchLanguage::English))
+            .await
+            .unwrap();
+        assert_eq!(
+            found_ids(res.events),
+            vec![meeting.id.clone(), lunch.id.clone()]
+        );
+
+        // The vector is updated with the texts
+        lunch.description = None;
+        ctx.repos.events.save(&lunch).await.unwrap();
+        let res = ctx
+            .repos
+            .events
+            .search_events_for_account(search("meeting", SearchLanguage::English))
+            .await
+            .unwrap();
+        assert_eq!(found_ids(res.events), vec![meeting.id.clone()]);
+
+        let highlights = ctx
+            .repos
+            .events
+            .find_text_highlights(
+                &[meeting.id.clone(), lunch.id.clone()],
+                &TextSearchQuery {
+                    text: "room meeting".into(),
+                    language: SearchLanguage::English,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            highlights,
+            vec![CalendarEventHighlight {
+                event_id: meeting.id.clone(),
+                title: Some("Weekly <b>meetings</b> of the team".into()),
+                description: None,
+                location: Some("<b>Room</b> 12".into()),
+            }]
+        );
+
+        // The texts are escaped, only the matches are marked up
+        let mut escaped = meeting.clone();
+        escaped.title = Some("Meetings & \"talks\"".into());
+        ctx.repos.events.save(&escaped).await.unwrap();
+        let highlights = ctx
+            .repos
+            .events
+            .find_text_highlights(
+                std::slice::from_ref(&meeting.id),
+                &TextSearchQuery {
+                    text: "meeting".into(),
+                    language: SearchLanguage::English,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            highlights[0].title.as_deref(),
+            Some("<b>Meetings</b> &amp; &quot;talks&quot;")
+        );
+    }
+
+    async fn search_on_the_recurrence_options(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        } = setup(ctx).await;
+
+        let mut event = generate_default_event(&account.id, &calendar.id, &user.id);
+        event.recurrence = Some(RRuleOptions {
+            freq: RRuleFrequency::Weekly,
+            byhour: Some(vec![9, 14]),
+            rdates: Some(vec![event.start_time + TimeDelta::days(3)]),
+            ..Default::default()
+        });
+        ctx.repos.events.insert(&event).await.unwrap();
+        let found = ctx.repos.events.find(&event.id).await.unwrap().unwrap();
+        assert_eq!(found.recurrence, event.recurrence);
+
+        let search = |options: serde_json::Value| SearchEventsForAccountParams {
+            account_id: account.id.clone(),
+            search_events_params: SearchEventsParams {
+                event_uid: None,
+                user_uid: None,
+                external_id: None,
+                external_parent_id: None,
+                start_time: None,
+                end_time: None,
+                status: None,
+                event_type: None,
+                recurring_event_uid: None,
+                original_start_time: None,
+                recurrence: Some(RecurrenceQuery::Matches(options)),
+                metadata: None,
+                created_at: None,
+                updated_at: None,
+                attendee: None,
+                text: None,
+            },
+            sort: None,
+            cursor: None,
+            limit: None,
+        };
+        for (options, matches) in [
+            (serde_json::json!({ "freq": "weekly" }), true),
+            (
+                serde_json::json!({ "freq": "weekly", "byhour": [14] }),
+                true,
+            ),
+            (serde_json::json!({ "byhour": [10] }), false),
+            (serde_json::json!({ "freq": "daily" }), false),
+        ] {
+            let res = ctx
+                .repos
+                .events
+                .search_events_for_account(search(options))
+                .await
+                .unwrap();
+            assert_eq!(res.events.len(), usize::from(matches));
+        }
+    }
+
+    async fn search_with_cursors(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        } = setup(ctx).await;
+
+        // Events with the same start time are ordered by their ids
+        let mut events = (0..5)
+            .map(|i| {
+                let mut event = generate_default_event(&account.id, &calendar.id, &user.id);
+                event.title = Some(format!("Meeting {}", "team ".repeat(i % 3 + 1)));
+                event.start_time += TimeDelta::hours((i / 2) as i64);
+                event
+            })
+            .collect::<Vec<_>>();
+        ctx.repos.events.insert_many(&events).await.unwrap();
+
+        let search = |sort: CalendarEventSort, text: Option<&str>| SearchEventsForAccountParams {
+            account_id: account.id.clone(),
+            search_events_params: SearchEventsParams {
+                event_uid: None,
+                user_uid: None,
+                external_id: None,
+                external_parent_id: None,
+                start_time: None,
+                end_time: None,
+                status: None,
+                event_type: None,
+                recurring_event_uid: None,
+                original_start_time: None,
+                recurrence: None,
+                metadata: None,
+                created_at: None,
+                updated_at: None,
+                attendee: None,
+                text: text.map(|text| TextSearchQuery {
+                    text: text.into(),
+                    language: SearchLanguage::Simple,
+                }),
+            },
+            sort: Some(sort),
+            cursor: None,
+            limit: Some(2),
+        };
+        let search_all_pages = async |params: SearchEventsForAccountParams| {
+            let mut pages = Vec::new();
+            let mut cursor = None;
+            loop {
+                let res = ctx
+                    .repos
+                    .events
+                    .search_events_for_account(SearchEventsForAccountParams {
+                        cursor,
+                        ..params.clone()
+                    })
+                    .await
+                    .unwrap();
+                pages.push(res.events.into_iter().map(|e| e.id).collect::<Vec<_>>());
+                match res.next_cursor {
+                    Some(next_cursor) => cursor = Some(next_cursor),
+                    None => return pages,
+                }
+            }
+        };
+
+        events.sort_by_key(|e| {
+            (
+                std::cmp::Reverse(e.start_time),
+                std::cmp::Reverse(*e.id.as_ref()),
+            )
+        });
+        let pages = search_all_pages(search(CalendarEventSort::StartTimeDesc, None)).await;
+        assert_eq!(
+            pages.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(
+            pages.concat(),
+            events.iter().map(|e| e.id.clone()).collect::<Vec<_>>()
+        );
+
+        // Sorted by relevance, the events with the same rank are ordered by their ids as well
+        let pages = search_all_pages(search(CalendarEventSort::RelevanceDesc, Some("team"))).await;
+        let found = pages.concat();
+        assert_eq!(found.len(), 5);
+        assert_eq!(
+            found.iter().collect::<std::collections::HashSet<_>>().len(),
+            5
+        );
+        let rank = |id: &ID| {
+            events
+                .iter()
+                .find(|e| &e.id == id)
+                .and_then(|e| e.title.as_ref())
+                .map(|title| title.matches("team").count())
+        };
+        assert!(found.windows(2).all(|ids| rank(&ids[0]) >= rank(&ids[1])));
+    }
+
+    async fn delete_by_user(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        } = setup(ctx).await;
         let event = generate_default_event(&account.id, &calendar.id, &user.id);
 
         // Insert
@@ -273,14 +1202,13 @@ mod tests {
         assert!(ctx.repos.events.find(&event.id).await.unwrap().is_none());
     }
 
-    #[tokio::test]
-    async fn delete_by_calendar() {
+    async fn delete_by_calendar(ctx: NitteiContext) {
         let TestContext {
             ctx,
             account,
             calendar,
             user,
-        } = setup().await;
+        } = setup(ctx).await;
         let event = generate_default_event(&account.id, &calendar.id, &user.id);
 
         // Insert
@@ -335,14 +1263,13 @@ mod tests {
         event
     }
 
-    #[tokio::test]
-    async fn find_by_calendar_and_timespan() {
+    async fn find_by_calendar_and_timespan(ctx: NitteiContext) {
         let TestContext {
             ctx,
             account,
             calendar,
             user,
-        } = setup().await;
+        } = setup(ctx).await;
         let start_ts = 100;
         let end_ts = 200;
         // All the possible combination of intervals
@@ -538,9 +1465,7 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn find_most_recently_created_service_events() {
-        let ctx = setup_context().await.unwrap();
+    async fn find_most_recently_created_service_events(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
 
@@ -660,9 +1585,7 @@ mod tests {
         assert_eq!(user3_recent_service_events.created, None);
     }
 
-    #[tokio::test]
-    async fn find_by_service_and_timespan() {
-        let ctx = setup_context().await.unwrap();
+    async fn find_by_service_and_timespan(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
 
@@ -868,14 +1791,13 @@ mod tests {
         assert_eq!(events_in_service_with_no_users.len(), 0);
     }
 
-    #[tokio::test]
-    async fn test_find_user_service_events() {
+    async fn test_find_user_service_events(ctx: NitteiContext) {
         let TestContext {
             ctx,
             account,
             calendar,
             user,
-        } = setup().await;
+        } = setup(ctx).await;
         let start_ts = 10;
         let end_ts = 20;
 
@@ -922,4 +1844,346 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert_eq!(res[0].id, service_event.id);
     }
+
+    async fn insert_service_booking(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        } = setup(ctx).await;
+        let service = Service::new(account.id.clone());
+        ctx.repos.services.insert(&service).await.unwrap();
+
+        let timestamp = DateTime::from_timestamp_millis(1000 * 60 * 60).unwrap();
+        let booking_event = || CalendarEvent {
+            service_id: Some(service.id.clone()),
+            busy: true,
+            start_time: timestamp,
+            duration: 1000 * 60 * 30,
+            end_time: timestamp + TimeDelta::minutes(30),
+            ..generate_default_event(&account.id, &calendar.id, &user.id)
+        };
+        let booking = |max_reservations: Option<usize>| super::ServiceBooking {
+            service_id: service.id.clone(),
+            timestamp,
+            hosts: vec![super::ServiceBookingHost {
+                user_id: user.id.clone(),
+                busy_start: timestamp - TimeDelta::minutes(10),
+                busy_end: timestamp + TimeDelta::minutes(30),
+                limits: Vec::new(),
+            }],
+            events: vec![booking_event()],
+            max_reservations,
+            hold_id: None,
+            now: Utc::now(),
+            cancelled_booking: None,
+        };
+
+        // The slot of a group service is only booked for the hosts once it is full
+        let group_booking = booking(Some(2));
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&group_booking, &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            res,
+            super::ServiceBookingOutcome::Booked {
+                events_created: false
+            }
+        );
+        assert!(
+            ctx.repos
+                .events
+                .find(&group_booking.events[0].id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let outbox = OutboxMessage::new(
+            account.id.clone(),
+            OutboxTask::CreateEventReminders {
+                event_id: booking_event().id,
+            },
+            Utc::now(),
+        );
+        let group_booking = booking(Some(2));
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&group_booking, std::slice::from_ref(&outbox))
+            .await
+            .unwrap();
+        assert_eq!(
+            res,
+            super::ServiceBookingOutcome::Booked {
+                events_created: true
+            }
+        );
+        assert!(
+            ctx.repos
+                .events
+                .find(&group_booking.events[0].id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .count(&service.id, timestamp)
+                .await
+                .unwrap(),
+            2
+        );
+
+        // The slot is full
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&booking(Some(2)), &[])
+            .await
+            .unwrap();
+        assert_eq!(res, super::ServiceBookingOutcome::Conflict);
+
+        // The host is busy with the events of the group (buffers included)
+        let other_booking = super::ServiceBooking {
+            timestamp: timestamp + TimeDelta::minutes(35),
+            hosts: vec![super::ServiceBookingHost {
+                user_id: user.id.clone(),
+                busy_start: timestamp + TimeDelta::minutes(25),
+                busy_end: timestamp + TimeDelta::minutes(65),
+                limits: Vec::new(),
+            }],
+            ..booking(None)
+        };
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&other_booking, &[])
+            .await
+            .unwrap();
+        assert_eq!(res, super::ServiceBookingOutcome::Conflict);
+        assert!(
+            ctx.repos
+                .events
+                .find(&other_booking.events[0].id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // Nothing has been written
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .count(&service.id, other_booking.timestamp)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .count(&service.id, timestamp)
+                .await
+                .unwrap(),
+            2
+        );
+
+        // The host has reached its maximum number of bookings in the day
+        let later_booking = |max: i64| super::ServiceBooking {
+            timestamp: timestamp + TimeDelta::hours(3),
+            hosts: vec![super::ServiceBookingHost {
+                user_id: user.id.clone(),
+                busy_start: timestamp + TimeDelta::hours(3),
+                busy_end: timestamp + TimeDelta::hours(3) + TimeDelta::minutes(30),
+                limits: vec![BookingPeriod {
+                    start_time: DateTime::from_timestamp_millis(0).unwrap(),
+                    end_time: DateTime::from_timestamp_millis(0).unwrap() + TimeDelta::days(1),
+                    max,
+                }],
+            }],
+            ..booking(None)
+        };
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&later_booking(1), &[])
+            .await
+            .unwrap();
+        assert_eq!(res, super::ServiceBookingOutcome::Conflict);
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&later_booking(2), &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            res,
+            super::ServiceBookingOutcome::Booked {
+                events_created: true
+            }
+        );
+    }
+
+    async fn cancel_service_booking(ctx: NitteiContext) {
+        let TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        } = setup(ctx).await;
+        let service = Service::new(account.id.clone());
+        ctx.repos.services.insert(&service).await.unwrap();
+
+        let timestamp = DateTime::from_timestamp_millis(1000 * 60 * 60).unwrap();
+        let booking = |timestamp: DateTime<Utc>| super::ServiceBooking {
+            service_id: service.id.clone(),
+            timestamp,
+            hosts: vec![super::ServiceBookingHost {
+                user_id: user.id.clone(),
+                busy_start: timestamp,
+                busy_end: timestamp + TimeDelta::minutes(30),
+                limits: Vec::new(),
+            }],
+            events: vec![CalendarEvent {
+                service_id: Some(service.id.clone()),
+                busy: true,
+                start_time: timestamp,
+                duration: 1000 * 60 * 30,
+                end_time: timestamp + TimeDelta::minutes(30),
+                ..generate_default_event(&account.id, &calendar.id, &user.id)
+            }],
+            max_reservations: None,
+            hold_id: None,
+            now: Utc::now(),
+            cancelled_booking: None,
+        };
+        let cancellation = |booking: &super::ServiceBooking| super::ServiceBookingCancellation {
+            service_id: service.id.clone(),
+            timestamp: booking.timestamp,
+            events: booking
+                .events
+                .iter()
+                .map(|e| CalendarEvent {
+                    status: CalendarEventStatus::Cancelled,
+                    version: e.version + 1,
+                    ..e.clone()
+                })
+                .collect(),
+        };
+
+        let first_booking = booking(timestamp);
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&first_booking, &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            res,
+            super::ServiceBookingOutcome::Booked {
+                events_created: true
+            }
+        );
+
+        // Rescheduling to an overlapping slot, the host is no longer busy with the cancelled booking
+        let rescheduled_booking = super::ServiceBooking {
+            cancelled_booking: Some(cancellation(&first_booking)),
+            ..booking(timestamp + TimeDelta::minutes(15))
+        };
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&rescheduled_booking, &[])
+            .await
+            .unwrap();
+        assert_eq!(
+            res,
+            super::ServiceBookingOutcome::Booked {
+                events_created: true
+            }
+        );
+        let cancelled_event = ctx
+            .repos
+            .events
+            .find(&first_booking.events[0].id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cancelled_event.status, CalendarEventStatus::Cancelled);
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .count(&service.id, timestamp)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .count(&service.id, rescheduled_booking.timestamp)
+                .await
+                .unwrap(),
+            1
+        );
+
+        // The booking has already been rescheduled
+        let other_booking = super::ServiceBooking {
+            cancelled_booking: Some(cancellation(&first_booking)),
+            ..booking(timestamp + TimeDelta::minutes(60))
+        };
+        let res = ctx
+            .repos
+            .events
+            .insert_service_booking(&other_booking, &[])
+            .await
+            .unwrap();
+        assert_eq!(res, super::ServiceBookingOutcome::Conflict);
+        assert!(
+            ctx.repos
+                .events
+                .find(&other_booking.events[0].id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        // Cancelling the booking frees its reservation, only once
+        let outbox = OutboxMessage::new(
+            account.id.clone(),
+            OutboxTask::SyncEventReminders {
+                event_id: rescheduled_booking.events[0].id.clone(),
+            },
+            Utc::now(),
+        );
+        let cancelled = ctx
+            .repos
+            .events
+            .cancel_service_booking(
+                &cancellation(&rescheduled_booking),
+                std::slice::from_ref(&outbox),
+            )
+            .await
+            .unwrap();
+        assert!(cancelled);
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .count(&service.id, rescheduled_booking.timestamp)
+                .await
+                .unwrap(),
+            0
+        );
+        let cancelled = ctx
+            .repos
+            .events
+            .cancel_service_booking(&cancellation(&rescheduled_booking), &[])
+            .await
+            .unwrap();
+        assert!(!cancelled);
+    }
 }
@@ -6,20 +6,33 @@ use std::{
 use anyhow::Context;
 use chrono::{DateTime, Utc};
 use nittei_domain::{
+    AttendeeQuery,
     CalendarEvent,
+    CalendarEventAttendee,
+    CalendarEventAttendeeResponseStatus,
+    CalendarEventCursor,
+    CalendarEventCursorKey,
+    CalendarEventHighlight,
     CalendarEventReminder,
+    CalendarEventSort,
     CalendarEventStatus,
     ID,
+    OutboxMessage,
     RRuleOptions,
     RecurrenceQuery,
     TimeSpan,
 };
+use serde::Deserialize;
 use serde_json::Value;
 use sqlx::{
     FromRow,
+    PgConnection,
+    PgExecutor,
     PgPool,
+    Postgres,
     QueryBuilder,
     Row,
+    postgres::PgRow,
     types::{Json, Uuid},
 };
 use tracing::{error, instrument};
@@ -29,11 +42,20 @@ use super::{
     MostRecentCreatedServiceEvents,
     SearchEventsForAccountParams,
     SearchEventsForUserParams,
+    SearchEventsResult,
+    ServiceBooking,
+    ServiceBookingCancellation,
+    ServiceBookingOutcome,
+    TextSearchQuery,
+    text_search::{HIGHLIGHT_START, HIGHLIGHT_STOP, highlighted_html},
 };
 use crate::repos::{
     apply_datetime_query,
     apply_id_query,
+    apply_id_query_with_alias,
     apply_string_query,
+    apply_string_query_with_alias,
+    outbox::insert_outbox_messages,
     shared::query_structs::MetadataFindQuery,
 };
 
@@ -101,7 +123,33 @@ struct EventRaw {
     original_start_time: Option<DateTime<Utc>>,
     reminders_jsonb: Option<Value>,
     service_uid: Option<Uuid>,
+    group_uid: Option<Uuid>,
     metadata: Value,
+    timezone: Option<String>,
+    version: i64,
+    attendees: Option<Value>,
+}
+
+/// Attendee as aggregated (JSONB) by the queries selecting calendar events
+#[derive(Debug, Deserialize)]
+struct EventAttendeeRaw {
+    user_uid: Option<Uuid>,
+    email: Option<String>,
+    role: String,
+    response_status: String,
+}
+
+impl TryFrom<EventAttendeeRaw> for CalendarEventAttendee {
+    type Error = anyhow::Error;
+
+    fn try_from(a: EventAttendeeRaw) -> anyhow::Result<Self> {
+        Ok(Self {
+            user_id: a.user_uid.map(|id| id.into()),
+            email: a.email,
+            role: a.role.try_into()?,
+            response_status: a.response_status.try_into()?,
+        })
+    }
 }
 
 impl<'a> FromRow<'a, sqlx::postgres::PgRow> for EventRaw {
@@ -134,7 +182,11 @@ impl<'a> FromRow<'a, sqlx::postgres::PgRow> for EventRaw {
             original_start_time: row.try_get("original_start_time")?,
             reminders_jsonb: row.try_get("reminders_jsonb")?,
             service_uid: row.try_get("service_uid")?,
+            group_uid: row.try_get("group_uid")?,
             metadata: row.try_get("metadata")?,
+            timezone: row.try_get("timezone")?,
+            version: row.try_get("version")?,
+            attendees: row.try_get("attendees")?,
         })
     }
 }
@@ -155,6 +207,14 @@ impl TryFrom<EventRaw> for CalendarEvent {
             }
             None => Vec::new(),
         };
+        let attendees: Vec<CalendarEventAttendee> = match e.attendees {
+            Some(json) => serde_json::from_value::<Vec<EventAttendeeRaw>>(json)
+                .context("Unable to convert attendees from JSON")?
+                .into_iter()
+                .map(|a| a.try_into())
+                .collect::<anyhow::Result<_>>()?,
+            None => Vec::new(),
+        };
 
         Ok(Self {
             id: e.event_uid.into(),
@@ -185,23 +245,535 @@ impl TryFrom<EventRaw> for CalendarEvent {
             recurring_event_id: e.recurring_event_uid.map(|id| id.into()),
             original_start_time: e.original_start_time,
             reminders,
+            attendees,
             service_id: e.service_uid.map(|id| id.into()),
+            group_id: e.group_uid.map(|id| id.into()),
             metadata: serde_json::from_value(e.metadata)
                 .context("Unable to convert metadata to JSON")?,
+            timezone: e
+                .timezone
+                .map(|tz| tz.parse())
+                .transpose()
+                .map_err(|e| anyhow::anyhow!("Unable to parse the timezone: {e}"))?,
+            version: e.version,
+        })
+    }
+}
+
+/// Filter the events on their attendees
+fn apply_attendee_query(query: &mut QueryBuilder<Postgres>, attendee: &Option<AttendeeQuery>) {
+    if let Some(attendee) = attendee {
+        query.push(
+            " AND e.event_uid IN (SELECT a.event_uid FROM calendar_event_attendees AS a WHERE TRUE",
+        );
+        apply_id_query_with_alias(query, "a", "user_uid", &attendee.user_id);
+        apply_string_query_with_alias(query, "a", "email", &attendee.email);
+        apply_string_query_with_alias(query, "a", "response_status", &attendee.response_status);
+        query.push(")");
+    }
+}
+
+/// Filter the events on the text searched in their title, description and location
+fn apply_text_query(query: &mut QueryBuilder<Postgres>, text: &Option<TextSearchQuery>) {
+    if let Some(text) = text {
+        query.push(" AND e.search_vector @@ ");
+        push_tsquery(query, text);
+    }
+}
+
+/// Push the query of the text searched (in the web search syntax)
+fn push_tsquery(query: &mut QueryBuilder<Postgres>, text: &TextSearchQuery) {
+    query.push("websearch_to_tsquery(");
+    query.push_bind(text.language.config_name());
+    query.push("::regconfig, ");
+    query.push_bind(text.text.clone());
+    query.push(")");
+}
+
+/// Key on which the events are sorted, before their ids (which order the events with the same key)
+enum SortKey<'a> {
+    /// Column of the events
+    Column(&'static str),
+    /// Rank of the events for the text searched (the matches in the title first)
+    Rank(&'a TextSearchQuery),
+    /// The events are only sorted by their ids
+    Id,
+}
+
+/// Value of the sort key of the event of a cursor, as saved
+enum SortKeyValue {
+    Time(DateTime<Utc>),
+    Millis(i64),
+    Rank(f32),
+}
+
+/// Key of the sort, and whether the order is descending
+fn sort_key<'a>(
+    sort: &CalendarEventSort,
+    text: &'a Option<TextSearchQuery>,
+) -> (SortKey<'a>, bool) {
+    match sort {
+        CalendarEventSort::StartTimeAsc => (SortKey::Column("start_time"), false),
+        CalendarEventSort::StartTimeDesc => (SortKey::Column("start_time"), true),
+        CalendarEventSort::EndTimeAsc => (SortKey::Column("end_time"), false),
+        CalendarEventSort::EndTimeDesc => (SortKey::Column("end_time"), true),
+        CalendarEventSort::CreatedAsc => (SortKey::Column("created"), false),
+        CalendarEventSort::CreatedDesc => (SortKey::Column("created"), true),
+        CalendarEventSort::UpdatedAsc => (SortKey::Column("updated"), false),
+        CalendarEventSort::UpdatedDesc => (SortKey::Column("updated"), true),
+        CalendarEventSort::EventUidAsc => (SortKey::Id, false),
+        CalendarEventSort::EventUidDesc => (SortKey::Id, true),
+        CalendarEventSort::RelevanceDesc => match text {
+            Some(text) => (SortKey::Rank(text), true),
+            // Without a text searched, there is no relevance
+            None => (SortKey::Column("start_time"), false),
+        },
+    }
+}
+
+fn push_sort_key(query: &mut QueryBuilder<Postgres>, key: &SortKey) {
+    match key {
+        SortKey::Column(column) => {
+            query.push(format!("e.{column}"));
+        }
+        SortKey::Rank(text) => {
+            query.push("ts_rank(e.search_vector, ");
+            push_tsquery(query, text);
+            query.push(")");
+        }
+        SortKey::Id => {}
+    }
+}
+
+/// Select the rank of the events for the text searched, when they are sorted by relevance (for their cursors)
+fn push_rank_column(
+    query: &mut QueryBuilder<Postgres>,
+    sort: &CalendarEventSort,
+    text: &Option<TextSearchQuery>,
+) {
+    match sort_key(sort, text) {
+        (key @ SortKey::Rank(_), _) => push_sort_key(query, &key),
+        _ => {
+            query.push("NULL::real");
+        }
+    }
+    query.push(" AS rank");
+}
+
+/// Filter the events after the cursor, in the order of the sort
+fn apply_cursor(
+    query: &mut QueryBuilder<Postgres>,
+    sort: &CalendarEventSort,
+    text: &Option<TextSearchQuery>,
+    cursor: &Option<CalendarEventCursor>,
+) -> anyhow::Result<()> {
+    let Some(cursor) = cursor else {
+        return Ok(());
+    };
+
+    let (key, descending) = sort_key(sort, text);
+    let operator = if descending { " < " } else { " > " };
+    let value = match (&key, &cursor.key) {
+        (SortKey::Id, CalendarEventCursorKey::None) => {
+            query.push(format!(" AND e.event_uid{operator}"));
+            query.push_bind::<Uuid>(cursor.id.clone().into());
+            return Ok(());
+        }
+        (SortKey::Column("created" | "updated"), CalendarEventCursorKey::Time(time)) => {
+            SortKeyValue::Millis(time.timestamp_millis())
+        }
+        (SortKey::Column(_), CalendarEventCursorKey::Time(time)) => SortKeyValue::Time(*time),
+        (SortKey::Rank(_), CalendarEventCursorKey::Rank(rank)) => SortKeyValue::Rank(*rank),
+        _ => anyhow::bail!("The cursor doesn't match the sort"),
+    };
+
+    query.push(" AND (");
+    push_sort_key(query, &key);
+    query.push(format!(", e.event_uid){operator}("));
+    match value {
+        SortKeyValue::Time(time) => query.push_bind(time),
+        SortKeyValue::Millis(millis) => query.push_bind(millis),
+        SortKeyValue::Rank(rank) => query.push_bind(rank),
+    };
+    query.push(", ");
+    query.push_bind::<Uuid>(cursor.id.clone().into());
+    query.push(")");
+    Ok(())
+}
+
+/// Sort the events on the key of the sort, then on their ids
+fn apply_sort(
+    query: &mut QueryBuilder<Postgres>,
+    sort: &CalendarEventSort,
+    text: &Option<TextSearchQuery>,
+) {
+    let (key, descending) = sort_key(sort, text);
+    let direction = if descending { "DESC" } else { "ASC" };
+    query.push(" ORDER BY ");
+    if !matches!(key, SortKey::Id) {
+        push_sort_key(query, &key);
+        query.push(format!(" {direction}, "));
+    }
+    query.push(format!("e.event_uid {direction}"));
+}
+
+/// Fetch one more event than the limit, to know if there is a next page
+fn apply_limit(query: &mut QueryBuilder<Postgres>, limit: Option<u16>) {
+    if let Some(limit) = limit {
+        query.push(" LIMIT ");
+        query.push(format!("{}", u32::from(limit) + 1));
+    }
+}
+
+/// Page of the events found, with the cursor after the last one when more events were found than the limit
+fn search_result(
+    mut rows: Vec<PgRow>,
+    sort: CalendarEventSort,
+    limit: Option<u16>,
+) -> anyhow::Result<SearchEventsResult> {
+    let has_next_page = limit.is_some_and(|limit| rows.len() > usize::from(limit));
+    if let Some(limit) = limit {
+        rows.truncate(usize::from(limit));
+    }
+    let rank: Option<f32> = match rows.last() {
+        Some(row) if has_next_page => row.try_get("rank")?,
+        _ => None,
+    };
+
+    let events = rows
+        .iter()
+        .map(EventRaw::from_row)
+        .collect::<Result<Vec<_>, sqlx::Error>>()?
+        .into_iter()
+        .map(CalendarEvent::try_from)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let next_cursor = match events.last() {
+        Some(event) if has_next_page => Some(CalendarEventCursor::after(event, sort, rank)),
+        _ => None,
+    };
+
+    Ok(SearchEventsResult {
+        events,
+        next_cursor,
+    })
+}
+
+/// Insert the attendees of a calendar event using the given executor
+/// The order of the attendees is kept through the `position` column
+async fn insert_attendees<'c, E>(
+    executor: E,
+    event_id: &ID,
+    attendees: &[CalendarEventAttendee],
+) -> anyhow::Result<()>
+where
+    E: PgExecutor<'c>,
+{
+    if attendees.is_empty() {
+        return Ok(());
+    }
+
+    let user_uids = attendees
+        .iter()
+        .map(|a| a.user_id.as_ref().map(|id| *id.as_ref()))
+        .collect::<Vec<Option<Uuid>>>();
+    let emails = attendees
+        .iter()
+        .map(|a| a.email.clone())
+        .collect::<Vec<Option<String>>>();
+    let roles = attendees
+        .iter()
+        .map(|a| a.role.clone().into())
+        .collect::<Vec<String>>();
+    let response_statuses = attendees
+        .iter()
+        .map(|a| a.response_status.clone().into())
+        .collect::<Vec<String>>();
+    let positions = (0..attendees.len() as i32).collect::<Vec<i32>>();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO calendar_event_attendees(event_uid, user_uid, email, role, response_status, position)
+        SELECT $1, * FROM UNNEST($2::uuid[], $3::text[], $4::text[], $5::text[], $6::int[])
+        "#,
+        event_id.as_ref(),
+        &user_uids as _,
+        &emails as _,
+        &roles,
+        &response_statuses,
+        &positions,
+    )
+    .execute(executor)
+    .await
+    .inspect_err(|err| {
+        error!(
+            event_id = %event_id,
+            error = ?err,
+            "Failed to insert calendar_event_attendees"
+        );
+    })?;
+
+    Ok(())
+}
+
+/// Replace the attendees of a calendar event using the given connection
+/// This allows to share the queries between `set_attendees` and `save_with_outbox`
+async fn replace_attendees(
+    conn: &mut PgConnection,
+    event_id: &ID,
+    attendees: &[CalendarEventAttendee],
+) -> anyhow::Result<()> {
+    sqlx::query!(
+        r#"
+        DELETE FROM calendar_event_attendees AS a
+        WHERE a.event_uid = $1
+        "#,
+        event_id.as_ref(),
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|err| {
+        error!(
+            event_id = %event_id,
+            error = ?err,
+            "Failed to delete calendar_event_attendees"
+        );
+    })?;
+
+    insert_attendees(&mut *conn, event_id, attendees).await
+}
+
+/// Insert calendar events, including their attendees, in a single query using the given connection
+/// This allows to share the query between `insert_many_with_outbox` and `insert_and_save_many_with_outbox`
+async fn insert_events(conn: &mut PgConnection, events: &[CalendarEvent]) -> anyhow::Result<()> {
+    // An INSERT without values is invalid
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let mut query_builder = QueryBuilder::new(
+        "INSERT INTO calendar_events (event_uid, account_uid, user_uid, calendar_uid, external_parent_id, external_id, title, description, event_type, location, status, all_day, start_time, duration, end_time, busy, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone) ",
+    );
+
+    // Collect the recurrence for each event beforehand
+    // This allows to return an error if one of the recurrence is invalid
+    let map_of_events_to_recurrence = events
+        .iter()
+        .map(|e| {
+            let recurrence = if e.recurrence.is_some() {
+                Some(serde_json::to_value(&e.recurrence).map_err(|e| {
+                    anyhow::anyhow!("Unable to convert recurrence to JSON: {:?}", e)
+                })?)
+            } else {
+                None
+            };
+            Ok((e.id.clone(), recurrence))
         })
+        .collect::<Result<HashMap<_, _>, anyhow::Error>>()?;
+
+    // Build the query
+    query_builder.push_values(events, |mut b, new_event| {
+        let status: String = new_event.status.clone().into();
+        let recurrence = map_of_events_to_recurrence
+            .get(&new_event.id)
+            .unwrap_or(&None);
+
+        b.push_bind(new_event.id.as_ref())
+            .push_bind(new_event.account_id.as_ref())
+            .push_bind(new_event.user_id.as_ref())
+            .push_bind(new_event.calendar_id.as_ref())
+            .push_bind(new_event.external_parent_id.clone())
+            .push_bind(new_event.external_id.clone())
+            .push_bind(new_event.title.clone())
+            .push_bind(new_event.description.clone())
+            .push_bind(new_event.event_type.clone())
+            .push_bind(new_event.location.clone())
+            .push_bind(status)
+            .push_bind(new_event.all_day)
+            .push_bind(new_event.start_time)
+            .push_bind(new_event.duration)
+            .push_bind(new_event.end_time)
+            .push_bind(new_event.busy)
+            .push_bind(new_event.created.timestamp_millis())
+            .push_bind(new_event.updated.timestamp_millis())
+            // (recurrence_jsonb) JSONB field
+            .push_bind(recurrence)
+            .push_bind(new_event.recurring_until)
+            .push_bind(&new_event.exdates)
+            .push_bind(new_event.recurring_event_id.as_ref().map(|id| id.as_ref()))
+            .push_bind(new_event.original_start_time)
+            // (reminders_jsonb) JSONB field
+            .push_bind(Json(&new_event.reminders))
+            .push_bind(new_event.service_id.as_ref().map(|id| id.as_ref()))
+            .push_bind(new_event.group_id.as_ref().map(|id| id.as_ref()))
+            .push_bind(Json(&new_event.metadata))
+            .push_bind(new_event.timezone.map(|tz| tz.name()));
+    });
+
+    let query = query_builder.build();
+
+    query.execute(&mut *conn).await.inspect_err(|err| {
+        error!(
+            error = ?err,
+            "Failed to insert calendar_events"
+        );
+    })?;
+
+    for event in events {
+        insert_attendees(&mut *conn, &event.id, &event.attendees).await?;
+    }
+
+    Ok(())
+}
+
+/// Save a calendar event and increment its version, using the given executor (pool or transaction)
+/// When `expected_version` is provided, the event is only saved if its current version matches
+/// Returns false if no row has been updated
+async fn save_event<'c, E>(
+    executor: E,
+    e: &CalendarEvent,
+    expected_version: Option<i64>,
+) -> anyhow::Result<bool>
+where
+    E: PgExecutor<'c>,
+{
+    let status: String = e.status.clone().into();
+    let recurrence = if e.recurrence.is_some() {
+        Some(serde_json::to_value(&e.recurrence)?)
+    } else {
+        None
+    };
+    let res = sqlx::query!(
+        r#"
+        UPDATE calendar_events SET
+            external_parent_id = $2,
+            external_id = $3,
+            title = $4,
+            description = $5,
+            event_type = $6,
+            location = $7,
+            status = $8,
+            all_day = $9,
+            start_time = $10,
+            duration = $11,
+            end_time = $12,
+            busy = $13,
+            created = $14,
+            updated = $15,
+            recurrence_jsonb = $16,
+            recurring_until = $17,
+            exdates = $18,
+            recurring_event_uid = $19,
+            original_start_time = $20,
+            reminders_jsonb = $21,
+            service_uid = $22,
+            group_uid = $23,
+            metadata = $24,
+            timezone = $25,
+            version = version + 1
+        WHERE event_uid = $1 AND ($26::bigint IS NULL OR version = $26)
+        "#,
+        e.id.as_ref(),
+        e.external_parent_id,
+        e.external_id,
+        e.title,
+        e.description,
+        e.event_type,
+        e.location,
+        status,
+        e.all_day,
+        e.start_time,
+        e.duration,
+        e.end_time,
+        e.busy,
+        e.created.timestamp_millis(),
+        e.updated.timestamp_millis(),
+        &recurrence as _,
+        e.recurring_until,
+        &e.exdates,
+        e.recurring_event_id.as_ref().map(|id| id.as_ref()),
+        e.original_start_time,
+        Json(&e.reminders) as _,
+        e.service_id.as_ref().map(|id| id.as_ref()),
+        e.group_id.as_ref().map(|id| id.as_ref()),
+        Json(&e.metadata) as _,
+        e.timezone.map(|tz| tz.name()),
+        expected_version,
+    )
+    .execute(executor)
+    .await
+    .inspect_err(|err| {
+        error!(
+            event = ?e,
+            error = ?err,
+            "Failed to save calendar_event"
+        );
+    })?;
+
+    Ok(res.rows_affected() > 0)
+}
+
+/// Save the cancelled events of a service booking and decrement the reservations of its slot using the given connection
+/// This allows to share the queries between `cancel_service_booking` and `insert_service_booking` (rescheduling)
+/// Returns false if one of the events has been modified in the meantime
+async fn cancel_booking(
+    conn: &mut PgConnection,
+    cancellation: &ServiceBookingCancellation,
+) -> anyhow::Result<bool> {
+    for e in &cancellation.events {
+        if !save_event(&mut *conn, e, Some(e.version - 1)).await? {
+            return Ok(false);
+        }
     }
+
+    sqlx::query!(
+        r#"
+        UPDATE service_reservations AS r
+        SET count = r.count - 1
+        WHERE r.service_uid = $1 AND r.timestamp = $2 AND r.count > 0
+        "#,
+        cancellation.service_id.as_ref(),
+        cancellation.timestamp,
+    )
+    .execute(&mut *conn)
+    .await
+    .inspect_err(|err| {
+        error!(
+            error = ?err,
+            "Failed to decrement the reservations of the cancelled service booking"
+        );
+    })?;
+
+    Ok(true)
 }
 
 #[async_trait::async_trait]
 impl IEventRepo for PostgresEventRepo {
     #[instrument(name = "calendar_event::insert")]
     async fn insert(&self, event: &CalendarEvent) -> anyhow::Result<()> {
+        self.insert_with_outbox(event, &[]).await
+    }
+
+    #[instrument(name = "calendar_event::insert_with_outbox", skip(outbox))]
+    async fn insert_with_outbox(
+        &self,
+        event: &CalendarEvent,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
         let status: String = event.status.clone().into();
         let recurrence = if event.recurrence.is_some() {
             Some(serde_json::to_value(&event.recurrence)?)
         } else {
             None
         };
+
+        // The event, its attendees and the outbox messages are inserted in the same transaction
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for inserting calendar_event"
+            );
+        })?;
+
         sqlx::query!(
             r#"
             INSERT INTO calendar_events(
@@ -230,9 +802,11 @@ impl IEventRepo for PostgresEventRepo {
                 original_start_time,
                 reminders_jsonb,
                 service_uid,
-                metadata
+                group_uid,
+                metadata,
+                timezone
             )
-            VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26)
+            VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28)
             "#,
             event.id.as_ref(),
             event.account_id.as_ref(),
@@ -261,9 +835,11 @@ impl IEventRepo for PostgresEventRepo {
             // (reminders_jsonb) JSONB field
             Json(&event.reminders) as _,
             event.service_id.as_ref().map(|id| id.as_ref()),
+            event.group_id.as_ref().map(|id| id.as_ref()),
             Json(&event.metadata) as _,
+            event.timezone.map(|tz| tz.name()),
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .inspect_err(|err| {
             error!(
@@ -273,158 +849,518 @@ impl IEventRepo for PostgresEventRepo {
             );
         })?;
 
+        insert_attendees(&mut *tx, &event.id, &event.attendees).await?;
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for inserting calendar_event"
+            );
+        })?;
+
         Ok(())
     }
 
-    /// Insert many calendar events in a single query
     #[instrument(name = "calendar_event::insert_many", fields(events = ?events))]
     async fn insert_many(&self, events: &[CalendarEvent]) -> anyhow::Result<()> {
-        let mut query_builder = QueryBuilder::new(
-            "INSERT INTO calendar_events (event_uid, account_uid, user_uid, calendar_uid, external_parent_id, external_id, title, description, event_type, location, status, all_day, start_time, duration, end_time, busy, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata) ",
-        );
+        self.insert_many_with_outbox(events, &[]).await
+    }
 
-        // Collect the recurrence for each event beforehand
-        // This allows to return an error if one of the recurrence is invalid
-        let map_of_events_to_recurrence = events
-            .iter()
-            .map(|e| {
-                let recurrence = if e.recurrence.is_some() {
-                    Some(serde_json::to_value(&e.recurrence).map_err(|e| {
-                        anyhow::anyhow!("Unable to convert recurrence to JSON: {:?}", e)
-                    })?)
-                } else {
-                    None
-                };
-                Ok((e.id.clone(), recurrence))
-            })
-            .collect::<Result<HashMap<_, _>, anyhow::Error>>()?;
-
-        // Build the query
-        query_builder.push_values(events, |mut b, new_event| {
-            let status: String = new_event.status.clone().into();
-            let recurrence = map_of_events_to_recurrence
-                .get(&new_event.id)
-                .unwrap_or(&None);
-
-            b.push_bind(new_event.id.as_ref())
-                .push_bind(new_event.account_id.as_ref())
-                .push_bind(new_event.user_id.as_ref())
-                .push_bind(new_event.calendar_id.as_ref())
-                .push_bind(new_event.external_parent_id.clone())
-                .push_bind(new_event.external_id.clone())
-                .push_bind(new_event.title.clone())
-                .push_bind(new_event.description.clone())
-                .push_bind(new_event.event_type.clone())
-                .push_bind(new_event.location.clone())
-                .push_bind(status)
-                .push_bind(new_event.all_day)
-                .push_bind(new_event.start_time)
-                .push_bind(new_event.duration)
-                .push_bind(new_event.end_time)
-                .push_bind(new_event.busy)
-                .push_bind(new_event.created.timestamp_millis())
-                .push_bind(new_event.updated.timestamp_millis())
-                // (recurrence_jsonb) JSONB field
-                .push_bind(recurrence)
-                .push_bind(new_event.recurring_until)
-                .push_bind(&new_event.exdates)
-                .push_bind(new_event.recurring_event_id.as_ref().map(|id| id.as_ref()))
-                .push_bind(new_event.original_start_time)
-                // (reminders_jsonb) JSONB field
-                .push_bind(Json(&new_event.reminders))
-                .push_bind(new_event.service_id.as_ref().map(|id| id.as_ref()))
-                .push_bind(Json(&new_event.metadata));
-        });
-
-        let query = query_builder.build();
-
-        query.execute(&self.pool).await.inspect_err(|err| {
+    /// Insert many calendar events in a single query
+    #[instrument(name = "calendar_event::insert_many_with_outbox", fields(events = ?events), skip(outbox))]
+    async fn insert_many_with_outbox(
+        &self,
+        events: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        // The events, their attendees and the outbox messages are inserted in the same transaction
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for inserting calendar_events"
+            );
+        })?;
+
+        insert_events(&mut tx, events).await?;
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
             error!(
                 error = ?err,
-                "Failed to insert calendar_events"
+                "Failed to commit transaction for inserting calendar_events"
             );
         })?;
+
         Ok(())
     }
 
     #[instrument(name = "calendar_event::save", fields(event_uid = %e.id))]
     async fn save(&self, e: &CalendarEvent) -> anyhow::Result<()> {
-        let status: String = e.status.clone().into();
-        let recurrence = if e.recurrence.is_some() {
-            Some(serde_json::to_value(&e.recurrence)?)
-        } else {
-            None
-        };
+        save_event(&self.pool, e, None).await.map(|_| ())
+    }
+
+    #[instrument(name = "calendar_event::save_if_version", fields(event_uid = %e.id))]
+    async fn save_if_version(&self, e: &CalendarEvent, version: i64) -> anyhow::Result<bool> {
+        save_event(&self.pool, e, Some(version)).await
+    }
+
+    #[instrument(name = "calendar_event::save_with_outbox", fields(event_uid = %e.id), skip(outbox))]
+    async fn save_with_outbox(
+        &self,
+        e: &CalendarEvent,
+        expected_version: Option<i64>,
+        replace_attendees: bool,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for saving calendar_event"
+            );
+        })?;
+
+        // Nothing is recorded in the outbox if the event hasn't been saved
+        let saved = save_event(&mut *tx, e, expected_version).await?;
+        if !saved {
+            return Ok(false);
+        }
+        if replace_attendees {
+            self::replace_attendees(&mut tx, &e.id, &e.attendees).await?;
+        }
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for saving calendar_event"
+            );
+        })?;
+
+        Ok(true)
+    }
+
+    #[instrument(name = "calendar_event::save_many", fields(event_ids = ?events.iter().map(|e| &e.id).collect::<Vec<_>>()))]
+    async fn save_many(&self, events: &[CalendarEvent]) -> anyhow::Result<()> {
+        self.save_many_with_outbox(events, &[]).await
+    }
+
+    /// Save many calendar events in a single transaction
+    /// Either all the events are saved, or none of them
+    #[instrument(name = "calendar_event::save_many_with_outbox", fields(event_ids = ?events.iter().map(|e| &e.id).collect::<Vec<_>>()), skip(outbox))]
+    async fn save_many_with_outbox(
+        &self,
+        events: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for saving calendar_events"
+            );
+        })?;
+
+        for e in events {
+            save_event(&mut *tx, e, None).await?;
+        }
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for saving calendar_events"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    /// Insert the new events and save the existing ones in a single transaction
+    /// Either all the events are written, or none of them
+    #[instrument(name = "calendar_event::insert_and_save_many_with_outbox", fields(inserted = ?inserted.iter().map(|e| &e.id).collect::<Vec<_>>(), saved = ?saved.iter().map(|e| &e.id).collect::<Vec<_>>()), skip(outbox))]
+    async fn insert_and_save_many_with_outbox(
+        &self,
+        inserted: &[CalendarEvent],
+        saved: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for inserting and saving calendar_events"
+            );
+        })?;
+
+        insert_events(&mut tx, inserted).await?;
+        for e in saved {
+            save_event(&mut *tx, e, None).await?;
+        }
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for inserting and saving calendar_events"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    /// Split a recurring series in a single transaction
+    /// Either the whole split is written, or nothing
+    #[instrument(name = "calendar_event::split_series_with_outbox", fields(event_uid = %series.id, new_event_uid = %new_series.id), skip(series, new_series, moved_exceptions, outbox))]
+    async fn split_series_with_outbox(
+        &self,
+        series: &CalendarEvent,
+        expected_version: Option<i64>,
+        new_series: &CalendarEvent,
+        moved_exceptions: &[CalendarEvent],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for splitting calendar_event"
+            );
+        })?;
+
+        // Nothing is written if the series hasn't been saved
+        if !save_event(&mut *tx, series, expected_version).await? {
+            return Ok(false);
+        }
+        insert_events(&mut tx, std::slice::from_ref(new_series)).await?;
+        for exception in moved_exceptions {
+            save_event(&mut *tx, exception, None).await?;
+        }
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for splitting calendar_event"
+            );
+        })?;
+
+        Ok(true)
+    }
+
+    #[instrument(name = "calendar_event::insert_service_booking", fields(service_id = %booking.service_id, timestamp = %booking.timestamp), skip(booking, outbox))]
+    async fn insert_service_booking(
+        &self,
+        booking: &ServiceBooking,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<ServiceBookingOutcome> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for inserting service booking"
+            );
+        })?;
+
+        // Lock the hosts (in the same order for all the bookings, to avoid deadlocks),
+        // so that the concurrent bookings of the same hosts are serialized
+        // FOR NO KEY UPDATE doesn't block the foreign key checks of the other writes
+        let host_uids = booking
+            .hosts
+            .iter()
+            .map(|host| *host.user_id.as_ref())
+            .collect::<Vec<_>>();
         sqlx::query!(
             r#"
-            UPDATE calendar_events SET
-                external_parent_id = $2,
-                external_id = $3,
-                title = $4,
-                description = $5,
-                event_type = $6,
-                location = $7,
-                status = $8,
-                all_day = $9,
-                start_time = $10,
-                duration = $11,
-                end_time = $12,
-                busy = $13,
-                created = $14,
-                updated = $15,
-                recurrence_jsonb = $16,
-                recurring_until = $17,
-                exdates = $18,
-                recurring_event_uid = $19,
-                original_start_time = $20,
-                reminders_jsonb = $21,
-                service_uid = $22,
-                metadata = $23
-            WHERE event_uid = $1
+            SELECT user_uid FROM users
+            WHERE user_uid = ANY($1)
+            ORDER BY user_uid
+            FOR NO KEY UPDATE
             "#,
-            e.id.as_ref(),
-            e.external_parent_id,
-            e.external_id,
-            e.title,
-            e.description,
-            e.event_type,
-            e.location,
-            status,
-            e.all_day,
-            e.start_time,
-            e.duration,
-            e.end_time,
-            e.busy,
-            e.created.timestamp_millis(),
-            e.updated.timestamp_millis(),
-            &recurrence as _,
-            e.recurring_until,
-            &e.exdates,
-            e.recurring_event_id.as_ref().map(|id| id.as_ref()),
-            e.original_start_time,
-            Json(&e.reminders) as _,
-            e.service_id.as_ref().map(|id| id.as_ref()),
-            Json(&e.metadata) as _,
+            &host_uids,
         )
-        .execute(&self.pool)
+        .fetch_all(&mut *tx)
         .await
         .inspect_err(|err| {
             error!(
-                event = ?e,
                 error = ?err,
-                "Failed to save calendar_event"
+                "Failed to lock the hosts of the service booking"
+            );
+        })?;
+
+        if let Some(hold_id) = &booking.hold_id {
+            let released = sqlx::query!(
+                r#"
+                DELETE FROM service_holds AS h
+                WHERE h.hold_uid = $1 AND h.expires_at > $2
+                "#,
+                hold_id.as_ref(),
+                booking.now,
+            )
+            .execute(&mut *tx)
+            .await
+            .inspect_err(|err| {
+                error!(
+                    error = ?err,
+                    "Failed to release the hold of the service booking"
+                );
+            })?;
+            if released.rows_affected() == 0 {
+                tx.rollback().await?;
+                return Ok(ServiceBookingOutcome::Conflict);
+            }
+        }
+
+        if let Some(cancelled_booking) = &booking.cancelled_booking
+            && !cancel_booking(&mut tx, cancelled_booking).await?
+        {
+            tx.rollback().await?;
+            return Ok(ServiceBookingOutcome::Conflict);
+        }
+
+        // The row of the slot stays locked until the end of the transaction
+        let reservations = sqlx::query_scalar!(
+            r#"
+            INSERT INTO service_reservations(service_uid, timestamp)
+            VALUES($1, $2)
+            ON CONFLICT(service_uid, timestamp) DO UPDATE SET count = service_reservations.count + 1
+            RETURNING count
+            "#,
+            booking.service_id.as_ref(),
+            booking.timestamp,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to increment the reservations of the service booking"
+            );
+        })?;
+        let reservations = usize::try_from(reservations)?;
+
+        let create_events = match booking.max_reservations {
+            Some(max) if reservations > max => {
+                tx.rollback().await?;
+                return Ok(ServiceBookingOutcome::Conflict);
+            }
+            Some(max) => reservations == max,
+            None => true,
+        };
+
+        if create_events {
+            let busy_starts = booking
+                .hosts
+                .iter()
+                .map(|host| host.busy_start)
+                .collect::<Vec<_>>();
+            let busy_ends = booking
+                .hosts
+                .iter()
+                .map(|host| host.busy_end)
+                .collect::<Vec<_>>();
+            let hosts_busy = sqlx::query_scalar!(
+                r#"
+                WITH hosts AS (
+                    SELECT * FROM UNNEST($1::uuid[], $2::timestamptz[], $3::timestamptz[]) AS h(user_uid, busy_start, busy_end)
+                )
+                SELECT (
+                    EXISTS(
+                        SELECT 1 FROM calendar_events AS e
+                        INNER JOIN hosts AS h ON e.user_uid = h.user_uid
+                        WHERE e.service_uid IS NOT NULL AND
+                        e.status <> 'cancelled' AND
+                        e.start_time < h.busy_end AND e.end_time > h.busy_start
+                    ) OR EXISTS(
+                        SELECT 1 FROM service_holds AS sh
+                        INNER JOIN hosts AS h ON h.user_uid = ANY(sh.host_user_uids)
+                        WHERE sh.expires_at > $4 AND
+                        sh.start_time < h.busy_end AND sh.end_time > h.busy_start
+                    )
+                ) AS "busy!"
+                "#,
+                &host_uids,
+                &busy_starts,
+                &busy_ends,
+                booking.now,
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .inspect_err(|err| {
+                error!(
+                    error = ?err,
+                    "Failed to check the availability of the hosts of the service booking"
+                );
+            })?;
+            if hosts_busy {
+                tx.rollback().await?;
+                return Ok(ServiceBookingOutcome::Conflict);
+            }
+            let limit_user_uids = booking
+                .hosts
+                .iter()
+                .flat_map(|host| host.limits.iter().map(|_| *host.user_id.as_ref()))
+                .collect::<Vec<_>>();
+            let limit_starts = booking
+                .hosts
+                .iter()
+                .flat_map(|host| host.limits.iter().map(|period| period.start_time))
+                .collect::<Vec<_>>();
+            let limit_ends = booking
+                .hosts
+                .iter()
+                .flat_map(|host| host.limits.iter().map(|period| period.end_time))
+                .collect::<Vec<_>>();
+            let limit_maxes = booking
+                .hosts
+                .iter()
+                .flat_map(|host| host.limits.iter().map(|period| period.max))
+                .collect::<Vec<_>>();
+            // Recounted under the lock of the hosts, as the concurrent bookings of other slots count too
+            let limits_reached = sqlx::query_scalar!(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM UNNEST($1::uuid[], $2::timestamptz[], $3::timestamptz[], $4::bigint[]) AS p(user_uid, start_time, end_time, max)
+                    WHERE (
+                        SELECT count(*) FROM calendar_events AS e
+                        WHERE e.user_uid = p.user_uid AND
+                        e.service_uid IS NOT NULL AND
+                        e.status <> 'cancelled' AND
+                        e.start_time >= p.start_time AND e.start_time < p.end_time
+                    ) + (
+                        SELECT count(*) FROM service_holds AS sh
+                        WHERE p.user_uid = ANY(sh.host_user_uids) AND
+                        sh.expires_at > $5 AND
+                        sh.start_time >= p.start_time AND sh.start_time < p.end_time
+                    ) >= p.max
+                ) AS "reached!"
+                "#,
+                &limit_user_uids,
+                &limit_starts,
+                &limit_ends,
+                &limit_maxes,
+                booking.now,
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .inspect_err(|err| {
+                error!(
+                    error = ?err,
+                    "Failed to check the booking limits of the hosts of the service booking"
+                );
+            })?;
+            if limits_reached {
+                tx.rollback().await?;
+                return Ok(ServiceBookingOutcome::Conflict);
+            }
+
+            insert_events(&mut tx, &booking.events).await?;
+            insert_outbox_messages(&mut *tx, outbox).await?;
+        }
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for inserting service booking"
+            );
+        })?;
+
+        Ok(ServiceBookingOutcome::Booked {
+            events_created: create_events,
+        })
+    }
+
+    #[instrument(name = "calendar_event::cancel_service_booking", fields(service_id = %cancellation.service_id, timestamp = %cancellation.timestamp), skip(cancellation, outbox))]
+    async fn cancel_service_booking(
+        &self,
+        cancellation: &ServiceBookingCancellation,
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for cancelling service booking"
+            );
+        })?;
+
+        if !cancel_booking(&mut tx, cancellation).await? {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for cancelling service booking"
+            );
+        })?;
+
+        Ok(true)
+    }
+
+    /// Replace the attendees of a calendar event
+    #[instrument(name = "calendar_event::set_attendees", fields(event_id = %event_id))]
+    async fn set_attendees(
+        &self,
+        event_id: &ID,
+        attendees: &[CalendarEventAttendee],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for setting calendar_event_attendees"
+            );
+        })?;
+
+        replace_attendees(&mut tx, event_id, attendees).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for setting calendar_event_attendees"
             );
         })?;
 
         Ok(())
     }
 
+    /// Update the response status of an internal user attending a calendar event
+    /// Only this attendee is updated, so that concurrent responses of other attendees are kept
+    #[instrument(name = "calendar_event::update_attendee_response_status", fields(event_id = %event_id, user_id = %user_id))]
+    async fn update_attendee_response_status(
+        &self,
+        event_id: &ID,
+        user_id: &ID,
+        response_status: CalendarEventAttendeeResponseStatus,
+    ) -> anyhow::Result<bool> {
+        let response_status: String = response_status.into();
+        let res = sqlx::query!(
+            r#"
+            UPDATE calendar_event_attendees AS a
+            SET response_status = $3
+            WHERE a.event_uid = $1 AND a.user_uid = $2
+            "#,
+            event_id.as_ref(),
+            user_id.as_ref(),
+            response_status,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                event_id = %event_id,
+                user_id = %user_id,
+                error = ?err,
+                "Failed to update calendar_event_attendee response status"
+            );
+        })?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
     #[instrument(name = "calendar_event::find", fields(event_uid = %event_uid))]
     async fn find(&self, event_uid: &ID) -> anyhow::Result<Option<CalendarEvent>> {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.event_uid = $1
             "#,
             event_uid.as_ref(),
@@ -457,7 +1393,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.recurring_event_uid = ANY($1::uuid[]) AND
                 (
                     (e.original_start_time >= $2 AND e.original_start_time <= $3)
@@ -495,7 +1431,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.event_uid = $1 OR e.recurring_event_uid = $1
             "#,
             event_id.as_ref(),
@@ -524,7 +1460,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.account_uid = $1 AND e.external_id = $2
             "#,
             account_uid.as_ref(),
@@ -555,7 +1491,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.account_uid = $1 AND e.external_id = any($2::text[])
             "#,
             account_uid.as_ref(),
@@ -582,7 +1518,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.event_uid = ANY($1::uuid[])
             "#,
             &ids as &[Uuid],
@@ -601,6 +1537,32 @@ impl IEventRepo for PostgresEventRepo {
         .collect()
     }
 
+    /// Find events by their group_id
+    #[instrument(name = "calendar_event::find_by_group", fields(group_id = %group_id))]
+    async fn find_by_group(&self, group_id: &ID) -> anyhow::Result<Vec<CalendarEvent>> {
+        sqlx::query_as!(
+            EventRaw,
+            r#"
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
+            WHERE e.group_uid = $1
+            ORDER BY e.start_time ASC
+            "#,
+            group_id.as_ref(),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                group_id = %group_id,
+                error = ?e,
+                "Failed to find calendar events for group id"
+            );
+        })?
+        .into_iter()
+        .map(|e| e.try_into())
+        .collect()
+    }
+
     /// Find events by their calendar_id
     #[instrument(name = "calendar_event::find_by_calendar", fields(calendar_id = %calendar_id))]
     async fn find_by_calendar(
@@ -612,7 +1574,7 @@ impl IEventRepo for PostgresEventRepo {
             sqlx::query_as!(
                 EventRaw,
                 r#"
-                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
                     WHERE e.calendar_uid = $1
                     AND (
                         (e.start_time <= $2 AND e.end_time >= $3)
@@ -640,7 +1602,7 @@ impl IEventRepo for PostgresEventRepo {
             sqlx::query_as!(
                 EventRaw,
                 r#"
-                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata
+                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees
                     FROM calendar_events AS e
                     WHERE e.calendar_uid = $1
                     "#,
@@ -675,7 +1637,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
                     WHERE e.calendar_uid  = any($1::uuid[])
                     AND (
                         (e.start_time <= $2 AND e.end_time >= $3)
@@ -731,7 +1693,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.user_uid = any($1::uuid[])
                 AND e.start_time <= $2
                 AND e.recurrence_jsonb IS NOT NULL
@@ -790,7 +1752,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
             WHERE e.user_uid = any($1::uuid[])
                 AND e.start_time <= $2
                 AND e.end_time >= $3
@@ -825,6 +1787,8 @@ impl IEventRepo for PostgresEventRepo {
     /// The parameter `include_tentative` is used to include events with the status "tentative"
     ///
     /// This is useful for the free/busy query
+    ///
+    /// The attendees of the events are not loaded, as they are not needed
     #[instrument(name = "calendar_event::find_busy_events_and_recurring_events_for_calendars", fields(calendar_ids = ?calendar_ids, timespan = ?timespan, include_tentative = %include_tentative))]
     async fn find_busy_events_and_recurring_events_for_calendars(
         &self,
@@ -847,7 +1811,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata FROM calendar_events AS e
+                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, NULL::jsonb AS attendees FROM calendar_events AS e
                     WHERE e.calendar_uid  = any($1::uuid[])
                     AND (
                         (e.start_time < $2 AND e.end_time > $3)
@@ -876,6 +1840,61 @@ impl IEventRepo for PostgresEventRepo {
         .collect()
     }
 
+    /// Find the busy events (and recurring events) of other users in which the users are attendees
+    /// Events declined by all the users are not returned
+    #[instrument(name = "calendar_event::find_busy_events_and_recurring_events_for_attendees", fields(user_ids = ?user_ids))]
+    async fn find_busy_events_and_recurring_events_for_attendees(
+        &self,
+        user_ids: &[ID],
+        timespan: nittei_domain::TimeSpan,
+        include_tentative: bool,
+    ) -> anyhow::Result<Vec<CalendarEvent>> {
+        let expected_status: Vec<String> = if include_tentative {
+            vec![
+                CalendarEventStatus::Tentative.into(),
+                CalendarEventStatus::Confirmed.into(),
+            ]
+        } else {
+            vec![CalendarEventStatus::Confirmed.into()]
+        };
+        let declined: String = CalendarEventAttendeeResponseStatus::Declined.into();
+        let user_ids = user_ids.iter().map(|id| *id.as_ref()).collect::<Vec<_>>();
+        sqlx::query_as!(
+            EventRaw,
+            r#"
+                    SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees FROM calendar_events AS e
+                    WHERE EXISTS (
+                        SELECT 1 FROM calendar_event_attendees AS a
+                        WHERE a.event_uid = e.event_uid AND a.user_uid = any($1::uuid[]) AND a.user_uid <> e.user_uid AND a.response_status <> $5
+                    )
+                    AND (
+                        (e.start_time < $2 AND e.end_time > $3)
+                        OR
+                        (e.start_time < $2 AND e.recurrence_jsonb IS NOT NULL AND (e.recurring_until IS NULL OR e.recurring_until > $3))
+                    )
+                    AND busy = true
+                    AND status = any($4::text[])
+                    "#,
+            &user_ids,
+            timespan.end(),
+            timespan.start(),
+            &expected_status as &[String],
+            declined,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                user_ids = ?user_ids,
+                error = ?e,
+                "Failed to find calendar events for attendees"
+            );
+        })?
+        .into_iter()
+        .map(|e| e.try_into())
+        .collect()
+    }
+
     /// Search events
     /// This method is used to search events based on the given parameters for one specific user:
     /// The parameters are optional and can be used to filter the events
@@ -885,10 +1904,15 @@ impl IEventRepo for PostgresEventRepo {
     async fn search_events_for_user(
         &self,
         params: SearchEventsForUserParams,
-    ) -> anyhow::Result<Vec<CalendarEvent>> {
+    ) -> anyhow::Result<SearchEventsResult> {
+        let sort = params.sort.unwrap_or_default();
         let mut query = QueryBuilder::new(
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees, "#,
+        );
+        push_rank_column(&mut query, &sort, &params.search_events_params.text);
+        query.push(
+            r#"
             FROM calendar_events AS e
             WHERE e.user_uid = "#,
         );
@@ -973,6 +1997,10 @@ impl IEventRepo for PostgresEventRepo {
                         query.push(" AND e.recurrence_jsonb IS NULL");
                     }
                 }
+                RecurrenceQuery::Matches(options) => {
+                    query.push(" AND e.recurrence_jsonb @> ");
+                    query.push_bind(Json(options));
+                }
             }
         }
 
@@ -981,6 +2009,10 @@ impl IEventRepo for PostgresEventRepo {
             query.push_bind(Json(metadata.clone()));
         }
 
+        apply_attendee_query(&mut query, &params.search_events_params.attendee);
+
+        apply_text_query(&mut query, &params.search_events_params.text);
+
         apply_datetime_query(
             &mut query,
             "created",
@@ -995,28 +2027,16 @@ impl IEventRepo for PostgresEventRepo {
             true,
         );
 
-        // Sort if needed
-        if let Some(sort) = params.sort {
-            query.push(" ORDER BY ");
-            query.push(match sort {
-                nittei_domain::CalendarEventSort::StartTimeAsc => "start_time ASC",
-                nittei_domain::CalendarEventSort::StartTimeDesc => "start_time DESC",
-                nittei_domain::CalendarEventSort::EndTimeAsc => "end_time ASC",
-                nittei_domain::CalendarEventSort::EndTimeDesc => "end_time DESC",
-                nittei_domain::CalendarEventSort::CreatedAsc => "created ASC",
-                nittei_domain::CalendarEventSort::CreatedDesc => "created DESC",
-                nittei_domain::CalendarEventSort::UpdatedAsc => "updated ASC",
-                nittei_domain::CalendarEventSort::UpdatedDesc => "updated DESC",
-                nittei_domain::CalendarEventSort::EventUidAsc => "event_uid ASC",
-                nittei_domain::CalendarEventSort::EventUidDesc => "event_uid DESC",
-            });
-        }
+        apply_cursor(
+            &mut query,
+            &sort,
+            &params.search_events_params.text,
+            &params.cursor,
+        )?;
 
-        // Limit if needed
-        if let Some(limit) = params.limit {
-            query.push(" LIMIT ");
-            query.push(format!("{limit}"));
-        }
+        apply_sort(&mut query, &sort, &params.search_events_params.text);
+
+        apply_limit(&mut query, params.limit);
 
         let rows = query.build().fetch_all(&self.pool).await.inspect_err(|e| {
             error!(
@@ -1025,15 +2045,7 @@ impl IEventRepo for PostgresEventRepo {
             );
         })?;
 
-        let events_raw: Vec<EventRaw> = rows
-            .into_iter()
-            .map(|row| EventRaw::from_row(&row))
-            .collect::<Result<Vec<_>, sqlx::Error>>()?;
-
-        events_raw
-            .into_iter()
-            .map(CalendarEvent::try_from)
-            .collect()
+        search_result(rows, sort, params.limit)
     }
 
     /// Search events at the account level
@@ -1045,10 +2057,15 @@ impl IEventRepo for PostgresEventRepo {
     async fn search_events_for_account(
         &self,
         params: SearchEventsForAccountParams,
-    ) -> anyhow::Result<Vec<CalendarEvent>> {
+    ) -> anyhow::Result<SearchEventsResult> {
+        let sort = params.sort.unwrap_or_default();
         let mut query = QueryBuilder::new(
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees, "#,
+        );
+        push_rank_column(&mut query, &sort, &params.search_events_params.text);
+        query.push(
+            r#"
             FROM calendar_events AS e
             WHERE e.account_uid = "#,
         );
@@ -1130,6 +2147,10 @@ impl IEventRepo for PostgresEventRepo {
                         query.push(" AND e.recurrence_jsonb IS NULL");
                     }
                 }
+                RecurrenceQuery::Matches(options) => {
+                    query.push(" AND e.recurrence_jsonb @> ");
+                    query.push_bind(Json(options));
+                }
             }
         }
 
@@ -1138,6 +2159,10 @@ impl IEventRepo for PostgresEventRepo {
             query.push_bind(Json(metadata.clone()));
         }
 
+        apply_attendee_query(&mut query, &params.search_events_params.attendee);
+
+        apply_text_query(&mut query, &params.search_events_params.text);
+
         apply_datetime_query(
             &mut query,
             "created",
@@ -1152,28 +2177,16 @@ impl IEventRepo for PostgresEventRepo {
             true,
         );
 
-        // Sort if needed
-        if let Some(sort) = params.sort {
-            query.push(" ORDER BY ");
-            query.push(match sort {
-                nittei_domain::CalendarEventSort::StartTimeAsc => "start_time ASC",
-                nittei_domain::CalendarEventSort::StartTimeDesc => "start_time DESC",
-                nittei_domain::CalendarEventSort::EndTimeAsc => "end_time ASC",
-                nittei_domain::CalendarEventSort::EndTimeDesc => "end_time DESC",
-                nittei_domain::CalendarEventSort::CreatedAsc => "created ASC",
-                nittei_domain::CalendarEventSort::CreatedDesc => "created DESC",
-                nittei_domain::CalendarEventSort::UpdatedAsc => "updated ASC",
-                nittei_domain::CalendarEventSort::UpdatedDesc => "updated DESC",
-                nittei_domain::CalendarEventSort::EventUidAsc => "event_uid ASC",
-                nittei_domain::CalendarEventSort::EventUidDesc => "event_uid DESC",
-            });
-        }
+        apply_cursor(
+            &mut query,
+            &sort,
+            &params.search_events_params.text,
+            &params.cursor,
+        )?;
 
-        // Limit if needed
-        if let Some(limit) = params.limit {
-            query.push(" LIMIT ");
-            query.push(format!("{limit}"));
-        }
+        apply_sort(&mut query, &sort, &params.search_events_params.text);
+
+        apply_limit(&mut query, params.limit);
 
         let rows = query.build().fetch_all(&self.pool).await.inspect_err(|e| {
             error!(
@@ -1182,15 +2195,63 @@ impl IEventRepo for PostgresEventRepo {
             );
         })?;
 
-        let events_raw: Vec<EventRaw> = rows
-            .into_iter()
-            .map(|row| EventRaw::from_row(&row))
-            .collect::<Result<Vec<_>, sqlx::Error>>()?;
+        search_result(rows, sort, params.limit)
+    }
+
+    #[instrument(name = "calendar_event::find_text_highlights", fields(event_ids = ?event_ids))]
+    async fn find_text_highlights(
+        &self,
+        event_ids: &[ID],
+        text: &TextSearchQuery,
+    ) -> anyhow::Result<Vec<CalendarEventHighlight>> {
+        let event_ids = event_ids.iter().map(|id| *id.as_ref()).collect::<Vec<_>>();
+        // The matches are marked with control characters (removed from the texts), so that the texts can be escaped
+        let options = format!("StartSel=\"{HIGHLIGHT_START}\", StopSel=\"{HIGHLIGHT_STOP}\"");
+        let markers = format!("{HIGHLIGHT_START}{HIGHLIGHT_STOP}");
+        // ts_headline returns the start of the texts without a match, so only the fragments with a match are kept
+        let rows = sqlx::query!(
+            r#"
+            SELECT highlights.* FROM (
+                SELECT e.event_uid,
+                    CASE WHEN strpos(h.title, $6) > 0 THEN h.title END AS title,
+                    CASE WHEN strpos(h.description, $6) > 0 THEN h.description END AS description,
+                    CASE WHEN strpos(h.location, $6) > 0 THEN h.location END AS location
+                FROM calendar_events AS e,
+                    (SELECT $2::text::regconfig AS language, websearch_to_tsquery($2::text::regconfig, $3) AS query) AS q,
+                    LATERAL (
+                        SELECT ts_headline(q.language, translate(e.title, $5, ''), q.query, $4) AS title,
+                            ts_headline(q.language, translate(e.description, $5, ''), q.query, $4 || ', MaxFragments=2, FragmentDelimiter=" ... "') AS description,
+                            ts_headline(q.language, translate(e.location, $5, ''), q.query, $4) AS location
+                    ) AS h
+                WHERE e.event_uid = ANY($1::uuid[])
+            ) AS highlights
+            WHERE highlights.title IS NOT NULL OR highlights.description IS NOT NULL OR highlights.location IS NOT NULL
+            "#,
+            &event_ids,
+            text.language.config_name(),
+            text.text,
+            options,
+            markers,
+            HIGHLIGHT_START.to_string(),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                error = ?e,
+                "Failed to find the text highlights of calendar events"
+            );
+        })?;
 
-        events_raw
+        Ok(rows
             .into_iter()
-            .map(CalendarEvent::try_from)
-            .collect()
+            .map(|row| CalendarEventHighlight {
+                event_id: row.event_uid.into(),
+                title: row.title.as_deref().map(highlighted_html),
+                description: row.description.as_deref().map(highlighted_html),
+                location: row.location.as_deref().map(highlighted_html),
+            })
+            .collect())
     }
 
     #[instrument(name = "calendar_event::find_most_recently_created_service_events", fields(service_id = %service_id, user_ids = ?user_ids))]
@@ -1243,7 +2304,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees
             FROM calendar_events AS e
             WHERE e.service_uid = $1 AND
             e.user_uid = ANY($2::uuid[]) AND
@@ -1268,6 +2329,7 @@ impl IEventRepo for PostgresEventRepo {
         .into_iter().map(|e| e.try_into()).collect()
     }
 
+    /// Find the service events of the user (for the booking slots), without their attendees
     #[instrument(name = "calendar_event::find_user_service_events", fields(user_id = %user_id, busy = %busy, min_time = %min_time, max_time = %max_time))]
     async fn find_user_service_events(
         &self,
@@ -1279,7 +2341,7 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, NULL::jsonb AS attendees
             FROM calendar_events AS e
             WHERE e.user_uid = $1 AND
             e.busy = $2 AND
@@ -1338,6 +2400,22 @@ impl IEventRepo for PostgresEventRepo {
     /// Delete multiple calendar events by their uids
     #[instrument(name = "calendar_event::delete_many", fields(event_ids = ?event_ids))]
     async fn delete_many(&self, event_ids: &[ID]) -> anyhow::Result<()> {
+        self.delete_many_with_outbox(event_ids, &[]).await
+    }
+
+    #[instrument(name = "calendar_event::delete_many_with_outbox", fields(event_ids = ?event_ids), skip(outbox))]
+    async fn delete_many_with_outbox(
+        &self,
+        event_ids: &[ID],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for deleting calendar events"
+            );
+        })?;
+
         let ids = event_ids.iter().map(|id| *id.as_ref()).collect::<Vec<_>>();
         sqlx::query!(
             r#"
@@ -1346,7 +2424,7 @@ impl IEventRepo for PostgresEventRepo {
             "#,
             &ids as &[Uuid],
         )
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
         .inspect_err(|e| {
             error!(
@@ -1355,6 +2433,62 @@ impl IEventRepo for PostgresEventRepo {
                 "Failed to delete calendar events"
             );
         })?;
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for deleting calendar events"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "calendar_event::save_and_delete_many_with_outbox", fields(event_uid = %e.id, deleted_event_ids = ?deleted_event_ids), skip(e, outbox))]
+    async fn save_and_delete_many_with_outbox(
+        &self,
+        e: &CalendarEvent,
+        deleted_event_ids: &[ID],
+        outbox: &[OutboxMessage],
+    ) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for saving and deleting calendar events"
+            );
+        })?;
+
+        save_event(&mut *tx, e, None).await?;
+        let ids = deleted_event_ids
+            .iter()
+            .map(|id| *id.as_ref())
+            .collect::<Vec<_>>();
+        sqlx::query!(
+            r#"
+            DELETE FROM calendar_events AS e
+            WHERE e.event_uid = ANY($1::uuid[])
+            "#,
+            &ids as &[Uuid],
+        )
+        .execute(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                event_ids = ?deleted_event_ids,
+                error = ?err,
+                "Failed to delete calendar events"
+            );
+        })?;
+        insert_outbox_messages(&mut *tx, outbox).await?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for saving and deleting calendar events"
+            );
+        })?;
+
         Ok(())
     }
 
@@ -1389,9 +2523,10 @@ impl IEventRepo for PostgresEventRepo {
         sqlx::query_as!(
             EventRaw,
             r#"
-            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, metadata
+            SELECT event_uid, calendar_uid, user_uid, account_uid, external_parent_id, external_id, title, description, event_type, location, all_day, status, start_time, duration, busy, end_time, created, updated, recurrence_jsonb, recurring_until, exdates, recurring_event_uid, original_start_time, reminders_jsonb, service_uid, group_uid, metadata, timezone, version, (SELECT jsonb_agg(jsonb_build_object('user_uid', a.user_uid, 'email', a.email, 'role', a.role, 'response_status', a.response_status) ORDER BY a.position) FROM calendar_event_attendees AS a WHERE a.event_uid = e.event_uid) AS attendees
             FROM calendar_events AS e
-            WHERE e.account_uid = $1 AND e.metadata @> $2
+            WHERE e.account_uid = $1 AND e.metadata @> $2 AND ($5::uuid IS NULL OR e.event_uid > $5)
+            ORDER BY e.event_uid
             LIMIT $3
             OFFSET $4
             "#,
@@ -1399,6 +2534,7 @@ impl IEventRepo for PostgresEventRepo {
             Json(&query.metadata) as _,
             query.limit as i64,
             query.skip as i64,
+            query.after.as_ref().map(|id| *id.as_ref()),
         )
         .fetch_all(&self.pool)
         .await
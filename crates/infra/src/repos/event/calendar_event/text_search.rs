@@ -0,0 +1,610 @@
+//! Full-text search on the texts of the events, for the in-memory repository
+//!
+//! It emulates the full-text search of Postgres (`to_tsvector`, `websearch_to_tsquery`, `ts_rank` and `ts_headline`):
+//! - the words are split on the non-alphanumeric characters and lowercased
+//! - in English, the stop words are ignored and the words are stemmed (with the Porter algorithm,
+//!   a close approximation of the Snowball stemmer used by Postgres)
+//! - in the other languages, the words are only lowercased (like the "simple" configuration)
+//!
+//! The HTML of the highlights is built here for both repositories (see `highlighted_html`)
+
+use std::collections::{HashMap, HashSet};
+
+use nittei_domain::SearchLanguage;
+
+/// Weights of the fields in the ranks (the defaults of `ts_rank` for the weights A, B and C)
+pub const TITLE_WEIGHT: f32 = 1.0;
+pub const DESCRIPTION_WEIGHT: f32 = 0.4;
+pub const LOCATION_WEIGHT: f32 = 0.2;
+
+/// Markers of the words matching a query in the texts highlighted by Postgres (`StartSel` and `StopSel` of `ts_headline`)
+/// These control characters are removed from the texts beforehand, so that they only mark the matches
+pub const HIGHLIGHT_START: char = '\u{2}';
+pub const HIGHLIGHT_STOP: char = '\u{3}';
+
+/// Stop words of the English configuration of Postgres
+const ENGLISH_STOP_WORDS: &str = "\
+    i me my myself we our ours ourselves you your yours yourself yourselves he him his himself \
+    she her hers herself it its itself they them their theirs themselves what which who whom \
+    this that these those am is are was were be been being have has had having do does did \
+    doing a an the and but if or because as until while of at by for with about against between \
+    into through during before after above below to from up down in out on off over under again \
+    further then once here there when where why how all any both each few more most other some \
+    such no nor not only own same so than too very s t can will just don should now";
+
+/// Words of a text, with their byte ranges
+fn words(text: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, _) = chars.by_ref().find(|(_, c)| c.is_alphanumeric())?;
+        let mut end = text.len();
+        while let Some((i, c)) = chars.peek() {
+            if !c.is_alphanumeric() {
+                end = *i;
+                break;
+            }
+            chars.next();
+        }
+        Some((start, end))
+    })
+}
+
+/// Lexeme of a word, `None` for the stop words
+fn lexeme(language: &SearchLanguage, word: &str) -> Option<String> {
+    let word = word.to_lowercase();
+    match language {
+        SearchLanguage::English => {
+            if ENGLISH_STOP_WORDS
+                .split_whitespace()
+                .any(|stop_word| stop_word == word)
+            {
+                None
+            } else {
+                Some(porter_stem(&word))
+            }
+        }
+        _ => Some(word),
+    }
+}
+
+/// Lexemes of a text with their positions (counting the stop words), like `to_tsvector`
+fn lexemes(language: &SearchLanguage, text: &str) -> Vec<(String, u32)> {
+    words(text)
+        .zip(1..)
+        .filter_map(|((start, end), position)| {
+            lexeme(language, &text[start..end]).map(|lexeme| (lexeme, position))
+        })
+        .collect()
+}
+
+/// Vector of the lexemes of the texts of an event, with their positions and weights
+#[derive(Debug, Default)]
+pub struct SearchDocument {
+    lexemes: HashMap<String, Vec<(u32, f32)>>,
+}
+
+impl SearchDocument {
+    /// The positions of a field follow the ones of the previous fields, like the concatenation of the vectors
+    pub fn new<'a>(
+        language: &SearchLanguage,
+        fields: impl IntoIterator<Item = (Option<&'a str>, f32)>,
+    ) -> Self {
+        let mut document = Self::default();
+        let mut offset = 0;
+        for (text, weight) in fields {
+            let mut last_position = offset;
+            for (lexeme, position) in lexemes(language, text.unwrap_or_default()) {
+                last_position = offset + position;
+                document
+                    .lexemes
+                    .entry(lexeme)
+                    .or_default()
+                    .push((last_position, weight));
+            }
+            offset = last_position;
+        }
+        document
+    }
+
+    fn positions(&self, lexeme: &str) -> &[(u32, f32)] {
+        self.lexemes
+            .get(lexeme)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Term of a query: a word or a phrase (lexemes with their offsets in the phrase), possibly negated
+#[derive(Debug, Clone, PartialEq)]
+struct QueryTerm {
+    negated: bool,
+    lexemes: Vec<(String, u32)>,
+}
+
+impl QueryTerm {
+    fn new(language: &SearchLanguage, text: &str, negated: bool) -> Option<Self> {
+        let lexemes = lexemes(language, text);
+        let first = lexemes.first()?.1;
+        Some(Self {
+            negated,
+            lexemes: lexemes
+                .into_iter()
+                .map(|(lexeme, position)| (lexeme, position - first))
+                .collect(),
+        })
+    }
+
+    /// Whether the lexemes of the term follow each other in the document
+    fn is_in(&self, document: &SearchDocument) -> bool {
+        let Some((first, rest)) = self.lexemes.split_first() else {
+            return false;
+        };
+        document.positions(&first.0).iter().any(|(position, _)| {
+            rest.iter().all(|(lexeme, offset)| {
+                document
+                    .positions(lexeme)
+                    .iter()
+                    .any(|(p, _)| *p == position + offset)
+            })
+        })
+    }
+
+    fn matches(&self, document: &SearchDocument) -> bool {
+        self.is_in(document) != self.negated
+    }
+}
+
+/// Query of a text searched, in the web search syntax (like `websearch_to_tsquery`):
+/// the words are all required, unless separated by `or`, `"quoted words"` are searched as a phrase
+/// and the words prefixed by `-` are excluded
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextQuery {
+    /// Alternatives (`or`) of the terms all required
+    alternatives: Vec<Vec<QueryTerm>>,
+}
+
+impl TextQuery {
+    pub fn parse(language: &SearchLanguage, text: &str) -> Self {
+        let mut alternatives = vec![Vec::new()];
+        let mut rest = text;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            let negated = rest.starts_with('-');
+            if negated {
+                rest = &rest[1..];
+            }
+            let operand = if let Some(quoted) = rest.strip_prefix('"') {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                rest = quoted.get(end + 1..).unwrap_or_default();
+                &quoted[..end]
+            } else {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '"')
+                    .unwrap_or(rest.len());
+                let word = &rest[..end];
+                rest = &rest[end..];
+                if !negated && word.eq_ignore_ascii_case("or") {
+                    if alternatives.last().is_some_and(|terms| !terms.is_empty()) {
+                        alternatives.push(Vec::new());
+                    }
+                    continue;
+                }
+                word
+            };
+            if let Some(term) = QueryTerm::new(language, operand, negated)
+                && let Some(terms) = alternatives.last_mut()
+            {
+                terms.push(term);
+            }
+        }
+        alternatives.retain(|terms| !terms.is_empty());
+        Self { alternatives }
+    }
+
+    /// A query without any word (e.g. only stop words) matches nothing
+    pub fn matches(&self, document: &SearchDocument) -> bool {
+        self.alternatives
+            .iter()
+            .any(|terms| terms.iter().all(|term| term.matches(document)))
+    }
+
+    /// Distinct lexemes of the query, optionally only the ones not excluded
+    fn lexemes(&self, only_included: bool) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.alternatives
+            .iter()
+            .flatten()
+            .filter(|term| !(only_included && term.negated))
+            .flat_map(|term| term.lexemes.iter().map(|(lexeme, _)| lexeme.as_str()))
+            .filter(|lexeme| seen.insert(*lexeme))
+            .collect()
+    }
+
+    /// Rank of a document, like `ts_rank` for a disjunction of the lexemes of the query:
+    /// each occurrence of a lexeme adds its weight, with a decreasing importance
+    pub fn rank(&self, document: &SearchDocument) -> f32 {
+        let lexemes = self.lexemes(false);
+        if lexemes.is_empty() {
+            return 0.0;
+        }
+        let total = lexemes
+            .iter()
+            .map(|lexeme| {
+                let mut positions = document.positions(lexeme).to_vec();
+                positions.sort_by_key(|(position, _)| *position);
+                let mut sum = 0.0;
+                let (mut max_weight, mut max_index) = (-1.0, 0);
+                for (i, (_, weight)) in positions.iter().enumerate() {
+                    let square = ((i + 1) * (i + 1)) as f32;
+                    sum += weight / square;
+                    if *weight > max_weight {
+                        (max_weight, max_index) = (*weight, i);
+                    }
+                }
+                if positions.is_empty() {
+                    return 0.0;
+                }
+                let square = ((max_index + 1) * (max_index + 1)) as f32;
+                (max_weight + sum - max_weight / square) / 1.644_934
+            })
+            .sum::<f32>();
+        total / lexemes.len() as f32
+    }
+
+    /// HTML of the text with the words matching the query in bold (`<b>`), like `ts_headline`
+    /// The text is escaped (see `highlighted_html`)
+    /// `None` when no word of the text matches
+    pub fn highlight(&self, language: &SearchLanguage, text: &str) -> Option<String> {
+        let lexemes = self.lexemes(true);
+        let mut highlighted = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, end) in words(text) {
+            let matches = lexeme(language, &text[start..end])
+                .is_some_and(|lexeme| lexemes.contains(&lexeme.as_str()));
+            if matches {
+                highlighted.push_str(&text[last..start]);
+                highlighted.push(HIGHLIGHT_START);
+                highlighted.push_str(&text[start..end]);
+                highlighted.push(HIGHLIGHT_STOP);
+                last = end;
+            }
+        }
+        if last == 0 {
+            return None;
+        }
+        highlighted.push_str(&text[last..]);
+        Some(highlighted_html(&highlighted))
+    }
+}
+
+/// Escape the text to be included in HTML
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// HTML of a text highlighted with the markers (see `HIGHLIGHT_START`): the text is escaped,
+/// and the matching words are put between `<b>` and `</b>`
+pub fn highlighted_html(highlighted: &str) -> String {
+    let mut html = String::with_capacity(highlighted.len());
+    let mut in_match = false;
+    for part in highlighted.split([HIGHLIGHT_START, HIGHLIGHT_STOP]) {
+        if in_match {
+            html.push_str("<b>");
+            html.push_str(&escape_html(part));
+            html.push_str("</b>");
+        } else {
+            html.push_str(&escape_html(part));
+        }
+        in_match = !in_match;
+    }
+    html
+}
+
+/// Stem of an English word, with the Porter algorithm
+/// The words with other characters than ASCII letters, and the ones of two letters or less, are kept as they are
+fn porter_stem(word: &str) -> String {
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word.to_string();
+    }
+    let mut stemmer = PorterStemmer {
+        word: word.as_bytes().to_vec(),
+        stem_len: 0,
+    };
+    stemmer.step1ab();
+    stemmer.step1c();
+    stemmer.step2();
+    stemmer.step3();
+    stemmer.step4();
+    stemmer.step5();
+    String::from_utf8_lossy(&stemmer.word).into_owned()
+}
+
+struct PorterStemmer {
+    word: Vec<u8>,
+    /// Length of the stem before the suffix last matched by `ends`
+    stem_len: usize,
+}
+
+impl PorterStemmer {
+    fn is_consonant(&self, i: usize) -> bool {
+        match self.word[i] {
+            b'a' | b'e' | b'i' | b'o' | b'u' => false,
+            b'y' => i == 0 || !self.is_consonant(i - 1),
+            _ => true,
+        }
+    }
+
+    /// Number of vowel-consonant sequences in the stem
+    fn measure(&self) -> usize {
+        let mut n = 0;
+        let mut previous_is_vowel = false;
+        for i in 0..self.stem_len {
+            let is_consonant = self.is_consonant(i);
+            if is_consonant && previous_is_vowel {
+                n += 1;
+            }
+            previous_is_vowel = !is_consonant;
+        }
+        n
+    }
+
+    fn has_vowel_in_stem(&self) -> bool {
+        (0..self.stem_len).any(|i| !self.is_consonant(i))
+    }
+
+    fn ends_with_double_consonant(&self, len: usize) -> bool {
+        len >= 2 && self.word[len - 1] == self.word[len - 2] && self.is_consonant(len - 1)
+    }
+
+    /// Whether the word ends (before `len`) with consonant-vowel-consonant, the last one not being w, x or y
+    fn ends_with_cvc(&self, len: usize) -> bool {
+        len >= 3
+            && self.is_consonant(len - 1)
+            && !self.is_consonant(len - 2)
+            && self.is_consonant(len - 3)
+            && !matches!(self.word[len - 1], b'w' | b'x' | b'y')
+    }
+
+    fn ends(&mut self, suffix: &str) -> bool {
+        if !self.word.ends_with(suffix.as_bytes()) {
+            return false;
+        }
+        self.stem_len = self.word.len() - suffix.len();
+        true
+    }
+
+    fn set_suffix(&mut self, suffix: &str) {
+        self.word.truncate(self.stem_len);
+        self.word.extend_from_slice(suffix.as_bytes());
+    }
+
+    /// Replace the first of the suffixes the word ends with, when the stem has the given measure
+    fn replace_suffix(&mut self, suffixes: &[(&str, &str)], min_measure: usize) {
+        if let Some((_, replacement)) = suffixes.iter().find(|(suffix, _)| self.ends(suffix))
+            && self.measure() >= min_measure
+        {
+            self.set_suffix(replacement);
+        }
+    }
+
+    /// Plurals and past participles
+    fn step1ab(&mut self) {
+        if self.word.ends_with(b"s") {
+            if self.ends("sses") {
+                self.set_suffix("ss");
+            } else if self.ends("ies") {
+                self.set_suffix("i");
+            } else if self.word.len() >= 2 && self.word[self.word.len() - 2] != b's' {
+                self.word.pop();
+            }
+        }
+        if self.ends("eed") {
+            if self.measure() > 0 {
+                self.word.pop();
+            }
+        } else if (self.ends("ed") || self.ends("ing")) && self.has_vowel_in_stem() {
+            self.word.truncate(self.stem_len);
+            let len = self.word.len();
+            if self.ends("at") {
+                self.set_suffix("ate");
+            } else if self.ends("bl") {
+                self.set_suffix("ble");
+            } else if self.ends("iz") {
+                self.set_suffix("ize");
+            } else if self.ends_with_double_consonant(len) {
+                if !matches!(self.word[len - 1], b'l' | b's' | b'z') {
+                    self.word.pop();
+                }
+            } else if self.measure() == 1 && self.ends_with_cvc(len) {
+                self.word.push(b'e');
+            }
+        }
+    }
+
+    /// Terminal y to i, when there is another vowel in the stem
+    fn step1c(&mut self) {
+        if self.ends("y") && self.has_vowel_in_stem() {
+            self.set_suffix("i");
+        }
+    }
+
+    /// Double suffixes to single ones
+    fn step2(&mut self) {
+        self.replace_suffix(
+            &[
+                ("ational", "ate"),
+                ("tional", "tion"),
+                ("enci", "ence"),
+                ("anci", "ance"),
+                ("izer", "ize"),
+                ("bli", "ble"),
+                ("alli", "al"),
+                ("entli", "ent"),
+                ("eli", "e"),
+                ("ousli", "ous"),
+                ("ization", "ize"),
+                ("ation", "ate"),
+                ("ator", "ate"),
+                ("alism", "al"),
+                ("iveness", "ive"),
+                ("fulness", "ful"),
+                ("ousness", "ous"),
+                ("aliti", "al"),
+                ("iviti", "ive"),
+                ("biliti", "ble"),
+                ("logi", "log"),
+            ],
+            1,
+        );
+    }
+
+    /// -ic-, -full, -ness...
+    fn step3(&mut self) {
+        self.replace_suffix(
+            &[
+                ("icate", "ic"),
+                ("ative", ""),
+                ("alize", "al"),
+                ("iciti", "ic"),
+                ("ical", "ic"),
+                ("ful", ""),
+                ("ness", ""),
+            ],
+            1,
+        );
+    }
+
+    /// -ant, -ence... when the stem is long enough
+    fn step4(&mut self) {
+        const SUFFIXES: &[&str] = &[
+            "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment", "ent", "ion",
+            "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+        ];
+        let found = SUFFIXES.iter().any(|suffix| {
+            self.ends(suffix)
+                && (*suffix != "ion"
+                    || (self.stem_len > 0 && matches!(self.word[self.stem_len - 1], b's' | b't')))
+        });
+        if found && self.measure() > 1 {
+            self.word.truncate(self.stem_len);
+        }
+    }
+
+    /// Final e, and double l
+    fn step5(&mut self) {
+        let len = self.word.len();
+        self.stem_len = len;
+        let measure = self.measure();
+        if self.word[len - 1] == b'e'
+            && (measure > 1 || measure == 1 && !self.ends_with_cvc(len - 1))
+        {
+            self.word.pop();
+        }
+        let len = self.word.len();
+        if self.word[len - 1] == b'l' && self.ends_with_double_consonant(len) && measure > 1 {
+            self.word.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stems_english_words() {
+        for (word, stem) in [
+            ("meetings", "meet"),
+            ("meeting", "meet"),
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("agreed", "agre"),
+            ("hopping", "hop"),
+            ("filing", "file"),
+            ("happy", "happi"),
+            ("relational", "relat"),
+            ("generalization", "gener"),
+            ("controlling", "control"),
+            ("room", "room"),
+        ] {
+            assert_eq!(porter_stem(word), stem, "{word}");
+        }
+    }
+
+    #[test]
+    fn matches_the_web_search_syntax() {
+        let document = SearchDocument::new(
+            &SearchLanguage::Simple,
+            [
+                (Some("Lunch"), TITLE_WEIGHT),
+                (Some("With the team, before the client"), DESCRIPTION_WEIGHT),
+                (None, LOCATION_WEIGHT),
+            ],
+        );
+        let matches =
+            |text: &str| TextQuery::parse(&SearchLanguage::Simple, text).matches(&document);
+
+        assert!(matches("TEAM lunch"));
+        assert!(!matches("team -client"));
+        assert!(matches("dinner or lunch"));
+        assert!(matches("\"the team\""));
+        assert!(!matches("\"team the\""));
+        assert!(!matches("dinner"));
+        assert!(!matches(""));
+    }
+
+    #[test]
+    fn ranks_and_highlights_in_english() {
+        let language = SearchLanguage::English;
+        let query = TextQuery::parse(&language, "the meetings");
+        let in_title = SearchDocument::new(&language, [(Some("Meeting"), TITLE_WEIGHT)]);
+        let in_description = SearchDocument::new(
+            &language,
+            [
+                (None, TITLE_WEIGHT),
+                (Some("A meeting"), DESCRIPTION_WEIGHT),
+            ],
+        );
+
+        assert!(query.matches(&in_title) && query.matches(&in_description));
+        assert!(query.rank(&in_title) > query.rank(&in_description));
+        assert_eq!(
+            query.highlight(&language, "The weekly meeting").as_deref(),
+            Some("The weekly <b>meeting</b>")
+        );
+        assert_eq!(query.highlight(&language, "Lunch"), None);
+    }
+
+    #[test]
+    fn escapes_the_highlighted_texts() {
+        let highlighted = format!(
+            "<script>alert('x')</script> & {HIGHLIGHT_START}<b>meeting</b>{HIGHLIGHT_STOP} \"room\""
+        );
+        assert_eq!(
+            highlighted_html(&highlighted),
+            "&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; <b>&lt;b&gt;meeting&lt;/b&gt;</b> &quot;room&quot;"
+        );
+
+        let query = TextQuery::parse(&SearchLanguage::Simple, "meeting");
+        assert_eq!(
+            query
+                .highlight(&SearchLanguage::Simple, "<i>Team</i> meeting")
+                .as_deref(),
+            Some("&lt;i&gt;Team&lt;/i&gt; <b>meeting</b>")
+        );
+    }
+}
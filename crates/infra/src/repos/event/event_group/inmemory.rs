@@ -0,0 +1,100 @@
+use nittei_domain::{ID, event_group::EventGroup};
+
+use super::IEventGroupRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryEventGroupRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryEventGroupRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+/// The external ids of the groups are unique per account
+fn ensure_unique_external_id(tables: &InMemoryTables, group: &EventGroup) -> anyhow::Result<()> {
+    if group.external_id.is_some()
+        && tables.event_groups.values().any(|g| {
+            g.id != group.id
+                && g.account_id == group.account_id
+                && g.external_id == group.external_id
+        })
+    {
+        anyhow::bail!("A group with this external id already exists");
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl IEventGroupRepo for InMemoryEventGroupRepo {
+    async fn insert(&self, group: &EventGroup) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.calendars, &group.calendar_id, "calendar")?;
+        InMemoryTables::ensure_exists(&tables.users, &group.user_id, "user")?;
+        InMemoryTables::ensure_exists(&tables.accounts, &group.account_id, "account")?;
+        if tables.event_groups.contains_key(group.id.as_ref()) {
+            anyhow::bail!("The group with id: {} already exists", group.id);
+        }
+        ensure_unique_external_id(&tables, group)?;
+        tables
+            .event_groups
+            .insert(*group.id.as_ref(), group.clone());
+        Ok(())
+    }
+
+    async fn save(&self, group: &EventGroup) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        ensure_unique_external_id(&tables, group)?;
+        if let Some(existing) = tables.event_groups.get_mut(group.id.as_ref()) {
+            existing.parent_id = group.parent_id.clone();
+            existing.external_id = group.external_id.clone();
+        }
+        Ok(())
+    }
+
+    async fn find(&self, group_id: &ID) -> anyhow::Result<Option<EventGroup>> {
+        let tables = self.store.lock().await;
+        Ok(tables.event_groups.get(group_id.as_ref()).cloned())
+    }
+
+    async fn get_by_external_id(
+        &self,
+        account_id: &ID,
+        external_id: &str,
+    ) -> anyhow::Result<Option<EventGroup>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .event_groups
+            .values()
+            .find(|g| &g.account_id == account_id && g.external_id.as_deref() == Some(external_id))
+            .cloned())
+    }
+
+    async fn find_by_parent_id(
+        &self,
+        account_id: &ID,
+        parent_id: &str,
+    ) -> anyhow::Result<Vec<EventGroup>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .event_groups
+            .values()
+            .filter(|g| &g.account_id == account_id && g.parent_id.as_deref() == Some(parent_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, group_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if tables.delete_event_group(group_id).is_none() {
+            tracing::warn!(
+                "Tried to delete event group with id: {:?}, but it does not exist",
+                group_id
+            );
+        }
+        Ok(())
+    }
+}
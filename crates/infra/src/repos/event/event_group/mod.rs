@@ -0,0 +1,184 @@
+mod inmemory;
+mod postgres;
+
+pub use inmemory::InMemoryEventGroupRepo;
+use nittei_domain::{ID, event_group::EventGroup};
+pub use postgres::PostgresEventGroupRepo;
+
+#[async_trait::async_trait]
+pub trait IEventGroupRepo: Send + Sync {
+    async fn insert(&self, group: &EventGroup) -> anyhow::Result<()>;
+    async fn save(&self, group: &EventGroup) -> anyhow::Result<()>;
+    async fn find(&self, group_id: &ID) -> anyhow::Result<Option<EventGroup>>;
+    async fn get_by_external_id(
+        &self,
+        account_id: &ID,
+        external_id: &str,
+    ) -> anyhow::Result<Option<EventGroup>>;
+    async fn find_by_parent_id(
+        &self,
+        account_id: &ID,
+        parent_id: &str,
+    ) -> anyhow::Result<Vec<EventGroup>>;
+    async fn delete(&self, group_id: &ID) -> anyhow::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use nittei_domain::{Account, Calendar, CalendarEvent, User, event_group::EventGroup};
+
+    use crate::{NitteiContext, backend_tests};
+
+    struct TestContext {
+        ctx: NitteiContext,
+        account: Account,
+        calendar: Calendar,
+        user: User,
+    }
+
+    async fn setup(ctx: NitteiContext) -> TestContext {
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let calendar = Calendar::new(&user.id, &account.id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        TestContext {
+            ctx,
+            account,
+            calendar,
+            user,
+        }
+    }
+
+    fn generate_group(ctx: &TestContext) -> EventGroup {
+        EventGroup {
+            calendar_id: ctx.calendar.id.clone(),
+            user_id: ctx.user.id.clone(),
+            account_id: ctx.account.id.clone(),
+            parent_id: Some("course".into()),
+            external_id: Some(format!("group_{}", ctx.calendar.id)),
+            ..Default::default()
+        }
+    }
+
+    backend_tests!(
+        create_and_delete,
+        update,
+        delete_group_detaches_events,
+        delete_by_calendar
+    );
+
+    async fn create_and_delete(ctx: NitteiContext) {
+        let test_ctx = setup(ctx).await;
+        let ctx = &test_ctx.ctx;
+        let group = generate_group(&test_ctx);
+
+        // Insert
+        assert!(ctx.repos.event_groups.insert(&group).await.is_ok());
+
+        // Different find methods
+        let res = ctx.repos.event_groups.find(&group.id).await.unwrap();
+        assert_eq!(res, Some(group.clone()));
+        let res = ctx
+            .repos
+            .event_groups
+            .get_by_external_id(&test_ctx.account.id, group.external_id.as_ref().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res, Some(group.clone()));
+        let res = ctx
+            .repos
+            .event_groups
+            .find_by_parent_id(&test_ctx.account.id, "course")
+            .await
+            .unwrap();
+        assert_eq!(res, vec![group.clone()]);
+
+        // Delete
+        assert!(ctx.repos.event_groups.delete(&group.id).await.is_ok());
+
+        // Find
+        assert!(
+            ctx.repos
+                .event_groups
+                .find(&group.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    async fn update(ctx: NitteiContext) {
+        let test_ctx = setup(ctx).await;
+        let ctx = &test_ctx.ctx;
+        let mut group = generate_group(&test_ctx);
+
+        // Insert
+        assert!(ctx.repos.event_groups.insert(&group).await.is_ok());
+
+        group.parent_id = None;
+
+        // Save
+        assert!(ctx.repos.event_groups.save(&group).await.is_ok());
+
+        // Find
+        assert_eq!(
+            ctx.repos.event_groups.find(&group.id).await.unwrap(),
+            Some(group)
+        );
+    }
+
+    async fn delete_group_detaches_events(ctx: NitteiContext) {
+        let test_ctx = setup(ctx).await;
+        let ctx = &test_ctx.ctx;
+        let group = generate_group(&test_ctx);
+        ctx.repos.event_groups.insert(&group).await.unwrap();
+
+        let event = CalendarEvent {
+            account_id: test_ctx.account.id.clone(),
+            calendar_id: test_ctx.calendar.id.clone(),
+            user_id: test_ctx.user.id.clone(),
+            group_id: Some(group.id.clone()),
+            ..Default::default()
+        };
+        ctx.repos.events.insert(&event).await.unwrap();
+
+        let events = ctx.repos.events.find_by_group(&group.id).await.unwrap();
+        assert_eq!(events.len(), 1);
+
+        // Delete the group
+        ctx.repos.event_groups.delete(&group.id).await.unwrap();
+
+        // The event still exists, but it isn't part of the group anymore
+        let event = ctx.repos.events.find(&event.id).await.unwrap().unwrap();
+        assert!(event.group_id.is_none());
+    }
+
+    async fn delete_by_calendar(ctx: NitteiContext) {
+        let test_ctx = setup(ctx).await;
+        let ctx = &test_ctx.ctx;
+        let group = generate_group(&test_ctx);
+        ctx.repos.event_groups.insert(&group).await.unwrap();
+
+        // Delete
+        assert!(
+            ctx.repos
+                .calendars
+                .delete(&test_ctx.calendar.id)
+                .await
+                .is_ok()
+        );
+
+        // Find after delete
+        assert!(
+            ctx.repos
+                .event_groups
+                .find(&group.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
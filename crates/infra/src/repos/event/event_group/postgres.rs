@@ -0,0 +1,212 @@
+use std::convert::{TryFrom, TryInto};
+
+use nittei_domain::{ID, event_group::EventGroup};
+use sqlx::{FromRow, PgPool, types::Uuid};
+use tracing::{error, instrument};
+
+use super::IEventGroupRepo;
+
+#[derive(Debug)]
+pub struct PostgresEventGroupRepo {
+    pool: PgPool,
+}
+
+impl PostgresEventGroupRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct EventGroupRaw {
+    group_uid: Uuid,
+    calendar_uid: Uuid,
+    user_uid: Uuid,
+    account_uid: Uuid,
+    parent_id: Option<String>,
+    external_id: Option<String>,
+}
+
+impl TryFrom<EventGroupRaw> for EventGroup {
+    type Error = anyhow::Error;
+
+    fn try_from(e: EventGroupRaw) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: e.group_uid.into(),
+            calendar_id: e.calendar_uid.into(),
+            user_id: e.user_uid.into(),
+            account_id: e.account_uid.into(),
+            parent_id: e.parent_id,
+            external_id: e.external_id,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl IEventGroupRepo for PostgresEventGroupRepo {
+    #[instrument(name = "event_group::insert", fields(group_uid = %group.id))]
+    async fn insert(&self, group: &EventGroup) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO events_groups(group_uid, calendar_uid, user_uid, account_uid, parent_id, external_id)
+            VALUES($1, $2, $3, $4, $5, $6)
+            "#,
+            group.id.as_ref(),
+            group.calendar_id.as_ref(),
+            group.user_id.as_ref(),
+            group.account_id.as_ref(),
+            group.parent_id,
+            group.external_id,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                group = ?group,
+                error = ?err,
+                "Failed to insert event group"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "event_group::save", fields(group_uid = %group.id))]
+    async fn save(&self, group: &EventGroup) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE events_groups SET
+                parent_id = $2,
+                external_id = $3
+            WHERE group_uid = $1
+            "#,
+            group.id.as_ref(),
+            group.parent_id,
+            group.external_id,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                group = ?group,
+                error = ?err,
+                "Failed to save event group"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "event_group::find", fields(group_uid = %group_id))]
+    async fn find(&self, group_id: &ID) -> anyhow::Result<Option<EventGroup>> {
+        sqlx::query_as!(
+            EventGroupRaw,
+            r#"
+            SELECT group_uid, calendar_uid, user_uid, account_uid, parent_id, external_id FROM events_groups AS g
+            WHERE g.group_uid = $1
+            "#,
+            group_id.as_ref(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                group_uid = %group_id,
+                error = ?err,
+                "Failed to find event group with id"
+            );
+        })?
+        .map(|g| g.try_into())
+        .transpose()
+    }
+
+    #[instrument(name = "event_group::get_by_external_id", fields(account_uid = %account_id, external_id = %external_id))]
+    async fn get_by_external_id(
+        &self,
+        account_id: &ID,
+        external_id: &str,
+    ) -> anyhow::Result<Option<EventGroup>> {
+        sqlx::query_as!(
+            EventGroupRaw,
+            r#"
+            SELECT group_uid, calendar_uid, user_uid, account_uid, parent_id, external_id FROM events_groups AS g
+            WHERE g.account_uid = $1 AND g.external_id = $2
+            "#,
+            account_id.as_ref(),
+            external_id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                account_uid = %account_id,
+                external_id = %external_id,
+                error = ?err,
+                "Failed to find event group with external_id"
+            );
+        })?
+        .map(|g| g.try_into())
+        .transpose()
+    }
+
+    #[instrument(name = "event_group::find_by_parent_id", fields(account_uid = %account_id, parent_id = %parent_id))]
+    async fn find_by_parent_id(
+        &self,
+        account_id: &ID,
+        parent_id: &str,
+    ) -> anyhow::Result<Vec<EventGroup>> {
+        sqlx::query_as!(
+            EventGroupRaw,
+            r#"
+            SELECT group_uid, calendar_uid, user_uid, account_uid, parent_id, external_id FROM events_groups AS g
+            WHERE g.account_uid = $1 AND g.parent_id = $2
+            "#,
+            account_id.as_ref(),
+            parent_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                account_uid = %account_id,
+                parent_id = %parent_id,
+                error = ?err,
+                "Failed to find event groups with parent_id"
+            );
+        })?
+        .into_iter()
+        .map(|g| g.try_into())
+        .collect()
+    }
+
+    /// Delete an event group by its uid
+    /// The events of the group are not deleted, they are only detached from the group
+    #[instrument(name = "event_group::delete", fields(group_uid = %group_id))]
+    async fn delete(&self, group_id: &ID) -> anyhow::Result<()> {
+        let group = sqlx::query!(
+            r#"
+            DELETE FROM events_groups AS g
+            WHERE g.group_uid = $1
+            RETURNING group_uid
+            "#,
+            group_id.as_ref(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                group_uid = %group_id,
+                error = ?err,
+                "Failed to delete event group"
+            );
+        })?;
+
+        if group.is_none() {
+            tracing::warn!(
+                "Tried to delete event group with id: {:?}, but it does not exist",
+                group_id
+            );
+        }
+        Ok(())
+    }
+}
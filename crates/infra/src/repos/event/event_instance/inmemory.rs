@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use nittei_domain::{EventInstance, ID, MaterializedEventInstances, TimeSpan};
+
+use super::IEventInstancesRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InstancesWindow};
+
+#[derive(Debug)]
+pub struct InMemoryEventInstancesRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryEventInstancesRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IEventInstancesRepo for InMemoryEventInstancesRepo {
+    async fn replace(
+        &self,
+        materialized: &MaterializedEventInstances,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+
+        // Only if the event still has the version and the exceptions the instances are materialized from
+        let Some(event) = tables.events.get(materialized.event_id.as_ref()) else {
+            return Ok(false);
+        };
+        let mut exceptions = materialized.exceptions.clone();
+        exceptions.sort();
+        let mut current_exceptions = tables
+            .events
+            .values()
+            .filter(|e| e.recurring_event_id.as_ref() == Some(&event.id))
+            .filter_map(|e| e.original_start_time)
+            .collect::<Vec<_>>();
+        current_exceptions.sort();
+        if event.version != materialized.event_version || current_exceptions != exceptions {
+            return Ok(false);
+        }
+
+        let mut instances = std::collections::BTreeMap::new();
+        for instance in &materialized.instances {
+            instances
+                .entry(instance.start_time)
+                .or_insert(instance.end_time);
+        }
+        tables.event_instances.insert(
+            *materialized.event_id.as_ref(),
+            InstancesWindow {
+                window_start: materialized.window.start(),
+                window_end: materialized.window.end(),
+                refreshed_at: now,
+                instances,
+            },
+        );
+
+        Ok(true)
+    }
+
+    async fn find_by_events(
+        &self,
+        event_ids: &[ID],
+        timespan: TimeSpan,
+    ) -> anyhow::Result<HashMap<ID, Vec<EventInstance>>> {
+        let tables = self.store.lock().await;
+        Ok(event_ids
+            .iter()
+            .filter_map(|event_id| {
+                let window = tables.event_instances.get(event_id.as_ref())?;
+                if window.window_start > timespan.start() || window.window_end < timespan.end() {
+                    return None;
+                }
+                let instances = window
+                    .instances
+                    .range(timespan.start()..=timespan.end())
+                    .map(|(start_time, end_time)| EventInstance {
+                        start_time: *start_time,
+                        end_time: *end_time,
+                        busy: true,
+                    })
+                    .collect();
+                Some((event_id.clone(), instances))
+            })
+            .collect())
+    }
+
+    async fn find_events_to_refresh(
+        &self,
+        refreshed_before: DateTime<Utc>,
+        ended_after: DateTime<Utc>,
+        after: Option<ID>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<ID>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .events
+            .iter()
+            .filter(|(id, _)| after.as_ref().is_none_or(|after| *id > after.as_ref()))
+            .filter(|(_, e)| {
+                e.recurrence.is_some() && e.recurring_until.is_none_or(|until| until >= ended_after)
+            })
+            .filter(|(id, _)| {
+                tables
+                    .event_instances
+                    .get(*id)
+                    .is_none_or(|w| w.refreshed_at < refreshed_before)
+            })
+            .take(usize::try_from(limit)?)
+            .map(|(_, e)| e.id.clone())
+            .collect())
+    }
+}
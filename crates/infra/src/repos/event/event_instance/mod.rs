@@ -0,0 +1,237 @@
+mod inmemory;
+mod postgres;
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryEventInstancesRepo;
+use nittei_domain::{EventInstance, ID, MaterializedEventInstances, TimeSpan};
+pub use postgres::PostgresEventInstancesRepo;
+
+#[async_trait::async_trait]
+pub trait IEventInstancesRepo: Send + Sync {
+    /// Replace the materialized instances of the event
+    /// Returns false (and doesn't replace them) if the event doesn't have anymore the version and the exceptions
+    /// they were materialized from
+    async fn replace(
+        &self,
+        materialized: &MaterializedEventInstances,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<bool>;
+    /// Find the materialized instances of the events starting in the timespan
+    /// Only the events with materialized instances for the whole timespan are returned
+    ///
+    /// The instances are busy, the caller is responsible for applying the busy flag of the events
+    async fn find_by_events(
+        &self,
+        event_ids: &[ID],
+        timespan: TimeSpan,
+    ) -> anyhow::Result<HashMap<ID, Vec<EventInstance>>>;
+    /// Find the recurring events not ended before `ended_after` which don't have materialized instances,
+    /// or have instances refreshed before `refreshed_before` (ordered by their ids, after the `after` event)
+    async fn find_events_to_refresh(
+        &self,
+        refreshed_before: DateTime<Utc>,
+        ended_after: DateTime<Utc>,
+        after: Option<ID>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<ID>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta, Utc};
+    use nittei_domain::{
+        Account,
+        Calendar,
+        CalendarEvent,
+        ID,
+        MaterializedEventInstances,
+        RRuleOptions,
+        TimeSpan,
+        User,
+    };
+
+    use crate::{NitteiContext, backend_tests};
+
+    backend_tests!(replace_find_and_remove_on_change);
+
+    /// Whether the event is among the events to refresh, going through all the pages
+    /// (the database can have the events of the other tests)
+    async fn needs_refresh(
+        ctx: &NitteiContext,
+        event_id: &ID,
+        refreshed_before: DateTime<Utc>,
+        ended_after: DateTime<Utc>,
+    ) -> bool {
+        let mut after = None;
+        loop {
+            let to_refresh = ctx
+                .repos
+                .event_instances
+                .find_events_to_refresh(refreshed_before, ended_after, after, 1000)
+                .await
+                .unwrap();
+            if to_refresh.contains(event_id) {
+                return true;
+            }
+            match to_refresh.last() {
+                Some(last) => after = Some(last.clone()),
+                None => return false,
+            }
+        }
+    }
+
+    async fn replace_find_and_remove_on_change(ctx: NitteiContext) {
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.unwrap();
+        let calendar = Calendar::new(&user.id, &account.id, None, None);
+        ctx.repos.calendars.insert(&calendar).await.unwrap();
+
+        let start_time = DateTime::from_timestamp_millis(1000 * 60 * 60 * 24 * 365).unwrap();
+        let one_day = TimeDelta::days(1);
+        let mut event = CalendarEvent {
+            account_id: account.id.clone(),
+            calendar_id: calendar.id.clone(),
+            user_id: user.id.clone(),
+            start_time,
+            duration: 1000 * 60 * 60,
+            busy: true,
+            ..Default::default()
+        };
+        event
+            .set_recurrence(RRuleOptions {
+                count: Some(10),
+                ..Default::default()
+            })
+            .unwrap();
+        ctx.repos.events.insert(&event).await.unwrap();
+
+        let window = TimeSpan::new(start_time, start_time + one_day * 30);
+        let materialized =
+            MaterializedEventInstances::new(&calendar, &event, Vec::new(), window, 100).unwrap();
+        assert!(
+            ctx.repos
+                .event_instances
+                .replace(&materialized, start_time)
+                .await
+                .unwrap()
+        );
+
+        // Only the instances starting in the timespan are found
+        let timespan = TimeSpan::new(start_time + one_day, start_time + one_day * 3);
+        let found = ctx
+            .repos
+            .event_instances
+            .find_by_events(std::slice::from_ref(&event.id), timespan.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            found.get(&event.id),
+            Some(&event.expand(Some(timespan), &calendar.settings).unwrap())
+        );
+
+        // The timespan must be within the window
+        let found = ctx
+            .repos
+            .event_instances
+            .find_by_events(
+                std::slice::from_ref(&event.id),
+                TimeSpan::new(start_time - one_day, start_time + one_day),
+            )
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+
+        // Not replaced if the event changed since
+        let mut outdated = materialized.clone();
+        outdated.event_version -= 1;
+        assert!(
+            !ctx.repos
+                .event_instances
+                .replace(&outdated, start_time)
+                .await
+                .unwrap()
+        );
+        let mut outdated = materialized.clone();
+        outdated.exceptions = vec![start_time];
+        assert!(
+            !ctx.repos
+                .event_instances
+                .replace(&outdated, start_time)
+                .await
+                .unwrap()
+        );
+
+        // Refreshed recently, so it doesn't need to be refreshed
+        assert!(!needs_refresh(&ctx, &event.id, start_time, start_time).await);
+        assert!(needs_refresh(&ctx, &event.id, start_time + one_day, start_time).await);
+
+        // A new exception of the event removes its instances
+        let exception = CalendarEvent {
+            recurring_event_id: Some(event.id.clone()),
+            original_start_time: Some(start_time + one_day),
+            recurrence: None,
+            ..event.clone()
+        };
+        let exception = CalendarEvent {
+            id: Default::default(),
+            ..exception
+        };
+        ctx.repos.events.insert(&exception).await.unwrap();
+        let found = ctx
+            .repos
+            .event_instances
+            .find_by_events(
+                std::slice::from_ref(&event.id),
+                TimeSpan::new(start_time, start_time + one_day),
+            )
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+        assert!(needs_refresh(&ctx, &event.id, start_time, start_time).await);
+
+        // Materialized again with the exception
+        let materialized = MaterializedEventInstances::new(
+            &calendar,
+            &event,
+            vec![start_time + one_day],
+            TimeSpan::new(start_time, start_time + one_day * 30),
+            100,
+        )
+        .unwrap();
+        assert!(
+            ctx.repos
+                .event_instances
+                .replace(&materialized, start_time)
+                .await
+                .unwrap()
+        );
+        let found = ctx
+            .repos
+            .event_instances
+            .find_by_events(
+                std::slice::from_ref(&event.id),
+                TimeSpan::new(start_time, start_time + one_day * 30),
+            )
+            .await
+            .unwrap();
+        assert_eq!(found.get(&event.id).map(Vec::len), Some(9));
+
+        // An update of the event removes its instances
+        event.duration *= 2;
+        ctx.repos.events.save(&event).await.unwrap();
+        let found = ctx
+            .repos
+            .event_instances
+            .find_by_events(
+                std::slice::from_ref(&event.id),
+                TimeSpan::new(start_time, start_time + one_day),
+            )
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+    }
+}
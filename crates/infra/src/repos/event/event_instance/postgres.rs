@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use nittei_domain::{EventInstance, ID, MaterializedEventInstances, TimeSpan};
+use sqlx::PgPool;
+use tracing::{error, instrument};
+
+use super::IEventInstancesRepo;
+
+#[derive(Debug)]
+pub struct PostgresEventInstancesRepo {
+    pool: PgPool,
+}
+
+impl PostgresEventInstancesRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait::async_trait]
+impl IEventInstancesRepo for PostgresEventInstancesRepo {
+    #[instrument(name = "event_instance::replace", skip(materialized), fields(event_uid = %materialized.event_id))]
+    async fn replace(
+        &self,
+        materialized: &MaterializedEventInstances,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM calendar_event_instance_windows
+            WHERE event_uid = $1
+            "#,
+            materialized.event_id.as_ref(),
+        )
+        .execute(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                event_id = %materialized.event_id,
+                error = ?err,
+                "Failed to delete the materialized instances of the event"
+            );
+        })?;
+
+        // Only if the event still has the version and the exceptions the instances are materialized from
+        // The event is locked, so that a concurrent change of the event removes the instances once replaced
+        let mut exceptions = materialized.exceptions.clone();
+        exceptions.sort();
+        let window = sqlx::query!(
+            r#"
+            INSERT INTO calendar_event_instance_windows(event_uid, window_start, window_end, refreshed_at)
+            SELECT e.event_uid, $2, $3, $4
+            FROM calendar_events AS e
+            WHERE e.event_uid = $1
+                AND e.version = $5
+                AND (
+                    SELECT COALESCE(array_agg(x.original_start_time ORDER BY x.original_start_time), '{}')
+                    FROM calendar_events AS x
+                    WHERE x.recurring_event_uid = e.event_uid AND x.original_start_time IS NOT NULL
+                ) = $6::timestamptz[]
+            FOR UPDATE OF e
+            RETURNING event_uid
+            "#,
+            materialized.event_id.as_ref(),
+            materialized.window.start(),
+            materialized.window.end(),
+            now,
+            materialized.event_version,
+            &exceptions,
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                event_id = %materialized.event_id,
+                error = ?err,
+                "Failed to insert the materialized instances window of the event"
+            );
+        })?;
+        if window.is_none() {
+            return Ok(false);
+        }
+
+        let (start_times, end_times): (Vec<_>, Vec<_>) = materialized
+            .instances
+            .iter()
+            .map(|instance| (instance.start_time, instance.end_time))
+            .unzip();
+        sqlx::query!(
+            r#"
+            INSERT INTO calendar_event_instances(event_uid, start_time, end_time)
+            SELECT $1, start_time, end_time
+            FROM unnest($2::timestamptz[], $3::timestamptz[]) AS i(start_time, end_time)
+            ON CONFLICT DO NOTHING
+            "#,
+            materialized.event_id.as_ref(),
+            &start_times,
+            &end_times,
+        )
+        .execute(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                event_id = %materialized.event_id,
+                error = ?err,
+                "Failed to insert the materialized instances of the event"
+            );
+        })?;
+
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    #[instrument(name = "event_instance::find_by_events", skip(event_ids))]
+    async fn find_by_events(
+        &self,
+        event_ids: &[ID],
+        timespan: TimeSpan,
+    ) -> anyhow::Result<HashMap<ID, Vec<EventInstance>>> {
+        let ids = event_ids.iter().map(|id| *id.as_ref()).collect::<Vec<_>>();
+        let rows = sqlx::query!(
+            r#"
+            SELECT w.event_uid, i.start_time AS "start_time?", i.end_time AS "end_time?"
+            FROM calendar_event_instance_windows AS w
+            LEFT JOIN calendar_event_instances AS i
+                ON i.event_uid = w.event_uid AND i.start_time >= $2 AND i.start_time <= $3
+            WHERE w.event_uid = ANY($1)
+                AND w.window_start <= $2
+                AND w.window_end >= $3
+            ORDER BY w.event_uid, i.start_time
+            "#,
+            &ids,
+            timespan.start(),
+            timespan.end(),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to find the materialized instances of the events"
+            );
+        })?;
+
+        let mut instances: HashMap<ID, Vec<EventInstance>> = HashMap::new();
+        for row in rows {
+            let event_instances = instances.entry(row.event_uid.into()).or_default();
+            if let (Some(start_time), Some(end_time)) = (row.start_time, row.end_time) {
+                event_instances.push(EventInstance {
+                    start_time,
+                    end_time,
+                    busy: true,
+                });
+            }
+        }
+
+        Ok(instances)
+    }
+
+    #[instrument(name = "event_instance::find_events_to_refresh")]
+    async fn find_events_to_refresh(
+        &self,
+        refreshed_before: DateTime<Utc>,
+        ended_after: DateTime<Utc>,
+        after: Option<ID>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<ID>> {
+        let event_ids = sqlx::query_scalar!(
+            r#"
+            SELECT e.event_uid
+            FROM calendar_events AS e
+            LEFT JOIN calendar_event_instance_windows AS w ON w.event_uid = e.event_uid
+            WHERE e.recurrence_jsonb IS NOT NULL
+                AND (e.recurring_until IS NULL OR e.recurring_until >= $2)
+                AND (w.event_uid IS NULL OR w.refreshed_at < $1)
+                AND ($3::uuid IS NULL OR e.event_uid > $3)
+            ORDER BY e.event_uid
+            LIMIT $4
+            "#,
+            refreshed_before,
+            ended_after,
+            after.as_ref().map(|id| *id.as_ref()),
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to find the events to materialize the instances of"
+            );
+        })?;
+
+        Ok(event_ids.into_iter().map(ID::from).collect())
+    }
+}
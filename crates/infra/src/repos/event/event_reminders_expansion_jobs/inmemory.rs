@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::EventRemindersExpansionJob;
+
+use super::IEventRemindersGenerationJobsRepo;
+use crate::repos::shared::inmemory::InMemoryStore;
+
+#[derive(Debug)]
+pub struct InMemoryEventReminderGenerationJobsRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryEventReminderGenerationJobsRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IEventRemindersGenerationJobsRepo for InMemoryEventReminderGenerationJobsRepo {
+    async fn bulk_insert(&self, jobs: &[EventRemindersExpansionJob]) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        for job in jobs {
+            if tables.reminder_versions.get(job.event_id.as_ref()) != Some(&job.version) {
+                anyhow::bail!(
+                    "The version: {} of the reminders of the event with id: {} doesn't exist",
+                    job.version,
+                    job.event_id
+                );
+            }
+            // There can only be one job at the time for an event
+            if tables
+                .reminders_generation_jobs
+                .iter()
+                .any(|j| j.event_id == job.event_id)
+            {
+                anyhow::bail!(
+                    "The event with id: {} already has a reminders generation job",
+                    job.event_id
+                );
+            }
+            tables.reminders_generation_jobs.push(job.clone());
+        }
+        Ok(())
+    }
+
+    async fn delete_all_before(
+        &self,
+        before: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<EventRemindersExpansionJob>> {
+        let mut tables = self.store.lock().await;
+        let (deleted, kept) = std::mem::take(&mut tables.reminders_generation_jobs)
+            .into_iter()
+            .partition(|j| j.timestamp <= before);
+        tables.reminders_generation_jobs = kept;
+        Ok(deleted)
+    }
+}
@@ -1,6 +1,8 @@
+mod inmemory;
 mod postgres;
 
 use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryEventReminderGenerationJobsRepo;
 use nittei_domain::EventRemindersExpansionJob;
 pub use postgres::PostgresEventReminderGenerationJobsRepo;
 
@@ -19,11 +21,11 @@ mod tests {
     use nittei_domain::{Account, Calendar, CalendarEvent, EventRemindersExpansionJob, User};
     use tracing::error;
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn crud() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(crud);
+
+    async fn crud(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
         let user = User::new(account.id.clone(), None);
@@ -0,0 +1,59 @@
+use nittei_domain::{ID, SyncedCalendarEvent};
+
+use super::IEventSyncedRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryEventSyncedRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryEventSyncedRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IEventSyncedRepo for InMemoryEventSyncedRepo {
+    async fn insert(&self, e: &SyncedCalendarEvent) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.events, &e.event_id, "event")?;
+        if !tables.synced_calendars.iter().any(|c| {
+            c.calendar_id == e.calendar_id
+                && c.provider == e.provider
+                && c.ext_calendar_id == e.ext_calendar_id
+        }) {
+            anyhow::bail!("The calendar with id: {} is not synced", e.calendar_id);
+        }
+        if tables.synced_events.iter().any(|s| {
+            s.event_id == e.event_id
+                && s.provider == e.provider
+                && s.ext_calendar_id == e.ext_calendar_id
+                && s.ext_event_id == e.ext_event_id
+        }) {
+            anyhow::bail!("The event with id: {} is already synced", e.event_id);
+        }
+        tables.synced_events.push(e.clone());
+        Ok(())
+    }
+
+    async fn find_by_event(&self, event_id: &ID) -> anyhow::Result<Vec<SyncedCalendarEvent>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .synced_events
+            .iter()
+            .filter(|e| &e.event_id == event_id)
+            .filter_map(|e| {
+                // The user is the one of the calendar
+                tables
+                    .calendars
+                    .get(e.calendar_id.as_ref())
+                    .map(|c| SyncedCalendarEvent {
+                        user_id: c.user_id.clone(),
+                        ..e.clone()
+                    })
+            })
+            .collect())
+    }
+}
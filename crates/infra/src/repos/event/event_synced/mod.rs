@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryEventSyncedRepo;
 use nittei_domain::{ID, SyncedCalendarEvent};
 pub use postgres::PostgresEventSyncedRepo;
 
@@ -23,12 +25,11 @@ mod tests {
         UserIntegration,
     };
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn test_event_synced_repo() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(test_event_synced_repo);
 
+    async fn test_event_synced_repo(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
@@ -1,18 +1,34 @@
 mod calendar_event;
+mod event_group;
+mod event_instance;
 mod event_reminders_expansion_jobs;
 mod event_synced;
 mod reminder;
 
 pub use calendar_event::{
     IEventRepo,
+    InMemoryEventRepo,
     PostgresEventRepo,
     SearchEventsForAccountParams,
     SearchEventsForUserParams,
     SearchEventsParams,
+    SearchEventsResult,
+    ServiceBooking,
+    ServiceBookingCancellation,
+    ServiceBookingHost,
+    ServiceBookingOutcome,
+    TextSearchQuery,
+};
+pub use event_group::{IEventGroupRepo, InMemoryEventGroupRepo, PostgresEventGroupRepo};
+pub use event_instance::{
+    IEventInstancesRepo,
+    InMemoryEventInstancesRepo,
+    PostgresEventInstancesRepo,
 };
 pub use event_reminders_expansion_jobs::{
     IEventRemindersGenerationJobsRepo,
+    InMemoryEventReminderGenerationJobsRepo,
     PostgresEventReminderGenerationJobsRepo,
 };
-pub use event_synced::{IEventSyncedRepo, PostgresEventSyncedRepo};
-pub use reminder::{IReminderRepo, PostgresReminderRepo};
+pub use event_synced::{IEventSyncedRepo, InMemoryEventSyncedRepo, PostgresEventSyncedRepo};
+pub use reminder::{IReminderRepo, InMemoryReminderRepo, PostgresReminderRepo};
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{ID, Reminder};
+
+use super::IReminderRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryReminderRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryReminderRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IReminderRepo for InMemoryReminderRepo {
+    async fn bulk_insert(&self, reminders: &[Reminder]) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        for reminder in reminders {
+            InMemoryTables::ensure_exists(&tables.accounts, &reminder.account_id, "account")?;
+            if tables.reminder_versions.get(reminder.event_id.as_ref()) != Some(&reminder.version) {
+                anyhow::bail!(
+                    "The version: {} of the reminders of the event with id: {} doesn't exist",
+                    reminder.version,
+                    reminder.event_id
+                );
+            }
+            if tables.reminders.iter().any(|r| {
+                r.event_id == reminder.event_id
+                    && r.remind_at == reminder.remind_at
+                    && r.identifier == reminder.identifier
+            }) {
+                anyhow::bail!(
+                    "The reminder {} of the event with id: {} already exists",
+                    reminder.identifier,
+                    reminder.event_id
+                );
+            }
+            tables.reminders.push(reminder.clone());
+        }
+        Ok(())
+    }
+
+    async fn init_version(&self, event_id: &ID) -> anyhow::Result<i64> {
+        // Incrementing initializes the version when there is none yet
+        self.inc_version(event_id).await
+    }
+
+    async fn inc_version(&self, event_id: &ID) -> anyhow::Result<i64> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.events, event_id, "event")?;
+
+        // The reminders and the generation jobs of the previous version are discarded
+        let version = tables
+            .reminder_versions
+            .get(event_id.as_ref())
+            .map_or(1, |version| version + 1);
+        tables.reminder_versions.insert(*event_id.as_ref(), version);
+        tables.reminders.retain(|r| &r.event_id != event_id);
+        tables
+            .reminders_generation_jobs
+            .retain(|j| &j.event_id != event_id);
+
+        Ok(version)
+    }
+
+    async fn delete_all_before(&self, before: DateTime<Utc>) -> anyhow::Result<Vec<Reminder>> {
+        let mut tables = self.store.lock().await;
+        let (deleted, kept) = std::mem::take(&mut tables.reminders)
+            .into_iter()
+            .partition(|r| r.remind_at <= before);
+        tables.reminders = kept;
+        Ok(deleted)
+    }
+}
@@ -1,13 +1,20 @@
+mod inmemory;
 mod postgres;
 
 use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryReminderRepo;
 use nittei_domain::{ID, Reminder};
 pub use postgres::PostgresReminderRepo;
 
 #[async_trait::async_trait]
 pub trait IReminderRepo: Send + Sync {
     async fn bulk_insert(&self, reminders: &[Reminder]) -> anyhow::Result<()>;
+    /// Init the version of the reminders of a new event
+    /// If it already exists (e.g. the creation is processed again after a failure),
+    /// it is incremented instead, so that the reminders generated before are discarded
     async fn init_version(&self, event_id: &ID) -> anyhow::Result<i64>;
+    /// Increment the version of the reminders of an event, the reminders generated before are discarded
+    /// The version is initialized if the event doesn't have one yet
     async fn inc_version(&self, event_id: &ID) -> anyhow::Result<i64>;
     async fn delete_all_before(&self, before: DateTime<Utc>) -> anyhow::Result<Vec<Reminder>>;
 }
@@ -17,11 +24,11 @@ mod tests {
     use chrono::DateTime;
     use nittei_domain::{Account, Calendar, CalendarEvent, Reminder, User};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn crud() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(crud);
+
+    async fn crud(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
         let user = User::new(account.id.clone(), None);
@@ -104,5 +111,14 @@ mod tests {
             .unwrap();
         // Reminders has been deleted because there is a new version now
         assert_eq!(delete_res.len(), 0);
+
+        // Init the version again (e.g. retried), it is incremented
+        let init_again_v = ctx
+            .repos
+            .reminders
+            .init_version(&event.id)
+            .await
+            .expect("To init reminder version again");
+        assert_eq!(init_again_v, new_e3_v + 1);
     }
 }
@@ -75,27 +75,8 @@ impl IReminderRepo for PostgresReminderRepo {
 
     #[instrument]
     async fn init_version(&self, event_id: &ID) -> anyhow::Result<i64> {
-        let r_version = sqlx::query_as!(
-            ReminderVersionRaw,
-            r#"
-            INSERT INTO event_reminder_versions
-                (event_uid)
-            VALUES
-                ($1)
-            RETURNING *
-            "#,
-            event_id.as_ref(),
-        )
-        .fetch_one(&self.pool)
-        .await
-        .inspect_err(|err| {
-            error!(
-                "Unable to insert calendar event reminder version for event id: {}. DB returned error: {:?}",
-                event_id, err
-            );
-        })?;
-
-        Ok(r_version.version)
+        // Incrementing initializes the version when there is none yet
+        self.inc_version(event_id).await
     }
 
     #[instrument]
@@ -110,7 +91,7 @@ impl IReminderRepo for PostgresReminderRepo {
             )
             INSERT INTO event_reminder_versions
                 (event_uid, version)
-            SELECT $1, version + 1 from prev_v
+            SELECT $1, COALESCE(MAX(version) + 1, 1) from prev_v
             RETURNING *
             "#,
             event_id.as_ref(),
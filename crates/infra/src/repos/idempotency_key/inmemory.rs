@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{IdempotencyKey, IdempotentResponse};
+
+use super::IIdempotencyKeyRepo;
+use crate::repos::shared::inmemory::InMemoryStore;
+
+#[derive(Debug)]
+pub struct InMemoryIdempotencyKeyRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryIdempotencyKeyRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IIdempotencyKeyRepo for InMemoryIdempotencyKeyRepo {
+    async fn insert(&self, key: &IdempotencyKey) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        let id = (key.scope.clone(), key.key.clone());
+        match tables.idempotency_keys.get(&id) {
+            Some(existing) if existing.expires_at > key.created_at => Ok(false),
+            _ => {
+                tables.idempotency_keys.insert(id, key.clone());
+                Ok(true)
+            }
+        }
+    }
+
+    async fn find(&self, scope: &str, key: &str) -> anyhow::Result<Option<IdempotencyKey>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .idempotency_keys
+            .get(&(scope.to_string(), key.to_string()))
+            .cloned())
+    }
+
+    async fn save_response(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &IdempotentResponse,
+    ) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(idempotency_key) = tables
+            .idempotency_keys
+            .get_mut(&(scope.to_string(), key.to_string()))
+        {
+            idempotency_key.response = Some(response.clone());
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, scope: &str, key: &str) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables
+            .idempotency_keys
+            .remove(&(scope.to_string(), key.to_string()));
+        Ok(())
+    }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let mut tables = self.store.lock().await;
+        let count = tables.idempotency_keys.len();
+        tables
+            .idempotency_keys
+            .retain(|_, idempotency_key| idempotency_key.expires_at > now);
+        Ok((count - tables.idempotency_keys.len()) as u64)
+    }
+}
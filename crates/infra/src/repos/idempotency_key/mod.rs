@@ -0,0 +1,100 @@
+mod inmemory;
+mod postgres;
+
+use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryIdempotencyKeyRepo;
+use nittei_domain::{IdempotencyKey, IdempotentResponse};
+pub use postgres::PostgresIdempotencyKeyRepo;
+
+#[async_trait::async_trait]
+pub trait IIdempotencyKeyRepo: Send + Sync {
+    /// Insert the key (without its response), or replace it if it has expired at `key.created_at`
+    /// Returns false if the key is already used (by a previous request, or one in progress)
+    async fn insert(&self, key: &IdempotencyKey) -> anyhow::Result<bool>;
+    async fn find(&self, scope: &str, key: &str) -> anyhow::Result<Option<IdempotencyKey>>;
+    /// Record the response of the request sent with the key
+    async fn save_response(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &IdempotentResponse,
+    ) -> anyhow::Result<()>;
+    async fn delete(&self, scope: &str, key: &str) -> anyhow::Result<()>;
+    /// Remove the keys expired at `now`
+    /// Returns the number of keys removed
+    async fn delete_expired(&self, now: DateTime<Utc>) -> anyhow::Result<u64>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta};
+    use nittei_domain::{ID, IdempotencyKey, IdempotentResponse};
+
+    use crate::{NitteiContext, backend_tests};
+
+    backend_tests!(insert_save_response_and_expire);
+
+    async fn insert_save_response_and_expire(ctx: NitteiContext) {
+        let repo = &ctx.repos.idempotency_keys;
+        // Unique scope, so that the tests don't share the keys
+        let scope = format!("test_scope_{}", ID::default());
+        let now = DateTime::from_timestamp_millis(1000 * 60 * 60 * 24).unwrap();
+        let ttl = TimeDelta::hours(1);
+        let key = IdempotencyKey {
+            scope: scope.clone(),
+            key: "key_1".into(),
+            request_hash: "hash_1".into(),
+            response: None,
+            created_at: now,
+            expires_at: now + ttl,
+        };
+
+        assert!(repo.insert(&key).await.unwrap());
+        assert_eq!(repo.find(&scope, "key_1").await.unwrap(), Some(key.clone()));
+        // Already used, even with another request
+        let retry = IdempotencyKey {
+            request_hash: "hash_2".into(),
+            created_at: now + TimeDelta::minutes(1),
+            expires_at: now + TimeDelta::minutes(1) + ttl,
+            ..key.clone()
+        };
+        assert!(!repo.insert(&retry).await.unwrap());
+
+        let response = IdempotentResponse {
+            status: 201,
+            content_type: Some("application/json".into()),
+            body: b"{\"id\":1}".to_vec(),
+        };
+        repo.save_response(&scope, "key_1", &response)
+            .await
+            .unwrap();
+        let saved = repo.find(&scope, "key_1").await.unwrap().unwrap();
+        assert_eq!(saved.request_hash, "hash_1");
+        assert_eq!(saved.response, Some(response));
+
+        // The keys are scoped
+        assert!(repo.find("other_scope", "key_1").await.unwrap().is_none());
+
+        // Replaced once expired
+        let expired = IdempotencyKey {
+            created_at: now + ttl,
+            expires_at: now + ttl * 2,
+            ..retry.clone()
+        };
+        assert!(repo.insert(&expired).await.unwrap());
+        assert_eq!(repo.find(&scope, "key_1").await.unwrap(), Some(expired));
+
+        // Removed once expired
+        let other_key = IdempotencyKey {
+            key: "key_2".into(),
+            ..key.clone()
+        };
+        assert!(repo.insert(&other_key).await.unwrap());
+        assert!(repo.delete_expired(now + ttl).await.unwrap() >= 1);
+        assert!(repo.find(&scope, "key_2").await.unwrap().is_none());
+        assert!(repo.find(&scope, "key_1").await.unwrap().is_some());
+
+        repo.delete(&scope, "key_1").await.unwrap();
+        assert!(repo.find(&scope, "key_1").await.unwrap().is_none());
+    }
+}
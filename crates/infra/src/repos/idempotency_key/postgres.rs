@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{IdempotencyKey, IdempotentResponse};
+use sqlx::{FromRow, PgPool};
+use tracing::{error, instrument};
+
+use super::IIdempotencyKeyRepo;
+
+#[derive(Debug)]
+pub struct PostgresIdempotencyKeyRepo {
+    pool: PgPool,
+}
+
+impl PostgresIdempotencyKeyRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct IdempotencyKeyRaw {
+    scope: String,
+    key: String,
+    request_hash: String,
+    response_status: Option<i16>,
+    response_content_type: Option<String>,
+    response_body: Option<Vec<u8>>,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<IdempotencyKeyRaw> for IdempotencyKey {
+    fn from(e: IdempotencyKeyRaw) -> Self {
+        Self {
+            scope: e.scope,
+            key: e.key,
+            request_hash: e.request_hash,
+            response: e.response_status.map(|status| IdempotentResponse {
+                status: status as u16,
+                content_type: e.response_content_type,
+                body: e.response_body.unwrap_or_default(),
+            }),
+            created_at: e.created_at,
+            expires_at: e.expires_at,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IIdempotencyKeyRepo for PostgresIdempotencyKeyRepo {
+    #[instrument(name = "idempotency_key::insert", skip_all, fields(key = %key.key))]
+    async fn insert(&self, key: &IdempotencyKey) -> anyhow::Result<bool> {
+        // The conflicting row is locked by the upsert, so only one request can replace an expired key
+        let inserted = sqlx::query!(
+            r#"
+            INSERT INTO idempotency_keys (scope, key, request_hash, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (scope, key) DO UPDATE SET
+                request_hash = EXCLUDED.request_hash,
+                response_status = NULL,
+                response_content_type = NULL,
+                response_body = NULL,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at
+            WHERE idempotency_keys.expires_at <= EXCLUDED.created_at
+            RETURNING key
+            "#,
+            key.scope,
+            key.key,
+            key.request_hash,
+            key.created_at,
+            key.expires_at,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                key = key.key,
+                error = ?err,
+                "Failed to insert the idempotency key"
+            );
+        })?;
+
+        Ok(inserted.is_some())
+    }
+
+    #[instrument(name = "idempotency_key::find", skip(scope))]
+    async fn find(&self, scope: &str, key: &str) -> anyhow::Result<Option<IdempotencyKey>> {
+        let idempotency_key = sqlx::query_as!(
+            IdempotencyKeyRaw,
+            r#"
+            SELECT * FROM idempotency_keys
+            WHERE scope = $1 AND key = $2
+            "#,
+            scope,
+            key,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                key,
+                error = ?err,
+                "Failed to find the idempotency key"
+            );
+        })?;
+
+        Ok(idempotency_key.map(|idempotency_key| idempotency_key.into()))
+    }
+
+    #[instrument(name = "idempotency_key::save_response", skip(scope, response))]
+    async fn save_response(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &IdempotentResponse,
+    ) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE idempotency_keys
+            SET response_status = $3, response_content_type = $4, response_body = $5
+            WHERE scope = $1 AND key = $2
+            "#,
+            scope,
+            key,
+            response.status as i16,
+            response.content_type,
+            response.body,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                key,
+                error = ?err,
+                "Failed to save the response of the idempotency key"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "idempotency_key::delete", skip(scope))]
+    async fn delete(&self, scope: &str, key: &str) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE scope = $1 AND key = $2
+            "#,
+            scope,
+            key,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                key,
+                error = ?err,
+                "Failed to delete the idempotency key"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "idempotency_key::delete_expired")]
+    async fn delete_expired(&self, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE expires_at <= $1
+            "#,
+            now,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to delete the expired idempotency keys"
+            );
+        })?;
+
+        Ok(res.rows_affected())
+    }
+}
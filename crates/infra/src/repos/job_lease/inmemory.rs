@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::JobLease;
+
+use super::IJobLeaseRepo;
+use crate::repos::shared::inmemory::InMemoryStore;
+
+#[derive(Debug)]
+pub struct InMemoryJobLeaseRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryJobLeaseRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IJobLeaseRepo for InMemoryJobLeaseRepo {
+    async fn acquire(
+        &self,
+        job: &str,
+        holder: &str,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        match tables.job_leases.get_mut(job) {
+            // Renewed by its holder
+            Some(lease) if lease.holder == holder => {
+                lease.expires_at = lease_until;
+                Ok(true)
+            }
+            // Taken over once expired
+            Some(lease) if lease.expires_at <= now => {
+                *lease = JobLease {
+                    job: job.to_string(),
+                    holder: holder.to_string(),
+                    acquired_at: now,
+                    expires_at: lease_until,
+                };
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => {
+                tables.job_leases.insert(
+                    job.to_string(),
+                    JobLease {
+                        job: job.to_string(),
+                        holder: holder.to_string(),
+                        acquired_at: now,
+                        expires_at: lease_until,
+                    },
+                );
+                Ok(true)
+            }
+        }
+    }
+
+    async fn find(&self, job: &str) -> anyhow::Result<Option<JobLease>> {
+        let tables = self.store.lock().await;
+        Ok(tables.job_leases.get(job).cloned())
+    }
+}
@@ -0,0 +1,109 @@
+mod inmemory;
+mod postgres;
+
+use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryJobLeaseRepo;
+use nittei_domain::JobLease;
+pub use postgres::PostgresJobLeaseRepo;
+
+#[async_trait::async_trait]
+pub trait IJobLeaseRepo: Send + Sync {
+    /// Acquire (or renew) the lease of the job for `holder` until `lease_until`
+    /// Returns false if the lease is held by another instance, and doesn't expire before `now`
+    async fn acquire(
+        &self,
+        job: &str,
+        holder: &str,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> anyhow::Result<bool>;
+    async fn find(&self, job: &str) -> anyhow::Result<Option<JobLease>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta};
+    use nittei_domain::ID;
+
+    use crate::{NitteiContext, backend_tests};
+
+    backend_tests!(acquire_renew_and_take_over);
+
+    async fn acquire_renew_and_take_over(ctx: NitteiContext) {
+        // Unique job name, so that the tests don't share the lease
+        let job = format!("test_job_{}", ID::default());
+        let now = DateTime::from_timestamp_millis(1000 * 60 * 60 * 24).unwrap();
+        let lease_duration = TimeDelta::minutes(1);
+
+        assert!(
+            ctx.repos
+                .job_leases
+                .acquire(&job, "instance_1", now, now + lease_duration)
+                .await
+                .unwrap()
+        );
+        // Held by another instance
+        assert!(
+            !ctx.repos
+                .job_leases
+                .acquire(&job, "instance_2", now, now + lease_duration)
+                .await
+                .unwrap()
+        );
+
+        // Renewed by the holder
+        let renewed_at = now + TimeDelta::seconds(30);
+        assert!(
+            ctx.repos
+                .job_leases
+                .acquire(&job, "instance_1", renewed_at, renewed_at + lease_duration)
+                .await
+                .unwrap()
+        );
+        let lease = ctx.repos.job_leases.find(&job).await.unwrap().unwrap();
+        assert_eq!(lease.holder, "instance_1");
+        assert_eq!(lease.acquired_at, now);
+        assert_eq!(lease.expires_at, renewed_at + lease_duration);
+        assert!(
+            !ctx.repos
+                .job_leases
+                .acquire(
+                    &job,
+                    "instance_2",
+                    now + lease_duration,
+                    now + lease_duration * 2
+                )
+                .await
+                .unwrap()
+        );
+
+        // Taken over once expired (e.g. the holder crashed)
+        let expired_at = renewed_at + lease_duration;
+        assert!(
+            ctx.repos
+                .job_leases
+                .acquire(&job, "instance_2", expired_at, expired_at + lease_duration)
+                .await
+                .unwrap()
+        );
+        let lease = ctx.repos.job_leases.find(&job).await.unwrap().unwrap();
+        assert_eq!(lease.holder, "instance_2");
+        assert_eq!(lease.acquired_at, expired_at);
+        assert!(
+            !ctx.repos
+                .job_leases
+                .acquire(&job, "instance_1", expired_at, expired_at + lease_duration)
+                .await
+                .unwrap()
+        );
+
+        assert!(
+            ctx.repos
+                .job_leases
+                .find("unknown_job")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::JobLease;
+use sqlx::{FromRow, PgPool};
+use tracing::{error, instrument};
+
+use super::IJobLeaseRepo;
+
+#[derive(Debug)]
+pub struct PostgresJobLeaseRepo {
+    pool: PgPool,
+}
+
+impl PostgresJobLeaseRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct JobLeaseRaw {
+    job_name: String,
+    holder: String,
+    acquired_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<JobLeaseRaw> for JobLease {
+    fn from(e: JobLeaseRaw) -> Self {
+        Self {
+            job: e.job_name,
+            holder: e.holder,
+            acquired_at: e.acquired_at,
+            expires_at: e.expires_at,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl IJobLeaseRepo for PostgresJobLeaseRepo {
+    #[instrument]
+    async fn acquire(
+        &self,
+        job: &str,
+        holder: &str,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        // The conflicting row is locked by the upsert, so only one instance can take over an expired lease
+        let acquired = sqlx::query!(
+            r#"
+            INSERT INTO job_leases (job_name, holder, acquired_at, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (job_name) DO UPDATE SET
+                holder = EXCLUDED.holder,
+                acquired_at = CASE
+                    WHEN job_leases.holder = EXCLUDED.holder THEN job_leases.acquired_at
+                    ELSE EXCLUDED.acquired_at
+                END,
+                expires_at = EXCLUDED.expires_at
+            WHERE job_leases.holder = EXCLUDED.holder OR job_leases.expires_at <= $3
+            RETURNING job_name
+            "#,
+            job,
+            holder,
+            now,
+            lease_until,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Unable to acquire the lease of the job: {}. DB returned error: {:?}",
+                job, e
+            );
+        })?;
+
+        Ok(acquired.is_some())
+    }
+
+    #[instrument]
+    async fn find(&self, job: &str) -> anyhow::Result<Option<JobLease>> {
+        let lease = sqlx::query_as!(
+            JobLeaseRaw,
+            r#"
+            SELECT * FROM job_leases
+            WHERE job_name = $1
+            "#,
+            job,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|e| {
+            error!(
+                "Unable to find the lease of the job: {}. DB returned error: {:?}",
+                job, e
+            );
+        })?;
+
+        Ok(lease.map(|lease| lease.into()))
+    }
+}
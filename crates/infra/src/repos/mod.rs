@@ -1,8 +1,12 @@
 mod account;
 mod account_integrations;
 mod calendar;
+mod calendar_feed;
 mod calendar_synced;
 mod event;
+mod idempotency_key;
+mod job_lease;
+mod outbox;
 mod reservation;
 mod schedule;
 mod service;
@@ -12,40 +16,88 @@ mod shared;
 mod status;
 pub(crate) mod user;
 mod user_integrations;
+mod webhook_delivery;
 
 use std::{sync::Arc, time::Duration};
 
-use account::{IAccountRepo, PostgresAccountRepo};
-use account_integrations::{IAccountIntegrationRepo, PostgresAccountIntegrationRepo};
+use account::{IAccountRepo, InMemoryAccountRepo, PostgresAccountRepo};
+use account_integrations::{
+    IAccountIntegrationRepo,
+    InMemoryAccountIntegrationRepo,
+    PostgresAccountIntegrationRepo,
+};
 use anyhow::Context;
-use calendar::{ICalendarRepo, PostgresCalendarRepo};
-use calendar_synced::{ICalendarSyncedRepo, PostgresCalendarSyncedRepo};
+use calendar::{ICalendarRepo, InMemoryCalendarRepo, PostgresCalendarRepo};
+use calendar_feed::{ICalendarFeedRepo, InMemoryCalendarFeedRepo, PostgresCalendarFeedRepo};
+use calendar_synced::{
+    ICalendarSyncedRepo,
+    InMemoryCalendarSyncedRepo,
+    PostgresCalendarSyncedRepo,
+};
 use event::{
+    IEventGroupRepo,
+    IEventInstancesRepo,
     IEventRemindersGenerationJobsRepo,
     IEventRepo,
     IEventSyncedRepo,
     IReminderRepo,
+    InMemoryEventGroupRepo,
+    InMemoryEventInstancesRepo,
+    InMemoryEventReminderGenerationJobsRepo,
+    InMemoryEventRepo,
+    InMemoryEventSyncedRepo,
+    InMemoryReminderRepo,
+    PostgresEventGroupRepo,
+    PostgresEventInstancesRepo,
     PostgresEventReminderGenerationJobsRepo,
     PostgresEventRepo,
     PostgresEventSyncedRepo,
     PostgresReminderRepo,
 };
-pub use event::{SearchEventsForAccountParams, SearchEventsForUserParams, SearchEventsParams};
-use reservation::{IReservationRepo, PostgresReservationRepo};
-use schedule::{IScheduleRepo, PostgresScheduleRepo};
-use service::{IServiceRepo, PostgresServiceRepo};
-use service_user::{IServiceUserRepo, PostgresServiceUserRepo};
+pub use event::{
+    SearchEventsForAccountParams,
+    SearchEventsForUserParams,
+    SearchEventsParams,
+    SearchEventsResult,
+    ServiceBooking,
+    ServiceBookingCancellation,
+    ServiceBookingHost,
+    ServiceBookingOutcome,
+    TextSearchQuery,
+};
+use idempotency_key::{
+    IIdempotencyKeyRepo,
+    InMemoryIdempotencyKeyRepo,
+    PostgresIdempotencyKeyRepo,
+};
+use job_lease::{IJobLeaseRepo, InMemoryJobLeaseRepo, PostgresJobLeaseRepo};
+use outbox::{IOutboxRepo, InMemoryOutboxRepo, PostgresOutboxRepo};
+use reservation::{IReservationRepo, InMemoryReservationRepo, PostgresReservationRepo};
+use schedule::{IScheduleRepo, InMemoryScheduleRepo, PostgresScheduleRepo};
+use service::{IServiceRepo, InMemoryServiceRepo, PostgresServiceRepo};
+use service_user::{IServiceUserRepo, InMemoryServiceUserRepo, PostgresServiceUserRepo};
 pub use service_user_busy_calendars::{BusyCalendarIdentifier, ExternalBusyCalendarIdentifier};
 use service_user_busy_calendars::{
     IServiceUserBusyCalendarRepo,
+    InMemoryServiceUserBusyCalendarRepo,
     PostgresServiceUseBusyCalendarRepo,
 };
+use shared::inmemory::InMemoryStore;
 pub use shared::query_structs::*;
 use sqlx::{Pool, Postgres, migrate::MigrateError, postgres::PgPoolOptions};
-use status::{IStatusRepo, PostgresStatusRepo};
+use status::{IStatusRepo, InMemoryStatusRepo, PostgresStatusRepo};
 use tracing::{error, info};
-use user::{IUserRepo, PostgresUserRepo};
-use user_integrations::{IUserIntegrationRepo, PostgresUserIntegrationRepo};
+use user::{IUserRepo, InMemoryUserRepo, PostgresUserRepo};
+use user_integrations::{
+    IUserIntegrationRepo,
+    InMemoryUserIntegrationRepo,
+    PostgresUserIntegrationRepo,
+};
+use webhook_delivery::{
+    IWebhookDeliveryRepo,
+    InMemoryWebhookDeliveryRepo,
+    PostgresWebhookDeliveryRepo,
+};
 
 use crate::metrics::{register_metrics, update_connection_pool_metrics};
 
@@ -83,9 +135,15 @@ pub struct Repos {
     pub account_integrations: Arc<dyn IAccountIntegrationRepo>,
     pub calendars: Arc<dyn ICalendarRepo>,
     pub calendar_synced: Arc<dyn ICalendarSyncedRepo>,
+    pub calendar_feeds: Arc<dyn ICalendarFeedRepo>,
     pub events: Arc<dyn IEventRepo>,
+    pub event_groups: Arc<dyn IEventGroupRepo>,
+    pub event_instances: Arc<dyn IEventInstancesRepo>,
     pub event_reminders_generation_jobs: Arc<dyn IEventRemindersGenerationJobsRepo>,
     pub event_synced: Arc<dyn IEventSyncedRepo>,
+    pub idempotency_keys: Arc<dyn IIdempotencyKeyRepo>,
+    pub job_leases: Arc<dyn IJobLeaseRepo>,
+    pub outbox: Arc<dyn IOutboxRepo>,
     pub schedules: Arc<dyn IScheduleRepo>,
     pub reminders: Arc<dyn IReminderRepo>,
     pub reservations: Arc<dyn IReservationRepo>,
@@ -95,6 +153,7 @@ pub struct Repos {
     pub status: Arc<dyn IStatusRepo>,
     pub users: Arc<dyn IUserRepo>,
     pub user_integrations: Arc<dyn IUserIntegrationRepo>,
+    pub webhook_deliveries: Arc<dyn IWebhookDeliveryRepo>,
 }
 
 pub async fn create_postgres_pool(connection_string: &str) -> anyhow::Result<Pool<Postgres>> {
@@ -166,8 +225,14 @@ impl Repos {
             account_integrations: Arc::new(PostgresAccountIntegrationRepo::new(pool.clone())),
             calendars: Arc::new(PostgresCalendarRepo::new(pool.clone())),
             calendar_synced: Arc::new(PostgresCalendarSyncedRepo::new(pool.clone())),
+            calendar_feeds: Arc::new(PostgresCalendarFeedRepo::new(pool.clone())),
             events: Arc::new(PostgresEventRepo::new(pool.clone())),
+            event_groups: Arc::new(PostgresEventGroupRepo::new(pool.clone())),
+            event_instances: Arc::new(PostgresEventInstancesRepo::new(pool.clone())),
             event_synced: Arc::new(PostgresEventSyncedRepo::new(pool.clone())),
+            idempotency_keys: Arc::new(PostgresIdempotencyKeyRepo::new(pool.clone())),
+            job_leases: Arc::new(PostgresJobLeaseRepo::new(pool.clone())),
+            outbox: Arc::new(PostgresOutboxRepo::new(pool.clone())),
             users: Arc::new(PostgresUserRepo::new(pool.clone())),
             user_integrations: Arc::new(PostgresUserIntegrationRepo::new(pool.clone())),
             services: Arc::new(PostgresServiceRepo::new(pool.clone())),
@@ -181,9 +246,50 @@ impl Repos {
             event_reminders_generation_jobs: Arc::new(
                 PostgresEventReminderGenerationJobsRepo::new(pool.clone()),
             ),
+            webhook_deliveries: Arc::new(PostgresWebhookDeliveryRepo::new(pool.clone())),
             status: Arc::new(PostgresStatusRepo::new(pool)),
         })
     }
+
+    /// Repositories keeping the data in memory, shared between them (nothing is persisted)
+    /// Useful for tests and local development, without a database
+    pub fn create_inmemory() -> Self {
+        info!("[repos] Creating in-memory repositories");
+
+        // Register metrics (it can't fail, duplicates are ignored)
+        let _ = register_metrics();
+
+        let store = InMemoryStore::new();
+        Self {
+            accounts: Arc::new(InMemoryAccountRepo::new(store.clone())),
+            account_integrations: Arc::new(InMemoryAccountIntegrationRepo::new(store.clone())),
+            calendars: Arc::new(InMemoryCalendarRepo::new(store.clone())),
+            calendar_synced: Arc::new(InMemoryCalendarSyncedRepo::new(store.clone())),
+            calendar_feeds: Arc::new(InMemoryCalendarFeedRepo::new(store.clone())),
+            events: Arc::new(InMemoryEventRepo::new(store.clone())),
+            event_groups: Arc::new(InMemoryEventGroupRepo::new(store.clone())),
+            event_instances: Arc::new(InMemoryEventInstancesRepo::new(store.clone())),
+            event_synced: Arc::new(InMemoryEventSyncedRepo::new(store.clone())),
+            idempotency_keys: Arc::new(InMemoryIdempotencyKeyRepo::new(store.clone())),
+            job_leases: Arc::new(InMemoryJobLeaseRepo::new(store.clone())),
+            outbox: Arc::new(InMemoryOutboxRepo::new(store.clone())),
+            users: Arc::new(InMemoryUserRepo::new(store.clone())),
+            user_integrations: Arc::new(InMemoryUserIntegrationRepo::new(store.clone())),
+            services: Arc::new(InMemoryServiceRepo::new(store.clone())),
+            service_users: Arc::new(InMemoryServiceUserRepo::new(store.clone())),
+            service_user_busy_calendars: Arc::new(InMemoryServiceUserBusyCalendarRepo::new(
+                store.clone(),
+            )),
+            schedules: Arc::new(InMemoryScheduleRepo::new(store.clone())),
+            reminders: Arc::new(InMemoryReminderRepo::new(store.clone())),
+            reservations: Arc::new(InMemoryReservationRepo::new(store.clone())),
+            event_reminders_generation_jobs: Arc::new(
+                InMemoryEventReminderGenerationJobsRepo::new(store.clone()),
+            ),
+            webhook_deliveries: Arc::new(InMemoryWebhookDeliveryRepo::new(store)),
+            status: Arc::new(InMemoryStatusRepo::new()),
+        }
+    }
 }
 
 /// Redact the password from the connection string
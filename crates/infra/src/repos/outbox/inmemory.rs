@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{ID, OutboxMessage, OutboxMessageStatus};
+
+use super::IOutboxRepo;
+use crate::repos::shared::inmemory::InMemoryStore;
+
+#[derive(Debug)]
+pub struct InMemoryOutboxRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryOutboxRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IOutboxRepo for InMemoryOutboxRepo {
+    async fn insert(&self, messages: &[OutboxMessage]) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.insert_outbox_messages(messages)
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<OutboxMessage>> {
+        let mut tables = self.store.lock().await;
+        let mut due = tables
+            .outbox_messages
+            .iter_mut()
+            .filter(|m| m.status == OutboxMessageStatus::Pending && m.next_attempt_at <= now)
+            .collect::<Vec<_>>();
+        due.sort_by_key(|m| m.next_attempt_at);
+
+        Ok(due
+            .into_iter()
+            .take(usize::try_from(limit)?)
+            .map(|m| {
+                m.attempts += 1;
+                m.next_attempt_at = lease_until;
+                m.clone()
+            })
+            .collect())
+    }
+
+    async fn fail(
+        &self,
+        message_id: &ID,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(message) = tables
+            .outbox_messages
+            .iter_mut()
+            .find(|m| &m.id == message_id)
+        {
+            message.status = match retry_at {
+                Some(_) => OutboxMessageStatus::Pending,
+                None => OutboxMessageStatus::DeadLetter,
+            };
+            message.last_error = Some(error.to_string());
+            message.next_attempt_at = retry_at.unwrap_or(message.next_attempt_at);
+        }
+        Ok(())
+    }
+
+    async fn replay(&self, message_id: &ID, now: DateTime<Utc>) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        let Some(message) = tables
+            .outbox_messages
+            .iter_mut()
+            .find(|m| &m.id == message_id)
+        else {
+            return Ok(false);
+        };
+        message.status = OutboxMessageStatus::Pending;
+        message.attempts = 0;
+        message.next_attempt_at = now;
+        Ok(true)
+    }
+
+    async fn find(&self, message_id: &ID) -> anyhow::Result<Option<OutboxMessage>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .outbox_messages
+            .iter()
+            .find(|m| &m.id == message_id)
+            .cloned())
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: &ID,
+        status: Option<OutboxMessageStatus>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<OutboxMessage>> {
+        let tables = self.store.lock().await;
+        let mut messages = tables
+            .outbox_messages
+            .iter()
+            .filter(|m| &m.account_id == account_id && status.is_none_or(|s| m.status == s))
+            .cloned()
+            .collect::<Vec<_>>();
+        messages.sort_by_key(|m| m.created);
+        messages.truncate(usize::try_from(limit)?);
+        Ok(messages)
+    }
+
+    async fn delete(&self, message_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.outbox_messages.retain(|m| &m.id != message_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+mod inmemory;
+mod postgres;
+
+use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryOutboxRepo;
+use nittei_domain::{ID, OutboxMessage, OutboxMessageStatus};
+pub use postgres::PostgresOutboxRepo;
+pub(crate) use postgres::insert_outbox_messages;
+
+#[async_trait::async_trait]
+pub trait IOutboxRepo: Send + Sync {
+    /// Record messages in the outbox
+    /// Prefer the repository methods recording them in the same transaction as the write that triggered them
+    async fn insert(&self, messages: &[OutboxMessage]) -> anyhow::Result<()>;
+    /// Claim (at most `limit`) pending messages due at `now`, and increment their number of attempts
+    /// They are leased until `lease_until`: if not processed by then (e.g. crash), they are claimed again
+    /// Messages locked by another worker are skipped
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<OutboxMessage>>;
+    /// Record a failed attempt for the message
+    /// It is retried at `retry_at`, or moved to the dead letter state if None
+    async fn fail(
+        &self,
+        message_id: &ID,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()>;
+    /// Make the message pending again, with its attempts reset, so that it is processed at `now`
+    /// Returns false if the message doesn't exist
+    async fn replay(&self, message_id: &ID, now: DateTime<Utc>) -> anyhow::Result<bool>;
+    async fn find(&self, message_id: &ID) -> anyhow::Result<Option<OutboxMessage>>;
+    /// Find the messages of an account, optionally filtered by status, oldest first
+    async fn find_by_account(
+        &self,
+        account_id: &ID,
+        status: Option<OutboxMessageStatus>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<OutboxMessage>>;
+    /// Remove a processed message
+    async fn delete(&self, message_id: &ID) -> anyhow::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta, Utc};
+    use nittei_domain::{Account, ID, OutboxMessage, OutboxMessageStatus, OutboxTask};
+
+    use crate::{NitteiContext, backend_tests};
+
+    async fn setup(ctx: NitteiContext) -> (NitteiContext, Account) {
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        (ctx, account)
+    }
+
+    fn generate_message(account: &Account) -> OutboxMessage {
+        OutboxMessage::new(
+            account.id.clone(),
+            OutboxTask::SyncEventReminders {
+                event_id: ID::default(),
+            },
+            // Far in the past, so that claiming it doesn't claim the messages of the other tests
+            DateTime::from_timestamp_millis(1000 * 60 * 60 * 24).unwrap(),
+        )
+    }
+
+    backend_tests!(claim_fail_and_replay);
+
+    async fn claim_fail_and_replay(ctx: NitteiContext) {
+        let (ctx, account) = setup(ctx).await;
+        let message = generate_message(&account);
+        ctx.repos
+            .outbox
+            .insert(std::slice::from_ref(&message))
+            .await
+            .unwrap();
+
+        let res = ctx.repos.outbox.find(&message.id).await.unwrap();
+        assert_eq!(res, Some(message.clone()));
+
+        // Claim the message, other workers don't see it anymore during the lease
+        let now = Utc::now();
+        let lease_until = now + TimeDelta::minutes(1);
+        let claimed = ctx
+            .repos
+            .outbox
+            .claim_due(message.next_attempt_at, lease_until, 1000)
+            .await
+            .unwrap();
+        let claimed = claimed
+            .into_iter()
+            .find(|m| m.id == message.id)
+            .expect("Message to be claimed");
+        assert_eq!(claimed.attempts, 1);
+        let claimed_again = ctx
+            .repos
+            .outbox
+            .claim_due(message.next_attempt_at, lease_until, 1000)
+            .await
+            .unwrap();
+        assert!(claimed_again.iter().all(|m| m.id != message.id));
+
+        // Failed attempt with a retry
+        ctx.repos
+            .outbox
+            .fail(&message.id, "first error", Some(message.next_attempt_at))
+            .await
+            .unwrap();
+        let res = ctx.repos.outbox.find(&message.id).await.unwrap().unwrap();
+        assert_eq!(res.status, OutboxMessageStatus::Pending);
+        assert_eq!(res.last_error, Some("first error".into()));
+
+        // Failed attempt without retry, the message is dead
+        ctx.repos
+            .outbox
+            .fail(&message.id, "second error", None)
+            .await
+            .unwrap();
+        let res = ctx
+            .repos
+            .outbox
+            .find_by_account(&account.id, Some(OutboxMessageStatus::DeadLetter), 10)
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].last_error, Some("second error".into()));
+        let res = ctx
+            .repos
+            .outbox
+            .find_by_account(&account.id, Some(OutboxMessageStatus::Pending), 10)
+            .await
+            .unwrap();
+        assert!(res.is_empty());
+
+        // Replay
+        assert!(
+            ctx.repos
+                .outbox
+                .replay(&message.id, message.next_attempt_at)
+                .await
+                .unwrap()
+        );
+        let res = ctx.repos.outbox.find(&message.id).await.unwrap().unwrap();
+        assert_eq!(res.status, OutboxMessageStatus::Pending);
+        assert_eq!(res.attempts, 0);
+        assert!(
+            !ctx.repos
+                .outbox
+                .replay(&ID::default(), message.next_attempt_at)
+                .await
+                .unwrap()
+        );
+
+        // Processed
+        ctx.repos.outbox.delete(&message.id).await.unwrap();
+        assert!(ctx.repos.outbox.find(&message.id).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,304 @@
+use std::convert::{TryFrom, TryInto};
+
+use chrono::{DateTime, Utc};
+use nittei_domain::{ID, OutboxMessage, OutboxMessageStatus};
+use sqlx::{FromRow, PgExecutor, PgPool, types::Uuid};
+use tracing::{error, instrument};
+
+use super::IOutboxRepo;
+
+#[derive(Debug)]
+pub struct PostgresOutboxRepo {
+    pool: PgPool,
+}
+
+impl PostgresOutboxRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct OutboxMessageRaw {
+    message_uid: Uuid,
+    account_uid: Uuid,
+    task: serde_json::Value,
+    status: String,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    last_error: Option<String>,
+    created: DateTime<Utc>,
+}
+
+impl TryFrom<OutboxMessageRaw> for OutboxMessage {
+    type Error = anyhow::Error;
+
+    fn try_from(e: OutboxMessageRaw) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: e.message_uid.into(),
+            account_id: e.account_uid.into(),
+            task: serde_json::from_value(e.task)?,
+            status: e.status.try_into()?,
+            attempts: e.attempts,
+            next_attempt_at: e.next_attempt_at,
+            last_error: e.last_error,
+            created: e.created,
+        })
+    }
+}
+
+/// Insert outbox messages using the given executor
+/// This allows the other repositories to record them in the transaction of their write
+pub(crate) async fn insert_outbox_messages<'c, E>(
+    executor: E,
+    messages: &[OutboxMessage],
+) -> anyhow::Result<()>
+where
+    E: PgExecutor<'c>,
+{
+    if messages.is_empty() {
+        return Ok(());
+    }
+
+    let message_uids = messages
+        .iter()
+        .map(|m| *m.id.as_ref())
+        .collect::<Vec<Uuid>>();
+    let account_uids = messages
+        .iter()
+        .map(|m| *m.account_id.as_ref())
+        .collect::<Vec<Uuid>>();
+    let tasks = messages
+        .iter()
+        .map(|m| serde_json::to_value(&m.task))
+        .collect::<Result<Vec<_>, _>>()?;
+    let statuses = messages
+        .iter()
+        .map(|m| m.status.into())
+        .collect::<Vec<String>>();
+    let attempts = messages.iter().map(|m| m.attempts).collect::<Vec<i32>>();
+    let next_attempt_ats = messages
+        .iter()
+        .map(|m| m.next_attempt_at)
+        .collect::<Vec<DateTime<Utc>>>();
+    let last_errors = messages
+        .iter()
+        .map(|m| m.last_error.clone())
+        .collect::<Vec<Option<String>>>();
+    let createds = messages
+        .iter()
+        .map(|m| m.created)
+        .collect::<Vec<DateTime<Utc>>>();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO outbox_messages(message_uid, account_uid, task, status, attempts, next_attempt_at, last_error, created)
+        SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::jsonb[], $4::text[], $5::int[], $6::timestamptz[], $7::text[], $8::timestamptz[])
+        "#,
+        &message_uids,
+        &account_uids,
+        &tasks,
+        &statuses,
+        &attempts,
+        &next_attempt_ats,
+        &last_errors as _,
+        &createds,
+    )
+    .execute(executor)
+    .await
+    .inspect_err(|err| {
+        error!(
+            error = ?err,
+            "Failed to insert outbox_messages"
+        );
+    })?;
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl IOutboxRepo for PostgresOutboxRepo {
+    #[instrument(name = "outbox::insert", skip(messages))]
+    async fn insert(&self, messages: &[OutboxMessage]) -> anyhow::Result<()> {
+        insert_outbox_messages(&self.pool, messages).await
+    }
+
+    #[instrument(name = "outbox::claim_due")]
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<OutboxMessage>> {
+        let messages: Vec<OutboxMessageRaw> = sqlx::query_as!(
+            OutboxMessageRaw,
+            r#"
+            UPDATE outbox_messages AS m SET
+                attempts = m.attempts + 1,
+                next_attempt_at = $2
+            WHERE m.message_uid IN (
+                SELECT message_uid FROM outbox_messages
+                WHERE status = 'pending' AND next_attempt_at <= $1
+                ORDER BY next_attempt_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING m.message_uid, m.account_uid, m.task, m.status, m.attempts, m.next_attempt_at, m.last_error, m.created
+            "#,
+            now,
+            lease_until,
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to claim outbox_messages"
+            );
+        })?;
+
+        messages.into_iter().map(|m| m.try_into()).collect()
+    }
+
+    #[instrument(name = "outbox::fail", fields(message_uid = %message_id))]
+    async fn fail(
+        &self,
+        message_id: &ID,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<()> {
+        let status: String = match retry_at {
+            Some(_) => OutboxMessageStatus::Pending.into(),
+            None => OutboxMessageStatus::DeadLetter.into(),
+        };
+        sqlx::query!(
+            r#"
+            UPDATE outbox_messages SET
+                status = $2,
+                last_error = $3,
+                next_attempt_at = COALESCE($4, next_attempt_at)
+            WHERE message_uid = $1
+            "#,
+            message_id.as_ref(),
+            status,
+            error,
+            retry_at,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                message_id = %message_id,
+                error = ?err,
+                "Failed to record failed attempt of outbox_message"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "outbox::replay", fields(message_uid = %message_id))]
+    async fn replay(&self, message_id: &ID, now: DateTime<Utc>) -> anyhow::Result<bool> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE outbox_messages SET
+                status = 'pending',
+                attempts = 0,
+                next_attempt_at = $2
+            WHERE message_uid = $1
+            "#,
+            message_id.as_ref(),
+            now,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                message_id = %message_id,
+                error = ?err,
+                "Failed to replay outbox_message"
+            );
+        })?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    #[instrument(name = "outbox::find", fields(message_uid = %message_id))]
+    async fn find(&self, message_id: &ID) -> anyhow::Result<Option<OutboxMessage>> {
+        let message: Option<OutboxMessageRaw> = sqlx::query_as!(
+            OutboxMessageRaw,
+            r#"
+            SELECT * FROM outbox_messages AS m
+            WHERE m.message_uid = $1
+            "#,
+            message_id.as_ref(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                message_id = %message_id,
+                error = ?err,
+                "Failed to find outbox_message"
+            );
+        })?;
+
+        message.map(|m| m.try_into()).transpose()
+    }
+
+    #[instrument(name = "outbox::find_by_account", fields(account_uid = %account_id))]
+    async fn find_by_account(
+        &self,
+        account_id: &ID,
+        status: Option<OutboxMessageStatus>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<OutboxMessage>> {
+        let status: Option<String> = status.map(|s| s.into());
+        let messages: Vec<OutboxMessageRaw> = sqlx::query_as!(
+            OutboxMessageRaw,
+            r#"
+            SELECT * FROM outbox_messages AS m
+            WHERE m.account_uid = $1 AND ($2::text IS NULL OR m.status = $2)
+            ORDER BY m.created
+            LIMIT $3
+            "#,
+            account_id.as_ref(),
+            status,
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                account_id = %account_id,
+                error = ?err,
+                "Failed to find outbox_messages of account"
+            );
+        })?;
+
+        messages.into_iter().map(|m| m.try_into()).collect()
+    }
+
+    #[instrument(name = "outbox::delete", fields(message_uid = %message_id))]
+    async fn delete(&self, message_id: &ID) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM outbox_messages AS m
+            WHERE m.message_uid = $1
+            "#,
+            message_id.as_ref(),
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                message_id = %message_id,
+                error = ?err,
+                "Failed to delete outbox_message"
+            );
+        })?;
+
+        Ok(())
+    }
+}
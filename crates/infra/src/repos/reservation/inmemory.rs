@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{ID, ServiceHold};
+
+use super::IReservationRepo;
+use crate::repos::{
+    ServiceBookingHost,
+    shared::inmemory::{InMemoryStore, InMemoryTables},
+};
+
+#[derive(Debug)]
+pub struct InMemoryReservationRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryReservationRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IReservationRepo for InMemoryReservationRepo {
+    async fn increment(&self, service_id: &ID, timestamp: DateTime<Utc>) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.services, service_id, "service")?;
+        *tables
+            .reservations
+            .entry((*service_id.as_ref(), timestamp))
+            .or_default() += 1;
+        Ok(())
+    }
+
+    async fn decrement(&self, service_id: &ID, timestamp: DateTime<Utc>) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(count) = tables
+            .reservations
+            .get_mut(&(*service_id.as_ref(), timestamp))
+        {
+            *count -= 1;
+        }
+        Ok(())
+    }
+
+    async fn count(&self, service_id: &ID, timestamp: DateTime<Utc>) -> anyhow::Result<usize> {
+        let tables = self.store.lock().await;
+        let count = tables
+            .reservations
+            .get(&(*service_id.as_ref(), timestamp))
+            .copied()
+            .unwrap_or(0);
+        Ok(usize::try_from(count)?)
+    }
+
+    async fn insert_hold(
+        &self,
+        hold: &ServiceHold,
+        hosts: &[ServiceBookingHost],
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.services, &hold.service_id, "service")?;
+        for user_id in &hold.host_user_ids {
+            InMemoryTables::ensure_exists(&tables.users, user_id, "user")?;
+        }
+        if tables.service_holds.contains_key(hold.id.as_ref()) {
+            anyhow::bail!("The service hold with id: {} already exists", hold.id);
+        }
+
+        let hosts_busy = hosts.iter().any(|host| {
+            tables.has_service_booking_or_hold(&host.user_id, host.busy_start, host.busy_end, now)
+        });
+        if hosts_busy {
+            return Ok(false);
+        }
+        let limits_reached = hosts
+            .iter()
+            .any(|host| tables.has_reached_booking_limits(&host.user_id, &host.limits, now));
+        if limits_reached {
+            return Ok(false);
+        }
+        tables.service_holds.insert(*hold.id.as_ref(), hold.clone());
+        Ok(true)
+    }
+
+    async fn find_hold(&self, hold_id: &ID) -> anyhow::Result<Option<ServiceHold>> {
+        let tables = self.store.lock().await;
+        Ok(tables.service_holds.get(hold_id.as_ref()).cloned())
+    }
+
+    async fn find_holds_for_user(
+        &self,
+        user_id: &ID,
+        min_time: DateTime<Utc>,
+        max_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ServiceHold>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .service_holds
+            .values()
+            .filter(|hold| {
+                hold.host_user_ids.contains(user_id)
+                    && !hold.is_expired(now)
+                    && hold.start_time < max_time
+                    && hold.end_time > min_time
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_hold(&self, hold_id: &ID) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        Ok(tables.service_holds.remove(hold_id.as_ref()).is_some())
+    }
+
+    async fn delete_expired_holds(&self, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let mut tables = self.store.lock().await;
+        let count = tables.service_holds.len();
+        tables.service_holds.retain(|_, hold| !hold.is_expired(now));
+        Ok(u64::try_from(count - tables.service_holds.len())?)
+    }
+}
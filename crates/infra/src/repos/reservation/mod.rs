@@ -1,27 +1,58 @@
+mod inmemory;
 mod postgres;
 
 use chrono::{DateTime, Utc};
-use nittei_domain::ID;
+pub use inmemory::InMemoryReservationRepo;
+use nittei_domain::{ID, ServiceHold};
 pub use postgres::PostgresReservationRepo;
 
+use crate::repos::ServiceBookingHost;
+
 #[async_trait::async_trait]
 pub trait IReservationRepo: Send + Sync {
     async fn increment(&self, service_id: &ID, timestamp: DateTime<Utc>) -> anyhow::Result<()>;
     async fn decrement(&self, service_id: &ID, timestamp: DateTime<Utc>) -> anyhow::Result<()>;
     async fn count(&self, service_id: &ID, timestamp: DateTime<Utc>) -> anyhow::Result<usize>;
+    /// Insert the hold, unless one of its `hosts` has another (non expired) hold
+    /// or a (non cancelled) service event between its `busy_start` and `busy_end`
+    ///
+    /// The hosts are locked during the transaction, as for `IEventRepo::insert_service_booking`
+    /// Returns false if the hold hasn't been inserted
+    async fn insert_hold(
+        &self,
+        hold: &ServiceHold,
+        hosts: &[ServiceBookingHost],
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<bool>;
+    async fn find_hold(&self, hold_id: &ID) -> anyhow::Result<Option<ServiceHold>>;
+    /// Find the non expired holds of the user overlapping the timespan
+    async fn find_holds_for_user(
+        &self,
+        user_id: &ID,
+        min_time: DateTime<Utc>,
+        max_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ServiceHold>>;
+    /// Returns false if the hold doesn't exist
+    async fn delete_hold(&self, hold_id: &ID) -> anyhow::Result<bool>;
+    /// Delete the holds expired at `now`, and return how many have been deleted
+    async fn delete_expired_holds(&self, now: DateTime<Utc>) -> anyhow::Result<u64>;
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::DateTime;
-    use nittei_domain::{Account, Service};
+    use chrono::{DateTime, TimeDelta};
+    use nittei_domain::{Account, ID, Service, ServiceHold, User, scheduling::BookingPeriod};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, ServiceBookingHost, backend_tests};
 
-    #[tokio::test]
-    async fn test_reservations_repo() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(
+        test_reservations_repo,
+        test_delete_reservation,
+        test_service_holds
+    );
 
+    async fn test_reservations_repo(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
@@ -87,10 +118,7 @@ mod tests {
         assert_eq!(count, 1);
     }
 
-    #[tokio::test]
-    async fn test_delete_reservation() {
-        let ctx = setup_context().await.unwrap();
-
+    async fn test_delete_reservation(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
@@ -161,4 +189,176 @@ mod tests {
 
         assert_eq!(count, 3);
     }
+
+    async fn test_service_holds(ctx: NitteiContext) {
+        let account = Account::new();
+        ctx.repos
+            .accounts
+            .insert(&account)
+            .await
+            .expect("To insert account");
+        let user = User::new(account.id.clone(), None);
+        ctx.repos.users.insert(&user).await.expect("To insert user");
+        let service = Service::new(account.id.clone());
+        ctx.repos
+            .services
+            .insert(&service)
+            .await
+            .expect("To insert service");
+
+        let now = DateTime::from_timestamp_millis(0).unwrap();
+        let start_time = now + TimeDelta::hours(1);
+        let hold = |start_time: DateTime<_>| ServiceHold {
+            id: ID::default(),
+            service_id: service.id.clone(),
+            host_user_ids: vec![user.id.clone()],
+            start_time,
+            end_time: start_time + TimeDelta::minutes(30),
+            expires_at: now + TimeDelta::minutes(10),
+            created: now,
+        };
+        let hosts = |hold: &ServiceHold, buffer: TimeDelta| {
+            vec![ServiceBookingHost {
+                user_id: user.id.clone(),
+                busy_start: hold.start_time - buffer,
+                busy_end: hold.end_time + buffer,
+                limits: Vec::new(),
+            }]
+        };
+
+        let first_hold = hold(start_time);
+        assert!(
+            ctx.repos
+                .reservations
+                .insert_hold(&first_hold, &hosts(&first_hold, TimeDelta::zero()), now)
+                .await
+                .expect("To insert hold")
+        );
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .find_hold(&first_hold.id)
+                .await
+                .expect("To find hold"),
+            Some(first_hold.clone())
+        );
+
+        // The host is already held at that time
+        let overlapping_hold = hold(start_time + TimeDelta::minutes(15));
+        assert!(
+            !ctx.repos
+                .reservations
+                .insert_hold(
+                    &overlapping_hold,
+                    &hosts(&overlapping_hold, TimeDelta::zero()),
+                    now
+                )
+                .await
+                .expect("To insert hold")
+        );
+        // But not once the first hold has expired
+        let later = now + TimeDelta::minutes(10);
+        let second_hold = hold(start_time + TimeDelta::minutes(15));
+        let second_hold = ServiceHold {
+            expires_at: later + TimeDelta::minutes(10),
+            ..second_hold
+        };
+        assert!(
+            ctx.repos
+                .reservations
+                .insert_hold(&second_hold, &hosts(&second_hold, TimeDelta::zero()), later)
+                .await
+                .expect("To insert hold")
+        );
+        // The host is busy during the buffer after the second hold
+        let adjacent_hold = ServiceHold {
+            expires_at: later + TimeDelta::minutes(10),
+            ..hold(second_hold.end_time)
+        };
+        assert!(
+            !ctx.repos
+                .reservations
+                .insert_hold(
+                    &adjacent_hold,
+                    &hosts(&adjacent_hold, TimeDelta::minutes(10)),
+                    later
+                )
+                .await
+                .expect("To insert hold")
+        );
+
+        // The host has reached its maximum number of bookings in the day with the second hold
+        let later_hold = ServiceHold {
+            expires_at: later + TimeDelta::minutes(10),
+            ..hold(start_time + TimeDelta::hours(3))
+        };
+        let limited_hosts = vec![ServiceBookingHost {
+            limits: vec![BookingPeriod {
+                start_time: now,
+                end_time: now + TimeDelta::days(1),
+                max: 1,
+            }],
+            ..hosts(&later_hold, TimeDelta::zero()).remove(0)
+        }];
+        assert!(
+            !ctx.repos
+                .reservations
+                .insert_hold(&later_hold, &limited_hosts, later)
+                .await
+                .expect("To insert hold")
+        );
+
+        let holds = ctx
+            .repos
+            .reservations
+            .find_holds_for_user(&user.id, start_time, start_time + TimeDelta::hours(1), now)
+            .await
+            .expect("To find holds");
+        assert_eq!(holds.len(), 2);
+        let holds = ctx
+            .repos
+            .reservations
+            .find_holds_for_user(
+                &user.id,
+                start_time,
+                start_time + TimeDelta::hours(1),
+                later,
+            )
+            .await
+            .expect("To find holds");
+        assert_eq!(holds, vec![second_hold.clone()]);
+
+        // Only the first hold is expired
+        assert_eq!(
+            ctx.repos
+                .reservations
+                .delete_expired_holds(later)
+                .await
+                .expect("To delete expired holds"),
+            1
+        );
+        assert!(
+            ctx.repos
+                .reservations
+                .find_hold(&first_hold.id)
+                .await
+                .expect("To find hold")
+                .is_none()
+        );
+
+        assert!(
+            ctx.repos
+                .reservations
+                .delete_hold(&second_hold.id)
+                .await
+                .expect("To delete hold")
+        );
+        assert!(
+            !ctx.repos
+                .reservations
+                .delete_hold(&second_hold.id)
+                .await
+                .expect("To delete hold")
+        );
+    }
 }
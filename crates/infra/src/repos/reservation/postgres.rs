@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
-use nittei_domain::ID;
+use nittei_domain::{ID, ServiceHold};
 use sqlx::{FromRow, PgPool, types::Uuid};
 use tracing::{error, instrument};
 
 use super::IReservationRepo;
+use crate::repos::ServiceBookingHost;
 
 #[derive(Debug)]
 pub struct PostgresReservationRepo {
@@ -24,6 +25,35 @@ struct ReservationRaw {
     service_uid: Uuid,
 }
 
+#[derive(Debug, FromRow)]
+struct ServiceHoldRaw {
+    hold_uid: Uuid,
+    service_uid: Uuid,
+    host_user_uids: Vec<Uuid>,
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    created: DateTime<Utc>,
+}
+
+impl From<ServiceHoldRaw> for ServiceHold {
+    fn from(hold: ServiceHoldRaw) -> Self {
+        Self {
+            id: hold.hold_uid.into(),
+            service_id: hold.service_uid.into(),
+            host_user_ids: hold
+                .host_user_uids
+                .into_iter()
+                .map(|id| id.into())
+                .collect(),
+            start_time: hold.start_time,
+            end_time: hold.end_time,
+            expires_at: hold.expires_at,
+            created: hold.created,
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl IReservationRepo for PostgresReservationRepo {
     #[instrument]
@@ -99,4 +129,271 @@ impl IReservationRepo for PostgresReservationRepo {
         let count = reservation.map(|r| r.count).unwrap_or(0);
         Ok(count as usize)
     }
+
+    #[instrument(name = "reservation::insert_hold", fields(hold_id = %hold.id, service_id = %hold.service_id), skip(hosts))]
+    async fn insert_hold(
+        &self,
+        hold: &ServiceHold,
+        hosts: &[ServiceBookingHost],
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to start transaction for inserting service hold"
+            );
+        })?;
+
+        // Lock the hosts, as the bookings do, so that they can't be booked or held concurrently
+        let host_uids = hold
+            .host_user_ids
+            .iter()
+            .map(|id| *id.as_ref())
+            .collect::<Vec<_>>();
+        sqlx::query!(
+            r#"
+            SELECT user_uid FROM users
+            WHERE user_uid = ANY($1)
+            ORDER BY user_uid
+            FOR NO KEY UPDATE
+            "#,
+            &host_uids,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to lock the hosts of the service hold"
+            );
+        })?;
+
+        let busy_user_uids = hosts
+            .iter()
+            .map(|host| *host.user_id.as_ref())
+            .collect::<Vec<_>>();
+        let busy_starts = hosts.iter().map(|host| host.busy_start).collect::<Vec<_>>();
+        let busy_ends = hosts.iter().map(|host| host.busy_end).collect::<Vec<_>>();
+        let hosts_busy = sqlx::query_scalar!(
+            r#"
+            WITH hosts AS (
+                SELECT * FROM UNNEST($1::uuid[], $2::timestamptz[], $3::timestamptz[]) AS h(user_uid, busy_start, busy_end)
+            )
+            SELECT (
+                EXISTS(
+                    SELECT 1 FROM calendar_events AS e
+                    INNER JOIN hosts AS h ON e.user_uid = h.user_uid
+                    WHERE e.service_uid IS NOT NULL AND
+                    e.status <> 'cancelled' AND
+                    e.start_time < h.busy_end AND e.end_time > h.busy_start
+                ) OR EXISTS(
+                    SELECT 1 FROM service_holds AS sh
+                    INNER JOIN hosts AS h ON h.user_uid = ANY(sh.host_user_uids)
+                    WHERE sh.expires_at > $4 AND
+                    sh.start_time < h.busy_end AND sh.end_time > h.busy_start
+                )
+            ) AS "busy!"
+            "#,
+            &busy_user_uids,
+            &busy_starts,
+            &busy_ends,
+            now,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to check the availability of the hosts of the service hold"
+            );
+        })?;
+        if hosts_busy {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+        let limit_user_uids = hosts
+            .iter()
+            .flat_map(|host| host.limits.iter().map(|_| *host.user_id.as_ref()))
+            .collect::<Vec<_>>();
+        let limit_starts = hosts
+            .iter()
+            .flat_map(|host| host.limits.iter().map(|period| period.start_time))
+            .collect::<Vec<_>>();
+        let limit_ends = hosts
+            .iter()
+            .flat_map(|host| host.limits.iter().map(|period| period.end_time))
+            .collect::<Vec<_>>();
+        let limit_maxes = hosts
+            .iter()
+            .flat_map(|host| host.limits.iter().map(|period| period.max))
+            .collect::<Vec<_>>();
+        // Recounted under the lock of the hosts, as the concurrent bookings of other slots count too
+        let limits_reached = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM UNNEST($1::uuid[], $2::timestamptz[], $3::timestamptz[], $4::bigint[]) AS p(user_uid, start_time, end_time, max)
+                WHERE (
+                    SELECT count(*) FROM calendar_events AS e
+                    WHERE e.user_uid = p.user_uid AND
+                    e.service_uid IS NOT NULL AND
+                    e.status <> 'cancelled' AND
+                    e.start_time >= p.start_time AND e.start_time < p.end_time
+                ) + (
+                    SELECT count(*) FROM service_holds AS sh
+                    WHERE p.user_uid = ANY(sh.host_user_uids) AND
+                    sh.expires_at > $5 AND
+                    sh.start_time >= p.start_time AND sh.start_time < p.end_time
+                ) >= p.max
+            ) AS "reached!"
+            "#,
+            &limit_user_uids,
+            &limit_starts,
+            &limit_ends,
+            &limit_maxes,
+            now,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to check the booking limits of the hosts of the service hold"
+            );
+        })?;
+        if limits_reached {
+            tx.rollback().await?;
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO service_holds(hold_uid, service_uid, host_user_uids, start_time, end_time, expires_at, created)
+            VALUES($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            hold.id.as_ref(),
+            hold.service_id.as_ref(),
+            &host_uids,
+            hold.start_time,
+            hold.end_time,
+            hold.expires_at,
+            hold.created,
+        )
+        .execute(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to insert service hold"
+            );
+        })?;
+
+        tx.commit().await.inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to commit transaction for inserting service hold"
+            );
+        })?;
+
+        Ok(true)
+    }
+
+    #[instrument(name = "reservation::find_hold", fields(hold_id = %hold_id))]
+    async fn find_hold(&self, hold_id: &ID) -> anyhow::Result<Option<ServiceHold>> {
+        let hold = sqlx::query_as!(
+            ServiceHoldRaw,
+            r#"
+            SELECT * FROM service_holds AS h
+            WHERE h.hold_uid = $1
+            "#,
+            hold_id.as_ref(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                hold_id = %hold_id,
+                error = ?err,
+                "Failed to find service hold"
+            );
+        })?;
+
+        Ok(hold.map(|hold| hold.into()))
+    }
+
+    #[instrument(name = "reservation::find_holds_for_user", fields(user_id = %user_id))]
+    async fn find_holds_for_user(
+        &self,
+        user_id: &ID,
+        min_time: DateTime<Utc>,
+        max_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<ServiceHold>> {
+        let holds = sqlx::query_as!(
+            ServiceHoldRaw,
+            r#"
+            SELECT * FROM service_holds AS h
+            WHERE $1 = ANY(h.host_user_uids) AND
+            h.expires_at > $4 AND
+            h.start_time < $3 AND h.end_time > $2
+            "#,
+            user_id.as_ref(),
+            min_time,
+            max_time,
+            now,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                user_id = %user_id,
+                error = ?err,
+                "Failed to find the service holds of the user"
+            );
+        })?;
+
+        Ok(holds.into_iter().map(|hold| hold.into()).collect())
+    }
+
+    #[instrument(name = "reservation::delete_hold", fields(hold_id = %hold_id))]
+    async fn delete_hold(&self, hold_id: &ID) -> anyhow::Result<bool> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM service_holds AS h
+            WHERE h.hold_uid = $1
+            "#,
+            hold_id.as_ref(),
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                hold_id = %hold_id,
+                error = ?err,
+                "Failed to delete service hold"
+            );
+        })?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    #[instrument(name = "reservation::delete_expired_holds")]
+    async fn delete_expired_holds(&self, now: DateTime<Utc>) -> anyhow::Result<u64> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM service_holds AS h
+            WHERE h.expires_at <= $1
+            "#,
+            now,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to delete the expired service holds"
+            );
+        })?;
+
+        Ok(res.rows_affected())
+    }
 }
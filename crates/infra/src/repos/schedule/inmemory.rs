@@ -0,0 +1,93 @@
+use nittei_domain::{ID, Schedule};
+
+use super::IScheduleRepo;
+use crate::{
+    MetadataFindQuery,
+    repos::shared::inmemory::{self, InMemoryStore, InMemoryTables},
+};
+
+#[derive(Debug)]
+pub struct InMemoryScheduleRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryScheduleRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+/// The schedules with their account, which is the one of their user
+fn schedules_with_account(tables: &InMemoryTables) -> impl Iterator<Item = Schedule> + '_ {
+    tables.schedules.values().filter_map(|s| {
+        tables.users.get(s.user_id.as_ref()).map(|u| Schedule {
+            account_id: u.account_id.clone(),
+            ..s.clone()
+        })
+    })
+}
+
+#[async_trait::async_trait]
+impl IScheduleRepo for InMemoryScheduleRepo {
+    async fn insert(&self, schedule: &Schedule) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.users, &schedule.user_id, "user")?;
+        if tables.schedules.contains_key(schedule.id.as_ref()) {
+            anyhow::bail!("The schedule with id: {} already exists", schedule.id);
+        }
+        tables
+            .schedules
+            .insert(*schedule.id.as_ref(), schedule.clone());
+        Ok(())
+    }
+
+    async fn save(&self, schedule: &Schedule) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(existing) = tables.schedules.get_mut(schedule.id.as_ref()) {
+            existing.rules = schedule.rules.clone();
+            existing.timezone = schedule.timezone;
+            existing.metadata = schedule.metadata.clone();
+        }
+        Ok(())
+    }
+
+    async fn find(&self, schedule_id: &ID) -> anyhow::Result<Option<Schedule>> {
+        let tables = self.store.lock().await;
+        Ok(schedules_with_account(&tables).find(|s| &s.id == schedule_id))
+    }
+
+    async fn find_many(&self, schedule_ids: &[ID]) -> anyhow::Result<Vec<Schedule>> {
+        let tables = self.store.lock().await;
+        Ok(schedules_with_account(&tables)
+            .filter(|s| schedule_ids.contains(&s.id))
+            .collect())
+    }
+
+    async fn find_by_user(&self, user_id: &ID) -> anyhow::Result<Vec<Schedule>> {
+        let tables = self.store.lock().await;
+        Ok(schedules_with_account(&tables)
+            .filter(|s| &s.user_id == user_id)
+            .collect())
+    }
+
+    async fn delete(&self, schedule_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables
+            .delete_schedule(schedule_id)
+            .map(|_| ())
+            .ok_or_else(|| anyhow::Error::msg("Unable to delete schedule"))
+    }
+
+    async fn find_by_metadata(&self, query: MetadataFindQuery) -> anyhow::Result<Vec<Schedule>> {
+        let tables = self.store.lock().await;
+        let schedules = schedules_with_account(&tables)
+            .map(|s| (*s.id.as_ref(), s))
+            .collect::<Vec<_>>();
+        inmemory::find_by_metadata(
+            schedules.iter().map(|(id, s)| (id, s)),
+            &query,
+            |s| &s.account_id,
+            |s| s.metadata.as_ref(),
+        )
+    }
+}
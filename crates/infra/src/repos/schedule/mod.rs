@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryScheduleRepo;
 use nittei_domain::{ID, Schedule};
 pub use postgres::PostgresScheduleRepo;
 
@@ -21,11 +23,11 @@ mod tests {
     use chrono_tz::US::Pacific;
     use nittei_domain::{Account, Entity, Schedule, User};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn create_and_delete() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(create_and_delete, update);
+
+    async fn create_and_delete(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos
             .accounts
@@ -87,9 +89,7 @@ mod tests {
         );
     }
 
-    #[tokio::test]
-    async fn update() {
-        let ctx = setup_context().await.unwrap();
+    async fn update(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos
             .accounts
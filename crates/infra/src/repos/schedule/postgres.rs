@@ -208,7 +208,8 @@ impl IScheduleRepo for PostgresScheduleRepo {
             SELECT s.*, u.account_uid FROM schedules AS s
             INNER JOIN users AS u
                 ON u.user_uid = s.user_uid
-            WHERE u.account_uid = $1 AND s.metadata @> $2
+            WHERE u.account_uid = $1 AND s.metadata @> $2 AND ($5::uuid IS NULL OR s.schedule_uid > $5)
+            ORDER BY s.schedule_uid
             LIMIT $3
             OFFSET $4
             "#,
@@ -216,6 +217,7 @@ impl IScheduleRepo for PostgresScheduleRepo {
             Json(&query.metadata) as _,
             query.limit as i64,
             query.skip as i64,
+            query.after.as_ref().map(|id| *id.as_ref()),
         )
         .fetch_all(&self.pool)
         .await
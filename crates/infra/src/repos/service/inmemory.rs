@@ -0,0 +1,88 @@
+use nittei_domain::{ID, Service, ServiceWithUsers};
+
+use super::IServiceRepo;
+use crate::repos::shared::{
+    inmemory::{self, InMemoryStore, InMemoryTables},
+    query_structs::MetadataFindQuery,
+};
+
+#[derive(Debug)]
+pub struct InMemoryServiceRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryServiceRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IServiceRepo for InMemoryServiceRepo {
+    async fn insert(&self, service: &Service) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.accounts, &service.account_id, "account")?;
+        if tables.services.contains_key(service.id.as_ref()) {
+            anyhow::bail!("The service with id: {} already exists", service.id);
+        }
+        tables
+            .services
+            .insert(*service.id.as_ref(), service.clone());
+        Ok(())
+    }
+
+    async fn save(&self, service: &Service) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(existing) = tables.services.get_mut(service.id.as_ref()) {
+            existing.multi_person = service.multi_person.clone();
+            existing.metadata = service.metadata.clone();
+            existing.cancellation_cutoff = service.cancellation_cutoff;
+            existing.reschedule_cutoff = service.reschedule_cutoff;
+            existing.distribution_window = service.distribution_window;
+        }
+        Ok(())
+    }
+
+    async fn find(&self, service_id: &ID) -> anyhow::Result<Option<Service>> {
+        let tables = self.store.lock().await;
+        Ok(tables.services.get(service_id.as_ref()).cloned())
+    }
+
+    async fn find_with_users(&self, service_id: &ID) -> anyhow::Result<Option<ServiceWithUsers>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .services
+            .get(service_id.as_ref())
+            .map(|service| ServiceWithUsers {
+                id: service.id.clone(),
+                account_id: service.account_id.clone(),
+                users: tables
+                    .service_users
+                    .iter()
+                    .filter(|u| &u.service_id == service_id)
+                    .cloned()
+                    .collect(),
+                multi_person: service.multi_person.clone(),
+                metadata: service.metadata.clone(),
+                cancellation_cutoff: service.cancellation_cutoff,
+                reschedule_cutoff: service.reschedule_cutoff,
+                distribution_window: service.distribution_window,
+            }))
+    }
+
+    async fn delete(&self, service_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.delete_service(service_id);
+        Ok(())
+    }
+
+    async fn find_by_metadata(&self, query: MetadataFindQuery) -> anyhow::Result<Vec<Service>> {
+        let tables = self.store.lock().await;
+        inmemory::find_by_metadata(
+            tables.services.iter(),
+            &query,
+            |s| &s.account_id,
+            |s| s.metadata.as_ref(),
+        )
+    }
+}
@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryServiceRepo;
 use nittei_domain::{ID, Service, ServiceWithUsers};
 pub use postgres::PostgresServiceRepo;
 
@@ -19,11 +21,11 @@ pub trait IServiceRepo: Send + Sync {
 mod tests {
     use nittei_domain::{Account, Service, ServiceResource, TimePlan, User};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn create_and_delete() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(create_and_delete);
+
+    async fn create_and_delete(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos
             .accounts
@@ -29,6 +29,9 @@ struct ServiceRaw {
     account_uid: Uuid,
     multi_person: Value,
     metadata: Value,
+    cancellation_cutoff: i64,
+    reschedule_cutoff: i64,
+    distribution_window: i64,
 }
 
 #[derive(Debug, FromRow)]
@@ -38,6 +41,9 @@ struct ServiceWithUsersRaw {
     users: Option<Value>,
     multi_person: Value,
     metadata: Value,
+    cancellation_cutoff: i64,
+    reschedule_cutoff: i64,
+    distribution_window: i64,
 }
 
 impl TryFrom<ServiceRaw> for Service {
@@ -48,6 +54,9 @@ impl TryFrom<ServiceRaw> for Service {
             account_id: e.account_uid.into(),
             multi_person: serde_json::from_value(e.multi_person)?,
             metadata: serde_json::from_value(e.metadata)?,
+            cancellation_cutoff: e.cancellation_cutoff,
+            reschedule_cutoff: e.reschedule_cutoff,
+            distribution_window: e.distribution_window,
         })
     }
 }
@@ -65,6 +74,9 @@ impl TryFrom<ServiceWithUsersRaw> for ServiceWithUsers {
             users: users.into_iter().map(|u| u.into()).collect(),
             multi_person: serde_json::from_value(e.multi_person)?,
             metadata: serde_json::from_value(e.metadata)?,
+            cancellation_cutoff: e.cancellation_cutoff,
+            reschedule_cutoff: e.reschedule_cutoff,
+            distribution_window: e.distribution_window,
         })
     }
 }
@@ -75,13 +87,16 @@ impl IServiceRepo for PostgresServiceRepo {
     async fn insert(&self, service: &Service) -> anyhow::Result<()> {
         sqlx::query!(
             r#"
-            INSERT INTO services(service_uid, account_uid, multi_person, metadata)
-            VALUES($1, $2, $3, $4)
+            INSERT INTO services(service_uid, account_uid, multi_person, metadata, cancellation_cutoff, reschedule_cutoff, distribution_window)
+            VALUES($1, $2, $3, $4, $5, $6, $7)
             "#,
             service.id.as_ref(),
             service.account_id.as_ref(),
             Json(&service.multi_person) as _,
             Json(&service.metadata) as _,
+            service.cancellation_cutoff,
+            service.reschedule_cutoff,
+            service.distribution_window,
         )
         .execute(&self.pool)
         .await
@@ -101,12 +116,18 @@ impl IServiceRepo for PostgresServiceRepo {
             r#"
             UPDATE services SET
                 multi_person = $2,
-                metadata = $3
+                metadata = $3,
+                cancellation_cutoff = $4,
+                reschedule_cutoff = $5,
+                distribution_window = $6
             WHERE service_uid = $1
             "#,
             service.id.as_ref(),
             Json(&service.multi_person) as _,
             Json(&service.metadata) as _,
+            service.cancellation_cutoff,
+            service.reschedule_cutoff,
+            service.distribution_window,
         )
         .execute(&self.pool)
         .await
@@ -194,7 +215,8 @@ impl IServiceRepo for PostgresServiceRepo {
             ServiceRaw,
             r#"
             SELECT * FROM services AS s
-            WHERE s.account_uid = $1 AND metadata @> $2
+            WHERE s.account_uid = $1 AND metadata @> $2 AND ($5::uuid IS NULL OR s.service_uid > $5)
+            ORDER BY s.service_uid
             LIMIT $3
             OFFSET $4
             "#,
@@ -202,6 +224,7 @@ impl IServiceRepo for PostgresServiceRepo {
             Json(&query.metadata) as _,
             query.limit as i64,
             query.skip as i64,
+            query.after.as_ref().map(|id| *id.as_ref()),
         )
         .fetch_all(&self.pool)
         .await
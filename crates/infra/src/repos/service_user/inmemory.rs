@@ -0,0 +1,88 @@
+use nittei_domain::{ID, ServiceResource, TimePlan};
+
+use super::IServiceUserRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryServiceUserRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryServiceUserRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+/// The calendar or the schedule used for the availability must exist
+fn ensure_availability_exists(
+    tables: &InMemoryTables,
+    user: &ServiceResource,
+) -> anyhow::Result<()> {
+    match &user.availability {
+        TimePlan::Calendar(id) => InMemoryTables::ensure_exists(&tables.calendars, id, "calendar"),
+        TimePlan::Schedule(id) => InMemoryTables::ensure_exists(&tables.schedules, id, "schedule"),
+        TimePlan::Empty => Ok(()),
+    }
+}
+
+#[async_trait::async_trait]
+impl IServiceUserRepo for InMemoryServiceUserRepo {
+    async fn insert(&self, user: &ServiceResource) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.services, &user.service_id, "service")?;
+        InMemoryTables::ensure_exists(&tables.users, &user.user_id, "user")?;
+        ensure_availability_exists(&tables, user)?;
+        if tables
+            .service_users
+            .iter()
+            .any(|u| u.service_id == user.service_id && u.user_id == user.user_id)
+        {
+            anyhow::bail!(
+                "The user with id: {} is already a member of the service with id: {}",
+                user.user_id,
+                user.service_id
+            );
+        }
+        tables.service_users.push(user.clone());
+        Ok(())
+    }
+
+    async fn save(&self, user: &ServiceResource) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        ensure_availability_exists(&tables, user)?;
+        if let Some(existing) = tables
+            .service_users
+            .iter_mut()
+            .find(|u| u.service_id == user.service_id && u.user_id == user.user_id)
+        {
+            *existing = user.clone();
+        }
+        Ok(())
+    }
+
+    async fn find(&self, service_id: &ID, user_id: &ID) -> anyhow::Result<Option<ServiceResource>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .service_users
+            .iter()
+            .find(|u| &u.service_id == service_id && &u.user_id == user_id)
+            .cloned())
+    }
+
+    async fn find_by_user(&self, user_id: &ID) -> anyhow::Result<Vec<ServiceResource>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .service_users
+            .iter()
+            .filter(|u| &u.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, service_id: &ID, user_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.delete_service_user(service_id, user_id);
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryServiceUserRepo;
 use nittei_domain::{ID, ServiceResource};
 pub use postgres::{PostgresServiceUserRepo, ServiceUserRaw};
 
@@ -16,11 +18,11 @@ pub trait IServiceUserRepo: Send + Sync {
 mod tests {
     use nittei_domain::{Account, Calendar, Entity, Service, ServiceResource, TimePlan, User};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn crud() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(crud);
+
+    async fn crud(ctx: NitteiContext) {
         let account = Account::default();
         ctx.repos.accounts.insert(&account).await.unwrap();
         let user = User::new(account.id.clone(), None);
@@ -59,6 +61,10 @@ mod tests {
 
         let mut service_user = res;
         service_user.buffer_after = 60;
+        service_user.weight = 2;
+        service_user.priority = 3;
+        service_user.max_bookings_per_day = Some(4);
+        service_user.max_bookings_per_week = Some(10);
         service_user.availability = TimePlan::Calendar(calendar.id.clone());
         assert!(ctx.repos.service_users.save(&service_user).await.is_ok());
 
@@ -70,6 +76,16 @@ mod tests {
             .unwrap()
             .unwrap();
         assert_eq!(updated_service_user.buffer_after, service_user.buffer_after);
+        assert_eq!(updated_service_user.weight, service_user.weight);
+        assert_eq!(updated_service_user.priority, service_user.priority);
+        assert_eq!(
+            updated_service_user.max_bookings_per_day,
+            service_user.max_bookings_per_day
+        );
+        assert_eq!(
+            updated_service_user.max_bookings_per_week,
+            service_user.max_bookings_per_week
+        );
         assert_eq!(updated_service_user.user_id, service_user.user_id);
         assert_eq!(updated_service_user.service_id, service_user.service_id);
 
@@ -26,6 +26,10 @@ pub struct ServiceUserRaw {
     buffer_before: i64,
     closest_booking_time: i64,
     furthest_booking_time: Option<i64>,
+    weight: i64,
+    priority: i64,
+    max_bookings_per_day: Option<i64>,
+    max_bookings_per_week: Option<i64>,
 }
 
 impl From<ServiceUserRaw> for ServiceResource {
@@ -46,6 +50,10 @@ impl From<ServiceUserRaw> for ServiceResource {
             buffer_before: e.buffer_before,
             closest_booking_time: e.closest_booking_time,
             furthest_booking_time: e.furthest_booking_time,
+            weight: e.weight,
+            priority: e.priority,
+            max_bookings_per_day: e.max_bookings_per_day,
+            max_bookings_per_week: e.max_bookings_per_week,
         }
     }
 }
@@ -62,8 +70,8 @@ impl IServiceUserRepo for PostgresServiceUserRepo {
 
         sqlx::query!(
             r#"
-            INSERT INTO service_users(service_uid, user_uid, available_calendar_uid, available_schedule_uid, buffer_after, buffer_before, closest_booking_time, furthest_booking_time)
-            VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO service_users(service_uid, user_uid, available_calendar_uid, available_schedule_uid, buffer_after, buffer_before, closest_booking_time, furthest_booking_time, weight, priority, max_bookings_per_day, max_bookings_per_week)
+            VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             "#,
             user.service_id.as_ref(),
             user.user_id.as_ref(),
@@ -73,6 +81,10 @@ impl IServiceUserRepo for PostgresServiceUserRepo {
             user.buffer_before,
             user.closest_booking_time,
             user.furthest_booking_time,
+            user.weight,
+            user.priority,
+            user.max_bookings_per_day,
+            user.max_bookings_per_week,
         )
         .execute(&self.pool)
         .await
@@ -102,7 +114,11 @@ impl IServiceUserRepo for PostgresServiceUserRepo {
                 buffer_after = $5,
                 buffer_before = $6,
                 closest_booking_time = $7,
-                furthest_booking_time = $8
+                furthest_booking_time = $8,
+                weight = $9,
+                priority = $10,
+                max_bookings_per_day = $11,
+                max_bookings_per_week = $12
             WHERE service_uid = $1 AND user_uid = $2
             "#,
             user.service_id.as_ref(),
@@ -113,6 +129,10 @@ impl IServiceUserRepo for PostgresServiceUserRepo {
             user.buffer_before,
             user.closest_booking_time,
             user.furthest_booking_time,
+            user.weight,
+            user.priority,
+            user.max_bookings_per_day,
+            user.max_bookings_per_week,
         )
         .execute(&self.pool)
         .await
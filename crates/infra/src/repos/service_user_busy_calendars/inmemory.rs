@@ -0,0 +1,149 @@
+use nittei_domain::{BusyCalendarProvider, ID, IntegrationProvider};
+
+use super::{BusyCalendarIdentifier, ExternalBusyCalendarIdentifier, IServiceUserBusyCalendarRepo};
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryServiceUserBusyCalendarRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryServiceUserBusyCalendarRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+fn ensure_service_user_exists(
+    tables: &InMemoryTables,
+    service_id: &ID,
+    user_id: &ID,
+) -> anyhow::Result<()> {
+    if !tables
+        .service_users
+        .iter()
+        .any(|u| &u.service_id == service_id && &u.user_id == user_id)
+    {
+        anyhow::bail!(
+            "The user with id: {} is not a member of the service with id: {}",
+            user_id,
+            service_id
+        );
+    }
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl IServiceUserBusyCalendarRepo for InMemoryServiceUserBusyCalendarRepo {
+    async fn exists(&self, input: BusyCalendarIdentifier) -> anyhow::Result<bool> {
+        let tables = self.store.lock().await;
+        Ok(tables.busy_calendars.iter().any(|c| {
+            c.service_id == input.service_id
+                && c.user_id == input.user_id
+                && c.calendar_id == input.calendar_id
+        }))
+    }
+
+    async fn exists_ext(&self, input: ExternalBusyCalendarIdentifier) -> anyhow::Result<bool> {
+        let tables = self.store.lock().await;
+        Ok(tables.external_busy_calendars.iter().any(|c| {
+            c.service_id == input.service_id
+                && c.user_id == input.user_id
+                && c.ext_calendar_id == input.ext_calendar_id
+        }))
+    }
+
+    async fn insert(&self, input: BusyCalendarIdentifier) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        ensure_service_user_exists(&tables, &input.service_id, &input.user_id)?;
+        InMemoryTables::ensure_exists(&tables.calendars, &input.calendar_id, "calendar")?;
+        if tables.busy_calendars.iter().any(|c| {
+            c.service_id == input.service_id
+                && c.user_id == input.user_id
+                && c.calendar_id == input.calendar_id
+        }) {
+            anyhow::bail!(
+                "The busy calendar with id: {} already exists",
+                input.calendar_id
+            );
+        }
+        tables.busy_calendars.push(input);
+        Ok(())
+    }
+
+    async fn insert_ext(&self, input: ExternalBusyCalendarIdentifier) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        ensure_service_user_exists(&tables, &input.service_id, &input.user_id)?;
+        if !tables
+            .user_integrations
+            .iter()
+            .any(|i| i.user_id == input.user_id && i.provider == input.provider)
+        {
+            anyhow::bail!(
+                "The user with id: {} has no integration for this provider",
+                input.user_id
+            );
+        }
+        if tables.external_busy_calendars.iter().any(|c| {
+            c.service_id == input.service_id
+                && c.user_id == input.user_id
+                && c.provider == input.provider
+                && c.ext_calendar_id == input.ext_calendar_id
+        }) {
+            anyhow::bail!(
+                "The external busy calendar with id: {} already exists",
+                input.ext_calendar_id
+            );
+        }
+        tables.external_busy_calendars.push(input);
+        Ok(())
+    }
+
+    async fn delete(&self, input: BusyCalendarIdentifier) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.busy_calendars.retain(|c| {
+            !(c.service_id == input.service_id
+                && c.user_id == input.user_id
+                && c.calendar_id == input.calendar_id)
+        });
+        Ok(())
+    }
+
+    async fn delete_ext(&self, input: ExternalBusyCalendarIdentifier) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        tables.external_busy_calendars.retain(|c| {
+            !(c.service_id == input.service_id
+                && c.user_id == input.user_id
+                && c.ext_calendar_id == input.ext_calendar_id
+                && c.provider == input.provider)
+        });
+        Ok(())
+    }
+
+    async fn find(
+        &self,
+        service_id: &ID,
+        user_id: &ID,
+    ) -> anyhow::Result<Vec<BusyCalendarProvider>> {
+        let tables = self.store.lock().await;
+        // The external calendars first, as the query of the Postgres repository
+        let external = tables
+            .external_busy_calendars
+            .iter()
+            .filter(|c| &c.service_id == service_id && &c.user_id == user_id)
+            .map(|c| match c.provider {
+                IntegrationProvider::Google => {
+                    BusyCalendarProvider::Google(c.ext_calendar_id.clone())
+                }
+                IntegrationProvider::Outlook => {
+                    BusyCalendarProvider::Outlook(c.ext_calendar_id.clone())
+                }
+            });
+        let nittei = tables
+            .busy_calendars
+            .iter()
+            .filter(|c| &c.service_id == service_id && &c.user_id == user_id)
+            .map(|c| BusyCalendarProvider::Nittei(c.calendar_id.clone()));
+        Ok(external.chain(nittei).collect())
+    }
+}
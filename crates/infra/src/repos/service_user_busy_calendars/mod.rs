@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryServiceUserBusyCalendarRepo;
 use nittei_domain::{BusyCalendarProvider, ID, IntegrationProvider};
 pub use postgres::PostgresServiceUseBusyCalendarRepo;
 
@@ -47,12 +49,16 @@ mod tests {
         UserIntegration,
     };
 
-    use crate::{BusyCalendarIdentifier, ExternalBusyCalendarIdentifier, setup_context};
+    use crate::{
+        BusyCalendarIdentifier,
+        ExternalBusyCalendarIdentifier,
+        NitteiContext,
+        backend_tests,
+    };
 
-    #[tokio::test]
-    async fn test_service_user_busy_calendars() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(test_service_user_busy_calendars);
 
+    async fn test_service_user_busy_calendars(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
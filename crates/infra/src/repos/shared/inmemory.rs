@@ -0,0 +1,647 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use chrono::{DateTime, SubsecRound, Utc};
+use nittei_domain::{
+    Account,
+    AccountIntegration,
+    Calendar,
+    CalendarEvent,
+    CalendarEventStatus,
+    CalendarFeed,
+    DateTimeQuery,
+    EventRemindersExpansionJob,
+    ID,
+    IDQuery,
+    IdempotencyKey,
+    IntegrationProvider,
+    JobLease,
+    OutboxMessage,
+    Reminder,
+    Schedule,
+    Service,
+    ServiceHold,
+    ServiceResource,
+    StringQuery,
+    SyncedCalendar,
+    SyncedCalendarEvent,
+    TimePlan,
+    User,
+    UserIntegration,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookEndpointStatus,
+    event_group::EventGroup,
+    scheduling::BookingPeriod,
+};
+use serde_json::Value;
+use tokio::sync::{Mutex, MutexGuard};
+use uuid::Uuid;
+
+use crate::repos::{
+    service_user_busy_calendars::{BusyCalendarIdentifier, ExternalBusyCalendarIdentifier},
+    shared::query_structs::MetadataFindQuery,
+};
+
+/// Store of the in-memory repositories, shared by all of them (like the database for the Postgres ones)
+///
+/// The tables are behind a single lock, so that a write spanning several tables (e.g. an event and its
+/// outbox messages) is atomic, and the deletions cascade as the foreign keys of the database do
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStore {
+    tables: Arc<Mutex<InMemoryTables>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn lock(&self) -> MutexGuard<'_, InMemoryTables> {
+        self.tables.lock().await
+    }
+}
+
+/// Materialized instances of a recurring event
+#[derive(Debug, Clone)]
+pub struct InstancesWindow {
+    pub window_start: DateTime<Utc>,
+    pub window_end: DateTime<Utc>,
+    pub refreshed_at: DateTime<Utc>,
+    /// End times of the instances, by start time
+    pub instances: BTreeMap<DateTime<Utc>, DateTime<Utc>>,
+}
+
+/// Tables of the in-memory store
+/// The entities with an id are keyed by it, ordered as the UUIDs in Postgres
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryTables {
+    pub accounts: BTreeMap<Uuid, Account>,
+    pub account_integrations: Vec<AccountIntegration>,
+    pub users: BTreeMap<Uuid, User>,
+    pub user_integrations: Vec<UserIntegration>,
+    pub calendars: BTreeMap<Uuid, Calendar>,
+    pub synced_calendars: Vec<SyncedCalendar>,
+    pub calendar_feeds: BTreeMap<Uuid, CalendarFeed>,
+    /// The events, with their attendees
+    pub events: BTreeMap<Uuid, CalendarEvent>,
+    pub event_groups: BTreeMap<Uuid, EventGroup>,
+    pub event_instances: HashMap<Uuid, InstancesWindow>,
+    pub synced_events: Vec<SyncedCalendarEvent>,
+    pub reminder_versions: HashMap<Uuid, i64>,
+    pub reminders: Vec<Reminder>,
+    pub reminders_generation_jobs: Vec<EventRemindersExpansionJob>,
+    pub idempotency_keys: HashMap<(String, String), IdempotencyKey>,
+    pub job_leases: HashMap<String, JobLease>,
+    pub outbox_messages: Vec<OutboxMessage>,
+    pub reservations: HashMap<(Uuid, DateTime<Utc>), i64>,
+    pub schedules: BTreeMap<Uuid, Schedule>,
+    pub services: BTreeMap<Uuid, Service>,
+    pub service_holds: BTreeMap<Uuid, ServiceHold>,
+    pub service_users: Vec<ServiceResource>,
+    pub busy_calendars: Vec<BusyCalendarIdentifier>,
+    pub external_busy_calendars: Vec<ExternalBusyCalendarIdentifier>,
+    pub webhook_deliveries: Vec<WebhookDelivery>,
+    pub webhook_delivery_attempts: Vec<WebhookDeliveryAttempt>,
+    pub webhook_endpoint_statuses: Vec<WebhookEndpointStatus>,
+}
+
+impl InMemoryTables {
+    /// Apply the writes on a copy of the tables, kept only if they all succeed (as a transaction)
+    pub fn transaction<T>(
+        &mut self,
+        writes: impl FnOnce(&mut Self) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let mut tables = self.clone();
+        let res = writes(&mut tables)?;
+        *self = tables;
+        Ok(res)
+    }
+
+    /// Check that the referenced entity exists, as a foreign key of the database
+    pub fn ensure_exists<T>(
+        table: &BTreeMap<Uuid, T>,
+        id: &ID,
+        entity: &str,
+    ) -> anyhow::Result<()> {
+        if !table.contains_key(id.as_ref()) {
+            anyhow::bail!("The {entity} with id: {id} doesn't exist");
+        }
+        Ok(())
+    }
+
+    /// Insert the outbox messages, failing (without inserting any) if one of them is already recorded
+    pub fn insert_outbox_messages(&mut self, messages: &[OutboxMessage]) -> anyhow::Result<()> {
+        for message in messages {
+            if self.outbox_messages.iter().any(|m| m.id == message.id) {
+                anyhow::bail!("The outbox message with id: {} already exists", message.id);
+            }
+            Self::ensure_exists(&self.accounts, &message.account_id, "account")?;
+        }
+        self.outbox_messages.extend_from_slice(messages);
+        Ok(())
+    }
+
+    pub fn delete_account(&mut self, account_id: &ID) -> Option<Account> {
+        let account = self.accounts.remove(account_id.as_ref())?;
+
+        let integrations = self
+            .account_integrations
+            .iter()
+            .filter(|i| &i.account_id == account_id)
+            .map(|i| i.provider.clone())
+            .collect::<Vec<_>>();
+        for provider in integrations {
+            self.delete_account_integration(account_id, &provider);
+        }
+        let user_ids = self
+            .users
+            .values()
+            .filter(|u| &u.account_id == account_id)
+            .map(|u| u.id.clone())
+            .collect::<Vec<_>>();
+        for user_id in user_ids {
+            self.delete_user(&user_id);
+        }
+        let service_ids = self
+            .services
+            .values()
+            .filter(|s| &s.account_id == account_id)
+            .map(|s| s.id.clone())
+            .collect::<Vec<_>>();
+        for service_id in service_ids {
+            self.delete_service(&service_id);
+        }
+        self.reminders.retain(|r| &r.account_id != account_id);
+        self.outbox_messages.retain(|m| &m.account_id != account_id);
+        let delivery_ids = self
+            .webhook_deliveries
+            .iter()
+            .filter(|d| &d.account_id == account_id)
+            .map(|d| d.id.clone())
+            .collect::<Vec<_>>();
+        self.delete_webhook_deliveries(&delivery_ids);
+        self.webhook_endpoint_statuses
+            .retain(|s| &s.account_id != account_id);
+
+        Some(account)
+    }
+
+    pub fn delete_account_integration(&mut self, account_id: &ID, provider: &IntegrationProvider) {
+        self.account_integrations
+            .retain(|i| !(&i.account_id == account_id && &i.provider == provider));
+        let user_ids = self
+            .user_integrations
+            .iter()
+            .filter(|i| &i.account_id == account_id && &i.provider == provider)
+            .map(|i| i.user_id.clone())
+            .collect::<Vec<_>>();
+        for user_id in user_ids {
+            self.delete_user_integration(&user_id, provider);
+        }
+    }
+
+    pub fn delete_user(&mut self, user_id: &ID) -> Option<User> {
+        let user = self.users.remove(user_id.as_ref())?;
+
+        let providers = self
+            .user_integrations
+            .iter()
+            .filter(|i| &i.user_id == user_id)
+            .map(|i| i.provider.clone())
+            .collect::<Vec<_>>();
+        for provider in providers {
+            self.delete_user_integration(user_id, &provider);
+        }
+        let service_ids = self
+            .service_users
+            .iter()
+            .filter(|u| &u.user_id == user_id)
+            .map(|u| u.service_id.clone())
+            .collect::<Vec<_>>();
+        for service_id in service_ids {
+            self.delete_service_user(&service_id, user_id);
+        }
+        let calendar_ids = self
+            .calendars
+            .values()
+            .filter(|c| &c.user_id == user_id)
+            .map(|c| c.id.clone())
+            .collect::<Vec<_>>();
+        for calendar_id in calendar_ids {
+            self.delete_calendar(&calendar_id);
+        }
+        let schedule_ids = self
+            .schedules
+            .values()
+            .filter(|s| &s.user_id == user_id)
+            .map(|s| s.id.clone())
+            .collect::<Vec<_>>();
+        for schedule_id in schedule_ids {
+            self.delete_schedule(&schedule_id);
+        }
+        for event in self.events.values_mut() {
+            event
+                .attendees
+                .retain(|a| a.user_id.as_ref() != Some(user_id));
+        }
+        let group_ids = self
+            .event_groups
+            .values()
+            .filter(|g| &g.user_id == user_id)
+            .map(|g| g.id.clone())
+            .collect::<Vec<_>>();
+        for group_id in group_ids {
+            self.delete_event_group(&group_id);
+        }
+        self.calendar_feeds.retain(|_, f| &f.user_id != user_id);
+
+        Some(user)
+    }
+
+    pub fn delete_user_integration(&mut self, user_id: &ID, provider: &IntegrationProvider) {
+        self.user_integrations
+            .retain(|i| !(&i.user_id == user_id && &i.provider == provider));
+        let synced_calendars = self
+            .synced_calendars
+            .iter()
+            .filter(|c| &c.user_id == user_id && &c.provider == provider)
+            .cloned()
+            .collect::<Vec<_>>();
+        for synced_calendar in synced_calendars {
+            self.delete_synced_calendar(&synced_calendar);
+        }
+        self.external_busy_calendars
+            .retain(|c| !(&c.user_id == user_id && &c.provider == provider));
+    }
+
+    /// Returns false if the synced calendar doesn't exist
+    pub fn delete_synced_calendar(&mut self, synced_calendar: &SyncedCalendar) -> bool {
+        let len = self.synced_calendars.len();
+        self.synced_calendars.retain(|c| {
+            !(c.calendar_id == synced_calendar.calendar_id
+                && c.user_id == synced_calendar.user_id
+                && c.ext_calendar_id == synced_calendar.ext_calendar_id
+                && c.provider == synced_calendar.provider)
+        });
+        let deleted = self.synced_calendars.len() < len;
+        if deleted {
+            self.synced_events.retain(|e| {
+                !(e.calendar_id == synced_calendar.calendar_id
+                    && e.ext_calendar_id == synced_calendar.ext_calendar_id
+                    && e.provider == synced_calendar.provider)
+            });
+        }
+        deleted
+    }
+
+    /// The calendar can't be deleted while it is a busy calendar of a service user
+    /// (the foreign key of the database doesn't cascade)
+    pub fn ensure_calendar_deletable(&self, calendar_id: &ID) -> anyhow::Result<()> {
+        if self
+            .busy_calendars
+            .iter()
+            .any(|c| &c.calendar_id == calendar_id)
+        {
+            anyhow::bail!(
+                "The calendar with id: {calendar_id} is a busy calendar of a service user"
+            );
+        }
+        Ok(())
+    }
+
+    pub fn delete_calendar(&mut self, calendar_id: &ID) -> Option<Calendar> {
+        let calendar = self.calendars.remove(calendar_id.as_ref())?;
+
+        self.synced_calendars
+            .retain(|c| &c.calendar_id != calendar_id);
+        self.synced_events.retain(|e| &e.calendar_id != calendar_id);
+        let event_ids = self
+            .events
+            .values()
+            .filter(|e| &e.calendar_id == calendar_id)
+            .map(|e| e.id.clone())
+            .collect::<Vec<_>>();
+        for event_id in event_ids {
+            self.delete_event(&event_id);
+        }
+        let group_ids = self
+            .event_groups
+            .values()
+            .filter(|g| &g.calendar_id == calendar_id)
+            .map(|g| g.id.clone())
+            .collect::<Vec<_>>();
+        for group_id in group_ids {
+            self.delete_event_group(&group_id);
+        }
+        self.calendar_feeds
+            .retain(|_, f| &f.calendar_id != calendar_id);
+        for service_user in self.service_users.iter_mut() {
+            if matches!(&service_user.availability, TimePlan::Calendar(id) if id == calendar_id) {
+                service_user.availability = TimePlan::Empty;
+            }
+        }
+
+        Some(calendar)
+    }
+
+    pub fn delete_event(&mut self, event_id: &ID) -> Option<CalendarEvent> {
+        let event = self.events.remove(event_id.as_ref())?;
+
+        self.remove_event_instances(&event);
+        self.synced_events.retain(|e| &e.event_id != event_id);
+        self.reminder_versions.remove(event_id.as_ref());
+        self.reminders.retain(|r| &r.event_id != event_id);
+        self.reminders_generation_jobs
+            .retain(|j| &j.event_id != event_id);
+
+        Some(event)
+    }
+
+    /// Remove the materialized instances of the event and of its recurring event (if it is an exception),
+    /// as the trigger of the database does when the occurrences of an event change
+    pub fn remove_event_instances(&mut self, event: &CalendarEvent) {
+        self.event_instances.remove(event.id.as_ref());
+        if let Some(recurring_event_id) = &event.recurring_event_id {
+            self.event_instances.remove(recurring_event_id.as_ref());
+        }
+    }
+
+    /// Detach the events of the group, and delete it
+    pub fn delete_event_group(&mut self, group_id: &ID) -> Option<EventGroup> {
+        let group = self.event_groups.remove(group_id.as_ref())?;
+        for event in self.events.values_mut() {
+            if event.group_id.as_ref() == Some(group_id) {
+                event.group_id = None;
+            }
+        }
+        Some(group)
+    }
+
+    pub fn delete_schedule(&mut self, schedule_id: &ID) -> Option<Schedule> {
+        let schedule = self.schedules.remove(schedule_id.as_ref())?;
+        for service_user in self.service_users.iter_mut() {
+            if matches!(&service_user.availability, TimePlan::Schedule(id) if id == schedule_id) {
+                service_user.availability = TimePlan::Empty;
+            }
+        }
+        Some(schedule)
+    }
+
+    pub fn delete_service(&mut self, service_id: &ID) -> Option<Service> {
+        let service = self.services.remove(service_id.as_ref())?;
+
+        let event_ids = self
+            .events
+            .values()
+            .filter(|e| e.service_id.as_ref() == Some(service_id))
+            .map(|e| e.id.clone())
+            .collect::<Vec<_>>();
+        for event_id in event_ids {
+            self.delete_event(&event_id);
+        }
+        let user_ids = self
+            .service_users
+            .iter()
+            .filter(|u| &u.service_id == service_id)
+            .map(|u| u.user_id.clone())
+            .collect::<Vec<_>>();
+        for user_id in user_ids {
+            self.delete_service_user(service_id, &user_id);
+        }
+        self.reservations
+            .retain(|(id, _), _| id != service_id.as_ref());
+        self.service_holds
+            .retain(|_, hold| &hold.service_id != service_id);
+
+        Some(service)
+    }
+
+    /// Whether the user has a (non cancelled) service event or a (non expired) service hold
+    /// overlapping the timespan, which prevents booking or holding a service for the user at this time
+    pub fn has_service_booking_or_hold(
+        &self,
+        user_id: &ID,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let has_service_event = self.events.values().any(|e| {
+            &e.user_id == user_id
+                && e.service_id.is_some()
+                && e.status != CalendarEventStatus::Cancelled
+                && e.start_time < end
+                && e.end_time > start
+        });
+        let has_hold = self.service_holds.values().any(|hold| {
+            !hold.is_expired(now)
+                && hold.host_user_ids.contains(user_id)
+                && hold.start_time < end
+                && hold.end_time > start
+        });
+        has_service_event || has_hold
+    }
+
+    /// Whether the user has already started `max` (non cancelled) service events
+    /// or (non expired) service holds in one of the periods
+    pub fn has_reached_booking_limits(
+        &self,
+        user_id: &ID,
+        periods: &[BookingPeriod],
+        now: DateTime<Utc>,
+    ) -> bool {
+        periods.iter().any(|period| {
+            let in_period = |start_time: DateTime<Utc>| {
+                start_time >= period.start_time && start_time < period.end_time
+            };
+            let service_events = self
+                .events
+                .values()
+                .filter(|e| {
+                    &e.user_id == user_id
+                        && e.service_id.is_some()
+                        && e.status != CalendarEventStatus::Cancelled
+                        && in_period(e.start_time)
+                })
+                .count();
+            let holds = self
+                .service_holds
+                .values()
+                .filter(|hold| {
+                    !hold.is_expired(now)
+                        && hold.host_user_ids.contains(user_id)
+                        && in_period(hold.start_time)
+                })
+                .count();
+            i64::try_from(service_events + holds).unwrap_or(i64::MAX) >= period.max
+        })
+    }
+
+    pub fn delete_service_user(&mut self, service_id: &ID, user_id: &ID) {
+        self.service_users
+            .retain(|u| !(&u.service_id == service_id && &u.user_id == user_id));
+        self.busy_calendars
+            .retain(|c| !(&c.service_id == service_id && &c.user_id == user_id));
+        self.external_busy_calendars
+            .retain(|c| !(&c.service_id == service_id && &c.user_id == user_id));
+    }
+
+    /// Delete the deliveries, with their attempts
+    pub fn delete_webhook_deliveries(&mut self, delivery_ids: &[ID]) {
+        self.webhook_deliveries
+            .retain(|d| !delivery_ids.contains(&d.id));
+        self.webhook_delivery_attempts
+            .retain(|a| !delivery_ids.contains(&a.delivery_id));
+    }
+}
+
+/// Timestamp as saved in the database, which only keeps the milliseconds (e.g. `created` of the events)
+pub fn to_millis_precision(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.trunc_subsecs(3)
+}
+
+/// In-memory counterpart of `apply_id_query`
+/// A missing value doesn't match any condition, but `Exists(false)` (as NULL in SQL)
+pub fn matches_id_query(value: Option<&ID>, id_query: &IDQuery) -> bool {
+    let value = value.map(|id| id.as_ref());
+    match (id_query, value) {
+        (IDQuery::Exists(exists), value) => *exists == value.is_some(),
+        (_, None) => false,
+        (IDQuery::Eq(id), Some(value)) => value == id.as_ref(),
+        (IDQuery::Ne(id), Some(value)) => value != id.as_ref(),
+        (IDQuery::In(ids), Some(value)) => ids.iter().any(|id| id.as_ref() == value),
+        (IDQuery::Nin(ids), Some(value)) => ids.iter().all(|id| id.as_ref() != value),
+        (IDQuery::Gt(id), Some(value)) => value > id.as_ref(),
+        (IDQuery::Gte(id), Some(value)) => value >= id.as_ref(),
+        (IDQuery::Lt(id), Some(value)) => value < id.as_ref(),
+        (IDQuery::Lte(id), Some(value)) => value <= id.as_ref(),
+    }
+}
+
+/// In-memory counterpart of `apply_string_query`
+pub fn matches_string_query(value: Option<&str>, string_query: &StringQuery) -> bool {
+    match (string_query, value) {
+        (StringQuery::Exists(exists), value) => *exists == value.is_some(),
+        (_, None) => false,
+        (StringQuery::Eq(eq), Some(value)) => value == eq,
+        (StringQuery::Ne(ne), Some(value)) => value != ne,
+        (StringQuery::In(values), Some(value)) => values.iter().any(|v| v == value),
+    }
+}
+
+/// In-memory counterpart of `apply_datetime_query`
+/// With `millis`, the values are compared in milliseconds (as the `created` and `updated` columns)
+pub fn matches_datetime_query(
+    value: Option<DateTime<Utc>>,
+    datetime_query: &DateTimeQuery,
+    millis: bool,
+) -> bool {
+    let Some(value) = value else {
+        return false;
+    };
+    let precision = |time: DateTime<Utc>| {
+        if millis {
+            to_millis_precision(time)
+        } else {
+            time
+        }
+    };
+    let value = precision(value);
+    match datetime_query {
+        DateTimeQuery::Eq(eq) => value == precision(*eq),
+        DateTimeQuery::Range(range) => {
+            let lower = match (range.gte, range.gt) {
+                (Some(gte), _) => value >= precision(gte),
+                (None, Some(gt)) => value > precision(gt),
+                (None, None) => true,
+            };
+            let upper = match (range.lte, range.lt) {
+                (Some(lte), _) => value <= precision(lte),
+                (None, Some(lt)) => value < precision(lt),
+                (None, None) => true,
+            };
+            lower && upper
+        }
+    }
+}
+
+/// JSON containment, as the `@>` operator of Postgres on `jsonb`
+/// * objects contain the objects with a subset of their keys, whose values are contained
+/// * arrays contain the arrays whose elements are each contained in one of their elements
+/// * a top level array contains a primitive value it has as element
+/// * primitive values only contain the same values
+pub fn json_contains(value: &Value, contained: &Value) -> bool {
+    match (value, contained) {
+        (Value::Array(values), contained) if !contained.is_array() && !contained.is_object() => {
+            values.contains(contained)
+        }
+        _ => json_contains_nested(value, contained),
+    }
+}
+
+fn json_contains_nested(value: &Value, contained: &Value) -> bool {
+    match (value, contained) {
+        (Value::Object(object), Value::Object(contained)) => contained.iter().all(|(key, v)| {
+            object
+                .get(key)
+                .is_some_and(|value| json_contains_nested(value, v))
+        }),
+        (Value::Array(values), Value::Array(contained)) => contained
+            .iter()
+            .all(|c| values.iter().any(|v| json_contains_nested(v, c))),
+        (Value::Number(number), Value::Number(contained)) => number.as_f64() == contained.as_f64(),
+        (value, contained) => value == contained,
+    }
+}
+
+/// Page of the entities of the account containing the metadata, ordered by their ids (as `find_by_metadata`)
+pub fn find_by_metadata<'a, T: Clone + 'a>(
+    entities: impl Iterator<Item = (&'a Uuid, &'a T)>,
+    query: &MetadataFindQuery,
+    account_id: impl Fn(&T) -> &ID,
+    metadata: impl Fn(&T) -> Option<&Value>,
+) -> anyhow::Result<Vec<T>> {
+    let contained = serde_json::to_value(&query.metadata)?;
+    Ok(entities
+        .filter(|(id, _)| {
+            query
+                .after
+                .as_ref()
+                .is_none_or(|after| *id > after.as_ref())
+        })
+        .filter(|(_, e)| account_id(e) == &query.account_id)
+        .filter(|(_, e)| json_contains(metadata(e).unwrap_or(&Value::Null), &contained))
+        .skip(query.skip)
+        .take(query.limit)
+        .map(|(_, e)| e.clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::json_contains;
+
+    #[test]
+    fn json_containment() {
+        let value =
+            json!({ "freq": "weekly", "byhour": [9, 14], "nested": { "a": 1, "b": [1, 2] } });
+        assert!(json_contains(&value, &json!({})));
+        assert!(json_contains(&value, &json!({ "freq": "weekly" })));
+        assert!(json_contains(&value, &json!({ "byhour": [14] })));
+        assert!(json_contains(&value, &json!({ "byhour": [] })));
+        assert!(json_contains(&value, &json!({ "nested": { "b": [2] } })));
+        assert!(!json_contains(&value, &json!({ "byhour": [10] })));
+        assert!(!json_contains(&value, &json!({ "byhour": 9 })));
+        assert!(!json_contains(&value, &json!({ "freq": "daily" })));
+        assert!(!json_contains(&value, &json!({ "missing": null })));
+
+        assert!(json_contains(&json!([1, "a"]), &json!("a")));
+        assert!(json_contains(&json!([[1, 2]]), &json!([[2]])));
+        assert!(!json_contains(&json!([[1, 2]]), &json!([2])));
+        assert!(!json_contains(&Value::Null, &json!({})));
+    }
+}
@@ -1 +1,2 @@
+pub mod inmemory;
 pub mod query_structs;
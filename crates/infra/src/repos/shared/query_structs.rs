@@ -3,11 +3,14 @@ use sqlx::Postgres;
 use uuid::Uuid;
 
 /// Query for finding events based on metadata only
+/// The entities are ordered by their ids
 #[derive(Debug, Clone)]
 pub struct MetadataFindQuery {
     pub metadata: Metadata,
     pub skip: usize,
     pub limit: usize,
+    /// Only the entities with an id greater than this one (keyset pagination)
+    pub after: Option<ID>,
     pub account_id: ID,
 }
 
@@ -27,26 +30,37 @@ pub fn apply_id_query(
     query_builder: &mut sqlx::QueryBuilder<Postgres>,
     field_name: &str,
     id_query: &Option<IDQuery>,
+) {
+    apply_id_query_with_alias(query_builder, "e", field_name, id_query);
+}
+
+/// Apply the conditions for the "ID query" to the field of the table with the given alias
+/// (see `apply_id_query`)
+pub fn apply_id_query_with_alias(
+    query_builder: &mut sqlx::QueryBuilder<Postgres>,
+    alias: &str,
+    field_name: &str,
+    id_query: &Option<IDQuery>,
 ) {
     if let Some(id_query) = id_query {
         match id_query {
             IDQuery::Eq(id) => {
-                query_builder.push(format!(" AND e.{field_name} = "));
+                query_builder.push(format!(" AND {alias}.{field_name} = "));
                 query_builder.push_bind::<Uuid>(id.clone().into());
             }
             IDQuery::Ne(id) => {
-                query_builder.push(format!(" AND e.{field_name} != "));
+                query_builder.push(format!(" AND {alias}.{field_name} != "));
                 query_builder.push_bind::<Uuid>(id.clone().into());
             }
             IDQuery::Exists(exists) => {
                 if *exists {
-                    query_builder.push(format!(" AND e.{field_name} IS NOT NULL"));
+                    query_builder.push(format!(" AND {alias}.{field_name} IS NOT NULL"));
                 } else {
-                    query_builder.push(format!(" AND e.{field_name} IS NULL"));
+                    query_builder.push(format!(" AND {alias}.{field_name} IS NULL"));
                 };
             }
             IDQuery::In(ids) => {
-                query_builder.push(format!(" AND e.{field_name} IN ("));
+                query_builder.push(format!(" AND {alias}.{field_name} IN ("));
                 let mut separated = query_builder.separated(", ");
                 for id in ids.iter() {
                     separated.push_bind::<Uuid>(id.clone().into());
@@ -54,7 +68,7 @@ pub fn apply_id_query(
                 separated.push_unseparated(")");
             }
             IDQuery::Nin(ids) => {
-                query_builder.push(format!(" AND e.{field_name} NOT IN ("));
+                query_builder.push(format!(" AND {alias}.{field_name} NOT IN ("));
                 let mut separated = query_builder.separated(", ");
                 for id in ids.iter() {
                     separated.push_bind::<Uuid>(id.clone().into());
@@ -62,19 +76,19 @@ pub fn apply_id_query(
                 separated.push_unseparated(")");
             }
             IDQuery::Gt(id) => {
-                query_builder.push(format!(" AND e.{field_name} > "));
+                query_builder.push(format!(" AND {alias}.{field_name} > "));
                 query_builder.push_bind::<Uuid>(id.clone().into());
             }
             IDQuery::Gte(id) => {
-                query_builder.push(format!(" AND e.{field_name} >= "));
+                query_builder.push(format!(" AND {alias}.{field_name} >= "));
                 query_builder.push_bind::<Uuid>(id.clone().into());
             }
             IDQuery::Lt(id) => {
-                query_builder.push(format!(" AND e.{field_name} < "));
+                query_builder.push(format!(" AND {alias}.{field_name} < "));
                 query_builder.push_bind::<Uuid>(id.clone().into());
             }
             IDQuery::Lte(id) => {
-                query_builder.push(format!(" AND e.{field_name} <= "));
+                query_builder.push(format!(" AND {alias}.{field_name} <= "));
                 query_builder.push_bind::<Uuid>(id.clone().into());
             }
         }
@@ -95,26 +109,37 @@ pub fn apply_string_query(
     query_builder: &mut sqlx::QueryBuilder<Postgres>,
     field_name: &str,
     string_query: &Option<StringQuery>,
+) {
+    apply_string_query_with_alias(query_builder, "e", field_name, string_query);
+}
+
+/// Apply the conditions for the "string query" to the field of the table with the given alias
+/// (see `apply_string_query`)
+pub fn apply_string_query_with_alias(
+    query_builder: &mut sqlx::QueryBuilder<Postgres>,
+    alias: &str,
+    field_name: &str,
+    string_query: &Option<StringQuery>,
 ) {
     if let Some(string_query) = string_query {
         match string_query {
             StringQuery::Eq(eq_query) => {
-                query_builder.push(format!(" AND e.{field_name} = "));
+                query_builder.push(format!(" AND {alias}.{field_name} = "));
                 query_builder.push_bind(eq_query.clone());
             }
             StringQuery::Ne(ne_query) => {
-                query_builder.push(format!(" AND e.{field_name} != "));
+                query_builder.push(format!(" AND {alias}.{field_name} != "));
                 query_builder.push_bind(ne_query.clone());
             }
             StringQuery::Exists(exists_query) => {
                 if *exists_query {
-                    query_builder.push(format!(" AND e.{field_name} IS NOT NULL"));
+                    query_builder.push(format!(" AND {alias}.{field_name} IS NOT NULL"));
                 } else {
-                    query_builder.push(format!(" AND e.{field_name} IS NULL"));
+                    query_builder.push(format!(" AND {alias}.{field_name} IS NULL"));
                 };
             }
             StringQuery::In(in_query) => {
-                query_builder.push(format!(" AND e.{field_name} IN ("));
+                query_builder.push(format!(" AND {alias}.{field_name} IN ("));
                 let mut separated = query_builder.separated(", ");
                 for value in in_query.iter() {
                     separated.push_bind(value.clone());
@@ -140,11 +165,29 @@ pub fn apply_datetime_query(
     field_name: &str,
     datetime_query: &Option<DateTimeQuery>,
     convert_to_millis: bool,
+) {
+    apply_datetime_query_with_alias(
+        query_builder,
+        "e",
+        field_name,
+        datetime_query,
+        convert_to_millis,
+    );
+}
+
+/// Apply the conditions for the "date query" to the field of the table with the given alias
+/// (see `apply_datetime_query`)
+pub fn apply_datetime_query_with_alias(
+    query_builder: &mut sqlx::QueryBuilder<Postgres>,
+    alias: &str,
+    field_name: &str,
+    datetime_query: &Option<DateTimeQuery>,
+    convert_to_millis: bool,
 ) {
     if let Some(datetime_query) = datetime_query {
         match datetime_query {
             DateTimeQuery::Eq(eq_query) => {
-                query_builder.push(format!(" AND e.{field_name} = "));
+                query_builder.push(format!(" AND {alias}.{field_name} = "));
                 if convert_to_millis {
                     query_builder.push_bind(eq_query.timestamp_millis());
                 } else {
@@ -153,14 +196,14 @@ pub fn apply_datetime_query(
             }
             DateTimeQuery::Range(range) => {
                 if let Some(gte_query) = range.gte {
-                    query_builder.push(format!(" AND e.{field_name} >= "));
+                    query_builder.push(format!(" AND {alias}.{field_name} >= "));
                     if convert_to_millis {
                         query_builder.push_bind(gte_query.timestamp_millis());
                     } else {
                         query_builder.push_bind(gte_query);
                     };
                 } else if let Some(gt_query) = range.gt {
-                    query_builder.push(format!(" AND e.{field_name} > "));
+                    query_builder.push(format!(" AND {alias}.{field_name} > "));
                     if convert_to_millis {
                         query_builder.push_bind(gt_query.timestamp_millis());
                     } else {
@@ -169,14 +212,14 @@ pub fn apply_datetime_query(
                 }
 
                 if let Some(lte_query) = range.lte {
-                    query_builder.push(format!(" AND e.{field_name} <= "));
+                    query_builder.push(format!(" AND {alias}.{field_name} <= "));
                     if convert_to_millis {
                         query_builder.push_bind(lte_query.timestamp_millis());
                     } else {
                         query_builder.push_bind(lte_query);
                     };
                 } else if let Some(lt_query) = range.lt {
-                    query_builder.push(format!(" AND e.{field_name} < "));
+                    query_builder.push(format!(" AND {alias}.{field_name} < "));
                     if convert_to_millis {
                         query_builder.push_bind(lt_query.timestamp_millis());
                     } else {
@@ -211,6 +254,20 @@ mod test {
         assert_eq!(built_query.sql(), " AND e.id = $1");
     }
 
+    #[test]
+    fn it_applies_id_query_with_alias() {
+        let mut query_builder = sqlx::QueryBuilder::new("");
+
+        let id1 = ID::default();
+        let id_query = Some(IDQuery::Eq(id1));
+
+        apply_id_query_with_alias(&mut query_builder, "a", "user_uid", &id_query);
+
+        let built_query = query_builder.build();
+
+        assert_eq!(built_query.sql(), " AND a.user_uid = $1");
+    }
+
     #[test]
     fn it_applies_id_query_for_ne() {
         let mut query_builder = sqlx::QueryBuilder::new("");
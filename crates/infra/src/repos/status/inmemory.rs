@@ -0,0 +1,18 @@
+use super::IStatusRepo;
+
+/// The in-memory repositories are always available
+#[derive(Debug)]
+pub struct InMemoryStatusRepo;
+
+impl InMemoryStatusRepo {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl IStatusRepo for InMemoryStatusRepo {
+    async fn check_connection(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
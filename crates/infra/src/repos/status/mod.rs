@@ -1,5 +1,7 @@
+pub use inmemory::InMemoryStatusRepo;
 pub use postgres::PostgresStatusRepo;
 
+mod inmemory;
 mod postgres;
 
 /// The status repository trait
@@ -12,12 +14,11 @@ pub trait IStatusRepo: Send + Sync {
 #[cfg(test)]
 mod tests {
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn check_connection() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(check_connection);
 
+    async fn check_connection(ctx: NitteiContext) {
         // Should not panic
         ctx.repos.status.check_connection().await.unwrap();
     }
@@ -0,0 +1,107 @@
+use nittei_domain::{ID, User};
+
+use super::IUserRepo;
+use crate::repos::shared::{
+    inmemory::{self, InMemoryStore, InMemoryTables},
+    query_structs::MetadataFindQuery,
+};
+
+#[derive(Debug)]
+pub struct InMemoryUserRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryUserRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IUserRepo for InMemoryUserRepo {
+    async fn insert(&self, user: &User) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.accounts, &user.account_id, "account")?;
+        if tables.users.contains_key(user.id.as_ref()) {
+            anyhow::bail!("The user with id: {} already exists", user.id);
+        }
+        if user.external_id.is_some()
+            && tables
+                .users
+                .values()
+                .any(|u| u.external_id == user.external_id)
+        {
+            anyhow::bail!("A user with this external id already exists");
+        }
+        tables.users.insert(*user.id.as_ref(), user.clone());
+        Ok(())
+    }
+
+    async fn save(&self, user: &User) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if user.external_id.is_some()
+            && tables
+                .users
+                .values()
+                .any(|u| u.id != user.id && u.external_id == user.external_id)
+        {
+            anyhow::bail!("A user with this external id already exists");
+        }
+        if let Some(existing) = tables.users.get_mut(user.id.as_ref()) {
+            *existing = user.clone();
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: &ID) -> anyhow::Result<Option<User>> {
+        let mut tables = self.store.lock().await;
+        Ok(tables.delete_user(user_id))
+    }
+
+    async fn find(&self, user_id: &ID) -> anyhow::Result<Option<User>> {
+        let tables = self.store.lock().await;
+        Ok(tables.users.get(user_id.as_ref()).cloned())
+    }
+
+    async fn find_many(&self, user_ids: &[ID]) -> anyhow::Result<Vec<User>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .users
+            .values()
+            .filter(|u| user_ids.contains(&u.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_by_account_id(
+        &self,
+        user_id: &ID,
+        account_id: &ID,
+    ) -> anyhow::Result<Option<User>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .users
+            .get(user_id.as_ref())
+            .filter(|u| &u.account_id == account_id)
+            .cloned())
+    }
+
+    async fn get_by_external_id(&self, external_id: &str) -> anyhow::Result<Option<User>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .users
+            .values()
+            .find(|u| u.external_id.as_deref() == Some(external_id))
+            .cloned())
+    }
+
+    async fn find_by_metadata(&self, query: MetadataFindQuery) -> anyhow::Result<Vec<User>> {
+        let tables = self.store.lock().await;
+        inmemory::find_by_metadata(
+            tables.users.iter(),
+            &query,
+            |u| &u.account_id,
+            |u| u.metadata.as_ref(),
+        )
+    }
+}
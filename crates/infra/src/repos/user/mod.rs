@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryUserRepo;
 use nittei_domain::{ID, User};
 pub use postgres::PostgresUserRepo;
 
@@ -26,12 +28,11 @@ mod tests {
     use nittei_domain::{Account, Metadata};
 
     use super::*;
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn test_metadata_query() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(test_metadata_query);
 
+    async fn test_metadata_query(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
@@ -46,6 +47,7 @@ mod tests {
             limit: 100,
             metadata: Metadata::new_kv("group_id".to_string(), "123".to_string()),
             skip: 0,
+            after: None,
         };
 
         assert!(
@@ -72,6 +74,19 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert_eq!(res[0].id, user.id);
 
+        // The users are ordered by their ids, so none is after this one
+        assert!(
+            ctx.repos
+                .users
+                .find_by_metadata(MetadataFindQuery {
+                    after: Some(user.id.clone()),
+                    ..query.clone()
+                })
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
         // Different account id should give no results
         query.account_id = ID::default();
         assert!(
@@ -227,7 +227,8 @@ impl IUserRepo for PostgresUserRepo {
             UserRaw,
             r#"
             SELECT * FROM users AS u
-            WHERE u.account_uid = $1 AND metadata @> $2
+            WHERE u.account_uid = $1 AND metadata @> $2 AND ($5::uuid IS NULL OR u.user_uid > $5)
+            ORDER BY u.user_uid
             LIMIT $3
             OFFSET $4
             "#,
@@ -235,6 +236,7 @@ impl IUserRepo for PostgresUserRepo {
             Json(&query.metadata) as _,
             query.limit as i64,
             query.skip as i64,
+            query.after.as_ref().map(|id| *id.as_ref()),
         )
         .fetch_all(&self.pool)
         .await
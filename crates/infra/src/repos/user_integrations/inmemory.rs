@@ -0,0 +1,82 @@
+use nittei_domain::{ID, IntegrationProvider, UserIntegration};
+
+use super::IUserIntegrationRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryUserIntegrationRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryUserIntegrationRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IUserIntegrationRepo for InMemoryUserIntegrationRepo {
+    async fn insert(&self, integration: &UserIntegration) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.users, &integration.user_id, "user")?;
+        if !tables
+            .account_integrations
+            .iter()
+            .any(|i| i.account_id == integration.account_id && i.provider == integration.provider)
+        {
+            anyhow::bail!(
+                "The account with id: {} has no integration for this provider",
+                integration.account_id
+            );
+        }
+        if tables
+            .user_integrations
+            .iter()
+            .any(|i| i.user_id == integration.user_id && i.provider == integration.provider)
+        {
+            anyhow::bail!(
+                "The user with id: {} already has an integration for this provider",
+                integration.user_id
+            );
+        }
+        tables.user_integrations.push(integration.clone());
+        Ok(())
+    }
+
+    async fn save(&self, integration: &UserIntegration) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(existing) = tables
+            .user_integrations
+            .iter_mut()
+            .find(|i| i.user_id == integration.user_id && i.provider == integration.provider)
+        {
+            existing.access_token = integration.access_token.clone();
+            existing.access_token_expires_ts = integration.access_token_expires_ts;
+            existing.refresh_token = integration.refresh_token.clone();
+        }
+        Ok(())
+    }
+
+    async fn find(&self, user_id: &ID) -> anyhow::Result<Vec<UserIntegration>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .user_integrations
+            .iter()
+            .filter(|i| &i.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, user_id: &ID, provider: IntegrationProvider) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if !tables
+            .user_integrations
+            .iter()
+            .any(|i| &i.user_id == user_id && i.provider == provider)
+        {
+            return Err(anyhow::Error::msg("Unable to delete user integration"));
+        }
+        tables.delete_user_integration(user_id, &provider);
+        Ok(())
+    }
+}
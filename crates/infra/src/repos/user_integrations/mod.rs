@@ -1,5 +1,7 @@
+mod inmemory;
 mod postgres;
 
+pub use inmemory::InMemoryUserIntegrationRepo;
 use nittei_domain::{ID, IntegrationProvider, UserIntegration};
 pub use postgres::PostgresUserIntegrationRepo;
 
@@ -15,12 +17,11 @@ pub trait IUserIntegrationRepo: Send + Sync {
 mod tests {
     use nittei_domain::{Account, AccountIntegration, IntegrationProvider, User, UserIntegration};
 
-    use crate::setup_context;
+    use crate::{NitteiContext, backend_tests};
 
-    #[tokio::test]
-    async fn test_user_integrations() {
-        let ctx = setup_context().await.unwrap();
+    backend_tests!(test_user_integrations);
 
+    async fn test_user_integrations(ctx: NitteiContext) {
         let account = Account::new();
         ctx.repos
             .accounts
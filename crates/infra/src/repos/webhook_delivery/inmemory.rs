@@ -0,0 +1,229 @@
+use chrono::{DateTime, Utc};
+use nittei_domain::{
+    ID,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+    WebhookEndpointStatus,
+};
+
+use super::IWebhookDeliveryRepo;
+use crate::repos::shared::inmemory::{InMemoryStore, InMemoryTables};
+
+#[derive(Debug)]
+pub struct InMemoryWebhookDeliveryRepo {
+    store: InMemoryStore,
+}
+
+impl InMemoryWebhookDeliveryRepo {
+    pub fn new(store: InMemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait::async_trait]
+impl IWebhookDeliveryRepo for InMemoryWebhookDeliveryRepo {
+    async fn insert(&self, deliveries: &[WebhookDelivery]) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        for delivery in deliveries {
+            InMemoryTables::ensure_exists(&tables.accounts, &delivery.account_id, "account")?;
+        }
+        for delivery in deliveries {
+            if !tables
+                .webhook_deliveries
+                .iter()
+                .any(|d| d.id == delivery.id)
+            {
+                tables.webhook_deliveries.push(delivery.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<WebhookDelivery>> {
+        let mut tables = self.store.lock().await;
+        let mut due = tables
+            .webhook_deliveries
+            .iter_mut()
+            .filter(|d| d.status == WebhookDeliveryStatus::Pending && d.next_attempt_at <= now)
+            .collect::<Vec<_>>();
+        due.sort_by_key(|d| d.next_attempt_at);
+
+        Ok(due
+            .into_iter()
+            .take(usize::try_from(limit)?)
+            .map(|d| {
+                d.attempts += 1;
+                d.next_attempt_at = lease_until;
+                d.clone()
+            })
+            .collect())
+    }
+
+    async fn record_attempt(
+        &self,
+        account_id: &ID,
+        attempt: &WebhookDeliveryAttempt,
+        status: WebhookDeliveryStatus,
+        retry_at: Option<DateTime<Utc>>,
+        failing_threshold: i32,
+    ) -> anyhow::Result<WebhookEndpointStatus> {
+        let mut tables = self.store.lock().await;
+        InMemoryTables::ensure_exists(&tables.accounts, account_id, "account")?;
+        let Some(delivery) = tables
+            .webhook_deliveries
+            .iter_mut()
+            .find(|d| d.id == attempt.delivery_id)
+        else {
+            anyhow::bail!(
+                "The webhook delivery with id: {} doesn't exist",
+                attempt.delivery_id
+            );
+        };
+        delivery.status = status;
+        delivery.next_attempt_at = retry_at.unwrap_or(delivery.next_attempt_at);
+        tables.webhook_delivery_attempts.push(attempt.clone());
+
+        let success = attempt.is_success();
+        let endpoint_status = match tables
+            .webhook_endpoint_statuses
+            .iter_mut()
+            .find(|s| &s.account_id == account_id && s.url == attempt.url)
+        {
+            Some(endpoint_status) => {
+                if success {
+                    endpoint_status.consecutive_failures = 0;
+                    endpoint_status.failing_since = None;
+                } else {
+                    endpoint_status.consecutive_failures += 1;
+                    if endpoint_status.consecutive_failures >= failing_threshold {
+                        endpoint_status.failing_since =
+                            endpoint_status.failing_since.or(Some(attempt.attempted_at));
+                    }
+                }
+                endpoint_status.last_attempt_at = attempt.attempted_at;
+                endpoint_status.clone()
+            }
+            None => {
+                let endpoint_status = WebhookEndpointStatus {
+                    account_id: account_id.clone(),
+                    url: attempt.url.clone(),
+                    consecutive_failures: if success { 0 } else { 1 },
+                    failing_since: (!success && failing_threshold <= 1)
+                        .then_some(attempt.attempted_at),
+                    last_attempt_at: attempt.attempted_at,
+                };
+                tables
+                    .webhook_endpoint_statuses
+                    .push(endpoint_status.clone());
+                endpoint_status
+            }
+        };
+
+        Ok(endpoint_status)
+    }
+
+    async fn fail(&self, delivery_id: &ID) -> anyhow::Result<()> {
+        let mut tables = self.store.lock().await;
+        if let Some(delivery) = tables
+            .webhook_deliveries
+            .iter_mut()
+            .find(|d| &d.id == delivery_id)
+        {
+            delivery.status = WebhookDeliveryStatus::Failed;
+        }
+        Ok(())
+    }
+
+    async fn redeliver(
+        &self,
+        delivery_id: &ID,
+        now: DateTime<Utc>,
+        retry_until: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let mut tables = self.store.lock().await;
+        let Some(delivery) = tables
+            .webhook_deliveries
+            .iter_mut()
+            .find(|d| &d.id == delivery_id)
+        else {
+            return Ok(false);
+        };
+        delivery.status = WebhookDeliveryStatus::Pending;
+        delivery.next_attempt_at = now;
+        delivery.retry_until = retry_until;
+        Ok(true)
+    }
+
+    async fn find(&self, delivery_id: &ID) -> anyhow::Result<Option<WebhookDelivery>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .webhook_deliveries
+            .iter()
+            .find(|d| &d.id == delivery_id)
+            .cloned())
+    }
+
+    async fn find_by_account(
+        &self,
+        account_id: &ID,
+        status: Option<WebhookDeliveryStatus>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<WebhookDelivery>> {
+        let tables = self.store.lock().await;
+        let mut deliveries = tables
+            .webhook_deliveries
+            .iter()
+            .filter(|d| &d.account_id == account_id && status.is_none_or(|s| d.status == s))
+            .cloned()
+            .collect::<Vec<_>>();
+        deliveries.sort_by_key(|d| std::cmp::Reverse(d.created));
+        deliveries.truncate(usize::try_from(limit)?);
+        Ok(deliveries)
+    }
+
+    async fn find_attempts(
+        &self,
+        delivery_ids: &[ID],
+    ) -> anyhow::Result<Vec<WebhookDeliveryAttempt>> {
+        let tables = self.store.lock().await;
+        let mut attempts = tables
+            .webhook_delivery_attempts
+            .iter()
+            .filter(|a| delivery_ids.contains(&a.delivery_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        attempts.sort_by_key(|a| a.attempted_at);
+        Ok(attempts)
+    }
+
+    async fn find_endpoint_status(
+        &self,
+        account_id: &ID,
+        url: &str,
+    ) -> anyhow::Result<Option<WebhookEndpointStatus>> {
+        let tables = self.store.lock().await;
+        Ok(tables
+            .webhook_endpoint_statuses
+            .iter()
+            .find(|s| &s.account_id == account_id && s.url == url)
+            .cloned())
+    }
+
+    async fn delete_finished_before(&self, before: DateTime<Utc>) -> anyhow::Result<u64> {
+        let mut tables = self.store.lock().await;
+        let delivery_ids = tables
+            .webhook_deliveries
+            .iter()
+            .filter(|d| d.status != WebhookDeliveryStatus::Pending && d.created < before)
+            .map(|d| d.id.clone())
+            .collect::<Vec<_>>();
+        tables.delete_webhook_deliveries(&delivery_ids);
+        Ok(u64::try_from(delivery_ids.len())?)
+    }
+}
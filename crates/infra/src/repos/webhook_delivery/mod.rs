@@ -0,0 +1,242 @@
+mod inmemory;
+mod postgres;
+
+use chrono::{DateTime, Utc};
+pub use inmemory::InMemoryWebhookDeliveryRepo;
+use nittei_domain::{
+    ID,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+    WebhookEndpointStatus,
+};
+pub use postgres::PostgresWebhookDeliveryRepo;
+
+#[async_trait::async_trait]
+pub trait IWebhookDeliveryRepo: Send + Sync {
+    /// Record deliveries, the ones already recorded (same ID) are ignored
+    async fn insert(&self, deliveries: &[WebhookDelivery]) -> anyhow::Result<()>;
+    /// Claim (at most `limit`) pending deliveries due at `now`, and increment their number of attempts
+    /// They are leased until `lease_until`: if not attempted by then (e.g. crash), they are claimed again
+    /// Deliveries locked by another worker are skipped
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<WebhookDelivery>>;
+    /// Record an attempt of a delivery of the account, along with the resulting status of the delivery
+    /// A pending delivery is retried at `retry_at`
+    ///
+    /// The health of the endpoint is updated in the same transaction: it is marked as failing after
+    /// `failing_threshold` consecutive failed attempts, and healthy again after a successful one
+    async fn record_attempt(
+        &self,
+        account_id: &ID,
+        attempt: &WebhookDeliveryAttempt,
+        status: WebhookDeliveryStatus,
+        retry_at: Option<DateTime<Utc>>,
+        failing_threshold: i32,
+    ) -> anyhow::Result<WebhookEndpointStatus>;
+    /// Mark the delivery as failed without attempting it (e.g. the account has no webhook anymore)
+    async fn fail(&self, delivery_id: &ID) -> anyhow::Result<()>;
+    /// Make the delivery pending again, so that it is attempted at `now` and retried until `retry_until`
+    /// Returns false if the delivery doesn't exist
+    async fn redeliver(
+        &self,
+        delivery_id: &ID,
+        now: DateTime<Utc>,
+        retry_until: DateTime<Utc>,
+    ) -> anyhow::Result<bool>;
+    async fn find(&self, delivery_id: &ID) -> anyhow::Result<Option<WebhookDelivery>>;
+    /// Find the deliveries of an account, optionally filtered by status, newest first
+    async fn find_by_account(
+        &self,
+        account_id: &ID,
+        status: Option<WebhookDeliveryStatus>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<WebhookDelivery>>;
+    /// Find the attempts of the deliveries, oldest first
+    async fn find_attempts(
+        &self,
+        delivery_ids: &[ID],
+    ) -> anyhow::Result<Vec<WebhookDeliveryAttempt>>;
+    async fn find_endpoint_status(
+        &self,
+        account_id: &ID,
+        url: &str,
+    ) -> anyhow::Result<Option<WebhookEndpointStatus>>;
+    /// Remove the finished (succeeded or failed) deliveries created before `before`, with their attempts
+    /// Returns the number of deliveries removed
+    async fn delete_finished_before(&self, before: DateTime<Utc>) -> anyhow::Result<u64>;
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeDelta, Utc};
+    use nittei_domain::{
+        Account,
+        ID,
+        WebhookDelivery,
+        WebhookDeliveryAttempt,
+        WebhookDeliveryStatus,
+    };
+
+    use crate::{NitteiContext, backend_tests};
+
+    async fn setup(ctx: NitteiContext) -> (NitteiContext, Account) {
+        let account = Account::default();
+        ctx.repos.accounts.insert(&account).await.unwrap();
+        (ctx, account)
+    }
+
+    fn generate_delivery(account: &Account) -> WebhookDelivery {
+        WebhookDelivery::new(
+            account.id.clone(),
+            None,
+            serde_json::json!({ "reminders": [] }),
+            TimeDelta::hours(1),
+            // Far in the past, so that claiming it doesn't claim the deliveries of the other tests
+            DateTime::from_timestamp_millis(1000 * 60 * 60 * 24).unwrap(),
+        )
+    }
+
+    fn generate_attempt(
+        delivery: &WebhookDelivery,
+        status_code: Option<u16>,
+    ) -> WebhookDeliveryAttempt {
+        WebhookDeliveryAttempt {
+            id: ID::default(),
+            delivery_id: delivery.id.clone(),
+            url: "https://example.com".into(),
+            attempted_at: delivery.created,
+            status_code,
+            latency_millis: 100,
+            response_excerpt: Some("error".into()),
+        }
+    }
+
+    backend_tests!(claim_attempt_and_redeliver);
+
+    async fn claim_attempt_and_redeliver(ctx: NitteiContext) {
+        let (ctx, account) = setup(ctx).await;
+        let delivery = generate_delivery(&account);
+        let repo = &ctx.repos.webhook_deliveries;
+        repo.insert(std::slice::from_ref(&delivery)).await.unwrap();
+        // Inserting it again is ignored
+        repo.insert(std::slice::from_ref(&delivery)).await.unwrap();
+
+        let res = repo.find(&delivery.id).await.unwrap();
+        assert_eq!(res, Some(delivery.clone()));
+
+        // Claim the delivery, other workers don't see it anymore during the lease
+        let lease_until = Utc::now() + TimeDelta::minutes(1);
+        let claimed = repo
+            .claim_due(delivery.next_attempt_at, lease_until, 1000)
+            .await
+            .unwrap();
+        let claimed = claimed
+            .into_iter()
+            .find(|d| d.id == delivery.id)
+            .expect("Delivery to be claimed");
+        assert_eq!(claimed.attempts, 1);
+        let claimed_again = repo
+            .claim_due(delivery.next_attempt_at, lease_until, 1000)
+            .await
+            .unwrap();
+        assert!(claimed_again.iter().all(|d| d.id != delivery.id));
+
+        // Failed attempts, the endpoint is marked as failing after 2 of them
+        let endpoint_status = repo
+            .record_attempt(
+                &account.id,
+                &generate_attempt(&delivery, Some(500)),
+                WebhookDeliveryStatus::Pending,
+                Some(delivery.next_attempt_at),
+                2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(endpoint_status.consecutive_failures, 1);
+        assert_eq!(endpoint_status.failing_since, None);
+        let endpoint_status = repo
+            .record_attempt(
+                &account.id,
+                &generate_attempt(&delivery, None),
+                WebhookDeliveryStatus::Failed,
+                None,
+                2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(endpoint_status.consecutive_failures, 2);
+        assert_eq!(endpoint_status.failing_since, Some(delivery.created));
+
+        let res = repo
+            .find_by_account(&account.id, Some(WebhookDeliveryStatus::Failed), 10)
+            .await
+            .unwrap();
+        assert_eq!(res.len(), 1);
+        assert!(
+            repo.find_by_account(&account.id, Some(WebhookDeliveryStatus::Pending), 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let attempts = repo
+            .find_attempts(std::slice::from_ref(&delivery.id))
+            .await
+            .unwrap();
+        assert_eq!(attempts.len(), 2);
+        assert_eq!(attempts[0].status_code, Some(500));
+        assert_eq!(attempts[1].status_code, None);
+
+        // Redeliver
+        let now = delivery.next_attempt_at;
+        assert!(
+            repo.redeliver(&delivery.id, now, now + TimeDelta::hours(1))
+                .await
+                .unwrap()
+        );
+        let res = repo.find(&delivery.id).await.unwrap().unwrap();
+        assert_eq!(res.status, WebhookDeliveryStatus::Pending);
+        assert!(
+            !repo
+                .redeliver(&ID::default(), now, now + TimeDelta::hours(1))
+                .await
+                .unwrap()
+        );
+
+        // A successful attempt makes the endpoint healthy again
+        let endpoint_status = repo
+            .record_attempt(
+                &account.id,
+                &generate_attempt(&delivery, Some(200)),
+                WebhookDeliveryStatus::Succeeded,
+                None,
+                2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(endpoint_status.consecutive_failures, 0);
+        assert_eq!(endpoint_status.failing_since, None);
+        assert_eq!(
+            repo.find_endpoint_status(&account.id, "https://example.com")
+                .await
+                .unwrap(),
+            Some(endpoint_status)
+        );
+
+        // Finished deliveries are removed after the retention
+        repo.delete_finished_before(delivery.created + TimeDelta::seconds(1))
+            .await
+            .unwrap();
+        assert!(repo.find(&delivery.id).await.unwrap().is_none());
+        assert!(
+            repo.find_attempts(std::slice::from_ref(&delivery.id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,515 @@
+use std::convert::{TryFrom, TryInto};
+
+use chrono::{DateTime, Utc};
+use nittei_domain::{
+    ID,
+    WebhookDelivery,
+    WebhookDeliveryAttempt,
+    WebhookDeliveryStatus,
+    WebhookEndpointStatus,
+};
+use sqlx::{FromRow, PgPool, types::Uuid};
+use tracing::{error, instrument};
+
+use super::IWebhookDeliveryRepo;
+
+#[derive(Debug)]
+pub struct PostgresWebhookDeliveryRepo {
+    pool: PgPool,
+}
+
+impl PostgresWebhookDeliveryRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct WebhookDeliveryRaw {
+    delivery_uid: Uuid,
+    account_uid: Uuid,
+    event_type: Option<String>,
+    payload: serde_json::Value,
+    status: String,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    retry_until: DateTime<Utc>,
+    created: DateTime<Utc>,
+}
+
+impl TryFrom<WebhookDeliveryRaw> for WebhookDelivery {
+    type Error = anyhow::Error;
+
+    fn try_from(e: WebhookDeliveryRaw) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: e.delivery_uid.into(),
+            account_id: e.account_uid.into(),
+            event_type: e
+                .event_type
+                .map(|event_type| serde_json::from_value(serde_json::Value::String(event_type)))
+                .transpose()?,
+            payload: e.payload,
+            status: e.status.try_into()?,
+            attempts: e.attempts,
+            next_attempt_at: e.next_attempt_at,
+            retry_until: e.retry_until,
+            created: e.created,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct WebhookDeliveryAttemptRaw {
+    attempt_uid: Uuid,
+    delivery_uid: Uuid,
+    url: String,
+    attempted_at: DateTime<Utc>,
+    status_code: Option<i32>,
+    latency_millis: i64,
+    response_excerpt: Option<String>,
+}
+
+impl TryFrom<WebhookDeliveryAttemptRaw> for WebhookDeliveryAttempt {
+    type Error = anyhow::Error;
+
+    fn try_from(e: WebhookDeliveryAttemptRaw) -> anyhow::Result<Self> {
+        Ok(Self {
+            id: e.attempt_uid.into(),
+            delivery_id: e.delivery_uid.into(),
+            url: e.url,
+            attempted_at: e.attempted_at,
+            status_code: e.status_code.map(u16::try_from).transpose()?,
+            latency_millis: e.latency_millis,
+            response_excerpt: e.response_excerpt,
+        })
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct WebhookEndpointStatusRaw {
+    account_uid: Uuid,
+    url: String,
+    consecutive_failures: i32,
+    failing_since: Option<DateTime<Utc>>,
+    last_attempt_at: DateTime<Utc>,
+}
+
+impl From<WebhookEndpointStatusRaw> for WebhookEndpointStatus {
+    fn from(e: WebhookEndpointStatusRaw) -> Self {
+        Self {
+            account_id: e.account_uid.into(),
+            url: e.url,
+            consecutive_failures: e.consecutive_failures,
+            failing_since: e.failing_since,
+            last_attempt_at: e.last_attempt_at,
+        }
+    }
+}
+
+/// Text stored for the type of change of the event of a delivery (its serialized form)
+fn event_type_to_string(delivery: &WebhookDelivery) -> anyhow::Result<Option<String>> {
+    delivery
+        .event_type
+        .map(|event_type| match serde_json::to_value(event_type)? {
+            serde_json::Value::String(event_type) => Ok(event_type),
+            _ => Err(anyhow::anyhow!("Invalid webhook event type")),
+        })
+        .transpose()
+}
+
+#[async_trait::async_trait]
+impl IWebhookDeliveryRepo for PostgresWebhookDeliveryRepo {
+    #[instrument(name = "webhook_delivery::insert", skip(deliveries))]
+    async fn insert(&self, deliveries: &[WebhookDelivery]) -> anyhow::Result<()> {
+        if deliveries.is_empty() {
+            return Ok(());
+        }
+
+        let delivery_uids = deliveries
+            .iter()
+            .map(|d| *d.id.as_ref())
+            .collect::<Vec<Uuid>>();
+        let account_uids = deliveries
+            .iter()
+            .map(|d| *d.account_id.as_ref())
+            .collect::<Vec<Uuid>>();
+        let event_types = deliveries
+            .iter()
+            .map(event_type_to_string)
+            .collect::<anyhow::Result<Vec<Option<String>>>>()?;
+        let payloads = deliveries
+            .iter()
+            .map(|d| d.payload.clone())
+            .collect::<Vec<serde_json::Value>>();
+        let statuses = deliveries
+            .iter()
+            .map(|d| d.status.into())
+            .collect::<Vec<String>>();
+        let attempts = deliveries.iter().map(|d| d.attempts).collect::<Vec<i32>>();
+        let next_attempt_ats = deliveries
+            .iter()
+            .map(|d| d.next_attempt_at)
+            .collect::<Vec<DateTime<Utc>>>();
+        let retry_untils = deliveries
+            .iter()
+            .map(|d| d.retry_until)
+            .collect::<Vec<DateTime<Utc>>>();
+        let createds = deliveries
+            .iter()
+            .map(|d| d.created)
+            .collect::<Vec<DateTime<Utc>>>();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_deliveries(delivery_uid, account_uid, event_type, payload, status, attempts, next_attempt_at, retry_until, created)
+            SELECT * FROM UNNEST($1::uuid[], $2::uuid[], $3::text[], $4::jsonb[], $5::text[], $6::int[], $7::timestamptz[], $8::timestamptz[], $9::timestamptz[])
+            ON CONFLICT (delivery_uid) DO NOTHING
+            "#,
+            &delivery_uids,
+            &account_uids,
+            &event_types as _,
+            &payloads,
+            &statuses,
+            &attempts,
+            &next_attempt_ats,
+            &retry_untils,
+            &createds,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to insert webhook_deliveries"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "webhook_delivery::claim_due")]
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<WebhookDelivery>> {
+        let deliveries: Vec<WebhookDeliveryRaw> = sqlx::query_as!(
+            WebhookDeliveryRaw,
+            r#"
+            UPDATE webhook_deliveries AS d SET
+                attempts = d.attempts + 1,
+                next_attempt_at = $2
+            WHERE d.delivery_uid IN (
+                SELECT delivery_uid FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= $1
+                ORDER BY next_attempt_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING d.delivery_uid, d.account_uid, d.event_type, d.payload, d.status, d.attempts, d.next_attempt_at, d.retry_until, d.created
+            "#,
+            now,
+            lease_until,
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to claim webhook_deliveries"
+            );
+        })?;
+
+        deliveries.into_iter().map(|d| d.try_into()).collect()
+    }
+
+    #[instrument(name = "webhook_delivery::record_attempt", skip(attempt), fields(delivery_uid = %attempt.delivery_id))]
+    async fn record_attempt(
+        &self,
+        account_id: &ID,
+        attempt: &WebhookDeliveryAttempt,
+        status: WebhookDeliveryStatus,
+        retry_at: Option<DateTime<Utc>>,
+        failing_threshold: i32,
+    ) -> anyhow::Result<WebhookEndpointStatus> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO webhook_delivery_attempts(attempt_uid, delivery_uid, url, attempted_at, status_code, latency_millis, response_excerpt)
+            VALUES($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            attempt.id.as_ref(),
+            attempt.delivery_id.as_ref(),
+            attempt.url,
+            attempt.attempted_at,
+            attempt.status_code.map(i32::from),
+            attempt.latency_millis,
+            attempt.response_excerpt,
+        )
+        .execute(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                delivery_id = %attempt.delivery_id,
+                error = ?err,
+                "Failed to insert webhook_delivery_attempt"
+            );
+        })?;
+
+        let status: String = status.into();
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries SET
+                status = $2,
+                next_attempt_at = COALESCE($3, next_attempt_at)
+            WHERE delivery_uid = $1
+            "#,
+            attempt.delivery_id.as_ref(),
+            status,
+            retry_at,
+        )
+        .execute(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                delivery_id = %attempt.delivery_id,
+                error = ?err,
+                "Failed to update the status of webhook_delivery"
+            );
+        })?;
+
+        let endpoint_status: WebhookEndpointStatusRaw = sqlx::query_as!(
+            WebhookEndpointStatusRaw,
+            r#"
+            INSERT INTO webhook_endpoint_statuses AS s(account_uid, url, consecutive_failures, failing_since, last_attempt_at)
+            VALUES(
+                $1,
+                $2,
+                CASE WHEN $3::bool THEN 0 ELSE 1 END,
+                CASE WHEN NOT $3::bool AND $4 <= 1 THEN $5::timestamptz END,
+                $5
+            )
+            ON CONFLICT (account_uid, url) DO UPDATE SET
+                consecutive_failures = CASE WHEN $3::bool THEN 0 ELSE s.consecutive_failures + 1 END,
+                failing_since = CASE
+                    WHEN $3::bool THEN NULL
+                    WHEN s.consecutive_failures + 1 >= $4 THEN COALESCE(s.failing_since, $5)
+                    ELSE s.failing_since
+                END,
+                last_attempt_at = $5
+            RETURNING s.account_uid, s.url, s.consecutive_failures, s.failing_since, s.last_attempt_at
+            "#,
+            account_id.as_ref(),
+            attempt.url,
+            attempt.is_success(),
+            failing_threshold,
+            attempt.attempted_at,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .inspect_err(|err| {
+            error!(
+                account_id = %account_id,
+                error = ?err,
+                "Failed to update webhook_endpoint_status"
+            );
+        })?;
+
+        tx.commit().await?;
+
+        Ok(endpoint_status.into())
+    }
+
+    #[instrument(name = "webhook_delivery::fail", fields(delivery_uid = %delivery_id))]
+    async fn fail(&self, delivery_id: &ID) -> anyhow::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries SET
+                status = 'failed'
+            WHERE delivery_uid = $1
+            "#,
+            delivery_id.as_ref(),
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                delivery_id = %delivery_id,
+                error = ?err,
+                "Failed to mark webhook_delivery as failed"
+            );
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "webhook_delivery::redeliver", fields(delivery_uid = %delivery_id))]
+    async fn redeliver(
+        &self,
+        delivery_id: &ID,
+        now: DateTime<Utc>,
+        retry_until: DateTime<Utc>,
+    ) -> anyhow::Result<bool> {
+        let res = sqlx::query!(
+            r#"
+            UPDATE webhook_deliveries SET
+                status = 'pending',
+                next_attempt_at = $2,
+                retry_until = $3
+            WHERE delivery_uid = $1
+            "#,
+            delivery_id.as_ref(),
+            now,
+            retry_until,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                delivery_id = %delivery_id,
+                error = ?err,
+                "Failed to redeliver webhook_delivery"
+            );
+        })?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    #[instrument(name = "webhook_delivery::find", fields(delivery_uid = %delivery_id))]
+    async fn find(&self, delivery_id: &ID) -> anyhow::Result<Option<WebhookDelivery>> {
+        let delivery: Option<WebhookDeliveryRaw> = sqlx::query_as!(
+            WebhookDeliveryRaw,
+            r#"
+            SELECT * FROM webhook_deliveries AS d
+            WHERE d.delivery_uid = $1
+            "#,
+            delivery_id.as_ref(),
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                delivery_id = %delivery_id,
+                error = ?err,
+                "Failed to find webhook_delivery"
+            );
+        })?;
+
+        delivery.map(|d| d.try_into()).transpose()
+    }
+
+    #[instrument(name = "webhook_delivery::find_by_account", fields(account_uid = %account_id))]
+    async fn find_by_account(
+        &self,
+        account_id: &ID,
+        status: Option<WebhookDeliveryStatus>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<WebhookDelivery>> {
+        let status: Option<String> = status.map(|s| s.into());
+        let deliveries: Vec<WebhookDeliveryRaw> = sqlx::query_as!(
+            WebhookDeliveryRaw,
+            r#"
+            SELECT * FROM webhook_deliveries AS d
+            WHERE d.account_uid = $1 AND ($2::text IS NULL OR d.status = $2)
+            ORDER BY d.created DESC
+            LIMIT $3
+            "#,
+            account_id.as_ref(),
+            status,
+            limit,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                account_id = %account_id,
+                error = ?err,
+                "Failed to find webhook_deliveries of account"
+            );
+        })?;
+
+        deliveries.into_iter().map(|d| d.try_into()).collect()
+    }
+
+    #[instrument(name = "webhook_delivery::find_attempts", skip(delivery_ids))]
+    async fn find_attempts(
+        &self,
+        delivery_ids: &[ID],
+    ) -> anyhow::Result<Vec<WebhookDeliveryAttempt>> {
+        let delivery_uids = delivery_ids
+            .iter()
+            .map(|id| *id.as_ref())
+            .collect::<Vec<Uuid>>();
+        let attempts: Vec<WebhookDeliveryAttemptRaw> = sqlx::query_as!(
+            WebhookDeliveryAttemptRaw,
+            r#"
+            SELECT * FROM webhook_delivery_attempts AS a
+            WHERE a.delivery_uid = ANY($1)
+            ORDER BY a.attempted_at
+            "#,
+            &delivery_uids,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to find webhook_delivery_attempts"
+            );
+        })?;
+
+        attempts.into_iter().map(|a| a.try_into()).collect()
+    }
+
+    #[instrument(name = "webhook_delivery::find_endpoint_status", fields(account_uid = %account_id))]
+    async fn find_endpoint_status(
+        &self,
+        account_id: &ID,
+        url: &str,
+    ) -> anyhow::Result<Option<WebhookEndpointStatus>> {
+        let endpoint_status: Option<WebhookEndpointStatusRaw> = sqlx::query_as!(
+            WebhookEndpointStatusRaw,
+            r#"
+            SELECT * FROM webhook_endpoint_statuses AS s
+            WHERE s.account_uid = $1 AND s.url = $2
+            "#,
+            account_id.as_ref(),
+            url,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                account_id = %account_id,
+                error = ?err,
+                "Failed to find webhook_endpoint_status"
+            );
+        })?;
+
+        Ok(endpoint_status.map(|s| s.into()))
+    }
+
+    #[instrument(name = "webhook_delivery::delete_finished_before")]
+    async fn delete_finished_before(&self, before: DateTime<Utc>) -> anyhow::Result<u64> {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM webhook_deliveries AS d
+            WHERE d.status <> 'pending' AND d.created < $1
+            "#,
+            before,
+        )
+        .execute(&self.pool)
+        .await
+        .inspect_err(|err| {
+            error!(
+                error = ?err,
+                "Failed to delete finished webhook_deliveries"
+            );
+        })?;
+
+        Ok(res.rows_affected())
+    }
+}
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fmt};
 
 use chrono::{TimeZone, Utc};
-use nittei_domain::{CalendarEvent, providers::google::*};
+use nittei_domain::{CalendarEvent, Tz, providers::google::*};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tracing::error;
@@ -14,10 +14,11 @@ pub struct GoogleCalendarEventDateTime {
 }
 
 impl GoogleCalendarEventDateTime {
-    pub fn new(date_time_millis: i64) -> Self {
+    /// The timezone (UTC if not provided) is the one in which the event is shown and its recurrence expanded
+    pub fn new(date_time_millis: i64, timezone: Option<Tz>) -> Self {
         Self {
             date_time: GoogleDateTime::from_timestamp_millis(date_time_millis),
-            time_zone: String::from("UTC"),
+            time_zone: timezone.map_or("UTC", |tz| tz.name()).to_string(),
         }
     }
 }
@@ -75,9 +76,12 @@ impl From<CalendarEvent> for GoogleCalendarEventAttributes {
         Self {
             description: description.to_string(),
             summary: summary.to_string(),
-            start: GoogleCalendarEventDateTime::new(e.start_time.timestamp_millis()),
+            start: GoogleCalendarEventDateTime::new(e.start_time.timestamp_millis(), e.timezone),
             // Recurrence sync not supported yet, so e.end_ts will not be correct if used
-            end: GoogleCalendarEventDateTime::new(e.start_time.timestamp_millis() + e.duration),
+            end: GoogleCalendarEventDateTime::new(
+                e.start_time.timestamp_millis() + e.duration,
+                e.timezone,
+            ),
             // Recurrence sync not supported yet
             recurrence: Vec::new(),
             // Whether it blocks calendar time or not
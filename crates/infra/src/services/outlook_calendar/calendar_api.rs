@@ -63,14 +63,9 @@ impl From<CalendarEvent> for OutlookCalendarEventAttributes {
             })
             .unwrap_or(empty.clone());
         OutlookCalendarEventAttributes {
-            start: OutlookCalendarEventTime {
-                time_zone: "UTC".to_string(),
-                date_time: format!("{}", e.start_time.format("%+")),
-            },
-            end: OutlookCalendarEventTime {
-                time_zone: "UTC".to_string(),
-                date_time: format!("{}", e.end_time.format("%+")),
-            },
+            // In the local time of the event timezone, if it has one
+            start: OutlookCalendarEventTime::new(e.start_time, e.timezone),
+            end: OutlookCalendarEventTime::new(e.end_time, e.timezone),
             is_online_meeting: false,
             body: OutlookCalendarEventBody {
                 content_type: OutlookCalendarEventBodyContentType::HTML,